@@ -30,13 +30,13 @@ pub fn discover_protected_resource_metadata(
         format!(
             "{}://{}/.well-known/oauth-protected-resource",
             parsed.scheme(),
-            parsed.host_str().unwrap_or("localhost")
+            authority(&parsed)
         )
     } else {
         format!(
             "{}://{}/.well-known/oauth-protected-resource{}",
             parsed.scheme(),
-            parsed.host_str().unwrap_or("localhost"),
+            authority(&parsed),
             path.trim_end_matches('/')
         )
     };
@@ -48,7 +48,7 @@ pub fn discover_protected_resource_metadata(
             let root_url = format!(
                 "{}://{}/.well-known/oauth-protected-resource",
                 parsed.scheme(),
-                parsed.host_str().unwrap_or("localhost")
+                authority(&parsed)
             );
             return fetch_protected_resource_metadata(&root_url);
         }
@@ -80,7 +80,7 @@ pub fn discover_authorization_server_metadata(
         .map_err(|e| OAuthClientError::InvalidRequest(format!("Invalid authorization server URL: {}", e)))?;
 
     let path = parsed.path();
-    let host = parsed.host_str().unwrap_or("localhost");
+    let host = authority(&parsed);
     let scheme = parsed.scheme();
 
     // Build URLs to try
@@ -128,6 +128,15 @@ fn fetch_authorization_server_metadata(url: &str) -> Result<OAuthMetadata, OAuth
     Ok(metadata)
 }
 
+/// Host and, when not the scheme's default, port of `url`.
+fn authority(url: &url::Url) -> String {
+    let host = url.host_str().unwrap_or("localhost");
+    match url.port() {
+        Some(port) => format!("{}:{}", host, port),
+        None => host.to_string(),
+    }
+}
+
 /// Get the OAuth protected resource metadata URL from a server URL.
 pub fn get_protected_resource_metadata_url(server_url: &str) -> Result<String, OAuthClientError> {
     let parsed = url::Url::parse(server_url)
@@ -143,7 +152,7 @@ pub fn get_protected_resource_metadata_url(server_url: &str) -> Result<String, O
     Ok(format!(
         "{}://{}/.well-known/oauth-protected-resource{}",
         parsed.scheme(),
-        parsed.host_str().unwrap_or("localhost"),
+        authority(&parsed),
         rs_path
     ))
 }
@@ -0,0 +1,329 @@
+//! OAuth client provider that persists credentials to a JSON file.
+//!
+//! Tokens, registered client information and the pending PKCE code verifier
+//! are stored in a single JSON file, so a CLI can reuse them across runs.
+//! On Unix the file is created with mode `0600`.
+//!
+//! ## Concurrent processes
+//!
+//! Every read loads the file and every write reloads it, applies the change
+//! and atomically replaces the file (write to a temporary file, then
+//! rename). Readers therefore never see a partially written file, and a
+//! token refreshed by one process is picked up by the next read in another.
+//!
+//! Two processes that write at the same moment still race: the last rename
+//! wins. Give each server or profile its own file (see
+//! [`FileOAuthClientProvider::default_path`]), and if several invocations
+//! for the same server may refresh tokens at once, serialize them with an
+//! advisory lock on a sibling lock file (e.g. `flock` on `<file>.lock`).
+//!
+//! ## Example
+//!
+//! ```ignore
+//! use mcp_client::auth::{auth, AuthOptions, FileOAuthClientProvider};
+//!
+//! let path = FileOAuthClientProvider::default_path("https://gitlab.example.com/mcp")?;
+//! let provider = FileOAuthClientProvider::new(path, Some(redirect_url), client_metadata);
+//! let result = auth(&provider, AuthOptions::new("https://gitlab.example.com/mcp")).await?;
+//! ```
+
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Mutex, RwLock};
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+use mcp_core::auth::{OAuthClientInformation, OAuthClientMetadata, OAuthTokens};
+
+use super::provider::{InvalidationScope, OAuthClientError, OAuthClientProvider};
+
+/// Contents of the credentials file.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct StoredCredentials {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    client_information: Option<OAuthClientInformation>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    tokens: Option<OAuthTokens>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    code_verifier: Option<String>,
+}
+
+/// An OAuth client provider storing credentials in a JSON file.
+pub struct FileOAuthClientProvider {
+    path: PathBuf,
+    redirect_url: Option<String>,
+    client_metadata: OAuthClientMetadata,
+    authorization_url: RwLock<Option<String>>,
+    /// Serializes read-modify-write cycles within this process.
+    write_lock: Mutex<()>,
+}
+
+impl FileOAuthClientProvider {
+    /// Create a provider storing credentials in the file at `path`.
+    ///
+    /// The file and its parent directories are created on the first write.
+    pub fn new(
+        path: impl Into<PathBuf>,
+        redirect_url: Option<String>,
+        client_metadata: OAuthClientMetadata,
+    ) -> Self {
+        Self {
+            path: path.into(),
+            redirect_url,
+            client_metadata,
+            authorization_url: RwLock::new(None),
+            write_lock: Mutex::new(()),
+        }
+    }
+
+    /// Default credentials file for `server_url`:
+    /// `~/.mcp/oauth/<host>[_<port>].json`.
+    pub fn default_path(server_url: &str) -> Result<PathBuf, OAuthClientError> {
+        let url = url::Url::parse(server_url)
+            .map_err(|e| OAuthClientError::InvalidRequest(format!("Invalid server URL: {}", e)))?;
+        let host = url
+            .host_str()
+            .ok_or_else(|| OAuthClientError::InvalidRequest("Server URL has no host".to_string()))?;
+        let file_name = match url.port() {
+            Some(port) => format!("{}_{}.json", host, port),
+            None => format!("{}.json", host),
+        };
+
+        let home = std::env::var_os("HOME")
+            .or_else(|| std::env::var_os("USERPROFILE"))
+            .ok_or_else(|| OAuthClientError::Storage("Cannot determine home directory".to_string()))?;
+        Ok(PathBuf::from(home).join(".mcp").join("oauth").join(file_name))
+    }
+
+    /// Path of the credentials file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Get the last authorization URL that was set.
+    pub fn get_authorization_url(&self) -> Option<String> {
+        self.authorization_url.read().unwrap().clone()
+    }
+
+    /// Load the credentials file; a missing file is empty.
+    fn load(&self) -> Result<StoredCredentials, OAuthClientError> {
+        match std::fs::read(&self.path) {
+            Ok(bytes) => serde_json::from_slice(&bytes).map_err(|e| {
+                OAuthClientError::Storage(format!("Invalid credentials file {}: {}", self.path.display(), e))
+            }),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(StoredCredentials::default()),
+            Err(e) => Err(storage_error(&self.path, e)),
+        }
+    }
+
+    /// Reload the file, apply `change` and atomically write the result back.
+    fn update(&self, change: impl FnOnce(&mut StoredCredentials)) -> Result<(), OAuthClientError> {
+        let _guard = self.write_lock.lock().unwrap();
+        let mut credentials = self.load()?;
+        change(&mut credentials);
+
+        let json = serde_json::to_vec_pretty(&credentials)
+            .map_err(|e| OAuthClientError::Storage(format!("Failed to serialize credentials: {}", e)))?;
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| storage_error(parent, e))?;
+        }
+
+        // Unique per process and write, so concurrent writers never share a temporary file
+        static WRITES: AtomicUsize = AtomicUsize::new(0);
+        let mut tmp_name = self.path.file_name().unwrap_or_default().to_os_string();
+        tmp_name.push(format!(".tmp.{}.{}", std::process::id(), WRITES.fetch_add(1, Ordering::Relaxed)));
+        let tmp_path = self.path.with_file_name(tmp_name);
+        write_private(&tmp_path, &json).map_err(|e| storage_error(&tmp_path, e))?;
+        std::fs::rename(&tmp_path, &self.path).map_err(|e| {
+            let _ = std::fs::remove_file(&tmp_path);
+            storage_error(&self.path, e)
+        })
+    }
+}
+
+/// Write `contents` to a new file readable only by the owner.
+fn write_private(path: &Path, contents: &[u8]) -> std::io::Result<()> {
+    use std::io::Write;
+
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::{OpenOptionsExt, PermissionsExt};
+
+        options.mode(0o600);
+        let mut file = options.open(path)?;
+        // The mode only applies to newly created files
+        file.set_permissions(std::fs::Permissions::from_mode(0o600))?;
+        file.write_all(contents)?;
+        file.sync_all()
+    }
+    #[cfg(not(unix))]
+    {
+        let mut file = options.open(path)?;
+        file.write_all(contents)?;
+        file.sync_all()
+    }
+}
+
+fn storage_error(path: &Path, error: std::io::Error) -> OAuthClientError {
+    OAuthClientError::Storage(format!("{}: {}", path.display(), error))
+}
+
+#[async_trait]
+impl OAuthClientProvider for FileOAuthClientProvider {
+    fn redirect_url(&self) -> Option<&str> {
+        self.redirect_url.as_deref()
+    }
+
+    fn client_metadata(&self) -> &OAuthClientMetadata {
+        &self.client_metadata
+    }
+
+    async fn client_information(&self) -> Option<OAuthClientInformation> {
+        self.load().ok()?.client_information
+    }
+
+    async fn save_client_information(&self, info: OAuthClientInformation) -> Result<(), OAuthClientError> {
+        self.update(|credentials| credentials.client_information = Some(info))
+    }
+
+    async fn tokens(&self) -> Option<OAuthTokens> {
+        self.load().ok()?.tokens
+    }
+
+    async fn save_tokens(&self, tokens: OAuthTokens) -> Result<(), OAuthClientError> {
+        self.update(|credentials| credentials.tokens = Some(tokens))
+    }
+
+    async fn redirect_to_authorization(&self, url: &str) -> Result<(), OAuthClientError> {
+        *self.authorization_url.write().unwrap() = Some(url.to_string());
+        Ok(())
+    }
+
+    async fn save_code_verifier(&self, verifier: String) -> Result<(), OAuthClientError> {
+        self.update(|credentials| credentials.code_verifier = Some(verifier))
+    }
+
+    async fn code_verifier(&self) -> Result<String, OAuthClientError> {
+        self.load()?
+            .code_verifier
+            .ok_or_else(|| OAuthClientError::Storage("No code verifier saved".to_string()))
+    }
+
+    async fn invalidate_credentials(&self, scope: InvalidationScope) -> Result<(), OAuthClientError> {
+        self.update(|credentials| match scope {
+            InvalidationScope::All => *credentials = StoredCredentials::default(),
+            InvalidationScope::Client => credentials.client_information = None,
+            InvalidationScope::Tokens => credentials.tokens = None,
+            InvalidationScope::Verifier => credentials.code_verifier = None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A fresh credentials path under the system temp directory.
+    fn temp_path() -> PathBuf {
+        static COUNTER: AtomicUsize = AtomicUsize::new(0);
+        let dir = std::env::temp_dir().join(format!(
+            "mcp-file-provider-{}-{}",
+            std::process::id(),
+            COUNTER.fetch_add(1, Ordering::Relaxed)
+        ));
+        let _ = std::fs::remove_dir_all(&dir);
+        dir.join("oauth").join("server.json")
+    }
+
+    fn provider(path: &Path) -> FileOAuthClientProvider {
+        FileOAuthClientProvider::new(path, None, OAuthClientMetadata::default())
+    }
+
+    fn tokens(access_token: &str) -> OAuthTokens {
+        OAuthTokens {
+            access_token: access_token.to_string(),
+            token_type: "Bearer".to_string(),
+            expires_in: Some(3600),
+            refresh_token: Some("refresh".to_string()),
+            scope: None,
+            id_token: None,
+        }
+    }
+
+    fn client_info() -> OAuthClientInformation {
+        OAuthClientInformation {
+            client_id: "cli".to_string(),
+            client_secret: None,
+            client_id_issued_at: None,
+            client_secret_expires_at: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn credentials_survive_a_new_provider() {
+        let path = temp_path();
+        let first = provider(&path);
+        first.save_client_information(client_info()).await.unwrap();
+        first.save_tokens(tokens("access-1")).await.unwrap();
+        first.save_code_verifier("verifier".to_string()).await.unwrap();
+
+        // A later run reads the same file
+        let second = provider(&path);
+        assert_eq!(second.client_information().await.unwrap().client_id, "cli");
+        assert_eq!(second.tokens().await.unwrap().access_token, "access-1");
+        assert_eq!(second.code_verifier().await.unwrap(), "verifier");
+
+        // Writes from one provider are visible to the other
+        second.save_tokens(tokens("access-2")).await.unwrap();
+        assert_eq!(first.tokens().await.unwrap().access_token, "access-2");
+        assert_eq!(first.client_information().await.unwrap().client_id, "cli");
+
+        let _ = std::fs::remove_dir_all(path.parent().unwrap().parent().unwrap());
+    }
+
+    #[tokio::test]
+    async fn invalidation_clears_disk() {
+        let path = temp_path();
+        let provider = provider(&path);
+        provider.save_client_information(client_info()).await.unwrap();
+        provider.save_tokens(tokens("revoked")).await.unwrap();
+
+        provider.invalidate_credentials(InvalidationScope::Tokens).await.unwrap();
+        let reloaded = self::provider(&path);
+        assert!(reloaded.tokens().await.is_none());
+        assert!(reloaded.client_information().await.is_some());
+        assert!(!std::fs::read_to_string(&path).unwrap().contains("revoked"));
+
+        provider.invalidate_credentials(InvalidationScope::All).await.unwrap();
+        assert!(reloaded.client_information().await.is_none());
+        assert!(reloaded.code_verifier().await.is_err());
+
+        let _ = std::fs::remove_dir_all(path.parent().unwrap().parent().unwrap());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn file_is_private() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let path = temp_path();
+        provider(&path).save_tokens(tokens("secret")).await.unwrap();
+
+        let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+
+        let _ = std::fs::remove_dir_all(path.parent().unwrap().parent().unwrap());
+    }
+
+    #[test]
+    fn default_path_is_per_server() {
+        let path = FileOAuthClientProvider::default_path("http://localhost:8080/mcp").unwrap();
+        assert!(path.ends_with(".mcp/oauth/localhost_8080.json"), "{}", path.display());
+
+        let path = FileOAuthClientProvider::default_path("https://gitlab.com/api/mcp").unwrap();
+        assert!(path.ends_with(".mcp/oauth/gitlab.com.json"), "{}", path.display());
+    }
+}
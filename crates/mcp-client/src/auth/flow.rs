@@ -8,6 +8,8 @@ use mcp_core::auth::{
 };
 
 use super::discovery::{discover_authorization_server_metadata, discover_protected_resource_metadata};
+use super::grant_type::GrantType;
+use super::pkce::{CodeChallengeMethod, MAX_VERIFIER_LENGTH, MIN_VERIFIER_LENGTH, PkceChallenge};
 use super::provider::{AuthResult, InvalidationScope, OAuthClientError, OAuthClientProvider};
 
 /// Main entry point for the OAuth authorization flow.
@@ -47,6 +49,12 @@ pub struct AuthOptions<'a> {
     pub scope: Option<&'a str>,
     /// Resource metadata URL (from WWW-Authenticate header).
     pub resource_metadata_url: Option<&'a str>,
+    /// Length of the PKCE code verifier, 43–128 (default: 128).
+    pub pkce_verifier_length: usize,
+    /// PKCE code challenge method (default: S256).
+    pub code_challenge_method: CodeChallengeMethod,
+    /// Grant used to obtain tokens (default: authorization code).
+    pub grant_type: GrantType,
 }
 
 impl<'a> AuthOptions<'a> {
@@ -57,6 +65,9 @@ impl<'a> AuthOptions<'a> {
             authorization_code: None,
             scope: None,
             resource_metadata_url: None,
+            pkce_verifier_length: MAX_VERIFIER_LENGTH,
+            code_challenge_method: CodeChallengeMethod::S256,
+            grant_type: GrantType::AuthorizationCode,
         }
     }
 
@@ -77,6 +88,31 @@ impl<'a> AuthOptions<'a> {
         self.resource_metadata_url = Some(url);
         self
     }
+
+    /// Set the PKCE code verifier length, clamped to the 43–128 characters
+    /// RFC 7636 allows.
+    pub fn pkce_verifier_length(mut self, length: usize) -> Self {
+        self.pkce_verifier_length = length.clamp(MIN_VERIFIER_LENGTH, MAX_VERIFIER_LENGTH);
+        self
+    }
+
+    /// Set the PKCE code challenge method.
+    ///
+    /// `Plain` is only sent to authorization servers whose metadata lists it
+    /// in `code_challenge_methods_supported`; otherwise the flow fails.
+    pub fn code_challenge_method(mut self, method: CodeChallengeMethod) -> Self {
+        self.code_challenge_method = method;
+        self
+    }
+
+    /// Set the grant used to obtain tokens.
+    ///
+    /// With [`GrantType::ClientCredentials`] no redirect takes place: each
+    /// call requests a new token with the client's registered secret.
+    pub fn with_grant_type(mut self, grant_type: GrantType) -> Self {
+        self.grant_type = grant_type;
+        self
+    }
 }
 
 /// Internal auth implementation.
@@ -123,6 +159,14 @@ async fn auth_internal<P: OAuthClientProvider>(
         .validate_resource_url(options.server_url, resource_metadata.as_ref().map(|m| m.resource.as_str()))
         .await?;
 
+    // Client credentials: no user involved, and renewal repeats the grant
+    if options.grant_type == GrantType::ClientCredentials {
+        let scope = options.scope.or(provider.client_metadata().scope.as_deref());
+        let tokens = request_client_credentials_token(&metadata, &client_info, scope, resource.as_deref()).await?;
+        provider.save_tokens(tokens).await?;
+        return Ok(AuthResult::Authorized);
+    }
+
     // Non-interactive flows
     if provider.redirect_url().is_none() {
         let tokens = fetch_token(provider, &metadata, &client_info, resource.as_deref(), options.authorization_code).await?;
@@ -165,14 +209,27 @@ async fn auth_internal<P: OAuthClientProvider>(
         .and_then(|m| m.scopes_supported.as_ref())
         .map(|s| s.join(" "));
     let scope = options.scope.map(|s| s.to_string()).or(scope_from_metadata);
-    let (auth_url, code_verifier) = start_authorization(
+    if options.code_challenge_method == CodeChallengeMethod::Plain
+        && !metadata
+            .code_challenge_methods_supported
+            .as_ref()
+            .is_some_and(|methods| methods.iter().any(|method| method == "plain"))
+    {
+        return Err(OAuthClientError::InvalidRequest(
+            "Authorization server does not support the plain PKCE method".to_string(),
+        ));
+    }
+    let pkce = PkceChallenge::generate(options.pkce_verifier_length, options.code_challenge_method);
+    let auth_url = authorization_url(
         &metadata,
         &client_info,
         provider.redirect_url().unwrap(),
         scope.as_deref(),
         state.as_deref(),
         resource.as_deref(),
+        &pkce,
     )?;
+    let code_verifier = pkce.code_verifier;
 
     provider.save_code_verifier(code_verifier).await?;
     provider.redirect_to_authorization(&auth_url).await?;
@@ -203,6 +260,9 @@ pub fn register_client(
 }
 
 /// Start the authorization flow (RFC 6749 + PKCE).
+///
+/// Uses a 128-character verifier with the S256 method and returns the
+/// authorization URL and the code verifier.
 pub fn start_authorization(
     metadata: &OAuthMetadata,
     client_info: &OAuthClientInformation,
@@ -211,18 +271,29 @@ pub fn start_authorization(
     state: Option<&str>,
     resource: Option<&str>,
 ) -> Result<(String, String), OAuthClientError> {
-    // Generate PKCE challenge
-    let (code_verifier, code_challenge) = generate_pkce_challenge();
+    let pkce = PkceChallenge::generate(MAX_VERIFIER_LENGTH, CodeChallengeMethod::S256);
+    let url = authorization_url(metadata, client_info, redirect_url, scope, state, resource, &pkce)?;
+    Ok((url, pkce.code_verifier))
+}
 
-    // Build authorization URL
+/// Build the authorization URL for `pkce`.
+pub fn authorization_url(
+    metadata: &OAuthMetadata,
+    client_info: &OAuthClientInformation,
+    redirect_url: &str,
+    scope: Option<&str>,
+    state: Option<&str>,
+    resource: Option<&str>,
+    pkce: &PkceChallenge,
+) -> Result<String, OAuthClientError> {
     let mut url = url::Url::parse(&metadata.authorization_endpoint)
         .map_err(|e| OAuthClientError::InvalidRequest(format!("Invalid authorization endpoint: {}", e)))?;
 
     url.query_pairs_mut()
         .append_pair("response_type", "code")
         .append_pair("client_id", &client_info.client_id)
-        .append_pair("code_challenge", &code_challenge)
-        .append_pair("code_challenge_method", "S256")
+        .append_pair("code_challenge", &pkce.code_challenge)
+        .append_pair("code_challenge_method", pkce.code_challenge_method.as_str())
         .append_pair("redirect_uri", redirect_url);
 
     if let Some(state) = state {
@@ -237,7 +308,7 @@ pub fn start_authorization(
         url.query_pairs_mut().append_pair("resource", resource);
     }
 
-    Ok((url.to_string(), code_verifier))
+    Ok(url.to_string())
 }
 
 /// Exchange authorization code for tokens.
@@ -302,6 +373,47 @@ async fn refresh_authorization(
     Ok(tokens)
 }
 
+/// Request tokens with the client credentials grant (RFC 6749, section 4.4).
+///
+/// The client authenticates with its registered `client_secret`, so one is
+/// required.
+pub async fn request_client_credentials_token(
+    metadata: &OAuthMetadata,
+    client_info: &OAuthClientInformation,
+    scope: Option<&str>,
+    resource: Option<&str>,
+) -> Result<OAuthTokens, OAuthClientError> {
+    if metadata
+        .grant_types_supported
+        .as_ref()
+        .is_some_and(|grants| !grants.iter().any(|grant| grant == GrantType::ClientCredentials.as_str()))
+    {
+        return Err(OAuthClientError::InvalidRequest(
+            "Authorization server does not support the client_credentials grant".to_string(),
+        ));
+    }
+
+    let secret = client_info.client_secret.as_ref().ok_or_else(|| {
+        OAuthClientError::InvalidRequest("client_credentials grant requires a client secret".to_string())
+    })?;
+
+    let mut params = vec![
+        ("grant_type".to_string(), GrantType::ClientCredentials.as_str().to_string()),
+        ("client_id".to_string(), client_info.client_id.clone()),
+        ("client_secret".to_string(), secret.clone()),
+    ];
+
+    if let Some(scope) = scope {
+        params.push(("scope".to_string(), scope.to_string()));
+    }
+
+    if let Some(resource) = resource {
+        params.push(("resource".to_string(), resource.to_string()));
+    }
+
+    execute_token_request(&metadata.token_endpoint, params).await
+}
+
 /// Fetch tokens using provider's custom grant or authorization code.
 async fn fetch_token<P: OAuthClientProvider>(
     provider: &P,
@@ -361,40 +473,120 @@ async fn execute_token_request(
     Ok(tokens)
 }
 
-/// Generate PKCE code verifier and challenge.
-fn generate_pkce_challenge() -> (String, String) {
-    use sha2::{Sha256, Digest};
-    use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD};
+#[cfg(test)]
+mod tests {
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+    use std::sync::{Arc, Mutex};
+
+    use mcp_core::auth::OAuthClientMetadata;
+
+    use super::*;
+    use crate::auth::InMemoryOAuthClientProvider;
+
+    /// Serve authorization server metadata and a token endpoint, recording
+    /// token request bodies. Returns the server URL.
+    fn serve_authorization_server(token_requests: Arc<Mutex<Vec<String>>>) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base = format!("http://{}", listener.local_addr().unwrap());
+        let issuer = base.clone();
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let mut stream = stream.unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let mut request_line = String::new();
+                reader.read_line(&mut request_line).unwrap();
+                let mut content_length = 0;
+                loop {
+                    let mut header = String::new();
+                    reader.read_line(&mut header).unwrap();
+                    if header.trim().is_empty() {
+                        break;
+                    }
+                    if let Some((name, value)) = header.split_once(':')
+                        && name.eq_ignore_ascii_case("content-length")
+                    {
+                        content_length = value.trim().parse().unwrap();
+                    }
+                }
+                let mut body = vec![0; content_length];
+                reader.read_exact(&mut body).unwrap();
+
+                let path = request_line.split_whitespace().nth(1).unwrap_or_default();
+                let (status, response) = if path.starts_with("/.well-known/oauth-authorization-server") {
+                    let metadata = serde_json::json!({
+                        "issuer": issuer,
+                        "authorization_endpoint": format!("{issuer}/authorize"),
+                        "token_endpoint": format!("{issuer}/token"),
+                        "response_types_supported": ["code"],
+                        "grant_types_supported": ["authorization_code", "client_credentials"],
+                    });
+                    ("200 OK", metadata.to_string())
+                } else if path == "/token" {
+                    let mut requests = token_requests.lock().unwrap();
+                    requests.push(String::from_utf8(body).unwrap());
+                    let token = format!("token-{}", requests.len());
+                    ("200 OK", serde_json::json!({ "access_token": token, "token_type": "Bearer" }).to_string())
+                } else {
+                    ("404 Not Found", String::new())
+                };
+                let _ = write!(
+                    stream,
+                    "HTTP/1.1 {status}\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{response}",
+                    response.len()
+                );
+            }
+        });
+        base
+    }
 
-    // Generate random verifier (43-128 characters)
-    let verifier: String = (0..64)
-        .map(|_| {
-            let idx = rand_byte() % 66;
-            PKCE_CHARSET[idx as usize] as char
+    fn provider(client_secret: Option<&str>) -> InMemoryOAuthClientProvider {
+        let metadata = OAuthClientMetadata {
+            scope: Some("mcp:tools".to_string()),
+            ..Default::default()
+        };
+        InMemoryOAuthClientProvider::new(None, metadata).with_client_info(OAuthClientInformation {
+            client_id: "ci-job".to_string(),
+            client_secret: client_secret.map(str::to_string),
+            client_id_issued_at: None,
+            client_secret_expires_at: None,
         })
-        .collect();
-
-    // Create S256 challenge
-    let mut hasher = Sha256::new();
-    hasher.update(verifier.as_bytes());
-    let hash = hasher.finalize();
-    let challenge = URL_SAFE_NO_PAD.encode(hash);
+    }
 
-    (verifier, challenge)
-}
+    #[tokio::test]
+    async fn client_credentials_grant_fetches_and_renews_tokens() {
+        let token_requests = Arc::new(Mutex::new(Vec::new()));
+        let server_url = format!("{}/mcp", serve_authorization_server(token_requests.clone()));
+        let provider = provider(Some("s3cret"));
+        let options = || AuthOptions::new(&server_url).with_grant_type(GrantType::ClientCredentials);
+
+        assert_eq!(auth(&provider, options()).await.unwrap(), AuthResult::Authorized);
+        assert_eq!(provider.tokens().await.unwrap().access_token, "token-1");
+        assert!(provider.get_authorization_url().is_none());
+
+        // Renewing repeats the grant
+        assert_eq!(auth(&provider, options()).await.unwrap(), AuthResult::Authorized);
+        assert_eq!(provider.tokens().await.unwrap().access_token, "token-2");
+
+        let requests = token_requests.lock().unwrap();
+        assert_eq!(requests.len(), 2);
+        for body in requests.iter() {
+            assert!(body.contains("grant_type=client_credentials"), "{body}");
+            assert!(body.contains("client_id=ci-job"), "{body}");
+            assert!(body.contains("client_secret=s3cret"), "{body}");
+            assert!(body.contains("scope=mcp%3Atools"), "{body}");
+        }
+    }
 
-/// Get a pseudo-random byte.
-fn rand_byte() -> u8 {
-    use std::time::{SystemTime, UNIX_EPOCH};
+    #[tokio::test]
+    async fn client_credentials_grant_requires_secret() {
+        let token_requests = Arc::new(Mutex::new(Vec::new()));
+        let server_url = serve_authorization_server(token_requests.clone());
+        let options = AuthOptions::new(&server_url).with_grant_type(GrantType::ClientCredentials);
 
-    let duration = SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .unwrap_or_default();
+        let err = auth(&provider(None), options).await.unwrap_err();
 
-    // Simple PRNG based on time - for production, use a proper CSPRNG
-    let seed = duration.as_nanos() as u64;
-    ((seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407)) >> 56) as u8
+        assert!(matches!(err, OAuthClientError::InvalidRequest(_)), "{err}");
+        assert!(token_requests.lock().unwrap().is_empty());
+    }
 }
-
-/// Characters allowed in PKCE code verifier.
-const PKCE_CHARSET: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-._~";
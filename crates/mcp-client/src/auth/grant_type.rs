@@ -0,0 +1,24 @@
+//! OAuth grant used to obtain tokens.
+
+/// How [`auth`](super::auth) obtains tokens.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum GrantType {
+    /// Interactive authorization code flow with PKCE: the user is redirected
+    /// to the authorization server and the code is exchanged for tokens.
+    #[default]
+    AuthorizationCode,
+    /// Client credentials grant (RFC 6749, section 4.4) for services with
+    /// no user present. The client authenticates with its secret and
+    /// receives a token directly; renewing the token repeats the grant.
+    ClientCredentials,
+}
+
+impl GrantType {
+    /// Value of the `grant_type` parameter.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::AuthorizationCode => "authorization_code",
+            Self::ClientCredentials => "client_credentials",
+        }
+    }
+}
@@ -6,6 +6,8 @@
 //! - Protected resource metadata discovery (RFC 9728)
 //! - Dynamic client registration (RFC 7591)
 //! - PKCE support (RFC 7636)
+//! - Client credentials grant for headless services
+//! - File-backed credential storage (`FileOAuthClientProvider`)
 //! - Token refresh
 //!
 //! ## Example
@@ -33,14 +35,23 @@
 //! ```
 
 mod discovery;
+mod file_provider;
 mod flow;
+mod grant_type;
+mod pkce;
 mod provider;
 
 pub use discovery::{
     discover_authorization_server_metadata, discover_protected_resource_metadata,
     get_protected_resource_metadata_url,
 };
-pub use flow::{auth, register_client, start_authorization, AuthOptions};
+pub use file_provider::FileOAuthClientProvider;
+pub use flow::{
+    auth, authorization_url, register_client, request_client_credentials_token, start_authorization,
+    AuthOptions,
+};
+pub use grant_type::GrantType;
+pub use pkce::{CodeChallengeMethod, PkceChallenge, MAX_VERIFIER_LENGTH, MIN_VERIFIER_LENGTH};
 pub use provider::{
     AuthResult, InMemoryOAuthClientProvider, InvalidationScope, OAuthClientError,
     OAuthClientProvider,
@@ -1,27 +1,37 @@
 use std::collections::HashMap;
-use std::sync::Arc;
-use std::sync::mpsc::{Sender, channel};
+use std::sync::mpsc::{Receiver, Sender, channel};
+use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant};
 
+use futures::future::{self, BoxFuture};
+use futures::{FutureExt, select};
+use futures_timer::Delay;
+use serde::de::DeserializeOwned;
 use serde_json::{Value, json};
 
-use mcp_core::stdio::Transport;
+use mcp_core::stdio::{Transport, TransportStats};
 use mcp_core::{
-    protocol::Protocol,
+    protocol::{Protocol, ProtocolError, RequiredCapability, required_capability},
     stdio::JsonRpcMessage,
     types::{
-        CreateMessageRequestParams, ElicitRequestFormParams, ElicitRequestUrlParams,
-        ElicitationMode, ErrorCode, ErrorObject, ListRootsResult, MessageId, NotificationMessage,
-        RequestMessage, ResultMessage,
+        CreateMessageRequestParams, CreateTaskResult, ElicitRequestFormParams,
+        ElicitRequestUrlParams, ElicitationMode, ErrorCode, ErrorObject, GetPromptResult,
+        ListRootsResult, MessageDeltaNotificationParams, MessageId, NotificationMessage,
+        ReadResourceResult, RequestMessage, ResultMessage, SUPPORTED_PROTOCOL_VERSIONS,
+        TaskCreationParams,
     },
 };
 
 use crate::client::{
-    BoxedFormElicitationHandler, BoxedSamplingHandler, BoxedUrlElicitationHandler,
-    ClientCapabilities, ClientError, ClientOptions, Implementation, InitializeResult,
+    BoxedFormElicitationHandler, BoxedSamplingHandler, BoxedStreamingSamplingHandler,
+    BoxedUrlElicitationHandler,
+    ClientCapabilities, ClientError, ClientOptions, ConnectedClient, Implementation,
+    InitializeResult,
     JsonSchemaValidator, ListChangedHandlers, ListChangedKind, NoopJsonSchemaValidator,
-    PromptListResult, RequestStream, ResourceListResult, ResponseMessage, ServerCapabilities,
-    TaskGetResult, TaskInfo, TaskListResult, TaskResult, ToolCache, ToolCallResult, ToolListResult,
+    PendingResponse, PendingResponses, PromptListResult, RequestStream, ResourceListResult, ResponseMessage, SamplingError,
+    SamplingStream, SamplingStreamSender, ServerCapabilities,
+    TaskGetResult, TaskInfo, TaskListResult, TaskResult, ToolCache, ToolCallResult,
+    ToolDefinition, ToolListResult,
 };
 
 /// Minimal client that wires a `Transport` and `Protocol` together.
@@ -34,25 +44,27 @@ where
     transport: T,
     options: ClientOptions,
     capabilities: ClientCapabilities,
-    #[allow(dead_code)]
     json_schema_validator: Arc<dyn JsonSchemaValidator>,
     pending_list_changed: Option<ListChangedHandlers>,
     list_changed_handlers: ListChangedHandlers,
     list_changed_due: HashMap<ListChangedKind, Instant>,
     list_changed_pending: HashMap<MessageId, ListChangedKind>,
-    tool_cache: ToolCache,
+    tool_cache: Arc<Mutex<ToolCache>>,
     roots: Vec<mcp_core::types::Root>,
     server_capabilities: Option<ServerCapabilities>,
     server_info: Option<Implementation>,
     instructions: Option<String>,
+    initialize_result: Option<InitializeResult>,
     pending_initialize_id: Option<MessageId>,
     pending_requests: HashMap<MessageId, String>,
-    pending_tool_calls: HashMap<MessageId, String>,
     pending_streams: HashMap<MessageId, Sender<ResponseMessage>>,
+    responses: Arc<PendingResponses>,
     next_id: i64,
     connected: bool,
     // Sampling/Elicitation handlers
     sampling_handler: Option<BoxedSamplingHandler>,
+    streaming_sampling_handler: Option<BoxedStreamingSamplingHandler>,
+    sampling_streams: Vec<Sender<MessageDeltaNotificationParams>>,
     form_elicitation_handler: Option<BoxedFormElicitationHandler>,
     url_elicitation_handler: Option<BoxedUrlElicitationHandler>,
 }
@@ -83,18 +95,21 @@ where
             list_changed_handlers: ListChangedHandlers::default(),
             list_changed_due: HashMap::new(),
             list_changed_pending: HashMap::new(),
-            tool_cache: ToolCache::default(),
+            tool_cache: Arc::default(),
             roots,
             server_capabilities: None,
             server_info: None,
             instructions: None,
+            initialize_result: None,
             pending_initialize_id: None,
             pending_requests: HashMap::new(),
-            pending_tool_calls: HashMap::new(),
             pending_streams: HashMap::new(),
+            responses: Arc::new(PendingResponses::default()),
             next_id: 1,
             connected: false,
             sampling_handler: None,
+            streaming_sampling_handler: None,
+            sampling_streams: Vec::new(),
             form_elicitation_handler: None,
             url_elicitation_handler: None,
         }
@@ -154,6 +169,10 @@ where
             return Ok(());
         }
 
+        if let Some(handler) = self.streaming_sampling_handler.clone() {
+            return self.handle_streaming_sampling_request(request, handler);
+        }
+
         // Check if handler is registered
         let handler = match &self.sampling_handler {
             Some(h) => h.clone(),
@@ -177,14 +196,20 @@ where
                 ClientError::Serialization(e)
             })?;
 
-        // Execute handler synchronously
-        let result = handler.handle(params);
+        // Execute handler synchronously; only requests offering tools may
+        // be answered with several content blocks
+        let result = if params.tools.is_some() {
+            handler.handle_with_tools(params).and_then(|result| {
+                serde_json::to_value(result).map_err(|e| SamplingError(e.to_string()))
+            })
+        } else {
+            handler.handle(params).and_then(|result| {
+                serde_json::to_value(result).map_err(|e| SamplingError(e.to_string()))
+            })
+        };
 
         let response = match result {
-            Ok(result) => {
-                let payload = serde_json::to_value(result).map_err(ClientError::Serialization)?;
-                ResultMessage::success(request.id.clone(), payload)
-            }
+            Ok(payload) => ResultMessage::success(request.id.clone(), payload),
             Err(err) => {
                 let error = ErrorObject::new(
                     ErrorCode::InternalError as i32,
@@ -201,6 +226,64 @@ where
         Ok(())
     }
 
+    fn handle_streaming_sampling_request(
+        &mut self,
+        request: RequestMessage,
+        handler: BoxedStreamingSamplingHandler,
+    ) -> Result<(), ClientError<T::Error>> {
+        let params: CreateMessageRequestParams =
+            serde_json::from_value(request.params.clone()).map_err(ClientError::Serialization)?;
+
+        let (sender, deltas) = channel();
+        let mut chunks = Vec::new();
+        let mut forward = true;
+        let result = std::thread::scope(|scope| {
+            let worker = scope
+                .spawn(move || handler.handle_streaming(params, SamplingStreamSender::new(sender)));
+            // Ends once the handler and every clone of its sender are done
+            for delta in deltas {
+                let delta = MessageDeltaNotificationParams::new(request.id.clone(), delta);
+                // A transport that cannot carry the deltas still gets the
+                // assembled result
+                forward = forward && self.forward_sampling_delta(&delta);
+                self.sampling_streams
+                    .retain(|stream| stream.send(delta.clone()).is_ok());
+                chunks.push(delta.delta);
+            }
+            worker
+                .join()
+                .unwrap_or_else(|_| Err(SamplingError("sampling handler panicked".to_string())))
+        });
+
+        let response = match result.and_then(|end| end.into_result(chunks)) {
+            Ok(result) => {
+                let payload = serde_json::to_value(result).map_err(ClientError::Serialization)?;
+                ResultMessage::success(request.id.clone(), payload)
+            }
+            Err(err) => {
+                let error = ErrorObject::new(ErrorCode::InternalError as i32, err.0, None);
+                ResultMessage::failure(request.id.clone(), error)
+            }
+        };
+
+        self.transport
+            .send(&JsonRpcMessage::Result(response))
+            .map_err(ClientError::Transport)?;
+        Ok(())
+    }
+
+    /// Send one piece of a streamed sampling response to the server,
+    /// returning false if the transport refused it.
+    fn forward_sampling_delta(&mut self, delta: &MessageDeltaNotificationParams) -> bool {
+        let Ok(params) = serde_json::to_value(delta) else {
+            return false;
+        };
+        let notification = NotificationMessage::new("notifications/message/delta", Some(params));
+        self.transport
+            .send(&JsonRpcMessage::Notification(notification))
+            .is_ok()
+    }
+
     fn handle_elicitation_request(
         &mut self,
         request: RequestMessage,
@@ -264,7 +347,7 @@ where
                     Err(err) => {
                         let error = ErrorObject::new(
                             ErrorCode::InternalError as i32,
-                            err.0,
+                            err.to_string(),
                             None,
                         );
                         ResultMessage::failure(request.id.clone(), error)
@@ -305,7 +388,7 @@ where
                     Err(err) => {
                         let error = ErrorObject::new(
                             ErrorCode::InternalError as i32,
-                            err.0,
+                            err.to_string(),
                             None,
                         );
                         ResultMessage::failure(request.id.clone(), error)
@@ -344,6 +427,22 @@ where
         self.sampling_handler = Some(Arc::new(handler));
     }
 
+    /// Set a handler answering sampling/createMessage requests piece by piece.
+    /// It takes precedence over a handler set with `set_sampling_handler`.
+    pub fn set_streaming_sampling_handler<H>(&mut self, handler: H)
+    where
+        H: crate::client::StreamingSamplingHandler,
+    {
+        self.streaming_sampling_handler = Some(Arc::new(handler));
+    }
+
+    /// Receive the pieces of every streamed sampling response from now on.
+    pub fn subscribe_sampling_stream(&mut self) -> SamplingStream {
+        let (sender, receiver) = channel();
+        self.sampling_streams.push(sender);
+        SamplingStream::new(receiver)
+    }
+
     /// Set the handler for form-based elicitation/create requests from the server.
     /// This should be called before connecting if form elicitation capability is declared.
     pub fn set_form_elicitation_handler<H>(&mut self, handler: H)
@@ -362,13 +461,79 @@ where
         self.url_elicitation_handler = Some(Arc::new(handler));
     }
 
-    /// Connect to the transport and send an initialize request.
-    pub fn connect(&mut self) -> Result<(), ClientError<T::Error>> {
+    /// Connect over `transport` and complete the initialize handshake.
+    ///
+    /// Sends initialize with the client capabilities, waits up to the
+    /// request timeout of `options` for the result, checks the protocol
+    /// version the server chose and sends `notifications/initialized`. The
+    /// transport is closed again when the handshake fails.
+    ///
+    /// Replaces the transport's message and close handlers: responses to
+    /// [`request`](Self::request) futures are resolved on the transport's
+    /// callback thread, everything else is queued for the returned client.
+    pub fn connect(
+        transport: T,
+        options: ClientOptions,
+    ) -> Result<ConnectedClient<T>, ClientError<T::Error>> {
+        let timeout = options.request_timeout;
+        let mut client = Self::new(transport, options);
+
+        let (sender, incoming) = channel();
+        let responses = Arc::clone(&client.responses);
+        client.transport.on_message(Arc::new(move |message| {
+            let message = match message {
+                JsonRpcMessage::Result(result) => match responses.complete(result) {
+                    Some(result) => JsonRpcMessage::Result(result),
+                    None => return,
+                },
+                message => message,
+            };
+            let _ = sender.send(message);
+        }));
+        let responses = Arc::clone(&client.responses);
+        client
+            .transport
+            .on_close_with_reason(Arc::new(move |_reason| responses.close()));
+
+        match client.complete_handshake(&incoming, timeout) {
+            Ok(init) => Ok(ConnectedClient::new(client, incoming, init, timeout)),
+            Err(error) => {
+                let _ = client.close();
+                Err(error)
+            }
+        }
+    }
+
+    fn complete_handshake(
+        &mut self,
+        incoming: &Receiver<JsonRpcMessage>,
+        timeout: Duration,
+    ) -> Result<InitializeResult, ClientError<T::Error>> {
+        self.start()?;
+        let deadline = Instant::now() + timeout;
+        loop {
+            if let Some(init) = &self.initialize_result {
+                return Ok(init.clone());
+            }
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            let message = incoming
+                .recv_timeout(remaining)
+                .map_err(|_| ClientError::Protocol(ProtocolError::Timeout))?;
+            self.handle_message(message)?;
+        }
+    }
+
+    /// Start the transport and send an initialize request.
+    ///
+    /// The caller feeds the response to [`handle_message`](Self::handle_message);
+    /// [`connect`](Self::connect) does both and waits for the handshake.
+    pub fn start(&mut self) -> Result<(), ClientError<T::Error>> {
         if self.connected {
             return Ok(());
         }
         self.transport.start()?;
         self.connected = true;
+        self.responses.reopen();
 
         let id = self.next_message_id();
         self.pending_initialize_id = Some(id.clone());
@@ -397,6 +562,9 @@ where
                         return self.handle_initialize_result(result);
                     }
                 }
+                let Some(result) = self.responses.complete(result) else {
+                    return Ok(());
+                };
                 if let Some(sender) = self.pending_streams.remove(&result.id) {
                     if let Some(error) = result.error {
                         let _ = sender.send(ResponseMessage::Error(error.message));
@@ -412,6 +580,8 @@ where
                     self.flush_debounced_list_changed();
                     return response;
                 }
+                // Answered already (a duplicate), timed out, or never sent
+                eprintln!("warning: ignoring response with unknown id {}", result.id);
                 self.flush_debounced_list_changed();
                 Ok(())
             }
@@ -443,6 +613,11 @@ where
         self.instructions.as_deref()
     }
 
+    /// The initialize result, once the handshake has completed.
+    pub fn initialize_result(&self) -> Option<&InitializeResult> {
+        self.initialize_result.as_ref()
+    }
+
     /// Send a plain request message through the transport.
     pub fn send_request(
         &mut self,
@@ -450,6 +625,7 @@ where
         params: Value,
     ) -> Result<MessageId, ClientError<T::Error>> {
         let method = method.into();
+        self.assert_initialized(&method)?;
         self.assert_capability_for_method(&method)?;
 
         let request = RequestMessage::new(self.next_message_id(), method, params);
@@ -460,6 +636,20 @@ where
         Ok(id)
     }
 
+    fn send_awaited(
+        &mut self,
+        method: String,
+        params: Value,
+    ) -> Result<PendingResponse, ClientError<T::Error>> {
+        self.assert_initialized(&method)?;
+        self.assert_capability_for_method(&method)?;
+
+        let request = RequestMessage::new(self.next_message_id(), method, params);
+        let response = self.responses.register(request.id.clone());
+        self.transport.send(&JsonRpcMessage::Request(request))?;
+        Ok(response)
+    }
+
     /// Send a notification through the transport.
     pub fn send_notification(
         &mut self,
@@ -474,51 +664,20 @@ where
         Ok(())
     }
 
-    /// Shutdown the transport.
+    /// Messages and bytes exchanged over the transport so far.
+    pub fn transport_stats(&self) -> TransportStats {
+        self.transport.stats()
+    }
+
+    /// Shutdown the transport, failing pending [`request`](Self::request)
+    /// futures with [`ClientError::ConnectionClosed`].
     pub fn close(&mut self) -> Result<(), ClientError<T::Error>> {
+        self.responses.close();
         self.transport.close()?;
         self.connected = false;
         Ok(())
     }
 
-    /// Send a tools/list request.
-    pub fn list_tools(&mut self) -> Result<MessageId, ClientError<T::Error>> {
-        self.send_request("tools/list", json!({}))
-    }
-
-    /// Send a prompts/list request.
-    pub fn list_prompts(&mut self) -> Result<MessageId, ClientError<T::Error>> {
-        self.send_request("prompts/list", json!({}))
-    }
-
-    /// Send a resources/list request.
-    pub fn list_resources(&mut self) -> Result<MessageId, ClientError<T::Error>> {
-        self.send_request("resources/list", json!({}))
-    }
-
-    /// Send a tools/call request.
-    pub fn call_tool(
-        &mut self,
-        name: impl Into<String>,
-        arguments: Value,
-    ) -> Result<MessageId, ClientError<T::Error>> {
-        let name = name.into();
-        if self.tool_cache.is_task_required(&name) {
-            return Err(ClientError::Capability(format!(
-                "tool \"{name}\" requires task-based execution"
-            )));
-        }
-        let id = self.send_request(
-            "tools/call",
-            json!({
-                "name": name,
-                "arguments": arguments
-            }),
-        )?;
-        self.pending_tool_calls.insert(id.clone(), name);
-        Ok(id)
-    }
-
     /// Call a tool using a streaming request interface.
     pub fn call_tool_stream(
         &mut self,
@@ -530,41 +689,6 @@ where
         self.request_stream("tools/call", params)
     }
 
-    /// Request task status by task id.
-    pub fn get_task(
-        &mut self,
-        task_id: impl Into<String>,
-    ) -> Result<MessageId, ClientError<T::Error>> {
-        self.send_request("tasks/get", json!({ "taskId": task_id.into() }))
-    }
-
-    /// Request task result by task id.
-    pub fn get_task_result(
-        &mut self,
-        task_id: impl Into<String>,
-    ) -> Result<MessageId, ClientError<T::Error>> {
-        self.send_request("tasks/result", json!({ "taskId": task_id.into() }))
-    }
-
-    /// List tasks with optional cursor.
-    pub fn list_tasks(
-        &mut self,
-        cursor: Option<String>,
-    ) -> Result<MessageId, ClientError<T::Error>> {
-        let params = cursor
-            .map(|cursor| json!({ "cursor": cursor }))
-            .unwrap_or_else(|| json!({}));
-        self.send_request("tasks/list", params)
-    }
-
-    /// Cancel a running task.
-    pub fn cancel_task(
-        &mut self,
-        task_id: impl Into<String>,
-    ) -> Result<MessageId, ClientError<T::Error>> {
-        self.send_request("tasks/cancel", json!({ "taskId": task_id.into() }))
-    }
-
     /// Send a request and receive responses through a stream interface.
     pub fn request_stream(
         &mut self,
@@ -572,6 +696,7 @@ where
         params: Value,
     ) -> Result<RequestStream, ClientError<T::Error>> {
         let method = method.into();
+        self.assert_initialized(&method)?;
         self.assert_capability_for_method(&method)?;
 
         let request = RequestMessage::new(self.next_message_id(), method, params);
@@ -583,9 +708,15 @@ where
         Ok(RequestStream::new(receiver))
     }
 
-    #[allow(dead_code)]
     pub(crate) fn is_tool_task_required(&self, tool_name: &str) -> bool {
-        self.tool_cache.is_task_required(tool_name)
+        self.tool_cache
+            .lock()
+            .expect("tool cache")
+            .is_task_required(tool_name)
+    }
+
+    pub(crate) fn update_tool_cache(&mut self, tools: &[ToolDefinition]) {
+        self.tool_cache.lock().expect("tool cache").update(tools);
     }
 
     fn next_message_id(&mut self) -> MessageId {
@@ -608,16 +739,21 @@ where
 
         let init: InitializeResult =
             serde_json::from_value(payload).map_err(ClientError::Serialization)?;
-        if init.protocol_version != self.options.protocol_version {
-            return Err(ClientError::Initialization(format!(
-                "unsupported protocol version: {}",
-                init.protocol_version
-            )));
+        // The server answers with the requested version or another one it
+        // supports; anything this SDK does not know ends the handshake
+        let requested = &self.options.protocol_version;
+        if init.protocol_version != *requested
+            && !SUPPORTED_PROTOCOL_VERSIONS.contains(&init.protocol_version.as_str())
+        {
+            return Err(ClientError::ProtocolVersionMismatch {
+                requested: requested.clone(),
+                received: init.protocol_version,
+            });
         }
 
-        self.server_capabilities = Some(init.capabilities);
-        self.server_info = Some(init.server_info);
-        self.instructions = init.instructions;
+        self.server_capabilities = Some(init.capabilities.clone());
+        self.server_info = Some(init.server_info.clone());
+        self.instructions = init.instructions.clone();
         self.pending_initialize_id = None;
 
         if let Some(list_changed) = self.pending_list_changed.take() {
@@ -625,6 +761,7 @@ where
         }
 
         self.send_notification("notifications/initialized", None)?;
+        self.initialize_result = Some(init);
         Ok(())
     }
 
@@ -641,7 +778,6 @@ where
         let payload = result.result.unwrap_or(Value::Null);
         match method.as_str() {
             "tools/list" => self.handle_tools_list(id, payload),
-            "tools/call" => self.handle_tool_call(id, payload),
             "prompts/list" => self.handle_prompts_list(id, payload),
             "resources/list" => self.handle_resources_list(id, payload),
             "tasks/list" => self.handle_tasks_list(id, payload),
@@ -658,7 +794,7 @@ where
     ) -> Result<(), ClientError<T::Error>> {
         let list: ToolListResult =
             serde_json::from_value(payload).map_err(ClientError::Serialization)?;
-        self.tool_cache.update(&list.tools);
+        self.update_tool_cache(&list.tools);
 
         self.handle_list_changed_items(
             id,
@@ -668,34 +804,6 @@ where
         Ok(())
     }
 
-    fn handle_tool_call(
-        &mut self,
-        id: MessageId,
-        payload: Value,
-    ) -> Result<(), ClientError<T::Error>> {
-        let result: ToolCallResult =
-            serde_json::from_value(payload).map_err(ClientError::Serialization)?;
-        if result.is_error.unwrap_or(false) {
-            return Ok(());
-        }
-        let Some(tool_name) = self.pending_tool_calls.remove(&id) else {
-            return Ok(());
-        };
-
-        if let Some(schema) = self.tool_cache.output_schema(&tool_name) {
-            if let Some(structured) = result.structured_content.as_ref() {
-                self.json_schema_validator
-                    .validate(schema, structured)
-                    .map_err(ClientError::Validation)?;
-            } else {
-                return Err(ClientError::Validation(
-                    "tool has output schema but returned no structured content".to_string(),
-                ));
-            }
-        }
-        Ok(())
-    }
-
     fn handle_prompts_list(
         &mut self,
         id: MessageId,
@@ -808,6 +916,10 @@ where
     }
 
     fn on_list_changed(&mut self, kind: ListChangedKind) {
+        // Stale whether or not anyone asked to be told about it
+        if kind == ListChangedKind::Tools {
+            self.tool_cache.lock().expect("tool cache").clear();
+        }
         if !self.is_list_changed_supported(kind) {
             return;
         }
@@ -896,11 +1008,12 @@ where
             return;
         }
 
-        let request_id = match kind {
-            ListChangedKind::Tools => self.list_tools(),
-            ListChangedKind::Prompts => self.list_prompts(),
-            ListChangedKind::Resources => self.list_resources(),
+        let method = match kind {
+            ListChangedKind::Tools => "tools/list",
+            ListChangedKind::Prompts => "prompts/list",
+            ListChangedKind::Resources => "resources/list",
         };
+        let request_id = self.send_request(method, json!({}));
 
         match request_id {
             Ok(id) => {
@@ -935,65 +1048,48 @@ where
         }
     }
 
+    /// Only `ping` may be sent before the initialize handshake completes.
+    fn assert_initialized(&self, method: &str) -> Result<(), ClientError<T::Error>> {
+        if self.initialize_result.is_none() && method != "ping" {
+            return Err(ClientError::Initialization(format!(
+                "cannot send {method} before the initialize handshake completes"
+            )));
+        }
+        Ok(())
+    }
+
     fn assert_capability_for_method(&self, method: &str) -> Result<(), ClientError<T::Error>> {
+        let Some(required) = required_capability(method) else {
+            return Ok(());
+        };
         let server = self.server_capabilities.as_ref().ok_or_else(|| {
             ClientError::Capability("server capabilities unavailable".to_string())
         })?;
-
-        match method {
-            "logging/setLevel" => {
-                if server.logging.is_none() {
-                    return Err(ClientError::Capability(format!(
-                        "server does not support logging (required for {method})",
-                    )));
-                }
-            }
-            "prompts/get" | "prompts/list" => {
-                if server.prompts.is_none() {
-                    return Err(ClientError::Capability(format!(
-                        "server does not support prompts (required for {method})",
-                    )));
-                }
-            }
-            "resources/list"
-            | "resources/templates/list"
-            | "resources/read"
-            | "resources/subscribe"
-            | "resources/unsubscribe" => {
-                let resources = server.resources.as_ref().ok_or_else(|| {
-                    ClientError::Capability(format!(
-                        "server does not support resources (required for {method})",
-                    ))
-                })?;
-                if method == "resources/subscribe" && resources.subscribe != Some(true) {
-                    return Err(ClientError::Capability(format!(
-                        "server does not support resource subscriptions (required for {method})",
-                    )));
-                }
-            }
-            "tools/call" | "tools/list" => {
-                if server.tools.is_none() {
-                    return Err(ClientError::Capability(format!(
-                        "server does not support tools (required for {method})",
-                    )));
-                }
-            }
-            "completion/complete" => {
-                if server.completions.is_none() {
-                    return Err(ClientError::Capability(format!(
-                        "server does not support completions (required for {method})",
-                    )));
-                }
-            }
-            "initialize" | "ping" => {}
-            "tasks/get" | "tasks/list" | "tasks/result" | "tasks/cancel" => {
-                if server.tasks.is_none() {
-                    return Err(ClientError::Capability(format!(
-                        "server does not support tasks (required for {method})",
-                    )));
-                }
-            }
-            _ => {}
+        let supported = match required {
+            RequiredCapability::Tools => server.tools.is_some(),
+            RequiredCapability::Prompts => server.prompts.is_some(),
+            RequiredCapability::Resources => server.resources.is_some(),
+            RequiredCapability::ResourceSubscriptions => server
+                .resources
+                .as_ref()
+                .is_some_and(|resources| resources.subscribe == Some(true)),
+            RequiredCapability::Logging => server.logging.is_some(),
+            RequiredCapability::Completions => server.completions.is_some(),
+            RequiredCapability::Tasks => server.tasks.is_some(),
+            // Client capabilities; the server never handles these
+            RequiredCapability::Sampling
+            | RequiredCapability::Elicitation
+            | RequiredCapability::Roots => true,
+        };
+        if !supported {
+            let feature = match required {
+                RequiredCapability::ResourceSubscriptions => "resource subscriptions",
+                other => other.name(),
+            };
+            return Err(ClientError::CapabilityNotSupported {
+                capability: feature.to_string(),
+                method: method.to_string(),
+            });
         }
 
         Ok(())
@@ -1060,3 +1156,253 @@ where
         Ok(())
     }
 }
+
+/// Requests that resolve to typed results. Each one checks the server's
+/// capabilities first and fails with [`ClientError::CapabilityNotSupported`]
+/// instead of sending a request the server cannot handle.
+impl<T> Client<T>
+where
+    T: Transport<Message = JsonRpcMessage>,
+    T::Error: Send + 'static,
+{
+    /// Send a request and resolve with its response, whether a result or
+    /// an error object.
+    ///
+    /// The response is matched by id as soon as it reaches the client: on
+    /// the transport's callback thread for clients from
+    /// [`connect`](Self::connect), otherwise in
+    /// [`handle_message`](Self::handle_message). Resolves to
+    /// [`ProtocolError::Timeout`] after the request timeout and to
+    /// [`ClientError::ConnectionClosed`] when the transport closes first.
+    pub fn request(
+        &mut self,
+        method: impl Into<String>,
+        params: Value,
+    ) -> BoxFuture<'static, Result<ResultMessage, ClientError<T::Error>>> {
+        let response = self.send_awaited(method.into(), params);
+        let timeout = self.options.request_timeout;
+        async move {
+            // Dropping the response on timeout forgets the request
+            let mut response = response?.fuse();
+            let mut delay = Delay::new(timeout).fuse();
+            select! {
+                response = response => response.map_err(|_| ClientError::ConnectionClosed),
+                _ = delay => Err(ClientError::Protocol(ProtocolError::Timeout)),
+            }
+        }
+        .boxed()
+    }
+
+    /// Check that the server is responsive, resolving to the round-trip
+    /// time of a `ping` request. Like other requests it fails with
+    /// [`ProtocolError::Timeout`] after the request timeout.
+    pub fn ping(&mut self) -> BoxFuture<'static, Result<Duration, ClientError<T::Error>>> {
+        let started = Instant::now();
+        let response = self.typed_request::<Value>("ping", json!({}));
+        async move {
+            response.await?;
+            Ok(started.elapsed())
+        }
+        .boxed()
+    }
+
+    /// List the server's tools. The first page replaces the cached output
+    /// schemas and task support that [`call_tool`](Self::call_tool) checks;
+    /// later pages add to them.
+    pub fn list_tools(
+        &mut self,
+        cursor: Option<String>,
+    ) -> BoxFuture<'static, Result<ToolListResult, ClientError<T::Error>>> {
+        let first_page = cursor.is_none();
+        let list = self.typed_request::<ToolListResult>("tools/list", cursor_params(cursor));
+        let cache = Arc::clone(&self.tool_cache);
+        async move {
+            let list = list.await?;
+            let mut cache = cache.lock().expect("tool cache");
+            if first_page {
+                cache.update(&list.tools);
+            } else {
+                cache.extend(&list.tools);
+            }
+            drop(cache);
+            Ok(list)
+        }
+        .boxed()
+    }
+
+    /// Call a tool. Unless the result is an error, its structured content
+    /// is validated against the output schema from the last
+    /// [`list_tools`](Self::list_tools).
+    pub fn call_tool(
+        &mut self,
+        name: impl Into<String>,
+        arguments: Value,
+    ) -> BoxFuture<'static, Result<ToolCallResult, ClientError<T::Error>>> {
+        let name = name.into();
+        let (task_required, output_schema) = {
+            let cache = self.tool_cache.lock().expect("tool cache");
+            (
+                cache.is_task_required(&name),
+                cache.output_schema(&name).cloned(),
+            )
+        };
+        if task_required {
+            return future::ready(Err(ClientError::Capability(format!(
+                "tool \"{name}\" requires task-based execution"
+            ))))
+            .boxed();
+        }
+        let params = json!({ "name": name, "arguments": arguments });
+        let result = self.typed_request::<ToolCallResult>("tools/call", params);
+        let validator = Arc::clone(&self.json_schema_validator);
+        async move {
+            let result = result.await?;
+            if let Some(schema) = output_schema
+                && result.is_error != Some(true)
+            {
+                let structured = result.structured_content.as_ref().ok_or_else(|| {
+                    ClientError::Validation(
+                        "tool has output schema but returned no structured content".to_string(),
+                    )
+                })?;
+                validator
+                    .validate(&schema, structured)
+                    .map_err(ClientError::Validation)?;
+            }
+            Ok(result)
+        }
+        .boxed()
+    }
+
+    /// List the server's resources.
+    pub fn list_resources(
+        &mut self,
+        cursor: Option<String>,
+    ) -> BoxFuture<'static, Result<ResourceListResult, ClientError<T::Error>>> {
+        self.typed_request("resources/list", cursor_params(cursor))
+    }
+
+    /// Read the contents of a resource.
+    pub fn read_resource(
+        &mut self,
+        uri: impl Into<String>,
+    ) -> BoxFuture<'static, Result<ReadResourceResult, ClientError<T::Error>>> {
+        self.typed_request("resources/read", json!({ "uri": uri.into() }))
+    }
+
+    /// List the server's prompts.
+    pub fn list_prompts(
+        &mut self,
+        cursor: Option<String>,
+    ) -> BoxFuture<'static, Result<PromptListResult, ClientError<T::Error>>> {
+        self.typed_request("prompts/list", cursor_params(cursor))
+    }
+
+    /// Render a prompt with its arguments.
+    pub fn get_prompt(
+        &mut self,
+        name: impl Into<String>,
+        arguments: Option<HashMap<String, String>>,
+    ) -> BoxFuture<'static, Result<GetPromptResult, ClientError<T::Error>>> {
+        let mut params = json!({ "name": name.into() });
+        if let Some(arguments) = arguments {
+            params["arguments"] = json!(arguments);
+        }
+        self.typed_request("prompts/get", params)
+    }
+
+    /// Call a tool as a task. Resolves once the server has created the
+    /// task; follow it with [`get_task`](Self::get_task) and
+    /// [`task_result`](Self::task_result).
+    pub fn create_task_call(
+        &mut self,
+        name: impl Into<String>,
+        arguments: Value,
+        task: TaskCreationParams,
+    ) -> BoxFuture<'static, Result<CreateTaskResult, ClientError<T::Error>>> {
+        let task_calls = self
+            .server_capabilities
+            .as_ref()
+            .and_then(|server| server.tasks.as_ref())
+            .and_then(|tasks| tasks.pointer("/requests/tools/call"));
+        if self.server_capabilities.is_some() && task_calls.is_none() {
+            return future::ready(Err(ClientError::CapabilityNotSupported {
+                capability: "tasks.requests.tools.call".to_string(),
+                method: "tools/call".to_string(),
+            }))
+            .boxed();
+        }
+        let params = json!({ "name": name.into(), "arguments": arguments, "task": task });
+        self.typed_request("tools/call", params)
+    }
+
+    /// Fetch a task's current status.
+    pub fn get_task(
+        &mut self,
+        task_id: impl Into<String>,
+    ) -> BoxFuture<'static, Result<TaskGetResult, ClientError<T::Error>>> {
+        self.typed_request("tasks/get", json!({ "taskId": task_id.into() }))
+    }
+
+    /// Wait for a task to finish and fetch the result of the request it
+    /// ran.
+    pub fn task_result(
+        &mut self,
+        task_id: impl Into<String>,
+    ) -> BoxFuture<'static, Result<TaskResult, ClientError<T::Error>>> {
+        self.typed_request("tasks/result", json!({ "taskId": task_id.into() }))
+    }
+
+    /// List the server's tasks.
+    pub fn list_tasks(
+        &mut self,
+        cursor: Option<String>,
+    ) -> BoxFuture<'static, Result<TaskListResult, ClientError<T::Error>>> {
+        self.typed_request("tasks/list", cursor_params(cursor))
+    }
+
+    /// Cancel a running task, resolving to its status afterwards.
+    pub fn cancel_task(
+        &mut self,
+        task_id: impl Into<String>,
+    ) -> BoxFuture<'static, Result<TaskGetResult, ClientError<T::Error>>> {
+        self.typed_request("tasks/cancel", json!({ "taskId": task_id.into() }))
+    }
+
+    fn typed_request<R>(
+        &mut self,
+        method: &'static str,
+        params: Value,
+    ) -> BoxFuture<'static, Result<R, ClientError<T::Error>>>
+    where
+        R: DeserializeOwned + Send + 'static,
+    {
+        self.request(method, params)
+            .map(move |response| parse_result(method, response?))
+            .boxed()
+    }
+}
+
+fn cursor_params(cursor: Option<String>) -> Value {
+    cursor
+        .map(|cursor| json!({ "cursor": cursor }))
+        .unwrap_or_else(|| json!({}))
+}
+
+/// Turn a response into the result type of `method`. An error response
+/// becomes [`ProtocolError::Handler`]; a result of the wrong shape keeps
+/// its raw JSON for debugging.
+fn parse_result<R, E>(method: &str, response: ResultMessage) -> Result<R, ClientError<E>>
+where
+    R: DeserializeOwned,
+{
+    if let Some(error) = response.error {
+        return Err(ClientError::Protocol(ProtocolError::Handler(error.message)));
+    }
+    let raw = response.result.unwrap_or(Value::Null);
+    R::deserialize(&raw).map_err(|source| ClientError::Deserialization {
+        method: method.to_string(),
+        raw: raw.to_string(),
+        source,
+    })
+}
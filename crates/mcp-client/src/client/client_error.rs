@@ -17,9 +17,32 @@ pub enum ClientError<TransportError> {
     #[error("initialization failed: {0}")]
     Initialization(String),
 
+    /// The transport closed before the response to a request arrived.
+    #[error("connection closed before the response arrived")]
+    ConnectionClosed,
+
+    /// The server answered initialize with a protocol version this client
+    /// does not speak.
+    #[error("server chose protocol version {received}, but {requested} was requested")]
+    ProtocolVersionMismatch { requested: String, received: String },
+
     #[error("capability mismatch: {0}")]
     Capability(String),
 
+    /// The server did not advertise the capability a request needs, so the
+    /// request was not sent.
+    #[error("server does not support {capability} (required for {method})")]
+    CapabilityNotSupported { capability: String, method: String },
+
+    /// A result did not have the shape expected for its method.
+    #[error("unexpected {method} result {raw}: {source}")]
+    Deserialization {
+        method: String,
+        /// The result as received, serialized as JSON.
+        raw: String,
+        source: serde_json::Error,
+    },
+
     #[error("validation failed: {0}")]
     Validation(String),
 }
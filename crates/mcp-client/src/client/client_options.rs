@@ -1,10 +1,16 @@
 use std::sync::Arc;
+use std::time::Duration;
 
 use crate::client::{
     ClientCapabilities, Implementation, JsonSchemaValidator, ListChangedHandlers,
     NoopJsonSchemaValidator,
 };
 
+/// How long [`Client::connect`](crate::client::Client::connect) and
+/// [`ConnectedClient`](crate::client::ConnectedClient) requests wait for a
+/// response by default.
+pub const DEFAULT_REQUEST_TIMEOUT: Duration = Duration::from_secs(60);
+
 /// Options provided when constructing a client.
 #[derive(Clone)]
 pub struct ClientOptions {
@@ -14,6 +20,8 @@ pub struct ClientOptions {
     pub list_changed: Option<ListChangedHandlers>,
     pub json_schema_validator: Option<Arc<dyn JsonSchemaValidator>>,
     pub roots: Option<Vec<mcp_core::types::Root>>,
+    /// How long a blocking request waits for its response.
+    pub request_timeout: Duration,
 }
 
 impl ClientOptions {
@@ -25,6 +33,7 @@ impl ClientOptions {
             list_changed: None,
             json_schema_validator: Some(Arc::new(NoopJsonSchemaValidator::default())),
             roots: None,
+            request_timeout: DEFAULT_REQUEST_TIMEOUT,
         }
     }
 
@@ -57,4 +66,9 @@ impl ClientOptions {
         self.roots = Some(roots);
         self
     }
+
+    pub fn with_request_timeout(mut self, timeout: Duration) -> Self {
+        self.request_timeout = timeout;
+        self
+    }
 }
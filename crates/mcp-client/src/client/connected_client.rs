@@ -0,0 +1,172 @@
+use std::sync::mpsc::{Receiver, RecvTimeoutError};
+use std::time::{Duration, Instant};
+
+use serde_json::{Value, json};
+
+use mcp_core::protocol::ProtocolError;
+use mcp_core::stdio::{JsonRpcMessage, Transport};
+use mcp_core::types::CallToolResult;
+
+use crate::client::{
+    Client, ClientError, Implementation, InitializeResult, ResponseMessage, ServerCapabilities,
+    ToolListResult,
+};
+
+/// A client whose initialize handshake has completed, returned by
+/// [`Client::connect`].
+///
+/// Requests block until their response arrives or the request timeout
+/// elapses. Notifications and server requests that arrive in the meantime
+/// are handled by the wrapped [`Client`].
+pub struct ConnectedClient<T>
+where
+    T: Transport<Message = JsonRpcMessage>,
+{
+    client: Client<T>,
+    incoming: Receiver<JsonRpcMessage>,
+    init: InitializeResult,
+    request_timeout: Duration,
+}
+
+impl<T> ConnectedClient<T>
+where
+    T: Transport<Message = JsonRpcMessage>,
+{
+    pub(crate) fn new(
+        client: Client<T>,
+        incoming: Receiver<JsonRpcMessage>,
+        init: InitializeResult,
+        request_timeout: Duration,
+    ) -> Self {
+        Self {
+            client,
+            incoming,
+            init,
+            request_timeout,
+        }
+    }
+
+    /// Capabilities the server advertised during initialization.
+    pub fn server_capabilities(&self) -> &ServerCapabilities {
+        &self.init.capabilities
+    }
+
+    /// Name and version of the server implementation.
+    pub fn server_info(&self) -> &Implementation {
+        &self.init.server_info
+    }
+
+    /// Usage instructions the server sent, if any.
+    pub fn instructions(&self) -> Option<&str> {
+        self.init.instructions.as_deref()
+    }
+
+    /// The protocol version both sides agreed on.
+    pub fn protocol_version(&self) -> &str {
+        &self.init.protocol_version
+    }
+
+    /// The underlying client, for handlers and lower-level requests.
+    pub fn client(&self) -> &Client<T> {
+        &self.client
+    }
+
+    /// Mutable access to the underlying client.
+    pub fn client_mut(&mut self) -> &mut Client<T> {
+        &mut self.client
+    }
+
+    /// Send a request and wait for its result.
+    ///
+    /// An error response surfaces as [`ProtocolError::Handler`], and no
+    /// response within the request timeout as [`ProtocolError::Timeout`].
+    pub fn request(
+        &mut self,
+        method: impl Into<String>,
+        params: Value,
+    ) -> Result<Value, ClientError<T::Error>> {
+        let stream = self.client.request_stream(method, params)?;
+        let deadline = Instant::now() + self.request_timeout;
+        loop {
+            while let Some(response) = stream.try_recv() {
+                match response {
+                    ResponseMessage::Result(value) => return Ok(value),
+                    ResponseMessage::Error(message) => {
+                        return Err(ClientError::Protocol(ProtocolError::Handler(message)));
+                    }
+                    ResponseMessage::TaskCreated(_) | ResponseMessage::TaskStatus(_) => {}
+                }
+            }
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            match self.incoming.recv_timeout(remaining) {
+                Ok(message) => self.client.handle_message(message)?,
+                Err(RecvTimeoutError::Timeout) => {
+                    return Err(ClientError::Protocol(ProtocolError::Timeout));
+                }
+                Err(RecvTimeoutError::Disconnected) => {
+                    return Err(ClientError::Protocol(ProtocolError::Handler(
+                        "transport stopped delivering messages".to_string(),
+                    )));
+                }
+            }
+        }
+    }
+
+    /// Check that the server is responsive, returning the round-trip time
+    /// of a `ping` request.
+    pub fn ping(&mut self) -> Result<Duration, ClientError<T::Error>> {
+        let started = Instant::now();
+        self.request("ping", json!({}))?;
+        Ok(started.elapsed())
+    }
+
+    /// List the server's tools.
+    pub fn list_tools(&mut self) -> Result<ToolListResult, ClientError<T::Error>> {
+        let value = self.request("tools/list", json!({}))?;
+        let list: ToolListResult =
+            serde_json::from_value(value).map_err(ClientError::Serialization)?;
+        self.client.update_tool_cache(&list.tools);
+        Ok(list)
+    }
+
+    /// Call a tool and wait for its result.
+    pub fn call_tool(
+        &mut self,
+        name: impl Into<String>,
+        arguments: Value,
+    ) -> Result<CallToolResult, ClientError<T::Error>> {
+        let name = name.into();
+        if self.client.is_tool_task_required(&name) {
+            return Err(ClientError::Capability(format!(
+                "tool \"{name}\" requires task-based execution"
+            )));
+        }
+        let value = self.request(
+            "tools/call",
+            json!({ "name": name, "arguments": arguments }),
+        )?;
+        serde_json::from_value(value).map_err(ClientError::Serialization)
+    }
+
+    /// Send a notification to the server.
+    pub fn notify(
+        &mut self,
+        method: impl Into<String>,
+        params: Option<Value>,
+    ) -> Result<(), ClientError<T::Error>> {
+        self.client.send_notification(method, params)
+    }
+
+    /// Handle the messages that arrived since the last request.
+    pub fn poll(&mut self) -> Result<(), ClientError<T::Error>> {
+        while let Ok(message) = self.incoming.try_recv() {
+            self.client.handle_message(message)?;
+        }
+        Ok(())
+    }
+
+    /// Shutdown the transport.
+    pub fn close(mut self) -> Result<(), ClientError<T::Error>> {
+        self.client.close()
+    }
+}
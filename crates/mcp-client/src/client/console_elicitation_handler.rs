@@ -0,0 +1,275 @@
+use std::collections::HashMap;
+use std::io::{self, BufRead, BufReader, Write};
+use std::sync::Mutex;
+
+use mcp_core::types::{
+    ElicitRequestFormParams, ElicitResult, ElicitationValue, PrimitiveSchemaDefinition,
+};
+
+use crate::client::{ElicitationError, FormElicitationHandler};
+
+/// Form elicitation handler asking the user on the terminal, for CLI clients.
+///
+/// The server's message is shown first and the user chooses to fill in the
+/// form, decline or cancel. Required fields are then asked for in the order
+/// the schema lists them, followed by the optional ones by name. Empty input
+/// takes the field's default or skips an optional field; enum options may be
+/// picked by number, and values failing their field's constraints are asked
+/// for again. End of input cancels the elicitation.
+///
+/// Questions are written to stderr so they do not mix with the client's own
+/// output on stdout.
+pub struct ConsoleElicitationHandler {
+    input: Mutex<Box<dyn BufRead + Send>>,
+    output: Mutex<Box<dyn Write + Send>>,
+}
+
+impl ConsoleElicitationHandler {
+    /// Read answers from stdin and write questions to stderr.
+    pub fn new() -> Self {
+        Self::with_io(BufReader::new(io::stdin()), io::stderr())
+    }
+
+    /// Read answers from `input` and write questions to `output`.
+    pub fn with_io(
+        input: impl BufRead + Send + 'static,
+        output: impl Write + Send + 'static,
+    ) -> Self {
+        Self {
+            input: Mutex::new(Box::new(input)),
+            output: Mutex::new(Box::new(output)),
+        }
+    }
+}
+
+impl Default for ConsoleElicitationHandler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FormElicitationHandler for ConsoleElicitationHandler {
+    fn handle(&self, params: ElicitRequestFormParams) -> Result<ElicitResult, ElicitationError> {
+        let (Ok(mut input), Ok(mut output)) = (self.input.lock(), self.output.lock()) else {
+            return Err(ElicitationError::Failed(
+                "console elicitation handler poisoned".to_string(),
+            ));
+        };
+        let mut console = Console {
+            input: &mut **input,
+            output: &mut **output,
+        };
+        console
+            .fill_in(params)
+            .map_err(|error| ElicitationError::Failed(format!("console elicitation: {error}")))
+    }
+}
+
+struct Console<'a> {
+    input: &'a mut dyn BufRead,
+    output: &'a mut dyn Write,
+}
+
+impl Console<'_> {
+    fn fill_in(&mut self, params: ElicitRequestFormParams) -> io::Result<ElicitResult> {
+        writeln!(self.output, "{}", params.message)?;
+        loop {
+            let Some(answer) = self.ask("Respond? [Y]es, [n]o, [c]ancel: ")? else {
+                return Ok(ElicitResult::cancel());
+            };
+            match answer.to_ascii_lowercase().as_str() {
+                "" | "y" | "yes" => break,
+                "n" | "no" => return Ok(ElicitResult::decline()),
+                "c" | "cancel" => return Ok(ElicitResult::cancel()),
+                _ => {}
+            }
+        }
+
+        let schema = params.requested_schema;
+        let required = schema.required.unwrap_or_default();
+        let mut optional: Vec<&String> = schema
+            .properties
+            .keys()
+            .filter(|name| !required.contains(name))
+            .collect();
+        optional.sort();
+
+        let fields = required
+            .iter()
+            .map(|name| (name, true))
+            .chain(optional.into_iter().map(|name| (name, false)));
+        let mut content = HashMap::new();
+        for (name, required) in fields {
+            let Some(field) = schema.properties.get(name) else {
+                continue;
+            };
+            match self.ask_field(name, field, required)? {
+                Answer::Value(value) => {
+                    content.insert(name.clone(), value);
+                }
+                Answer::Skipped => {}
+                Answer::EndOfInput => return Ok(ElicitResult::cancel()),
+            }
+        }
+        Ok(ElicitResult::accept(content))
+    }
+
+    fn ask_field(
+        &mut self,
+        name: &str,
+        field: &PrimitiveSchemaDefinition,
+        required: bool,
+    ) -> io::Result<Answer> {
+        let about = About::of(field);
+        let label = about.title.unwrap_or(name);
+        if let Some(description) = about.description {
+            writeln!(self.output, "{label}: {description}")?;
+        }
+        for (index, option) in about.options.iter().enumerate() {
+            writeln!(self.output, "  {}) {option}", index + 1)?;
+        }
+
+        let mut prompt = label.to_string();
+        if required {
+            prompt.push_str(" (required)");
+        }
+        if matches!(field, PrimitiveSchemaDefinition::Boolean(_)) {
+            prompt.push_str(" [y/n]");
+        }
+        if let Some(default) = &about.default {
+            prompt.push_str(&format!(" [default: {}]", display(default)));
+        }
+        prompt.push_str(": ");
+
+        loop {
+            let Some(text) = self.ask(&prompt)? else {
+                return Ok(Answer::EndOfInput);
+            };
+            if text.is_empty() {
+                if let Some(default) = about.default.clone() {
+                    return Ok(Answer::Value(default));
+                }
+                if !required {
+                    return Ok(Answer::Skipped);
+                }
+                writeln!(self.output, "{label} is required")?;
+                continue;
+            }
+            let value = parse(field, &text);
+            match field.check(&value) {
+                Ok(()) => return Ok(Answer::Value(value)),
+                Err(problem) => writeln!(self.output, "{label} {problem}")?,
+            }
+        }
+    }
+
+    /// Show `prompt` and read a trimmed line, or `None` at end of input.
+    fn ask(&mut self, prompt: &str) -> io::Result<Option<String>> {
+        write!(self.output, "{prompt}")?;
+        self.output.flush()?;
+        let mut line = String::new();
+        if self.input.read_line(&mut line)? == 0 {
+            return Ok(None);
+        }
+        Ok(Some(line.trim().to_string()))
+    }
+}
+
+enum Answer {
+    Value(ElicitationValue),
+    Skipped,
+    EndOfInput,
+}
+
+/// What the user is shown about a field.
+struct About<'a> {
+    title: Option<&'a str>,
+    description: Option<&'a str>,
+    default: Option<ElicitationValue>,
+    options: Vec<String>,
+}
+
+impl<'a> About<'a> {
+    fn of(field: &'a PrimitiveSchemaDefinition) -> Self {
+        let (title, description, default, options) = match field {
+            PrimitiveSchemaDefinition::Boolean(schema) => (
+                &schema.title,
+                &schema.description,
+                schema.default.map(ElicitationValue::Boolean),
+                Vec::new(),
+            ),
+            PrimitiveSchemaDefinition::String(schema) => (
+                &schema.title,
+                &schema.description,
+                schema.default.clone().map(ElicitationValue::String),
+                Vec::new(),
+            ),
+            PrimitiveSchemaDefinition::Number(schema) => (
+                &schema.title,
+                &schema.description,
+                schema.default.map(ElicitationValue::Number),
+                Vec::new(),
+            ),
+            PrimitiveSchemaDefinition::UntitledEnum(schema) => (
+                &schema.title,
+                &schema.description,
+                schema.default.clone().map(ElicitationValue::String),
+                schema.enum_values.clone(),
+            ),
+            PrimitiveSchemaDefinition::TitledEnum(schema) => (
+                &schema.title,
+                &schema.description,
+                schema.default.clone().map(ElicitationValue::String),
+                schema
+                    .one_of
+                    .iter()
+                    .map(|option| format!("{} ({})", option.title, option.const_value))
+                    .collect(),
+            ),
+        };
+        Self {
+            title: title.as_deref(),
+            description: description.as_deref(),
+            default,
+            options,
+        }
+    }
+}
+
+/// Turn typed text into a value of the field's type where it reads as one.
+fn parse(field: &PrimitiveSchemaDefinition, text: &str) -> ElicitationValue {
+    let picked = text
+        .parse::<usize>()
+        .ok()
+        .and_then(|number| number.checked_sub(1));
+    let chosen = match field {
+        PrimitiveSchemaDefinition::Boolean(_) => match text.to_ascii_lowercase().as_str() {
+            "y" | "yes" => Some(true.into()),
+            "n" | "no" => Some(false.into()),
+            _ => None,
+        },
+        PrimitiveSchemaDefinition::UntitledEnum(schema) => picked
+            .and_then(|index| schema.enum_values.get(index))
+            .map(|value| value.as_str().into()),
+        PrimitiveSchemaDefinition::TitledEnum(schema) => picked
+            .and_then(|index| schema.one_of.get(index))
+            .or_else(|| {
+                schema
+                    .one_of
+                    .iter()
+                    .find(|option| option.title.eq_ignore_ascii_case(text))
+            })
+            .map(|option| option.const_value.as_str().into()),
+        _ => None,
+    };
+    chosen.unwrap_or_else(|| field.coerce(text.into()))
+}
+
+fn display(value: &ElicitationValue) -> String {
+    match value {
+        ElicitationValue::String(text) => text.clone(),
+        ElicitationValue::Number(number) => number.to_string(),
+        ElicitationValue::Boolean(flag) => flag.to_string(),
+        ElicitationValue::StringArray(values) => values.join(", "),
+    }
+}
@@ -3,12 +3,25 @@ use std::sync::Arc;
 use mcp_core::types::{ElicitRequestFormParams, ElicitRequestUrlParams, ElicitResult};
 
 /// Error type for elicitation handler.
-#[derive(Debug, Clone)]
-pub struct ElicitationError(pub String);
+#[derive(Debug, Clone, PartialEq)]
+pub enum ElicitationError {
+    /// The handler could not produce a response.
+    Failed(String),
+    /// Accepted content does not match the requested schema, one entry per
+    /// problem found.
+    InvalidResponse(Vec<String>),
+}
 
 impl std::fmt::Display for ElicitationError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.0)
+        match self {
+            Self::Failed(message) => write!(f, "{message}"),
+            Self::InvalidResponse(problems) => write!(
+                f,
+                "elicitation response does not match the requested schema: {}",
+                problems.join("; ")
+            ),
+        }
     }
 }
 
@@ -3,7 +3,9 @@ mod client;
 mod client_capabilities;
 mod client_error;
 mod client_options;
+mod connected_client;
 mod client_tasks_capability;
+mod console_elicitation_handler;
 mod elicitation_capability;
 mod elicitation_form_capability;
 mod elicitation_handler;
@@ -14,6 +16,8 @@ mod list_changed_handlers;
 mod list_changed_kind;
 mod list_changed_options;
 mod noop_json_schema_validator;
+mod pending_response;
+mod pending_responses;
 mod prompt_capabilities;
 mod prompt_definition;
 mod prompt_list_result;
@@ -24,7 +28,13 @@ mod resource_list_result;
 mod response_message;
 mod roots_capability;
 mod sampling_handler;
+mod sampling_loop;
+mod sampling_router;
+mod sampling_stream;
+mod sampling_stream_end;
+mod sampling_stream_sender;
 mod server_capabilities;
+mod streaming_sampling_handler;
 mod task_get_result;
 mod task_info;
 mod task_list_result;
@@ -35,13 +45,16 @@ mod tool_capabilities;
 mod tool_definition;
 mod tool_execution;
 mod tool_list_result;
+mod validating_elicitation_handler;
 
 pub use capability_flag::CapabilityFlag;
 pub use client::Client;
 pub use client_capabilities::ClientCapabilities;
 pub use client_error::ClientError;
-pub use client_options::ClientOptions;
+pub use client_options::{ClientOptions, DEFAULT_REQUEST_TIMEOUT};
+pub use connected_client::ConnectedClient;
 pub use client_tasks_capability::ClientTasksCapability;
+pub use console_elicitation_handler::ConsoleElicitationHandler;
 pub use elicitation_capability::ElicitationCapability;
 pub use elicitation_form_capability::ElicitationFormCapability;
 pub use elicitation_handler::{
@@ -57,6 +70,8 @@ pub use list_changed_kind::ListChangedKind;
 pub use list_changed_options::ListChangedOptions;
 pub use mcp_core::types::Root;
 pub use noop_json_schema_validator::NoopJsonSchemaValidator;
+pub(crate) use pending_response::PendingResponse;
+pub(crate) use pending_responses::PendingResponses;
 pub use prompt_capabilities::PromptCapabilities;
 pub use prompt_definition::PromptDefinition;
 pub use prompt_list_result::PromptListResult;
@@ -69,7 +84,15 @@ pub use roots_capability::RootsCapability;
 pub use sampling_handler::{
     BoxedSamplingHandler, SamplingError, SamplingHandler, SamplingHandlerFn,
 };
+pub use sampling_loop::{DEFAULT_MAX_TOOL_ITERATIONS, SamplingLoop, ToolExecutor};
+pub use sampling_router::{BackendProfile, MultiBackendSamplingHandler};
+pub use sampling_stream::SamplingStream;
+pub use sampling_stream_end::SamplingStreamEnd;
+pub use sampling_stream_sender::SamplingStreamSender;
 pub use server_capabilities::ServerCapabilities;
+pub use streaming_sampling_handler::{
+    BoxedStreamingSamplingHandler, StreamingSamplingHandler, StreamingSamplingHandlerFn,
+};
 pub use task_get_result::TaskGetResult;
 pub use task_info::TaskInfo;
 pub use task_list_result::TaskListResult;
@@ -80,6 +103,7 @@ pub use tool_capabilities::ToolCapabilities;
 pub use tool_definition::ToolDefinition;
 pub use tool_execution::ToolExecution;
 pub use tool_list_result::ToolListResult;
+pub use validating_elicitation_handler::ValidatingFormElicitationHandler;
 
 #[cfg(test)]
 mod tests;
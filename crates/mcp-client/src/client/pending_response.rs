@@ -0,0 +1,48 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use futures::channel::oneshot;
+
+use mcp_core::types::{MessageId, ResultMessage};
+
+use crate::client::PendingResponses;
+
+/// Future for the response to one request; resolves to `Err` when the
+/// transport closes first.
+///
+/// Dropping it, e.g. after a timeout, removes the request from the map.
+pub(crate) struct PendingResponse {
+    id: MessageId,
+    receiver: oneshot::Receiver<ResultMessage>,
+    responses: Arc<PendingResponses>,
+}
+
+impl PendingResponse {
+    pub(crate) fn new(
+        id: MessageId,
+        receiver: oneshot::Receiver<ResultMessage>,
+        responses: Arc<PendingResponses>,
+    ) -> Self {
+        Self {
+            id,
+            receiver,
+            responses,
+        }
+    }
+}
+
+impl Future for PendingResponse {
+    type Output = Result<ResultMessage, oneshot::Canceled>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        Pin::new(&mut self.receiver).poll(cx)
+    }
+}
+
+impl Drop for PendingResponse {
+    fn drop(&mut self) {
+        self.responses.cancel(&self.id);
+    }
+}
@@ -0,0 +1,80 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use futures::channel::oneshot;
+
+use mcp_core::types::{MessageId, ResultMessage};
+
+use crate::client::PendingResponse;
+
+type Waiting = HashMap<MessageId, oneshot::Sender<ResultMessage>>;
+
+/// Requests sent by [`Client::request`](crate::client::Client::request)
+/// that are waiting for their response.
+///
+/// Shared with the transport's message callback, so a response resolves
+/// its future on whichever thread the transport delivers it.
+pub(crate) struct PendingResponses {
+    /// `None` once the transport has closed.
+    waiting: Mutex<Option<Waiting>>,
+}
+
+impl Default for PendingResponses {
+    fn default() -> Self {
+        Self {
+            waiting: Mutex::new(Some(HashMap::new())),
+        }
+    }
+}
+
+impl PendingResponses {
+    /// Wait for the response to `id`. After `close` the returned future
+    /// fails straight away.
+    pub fn register(self: &Arc<Self>, id: MessageId) -> PendingResponse {
+        let (tx, rx) = oneshot::channel();
+        if let Some(waiting) = self.waiting.lock().expect("pending responses").as_mut() {
+            waiting.insert(id.clone(), tx);
+        }
+        PendingResponse::new(id, rx, Arc::clone(self))
+    }
+
+    /// Deliver a response to its waiting request. Returns the response
+    /// when no request with its id is waiting.
+    pub fn complete(&self, response: ResultMessage) -> Option<ResultMessage> {
+        let sender = self
+            .waiting
+            .lock()
+            .expect("pending responses")
+            .as_mut()
+            .and_then(|waiting| waiting.remove(&response.id));
+        match sender {
+            Some(sender) => {
+                // The future may have been dropped in the meantime
+                let _ = sender.send(response);
+                None
+            }
+            None => Some(response),
+        }
+    }
+
+    /// Stop waiting for a response, e.g. after a timeout.
+    pub fn cancel(&self, id: &MessageId) {
+        if let Some(waiting) = self.waiting.lock().expect("pending responses").as_mut() {
+            waiting.remove(id);
+        }
+    }
+
+    /// Fail every waiting request, and those registered later, because the
+    /// transport is gone.
+    pub fn close(&self) {
+        *self.waiting.lock().expect("pending responses") = None;
+    }
+
+    /// Accept requests again after the transport is restarted.
+    pub fn reopen(&self) {
+        self.waiting
+            .lock()
+            .expect("pending responses")
+            .get_or_insert_with(HashMap::new);
+    }
+}
@@ -6,4 +6,6 @@ use crate::client::PromptDefinition;
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
 pub struct PromptListResult {
     pub prompts: Vec<PromptDefinition>,
+    #[serde(rename = "nextCursor", skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
 }
@@ -16,6 +16,11 @@ impl RequestStream {
     pub fn recv(&self) -> Option<ResponseMessage> {
         self.receiver.recv().ok()
     }
+
+    /// The next response, if one has already arrived.
+    pub(crate) fn try_recv(&self) -> Option<ResponseMessage> {
+        self.receiver.try_recv().ok()
+    }
 }
 
 impl Iterator for RequestStream {
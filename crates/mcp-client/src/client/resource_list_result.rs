@@ -6,4 +6,6 @@ use crate::client::ResourceDefinition;
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
 pub struct ResourceListResult {
     pub resources: Vec<ResourceDefinition>,
+    #[serde(rename = "nextCursor", skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
 }
@@ -1,6 +1,8 @@
 use std::sync::Arc;
 
-use mcp_core::types::{CreateMessageRequestParams, CreateMessageResult};
+use mcp_core::types::{
+    CreateMessageRequestParams, CreateMessageResult, CreateMessageResultWithTools,
+};
 
 /// Error type for sampling handler.
 #[derive(Debug, Clone)]
@@ -18,6 +20,17 @@ impl std::error::Error for SamplingError {}
 pub trait SamplingHandler: Send + Sync + 'static {
     /// Handle a sampling request from the server.
     fn handle(&self, params: CreateMessageRequestParams) -> Result<CreateMessageResult, SamplingError>;
+
+    /// Handle a sampling request that offers the model `tools`.
+    ///
+    /// The result may hold several content blocks, including tool use. By
+    /// default the request is answered by [`handle`](Self::handle).
+    fn handle_with_tools(
+        &self,
+        params: CreateMessageRequestParams,
+    ) -> Result<CreateMessageResultWithTools, SamplingError> {
+        self.handle(params).map(Into::into)
+    }
 }
 
 /// Type alias for boxed sampling handler.
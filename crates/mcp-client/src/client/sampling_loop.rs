@@ -0,0 +1,123 @@
+use std::sync::Arc;
+
+use mcp_core::types::{
+    CreateMessageContentOrArray, CreateMessageRequestParams, CreateMessageResult,
+    CreateMessageResultWithTools, SamplingMessage, SamplingMessageContent,
+    SamplingMessageContentOrArray, StopReason, ToolChoiceMode, ToolResultContent, ToolUseContent,
+};
+
+use crate::client::{BoxedSamplingHandler, SamplingError, SamplingHandler};
+
+/// Tool rounds a [`SamplingLoop`] runs before giving up, unless configured.
+pub const DEFAULT_MAX_TOOL_ITERATIONS: usize = 10;
+
+/// Callback running one tool call requested by the model.
+///
+/// Failures are reported to the model as a [`ToolResultContent::error`].
+pub type ToolExecutor = Arc<dyn Fn(&ToolUseContent) -> ToolResultContent + Send + Sync>;
+
+/// Sampling handler resolving the model's tool calls on the client.
+///
+/// When a request offering `tools` is answered with tool use (stop reason
+/// `toolUse`), each call is run by the executor, the model's message and a
+/// user message with the results are appended to the conversation, and the
+/// wrapped handler is asked again. The server only sees the final message.
+/// Nothing is run when the request's `ToolChoice` mode is `none`.
+///
+/// Requests without tools go straight to the wrapped handler.
+pub struct SamplingLoop {
+    handler: BoxedSamplingHandler,
+    executor: ToolExecutor,
+    max_iterations: usize,
+}
+
+impl SamplingLoop {
+    /// Loop around `handler`, running tool calls with `executor`.
+    pub fn new<F>(handler: BoxedSamplingHandler, executor: F) -> Self
+    where
+        F: Fn(&ToolUseContent) -> ToolResultContent + Send + Sync + 'static,
+    {
+        Self {
+            handler,
+            executor: Arc::new(executor),
+            max_iterations: DEFAULT_MAX_TOOL_ITERATIONS,
+        }
+    }
+
+    /// Fail the request once the model still asks for tools after
+    /// `max_iterations` rounds of tool calls.
+    pub fn with_max_iterations(mut self, max_iterations: usize) -> Self {
+        self.max_iterations = max_iterations;
+        self
+    }
+}
+
+impl SamplingHandler for SamplingLoop {
+    fn handle(
+        &self,
+        params: CreateMessageRequestParams,
+    ) -> Result<CreateMessageResult, SamplingError> {
+        self.handler.handle(params)
+    }
+
+    fn handle_with_tools(
+        &self,
+        mut params: CreateMessageRequestParams,
+    ) -> Result<CreateMessageResultWithTools, SamplingError> {
+        let tools_allowed = params
+            .tool_choice
+            .as_ref()
+            .and_then(|choice| choice.mode.as_ref())
+            != Some(&ToolChoiceMode::None);
+
+        let mut result = self.handler.handle_with_tools(params.clone())?;
+        let mut iterations = 0;
+        loop {
+            let calls = tool_uses(&result);
+            let wants_tools = result.stop_reason == Some(StopReason::ToolUse) && !calls.is_empty();
+            if !tools_allowed || !wants_tools {
+                return Ok(result);
+            }
+            if iterations == self.max_iterations {
+                return Err(SamplingError(format!(
+                    "model still requested tools after {} iterations",
+                    self.max_iterations
+                )));
+            }
+            iterations += 1;
+
+            let results = calls
+                .into_iter()
+                .map(|call| (self.executor)(&call).into())
+                .collect();
+            params.messages.push(SamplingMessage {
+                role: result.role,
+                content: SamplingMessageContentOrArray::Array(into_blocks(result.content)),
+                meta: None,
+            });
+            params.messages.push(SamplingMessage::user_multi(results));
+            result = self.handler.handle_with_tools(params.clone())?;
+        }
+    }
+}
+
+fn tool_uses(result: &CreateMessageResultWithTools) -> Vec<ToolUseContent> {
+    let blocks = match &result.content {
+        CreateMessageContentOrArray::Single(block) => std::slice::from_ref(block),
+        CreateMessageContentOrArray::Array(blocks) => blocks.as_slice(),
+    };
+    blocks
+        .iter()
+        .filter_map(|block| match block {
+            SamplingMessageContent::ToolUse(call) => Some(call.clone()),
+            _ => None,
+        })
+        .collect()
+}
+
+fn into_blocks(content: CreateMessageContentOrArray) -> Vec<SamplingMessageContent> {
+    match content {
+        CreateMessageContentOrArray::Single(block) => vec![block],
+        CreateMessageContentOrArray::Array(blocks) => blocks,
+    }
+}
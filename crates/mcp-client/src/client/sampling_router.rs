@@ -0,0 +1,128 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use mcp_core::types::{
+    CreateMessageRequestParams, CreateMessageResult, CreateMessageResultWithTools, ModelPreferences,
+};
+
+use crate::client::{BoxedSamplingHandler, SamplingError, SamplingHandler};
+
+/// How a backend compares to the others when several match a hint.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BackendProfile {
+    /// Relative cost per request, from 0 (cheapest) to 1.
+    pub cost: f64,
+    /// Relative speed, from 0 to 1 (fastest).
+    pub speed: f64,
+}
+
+impl Default for BackendProfile {
+    fn default() -> Self {
+        Self {
+            cost: 0.5,
+            speed: 0.5,
+        }
+    }
+}
+
+/// Sampling handler routing each request to one of several named backends
+/// according to the server's `ModelPreferences`.
+///
+/// Hints are tried in order. A backend whose name equals the hint wins
+/// outright; otherwise every backend whose name contains it is a candidate,
+/// as hints may name a model family (e.g. `claude` for `claude-sonnet`).
+/// Candidates are ranked by the request's `cost_priority` and
+/// `speed_priority` against their [`BackendProfile`]. Requests matching no
+/// backend go to the default one.
+pub struct MultiBackendSamplingHandler {
+    backends: HashMap<String, BoxedSamplingHandler>,
+    profiles: HashMap<String, BackendProfile>,
+    default: BoxedSamplingHandler,
+}
+
+impl MultiBackendSamplingHandler {
+    /// Create a router sending every request to `default` until backends
+    /// are added.
+    pub fn new<H: SamplingHandler>(default: H) -> Self {
+        Self {
+            backends: HashMap::new(),
+            profiles: HashMap::new(),
+            default: Arc::new(default),
+        }
+    }
+
+    /// Add a backend selected by hints matching `name`.
+    pub fn with_backend<H: SamplingHandler>(self, name: impl Into<String>, handler: H) -> Self {
+        self.with_profiled_backend(name, handler, BackendProfile::default())
+    }
+
+    /// Add a backend with its cost and speed, used to break ties between
+    /// backends matching the same hint.
+    pub fn with_profiled_backend<H: SamplingHandler>(
+        mut self,
+        name: impl Into<String>,
+        handler: H,
+        profile: BackendProfile,
+    ) -> Self {
+        let name = name.into();
+        self.profiles.insert(name.clone(), profile);
+        self.backends.insert(name, Arc::new(handler));
+        self
+    }
+
+    /// Name of the backend a request with `preferences` goes to, or `None`
+    /// for the default backend.
+    pub fn route(&self, preferences: Option<&ModelPreferences>) -> Option<&str> {
+        let preferences = preferences?;
+        let hints = preferences.hints.as_deref().unwrap_or_default();
+        for hint in hints {
+            let Some(wanted) = hint.name.as_deref() else {
+                continue;
+            };
+            if let Some((name, _)) = self.backends.get_key_value(wanted) {
+                return Some(name);
+            }
+            let best = self
+                .backends
+                .keys()
+                .filter(|name| name.contains(wanted))
+                .map(|name| (self.score(name, preferences), name))
+                // Equal scores fall back to the name so routing is stable
+                .max_by(|(a, a_name), (b, b_name)| a.total_cmp(b).then(b_name.cmp(a_name)));
+            if let Some((_, name)) = best {
+                return Some(name);
+            }
+        }
+        None
+    }
+
+    fn backend(&self, params: &CreateMessageRequestParams) -> &BoxedSamplingHandler {
+        match self.route(params.model_preferences.as_ref()) {
+            Some(name) => &self.backends[name],
+            None => &self.default,
+        }
+    }
+
+    fn score(&self, name: &str, preferences: &ModelPreferences) -> f64 {
+        let profile = self.profiles.get(name).copied().unwrap_or_default();
+        let cost_priority = preferences.cost_priority.unwrap_or(0.0);
+        let speed_priority = preferences.speed_priority.unwrap_or(0.0);
+        cost_priority * (1.0 - profile.cost) + speed_priority * profile.speed
+    }
+}
+
+impl SamplingHandler for MultiBackendSamplingHandler {
+    fn handle(
+        &self,
+        params: CreateMessageRequestParams,
+    ) -> Result<CreateMessageResult, SamplingError> {
+        self.backend(&params).handle(params)
+    }
+
+    fn handle_with_tools(
+        &self,
+        params: CreateMessageRequestParams,
+    ) -> Result<CreateMessageResultWithTools, SamplingError> {
+        self.backend(&params).handle_with_tools(params)
+    }
+}
@@ -0,0 +1,33 @@
+use std::sync::mpsc::Receiver;
+
+use mcp_core::types::MessageDeltaNotificationParams;
+
+/// Blocking stream of the pieces of streamed sampling responses, as
+/// returned by [`Client::subscribe_sampling_stream`](crate::client::Client::subscribe_sampling_stream).
+#[derive(Debug)]
+pub struct SamplingStream {
+    receiver: Receiver<MessageDeltaNotificationParams>,
+}
+
+impl SamplingStream {
+    pub(crate) fn new(receiver: Receiver<MessageDeltaNotificationParams>) -> Self {
+        Self { receiver }
+    }
+
+    pub fn recv(&self) -> Option<MessageDeltaNotificationParams> {
+        self.receiver.recv().ok()
+    }
+
+    /// The next piece if one has already arrived.
+    pub fn try_recv(&self) -> Option<MessageDeltaNotificationParams> {
+        self.receiver.try_recv().ok()
+    }
+}
+
+impl Iterator for SamplingStream {
+    type Item = MessageDeltaNotificationParams;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.receiver.recv().ok()
+    }
+}
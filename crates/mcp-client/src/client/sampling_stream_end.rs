@@ -0,0 +1,55 @@
+use mcp_core::types::{CreateMessageResult, Role, SamplingContent, StopReason, TextContent};
+
+use crate::client::SamplingError;
+
+/// What a [`StreamingSamplingHandler`](crate::client::StreamingSamplingHandler)
+/// reports once it has sent the whole message.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SamplingStreamEnd {
+    /// The name of the model that generated the message.
+    pub model: String,
+    /// The reason why sampling stopped, if known.
+    pub stop_reason: Option<StopReason>,
+}
+
+impl SamplingStreamEnd {
+    pub fn new(model: impl Into<String>) -> Self {
+        Self {
+            model: model.into(),
+            stop_reason: None,
+        }
+    }
+
+    pub fn with_stop_reason(mut self, stop_reason: StopReason) -> Self {
+        self.stop_reason = Some(stop_reason);
+        self
+    }
+
+    /// Assemble the streamed pieces into the response to the request.
+    ///
+    /// Text pieces are concatenated; an image or audio piece must be the
+    /// only one, since the result holds a single content block.
+    pub(crate) fn into_result(
+        self,
+        chunks: Vec<SamplingContent>,
+    ) -> Result<CreateMessageResult, SamplingError> {
+        let content = match <[SamplingContent; 1]>::try_from(chunks) {
+            Ok([chunk]) => chunk,
+            Err(chunks) => {
+                let mut text = String::new();
+                for chunk in chunks {
+                    let SamplingContent::Text(chunk) = chunk else {
+                        return Err(SamplingError(
+                            "streamed message mixes text with image or audio".to_string(),
+                        ));
+                    };
+                    text.push_str(&chunk.text);
+                }
+                SamplingContent::Text(TextContent::new(text))
+            }
+        };
+        let mut result = CreateMessageResult::new(self.model, Role::Assistant, content);
+        result.stop_reason = self.stop_reason;
+        Ok(result)
+    }
+}
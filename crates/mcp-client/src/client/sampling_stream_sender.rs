@@ -0,0 +1,33 @@
+use std::sync::mpsc::Sender;
+
+use mcp_core::types::{SamplingContent, TextContent};
+
+use crate::client::SamplingError;
+
+/// Handle a [`StreamingSamplingHandler`](crate::client::StreamingSamplingHandler)
+/// sends the pieces of a generated message through.
+///
+/// Clones share one channel; the client assembles the final result once
+/// every clone has been dropped.
+#[derive(Debug, Clone)]
+pub struct SamplingStreamSender {
+    sender: Sender<SamplingContent>,
+}
+
+impl SamplingStreamSender {
+    pub(crate) fn new(sender: Sender<SamplingContent>) -> Self {
+        Self { sender }
+    }
+
+    /// Send the next piece of the message.
+    pub fn send(&self, delta: impl Into<SamplingContent>) -> Result<(), SamplingError> {
+        self.sender
+            .send(delta.into())
+            .map_err(|_| SamplingError("sampling stream closed".to_string()))
+    }
+
+    /// Send the next piece of text.
+    pub fn send_text(&self, text: impl Into<String>) -> Result<(), SamplingError> {
+        self.send(TextContent::new(text))
+    }
+}
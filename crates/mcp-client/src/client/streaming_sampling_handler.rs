@@ -0,0 +1,44 @@
+use std::sync::Arc;
+
+use mcp_core::types::CreateMessageRequestParams;
+
+use crate::client::{SamplingError, SamplingStreamEnd, SamplingStreamSender};
+
+/// Handler trait for sampling/createMessage requests answered piece by piece.
+///
+/// The handler runs on its own thread while the client forwards each piece
+/// to the server as a `notifications/message/delta` notification and to
+/// [`SamplingStream`](crate::client::SamplingStream) subscribers.
+pub trait StreamingSamplingHandler: Send + Sync + 'static {
+    /// Generate the message for `params`, sending its pieces through `sender`.
+    fn handle_streaming(
+        &self,
+        params: CreateMessageRequestParams,
+        sender: SamplingStreamSender,
+    ) -> Result<SamplingStreamEnd, SamplingError>;
+}
+
+/// Type alias for boxed streaming sampling handler.
+pub type BoxedStreamingSamplingHandler = Arc<dyn StreamingSamplingHandler>;
+
+/// Function-based streaming sampling handler implementation.
+pub struct StreamingSamplingHandlerFn<F>(pub F);
+
+impl<F> StreamingSamplingHandler for StreamingSamplingHandlerFn<F>
+where
+    F: Fn(
+            CreateMessageRequestParams,
+            SamplingStreamSender,
+        ) -> Result<SamplingStreamEnd, SamplingError>
+        + Send
+        + Sync
+        + 'static,
+{
+    fn handle_streaming(
+        &self,
+        params: CreateMessageRequestParams,
+        sender: SamplingStreamSender,
+    ) -> Result<SamplingStreamEnd, SamplingError> {
+        (self.0)(params, sender)
+    }
+}
@@ -2,8 +2,10 @@ use serde::{Deserialize, Serialize};
 
 use crate::client::TaskInfo;
 
-/// Result payload for tasks/get.
+/// Result payload for tasks/get and tasks/cancel: the task's fields at the
+/// top level of the result.
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
 pub struct TaskGetResult {
+    #[serde(flatten)]
     pub task: TaskInfo,
 }
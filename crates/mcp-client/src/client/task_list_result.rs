@@ -6,6 +6,6 @@ use crate::client::TaskInfo;
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
 pub struct TaskListResult {
     pub tasks: Vec<TaskInfo>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub cursor: Option<String>,
+    #[serde(rename = "nextCursor", skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
 }
@@ -1,8 +1,10 @@
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
-/// Result payload for tasks/result.
+/// Result payload for tasks/result: the result of the request the task
+/// ran, such as a tools/call result.
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
 pub struct TaskResult {
+    #[serde(flatten)]
     pub task: Value,
 }
@@ -1,9 +1,17 @@
 use super::*;
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
 use std::rc::Rc;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
 
-use mcp_core::stdio::{JsonRpcMessage, Transport};
-use mcp_core::types::{NotificationMessage, ResultMessage};
+use futures::executor::block_on;
+
+use mcp_core::protocol::ProtocolError;
+use mcp_core::stdio::{CloseHandler, CloseReason, JsonRpcMessage, MessageHandler, Transport};
+use mcp_core::types::{
+    ContentBlock, CreateMessageRequestParams, CreateMessageResult, ErrorCode, ErrorObject,
+    MessageId, NotificationMessage, ResultMessage, TaskCreationParams,
+};
 
 #[derive(Debug)]
 enum MockError {}
@@ -50,7 +58,7 @@ fn initialize_is_sent_on_connect() {
     let transport = MockTransport::new(Rc::clone(&history));
     let mut client = Client::new(transport, ClientOptions::new("rust-client"));
 
-    client.connect().unwrap();
+    client.start().unwrap();
 
     let sent = history.borrow();
     assert_eq!(sent.len(), 1);
@@ -68,7 +76,7 @@ fn initialize_response_sends_initialized_notification() {
     let transport = MockTransport::new(Rc::clone(&history));
     let mut client = Client::new(transport, ClientOptions::new("rust-client"));
 
-    client.connect().unwrap();
+    client.start().unwrap();
 
     let init_id = match history.borrow().get(0) {
         Some(JsonRpcMessage::Request(req)) => req.id.clone(),
@@ -107,7 +115,7 @@ fn list_tools_caches_task_support() {
     let transport = MockTransport::new(Rc::clone(&history));
     let mut client = Client::new(transport, ClientOptions::new("rust-client"));
 
-    client.connect().unwrap();
+    client.start().unwrap();
     let init_id = match history.borrow().get(0) {
         Some(JsonRpcMessage::Request(req)) => req.id.clone(),
         _ => panic!("expected initialize request"),
@@ -124,9 +132,9 @@ fn list_tools_caches_task_support() {
         .handle_message(JsonRpcMessage::Result(response))
         .unwrap();
 
-    let list_id = client.list_tools().unwrap();
+    let list = client.list_tools(None);
     let list_response = ResultMessage::success(
-        list_id.clone(),
+        last_request_id(&history),
         serde_json::json!({
             "tools": [
                 {
@@ -141,7 +149,12 @@ fn list_tools_caches_task_support() {
         .handle_message(JsonRpcMessage::Result(list_response))
         .unwrap();
 
+    let list = block_on(list).unwrap();
+    assert_eq!(list.tools[0].name, "required-tool");
     assert!(client.is_tool_task_required("required-tool"));
+    let err = block_on(client.call_tool("required-tool", serde_json::json!({})))
+        .expect_err("the tool only runs as a task");
+    assert!(matches!(err, ClientError::Capability(_)));
 }
 
 #[test]
@@ -150,7 +163,7 @@ fn tool_call_requires_structured_content_when_schema_exists() {
     let transport = MockTransport::new(Rc::clone(&history));
     let mut client = Client::new(transport, ClientOptions::new("rust-client"));
 
-    client.connect().unwrap();
+    client.start().unwrap();
     let init_id = match history.borrow().get(0) {
         Some(JsonRpcMessage::Request(req)) => req.id.clone(),
         _ => panic!("expected initialize request"),
@@ -167,9 +180,9 @@ fn tool_call_requires_structured_content_when_schema_exists() {
         .handle_message(JsonRpcMessage::Result(response))
         .unwrap();
 
-    let list_id = client.list_tools().unwrap();
+    let list = client.list_tools(None);
     let list_response = ResultMessage::success(
-        list_id.clone(),
+        last_request_id(&history),
         serde_json::json!({
             "tools": [
                 {
@@ -183,21 +196,29 @@ fn tool_call_requires_structured_content_when_schema_exists() {
         .handle_message(JsonRpcMessage::Result(list_response))
         .unwrap();
 
-    let call_id = client
-        .call_tool("schema-tool", serde_json::json!({}))
-        .unwrap();
+    block_on(list).unwrap();
+
+    let call = client.call_tool("schema-tool", serde_json::json!({}));
     let call_response = ResultMessage::success(
-        call_id,
+        last_request_id(&history),
         serde_json::json!({
             "isError": false
         }),
     );
-    let err = client
+    client
         .handle_message(JsonRpcMessage::Result(call_response))
-        .expect_err("missing structuredContent should error");
+        .unwrap();
+    let err = block_on(call).expect_err("missing structuredContent should error");
     assert!(matches!(err, ClientError::Validation(_)));
 }
 
+fn last_request_id(history: &Rc<RefCell<Vec<JsonRpcMessage>>>) -> MessageId {
+    match history.borrow().last() {
+        Some(JsonRpcMessage::Request(request)) => request.id.clone(),
+        other => panic!("expected a request, got {other:?}"),
+    }
+}
+
 #[test]
 fn list_changed_debounce_delays_refresh() {
     let history = Rc::new(RefCell::new(Vec::new()));
@@ -211,7 +232,7 @@ fn list_changed_debounce_delays_refresh() {
         ClientOptions::new("rust-client").with_list_changed(handlers),
     );
 
-    client.connect().unwrap();
+    client.start().unwrap();
     let init_id = match history.borrow().get(0) {
         Some(JsonRpcMessage::Request(req)) => req.id.clone(),
         _ => panic!("expected initialize request"),
@@ -277,7 +298,7 @@ fn prompts_list_changed_triggers_handler() {
         ClientOptions::new("rust-client").with_list_changed(handlers),
     );
 
-    client.connect().unwrap();
+    client.start().unwrap();
     let init_id = match history.borrow().get(0) {
         Some(JsonRpcMessage::Request(req)) => req.id.clone(),
         _ => panic!("expected initialize request"),
@@ -336,7 +357,7 @@ fn request_stream_returns_result() {
     let transport = MockTransport::new(Rc::clone(&history));
     let mut client = Client::new(transport, ClientOptions::new("rust-client"));
 
-    client.connect().unwrap();
+    client.start().unwrap();
     let init_id = match history.borrow().get(0) {
         Some(JsonRpcMessage::Request(req)) => req.id.clone(),
         _ => panic!("expected initialize request"),
@@ -394,7 +415,7 @@ fn request_stream_emits_task_notifications() {
     let transport = MockTransport::new(Rc::clone(&history));
     let mut client = Client::new(transport, ClientOptions::new("rust-client"));
 
-    client.connect().unwrap();
+    client.start().unwrap();
     let init_id = match history.borrow().get(0) {
         Some(JsonRpcMessage::Request(req)) => req.id.clone(),
         _ => panic!("expected initialize request"),
@@ -440,7 +461,7 @@ fn task_requests_require_tasks_capability() {
     let transport = MockTransport::new(Rc::clone(&history));
     let mut client = Client::new(transport, ClientOptions::new("rust-client"));
 
-    client.connect().unwrap();
+    client.start().unwrap();
     let init_id = match history.borrow().get(0) {
         Some(JsonRpcMessage::Request(req)) => req.id.clone(),
         _ => panic!("expected initialize request"),
@@ -457,8 +478,1380 @@ fn task_requests_require_tasks_capability() {
         .handle_message(JsonRpcMessage::Result(response))
         .unwrap();
 
+    let err = block_on(client.get_task("task-1")).expect_err("tasks should be unsupported");
+    match err {
+        ClientError::CapabilityNotSupported { capability, method } => {
+            assert_eq!(capability, "tasks");
+            assert_eq!(method, "tasks/get");
+        }
+        other => panic!("unexpected error: {other:?}"),
+    }
+    // Refused without sending anything after initialized
+    assert_eq!(history.borrow().len(), 2);
+}
+
+type Script = Box<dyn Fn(&JsonRpcMessage) -> Vec<JsonRpcMessage>>;
+
+/// Transport whose peer answers every message with the replies of a script.
+struct ScriptedTransport {
+    sent: Rc<RefCell<Vec<JsonRpcMessage>>>,
+    closed: Rc<Cell<bool>>,
+    script: Script,
+    handler: Option<MessageHandler<JsonRpcMessage>>,
+}
+
+impl ScriptedTransport {
+    fn new(sent: &Rc<RefCell<Vec<JsonRpcMessage>>>, script: Script) -> Self {
+        Self {
+            sent: Rc::clone(sent),
+            closed: Rc::new(Cell::new(false)),
+            script,
+            handler: None,
+        }
+    }
+}
+
+impl Transport for ScriptedTransport {
+    type Message = JsonRpcMessage;
+    type Error = MockError;
+
+    fn start(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn send(&mut self, message: &Self::Message) -> Result<(), Self::Error> {
+        self.sent.borrow_mut().push(message.clone());
+        for reply in (self.script)(message) {
+            if let Some(handler) = &self.handler {
+                handler(reply);
+            }
+        }
+        Ok(())
+    }
+
+    fn close(&mut self) -> Result<(), Self::Error> {
+        self.closed.set(true);
+        Ok(())
+    }
+
+    fn on_message(&mut self, handler: MessageHandler<Self::Message>) {
+        self.handler = Some(handler);
+    }
+}
+
+/// A server that negotiates `version` and offers an echo tool.
+fn scripted_server(version: &'static str) -> Script {
+    Box::new(move |message| {
+        let JsonRpcMessage::Request(request) = message else {
+            return Vec::new();
+        };
+        let reply = match request.method.as_str() {
+            "initialize" => ResultMessage::success(
+                request.id.clone(),
+                serde_json::json!({
+                    "protocolVersion": version,
+                    "capabilities": { "tools": {} },
+                    "serverInfo": { "name": "scripted-server", "version": "1.0.0" },
+                    "instructions": "Call echo to test the connection"
+                }),
+            ),
+            "tools/list" => ResultMessage::success(
+                request.id.clone(),
+                serde_json::json!({
+                    "tools": [{ "name": "echo", "inputSchema": { "type": "object" } }]
+                }),
+            ),
+            "tools/call" => ResultMessage::success(
+                request.id.clone(),
+                serde_json::json!({
+                    "content": [{
+                        "type": "text",
+                        "text": request.params["arguments"]["message"]
+                    }]
+                }),
+            ),
+            "ping" => ResultMessage::success(request.id.clone(), serde_json::json!({})),
+            other => ResultMessage::failure(
+                request.id.clone(),
+                ErrorObject::new(
+                    ErrorCode::MethodNotFound as i32,
+                    format!("unknown method: {other}"),
+                    None,
+                ),
+            ),
+        };
+        vec![JsonRpcMessage::Result(reply)]
+    })
+}
+
+#[test]
+fn connect_completes_the_initialize_handshake() {
+    let sent = Rc::new(RefCell::new(Vec::new()));
+    let transport = ScriptedTransport::new(&sent, scripted_server("0.1.0"));
+
+    let client = Client::connect(transport, ClientOptions::new("rust-client")).unwrap();
+
+    assert_eq!(client.server_info().name, "scripted-server");
+    assert!(client.server_capabilities().tools.is_some());
+    assert_eq!(
+        client.instructions(),
+        Some("Call echo to test the connection")
+    );
+    assert_eq!(client.protocol_version(), "0.1.0");
+
+    let sent = sent.borrow();
+    assert_eq!(sent.len(), 2);
+    assert!(matches!(&sent[0], JsonRpcMessage::Request(req) if req.method == "initialize"));
+    assert!(matches!(
+        &sent[1],
+        JsonRpcMessage::Notification(note) if note.method == "notifications/initialized"
+    ));
+}
+
+#[test]
+fn connect_accepts_another_supported_version() {
+    let sent = Rc::new(RefCell::new(Vec::new()));
+    let transport = ScriptedTransport::new(&sent, scripted_server("2025-06-18"));
+    let options = ClientOptions::new("rust-client").with_protocol_version("2025-11-25");
+
+    let client = Client::connect(transport, options).unwrap();
+
+    assert_eq!(client.protocol_version(), "2025-06-18");
+}
+
+#[test]
+fn connect_rejects_an_unknown_protocol_version() {
+    let sent = Rc::new(RefCell::new(Vec::new()));
+    let transport = ScriptedTransport::new(&sent, scripted_server("1999-01-01"));
+    let closed = Rc::clone(&transport.closed);
+
+    let err = match Client::connect(transport, ClientOptions::new("rust-client")) {
+        Ok(_) => panic!("the handshake should fail"),
+        Err(err) => err,
+    };
+
+    match err {
+        ClientError::ProtocolVersionMismatch {
+            requested,
+            received,
+        } => {
+            assert_eq!(requested, "0.1.0");
+            assert_eq!(received, "1999-01-01");
+        }
+        other => panic!("unexpected error: {other:?}"),
+    }
+    assert!(closed.get());
+    // The handshake never completed, so the server was not told it did
+    assert_eq!(sent.borrow().len(), 1);
+}
+
+#[test]
+fn connect_times_out_without_an_initialize_result() {
+    let sent = Rc::new(RefCell::new(Vec::new()));
+    let transport = ScriptedTransport::new(&sent, Box::new(|_| Vec::new()));
+    let options = ClientOptions::new("rust-client").with_request_timeout(Duration::from_millis(50));
+
+    let err = match Client::connect(transport, options) {
+        Ok(_) => panic!("the handshake should time out"),
+        Err(err) => err,
+    };
+    assert!(matches!(err, ClientError::Protocol(ProtocolError::Timeout)));
+}
+
+#[test]
+fn requests_before_the_handshake_are_refused() {
+    let history = Rc::new(RefCell::new(Vec::new()));
+    let transport = MockTransport::new(Rc::clone(&history));
+    let mut client = Client::new(transport, ClientOptions::new("rust-client"));
+
+    let err = block_on(client.list_tools(None)).expect_err("not initialized yet");
+    assert!(matches!(err, ClientError::Initialization(_)));
+
+    // Still refused while the initialize result is outstanding
+    client.start().unwrap();
+    let err = client
+        .request_stream("tools/list", serde_json::json!({}))
+        .expect_err("handshake still in flight");
+    assert!(matches!(err, ClientError::Initialization(_)));
+    assert_eq!(history.borrow().len(), 1);
+}
+
+#[test]
+fn connected_client_waits_for_request_results() {
+    let sent = Rc::new(RefCell::new(Vec::new()));
+    let transport = ScriptedTransport::new(&sent, scripted_server("0.1.0"));
+    let mut client = Client::connect(transport, ClientOptions::new("rust-client")).unwrap();
+
+    let tools = client.list_tools().unwrap();
+    assert_eq!(tools.tools[0].name, "echo");
+
+    let result = client
+        .call_tool("echo", serde_json::json!({ "message": "hello" }))
+        .unwrap();
+    assert_eq!(
+        serde_json::to_value(&result.content[0]).unwrap()["text"],
+        "hello"
+    );
+
     let err = client
-        .get_task("task-1")
-        .expect_err("tasks should be unsupported");
-    assert!(matches!(err, ClientError::Capability(_)));
+        .request("prompts/list", serde_json::json!({}))
+        .expect_err("prompts are not offered");
+    assert!(matches!(err, ClientError::CapabilityNotSupported { .. }));
+
+    let err = client
+        .request("tools/unknown", serde_json::json!({}))
+        .expect_err("unknown method");
+    match err {
+        ClientError::Protocol(ProtocolError::Handler(message)) => {
+            assert_eq!(message, "unknown method: tools/unknown");
+        }
+        other => panic!("unexpected error: {other:?}"),
+    }
+}
+
+#[test]
+fn ping_measures_the_round_trip() {
+    let sent = Rc::new(RefCell::new(Vec::new()));
+    let transport = ScriptedTransport::new(&sent, scripted_server("0.1.0"));
+    let mut client = Client::connect(transport, ClientOptions::new("rust-client")).unwrap();
+
+    let round_trip = client.ping().unwrap();
+    assert!(round_trip < Duration::from_secs(5));
+    block_on(client.client_mut().ping()).unwrap();
+
+    let sent = sent.borrow();
+    assert!(matches!(sent.last(), Some(JsonRpcMessage::Request(req)) if req.method == "ping"));
+}
+
+#[test]
+fn ping_may_precede_the_handshake() {
+    let history = Rc::new(RefCell::new(Vec::new()));
+    let transport = MockTransport::new(Rc::clone(&history));
+    let mut client = Client::new(transport, ClientOptions::new("rust-client"));
+
+    let ping = client.ping();
+    let id = match history.borrow().last() {
+        Some(JsonRpcMessage::Request(request)) if request.method == "ping" => request.id.clone(),
+        other => panic!("expected a ping, got {other:?}"),
+    };
+    client
+        .handle_message(JsonRpcMessage::Result(ResultMessage::success(
+            id,
+            serde_json::json!({}),
+        )))
+        .unwrap();
+
+    assert!(block_on(ping).is_ok());
+}
+
+/// A server whose only tool, which must run as a task, is renamed once
+/// `renamed` is set.
+fn renaming_server(renamed: Rc<Cell<bool>>) -> Script {
+    Box::new(move |message| {
+        let JsonRpcMessage::Request(request) = message else {
+            return Vec::new();
+        };
+        let result = match request.method.as_str() {
+            "initialize" => serde_json::json!({
+                "protocolVersion": "0.1.0",
+                "capabilities": { "tools": { "listChanged": true } },
+                "serverInfo": { "name": "renaming-server" }
+            }),
+            "tools/list" => {
+                let name = if renamed.get() {
+                    "search_issues"
+                } else {
+                    "find_issues"
+                };
+                serde_json::json!({
+                    "tools": [{
+                        "name": name,
+                        "inputSchema": { "type": "object" },
+                        "execution": { "taskSupport": "required" }
+                    }]
+                })
+            }
+            _ => return Vec::new(),
+        };
+        vec![JsonRpcMessage::Result(ResultMessage::success(
+            request.id.clone(),
+            result,
+        ))]
+    })
+}
+
+#[test]
+fn tools_list_changed_invalidates_and_refreshes_the_tool_cache() {
+    let refreshed = Arc::new(Mutex::new(Vec::new()));
+    let handlers = ListChangedHandlers {
+        tools: Some(ListChangedOptions::new({
+            let refreshed = Arc::clone(&refreshed);
+            move |result| refreshed.lock().unwrap().push(result)
+        })),
+        ..Default::default()
+    };
+    let options = ClientOptions::new("rust-client").with_list_changed(handlers);
+    let renamed = Rc::new(Cell::new(false));
+    let sent = Rc::new(RefCell::new(Vec::new()));
+    let transport = ScriptedTransport::new(&sent, renaming_server(Rc::clone(&renamed)));
+    let mut client = Client::connect(transport, options).unwrap();
+
+    block_on(client.client_mut().list_tools(None)).unwrap();
+    assert!(client.client().is_tool_task_required("find_issues"));
+
+    // The server renames the tool and says so
+    renamed.set(true);
+    let notification = NotificationMessage::new("notifications/tools/list_changed", None);
+    client
+        .client_mut()
+        .handle_message(JsonRpcMessage::Notification(notification))
+        .unwrap();
+    assert!(!client.client().is_tool_task_required("find_issues"));
+    assert!(refreshed.lock().unwrap().is_empty());
+
+    // The refetched list arrives with the next poll
+    client.poll().unwrap();
+    assert!(client.client().is_tool_task_required("search_issues"));
+    let refreshed = refreshed.lock().unwrap();
+    let [Ok(Some(tools))] = refreshed.as_slice() else {
+        panic!("expected one refreshed list: {refreshed:?}");
+    };
+    assert_eq!(tools.len(), 1);
+    assert_eq!(tools[0]["name"], "search_issues");
+}
+
+/// Transport that answers initialize itself and leaves every other
+/// response to the test, which delivers it through a `ThreadedPeer` from
+/// any thread.
+struct ThreadedTransport {
+    peer: ThreadedPeer,
+}
+
+#[derive(Clone, Default)]
+struct ThreadedPeer {
+    sent: Arc<Mutex<Vec<JsonRpcMessage>>>,
+    handler: Arc<Mutex<Option<MessageHandler<JsonRpcMessage>>>>,
+    close_handler: Arc<Mutex<Option<CloseHandler>>>,
+}
+
+impl ThreadedPeer {
+    fn deliver(&self, message: ResultMessage) {
+        let handler = self.handler.lock().unwrap().clone();
+        handler.expect("message handler")(JsonRpcMessage::Result(message));
+    }
+
+    fn request_id(&self, index: usize) -> MessageId {
+        match &self.sent.lock().unwrap()[index] {
+            JsonRpcMessage::Request(request) => request.id.clone(),
+            other => panic!("expected a request, got {other:?}"),
+        }
+    }
+
+    fn close(&self) {
+        let handler = self.close_handler.lock().unwrap().clone();
+        handler.expect("close handler")(CloseReason::Eof);
+    }
+}
+
+impl Transport for ThreadedTransport {
+    type Message = JsonRpcMessage;
+    type Error = MockError;
+
+    fn start(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn send(&mut self, message: &Self::Message) -> Result<(), Self::Error> {
+        self.peer.sent.lock().unwrap().push(message.clone());
+        if let JsonRpcMessage::Request(request) = message
+            && request.method == "initialize"
+        {
+            self.peer.deliver(ResultMessage::success(
+                request.id.clone(),
+                serde_json::json!({
+                    "protocolVersion": "0.1.0",
+                    "capabilities": { "tools": {} },
+                    "serverInfo": { "name": "threaded-server", "version": "1.0.0" }
+                }),
+            ));
+        }
+        Ok(())
+    }
+
+    fn close(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn on_message(&mut self, handler: MessageHandler<Self::Message>) {
+        *self.peer.handler.lock().unwrap() = Some(handler);
+    }
+
+    fn on_close_with_reason(&mut self, handler: CloseHandler) {
+        *self.peer.close_handler.lock().unwrap() = Some(handler);
+    }
+}
+
+fn connect_threaded(options: ClientOptions) -> (ConnectedClient<ThreadedTransport>, ThreadedPeer) {
+    let peer = ThreadedPeer::default();
+    let transport = ThreadedTransport { peer: peer.clone() };
+    let client = Client::connect(transport, options).unwrap();
+    (client, peer)
+}
+
+#[test]
+fn request_futures_resolve_on_the_transport_thread() {
+    let (mut client, peer) = connect_threaded(ClientOptions::new("rust-client"));
+    let first = client
+        .client_mut()
+        .request("tools/list", serde_json::json!({}));
+    let second = client
+        .client_mut()
+        .request("tools/call", serde_json::json!({ "name": "missing" }));
+    // initialize and notifications/initialized come first
+    let (first_id, second_id) = (peer.request_id(2), peer.request_id(3));
+
+    let transport_thread = {
+        let peer = peer.clone();
+        std::thread::spawn(move || {
+            peer.deliver(ResultMessage::failure(
+                second_id,
+                ErrorObject::new(ErrorCode::InvalidParams as i32, "unknown tool", None),
+            ));
+            peer.deliver(ResultMessage::success(
+                first_id,
+                serde_json::json!({ "tools": [] }),
+            ));
+        })
+    };
+    transport_thread.join().unwrap();
+
+    let first = block_on(first).unwrap();
+    assert_eq!(first.result, Some(serde_json::json!({ "tools": [] })));
+    let second = block_on(second).unwrap();
+    assert_eq!(second.error.unwrap().message, "unknown tool");
+    // Nothing was left for the connected client to handle
+    assert!(client.poll().is_ok());
+}
+
+#[test]
+fn duplicate_responses_are_ignored() {
+    let (mut client, peer) = connect_threaded(ClientOptions::new("rust-client"));
+    let response = client
+        .client_mut()
+        .request("tools/list", serde_json::json!({}));
+    let id = peer.request_id(2);
+
+    peer.deliver(ResultMessage::success(
+        id.clone(),
+        serde_json::json!({ "tools": [] }),
+    ));
+    peer.deliver(ResultMessage::success(
+        id,
+        serde_json::json!({ "tools": [{ "name": "late" }] }),
+    ));
+
+    let response = block_on(response).unwrap();
+    assert_eq!(response.result, Some(serde_json::json!({ "tools": [] })));
+    // The duplicate reaches the client, which drops it with a warning
+    assert!(client.poll().is_ok());
+}
+
+#[test]
+fn timed_out_requests_are_forgotten() {
+    let options = ClientOptions::new("rust-client").with_request_timeout(Duration::from_millis(20));
+    let (mut client, peer) = connect_threaded(options);
+    let response = client
+        .client_mut()
+        .request("tools/list", serde_json::json!({}));
+
+    let err = block_on(response).expect_err("no response was delivered");
+    assert!(matches!(err, ClientError::Protocol(ProtocolError::Timeout)));
+
+    // A late response is handed to the client, which drops it
+    peer.deliver(ResultMessage::success(
+        peer.request_id(2),
+        serde_json::json!({}),
+    ));
+    assert!(client.poll().is_ok());
+}
+
+#[test]
+fn closing_the_transport_rejects_pending_requests() {
+    let (mut client, peer) = connect_threaded(ClientOptions::new("rust-client"));
+    let first = client
+        .client_mut()
+        .request("tools/list", serde_json::json!({}));
+
+    peer.close();
+    let err = block_on(first).expect_err("the transport closed");
+    assert!(matches!(err, ClientError::ConnectionClosed));
+
+    // Requests sent after the transport closed fail the same way
+    let later = client
+        .client_mut()
+        .request("tools/list", serde_json::json!({}));
+    let err = block_on(later).expect_err("the transport is closed");
+    assert!(matches!(err, ClientError::ConnectionClosed));
+}
+
+#[test]
+fn client_close_rejects_pending_requests() {
+    let (mut client, _peer) = connect_threaded(ClientOptions::new("rust-client"));
+    let response = client
+        .client_mut()
+        .request("tools/list", serde_json::json!({}));
+
+    client.close().unwrap();
+
+    let err = block_on(response).expect_err("the client closed");
+    assert!(matches!(err, ClientError::ConnectionClosed));
+}
+
+#[test]
+fn dropped_request_futures_leave_no_pending_entry() {
+    let responses = Arc::new(PendingResponses::default());
+    let id = MessageId::Number(7);
+
+    drop(responses.register(id.clone()));
+
+    let late = ResultMessage::success(id, serde_json::json!({}));
+    assert!(responses.complete(late).is_some());
+}
+
+#[test]
+fn handle_message_resolves_request_futures() {
+    let history = Rc::new(RefCell::new(Vec::new()));
+    let transport = MockTransport::new(Rc::clone(&history));
+    let mut client = Client::new(transport, ClientOptions::new("rust-client"));
+    client.start().unwrap();
+    let init_id = match &history.borrow()[0] {
+        JsonRpcMessage::Request(request) => request.id.clone(),
+        _ => panic!("expected initialize request"),
+    };
+    client
+        .handle_message(JsonRpcMessage::Result(ResultMessage::success(
+            init_id,
+            serde_json::json!({
+                "protocolVersion": "0.1.0",
+                "capabilities": { "tools": {} },
+                "serverInfo": { "name": "rust-server", "version": "1.2.3" }
+            }),
+        )))
+        .unwrap();
+
+    let response = client.request("tools/list", serde_json::json!({}));
+    let id = match history.borrow().last() {
+        Some(JsonRpcMessage::Request(request)) => request.id.clone(),
+        _ => panic!("expected tools/list request"),
+    };
+    client
+        .handle_message(JsonRpcMessage::Result(ResultMessage::success(
+            id,
+            serde_json::json!({ "tools": [] }),
+        )))
+        .unwrap();
+
+    let response = block_on(response).unwrap();
+    assert_eq!(response.result, Some(serde_json::json!({ "tools": [] })));
+}
+
+#[test]
+fn call_tool_parses_content_and_is_error() {
+    let (mut client, peer) = connect_threaded(ClientOptions::new("rust-client"));
+    let call = client
+        .client_mut()
+        .call_tool("flaky", serde_json::json!({}));
+
+    peer.deliver(ResultMessage::success(
+        peer.request_id(2),
+        serde_json::json!({
+            "content": [{ "type": "text", "text": "backend unavailable" }],
+            "isError": true
+        }),
+    ));
+
+    let result = block_on(call).unwrap();
+    assert_eq!(result.is_error, Some(true));
+    let [ContentBlock::Text(text)] = result.content.as_slice() else {
+        panic!("expected one text block: {:?}", result.content);
+    };
+    assert_eq!(text.text, "backend unavailable");
+}
+
+fn assert_unsupported<R: std::fmt::Debug>(
+    result: Result<R, ClientError<MockError>>,
+    expected: &str,
+) {
+    match result {
+        Err(ClientError::CapabilityNotSupported { capability, .. }) => {
+            assert_eq!(capability, expected);
+        }
+        other => panic!("unexpected result: {other:?}"),
+    }
+}
+
+#[test]
+fn typed_requests_fail_fast_without_the_server_capability() {
+    let (mut client, peer) = connect_threaded(ClientOptions::new("rust-client"));
+    let client = client.client_mut();
+
+    assert_unsupported(block_on(client.list_prompts(None)), "prompts");
+    assert_unsupported(block_on(client.get_prompt("greet", None)), "prompts");
+    assert_unsupported(block_on(client.list_resources(None)), "resources");
+    assert_unsupported(block_on(client.read_resource("file:///a")), "resources");
+    assert_unsupported(block_on(client.list_tasks(None)), "tasks");
+    assert_unsupported(block_on(client.cancel_task("task-1")), "tasks");
+    let task_call =
+        client.create_task_call("echo", serde_json::json!({}), TaskCreationParams::default());
+    assert_unsupported(block_on(task_call), "tasks.requests.tools.call");
+    // Only initialize and notifications/initialized reached the server
+    assert_eq!(peer.sent.lock().unwrap().len(), 2);
+}
+
+#[test]
+fn malformed_results_report_the_raw_json() {
+    let (mut client, peer) = connect_threaded(ClientOptions::new("rust-client"));
+    let list = client.client_mut().list_tools(None);
+
+    peer.deliver(ResultMessage::success(
+        peer.request_id(2),
+        serde_json::json!({ "tools": "none" }),
+    ));
+
+    match block_on(list).expect_err("tools is not a list") {
+        ClientError::Deserialization { method, raw, .. } => {
+            assert_eq!(method, "tools/list");
+            assert_eq!(raw, r#"{"tools":"none"}"#);
+        }
+        other => panic!("unexpected error: {other:?}"),
+    }
+}
+
+/// A server that runs every tool call as a task.
+fn task_server() -> Script {
+    let task = serde_json::json!({
+        "taskId": "task-1",
+        "status": "working",
+        "createdAt": "2026-01-01T00:00:00Z",
+        "lastUpdatedAt": "2026-01-01T00:00:00Z"
+    });
+    Box::new(move |message| {
+        let JsonRpcMessage::Request(request) = message else {
+            return Vec::new();
+        };
+        let result = match request.method.as_str() {
+            "initialize" => serde_json::json!({
+                "protocolVersion": "0.1.0",
+                "capabilities": {
+                    "tools": {},
+                    "tasks": { "list": {}, "cancel": {}, "requests": { "tools": { "call": {} } } }
+                },
+                "serverInfo": { "name": "task-server" }
+            }),
+            "tools/call" => serde_json::json!({ "task": task }),
+            "tasks/get" => task.clone(),
+            "tasks/cancel" => {
+                let mut cancelled = task.clone();
+                cancelled["status"] = serde_json::json!("cancelled");
+                cancelled
+            }
+            "tasks/result" => serde_json::json!({
+                "content": [{ "type": "text", "text": "done" }]
+            }),
+            "tasks/list" => serde_json::json!({ "tasks": [task], "nextCursor": "2" }),
+            _ => return Vec::new(),
+        };
+        vec![JsonRpcMessage::Result(ResultMessage::success(
+            request.id.clone(),
+            result,
+        ))]
+    })
+}
+
+#[test]
+fn task_requests_map_onto_task_results() {
+    let sent = Rc::new(RefCell::new(Vec::new()));
+    let transport = ScriptedTransport::new(&sent, task_server());
+    let mut client = Client::connect(transport, ClientOptions::new("rust-client")).unwrap();
+    let client = client.client_mut();
+
+    let created = block_on(client.create_task_call(
+        "slow",
+        serde_json::json!({}),
+        TaskCreationParams {
+            ttl: Some(60_000),
+            poll_interval: None,
+        },
+    ))
+    .unwrap();
+    assert_eq!(created.task.task_id, "task-1");
+    match sent.borrow().last() {
+        Some(JsonRpcMessage::Request(request)) => {
+            assert_eq!(request.params["task"], serde_json::json!({ "ttl": 60_000 }));
+        }
+        other => panic!("expected tools/call, got {other:?}"),
+    }
+
+    let status = block_on(client.get_task("task-1")).unwrap();
+    assert_eq!(status.task.status.as_deref(), Some("working"));
+
+    let result = block_on(client.task_result("task-1")).unwrap();
+    assert_eq!(result.task["content"][0]["text"], "done");
+
+    let tasks = block_on(client.list_tasks(None)).unwrap();
+    assert_eq!(tasks.tasks.len(), 1);
+    assert_eq!(tasks.next_cursor.as_deref(), Some("2"));
+
+    let cancelled = block_on(client.cancel_task("task-1")).unwrap();
+    assert_eq!(cancelled.task.status.as_deref(), Some("cancelled"));
+}
+
+fn sampling_client(
+    history: &Rc<RefCell<Vec<JsonRpcMessage>>>,
+) -> Client<MockTransport> {
+    let options = ClientOptions::new("rust-client").with_capabilities(ClientCapabilities {
+        sampling: Some(CapabilityFlag::default()),
+        ..Default::default()
+    });
+    Client::new(MockTransport::new(Rc::clone(history)), options)
+}
+
+fn sampling_request() -> JsonRpcMessage {
+    JsonRpcMessage::Request(mcp_core::types::RequestMessage::new(
+        "sample-1",
+        "sampling/createMessage",
+        serde_json::json!({ "messages": [], "maxTokens": 16 }),
+    ))
+}
+
+#[test]
+fn streamed_sampling_deltas_are_forwarded_and_assembled() {
+    let history = Rc::new(RefCell::new(Vec::new()));
+    let mut client = sampling_client(&history);
+    client.set_streaming_sampling_handler(StreamingSamplingHandlerFn(
+        |_params, sender: SamplingStreamSender| {
+            sender.send_text("Hello, ")?;
+            // Clones may keep sending from other threads
+            let clone = sender.clone();
+            std::thread::spawn(move || clone.send_text("world"))
+                .join()
+                .unwrap()?;
+            Ok(SamplingStreamEnd::new("stream-model")
+                .with_stop_reason(mcp_core::types::StopReason::EndTurn))
+        },
+    ));
+    let stream = client.subscribe_sampling_stream();
+
+    client.handle_message(sampling_request()).unwrap();
+
+    let sent = history.borrow();
+    assert_eq!(sent.len(), 3);
+    for (message, text) in sent.iter().zip(["Hello, ", "world"]) {
+        let JsonRpcMessage::Notification(note) = message else {
+            panic!("expected a delta notification");
+        };
+        assert_eq!(note.method, "notifications/message/delta");
+        let params = note.params.as_ref().unwrap();
+        assert_eq!(params["requestId"], "sample-1");
+        assert_eq!(params["delta"]["text"], text);
+    }
+    let JsonRpcMessage::Result(result) = &sent[2] else {
+        panic!("expected the sampling result");
+    };
+    let result = result.result.as_ref().unwrap();
+    assert_eq!(result["content"]["text"], "Hello, world");
+    assert_eq!(result["model"], "stream-model");
+    assert_eq!(result["stopReason"], "endTurn");
+
+    let received: Vec<_> = std::iter::from_fn(|| stream.try_recv()).collect();
+    assert_eq!(received.len(), 2);
+    assert!(
+        matches!(&received[1].delta, mcp_core::types::SamplingContent::Text(text) if text.text == "world")
+    );
+}
+
+#[test]
+fn streamed_sampling_rejects_mixed_content() {
+    let history = Rc::new(RefCell::new(Vec::new()));
+    let mut client = sampling_client(&history);
+    client.set_streaming_sampling_handler(StreamingSamplingHandlerFn(
+        |_params, sender: SamplingStreamSender| {
+            sender.send_text("a picture: ")?;
+            sender.send(mcp_core::types::ImageContent::new("aGk=", "image/png"))?;
+            Ok(SamplingStreamEnd::new("stream-model"))
+        },
+    ));
+
+    client.handle_message(sampling_request()).unwrap();
+
+    let sent = history.borrow();
+    let Some(JsonRpcMessage::Result(result)) = sent.last() else {
+        panic!("expected the sampling result");
+    };
+    let error = result.error.as_ref().expect("mixed content should fail");
+    assert!(error.message.contains("mixes text"));
+}
+
+/// Sampling backend answering with its own name as the model.
+fn named_backend(
+    name: &'static str,
+) -> SamplingHandlerFn<
+    impl Fn(CreateMessageRequestParams) -> Result<CreateMessageResult, SamplingError> + Send + Sync,
+> {
+    SamplingHandlerFn(move |_params| {
+        Ok(CreateMessageResult::new(
+            name,
+            mcp_core::types::Role::Assistant,
+            mcp_core::types::TextContent::new("hi").into(),
+        ))
+    })
+}
+
+fn preferences(
+    hints: &[&str],
+    cost_priority: Option<f64>,
+    speed_priority: Option<f64>,
+) -> mcp_core::types::ModelPreferences {
+    mcp_core::types::ModelPreferences {
+        hints: Some(
+            hints
+                .iter()
+                .map(|hint| mcp_core::types::ModelHint::new(*hint))
+                .collect(),
+        ),
+        cost_priority,
+        speed_priority,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn sampling_router_follows_hints_in_order() {
+    let router = MultiBackendSamplingHandler::new(named_backend("default"))
+        .with_backend("claude-sonnet", named_backend("claude-sonnet"))
+        .with_backend("gpt-4o", named_backend("gpt-4o"));
+
+    let prefs = preferences(&["gemini", "gpt-4o", "claude"], None, None);
+    assert_eq!(router.route(Some(&prefs)), Some("gpt-4o"));
+
+    // Hints may name a family of models
+    let prefs = preferences(&["claude"], None, None);
+    assert_eq!(router.route(Some(&prefs)), Some("claude-sonnet"));
+
+    let prefs = preferences(&["gemini"], None, None);
+    assert_eq!(router.route(Some(&prefs)), None);
+    assert_eq!(router.route(None), None);
+
+    let mut params = CreateMessageRequestParams::new(Vec::new(), 16);
+    params.model_preferences = Some(preferences(&["gemini"], None, None));
+    assert_eq!(router.handle(params).unwrap().model, "default");
+}
+
+#[test]
+fn sampling_router_breaks_ties_by_cost_and_speed() {
+    let router = MultiBackendSamplingHandler::new(named_backend("default"))
+        .with_profiled_backend(
+            "claude-haiku",
+            named_backend("claude-haiku"),
+            BackendProfile {
+                cost: 0.1,
+                speed: 0.6,
+            },
+        )
+        .with_profiled_backend(
+            "claude-flash",
+            named_backend("claude-flash"),
+            BackendProfile {
+                cost: 0.4,
+                speed: 0.9,
+            },
+        );
+
+    let cheap = preferences(&["claude"], Some(0.9), Some(0.1));
+    assert_eq!(router.route(Some(&cheap)), Some("claude-haiku"));
+
+    let fast = preferences(&["claude"], Some(0.1), Some(0.9));
+    let mut params = CreateMessageRequestParams::new(Vec::new(), 16);
+    params.model_preferences = Some(fast);
+    assert_eq!(router.handle(params).unwrap().model, "claude-flash");
+
+    // Without priorities the choice is still stable
+    let neutral = preferences(&["claude"], None, None);
+    assert_eq!(router.route(Some(&neutral)), Some("claude-flash"));
+}
+/// Sampling backend replaying `replies` in order, repeating the last one,
+/// and recording every request.
+struct ScriptedSampling {
+    replies: Vec<mcp_core::types::CreateMessageResultWithTools>,
+    requests: Mutex<Vec<CreateMessageRequestParams>>,
+}
+
+impl ScriptedSampling {
+    fn new(replies: Vec<mcp_core::types::CreateMessageResultWithTools>) -> Arc<Self> {
+        Arc::new(Self {
+            replies,
+            requests: Mutex::new(Vec::new()),
+        })
+    }
+}
+
+impl SamplingHandler for ScriptedSampling {
+    fn handle(
+        &self,
+        _params: CreateMessageRequestParams,
+    ) -> Result<CreateMessageResult, SamplingError> {
+        Err(SamplingError("expected a request with tools".to_string()))
+    }
+
+    fn handle_with_tools(
+        &self,
+        params: CreateMessageRequestParams,
+    ) -> Result<mcp_core::types::CreateMessageResultWithTools, SamplingError> {
+        let mut requests = self.requests.lock().unwrap();
+        requests.push(params);
+        let index = (requests.len() - 1).min(self.replies.len() - 1);
+        Ok(self.replies[index].clone())
+    }
+}
+
+fn tool_use_reply(id: &str) -> mcp_core::types::CreateMessageResultWithTools {
+    let call = mcp_core::types::ToolUseContent::new(
+        "get_weather",
+        id,
+        [("city".to_string(), serde_json::json!("Paris"))].into(),
+    );
+    mcp_core::types::CreateMessageResultWithTools::new(
+        "scripted",
+        mcp_core::types::Role::Assistant,
+        vec![
+            mcp_core::types::TextContent::new("Let me check.").into(),
+            call.into(),
+        ],
+    )
+    .with_stop_reason(mcp_core::types::StopReason::ToolUse)
+}
+
+fn text_reply(text: &str) -> mcp_core::types::CreateMessageResultWithTools {
+    mcp_core::types::CreateMessageResultWithTools::new(
+        "scripted",
+        mcp_core::types::Role::Assistant,
+        mcp_core::types::SamplingMessageContent::from(mcp_core::types::TextContent::new(text)),
+    )
+    .with_stop_reason(mcp_core::types::StopReason::EndTurn)
+}
+
+/// Tool executor answering every call with "sunny", counting the calls.
+fn weather_executor(
+    calls: &Arc<Mutex<Vec<String>>>,
+) -> impl Fn(&mcp_core::types::ToolUseContent) -> mcp_core::types::ToolResultContent + use<> {
+    let calls = Arc::clone(calls);
+    move |call| {
+        calls.lock().unwrap().push(call.id.clone());
+        mcp_core::types::ToolResultContent::new(
+            call.id.clone(),
+            vec![ContentBlock::Text(mcp_core::types::TextContent::new(
+                "sunny",
+            ))],
+        )
+    }
+}
+
+fn tools_params(choice: Option<mcp_core::types::ToolChoice>) -> CreateMessageRequestParams {
+    let tool: mcp_core::types::Tool = serde_json::from_value(serde_json::json!({
+        "name": "get_weather",
+        "inputSchema": { "type": "object" }
+    }))
+    .unwrap();
+    CreateMessageRequestParams::new(
+        vec![mcp_core::types::SamplingMessage::user(
+            mcp_core::types::TextContent::new("Weather in Paris?"),
+        )],
+        64,
+    )
+    .with_tools(vec![tool], choice)
+}
+
+#[test]
+fn sampling_loop_returns_answers_without_tool_use() {
+    let calls = Arc::new(Mutex::new(Vec::new()));
+
+    let scripted = ScriptedSampling::new(vec![text_reply("No tools needed.")]);
+    let sampling = SamplingLoop::new(scripted.clone(), weather_executor(&calls));
+    let result = sampling.handle_with_tools(tools_params(None)).unwrap();
+    assert_eq!(result, text_reply("No tools needed."));
+    assert_eq!(scripted.requests.lock().unwrap().len(), 1);
+
+    // Tool use is passed through untouched when the server forbids tools
+    let scripted = ScriptedSampling::new(vec![tool_use_reply("call-1")]);
+    let sampling = SamplingLoop::new(scripted.clone(), weather_executor(&calls));
+    let result = sampling
+        .handle_with_tools(tools_params(Some(mcp_core::types::ToolChoice::none())))
+        .unwrap();
+    assert_eq!(result, tool_use_reply("call-1"));
+    assert_eq!(scripted.requests.lock().unwrap().len(), 1);
+
+    assert!(calls.lock().unwrap().is_empty());
+}
+
+#[test]
+fn sampling_loop_runs_tool_calls_before_answering_the_server() {
+    let calls = Arc::new(Mutex::new(Vec::new()));
+    let scripted =
+        ScriptedSampling::new(vec![tool_use_reply("call-1"), text_reply("It is sunny.")]);
+    let history = Rc::new(RefCell::new(Vec::new()));
+    let mut client = sampling_client(&history);
+    client.set_sampling_handler(SamplingLoop::new(
+        scripted.clone(),
+        weather_executor(&calls),
+    ));
+
+    let params =
+        serde_json::to_value(tools_params(Some(mcp_core::types::ToolChoice::auto()))).unwrap();
+    client
+        .handle_message(JsonRpcMessage::Request(
+            mcp_core::types::RequestMessage::new("sample-1", "sampling/createMessage", params),
+        ))
+        .unwrap();
+
+    let sent = history.borrow();
+    let Some(JsonRpcMessage::Result(result)) = sent.last() else {
+        panic!("expected the sampling result");
+    };
+    let result = result.result.as_ref().unwrap();
+    assert_eq!(result["content"]["text"], "It is sunny.");
+    assert_eq!(result["stopReason"], "endTurn");
+    assert_eq!(*calls.lock().unwrap(), ["call-1"]);
+
+    // The follow-up request carries the model's tool use and its result
+    let requests = scripted.requests.lock().unwrap();
+    assert_eq!(requests.len(), 2);
+    let messages = serde_json::to_value(&requests[1].messages).unwrap();
+    assert_eq!(messages.as_array().unwrap().len(), 3);
+    assert_eq!(messages[1]["role"], "assistant");
+    assert_eq!(messages[1]["content"][1]["type"], "tool_use");
+    assert_eq!(messages[2]["role"], "user");
+    assert_eq!(messages[2]["content"][0]["type"], "tool_result");
+    assert_eq!(messages[2]["content"][0]["toolUseId"], "call-1");
+    assert_eq!(messages[2]["content"][0]["content"][0]["text"], "sunny");
+}
+
+#[test]
+fn sampling_loop_gives_up_after_max_iterations() {
+    let calls = Arc::new(Mutex::new(Vec::new()));
+    let scripted = ScriptedSampling::new(vec![tool_use_reply("call-1")]);
+    let sampling =
+        SamplingLoop::new(scripted.clone(), weather_executor(&calls)).with_max_iterations(2);
+
+    let error = sampling.handle_with_tools(tools_params(None)).unwrap_err();
+
+    assert!(error.0.contains("after 2 iterations"));
+    assert_eq!(calls.lock().unwrap().len(), 2);
+    assert_eq!(scripted.requests.lock().unwrap().len(), 3);
+}
+fn signup_schema() -> mcp_core::types::ElicitationSchema {
+    use mcp_core::types::{
+        BooleanSchema, ElicitationSchema, EnumOption, NumberSchema, PrimitiveSchemaDefinition,
+        StringFormat, StringSchema, TitledEnumSchema, UntitledEnumSchema,
+    };
+
+    let mut name = StringSchema::new();
+    name.min_length = Some(2);
+    name.max_length = Some(20);
+    let mut email = StringSchema::new();
+    email.format = Some(StringFormat::Email);
+    let mut age = NumberSchema::integer();
+    age.minimum = Some(0.0);
+    age.maximum = Some(150.0);
+    let region = |value: &str, title: &str| EnumOption {
+        const_value: value.to_string(),
+        title: title.to_string(),
+    };
+    ElicitationSchema::new()
+        .with_property("name", PrimitiveSchemaDefinition::String(name))
+        .with_property("email", PrimitiveSchemaDefinition::String(email))
+        .with_property("age", PrimitiveSchemaDefinition::Number(age))
+        .with_property(
+            "newsletter",
+            PrimitiveSchemaDefinition::Boolean(BooleanSchema::new()),
+        )
+        .with_property(
+            "plan",
+            PrimitiveSchemaDefinition::UntitledEnum(UntitledEnumSchema::new(vec![
+                "free".to_string(),
+                "pro".to_string(),
+            ])),
+        )
+        .with_property(
+            "region",
+            PrimitiveSchemaDefinition::TitledEnum(TitledEnumSchema::new(vec![
+                region("eu", "Europe"),
+                region("us", "United States"),
+            ])),
+        )
+        .with_required(vec![
+            "name".to_string(),
+            "email".to_string(),
+            "age".to_string(),
+        ])
+}
+
+fn signup_params() -> mcp_core::types::ElicitRequestFormParams {
+    mcp_core::types::ElicitRequestFormParams::new("Sign up", signup_schema())
+}
+
+/// Answer a signup form with the required fields plus `values`, through
+/// the validating handler.
+fn answer_signup(
+    values: &[(&str, mcp_core::types::ElicitationValue)],
+    coerce: bool,
+) -> Result<mcp_core::types::ElicitResult, ElicitationError> {
+    let mut content: std::collections::HashMap<_, _> = [
+        ("name".to_string(), "Ada".into()),
+        ("email".to_string(), "ada@example.com".into()),
+        ("age".to_string(), 36.into()),
+    ]
+    .into_iter()
+    .collect();
+    for (name, value) in values {
+        content.insert(name.to_string(), value.clone());
+    }
+    let handler = ValidatingFormElicitationHandler::new(FormElicitationHandlerFn(move |_params| {
+        Ok(mcp_core::types::ElicitResult::accept(content.clone()))
+    }))
+    .with_coerce(coerce);
+    handler.handle(signup_params())
+}
+
+fn signup_problems(values: &[(&str, mcp_core::types::ElicitationValue)]) -> Vec<String> {
+    match answer_signup(values, false) {
+        Ok(_) => Vec::new(),
+        Err(ElicitationError::InvalidResponse(problems)) => problems,
+        Err(other) => panic!("unexpected error: {other}"),
+    }
+}
+
+#[test]
+fn validated_form_accepts_matching_content() {
+    let result = answer_signup(
+        &[
+            ("newsletter", true.into()),
+            ("plan", "pro".into()),
+            ("region", "eu".into()),
+        ],
+        false,
+    )
+    .unwrap();
+    assert_eq!(result.content.unwrap()["plan"], "pro".into());
+}
+
+#[test]
+fn validated_form_requires_required_fields() {
+    let handler = ValidatingFormElicitationHandler::new(FormElicitationHandlerFn(|_params| {
+        Ok(mcp_core::types::ElicitResult::accept(
+            [("name".to_string(), "Ada".into())].into_iter().collect(),
+        ))
+    }));
+    let error = handler.handle(signup_params()).unwrap_err();
+    assert_eq!(
+        error,
+        ElicitationError::InvalidResponse(vec![
+            "`email` is required".to_string(),
+            "`age` is required".to_string(),
+        ])
+    );
+}
+
+#[test]
+fn validated_form_checks_string_lengths_and_formats() {
+    assert_eq!(
+        signup_problems(&[("name", "A".into()), ("email", "ada@".into())]),
+        [
+            "`email` must be a valid email address",
+            "`name` must be at least 2 characters",
+        ]
+    );
+    assert_eq!(
+        signup_problems(&[("name", 7.into())]),
+        ["`name` must be a string"]
+    );
+}
+
+#[test]
+fn validated_form_checks_number_ranges_and_integers() {
+    assert_eq!(
+        signup_problems(&[("age", 36.5.into())]),
+        ["`age` must be an integer"]
+    );
+    assert_eq!(
+        signup_problems(&[("age", 200.into())]),
+        ["`age` must be at most 150"]
+    );
+    assert_eq!(
+        signup_problems(&[("age", "36".into())]),
+        ["`age` must be a number"]
+    );
+}
+
+#[test]
+fn validated_form_checks_booleans() {
+    assert!(signup_problems(&[("newsletter", false.into())]).is_empty());
+    assert_eq!(
+        signup_problems(&[("newsletter", "yes".into())]),
+        ["`newsletter` must be a boolean"]
+    );
+}
+
+#[test]
+fn validated_form_checks_enum_membership() {
+    assert_eq!(
+        signup_problems(&[("plan", "enterprise".into())]),
+        ["`plan` must be one of the listed options"]
+    );
+    // Titled enums take the option's value, not its title
+    assert_eq!(
+        signup_problems(&[("region", "Europe".into())]),
+        ["`region` must be one of the listed options"]
+    );
+    assert!(signup_problems(&[("region", "us".into())]).is_empty());
+}
+
+#[test]
+fn validated_form_coerces_obvious_values_when_asked() {
+    let values = [("age", " 36 ".into()), ("newsletter", "true".into())];
+    let content = answer_signup(&values, true).unwrap().content.unwrap();
+    assert_eq!(content["age"], 36.into());
+    assert_eq!(content["newsletter"], true.into());
+
+    let still_invalid = answer_signup(&[("age", "thirty".into())], true).unwrap_err();
+    assert_eq!(
+        still_invalid,
+        ElicitationError::InvalidResponse(vec!["`age` must be a number".to_string()])
+    );
+}
+
+#[test]
+fn validated_form_passes_declines_through() {
+    let handler = ValidatingFormElicitationHandler::new(FormElicitationHandlerFn(|_params| {
+        Ok(mcp_core::types::ElicitResult::decline())
+    }));
+    let result = handler.handle(signup_params()).unwrap();
+    assert_eq!(result.action, mcp_core::types::ElicitAction::Decline);
+}
+
+#[test]
+fn invalid_elicitation_responses_are_rejected_to_the_server() {
+    let history = Rc::new(RefCell::new(Vec::new()));
+    let options = ClientOptions::new("rust-client").with_capabilities(ClientCapabilities {
+        elicitation: Some(ElicitationCapability::default()),
+        ..Default::default()
+    });
+    let mut client = Client::new(MockTransport::new(Rc::clone(&history)), options);
+    client.set_form_elicitation_handler(ValidatingFormElicitationHandler::new(
+        FormElicitationHandlerFn(|_params| {
+            Ok(mcp_core::types::ElicitResult::accept(Default::default()))
+        }),
+    ));
+
+    let params = serde_json::to_value(signup_params()).unwrap();
+    client
+        .handle_message(JsonRpcMessage::Request(
+            mcp_core::types::RequestMessage::new("elicit-1", "elicitation/create", params),
+        ))
+        .unwrap();
+
+    let sent = history.borrow();
+    let Some(JsonRpcMessage::Result(response)) = sent.last() else {
+        panic!("expected the elicitation response");
+    };
+    let error = response.error.as_ref().expect("error response");
+    assert!(
+        error.message.contains("`name` is required"),
+        "{}",
+        error.message
+    );
+}
+
+/// Output shared with the test after the handler takes ownership.
+#[derive(Clone, Default)]
+struct SharedOutput(Arc<Mutex<Vec<u8>>>);
+
+impl std::io::Write for SharedOutput {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.lock().unwrap().write(buf)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+impl SharedOutput {
+    fn text(&self) -> String {
+        String::from_utf8(self.0.lock().unwrap().clone()).unwrap()
+    }
+}
+
+fn console(input: &str) -> (ConsoleElicitationHandler, SharedOutput) {
+    let output = SharedOutput::default();
+    let handler =
+        ConsoleElicitationHandler::with_io(std::io::Cursor::new(input.to_string()), output.clone());
+    (handler, output)
+}
+
+#[test]
+fn console_handler_asks_for_each_field() {
+    // Required fields in schema order, then newsletter, plan and region
+    let (handler, output) =
+        console("\nA\nAda\nada@example\nada@example.com\n36.5\n36\n\n2\neurope\n");
+    let mut params = signup_params();
+    if let Some(mcp_core::types::PrimitiveSchemaDefinition::Boolean(newsletter)) =
+        params.requested_schema.properties.get_mut("newsletter")
+    {
+        newsletter.default = Some(true);
+    }
+
+    let result = handler.handle(params).unwrap();
+
+    assert_eq!(result.action, mcp_core::types::ElicitAction::Accept);
+    let content = result.content.unwrap();
+    assert_eq!(content["name"], "Ada".into());
+    assert_eq!(content["email"], "ada@example.com".into());
+    assert_eq!(content["age"], 36.into());
+    assert_eq!(content["newsletter"], true.into());
+    assert_eq!(content["plan"], "pro".into());
+    assert_eq!(content["region"], "eu".into());
+
+    let output = output.text();
+    assert!(output.starts_with("Sign up\n"));
+    assert!(output.contains("name must be at least 2 characters"));
+    assert!(output.contains("email must be a valid email address"));
+    assert!(output.contains("age must be an integer"));
+    assert!(output.contains("newsletter [y/n] [default: true]: "));
+    assert!(output.contains("  2) United States (us)"));
+}
+
+#[test]
+fn console_handler_skips_optional_fields_and_parses_booleans() {
+    let (handler, _) = console("y\nAda\nada@example.com\n36\nno\n\n\n");
+    let content = handler.handle(signup_params()).unwrap().content.unwrap();
+    assert_eq!(content["newsletter"], false.into());
+    assert!(!content.contains_key("plan"));
+    assert!(!content.contains_key("region"));
+}
+
+#[test]
+fn console_handler_declines_and_cancels() {
+    let (handler, _) = console("n\n");
+    let result = handler.handle(signup_params()).unwrap();
+    assert_eq!(result.action, mcp_core::types::ElicitAction::Decline);
+
+    let (handler, _) = console("c\n");
+    let result = handler.handle(signup_params()).unwrap();
+    assert_eq!(result.action, mcp_core::types::ElicitAction::Cancel);
+
+    // Input ending before a required field is answered
+    let (handler, output) = console("\nAda\n");
+    let result = handler.handle(signup_params()).unwrap();
+    assert_eq!(result.action, mcp_core::types::ElicitAction::Cancel);
+    assert!(output.text().ends_with("email (required): "));
 }
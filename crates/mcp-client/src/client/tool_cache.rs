@@ -14,10 +14,19 @@ pub struct ToolCache {
 
 impl ToolCache {
     pub fn update(&mut self, tools: &[ToolDefinition]) {
+        self.clear();
+        self.extend(tools);
+    }
+
+    /// Forget every tool, e.g. after notifications/tools/list_changed.
+    pub fn clear(&mut self) {
         self.output_schemas.clear();
         self.known_task_tools.clear();
         self.required_task_tools.clear();
+    }
 
+    /// Add tools from a later tools/list page.
+    pub fn extend(&mut self, tools: &[ToolDefinition]) {
         for tool in tools {
             if let Some(schema) = tool.output_schema.clone() {
                 self.output_schemas.insert(tool.name.clone(), schema);
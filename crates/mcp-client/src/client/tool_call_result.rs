@@ -1,9 +1,13 @@
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
+use mcp_core::types::ContentBlock;
+
 /// Result payload for tools/call.
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
 pub struct ToolCallResult {
+    #[serde(default)]
+    pub content: Vec<ContentBlock>,
     #[serde(rename = "structuredContent", skip_serializing_if = "Option::is_none")]
     pub structured_content: Option<Value>,
     #[serde(rename = "isError", skip_serializing_if = "Option::is_none")]
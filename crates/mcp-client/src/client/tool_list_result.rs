@@ -6,4 +6,6 @@ use crate::client::ToolDefinition;
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
 pub struct ToolListResult {
     pub tools: Vec<ToolDefinition>,
+    #[serde(rename = "nextCursor", skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
 }
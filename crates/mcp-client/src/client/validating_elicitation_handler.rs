@@ -0,0 +1,57 @@
+use std::sync::Arc;
+
+use mcp_core::schema::ValidationError;
+use mcp_core::types::{ElicitAction, ElicitRequestFormParams, ElicitResult};
+
+use crate::client::{BoxedFormElicitationHandler, ElicitationError, FormElicitationHandler};
+
+/// Form elicitation handler checking the wrapped handler's answers against
+/// the request's `requestedSchema` before they reach the server.
+///
+/// Accepted content missing a required field or holding a value its property
+/// does not allow is rejected with [`ElicitationError::InvalidResponse`].
+/// With [`with_coerce`](Self::with_coerce), numeric strings given for number
+/// fields and `true`/`false` given for boolean fields are converted first.
+/// Declined and cancelled responses pass through unchecked.
+pub struct ValidatingFormElicitationHandler {
+    handler: BoxedFormElicitationHandler,
+    coerce: bool,
+}
+
+impl ValidatingFormElicitationHandler {
+    /// Validate the responses of `handler`.
+    pub fn new<H: FormElicitationHandler>(handler: H) -> Self {
+        Self {
+            handler: Arc::new(handler),
+            coerce: false,
+        }
+    }
+
+    /// Convert unambiguous values to their field's type before validating.
+    pub fn with_coerce(mut self, coerce: bool) -> Self {
+        self.coerce = coerce;
+        self
+    }
+}
+
+impl FormElicitationHandler for ValidatingFormElicitationHandler {
+    fn handle(&self, params: ElicitRequestFormParams) -> Result<ElicitResult, ElicitationError> {
+        let schema = params.requested_schema.clone();
+        let mut result = self.handler.handle(params)?;
+        if result.action != ElicitAction::Accept {
+            return Ok(result);
+        }
+
+        let content = result.content.get_or_insert_default();
+        if self.coerce {
+            schema.coerce(content);
+        }
+        match schema.validate(content) {
+            Ok(()) => Ok(result),
+            Err(ValidationError::Failed(problems)) => {
+                Err(ElicitationError::InvalidResponse(problems))
+            }
+            Err(error) => Err(ElicitationError::InvalidResponse(vec![error.to_string()])),
+        }
+    }
+}
@@ -31,6 +31,10 @@ pub struct HttpClientConfig {
     /// Whether to automatically reconnect on connection loss.
     pub auto_reconnect: bool,
 
+    /// Whether to send the last received event ID as `Last-Event-ID` when
+    /// reconnecting, so the server can replay missed events.
+    pub send_last_event_id: bool,
+
     /// OAuth client provider for authentication.
     /// When set, the transport will automatically handle OAuth authentication,
     /// including adding Bearer tokens to requests and refreshing tokens on 401 responses.
@@ -47,6 +51,7 @@ impl std::fmt::Debug for HttpClientConfig {
             .field("reconnect_options", &self.reconnect_options)
             .field("custom_headers", &self.custom_headers)
             .field("auto_reconnect", &self.auto_reconnect)
+            .field("send_last_event_id", &self.send_last_event_id)
             .field("auth_provider", &self.auth_provider.is_some())
             .finish()
     }
@@ -63,6 +68,7 @@ impl HttpClientConfig {
             reconnect_options: ReconnectOptions::default(),
             custom_headers: HashMap::new(),
             auto_reconnect: true,
+            send_last_event_id: true,
             auth_provider: None,
         }
     }
@@ -103,6 +109,12 @@ impl HttpClientConfig {
         self
     }
 
+    /// Set whether to resume SSE streams with `Last-Event-ID`.
+    pub fn send_last_event_id(mut self, enabled: bool) -> Self {
+        self.send_last_event_id = enabled;
+        self
+    }
+
     /// Set the OAuth client provider for authentication.
     pub fn auth_provider<P: OAuthClientProvider + 'static>(mut self, provider: Arc<P>) -> Self {
         self.auth_provider = Some(provider);
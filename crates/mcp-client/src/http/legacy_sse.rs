@@ -23,6 +23,7 @@ use mcp_core::http::{headers, ConnectionState, SseParser};
 use mcp_core::stdio::{serialize_message, JsonRpcMessage};
 
 use super::error::HttpClientError;
+use super::sse_reader::read_sse_line;
 
 type MessageHandler = Arc<dyn Fn(JsonRpcMessage) + Send + Sync>;
 type ErrorHandler = Arc<dyn Fn(HttpClientError) + Send + Sync>;
@@ -325,7 +326,7 @@ fn process_sse_stream(
         }
 
         line.clear();
-        match std::io::BufRead::read_line(&mut buf_reader, &mut line) {
+        match read_sse_line(&mut buf_reader, &mut line) {
             Ok(0) => break, // EOF
             Ok(_) => {
                 parser.append(&line);
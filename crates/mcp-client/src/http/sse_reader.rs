@@ -1,17 +1,47 @@
 //! SSE stream reader for HTTP client transport.
 
-use std::io::{BufRead, BufReader, Read};
+use std::io::{self, BufRead, BufReader, Read};
 
 use mcp_core::http::{SseEvent, SseParser};
 use mcp_core::stdio::JsonRpcMessage;
 
 use super::error::HttpClientError;
 
+/// Read one SSE line, including its ending, and append it to `line`.
+///
+/// Unlike `BufRead::read_line` this also stops at a bare `\r`, so streams
+/// using `\r` line endings are not held up until a `\n` arrives. Returns
+/// the number of bytes read, 0 at the end of the stream.
+pub(crate) fn read_sse_line<R: BufRead>(reader: &mut R, line: &mut String) -> io::Result<usize> {
+    let mut bytes = Vec::new();
+    loop {
+        let available = match reader.fill_buf() {
+            Ok(available) => available,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e),
+        };
+        let (used, done) = match available.iter().position(|b| matches!(b, b'\r' | b'\n')) {
+            Some(end) => (end + 1, true),
+            None => (available.len(), available.is_empty()),
+        };
+        bytes.extend_from_slice(&available[..used]);
+        reader.consume(used);
+        if done {
+            break;
+        }
+    }
+    let text = std::str::from_utf8(&bytes)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+    line.push_str(text);
+    Ok(bytes.len())
+}
+
 /// Reader for SSE event streams.
 pub struct SseReader<R: Read> {
     reader: BufReader<R>,
     parser: SseParser,
     last_event_id: Option<String>,
+    bytes_read: usize,
 }
 
 impl<R: Read> SseReader<R> {
@@ -21,6 +51,7 @@ impl<R: Read> SseReader<R> {
             reader: BufReader::new(reader),
             parser: SseParser::new(),
             last_event_id: None,
+            bytes_read: 0,
         }
     }
 
@@ -30,6 +61,7 @@ impl<R: Read> SseReader<R> {
             reader: BufReader::with_capacity(capacity, reader),
             parser: SseParser::new(),
             last_event_id: None,
+            bytes_read: 0,
         }
     }
 
@@ -38,6 +70,11 @@ impl<R: Read> SseReader<R> {
         self.last_event_id.as_deref()
     }
 
+    /// Number of bytes read from the stream so far.
+    pub fn bytes_read(&self) -> usize {
+        self.bytes_read
+    }
+
     /// Read and parse the next SSE event.
     ///
     /// Returns `Ok(None)` if the stream has ended.
@@ -58,9 +95,10 @@ impl<R: Read> SseReader<R> {
 
             // Read more data from the stream
             let mut line = String::new();
-            match self.reader.read_line(&mut line) {
+            match read_sse_line(&mut self.reader, &mut line) {
                 Ok(0) => return Ok(None), // EOF
-                Ok(_) => {
+                Ok(n) => {
+                    self.bytes_read += n;
                     self.parser.append(&line);
                 }
                 Err(e) => return Err(HttpClientError::Io(e)),
@@ -89,7 +127,31 @@ impl<R: Read> SseReader<R> {
 #[cfg(feature = "tokio")]
 pub mod async_reader {
     use super::*;
-    use tokio::io::{AsyncBufReadExt, AsyncRead, BufReader as TokioBufReader};
+    use tokio::io::{AsyncBufRead, AsyncBufReadExt, AsyncRead, BufReader as TokioBufReader};
+
+    /// Async counterpart of [`read_sse_line`](super::read_sse_line).
+    async fn read_sse_line<R: AsyncBufRead + Unpin>(
+        reader: &mut R,
+        line: &mut String,
+    ) -> io::Result<usize> {
+        let mut bytes = Vec::new();
+        loop {
+            let available = reader.fill_buf().await?;
+            let (used, done) = match available.iter().position(|b| matches!(b, b'\r' | b'\n')) {
+                Some(end) => (end + 1, true),
+                None => (available.len(), available.is_empty()),
+            };
+            bytes.extend_from_slice(&available[..used]);
+            reader.consume(used);
+            if done {
+                break;
+            }
+        }
+        let text = std::str::from_utf8(&bytes)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        line.push_str(text);
+        Ok(bytes.len())
+    }
 
     /// Async reader for SSE event streams.
     #[allow(dead_code)]
@@ -130,7 +192,7 @@ pub mod async_reader {
                 }
 
                 let mut line = String::new();
-                match self.reader.read_line(&mut line).await {
+                match read_sse_line(&mut self.reader, &mut line).await {
                     Ok(0) => return Ok(None),
                     Ok(_) => {
                         self.parser.append(&line);
@@ -229,4 +291,39 @@ mod tests {
         // EOF
         assert!(reader.next_event().unwrap().is_none());
     }
+    #[test]
+    fn test_sse_reader_bom_and_crlf() {
+        let data = concat!(
+            "\u{FEFF}event: session\r\ndata: sess-123\r\n\r\n",
+            ":ping\r\r",
+            "event: message\ndata: {\"jsonrpc\":\"2.0\",\"id\":1,\"result\":null}\r\n\n"
+        );
+        let mut reader = SseReader::new(Cursor::new(data));
+
+        let event = reader.next_event().unwrap().unwrap();
+        assert!(
+            matches!(event, SseEvent::SessionReady { session_id } if session_id.as_str() == "sess-123")
+        );
+        assert!(matches!(reader.next_event().unwrap().unwrap(), SseEvent::Ping));
+        assert!(matches!(reader.next_event().unwrap().unwrap(), SseEvent::Message { .. }));
+        assert!(reader.next_event().unwrap().is_none());
+    }
+
+    /// Fails the test if the reader asks for more input.
+    struct Exhausted;
+
+    impl Read for Exhausted {
+        fn read(&mut self, _buf: &mut [u8]) -> io::Result<usize> {
+            panic!("read past the end of the event");
+        }
+    }
+
+    #[test]
+    fn test_sse_reader_does_not_wait_for_lf() {
+        let data = "event: message\rdata: {\"jsonrpc\":\"2.0\",\"id\":1,\"result\":null}\r\r";
+        let mut reader = SseReader::new(Cursor::new(data).chain(Exhausted));
+
+        let event = reader.next_event().unwrap().unwrap();
+        assert!(matches!(event, SseEvent::Message { .. }));
+    }
 }
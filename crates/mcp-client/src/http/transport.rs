@@ -2,17 +2,21 @@
 
 use std::sync::{Arc, Mutex, RwLock};
 use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
 
 use mcp_core::http::{headers, ConnectionState, SessionId, SseEvent, SseParser};
-use mcp_core::stdio::{serialize_message, JsonRpcMessage};
+use mcp_core::stdio::{
+    deserialize_message, serialize_message, CloseHandler, CloseReason, JsonRpcMessage, Transport,
+    TransportCounters, TransportStats,
+};
 
 use super::config::HttpClientConfig;
 use super::error::HttpClientError;
 use super::reconnect::ReconnectState;
+use super::sse_reader::{read_sse_line, SseReader};
 
 type MessageHandler = Arc<dyn Fn(JsonRpcMessage) + Send + Sync>;
 type ErrorHandler = Arc<dyn Fn(HttpClientError) + Send + Sync>;
-type CloseHandler = Arc<dyn Fn() + Send + Sync>;
 
 #[derive(Default)]
 struct EventHandlers {
@@ -32,6 +36,8 @@ pub struct HttpClientTransport {
     sse_handle: Option<JoinHandle<()>>,
     shutdown: Arc<std::sync::atomic::AtomicBool>,
     last_event_id: Arc<RwLock<Option<String>>>,
+    counters: Arc<TransportCounters>,
+    close_dispatched: Arc<std::sync::atomic::AtomicBool>,
 }
 
 impl HttpClientTransport {
@@ -45,6 +51,8 @@ impl HttpClientTransport {
             sse_handle: None,
             shutdown: Arc::new(std::sync::atomic::AtomicBool::new(false)),
             last_event_id: Arc::new(RwLock::new(None)),
+            counters: Arc::new(TransportCounters::default()),
+            close_dispatched: Arc::new(std::sync::atomic::AtomicBool::new(false)),
         }
     }
 
@@ -74,6 +82,14 @@ impl HttpClientTransport {
 
     /// Register a handler for connection close events.
     pub fn on_close(&mut self, handler: impl Fn() + Send + Sync + 'static) -> &mut Self {
+        self.on_close_with_reason(move |_reason| handler())
+    }
+
+    /// Register a handler that receives the [`CloseReason`] of connection close events.
+    pub fn on_close_with_reason(
+        &mut self,
+        handler: impl Fn(CloseReason) + Send + Sync + 'static,
+    ) -> &mut Self {
         {
             let mut guard = self.handlers.lock().unwrap();
             guard.close = Some(Arc::new(handler));
@@ -91,6 +107,17 @@ impl HttpClientTransport {
         *self.state.read().unwrap()
     }
 
+    /// ID of the last event received on the SSE stream, sent as
+    /// `Last-Event-ID` when reconnecting.
+    pub fn last_event_id(&self) -> Option<String> {
+        self.last_event_id.read().ok()?.clone()
+    }
+
+    /// Messages and bytes exchanged with the server so far.
+    pub fn stats(&self) -> TransportStats {
+        self.counters.snapshot()
+    }
+
     /// Start the transport and establish an SSE connection.
     pub fn start(&mut self) -> Result<(), HttpClientError> {
         if self.state() != ConnectionState::Disconnected {
@@ -100,6 +127,8 @@ impl HttpClientTransport {
         self.set_state(ConnectionState::Connecting);
         self.shutdown
             .store(false, std::sync::atomic::Ordering::SeqCst);
+        self.close_dispatched
+            .store(false, std::sync::atomic::Ordering::SeqCst);
 
         // Spawn SSE reader thread
         let config = self.config.clone();
@@ -108,16 +137,56 @@ impl HttpClientTransport {
         let handlers = Arc::clone(&self.handlers);
         let shutdown = Arc::clone(&self.shutdown);
         let last_event_id = Arc::clone(&self.last_event_id);
+        let counters = Arc::clone(&self.counters);
+        let close_dispatched = Arc::clone(&self.close_dispatched);
 
         let handle = thread::spawn(move || {
-            run_sse_loop(config, session_id, state, handlers, shutdown, last_event_id);
+            let reason = run_sse_loop(
+                config,
+                session_id,
+                state,
+                handlers.clone(),
+                shutdown.clone(),
+                last_event_id,
+                counters,
+            );
+            if !shutdown.load(std::sync::atomic::Ordering::SeqCst) {
+                dispatch_close(&handlers, &close_dispatched, reason);
+            }
         });
 
         self.sse_handle = Some(handle);
+        self.counters.record_connected();
         Ok(())
     }
 
+    /// Block until the SSE connection opened by [`start`](Self::start) is
+    /// up, so that messages can be sent.
+    ///
+    /// Fails with [`HttpClientError::NotConnected`] when the connection
+    /// attempt gives up or `timeout` elapses first.
+    pub fn wait_connected(&self, timeout: Duration) -> Result<(), HttpClientError> {
+        let deadline = Instant::now() + timeout;
+        loop {
+            match self.state() {
+                ConnectionState::Connected => return Ok(()),
+                ConnectionState::Disconnected | ConnectionState::Closed => {
+                    return Err(HttpClientError::NotConnected);
+                }
+                ConnectionState::Connecting | ConnectionState::Reconnecting => {}
+            }
+            if Instant::now() >= deadline {
+                return Err(HttpClientError::NotConnected);
+            }
+            thread::sleep(Duration::from_millis(10));
+        }
+    }
+
     /// Send a JSON-RPC message via HTTP POST.
+    ///
+    /// The server answers a request either with a JSON body or with an SSE
+    /// stream of notifications ending in the result; messages from either
+    /// are passed to the message handler before this returns.
     pub fn send(&self, message: &JsonRpcMessage) -> Result<(), HttpClientError> {
         if self.state() != ConnectionState::Connected {
             return Err(HttpClientError::NotConnected);
@@ -128,13 +197,15 @@ impl HttpClientTransport {
 
         // Build request
         let session_id = self.session_id.read().unwrap();
+        let accept = format!("{}, {}", headers::CONTENT_TYPE_JSON, headers::ACCEPT_SSE);
         let mut request = ureq::post(&url)
             .set("Content-Type", headers::CONTENT_TYPE_JSON)
-            .set("Accept", headers::CONTENT_TYPE_JSON);
+            .set("Accept", &accept);
 
         if let Some(ref sid) = *session_id {
             request = request.set(headers::MCP_SESSION_ID, sid.as_str());
         }
+        drop(session_id);
 
         for (name, value) in &self.config.custom_headers {
             request = request.set(name, value);
@@ -152,6 +223,18 @@ impl HttpClientTransport {
             });
         }
 
+        self.counters.record_sent(payload.len());
+
+        if let Some(sid) = response.header(headers::MCP_SESSION_ID) {
+            *self.session_id.write().unwrap() = Some(SessionId::from_string(sid));
+        }
+
+        let content_type = response.content_type().to_string();
+        let messages = read_post_response(&content_type, response.into_reader())?;
+        for message in messages {
+            self.counters.record_message_received();
+            dispatch_message(&self.handlers, message);
+        }
         Ok(())
     }
 
@@ -175,7 +258,7 @@ impl HttpClientTransport {
         }
 
         // Dispatch close event
-        dispatch_close(&self.handlers);
+        dispatch_close(&self.handlers, &self.close_dispatched, CloseReason::Explicit);
 
         Ok(())
     }
@@ -186,6 +269,37 @@ impl HttpClientTransport {
     }
 }
 
+impl Transport for HttpClientTransport {
+    type Message = JsonRpcMessage;
+    type Error = HttpClientError;
+
+    fn start(&mut self) -> Result<(), Self::Error> {
+        HttpClientTransport::start(self)?;
+        // Sends need the SSE connection, which opens on a background thread
+        self.wait_connected(self.config.request_timeout)
+    }
+
+    fn send(&mut self, message: &Self::Message) -> Result<(), Self::Error> {
+        HttpClientTransport::send(self, message)
+    }
+
+    fn close(&mut self) -> Result<(), Self::Error> {
+        HttpClientTransport::close(self)
+    }
+
+    fn on_message(&mut self, handler: MessageHandler) {
+        HttpClientTransport::on_message(self, move |message| handler(message));
+    }
+
+    fn on_close_with_reason(&mut self, handler: CloseHandler) {
+        HttpClientTransport::on_close_with_reason(self, move |reason| handler(reason));
+    }
+
+    fn stats(&self) -> TransportStats {
+        HttpClientTransport::stats(self)
+    }
+}
+
 impl Drop for HttpClientTransport {
     fn drop(&mut self) {
         let _ = self.close();
@@ -199,7 +313,9 @@ fn run_sse_loop(
     handlers: Arc<Mutex<EventHandlers>>,
     shutdown: Arc<std::sync::atomic::AtomicBool>,
     last_event_id: Arc<RwLock<Option<String>>>,
-) {
+    counters: Arc<TransportCounters>,
+) -> CloseReason {
+    let mut reason = CloseReason::Eof;
     let mut reconnect_state = ReconnectState::new(config.reconnect_options.clone());
 
     loop {
@@ -218,10 +334,21 @@ fn run_sse_loop(
                 }
 
                 // Process SSE events
-                if let Err(e) = process_sse_stream(reader, &handlers, &session_id, &last_event_id, &shutdown)
-                {
-                    dispatch_error(&handlers, e);
-                }
+                reason = match process_sse_stream(
+                    reader,
+                    &handlers,
+                    &session_id,
+                    &last_event_id,
+                    &shutdown,
+                    &counters,
+                ) {
+                    Ok(()) => CloseReason::Eof,
+                    Err(e) => {
+                        let reason = CloseReason::Error(e.to_string());
+                        dispatch_error(&handlers, e);
+                        reason
+                    }
+                };
 
                 // Check if we should reconnect
                 if shutdown.load(std::sync::atomic::Ordering::SeqCst) {
@@ -238,6 +365,7 @@ fn run_sse_loop(
                 }
             }
             Err(e) => {
+                reason = CloseReason::Error(e.to_string());
                 dispatch_error(&handlers, e);
 
                 if !config.auto_reconnect {
@@ -251,6 +379,9 @@ fn run_sse_loop(
                     }
                     None => {
                         // Max retries exceeded
+                        reason = CloseReason::Error(
+                            HttpClientError::ReconnectionExhausted.to_string(),
+                        );
                         dispatch_error(&handlers, HttpClientError::ReconnectionExhausted);
                         break;
                     }
@@ -266,6 +397,8 @@ fn run_sse_loop(
             *s = ConnectionState::Disconnected;
         }
     }
+
+    reason
 }
 
 fn connect_sse(
@@ -282,8 +415,10 @@ fn connect_sse(
         request = request.set(headers::MCP_SESSION_ID, sid.as_str());
     }
 
-    // Add Last-Event-ID for reconnection
-    if let Some(ref id) = *last_event_id.read().unwrap() {
+    // Add Last-Event-ID so the server replays events missed while disconnected
+    if config.send_last_event_id
+        && let Some(ref id) = *last_event_id.read().unwrap()
+    {
         request = request.set(headers::LAST_EVENT_ID, id);
     }
 
@@ -318,48 +453,73 @@ fn process_sse_stream(
     session_id: &Arc<RwLock<Option<SessionId>>>,
     last_event_id: &Arc<RwLock<Option<String>>>,
     shutdown: &Arc<std::sync::atomic::AtomicBool>,
+    counters: &TransportCounters,
 ) -> Result<(), HttpClientError> {
-    let reader = response.into_reader();
-    let mut buf_reader = std::io::BufReader::new(reader);
-    let mut parser = SseParser::new();
-    let mut line = String::new();
+    let mut reader = SseReader::new(response.into_reader());
+    let mut counted = 0;
 
     loop {
         if shutdown.load(std::sync::atomic::Ordering::SeqCst) {
             break;
         }
 
-        line.clear();
-        match std::io::BufRead::read_line(&mut buf_reader, &mut line) {
-            Ok(0) => break, // EOF
-            Ok(_) => {
-                parser.append(&line);
-
-                while let Some(parsed) = parser.next_event() {
-                    // Update last event ID
-                    if let Some(ref id) = parsed.id {
-                        let mut guard = last_event_id.write().unwrap();
-                        *guard = Some(id.clone());
-                    }
+        let next = reader.next_event();
+        counters.record_bytes_received(reader.bytes_read() - counted);
+        counted = reader.bytes_read();
+        let Some(event) = next? else {
+            break; // EOF
+        };
 
-                    // Convert to MCP event
-                    match parsed.to_mcp_event() {
-                        Ok(event) => {
-                            handle_sse_event(event, handlers, session_id);
-                        }
-                        Err(e) => {
-                            return Err(HttpClientError::Sse(e.to_string()));
-                        }
-                    }
-                }
+        // Remember where to resume from
+        if let Some(id) = reader.last_event_id() {
+            *last_event_id.write().unwrap() = Some(id.to_string());
+        }
+
+        if matches!(event, SseEvent::Message { .. }) {
+            counters.record_message_received();
+        }
+        handle_sse_event(event, handlers, session_id);
+    }
+
+    Ok(())
+}
+
+/// Read the messages in the body of a POST response, which is either a
+/// single JSON-RPC message, an SSE stream of them, or empty (202 Accepted).
+fn read_post_response(
+    content_type: &str,
+    reader: impl std::io::Read,
+) -> Result<Vec<JsonRpcMessage>, HttpClientError> {
+    let mut buf_reader = std::io::BufReader::new(reader);
+
+    if content_type.starts_with(headers::CONTENT_TYPE_SSE) {
+        let mut parser = SseParser::new();
+        let mut line = String::new();
+        let mut messages = Vec::new();
+        loop {
+            line.clear();
+            if read_sse_line(&mut buf_reader, &mut line)? == 0 {
+                break;
             }
-            Err(e) => {
-                return Err(HttpClientError::Io(e));
+            parser.append(&line);
+            while let Some(parsed) = parser.next_event() {
+                let event = parsed
+                    .to_mcp_event()
+                    .map_err(|e| HttpClientError::Sse(e.to_string()))?;
+                if let SseEvent::Message { data, .. } = event {
+                    messages.push(data);
+                }
             }
         }
+        return Ok(messages);
     }
 
-    Ok(())
+    let mut body = String::new();
+    std::io::Read::read_to_string(&mut buf_reader, &mut body)?;
+    if body.trim().is_empty() {
+        return Ok(Vec::new());
+    }
+    Ok(vec![deserialize_message(body.trim())?])
 }
 
 fn handle_sse_event(
@@ -400,17 +560,85 @@ fn dispatch_error(handlers: &Arc<Mutex<EventHandlers>>, error: HttpClientError)
     }
 }
 
-fn dispatch_close(handlers: &Arc<Mutex<EventHandlers>>) {
+/// Invoke the close handler at most once per connection.
+fn dispatch_close(
+    handlers: &Arc<Mutex<EventHandlers>>,
+    close_dispatched: &std::sync::atomic::AtomicBool,
+    reason: CloseReason,
+) {
+    if close_dispatched.swap(true, std::sync::atomic::Ordering::SeqCst) {
+        return;
+    }
     let handler = handlers.lock().unwrap().close.clone();
     if let Some(handler) = handler {
-        handler();
+        handler(reason);
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+    use std::sync::mpsc;
+    use std::time::Duration;
+
     use super::*;
 
+    /// Serve an SSE endpoint whose first stream sends an event with id `7`
+    /// and every stream then ends, so the client keeps reconnecting. Sends
+    /// the lowercased headers of each GET request.
+    fn serve_dropped_streams() -> (String, mpsc::Receiver<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base = format!("http://{}", listener.local_addr().unwrap());
+        let (tx, rx) = mpsc::channel();
+        std::thread::spawn(move || {
+            for (i, stream) in listener.incoming().enumerate() {
+                let mut stream = stream.unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let mut request_line = String::new();
+                reader.read_line(&mut request_line).unwrap();
+                let mut headers = Vec::new();
+                loop {
+                    let mut header = String::new();
+                    reader.read_line(&mut header).unwrap();
+                    if header.trim().is_empty() {
+                        break;
+                    }
+                    headers.push(header.trim().to_ascii_lowercase());
+                }
+                if !request_line.starts_with("GET") {
+                    let _ = write!(stream, "HTTP/1.1 200 OK\r\ncontent-length: 0\r\n\r\n");
+                    continue;
+                }
+                let _ = tx.send(headers);
+                let _ = write!(
+                    stream,
+                    "HTTP/1.1 200 OK\r\ncontent-type: text/event-stream\r\nconnection: close\r\n\r\n"
+                );
+                if i == 0 {
+                    let data = r#"{"jsonrpc":"2.0","method":"notifications/message","params":{"level":"info","data":"hi"}}"#;
+                    let _ = write!(stream, "id: 7\nevent: message\ndata: {data}\n\n");
+                }
+            }
+        });
+        (base, rx)
+    }
+
+    /// Headers of the first and second GET of a transport started with
+    /// `config`, and its last event id once reconnected.
+    fn reconnect(
+        config: HttpClientConfig,
+        rx: mpsc::Receiver<Vec<String>>,
+    ) -> (Vec<String>, Vec<String>, Option<String>) {
+        let mut transport = HttpClientTransport::new(config);
+        transport.start().unwrap();
+        let first = rx.recv_timeout(Duration::from_secs(5)).unwrap();
+        let second = rx.recv_timeout(Duration::from_secs(5)).unwrap();
+        let last_event_id = transport.last_event_id();
+        transport.close().unwrap();
+        (first, second, last_event_id)
+    }
+
     #[test]
     fn test_transport_creation() {
         let config = HttpClientConfig::new("http://localhost:8080");
@@ -419,4 +647,64 @@ mod tests {
         assert_eq!(transport.state(), ConnectionState::Disconnected);
         assert!(transport.session_id().is_none());
     }
+
+    #[test]
+    fn test_read_post_response_json() {
+        let body = r#"{"jsonrpc":"2.0","id":1,"result":{}}"#;
+        let messages = read_post_response("application/json", body.as_bytes()).unwrap();
+        assert_eq!(messages.len(), 1);
+        assert!(matches!(messages[0], JsonRpcMessage::Result(_)));
+
+        assert!(read_post_response("application/json", &b""[..]).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_read_post_response_sse() {
+        let body = concat!(
+            "event: message\n",
+            "data: {\"jsonrpc\":\"2.0\",\"method\":\"notifications/progress\",\"params\":{\"progressToken\":\"t\",\"progress\":1}}\n",
+            "\n",
+            "event: message\n",
+            "data: {\"jsonrpc\":\"2.0\",\"id\":1,\"result\":{}}\n",
+            "\n",
+        );
+        let messages = read_post_response("text/event-stream", body.as_bytes()).unwrap();
+        assert_eq!(messages.len(), 2);
+        assert!(matches!(messages[0], JsonRpcMessage::Notification(_)));
+        assert!(matches!(messages[1], JsonRpcMessage::Result(_)));
+    }
+
+    #[test]
+    fn test_explicit_close_reports_reason_once() {
+        let config = HttpClientConfig::new("http://localhost:8080");
+        let mut transport = HttpClientTransport::new(config);
+        let reasons = Arc::new(Mutex::new(Vec::new()));
+        let sink = reasons.clone();
+        transport.on_close_with_reason(move |reason| sink.lock().unwrap().push(reason));
+
+        transport.close().unwrap();
+        drop(transport);
+
+        assert_eq!(*reasons.lock().unwrap(), vec![CloseReason::Explicit]);
+    }
+
+    #[test]
+    fn test_reconnect_resumes_from_last_event_id() {
+        let (base, rx) = serve_dropped_streams();
+        let (first, second, last_event_id) = reconnect(HttpClientConfig::new(base), rx);
+
+        assert!(!first.iter().any(|h| h.starts_with("last-event-id:")));
+        assert!(second.contains(&"last-event-id: 7".to_string()));
+        assert_eq!(last_event_id.as_deref(), Some("7"));
+    }
+
+    #[test]
+    fn test_last_event_id_header_can_be_disabled() {
+        let (base, rx) = serve_dropped_streams();
+        let config = HttpClientConfig::new(base).send_last_event_id(false);
+        let (_, second, last_event_id) = reconnect(config, rx);
+
+        assert!(!second.iter().any(|h| h.starts_with("last-event-id:")));
+        assert_eq!(last_event_id.as_deref(), Some("7"));
+    }
 }
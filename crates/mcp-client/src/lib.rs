@@ -11,7 +11,7 @@ pub use stdio::{
     get_default_environment, serialize_message,
 };
 
-pub use client::{Client, ClientCapabilities, ClientError, ClientOptions};
+pub use client::{Client, ClientCapabilities, ClientError, ClientOptions, ConnectedClient};
 
 pub use http::{
     HttpClientConfig, HttpClientError, HttpClientTransport, LegacySseClientConfig,
@@ -23,7 +23,8 @@ pub use websocket::{WebSocketClientError, WebSocketClientTransport};
 
 pub use auth::{
     auth, discover_authorization_server_metadata, discover_protected_resource_metadata,
-    get_protected_resource_metadata_url, register_client, start_authorization, AuthOptions,
-    AuthResult, InMemoryOAuthClientProvider, InvalidationScope, OAuthClientError,
-    OAuthClientProvider,
+    get_protected_resource_metadata_url, authorization_url, register_client,
+    request_client_credentials_token, start_authorization, AuthOptions, AuthResult,
+    CodeChallengeMethod, FileOAuthClientProvider, GrantType, InMemoryOAuthClientProvider, InvalidationScope,
+    OAuthClientError, OAuthClientProvider, PkceChallenge,
 };
@@ -29,7 +29,7 @@ fn run() -> Result<(), ClientError<StdioClientTransportError>> {
         ClientOptions::new(&config.service_name).with_version("0.1.0"),
     );
 
-    client.connect()?;
+    client.start()?;
 
     println!(
         "Client expects handshake on port {} and will reuse {:?}.",
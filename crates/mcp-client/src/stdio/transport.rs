@@ -1,12 +1,18 @@
 use std::{
     io::{Read, Write},
     process::{Child, ChildStderr, ChildStdout, Command, Stdio},
-    sync::{Arc, Mutex},
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicBool, Ordering},
+    },
     thread::{self, JoinHandle},
     time::{Duration, Instant},
 };
 
-use mcp_core::stdio::{JsonRpcMessage, ReadBuffer, ReadBufferError, Transport, serialize_message};
+use mcp_core::stdio::{
+    CloseHandler, CloseReason, JsonRpcMessage, ReadBuffer, ReadBufferError, Transport,
+    TransportCounters, TransportStats, serialize_message,
+};
 
 use crate::stdio::{
     env::get_default_environment, error::StdioClientTransportError, params::StdioServerParameters,
@@ -14,7 +20,6 @@ use crate::stdio::{
 
 type MessageHandler = Arc<dyn Fn(JsonRpcMessage) + Send + Sync>;
 type ErrorHandler = Arc<dyn Fn(StdioClientTransportError) + Send + Sync>;
-type CloseHandler = Arc<dyn Fn() + Send + Sync>;
 
 #[derive(Default)]
 struct EventHandlers {
@@ -30,6 +35,8 @@ pub struct StdioClientTransport {
     stderr_handle: Option<ChildStderr>,
     reader_handle: Option<JoinHandle<()>>,
     handlers: Arc<Mutex<EventHandlers>>,
+    counters: Arc<TransportCounters>,
+    closing: Arc<AtomicBool>,
 }
 
 impl StdioClientTransport {
@@ -41,6 +48,8 @@ impl StdioClientTransport {
             stderr_handle: None,
             reader_handle: None,
             handlers: Arc::new(Mutex::new(EventHandlers::default())),
+            counters: Arc::new(TransportCounters::default()),
+            closing: Arc::new(AtomicBool::new(false)),
         }
     }
 
@@ -70,6 +79,15 @@ impl StdioClientTransport {
 
     /// Register a handler invoked when the child process closes its stdout.
     pub fn on_close(&mut self, handler: impl Fn() + Send + Sync + 'static) -> &mut Self {
+        self.on_close_with_reason(move |_reason| handler())
+    }
+
+    /// Register a handler invoked with the [`CloseReason`] when the child
+    /// process closes its stdout.
+    pub fn on_close_with_reason(
+        &mut self,
+        handler: impl Fn(CloseReason) + Send + Sync + 'static,
+    ) -> &mut Self {
         {
             let mut guard = self.handlers.lock().unwrap();
             guard.close = Some(Arc::new(handler));
@@ -77,6 +95,11 @@ impl StdioClientTransport {
         self
     }
 
+    /// Messages and bytes exchanged with the child process so far.
+    pub fn stats(&self) -> TransportStats {
+        self.counters.snapshot()
+    }
+
     /// Start the child process and begin listening for messages on stdout.
     pub fn start(&mut self) -> Result<(), StdioClientTransportError> {
         if self.child.is_some() {
@@ -110,11 +133,17 @@ impl StdioClientTransport {
             .ok_or(StdioClientTransportError::NotConnected)?;
         self.stderr_handle = child.stderr.take();
 
-        let handlers = Arc::clone(&self.handlers);
-        let reader_handle = spawn_reader(stdout, handlers);
+        self.closing.store(false, Ordering::SeqCst);
+        let reader_handle = spawn_reader(
+            stdout,
+            Arc::clone(&self.handlers),
+            Arc::clone(&self.counters),
+            Arc::clone(&self.closing),
+        );
 
         self.child = Some(child);
         self.reader_handle = Some(reader_handle);
+        self.counters.record_connected();
         Ok(())
     }
 
@@ -132,12 +161,14 @@ impl StdioClientTransport {
         let payload = serialize_message(message)?;
         stdin.write_all(payload.as_bytes())?;
         stdin.flush()?;
+        self.counters.record_sent(payload.len());
         Ok(())
     }
 
     /// Close the transport and wait for the child to exit.
     pub fn close(&mut self) -> Result<(), StdioClientTransportError> {
         if let Some(mut child) = self.child.take() {
+            self.closing.store(true, Ordering::SeqCst);
             if let Some(mut stdin) = child.stdin.take() {
                 let _ = stdin.flush();
             }
@@ -204,38 +235,66 @@ impl Transport for StdioClientTransport {
     fn close(&mut self) -> Result<(), Self::Error> {
         StdioClientTransport::close(self)
     }
+
+    fn on_message(&mut self, handler: MessageHandler) {
+        StdioClientTransport::on_message(self, move |message| handler(message));
+    }
+
+    fn on_close_with_reason(&mut self, handler: CloseHandler) {
+        StdioClientTransport::on_close_with_reason(self, move |reason| handler(reason));
+    }
+
+    fn stats(&self) -> TransportStats {
+        StdioClientTransport::stats(self)
+    }
 }
 
-fn spawn_reader(stdout: ChildStdout, handlers: Arc<Mutex<EventHandlers>>) -> JoinHandle<()> {
+fn spawn_reader(
+    stdout: ChildStdout,
+    handlers: Arc<Mutex<EventHandlers>>,
+    counters: Arc<TransportCounters>,
+    closing: Arc<AtomicBool>,
+) -> JoinHandle<()> {
     thread::spawn(move || {
         let mut stdout = stdout;
         let mut buffer = ReadBuffer::default();
         let mut temp = [0u8; 4096];
 
-        'outer: loop {
+        let reason = 'outer: loop {
             match stdout.read(&mut temp) {
-                Ok(0) => break,
+                Ok(0) => break CloseReason::Eof,
                 Ok(n) => {
+                    counters.record_bytes_received(n);
                     buffer.append(&temp[..n]);
                     loop {
                         match buffer.read_message() {
-                            Ok(Some(message)) => dispatch_message(&handlers, message),
+                            Ok(Some(message)) => {
+                                counters.record_message_received();
+                                dispatch_message(&handlers, message);
+                            }
                             Ok(None) => break,
                             Err(err) => {
-                                dispatch_error(&handlers, err.into());
-                                break 'outer;
+                                let err: StdioClientTransportError = err.into();
+                                let reason = CloseReason::Error(err.to_string());
+                                dispatch_error(&handlers, err);
+                                break 'outer reason;
                             }
                         }
                     }
                 }
                 Err(err) => {
+                    let reason = CloseReason::Error(err.to_string());
                     dispatch_error(&handlers, StdioClientTransportError::Io(err));
-                    break;
+                    break reason;
                 }
             }
-        }
+        };
 
-        dispatch_close(&handlers);
+        let reason = match reason {
+            CloseReason::Eof if closing.load(Ordering::SeqCst) => CloseReason::Explicit,
+            reason => reason,
+        };
+        dispatch_close(&handlers, reason);
     })
 }
 
@@ -253,10 +312,10 @@ fn dispatch_error(handlers: &Arc<Mutex<EventHandlers>>, error: StdioClientTransp
     }
 }
 
-fn dispatch_close(handlers: &Arc<Mutex<EventHandlers>>) {
+fn dispatch_close(handlers: &Arc<Mutex<EventHandlers>>, reason: CloseReason) {
     let handler = handlers.lock().unwrap().close.clone();
     if let Some(handler) = handler {
-        handler();
+        handler(reason);
     }
 }
 
@@ -2,7 +2,7 @@ use super::*;
 use mcp_core::types::RequestMessage;
 use serde_json::json;
 use std::sync::mpsc::channel;
-use std::time::Duration;
+use std::time::{Duration, SystemTime};
 
 #[cfg(unix)]
 #[test]
@@ -86,3 +86,85 @@ fn reads_multiple_messages() {
 
     transport.close().expect("should close cleanly");
 }
+
+#[cfg(unix)]
+#[test]
+fn stats_count_scripted_conversation() {
+    let params = StdioServerParameters::new("cat");
+    let mut transport = StdioClientTransport::new(params);
+    let (tx, rx) = channel();
+
+    transport.on_message(move |message| {
+        let _ = tx.send(message);
+    });
+    assert_eq!(transport.stats(), TransportStats::default());
+    let before_start = SystemTime::now();
+    transport.start().expect("should start cat");
+    let started = transport.stats();
+    assert_eq!(started.messages_sent, 0);
+    assert_eq!(started.bytes_received, 0);
+    let connected_since = started.connected_since.expect("connected since start");
+    assert!(connected_since >= before_start);
+    assert!(connected_since <= SystemTime::now());
+
+    let messages = vec![
+        JsonRpcMessage::Request(RequestMessage::new("1", "echo", json!({ "text": "one" }))),
+        JsonRpcMessage::Request(RequestMessage::new("2", "echo", json!({ "text": "two" }))),
+        JsonRpcMessage::Request(RequestMessage::new("3", "echo", json!({ "text": "three" }))),
+    ];
+    let mut expected_bytes = 0;
+    for message in &messages {
+        expected_bytes += serialize_message(message).unwrap().len() as u64;
+        transport.send(message).expect("should send to cat");
+    }
+    for _ in 0..messages.len() {
+        rx.recv_timeout(Duration::from_secs(1))
+            .expect("cat should echo");
+    }
+
+    let stats = transport.stats();
+    assert_eq!(stats.messages_sent, 3);
+    assert_eq!(stats.messages_received, 3);
+    assert_eq!(stats.bytes_sent, expected_bytes);
+    assert_eq!(stats.bytes_received, expected_bytes);
+    assert_eq!(stats.connected_since, Some(connected_since));
+    assert!(stats.uptime.expect("uptime") >= started.uptime.expect("uptime"));
+
+    transport.close().expect("should close cleanly");
+}
+
+#[cfg(unix)]
+#[test]
+fn close_reason_is_explicit_after_close() {
+    let params = StdioServerParameters::new("cat");
+    let mut transport = StdioClientTransport::new(params);
+    let (tx, rx) = channel();
+
+    transport.on_close_with_reason(move |reason| {
+        let _ = tx.send(reason);
+    });
+
+    transport.start().expect("should start cat");
+    transport.close().expect("should close cleanly");
+    assert_eq!(rx.try_recv(), Ok(CloseReason::Explicit));
+}
+
+#[cfg(unix)]
+#[test]
+fn close_reason_is_eof_when_child_exits() {
+    let params = StdioServerParameters::new("true");
+    let mut transport = StdioClientTransport::new(params);
+    let (tx, rx) = channel();
+
+    transport.on_close_with_reason(move |reason| {
+        let _ = tx.send(reason);
+    });
+
+    transport.start().expect("should start true");
+    assert_eq!(
+        rx.recv_timeout(Duration::from_secs(1)),
+        Ok(CloseReason::Eof)
+    );
+    transport.close().expect("should close cleanly");
+    assert!(rx.try_recv().is_err());
+}
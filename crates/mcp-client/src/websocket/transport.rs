@@ -1,5 +1,6 @@
 //! WebSocket client transport implementation.
 
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 
 use futures_util::{SinkExt, StreamExt};
@@ -7,7 +8,10 @@ use tokio::sync::{mpsc, Mutex, RwLock};
 use tokio_tungstenite::{connect_async, tungstenite::protocol::Message};
 
 use mcp_core::http::ConnectionState;
-use mcp_core::stdio::{deserialize_message, serialize_message, JsonRpcMessage};
+use mcp_core::stdio::{
+    deserialize_message, serialize_message, CloseHandler, CloseReason, JsonRpcMessage,
+    TransportCounters, TransportStats,
+};
 
 use super::error::WebSocketClientError;
 
@@ -16,7 +20,6 @@ pub const MCP_SUBPROTOCOL: &str = "mcp";
 
 type MessageHandler = Arc<dyn Fn(JsonRpcMessage) + Send + Sync>;
 type ErrorHandler = Arc<dyn Fn(WebSocketClientError) + Send + Sync>;
-type CloseHandler = Arc<dyn Fn() + Send + Sync>;
 
 #[derive(Default)]
 struct EventHandlers {
@@ -54,6 +57,8 @@ pub struct WebSocketClientTransport {
     handlers: Arc<Mutex<EventHandlers>>,
     tx: Arc<RwLock<Option<mpsc::Sender<JsonRpcMessage>>>>,
     shutdown: Arc<RwLock<bool>>,
+    counters: Arc<TransportCounters>,
+    close_dispatched: Arc<AtomicBool>,
 }
 
 impl WebSocketClientTransport {
@@ -65,6 +70,8 @@ impl WebSocketClientTransport {
             handlers: Arc::new(Mutex::new(EventHandlers::default())),
             tx: Arc::new(RwLock::new(None)),
             shutdown: Arc::new(RwLock::new(false)),
+            counters: Arc::new(TransportCounters::default()),
+            close_dispatched: Arc::new(AtomicBool::new(false)),
         }
     }
 
@@ -96,6 +103,14 @@ impl WebSocketClientTransport {
 
     /// Register a handler for connection close events.
     pub fn on_close(&mut self, handler: impl Fn() + Send + Sync + 'static) -> &mut Self {
+        self.on_close_with_reason(move |_reason| handler())
+    }
+
+    /// Register a handler that receives the [`CloseReason`] of connection close events.
+    pub fn on_close_with_reason(
+        &mut self,
+        handler: impl Fn(CloseReason) + Send + Sync + 'static,
+    ) -> &mut Self {
         let handlers = self.handlers.clone();
         tokio::spawn(async move {
             let mut guard = handlers.lock().await;
@@ -109,6 +124,11 @@ impl WebSocketClientTransport {
         *self.state.read().await
     }
 
+    /// Messages and bytes exchanged with the server so far.
+    pub fn stats(&self) -> TransportStats {
+        self.counters.snapshot()
+    }
+
     /// Start the transport and establish WebSocket connection.
     pub async fn start(&mut self) -> Result<(), WebSocketClientError> {
         if *self.state.read().await != ConnectionState::Disconnected {
@@ -117,6 +137,7 @@ impl WebSocketClientTransport {
 
         *self.state.write().await = ConnectionState::Connecting;
         *self.shutdown.write().await = false;
+        self.close_dispatched.store(false, Ordering::SeqCst);
 
         // Build request with subprotocol
         let request = tokio_tungstenite::tungstenite::http::Request::builder()
@@ -139,6 +160,7 @@ impl WebSocketClientTransport {
             .map_err(|e| WebSocketClientError::Connection(e.to_string()))?;
 
         *self.state.write().await = ConnectionState::Connected;
+        self.counters.record_connected();
 
         // Split the WebSocket
         let (ws_sink, ws_stream) = ws_stream.split();
@@ -151,15 +173,18 @@ impl WebSocketClientTransport {
         let handlers = Arc::clone(&self.handlers);
         let state = Arc::clone(&self.state);
         let shutdown = Arc::clone(&self.shutdown);
+        let counters = Arc::clone(&self.counters);
+        let close_dispatched = Arc::clone(&self.close_dispatched);
 
         tokio::spawn(async move {
-            handle_incoming(ws_stream, handlers, state, shutdown).await;
+            handle_incoming(ws_stream, handlers, state, shutdown, counters, close_dispatched).await;
         });
 
         // Spawn write task
         let shutdown_write = Arc::clone(&self.shutdown);
+        let counters = Arc::clone(&self.counters);
         tokio::spawn(async move {
-            handle_outgoing(ws_sink, rx, shutdown_write).await;
+            handle_outgoing(ws_sink, rx, shutdown_write, counters).await;
         });
 
         Ok(())
@@ -192,10 +217,7 @@ impl WebSocketClientTransport {
         *self.tx.write().await = None;
 
         // Dispatch close event
-        let handlers = self.handlers.lock().await;
-        if let Some(ref handler) = handlers.close {
-            handler();
-        }
+        dispatch_close(&self.handlers, &self.close_dispatched, CloseReason::Explicit).await;
 
         Ok(())
     }
@@ -207,9 +229,12 @@ async fn handle_incoming<S>(
     handlers: Arc<Mutex<EventHandlers>>,
     state: Arc<RwLock<ConnectionState>>,
     shutdown: Arc<RwLock<bool>>,
+    counters: Arc<TransportCounters>,
+    close_dispatched: Arc<AtomicBool>,
 ) where
     S: StreamExt<Item = Result<Message, tokio_tungstenite::tungstenite::Error>> + Unpin,
 {
+    let mut reason = CloseReason::Eof;
     while let Some(result) = stream.next().await {
         if *shutdown.read().await {
             break;
@@ -219,8 +244,10 @@ async fn handle_incoming<S>(
             Ok(msg) => {
                 match msg {
                     Message::Text(text) => {
+                        counters.record_bytes_received(text.len());
                         match deserialize_message(&text) {
                             Ok(message) => {
+                                counters.record_message_received();
                                 let guard = handlers.lock().await;
                                 if let Some(ref handler) = guard.message {
                                     handler(message);
@@ -235,9 +262,11 @@ async fn handle_incoming<S>(
                         }
                     }
                     Message::Binary(data) => {
+                        counters.record_bytes_received(data.len());
                         // Try to parse as JSON
                         if let Ok(text) = String::from_utf8(data) {
                             if let Ok(message) = deserialize_message(&text) {
+                                counters.record_message_received();
                                 let guard = handlers.lock().await;
                                 if let Some(ref handler) = guard.message {
                                     handler(message);
@@ -257,6 +286,7 @@ async fn handle_incoming<S>(
                 }
             }
             Err(e) => {
+                reason = CloseReason::Error(e.to_string());
                 let guard = handlers.lock().await;
                 if let Some(ref handler) = guard.error {
                     handler(WebSocketClientError::WebSocket(e.to_string()));
@@ -272,10 +302,24 @@ async fn handle_incoming<S>(
         *s = ConnectionState::Disconnected;
     }
 
+    drop(s);
+
     // Dispatch close event
+    dispatch_close(&handlers, &close_dispatched, reason).await;
+}
+
+/// Invoke the close handler at most once per connection.
+async fn dispatch_close(
+    handlers: &Mutex<EventHandlers>,
+    close_dispatched: &AtomicBool,
+    reason: CloseReason,
+) {
+    if close_dispatched.swap(true, Ordering::SeqCst) {
+        return;
+    }
     let guard = handlers.lock().await;
     if let Some(ref handler) = guard.close {
-        handler();
+        handler(reason);
     }
 }
 
@@ -284,6 +328,7 @@ async fn handle_outgoing<S>(
     mut sink: S,
     mut rx: mpsc::Receiver<JsonRpcMessage>,
     shutdown: Arc<RwLock<bool>>,
+    counters: Arc<TransportCounters>,
 ) where
     S: SinkExt<Message> + Unpin,
     S::Error: std::fmt::Display,
@@ -295,9 +340,11 @@ async fn handle_outgoing<S>(
 
         match serialize_message(&message) {
             Ok(text) => {
+                let bytes = text.len();
                 if sink.send(Message::Text(text.into())).await.is_err() {
                     break;
                 }
+                counters.record_sent(bytes);
             }
             Err(e) => {
                 eprintln!("Serialization error: {}", e);
@@ -7,6 +7,7 @@
 //! - RFC 9728: OAuth 2.0 Protected Resource Metadata
 //! - RFC 7591: OAuth 2.0 Dynamic Client Registration
 //! - RFC 7009: OAuth 2.0 Token Revocation
+//! - RFC 7662: OAuth 2.0 Token Introspection
 //! - RFC 6750: OAuth 2.0 Bearer Token Usage
 
 mod errors;
@@ -22,5 +23,5 @@ pub use errors::{
 pub use types::{
     AuthInfo, AuthorizationParams, OAuthClientInformation, OAuthClientInformationFull,
     OAuthClientMetadata, OAuthErrorResponse, OAuthMetadata, OAuthProtectedResourceMetadata,
-    OAuthTokenRevocationRequest, OAuthTokens,
+    OAuthTokenIntrospectionResponse, OAuthTokenRevocationRequest, OAuthTokens,
 };
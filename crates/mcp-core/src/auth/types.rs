@@ -240,6 +240,98 @@ pub struct OAuthTokenRevocationRequest {
     pub token_type_hint: Option<String>,
 }
 
+/// RFC 7662: OAuth 2.0 Token Introspection - Introspection Response
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct OAuthTokenIntrospectionResponse {
+    /// Whether the token is currently active. Inactive responses carry no
+    /// other members.
+    pub active: bool,
+
+    /// Space-separated scopes associated with the token.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scope: Option<String>,
+
+    /// Client the token was issued to.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub client_id: Option<String>,
+
+    /// Human-readable identifier of the resource owner.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub username: Option<String>,
+
+    /// Type of the token, e.g. `Bearer`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub token_type: Option<String>,
+
+    /// Unix timestamp when the token expires.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub exp: Option<u64>,
+
+    /// Unix timestamp when the token was issued.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub iat: Option<u64>,
+
+    /// Subject of the token, usually the resource owner.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sub: Option<String>,
+
+    /// Intended audience of the token.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub aud: Option<String>,
+
+    /// Issuer of the token.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub iss: Option<String>,
+}
+
+impl OAuthTokenIntrospectionResponse {
+    /// Response for an unknown, expired or revoked token.
+    pub fn inactive() -> Self {
+        Self::default()
+    }
+
+    /// Active response describing `info`. `sub` is taken from a string
+    /// `sub` member of `info.extra`, if present.
+    pub fn from_auth_info(info: &AuthInfo) -> Self {
+        if info.is_expired() {
+            return Self::inactive();
+        }
+        Self {
+            active: true,
+            scope: (!info.scopes.is_empty()).then(|| info.scopes.join(" ")),
+            client_id: info.client_id.clone(),
+            token_type: Some("Bearer".to_string()),
+            exp: info.expires_at,
+            sub: info
+                .extra
+                .as_ref()
+                .and_then(|extra| extra.get("sub"))
+                .and_then(|sub| sub.as_str())
+                .map(str::to_string),
+            ..Self::default()
+        }
+    }
+
+    /// Convert an active response into `AuthInfo` for `token`; `None` when
+    /// the token is inactive.
+    pub fn into_auth_info(self, token: impl Into<String>) -> Option<AuthInfo> {
+        if !self.active {
+            return None;
+        }
+        let extra = self.sub.map(|sub| serde_json::json!({ "sub": sub }));
+        Some(AuthInfo {
+            token: token.into(),
+            client_id: self.client_id,
+            scopes: self
+                .scope
+                .map(|scope| scope.split_whitespace().map(str::to_string).collect())
+                .unwrap_or_default(),
+            expires_at: self.exp,
+            extra,
+        })
+    }
+}
+
 /// OAuth error response.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct OAuthErrorResponse {
@@ -21,6 +21,12 @@ impl SessionId {
     pub fn as_str(&self) -> &str {
         &self.0
     }
+
+    /// Whether `id` is a syntactically valid session ID: non-empty and made
+    /// of visible ASCII characters (0x21 to 0x7E) only.
+    pub fn is_well_formed(id: &str) -> bool {
+        !id.is_empty() && id.bytes().all(|b| (0x21..=0x7e).contains(&b))
+    }
 }
 
 impl Default for SessionId {
@@ -187,6 +193,16 @@ mod tests {
         assert_eq!(id.as_str(), "test-session-123");
     }
 
+    #[test]
+    fn test_session_id_is_well_formed() {
+        assert!(SessionId::is_well_formed(SessionId::new().as_str()));
+        assert!(SessionId::is_well_formed("abc-123_~!"));
+        assert!(!SessionId::is_well_formed(""));
+        assert!(!SessionId::is_well_formed("has space"));
+        assert!(!SessionId::is_well_formed("tab\there"));
+        assert!(!SessionId::is_well_formed("café"));
+    }
+
     #[test]
     fn test_resumption_token_encode_decode() {
         let session_id = SessionId::from_string("test-session");
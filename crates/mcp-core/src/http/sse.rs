@@ -60,6 +60,12 @@ pub struct SseParser {
     current_event: Option<String>,
     current_data: Vec<String>,
     current_id: Option<String>,
+    /// Whether any input arrived yet; a byte order mark is only stripped
+    /// from the start of the stream.
+    started: bool,
+    /// The last line ended with a `\r` at the end of the buffer, so a `\n`
+    /// starting the next chunk belongs to the same line ending.
+    skip_lf: bool,
 }
 
 /// A parsed SSE event with raw fields.
@@ -80,64 +86,90 @@ impl SseParser {
     }
 
     /// Append data to the parser buffer.
+    ///
+    /// A UTF-8 byte order mark at the start of the stream is dropped.
     pub fn append(&mut self, chunk: &str) {
+        let mut chunk = chunk;
+        if !self.started && !chunk.is_empty() {
+            self.started = true;
+            chunk = chunk.strip_prefix('\u{FEFF}').unwrap_or(chunk);
+        }
         self.buffer.push_str(chunk);
     }
 
     /// Try to parse the next complete event from the buffer.
+    ///
+    /// Lines may end with `\r\n`, `\r` or `\n`, as the SSE spec allows.
     pub fn next_event(&mut self) -> Option<ParsedSseEvent> {
-        loop {
-            if let Some(pos) = self.buffer.find('\n') {
-                let line = self.buffer[..pos].to_string();
-                self.buffer.drain(..=pos);
-
-                // Remove optional \r
-                let line = line.trim_end_matches('\r');
-
-                if line.is_empty() {
-                    // Empty line = dispatch event
-                    if !self.current_data.is_empty() || self.current_event.is_some() {
-                        let event = ParsedSseEvent {
-                            event: self.current_event.take(),
-                            data: self.current_data.join("\n"),
-                            id: self.current_id.take(),
-                        };
-                        self.current_data.clear();
-                        return Some(event);
-                    }
-                } else if let Some(stripped) = line.strip_prefix(':') {
-                    // Comment line, ignore (but could be a ping)
-                    if stripped.trim() == "ping" {
-                        return Some(ParsedSseEvent {
-                            event: Some("ping".to_string()),
-                            data: String::new(),
-                            id: None,
-                        });
-                    }
-                } else if let Some(colon_pos) = line.find(':') {
-                    let field = &line[..colon_pos];
-                    let value = line[colon_pos + 1..].trim_start_matches(' ');
-
-                    match field {
-                        "event" => self.current_event = Some(value.to_string()),
-                        "data" => self.current_data.push(value.to_string()),
-                        "id" => self.current_id = Some(value.to_string()),
-                        "retry" => {} // Ignore retry field for now
-                        _ => {}       // Unknown field
-                    }
-                } else {
-                    // Field with no value
-                    match line {
-                        "event" => self.current_event = Some(String::new()),
-                        "data" => self.current_data.push(String::new()),
-                        "id" => self.current_id = Some(String::new()),
-                        _ => {}
-                    }
+        while let Some(line) = self.next_line() {
+            let line = line.as_str();
+            if line.is_empty() {
+                // Empty line = dispatch event
+                if !self.current_data.is_empty() || self.current_event.is_some() {
+                    let event = ParsedSseEvent {
+                        event: self.current_event.take(),
+                        data: self.current_data.join("\n"),
+                        id: self.current_id.take(),
+                    };
+                    self.current_data.clear();
+                    return Some(event);
+                }
+            } else if let Some(stripped) = line.strip_prefix(':') {
+                // Comment line, ignore (but could be a ping)
+                if stripped.trim() == "ping" {
+                    return Some(ParsedSseEvent {
+                        event: Some("ping".to_string()),
+                        data: String::new(),
+                        id: None,
+                    });
+                }
+            } else if let Some(colon_pos) = line.find(':') {
+                let field = &line[..colon_pos];
+                // Only a single leading space is part of the separator
+                let value = &line[colon_pos + 1..];
+                let value = value.strip_prefix(' ').unwrap_or(value);
+
+                match field {
+                    "event" => self.current_event = Some(value.to_string()),
+                    "data" => self.current_data.push(value.to_string()),
+                    "id" => self.current_id = Some(value.to_string()),
+                    "retry" => {} // Ignore retry field for now
+                    _ => {}       // Unknown field
                 }
             } else {
-                return None;
+                // Field with no value
+                match line {
+                    "event" => self.current_event = Some(String::new()),
+                    "data" => self.current_data.push(String::new()),
+                    "id" => self.current_id = Some(String::new()),
+                    _ => {}
+                }
             }
         }
+        None
+    }
+
+    /// Take the next complete line off the buffer, without its ending.
+    fn next_line(&mut self) -> Option<String> {
+        if self.skip_lf && !self.buffer.is_empty() {
+            self.skip_lf = false;
+            if self.buffer.starts_with('\n') {
+                self.buffer.drain(..1);
+            }
+        }
+
+        let end = self.buffer.find(['\r', '\n'])?;
+        let rest = &self.buffer[end..];
+        let ending = if rest.starts_with("\r\n") {
+            2
+        } else {
+            // A `\r` ending the buffer may still be followed by a `\n`
+            self.skip_lf = rest == "\r";
+            1
+        };
+        let line = self.buffer[..end].to_string();
+        self.buffer.drain(..end + ending);
+        Some(line)
     }
 
     /// Clear the parser state.
@@ -146,6 +178,8 @@ impl SseParser {
         self.current_event = None;
         self.current_data.clear();
         self.current_id = None;
+        self.started = false;
+        self.skip_lf = false;
     }
 }
 
@@ -296,4 +330,57 @@ mod tests {
         assert_eq!(event.event, Some("message".to_string()));
         assert_eq!(event.data, "test");
     }
+    #[test]
+    fn test_sse_parser_mixed_line_endings() {
+        let mut parser = SseParser::new();
+        parser.append("event: message\r\ndata: one\rdata: two\ndata: three\r\n\r");
+        parser.append("id: 7\r\rdata: last\n\n");
+
+        let event = parser.next_event().unwrap();
+        assert_eq!(event.event, Some("message".to_string()));
+        assert_eq!(event.data, "one\ntwo\nthree");
+
+        // A block with only an id dispatches nothing; the id carries over
+        let event = parser.next_event().unwrap();
+        assert_eq!(event.id, Some("7".to_string()));
+        assert_eq!(event.data, "last");
+        assert!(parser.next_event().is_none());
+    }
+
+    #[test]
+    fn test_sse_parser_crlf_split_across_chunks() {
+        let mut parser = SseParser::new();
+        parser.append("data: a\r");
+        assert!(parser.next_event().is_none());
+
+        // The `\n` completes the `\r\n` rather than ending an empty line
+        parser.append("\ndata: b\r");
+        assert!(parser.next_event().is_none());
+        parser.append("\n\r\n");
+
+        assert_eq!(parser.next_event().unwrap().data, "a\nb");
+    }
+
+    #[test]
+    fn test_sse_parser_strips_leading_bom() {
+        let mut parser = SseParser::new();
+        parser.append("\u{FEFF}event: session\r\ndata: sess-1\r\n\r\n");
+        parser.append("\u{FEFF}data: kept\n\n");
+
+        let event = parser.next_event().unwrap();
+        assert_eq!(event.event, Some("session".to_string()));
+        assert_eq!(event.data, "sess-1");
+
+        // Later on it is part of the field name, which is ignored
+        assert!(parser.next_event().is_none());
+    }
+
+    #[test]
+    fn test_sse_parser_removes_one_leading_space() {
+        let mut parser = SseParser::new();
+        parser.append("data:  indented\ndata:tight\n\n");
+
+        let event = parser.next_event().unwrap();
+        assert_eq!(event.data, " indented\ntight");
+    }
 }
@@ -9,15 +9,23 @@ pub mod protocol;
 pub mod schema;
 pub mod stdio;
 pub mod types;
+pub mod uri_template;
 
 pub use crate::protocol::{
-    CancellationToken, CapabilityChecker, NotificationContext, NotificationHandler, Protocol,
-    ProtocolError, ProtocolOptions, RequestContext, RequestHandler, RequestOptions, TaskStore,
+    CancellationToken, CapabilityChecker, IdempotencyCache, NotificationContext, NotificationHandler, Peer,
+    PeerNotifyFn, PeerRequestFn, Protocol, ProtocolError, ProtocolOptions, QUEUED_STATUS_MESSAGE, ReplayFilter,
+    RequestContext, RequestHandler, RequestOptions, RequiredCapability, RunningTasks, SessionData, StreamedResult,
+    StreamedResultBuffer, TaskLimiter, TaskPermit, TaskSpawner, TaskStatusChange,
+    TaskStatusListener, TaskStore, required_capability,
+};
+pub use crate::schema::{
+    CustomKeywordValidator, JsonSchemaValidator, SchemaValidator, ValidationError,
 };
-pub use crate::schema::{JsonSchemaValidator, SchemaValidator, ValidationError};
 pub use crate::stdio::{
-    JsonRpcMessage, ReadBuffer, ReadBufferError, deserialize_message, serialize_message,
+    CloseHandler, CloseReason, JsonRpcMessage, ReadBuffer, ReadBufferError, TransportCounters,
+    TransportStats, deserialize_message, serialize_message,
 };
+pub use crate::uri_template::{UriTemplate, UriTemplateError};
 pub use crate::types::{
     // Capabilities
     CapabilityFlag, ClientCapabilities, ClientTasksCapability, ClientTasksElicitationCapabilities,
@@ -90,6 +98,6 @@ pub mod prelude {
         RequestContext, RequestHandler, RequestOptions,
     };
     pub use super::{CoreConfig as Config, Environment};
-    pub use super::{JsonSchemaValidator, SchemaValidator};
+    pub use super::{CustomKeywordValidator, JsonSchemaValidator, SchemaValidator};
     pub use super::{Message, NotificationMessage, RequestMessage, ResultMessage, Role};
 }
@@ -1,42 +1,194 @@
+use std::collections::HashMap;
 use std::sync::{
-    Arc,
+    Arc, Mutex, Weak,
     atomic::{AtomicBool, Ordering},
 };
+use std::task::{Context, Poll, Waker};
 
 use futures::future::poll_fn;
-use futures::task::AtomicWaker;
 
 /// Cooperative cancellation token for request handling.
+///
+/// Tokens form a tree: cancelling a token cancels every token derived from
+/// it with [`child`](Self::child) or [`race`](Self::race), while cancelling
+/// a derived token leaves its parents alone. Clones share one token.
 #[derive(Debug, Clone, Default)]
 pub struct CancellationToken {
-    cancelled: Arc<AtomicBool>,
-    waker: Arc<AtomicWaker>,
+    node: Arc<Node>,
+}
+
+#[derive(Debug, Default)]
+struct Node {
+    cancelled: AtomicBool,
+    state: Mutex<NodeState>,
+}
+
+#[derive(Debug, Default)]
+struct NodeState {
+    /// Wakers of pending `cancelled()` futures, by waiter.
+    waiters: HashMap<u64, Waker>,
+    next_waiter: u64,
+    /// Derived tokens, held weakly so dropped children don't pile up.
+    children: Vec<Weak<Node>>,
 }
 
 impl CancellationToken {
     /// Returns true if cancellation has been requested.
     pub fn is_cancelled(&self) -> bool {
-        self.cancelled.load(Ordering::SeqCst)
+        self.node.cancelled.load(Ordering::SeqCst)
     }
 
-    /// Request cancellation and wake any waiters.
+    /// Request cancellation, wake any waiters and cancel derived tokens.
     pub fn cancel(&self) {
-        self.cancelled.store(true, Ordering::SeqCst);
-        self.waker.wake();
+        let mut pending = vec![Arc::clone(&self.node)];
+        while let Some(node) = pending.pop() {
+            if node.cancelled.swap(true, Ordering::SeqCst) {
+                continue;
+            }
+            let (waiters, children) = {
+                let mut state = node.state.lock().expect("cancellation token");
+                (
+                    std::mem::take(&mut state.waiters),
+                    std::mem::take(&mut state.children),
+                )
+            };
+            waiters.into_values().for_each(Waker::wake);
+            pending.extend(children.iter().filter_map(Weak::upgrade));
+        }
+    }
+
+    /// Create a token cancelled along with this one.
+    ///
+    /// Cancelling the child does not cancel this token, so a handler can
+    /// give each subtask its own child and stop them one at a time.
+    pub fn child(&self) -> CancellationToken {
+        let child = CancellationToken::default();
+        self.adopt(&child);
+        child
+    }
+
+    /// Create a token cancelled as soon as either this token or `other` is.
+    pub fn race(&self, other: &CancellationToken) -> CancellationToken {
+        let token = self.child();
+        other.adopt(&token);
+        token
     }
 
     /// Future that resolves when cancellation is requested.
     pub async fn cancelled(&self) {
-        let cancelled = &self.cancelled;
-        let waker = &self.waker;
-        poll_fn(|cx| {
-            waker.register(cx.waker());
-            if cancelled.load(Ordering::SeqCst) {
-                std::task::Poll::Ready(())
-            } else {
-                std::task::Poll::Pending
+        let mut waiter = Waiter {
+            node: &self.node,
+            key: None,
+        };
+        poll_fn(|cx| waiter.poll(cx)).await
+    }
+
+    /// Cancel `child` when this token is cancelled.
+    fn adopt(&self, child: &CancellationToken) {
+        {
+            let mut state = self.node.state.lock().expect("cancellation token");
+            // Checked under the lock so a concurrent `cancel` either sees
+            // the child or has already set the flag
+            if !self.is_cancelled() {
+                state.children.retain(|child| child.strong_count() > 0);
+                state.children.push(Arc::downgrade(&child.node));
+                return;
             }
-        })
-        .await
+        }
+        child.cancel();
+    }
+}
+
+/// Registration of one `cancelled()` future, removed when it is dropped.
+struct Waiter<'a> {
+    node: &'a Node,
+    key: Option<u64>,
+}
+
+impl Waiter<'_> {
+    fn poll(&mut self, cx: &mut Context<'_>) -> Poll<()> {
+        if self.node.cancelled.load(Ordering::SeqCst) {
+            return Poll::Ready(());
+        }
+        let mut state = self.node.state.lock().expect("cancellation token");
+        // `cancel` sets the flag before taking the waiters
+        if self.node.cancelled.load(Ordering::SeqCst) {
+            return Poll::Ready(());
+        }
+        let key = *self.key.get_or_insert_with(|| {
+            state.next_waiter += 1;
+            state.next_waiter
+        });
+        state.waiters.insert(key, cx.waker().clone());
+        Poll::Pending
+    }
+}
+
+impl Drop for Waiter<'_> {
+    fn drop(&mut self) {
+        let Some(key) = self.key else {
+            return;
+        };
+        if let Ok(mut state) = self.node.state.lock() {
+            state.waiters.remove(&key);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use futures::executor::block_on;
+    use futures::future::{FutureExt, join};
+
+    #[test]
+    fn cancelling_a_parent_cancels_its_children() {
+        let parent = CancellationToken::default();
+        let child = parent.child();
+        let grandchild = child.child();
+
+        parent.cancel();
+        assert!(child.is_cancelled());
+        assert!(grandchild.is_cancelled());
+
+        // Children of a cancelled token start out cancelled
+        assert!(parent.child().is_cancelled());
+    }
+
+    #[test]
+    fn cancelling_a_child_leaves_the_parent() {
+        let parent = CancellationToken::default();
+        let first = parent.child();
+        let second = parent.child();
+
+        first.cancel();
+        assert!(!parent.is_cancelled());
+        assert!(!second.is_cancelled());
+    }
+
+    #[test]
+    fn race_fires_with_either_token() {
+        let a = CancellationToken::default();
+        let b = CancellationToken::default();
+        let either = a.race(&b);
+
+        b.cancel();
+        assert!(either.is_cancelled());
+        assert!(!a.is_cancelled());
+        block_on(either.cancelled());
+    }
+
+    #[test]
+    fn wakes_every_waiter() {
+        let parent = CancellationToken::default();
+        let child = parent.child();
+
+        let waiting = join(parent.cancelled(), child.cancelled());
+        futures::pin_mut!(waiting);
+        assert!(waiting.as_mut().now_or_never().is_none());
+
+        parent.cancel();
+        block_on(waiting);
     }
 }
@@ -0,0 +1,246 @@
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use futures::channel::oneshot;
+use serde_json::Value;
+
+use crate::types::{MessageId, RequestMessage, ResultMessage};
+
+use super::RequestContext;
+
+/// Number of responses kept when `idempotency_window` is set.
+pub const DEFAULT_IDEMPOTENCY_CACHE_CAPACITY: usize = 1000;
+
+/// Requests are told apart by session and id.
+type CacheKey = (Option<String>, MessageId);
+
+/// What must match for a request to count as a retransmission rather than
+/// a new request reusing an id, e.g. from another stateless HTTP client.
+#[derive(PartialEq)]
+struct Fingerprint {
+    method: String,
+    params: Value,
+    token: Option<String>,
+}
+
+enum EntryState {
+    /// The first copy is still running; duplicates wait for its response.
+    InFlight(Vec<oneshot::Sender<ResultMessage>>),
+    Done {
+        response: ResultMessage,
+        finished_at: Instant,
+    },
+}
+
+struct Entry {
+    fingerprint: Fingerprint,
+    state: EntryState,
+    /// Tells the request that created the entry from a later one reusing
+    /// its key.
+    generation: u64,
+    /// Position in `CacheState::recency`.
+    used: u64,
+}
+
+#[derive(Default)]
+struct CacheState {
+    entries: HashMap<CacheKey, Entry>,
+    /// Least recently used first.
+    recency: BTreeMap<u64, CacheKey>,
+    clock: u64,
+}
+
+impl CacheState {
+    fn touch(&mut self, key: &CacheKey) {
+        self.clock += 1;
+        let clock = self.clock;
+        if let Some(entry) = self.entries.get_mut(key) {
+            self.recency.remove(&entry.used);
+            entry.used = clock;
+            self.recency.insert(clock, key.clone());
+        }
+    }
+
+    fn remove(&mut self, key: &CacheKey) -> Option<Entry> {
+        let entry = self.entries.remove(key)?;
+        self.recency.remove(&entry.used);
+        Some(entry)
+    }
+}
+
+/// Outcome of looking a request up in an [`IdempotencyCache`].
+pub enum IdempotencyLookup<'a> {
+    /// Not seen before: run it, then record the response through the guard.
+    Miss(InFlightGuard<'a>),
+    /// A response recorded within the window.
+    Hit(ResultMessage),
+    /// The first copy is still running. The receiver is cancelled if it
+    /// fails, in which case the duplicate should run on its own.
+    Pending(oneshot::Receiver<ResultMessage>),
+}
+
+/// Bounded LRU of responses to recent requests, used to answer
+/// retransmitted requests without running their handler again.
+///
+/// Entries are keyed by session and `MessageId`; a request only matches
+/// when its method, params and access token are the same, so a reused id
+/// is treated as a new request. Only successful responses are recorded.
+pub struct IdempotencyCache {
+    window: Duration,
+    capacity: usize,
+    state: Mutex<CacheState>,
+}
+
+impl IdempotencyCache {
+    /// Create a cache replaying responses for `window` after they were
+    /// produced, holding at most `capacity` requests (at least one).
+    pub fn new(window: Duration, capacity: usize) -> Self {
+        Self {
+            window,
+            capacity: capacity.max(1),
+            state: Mutex::new(CacheState::default()),
+        }
+    }
+
+    /// How long a response is replayed.
+    pub fn window(&self) -> Duration {
+        self.window
+    }
+
+    /// Maximum number of requests remembered.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Number of requests currently remembered.
+    pub fn len(&self) -> usize {
+        self.state.lock().expect("idempotency cache").entries.len()
+    }
+
+    /// Whether no request is remembered.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Look up `request`, registering it as in flight if it is new.
+    pub fn begin(&self, request: &RequestMessage, context: &RequestContext) -> IdempotencyLookup<'_> {
+        let key = (context.session_id.clone(), request.id.clone());
+        let fingerprint = Fingerprint {
+            method: request.method.clone(),
+            params: request.params.clone(),
+            token: context.auth_info.as_ref().map(|auth| auth.token.clone()),
+        };
+
+        let mut state = self.state.lock().expect("idempotency cache");
+        let replay = match state.entries.get_mut(&key) {
+            Some(entry) if entry.fingerprint == fingerprint => match &mut entry.state {
+                EntryState::Done {
+                    response,
+                    finished_at,
+                } if finished_at.elapsed() <= self.window => {
+                    Some(IdempotencyLookup::Hit(response.clone()))
+                }
+                EntryState::Done { .. } => None,
+                EntryState::InFlight(waiters) => {
+                    let (tx, rx) = oneshot::channel();
+                    waiters.push(tx);
+                    Some(IdempotencyLookup::Pending(rx))
+                }
+            },
+            _ => None,
+        };
+        if let Some(replay) = replay {
+            state.touch(&key);
+            return replay;
+        }
+
+        state.remove(&key);
+        while state.entries.len() >= self.capacity {
+            let Some((_, oldest)) = state.recency.pop_first() else {
+                break;
+            };
+            state.entries.remove(&oldest);
+        }
+        state.clock += 1;
+        let generation = state.clock;
+        state.entries.insert(
+            key.clone(),
+            Entry {
+                fingerprint,
+                state: EntryState::InFlight(Vec::new()),
+                generation,
+                used: 0,
+            },
+        );
+        state.touch(&key);
+
+        IdempotencyLookup::Miss(InFlightGuard {
+            cache: self,
+            key: Some(key),
+            generation,
+        })
+    }
+
+    fn finish(&self, key: CacheKey, generation: u64, response: Option<ResultMessage>) {
+        let mut state = self.state.lock().expect("idempotency cache");
+        if state.entries.get(&key).map(|entry| entry.generation) != Some(generation) {
+            // Evicted, or replaced by a request reusing the key
+            return;
+        }
+        let waiters = match response {
+            Some(response) => match state.entries.get_mut(&key) {
+                Some(entry) => {
+                    let done = EntryState::Done {
+                        response: response.clone(),
+                        finished_at: Instant::now(),
+                    };
+                    match std::mem::replace(&mut entry.state, done) {
+                        EntryState::InFlight(waiters) => {
+                            waiters.into_iter().map(|tx| (tx, response.clone())).collect()
+                        }
+                        EntryState::Done { .. } => Vec::new(),
+                    }
+                }
+                None => Vec::new(),
+            },
+            None => {
+                // Dropping the waiters' senders lets them run on their own
+                state.remove(&key);
+                Vec::new()
+            }
+        };
+        drop(state);
+        for (tx, response) in waiters {
+            let _ = tx.send(response);
+        }
+    }
+}
+
+/// Marks a request as in flight until its response is recorded.
+///
+/// Dropping the guard without [`finish`](Self::finish), e.g. because the
+/// request failed or its future was dropped, forgets the request so a
+/// retransmission runs again.
+pub struct InFlightGuard<'a> {
+    cache: &'a IdempotencyCache,
+    key: Option<CacheKey>,
+    generation: u64,
+}
+
+impl InFlightGuard<'_> {
+    /// Record the response and hand it to duplicates waiting for it.
+    pub fn finish(mut self, response: &ResultMessage) {
+        if let Some(key) = self.key.take() {
+            self.cache.finish(key, self.generation, Some(response.clone()));
+        }
+    }
+}
+
+impl Drop for InFlightGuard<'_> {
+    fn drop(&mut self) {
+        if let Some(key) = self.key.take() {
+            self.cache.finish(key, self.generation, None);
+        }
+    }
+}
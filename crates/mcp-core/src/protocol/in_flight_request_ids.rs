@@ -0,0 +1,48 @@
+use std::collections::HashSet;
+use std::sync::Mutex;
+
+use crate::types::MessageId;
+
+/// Requests are told apart by session and id.
+type RequestKey = (Option<String>, MessageId);
+
+/// Ids of the requests each session has in flight.
+///
+/// JSON-RPC forbids reusing the id of an unanswered request; a second
+/// request with it could not be told apart from the first by its response.
+#[derive(Default)]
+pub(crate) struct InFlightRequestIds {
+    ids: Mutex<HashSet<RequestKey>>,
+}
+
+impl InFlightRequestIds {
+    /// Mark `id` as in flight for `session_id`, or `None` if it already is.
+    pub(crate) fn claim(
+        &self,
+        session_id: Option<String>,
+        id: &MessageId,
+    ) -> Option<RequestIdClaim<'_>> {
+        let key = (session_id, id.clone());
+        let mut ids = self.ids.lock().expect("in-flight request ids");
+        if !ids.insert(key.clone()) {
+            return None;
+        }
+        Some(RequestIdClaim { ids: self, key })
+    }
+}
+
+/// Frees its request id when dropped.
+pub(crate) struct RequestIdClaim<'a> {
+    ids: &'a InFlightRequestIds,
+    key: RequestKey,
+}
+
+impl Drop for RequestIdClaim<'_> {
+    fn drop(&mut self) {
+        self.ids
+            .ids
+            .lock()
+            .expect("in-flight request ids")
+            .remove(&self.key);
+    }
+}
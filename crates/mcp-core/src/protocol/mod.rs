@@ -1,23 +1,59 @@
 pub mod cancellation_token;
 pub mod capability_checker;
+pub mod idempotency_cache;
+mod in_flight_request_ids;
 pub mod notification_context;
 pub mod notification_handler;
+pub mod peer;
+pub mod peer_notify_fn;
+pub mod peer_request_fn;
 pub mod protocol;
 pub mod protocol_error;
 pub mod protocol_options;
+pub mod replay_filter;
 pub mod request_context;
 pub mod request_handler;
 pub mod request_options;
+pub mod required_capability;
+pub mod running_tasks;
+pub mod session_data;
+pub mod streamed_result;
+pub mod streamed_result_buffer;
+pub mod streamed_result_slot;
+pub mod task_limiter;
+pub mod task_permit;
+pub mod task_spawner;
+pub mod task_status_change;
+pub mod task_status_listener;
 pub mod task_store;
 
 pub use cancellation_token::CancellationToken;
 pub use capability_checker::CapabilityChecker;
+pub use idempotency_cache::{
+    DEFAULT_IDEMPOTENCY_CACHE_CAPACITY, IdempotencyCache, IdempotencyLookup, InFlightGuard,
+};
+pub(crate) use in_flight_request_ids::InFlightRequestIds;
 pub use notification_context::NotificationContext;
 pub use notification_handler::NotificationHandler;
-pub use protocol::Protocol;
+pub use peer::Peer;
+pub use peer_notify_fn::PeerNotifyFn;
+pub use peer_request_fn::PeerRequestFn;
+pub use protocol::{Protocol, QUEUED_STATUS_MESSAGE};
 pub use protocol_error::ProtocolError;
-pub use protocol_options::ProtocolOptions;
+pub use protocol_options::{DEFAULT_TASK_CANCEL_GRACE_PERIOD, ProtocolOptions};
+pub use replay_filter::ReplayFilter;
 pub use request_context::RequestContext;
 pub use request_handler::RequestHandler;
 pub use request_options::RequestOptions;
+pub use required_capability::{RequiredCapability, required_capability};
+pub use running_tasks::RunningTasks;
+pub use session_data::SessionData;
+pub use streamed_result::{DEFAULT_MAX_STREAMED_RESULT_BYTES, StreamedResult};
+pub use streamed_result_buffer::StreamedResultBuffer;
+pub use streamed_result_slot::StreamedResultSlot;
+pub use task_limiter::TaskLimiter;
+pub use task_permit::TaskPermit;
+pub use task_spawner::TaskSpawner;
+pub use task_status_change::TaskStatusChange;
+pub use task_status_listener::TaskStatusListener;
 pub use task_store::TaskStore;
@@ -0,0 +1,51 @@
+use std::fmt;
+
+use serde_json::Value;
+
+use super::{PeerNotifyFn, PeerRequestFn, ProtocolError};
+
+/// Handle for sending requests back to the peer while a request is handled.
+#[derive(Clone)]
+pub struct Peer {
+    send: PeerRequestFn,
+    notify: Option<PeerNotifyFn>,
+}
+
+impl Peer {
+    pub fn new(send: PeerRequestFn) -> Self {
+        Self { send, notify: None }
+    }
+
+    /// Also deliver notifications through `notify`.
+    pub fn with_notifier(mut self, notify: PeerNotifyFn) -> Self {
+        self.notify = Some(notify);
+        self
+    }
+
+    /// Send `method` with `params` to the peer and wait for its result.
+    pub async fn request(
+        &self,
+        method: impl Into<String>,
+        params: Value,
+    ) -> Result<Value, ProtocolError> {
+        (self.send)(method.into(), params).await
+    }
+
+    /// Send a notification to the peer.
+    ///
+    /// Fails with a capability error when the transport cannot deliver
+    /// notifications while a request is handled.
+    pub fn notify(&self, method: impl Into<String>, params: Value) -> Result<(), ProtocolError> {
+        let notify = self.notify.as_ref().ok_or_else(|| {
+            ProtocolError::Capability("transport does not support notifications".to_string())
+        })?;
+        notify(method.into(), params);
+        Ok(())
+    }
+}
+
+impl fmt::Debug for Peer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Peer").finish_non_exhaustive()
+    }
+}
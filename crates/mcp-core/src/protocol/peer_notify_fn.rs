@@ -0,0 +1,6 @@
+use std::sync::Arc;
+
+use serde_json::Value;
+
+/// Sends a notification (method and params) to the connected peer.
+pub type PeerNotifyFn = Arc<dyn Fn(String, Value) + Send + Sync>;
@@ -0,0 +1,11 @@
+use std::sync::Arc;
+
+use futures::future::BoxFuture;
+use serde_json::Value;
+
+use super::ProtocolError;
+
+/// Sends a request (method and params) to the connected peer and resolves
+/// with the `result` of its response.
+pub type PeerRequestFn =
+    Arc<dyn Fn(String, Value) -> BoxFuture<'static, Result<Value, ProtocolError>> + Send + Sync>;
@@ -1,5 +1,7 @@
-use std::{collections::HashMap, sync::Arc};
+use std::{collections::HashMap, sync::Arc, time::Duration};
 
+use futures::channel::oneshot;
+use futures::future::{AbortHandle, Abortable};
 use futures::{FutureExt, select};
 use serde_json::Value;
 
@@ -10,10 +12,15 @@ use crate::types::{
 };
 
 use super::{
-    CapabilityChecker, NotificationContext, NotificationHandler, ProtocolError, ProtocolOptions,
-    RequestContext, RequestHandler, TaskStore,
+    CancellationToken, CapabilityChecker, DEFAULT_IDEMPOTENCY_CACHE_CAPACITY,
+    DEFAULT_TASK_CANCEL_GRACE_PERIOD, IdempotencyCache, IdempotencyLookup, InFlightRequestIds,
+    NotificationContext, NotificationHandler, ProtocolError, ProtocolOptions, ReplayFilter,
+    RequestContext, RequestHandler, RunningTasks, TaskLimiter, TaskPermit, TaskStore,
 };
 
+/// `statusMessage` of a task waiting for a concurrency slot.
+pub const QUEUED_STATUS_MESSAGE: &str = "queued";
+
 struct RequestHandlerRegistration<S> {
     handler: Arc<dyn RequestHandler>,
     schema: S,
@@ -30,6 +37,10 @@ pub struct Protocol<V: SchemaValidator = crate::schema::JsonSchemaValidator> {
     options: ProtocolOptions,
     request_handlers: HashMap<String, RequestHandlerRegistration<V::Schema>>,
     notification_handlers: HashMap<String, NotificationHandlerRegistration<V::Schema>>,
+    running_tasks: Arc<RunningTasks>,
+    task_limiter: Option<Arc<TaskLimiter>>,
+    idempotency_cache: Option<IdempotencyCache>,
+    request_ids: InFlightRequestIds,
 }
 
 impl<V: SchemaValidator> Protocol<V> {
@@ -40,19 +51,72 @@ impl<V: SchemaValidator> Protocol<V> {
 
     /// Create a new protocol runtime with explicit options.
     pub fn with_options(validator: V, options: ProtocolOptions) -> Self {
+        let task_limiter = options
+            .max_concurrent_tasks
+            .map(|limit| Arc::new(TaskLimiter::new(limit)));
+        let idempotency_cache = options.idempotency_window.map(|window| {
+            let capacity = options
+                .idempotency_capacity
+                .unwrap_or(DEFAULT_IDEMPOTENCY_CACHE_CAPACITY);
+            IdempotencyCache::new(window, capacity)
+        });
         Self {
             validator,
             options,
+            task_limiter,
+            idempotency_cache,
+            request_ids: InFlightRequestIds::default(),
             request_handlers: HashMap::new(),
             notification_handlers: HashMap::new(),
+            running_tasks: Arc::new(RunningTasks::default()),
         }
     }
 
+    /// Task-augmented requests currently executing in the background.
+    pub fn running_tasks(&self) -> Arc<RunningTasks> {
+        Arc::clone(&self.running_tasks)
+    }
+
+    /// Limiter bounding concurrent task execution, if `max_concurrent_tasks` is set.
+    pub fn task_limiter(&self) -> Option<Arc<TaskLimiter>> {
+        self.task_limiter.clone()
+    }
+
+    /// Number of tasks waiting for a concurrency slot.
+    pub fn task_queue_depth(&self) -> usize {
+        self.task_limiter
+            .as_ref()
+            .map_or(0, |limiter| limiter.queue_depth())
+    }
+
+    /// Grace period between tripping a cancelled task's token and aborting it.
+    pub fn task_cancel_grace_period(&self) -> Duration {
+        self.options
+            .task_cancel_grace_period
+            .unwrap_or(DEFAULT_TASK_CANCEL_GRACE_PERIOD)
+    }
+
     /// Override the capability checker.
     pub fn set_capability_checker(&mut self, checker: Option<Arc<dyn CapabilityChecker>>) {
         self.options.capability_checker = checker;
     }
 
+    /// Responses remembered for retransmitted requests, if
+    /// `idempotency_window` is set.
+    pub fn idempotency_cache(&self) -> Option<&IdempotencyCache> {
+        self.idempotency_cache.as_ref()
+    }
+
+    /// Filter deciding which requests may be answered from the idempotency cache.
+    pub fn replay_filter(&self) -> Option<ReplayFilter> {
+        self.options.replay_filter.clone()
+    }
+
+    /// Override which requests may be answered from the idempotency cache.
+    pub fn set_replay_filter(&mut self, filter: Option<ReplayFilter>) {
+        self.options.replay_filter = filter;
+    }
+
     /// Configure a task store for task-augmented requests.
     pub fn set_task_store(&mut self, store: Option<Arc<dyn TaskStore>>) {
         self.options.task_store = store;
@@ -124,20 +188,81 @@ impl<V: SchemaValidator> Protocol<V> {
     }
 
     /// Handle a request with explicit context options.
+    ///
+    /// With an `idempotency_window`, a retransmission of a request that
+    /// succeeded is answered with the recorded response, and one arriving
+    /// while the first copy still runs waits for its response. Any other
+    /// request reusing the id of one still in progress in the same session
+    /// fails with [`ProtocolError::DuplicateRequestId`], and a request with a
+    /// null id with [`ProtocolError::NullRequestId`].
     pub async fn handle_request_with_context(
+        &self,
+        request: RequestMessage,
+        context: RequestContext,
+    ) -> Result<ResultMessage, ProtocolError> {
+        if request.id.is_null() {
+            return Err(ProtocolError::NullRequestId);
+        }
+        let replayable = self
+            .options
+            .replay_filter
+            .as_ref()
+            .is_none_or(|filter| filter(&request));
+        let Some(cache) = self.idempotency_cache.as_ref().filter(|_| replayable) else {
+            return self.dispatch_unique_request(request, context).await;
+        };
+
+        match cache.begin(&request, &context) {
+            IdempotencyLookup::Hit(response) => Ok(response),
+            IdempotencyLookup::Pending(response) => match response.await {
+                Ok(response) => Ok(response),
+                // The first copy failed; retry like any new request
+                Err(_) => self.dispatch_unique_request(request, context).await,
+            },
+            IdempotencyLookup::Miss(guard) => {
+                let result = self.dispatch_unique_request(request, context).await;
+                if let Ok(response) = &result {
+                    guard.finish(response);
+                }
+                result
+            }
+        }
+    }
+
+    /// Dispatch `request`, keeping its id reserved until it is answered.
+    async fn dispatch_unique_request(
+        &self,
+        request: RequestMessage,
+        context: RequestContext,
+    ) -> Result<ResultMessage, ProtocolError> {
+        let _claim = if context.stateless {
+            None
+        } else {
+            let claim = self
+                .request_ids
+                .claim(context.session_id.clone(), &request.id)
+                .ok_or_else(|| ProtocolError::DuplicateRequestId(request.id.clone()))?;
+            Some(claim)
+        };
+        self.dispatch_request(request, context).await
+    }
+
+    async fn dispatch_request(
         &self,
         request: RequestMessage,
         mut context: RequestContext,
     ) -> Result<ResultMessage, ProtocolError> {
+        // Checked first: a disabled capability usually has no handler, and
+        // naming the capability beats reporting an unknown method
+        if let Some(checker) = self.options.capability_checker.as_ref() {
+            checker.assert_request(&request.method)?;
+        }
+
         let entry = self
             .request_handlers
             .get(&request.method)
             .ok_or_else(|| ProtocolError::UnknownMethod(request.method.clone()))?;
 
-        if let Some(checker) = self.options.capability_checker.as_ref() {
-            checker.assert_request(&request.method)?;
-        }
-
         self.validator.validate(&entry.schema, &request.params)?;
 
         context.meta = context.meta.or_else(|| extract_meta(&request.params));
@@ -149,19 +274,89 @@ impl<V: SchemaValidator> Protocol<V> {
                 .task_store
                 .as_ref()
                 .ok_or(ProtocolError::TaskUnsupported)?;
-            let task_state = store
-                .create_task(task, request.id.clone(), request.clone())
+            context.streamed_result = Some(Default::default());
+            let mut task_state = store
+                .create_task(
+                    task,
+                    request.id.clone(),
+                    request.clone(),
+                    context.session_id.clone(),
+                )
                 .await?;
-            let result = run_with_options(entry.handler.as_ref(), &request, &context).await;
-            let store_result = match result {
-                Ok(value) => store.set_task_result(&task_state.task_id, Ok(value)).await,
-                Err(err) => {
-                    let error =
-                        ErrorObject::new(ErrorCode::InternalError as i32, err.to_string(), None);
-                    store.set_task_result(&task_state.task_id, Err(error)).await
+
+            let permit = self
+                .task_limiter
+                .as_ref()
+                .map(|limiter| limiter.acquire(&task_state.task_id));
+            if let Some(Err(_)) = &permit {
+                store
+                    .set_status_message(
+                        &task_state.task_id,
+                        Some(QUEUED_STATUS_MESSAGE.to_string()),
+                    )
+                    .await?;
+                task_state.status_message = Some(QUEUED_STATUS_MESSAGE.to_string());
+            }
+
+            match self.options.task_spawner.as_ref() {
+                Some(spawner) => {
+                    let cancel_token = context
+                        .options
+                        .cancel_token
+                        .get_or_insert_with(CancellationToken::default)
+                        .clone();
+                    let (abort_handle, abort_registration) = AbortHandle::new_pair();
+                    let (finished_tx, finished_rx) = oneshot::channel::<()>();
+                    let task_id = task_state.task_id.clone();
+                    self.running_tasks.insert(
+                        task_id.clone(),
+                        cancel_token,
+                        abort_handle,
+                        finished_rx,
+                    );
+
+                    let handler = Arc::clone(&entry.handler);
+                    let store = Arc::clone(store);
+                    let running_tasks = Arc::clone(&self.running_tasks);
+                    let task_request = request.clone();
+                    let task_context = context.clone();
+                    let execution = {
+                        let task_id = task_id.clone();
+                        async move {
+                            let Some(_permit) =
+                                wait_for_permit(store.as_ref(), &task_id, permit).await
+                            else {
+                                return;
+                            };
+                            let result =
+                                run_until_aborted(handler.as_ref(), &task_request, &task_context)
+                                    .await;
+                            let _ = record_task_result(
+                                store.as_ref(),
+                                &task_id,
+                                result,
+                                &task_context,
+                            )
+                            .await;
+                        }
+                    };
+                    spawner(Box::pin(async move {
+                        let _ = Abortable::new(execution, abort_registration).await;
+                        running_tasks.remove(&task_id);
+                        drop(finished_tx);
+                    }));
                 }
-            };
-            store_result?;
+                None => {
+                    let Some(_permit) =
+                        wait_for_permit(store.as_ref(), &task_state.task_id, permit).await
+                    else {
+                        return Err(ProtocolError::Cancelled);
+                    };
+                    let result = run_with_options(entry.handler.as_ref(), &request, &context).await;
+                    record_task_result(store.as_ref(), &task_state.task_id, result, &context)
+                        .await?;
+                }
+            }
 
             let response = CreateTaskResult {
                 task: task_state,
@@ -224,6 +419,77 @@ fn extract_task(params: &Value) -> Option<TaskMetadata> {
     serde_json::from_value(task).ok()
 }
 
+/// Wait for a queued task's concurrency slot and clear its queued status
+/// message. Returns `None` if the task was removed from the queue; the inner
+/// permit is `None` when no limit is configured.
+async fn wait_for_permit(
+    store: &dyn TaskStore,
+    task_id: &str,
+    permit: Option<Result<TaskPermit, oneshot::Receiver<TaskPermit>>>,
+) -> Option<Option<TaskPermit>> {
+    match permit {
+        None => Some(None),
+        Some(Ok(permit)) => Some(Some(permit)),
+        Some(Err(queued)) => {
+            let permit = queued.await.ok()?;
+            let _ = store.set_status_message(task_id, None).await;
+            Some(Some(permit))
+        }
+    }
+}
+
+/// Store a finished task's outcome. A result streamed through the context
+/// replaces the returned value; if writing it fails, the task fails.
+async fn record_task_result(
+    store: &dyn TaskStore,
+    task_id: &str,
+    result: Result<Value, ProtocolError>,
+    context: &RequestContext,
+) -> Result<(), ProtocolError> {
+    let streamed = context
+        .streamed_result
+        .as_ref()
+        .and_then(|slot| slot.lock().expect("streamed result slot").take());
+    let result = match (result, streamed) {
+        (Ok(_), Some(streamed)) => match store.set_task_result_streamed(task_id, streamed).await {
+            Ok(()) => return Ok(()),
+            Err(err) => Err(err),
+        },
+        (result, _) => result,
+    };
+    match result {
+        Ok(value) => store.set_task_result(task_id, Ok(value)).await,
+        Err(err) => {
+            let error = ErrorObject::new(ErrorCode::InternalError as i32, err.to_string(), None);
+            store.set_task_result(task_id, Err(error)).await
+        }
+    }
+}
+
+/// Run a detached task handler. Cancellation is observed by the handler
+/// through its context token and enforced by the abort handle, so only the
+/// timeout is raced here.
+async fn run_until_aborted(
+    handler: &dyn RequestHandler,
+    request: &RequestMessage,
+    context: &RequestContext,
+) -> Result<Value, ProtocolError> {
+    let fut = handler.handle(request, context).fuse();
+    futures::pin_mut!(fut);
+
+    match context.options.timeout {
+        Some(timeout) => {
+            let delay = futures_timer::Delay::new(timeout).fuse();
+            futures::pin_mut!(delay);
+            select! {
+                result = fut => result,
+                _ = delay => Err(ProtocolError::Timeout),
+            }
+        }
+        None => fut.await,
+    }
+}
+
 async fn run_with_options(
     handler: &dyn RequestHandler,
     request: &RequestMessage,
@@ -1,6 +1,8 @@
 use thiserror::Error;
 
+use crate::auth::InsufficientScopeError;
 use crate::schema::ValidationError;
+use crate::types::MessageId;
 
 /// Errors that can occur inside the protocol runtime.
 #[derive(Debug, Error)]
@@ -17,12 +19,30 @@ pub enum ProtocolError {
     #[error("capability check failed: {0}")]
     Capability(String),
 
+    /// The receiver did not advertise the capability a request needs,
+    /// named by its path, e.g. `prompts` or `resources.subscribe`.
+    #[error("{0} capability not enabled")]
+    CapabilityNotEnabled(String),
+
     #[error("task support is not available")]
     TaskUnsupported,
 
+    /// A request reused the id of one from the same session that is still
+    /// being handled.
+    #[error("request id {0} is already in use by a request in progress")]
+    DuplicateRequestId(MessageId),
+
+    /// A request carried `"id": null`, which JSON-RPC only allows on
+    /// responses.
+    #[error("request id must not be null")]
+    NullRequestId,
+
     #[error(transparent)]
     Validation(#[from] ValidationError),
 
+    #[error(transparent)]
+    InsufficientScope(#[from] InsufficientScopeError),
+
     #[error("handler failed: {0}")]
     Handler(String),
 
@@ -1,6 +1,10 @@
 use std::sync::Arc;
+use std::time::Duration;
 
-use super::{CapabilityChecker, TaskStore};
+use super::{CapabilityChecker, ReplayFilter, TaskSpawner, TaskStore};
+
+/// Grace period granted to a cancelled task before its future is aborted.
+pub const DEFAULT_TASK_CANCEL_GRACE_PERIOD: Duration = Duration::from_secs(1);
 
 /// Configuration for the protocol runtime.
 #[derive(Clone, Default)]
@@ -8,4 +12,25 @@ pub struct ProtocolOptions {
     pub enforce_strict_capabilities: bool,
     pub capability_checker: Option<Arc<dyn CapabilityChecker>>,
     pub task_store: Option<Arc<dyn TaskStore>>,
+    /// Runs task-augmented requests in the background. Without a spawner,
+    /// tasks run to completion before `CreateTaskResult` is returned.
+    pub task_spawner: Option<TaskSpawner>,
+    /// How long a cancelled task may keep running after its cancellation
+    /// token is tripped. Defaults to [`DEFAULT_TASK_CANCEL_GRACE_PERIOD`].
+    pub task_cancel_grace_period: Option<Duration>,
+    /// Maximum number of task-augmented requests executing at once. Excess
+    /// tasks stay `working` with status message `"queued"` until a slot frees.
+    pub max_concurrent_tasks: Option<usize>,
+    /// Replay the response to a request retransmitted with the same id
+    /// within this long of the first response, instead of running the
+    /// handler again. The last `idempotency_capacity` requests are
+    /// remembered. Off when `None`.
+    pub idempotency_window: Option<Duration>,
+    /// How many requests the idempotency cache remembers; defaults to
+    /// [`DEFAULT_IDEMPOTENCY_CACHE_CAPACITY`].
+    ///
+    /// [`DEFAULT_IDEMPOTENCY_CACHE_CAPACITY`]: super::DEFAULT_IDEMPOTENCY_CACHE_CAPACITY
+    pub idempotency_capacity: Option<usize>,
+    /// Excludes requests from replay; see [`ReplayFilter`].
+    pub replay_filter: Option<ReplayFilter>,
 }
@@ -0,0 +1,8 @@
+use std::sync::Arc;
+
+use crate::types::RequestMessage;
+
+/// Decides whether a request may be answered from the idempotency cache.
+///
+/// Requests it rejects always run their handler, even when retransmitted.
+pub type ReplayFilter = Arc<dyn Fn(&RequestMessage) -> bool + Send + Sync>;
@@ -1,6 +1,17 @@
-use crate::types::{RequestMeta, TaskMetadata};
+use crate::types::{
+    CreateMessageRequestParams, CreateMessageResult, ElicitAction, ElicitRequestFormParams,
+    ElicitResult,
+    ElicitationSchema, NotificationParams, Progress, ProgressNotificationParams, RequestMeta,
+    TaskMetadata,
+};
 
-use super::RequestOptions;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::auth::AuthInfo;
+use crate::schema::ValidationError;
+
+use super::{Peer, ProtocolError, RequestOptions, SessionData, StreamedResult, StreamedResultSlot};
 
 /// Context passed to request handlers.
 #[derive(Debug, Clone, Default)]
@@ -9,4 +20,141 @@ pub struct RequestContext {
     pub options: RequestOptions,
     pub meta: Option<RequestMeta>,
     pub task: Option<TaskMetadata>,
+    /// Connection back to the peer that sent the request, if the transport supports it.
+    pub peer: Option<Peer>,
+    /// Per-session storage attached by the transport.
+    pub session_data: Option<Arc<SessionData>>,
+    /// Set by the runtime for task-augmented requests.
+    pub streamed_result: Option<StreamedResultSlot>,
+    /// Access token the transport verified for this request, if any.
+    pub auth_info: Option<AuthInfo>,
+    /// Set by transports whose requests each stand alone, such as stateless
+    /// HTTP. Their ids need not differ from those of concurrent requests.
+    pub stateless: bool,
+}
+
+impl RequestContext {
+    /// Storage shared by all requests of the current session.
+    ///
+    /// Servers attach a store per HTTP/WebSocket session and a process-wide
+    /// one for stdio. A context built without one gets a fresh, unshared store.
+    pub fn session_data(&self) -> Arc<SessionData> {
+        self.session_data.clone().unwrap_or_default()
+    }
+
+    /// Deliver this task's result as a [`StreamedResult`] instead of the
+    /// value the handler returns, which is then discarded.
+    ///
+    /// Only task-augmented requests store their results, so this fails when
+    /// `task` is not set.
+    pub fn stream_result(&self, result: StreamedResult) -> Result<(), ProtocolError> {
+        let slot = self.streamed_result.as_ref().ok_or_else(|| {
+            ProtocolError::Handler(
+                "streamed results require a task-augmented request".to_string(),
+            )
+        })?;
+        *slot.lock().expect("streamed result slot") = Some(result);
+        Ok(())
+    }
+
+    /// Send a notification to the client while the request is handled.
+    ///
+    /// Over Streamable HTTP a client that accepts `text/event-stream` gets
+    /// these on the response to its POST, ahead of the result.
+    pub fn notify(
+        &self,
+        method: impl Into<String>,
+        params: serde_json::Value,
+    ) -> Result<(), ProtocolError> {
+        let peer = self.peer.as_ref().ok_or_else(|| {
+            ProtocolError::Capability("no peer connection available for notifications".to_string())
+        })?;
+        peer.notify(method, params)
+    }
+
+    /// Report progress via `notifications/progress`.
+    ///
+    /// Returns `false` without sending anything when the request did not
+    /// ask for progress with a `progressToken`.
+    pub fn send_progress(&self, progress: Progress) -> Result<bool, ProtocolError> {
+        let Some(progress_token) = self.meta.as_ref().and_then(|meta| meta.progress_token.clone())
+        else {
+            return Ok(false);
+        };
+        let params = ProgressNotificationParams {
+            base: NotificationParams::default(),
+            progress,
+            progress_token,
+        };
+        self.notify("notifications/progress", serde_json::to_value(params)?)?;
+        Ok(true)
+    }
+
+    /// Ask the client to sample an LLM completion via `sampling/createMessage`.
+    ///
+    /// Fails with a capability error when no peer is attached or the client
+    /// did not declare `sampling`, and with a timeout when the client never answers.
+    pub async fn create_message(
+        &self,
+        params: CreateMessageRequestParams,
+    ) -> Result<CreateMessageResult, ProtocolError> {
+        let peer = self.peer.as_ref().ok_or_else(|| {
+            ProtocolError::Capability("no peer connection available for sampling".to_string())
+        })?;
+        let result = peer
+            .request("sampling/createMessage", serde_json::to_value(params)?)
+            .await?;
+        Ok(serde_json::from_value(result)?)
+    }
+
+    /// Ask the user to fill in a form via `elicitation/create`.
+    ///
+    /// Returns the client's [`ElicitResult`], whose action tells whether the
+    /// user accepted (with `content`), declined, or cancelled. Fails with a
+    /// capability error when no peer is attached or the client does not
+    /// support form elicitation, and with a validation error when accepted
+    /// content does not match `schema`.
+    pub async fn elicit_form(
+        &self,
+        schema: ElicitationSchema,
+        message: impl Into<String>,
+    ) -> Result<ElicitResult, ProtocolError> {
+        self.elicit_form_with_retries(schema, message, 0).await
+    }
+
+    /// Like [`elicit_form`](Self::elicit_form), but asks again up to
+    /// `retries` times when accepted content does not match `schema`,
+    /// listing the problems under the original message.
+    pub async fn elicit_form_with_retries(
+        &self,
+        schema: ElicitationSchema,
+        message: impl Into<String>,
+        retries: usize,
+    ) -> Result<ElicitResult, ProtocolError> {
+        let peer = self.peer.as_ref().ok_or_else(|| {
+            ProtocolError::Capability("no peer connection available for elicitation".to_string())
+        })?;
+        let message = message.into();
+        let mut prompt = message.clone();
+        let mut retries_left = retries;
+        loop {
+            let params = ElicitRequestFormParams::new(prompt, schema.clone());
+            let result: ElicitResult = serde_json::from_value(
+                peer.request("elicitation/create", serde_json::to_value(params)?)
+                    .await?,
+            )?;
+            if result.action != ElicitAction::Accept {
+                return Ok(result);
+            }
+            let empty = HashMap::new();
+            match schema.validate(result.content.as_ref().unwrap_or(&empty)) {
+                Ok(()) => return Ok(result),
+                Err(ValidationError::Failed(problems)) if retries_left > 0 => {
+                    retries_left -= 1;
+                    prompt = format!("{message}\n\n{}", problems.join("\n"));
+                }
+                Err(error) => return Err(error.into()),
+            }
+        }
+    }
 }
@@ -0,0 +1,120 @@
+/// Capability a peer must advertise before a method may be sent to it.
+///
+/// `Sampling`, `Elicitation` and `Roots` are client capabilities; `Tasks`
+/// applies to whichever side receives the request; the rest are server
+/// capabilities.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RequiredCapability {
+    /// `tools/*`
+    Tools,
+    /// `prompts/*`
+    Prompts,
+    /// `resources/*`
+    Resources,
+    /// `resources/subscribe` and `resources/unsubscribe`, which also need
+    /// `resources.subscribe`.
+    ResourceSubscriptions,
+    /// `logging/*`
+    Logging,
+    /// `completion/*`
+    Completions,
+    /// `tasks/*`
+    Tasks,
+    /// `sampling/*`
+    Sampling,
+    /// `elicitation/*`
+    Elicitation,
+    /// `roots/*`
+    Roots,
+}
+
+impl RequiredCapability {
+    /// Path of the capability in the `capabilities` object, e.g.
+    /// `resources.subscribe`.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Tools => "tools",
+            Self::Prompts => "prompts",
+            Self::Resources => "resources",
+            Self::ResourceSubscriptions => "resources.subscribe",
+            Self::Logging => "logging",
+            Self::Completions => "completions",
+            Self::Tasks => "tasks",
+            Self::Sampling => "sampling",
+            Self::Elicitation => "elicitation",
+            Self::Roots => "roots",
+        }
+    }
+}
+
+/// Capability the receiver of a `method` request must advertise.
+///
+/// Returns `None` for methods any peer accepts, such as `initialize` and
+/// `ping`, for notifications, and for unknown methods.
+pub fn required_capability(method: &str) -> Option<RequiredCapability> {
+    if matches!(method, "resources/subscribe" | "resources/unsubscribe") {
+        return Some(RequiredCapability::ResourceSubscriptions);
+    }
+    let (family, _) = method.split_once('/')?;
+    Some(match family {
+        "tools" => RequiredCapability::Tools,
+        "prompts" => RequiredCapability::Prompts,
+        "resources" => RequiredCapability::Resources,
+        "logging" => RequiredCapability::Logging,
+        "completion" => RequiredCapability::Completions,
+        "tasks" => RequiredCapability::Tasks,
+        "sampling" => RequiredCapability::Sampling,
+        "elicitation" => RequiredCapability::Elicitation,
+        "roots" => RequiredCapability::Roots,
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn maps_method_families_to_capabilities() {
+        let cases = [
+            ("tools/list", RequiredCapability::Tools),
+            ("tools/call", RequiredCapability::Tools),
+            ("prompts/get", RequiredCapability::Prompts),
+            ("resources/read", RequiredCapability::Resources),
+            ("resources/templates/list", RequiredCapability::Resources),
+            ("resources/subscribe", RequiredCapability::ResourceSubscriptions),
+            ("resources/unsubscribe", RequiredCapability::ResourceSubscriptions),
+            ("logging/setLevel", RequiredCapability::Logging),
+            ("completion/complete", RequiredCapability::Completions),
+            ("tasks/result", RequiredCapability::Tasks),
+            ("sampling/createMessage", RequiredCapability::Sampling),
+            ("elicitation/create", RequiredCapability::Elicitation),
+            ("roots/list", RequiredCapability::Roots),
+        ];
+        for (method, capability) in cases {
+            assert_eq!(required_capability(method), Some(capability), "{method}");
+        }
+    }
+
+    #[test]
+    fn capability_agnostic_methods_map_to_none() {
+        for method in [
+            "ping",
+            "initialize",
+            "notifications/initialized",
+            "notifications/tools/list_changed",
+            "custom/method",
+        ] {
+            assert_eq!(required_capability(method), None, "{method}");
+        }
+    }
+
+    #[test]
+    fn names_match_capability_paths() {
+        assert_eq!(RequiredCapability::Completions.name(), "completions");
+        assert_eq!(
+            RequiredCapability::ResourceSubscriptions.name(),
+            "resources.subscribe"
+        );
+    }
+}
@@ -0,0 +1,125 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use futures::channel::oneshot;
+use futures::future::AbortHandle;
+use futures::{FutureExt, select};
+
+use super::CancellationToken;
+
+struct RunningTask {
+    cancel_token: CancellationToken,
+    abort_handle: AbortHandle,
+    finished: Option<oneshot::Receiver<()>>,
+}
+
+/// Registry of task-augmented requests executing in the background.
+#[derive(Default)]
+pub struct RunningTasks {
+    tasks: Mutex<HashMap<String, RunningTask>>,
+    /// Woken when the last task is removed; locked after `tasks`.
+    idle: Mutex<Vec<oneshot::Sender<()>>>,
+}
+
+impl RunningTasks {
+    pub(crate) fn insert(
+        &self,
+        task_id: String,
+        cancel_token: CancellationToken,
+        abort_handle: AbortHandle,
+        finished: oneshot::Receiver<()>,
+    ) {
+        self.tasks.lock().expect("running tasks").insert(
+            task_id,
+            RunningTask {
+                cancel_token,
+                abort_handle,
+                finished: Some(finished),
+            },
+        );
+    }
+
+    pub(crate) fn remove(&self, task_id: &str) {
+        let mut tasks = self.tasks.lock().expect("running tasks");
+        tasks.remove(task_id);
+        if tasks.is_empty() {
+            let waiters = std::mem::take(&mut *self.idle.lock().expect("running tasks"));
+            drop(tasks);
+            for waiter in waiters {
+                let _ = waiter.send(());
+            }
+        }
+    }
+
+    /// Returns true if the task is still executing.
+    pub fn contains(&self, task_id: &str) -> bool {
+        self.tasks
+            .lock()
+            .expect("running tasks")
+            .contains_key(task_id)
+    }
+
+    /// Number of tasks still executing.
+    pub fn len(&self) -> usize {
+        self.tasks.lock().expect("running tasks").len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// IDs of the tasks still executing.
+    pub fn task_ids(&self) -> Vec<String> {
+        self.tasks
+            .lock()
+            .expect("running tasks")
+            .keys()
+            .cloned()
+            .collect()
+    }
+
+    /// Resolve once no task is executing.
+    pub async fn wait_until_empty(&self) {
+        let idle = {
+            let tasks = self.tasks.lock().expect("running tasks");
+            if tasks.is_empty() {
+                return;
+            }
+            let (tx, rx) = oneshot::channel();
+            self.idle.lock().expect("running tasks").push(tx);
+            rx
+        };
+        let _ = idle.await;
+    }
+
+    /// Trip the task's cancellation token, give the handler `grace_period`
+    /// to finish on its own, then abort its future.
+    ///
+    /// Returns false if no task with this id is running.
+    pub async fn cancel(&self, task_id: &str, grace_period: Duration) -> bool {
+        let (cancel_token, abort_handle, finished) = {
+            let mut tasks = self.tasks.lock().expect("running tasks");
+            let Some(task) = tasks.get_mut(task_id) else {
+                return false;
+            };
+            (
+                task.cancel_token.clone(),
+                task.abort_handle.clone(),
+                task.finished.take(),
+            )
+        };
+
+        cancel_token.cancel();
+        if let Some(finished) = finished.filter(|_| !grace_period.is_zero()) {
+            let mut finished = finished.fuse();
+            let mut delay = futures_timer::Delay::new(grace_period).fuse();
+            select! {
+                _ = finished => {},
+                _ = delay => {},
+            }
+        }
+        abort_handle.abort();
+        true
+    }
+}
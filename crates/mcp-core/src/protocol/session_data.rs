@@ -0,0 +1,159 @@
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::{Arc, RwLock};
+
+use serde_json::Value;
+
+/// Thread-safe per-session storage for request handlers.
+///
+/// Values are either keyed by type (`insert::<T>()` / `get::<T>()`) or by
+/// string with JSON values for dynamic data. Typed values are handed out as
+/// `Arc<T>`, so use interior mutability (atomics, mutexes) for state that
+/// changes across requests. The store is dropped together with its session.
+#[derive(Default)]
+pub struct SessionData {
+    typed: RwLock<HashMap<TypeId, Arc<dyn Any + Send + Sync>>>,
+    values: RwLock<HashMap<String, Value>>,
+}
+
+impl SessionData {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Store a value keyed by its type, returning the previous one.
+    pub fn insert<T: Send + Sync + 'static>(&self, value: T) -> Option<Arc<T>> {
+        self.typed
+            .write()
+            .expect("session data")
+            .insert(TypeId::of::<T>(), Arc::new(value))
+            .and_then(downcast)
+    }
+
+    /// Get the value stored for type `T`.
+    pub fn get<T: Send + Sync + 'static>(&self) -> Option<Arc<T>> {
+        self.typed
+            .read()
+            .expect("session data")
+            .get(&TypeId::of::<T>())
+            .cloned()
+            .and_then(downcast)
+    }
+
+    /// Get the value stored for type `T`, inserting `init()` first if absent.
+    pub fn get_or_insert_with<T, F>(&self, init: F) -> Arc<T>
+    where
+        T: Send + Sync + 'static,
+        F: FnOnce() -> T,
+    {
+        let mut typed = self.typed.write().expect("session data");
+        let value = typed
+            .entry(TypeId::of::<T>())
+            .or_insert_with(|| Arc::new(init()))
+            .clone();
+        downcast(value).expect("value stored under its own TypeId")
+    }
+
+    /// Remove the value stored for type `T`.
+    pub fn remove<T: Send + Sync + 'static>(&self) -> Option<Arc<T>> {
+        self.typed
+            .write()
+            .expect("session data")
+            .remove(&TypeId::of::<T>())
+            .and_then(downcast)
+    }
+
+    /// Store a JSON value under `key`, returning the previous one.
+    pub fn set_value(&self, key: impl Into<String>, value: Value) -> Option<Value> {
+        self.values
+            .write()
+            .expect("session data")
+            .insert(key.into(), value)
+    }
+
+    /// Get the JSON value stored under `key`.
+    pub fn get_value(&self, key: &str) -> Option<Value> {
+        self.values.read().expect("session data").get(key).cloned()
+    }
+
+    /// Remove the JSON value stored under `key`.
+    pub fn remove_value(&self, key: &str) -> Option<Value> {
+        self.values.write().expect("session data").remove(key)
+    }
+
+    /// Keys of the JSON values currently stored.
+    pub fn value_keys(&self) -> Vec<String> {
+        self.values
+            .read()
+            .expect("session data")
+            .keys()
+            .cloned()
+            .collect()
+    }
+}
+
+impl fmt::Debug for SessionData {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SessionData")
+            .field(
+                "typed_entries",
+                &self.typed.read().expect("session data").len(),
+            )
+            .field("values", &*self.values.read().expect("session data"))
+            .finish()
+    }
+}
+
+fn downcast<T: Send + Sync + 'static>(value: Arc<dyn Any + Send + Sync>) -> Option<Arc<T>> {
+    value.downcast::<T>().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicU64, Ordering};
+
+    use serde_json::json;
+
+    use super::*;
+
+    #[derive(Debug, PartialEq)]
+    struct Token(String);
+
+    #[test]
+    fn typed_values_are_keyed_by_type() {
+        let data = SessionData::new();
+        assert!(data.get::<Token>().is_none());
+
+        assert!(data.insert(Token("a".to_string())).is_none());
+        assert!(data.insert(7u32).is_none());
+        assert_eq!(*data.get::<Token>().unwrap(), Token("a".to_string()));
+        assert_eq!(*data.get::<u32>().unwrap(), 7);
+
+        let previous = data.insert(Token("b".to_string())).unwrap();
+        assert_eq!(*previous, Token("a".to_string()));
+        assert_eq!(*data.remove::<Token>().unwrap(), Token("b".to_string()));
+        assert!(data.get::<Token>().is_none());
+    }
+
+    #[test]
+    fn get_or_insert_with_shares_one_value() {
+        let data = SessionData::new();
+        let first = data.get_or_insert_with(AtomicU64::default);
+        first.fetch_add(1, Ordering::SeqCst);
+        let second = data.get_or_insert_with(|| AtomicU64::new(100));
+        assert_eq!(second.fetch_add(1, Ordering::SeqCst), 1);
+        assert!(Arc::ptr_eq(&first, &second));
+    }
+
+    #[test]
+    fn json_values_are_keyed_by_string() {
+        let data = SessionData::new();
+        assert!(data.set_value("cursor", json!("abc")).is_none());
+        assert_eq!(data.get_value("cursor"), Some(json!("abc")));
+        assert_eq!(data.set_value("cursor", json!(2)), Some(json!("abc")));
+        assert_eq!(data.value_keys(), vec!["cursor".to_string()]);
+        assert_eq!(data.remove_value("cursor"), Some(json!(2)));
+        assert!(data.get_value("cursor").is_none());
+    }
+}
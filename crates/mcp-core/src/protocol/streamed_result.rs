@@ -0,0 +1,141 @@
+use std::fmt;
+use std::io::{self, Write};
+
+use serde::Serialize;
+
+use super::ProtocolError;
+
+/// Default cap on the serialized size of a streamed task result (256 MiB).
+pub const DEFAULT_MAX_STREAMED_RESULT_BYTES: u64 = 256 * 1024 * 1024;
+
+type ResultWriter = Box<dyn FnOnce(&mut dyn Write) -> io::Result<()> + Send>;
+
+/// Task result serialized straight into the task store's result storage.
+///
+/// Large results never exist as a `serde_json::Value` or `String`: the writer
+/// callback receives the store's sink and serializes into it, typically with
+/// a `serde_json::Serializer`. It must write the complete JSON result object
+/// (for `tools/call`, a `CallToolResult`). Writing more than the byte cap
+/// fails the task.
+///
+/// Handlers of task-augmented requests hand one over with
+/// [`RequestContext::stream_result`](super::RequestContext::stream_result).
+pub struct StreamedResult {
+    writer: ResultWriter,
+    max_bytes: u64,
+}
+
+impl StreamedResult {
+    /// Result produced by `writer`, capped at [`DEFAULT_MAX_STREAMED_RESULT_BYTES`].
+    pub fn new<F>(writer: F) -> Self
+    where
+        F: FnOnce(&mut dyn Write) -> io::Result<()> + Send + 'static,
+    {
+        Self {
+            writer: Box::new(writer),
+            max_bytes: DEFAULT_MAX_STREAMED_RESULT_BYTES,
+        }
+    }
+
+    /// Result serialized from `value` with `serde_json::to_writer`.
+    ///
+    /// `value` can generate its contents lazily in its `Serialize` impl.
+    pub fn json<T>(value: T) -> Self
+    where
+        T: Serialize + Send + 'static,
+    {
+        Self::new(move |writer| serde_json::to_writer(writer, &value).map_err(io::Error::from))
+    }
+
+    /// Cap the serialized size at `max_bytes`.
+    pub fn with_max_bytes(mut self, max_bytes: u64) -> Self {
+        self.max_bytes = max_bytes;
+        self
+    }
+
+    pub fn max_bytes(&self) -> u64 {
+        self.max_bytes
+    }
+
+    /// Run the writer against `sink`, returning the number of bytes written.
+    pub fn write_to(self, sink: &mut dyn Write) -> Result<u64, ProtocolError> {
+        let max_bytes = self.max_bytes;
+        let mut capped = CappedWriter {
+            inner: sink,
+            written: 0,
+            max_bytes,
+            exceeded: false,
+        };
+        let outcome = (self.writer)(&mut capped).and_then(|()| capped.flush());
+        if capped.exceeded {
+            return Err(ProtocolError::Handler(format!(
+                "streamed result exceeds {max_bytes} bytes"
+            )));
+        }
+        outcome.map_err(|err| ProtocolError::Handler(format!("streamed result failed: {err}")))?;
+        Ok(capped.written)
+    }
+}
+
+impl fmt::Debug for StreamedResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("StreamedResult")
+            .field("max_bytes", &self.max_bytes)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Sink wrapper enforcing the byte cap.
+struct CappedWriter<'a> {
+    inner: &'a mut dyn Write,
+    written: u64,
+    max_bytes: u64,
+    exceeded: bool,
+}
+
+impl Write for CappedWriter<'_> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if self.written + buf.len() as u64 > self.max_bytes {
+            self.exceeded = true;
+            return Err(io::Error::other("streamed result byte cap exceeded"));
+        }
+        let written = self.inner.write(buf)?;
+        self.written += written as u64;
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn writes_json_and_counts_bytes() {
+        let mut sink = Vec::new();
+        let written = StreamedResult::json(json!({ "content": [] }))
+            .write_to(&mut sink)
+            .unwrap();
+        assert_eq!(sink, br#"{"content":[]}"#);
+        assert_eq!(written, sink.len() as u64);
+    }
+
+    #[test]
+    fn enforces_byte_cap() {
+        let mut sink = Vec::new();
+        let err = StreamedResult::new(|writer| writer.write_all(&[b' '; 64]))
+            .with_max_bytes(16)
+            .write_to(&mut sink)
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "handler failed: streamed result exceeds 16 bytes"
+        );
+        assert!(sink.len() <= 16);
+    }
+}
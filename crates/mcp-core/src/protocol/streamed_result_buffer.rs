@@ -0,0 +1,187 @@
+use std::io::{self, Write};
+
+use crate::types::{TaskResultChunk, TaskResultRange};
+
+use super::ProtocolError;
+
+/// Size of each chunk; also the unit of memory growth while writing.
+const CHUNK_SIZE: usize = 64 * 1024;
+
+/// Serialized task result kept as fixed-size chunks.
+///
+/// Growing never reallocates or copies what was already written, so the
+/// peak memory of a streamed write is the payload plus one chunk.
+#[derive(Debug, Default)]
+pub struct StreamedResultBuffer {
+    chunks: Vec<Vec<u8>>,
+    len: u64,
+}
+
+impl StreamedResultBuffer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Number of bytes written.
+    pub fn len(&self) -> u64 {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Copy the whole contents into one contiguous buffer.
+    pub fn to_vec(&self) -> Vec<u8> {
+        self.chunks.concat()
+    }
+
+    /// Read the bytes in `range` as JSON text.
+    ///
+    /// The returned data ends on a UTF-8 character boundary; `offset` must
+    /// start on one (any `nextOffset` from a previous read does).
+    pub fn read_range(&self, range: &TaskResultRange) -> Result<TaskResultChunk, ProtocolError> {
+        if range.offset > self.len {
+            return Err(ProtocolError::Handler(format!(
+                "range offset {} is beyond the result size {}",
+                range.offset, self.len
+            )));
+        }
+        let available = self.len - range.offset;
+        let wanted = range
+            .length
+            .map_or(available, |length| length.min(available));
+        let mut end = range.offset + wanted;
+        if self.is_continuation(range.offset) {
+            return Err(ProtocolError::Handler(format!(
+                "range offset {} is not on a character boundary",
+                range.offset
+            )));
+        }
+        while end < self.len && end > range.offset && self.is_continuation(end) {
+            end -= 1;
+        }
+        // Ranges shorter than one character still make progress.
+        if end == range.offset && wanted > 0 {
+            end += 1;
+            while end < self.len && self.is_continuation(end) {
+                end += 1;
+            }
+        }
+
+        let data = String::from_utf8(self.copy_range(range.offset, end))
+            .map_err(|err| ProtocolError::Handler(format!("stored result is not UTF-8: {err}")))?;
+        Ok(TaskResultChunk {
+            data,
+            offset: range.offset,
+            next_offset: (end < self.len).then_some(end),
+            total_bytes: self.len,
+        })
+    }
+
+    fn byte_at(&self, position: u64) -> Option<u8> {
+        let position = usize::try_from(position).ok()?;
+        self.chunks
+            .get(position / CHUNK_SIZE)?
+            .get(position % CHUNK_SIZE)
+            .copied()
+    }
+
+    fn is_continuation(&self, position: u64) -> bool {
+        self.byte_at(position)
+            .is_some_and(|byte| byte & 0b1100_0000 == 0b1000_0000)
+    }
+
+    fn copy_range(&self, start: u64, end: u64) -> Vec<u8> {
+        let (start, end) = (start as usize, end as usize);
+        let mut bytes = Vec::with_capacity(end - start);
+        let mut position = start;
+        while position < end {
+            let chunk = &self.chunks[position / CHUNK_SIZE];
+            let from = position % CHUNK_SIZE;
+            let to = chunk.len().min(from + (end - position));
+            bytes.extend_from_slice(&chunk[from..to]);
+            position += to - from;
+        }
+        bytes
+    }
+}
+
+impl Write for StreamedResultBuffer {
+    fn write(&mut self, mut buf: &[u8]) -> io::Result<usize> {
+        let written = buf.len();
+        while !buf.is_empty() {
+            if self
+                .chunks
+                .last()
+                .is_none_or(|chunk| chunk.len() == CHUNK_SIZE)
+            {
+                self.chunks.push(Vec::with_capacity(CHUNK_SIZE));
+            }
+            let chunk = self.chunks.last_mut().expect("chunk pushed above");
+            let take = buf.len().min(CHUNK_SIZE - chunk.len());
+            chunk.extend_from_slice(&buf[..take]);
+            buf = &buf[take..];
+        }
+        self.len += written as u64;
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn range(offset: u64, length: Option<u64>) -> TaskResultRange {
+        TaskResultRange { offset, length }
+    }
+
+    #[test]
+    fn writes_across_chunks() {
+        let mut buffer = StreamedResultBuffer::new();
+        let payload: Vec<u8> = (0..CHUNK_SIZE * 2 + 10)
+            .map(|i| b'a' + (i % 26) as u8)
+            .collect();
+        buffer.write_all(&payload[..7]).unwrap();
+        buffer.write_all(&payload[7..]).unwrap();
+        assert_eq!(buffer.len(), payload.len() as u64);
+        assert_eq!(buffer.to_vec(), payload);
+
+        let chunk = buffer
+            .read_range(&range(CHUNK_SIZE as u64 - 3, Some(6)))
+            .unwrap();
+        assert_eq!(
+            chunk.data.as_bytes(),
+            &payload[CHUNK_SIZE - 3..CHUNK_SIZE + 3]
+        );
+        assert_eq!(chunk.next_offset, Some(CHUNK_SIZE as u64 + 3));
+        assert_eq!(chunk.total_bytes, payload.len() as u64);
+    }
+
+    #[test]
+    fn ranges_end_on_character_boundaries() {
+        let mut buffer = StreamedResultBuffer::new();
+        buffer.write_all("\"añb\"".as_bytes()).unwrap();
+
+        // "ñ" spans bytes 2..4; a range ending inside it stops before it.
+        let first = buffer.read_range(&range(0, Some(3))).unwrap();
+        assert_eq!(first.data, "\"a");
+        assert_eq!(first.next_offset, Some(2));
+
+        // A range too short for the character still returns it whole.
+        let second = buffer.read_range(&range(2, Some(1))).unwrap();
+        assert_eq!(second.data, "ñ");
+        assert_eq!(second.next_offset, Some(4));
+
+        let rest = buffer.read_range(&range(4, None)).unwrap();
+        assert_eq!(rest.data, "b\"");
+        assert_eq!(rest.next_offset, None);
+
+        assert!(buffer.read_range(&range(3, None)).is_err());
+        assert!(buffer.read_range(&range(7, None)).is_err());
+    }
+}
@@ -0,0 +1,6 @@
+use std::sync::{Arc, Mutex};
+
+use super::StreamedResult;
+
+/// Slot through which a task handler hands a [`StreamedResult`] to the runtime.
+pub type StreamedResultSlot = Arc<Mutex<Option<StreamedResult>>>;
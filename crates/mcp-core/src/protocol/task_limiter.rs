@@ -0,0 +1,96 @@
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+
+use futures::channel::oneshot;
+
+use super::TaskPermit;
+
+struct LimiterState {
+    active: usize,
+    queue: VecDeque<(String, oneshot::Sender<TaskPermit>)>,
+}
+
+/// FIFO semaphore bounding how many task-augmented requests execute at once.
+///
+/// Tasks that cannot get a permit wait in a queue in arrival order; a queued
+/// task can be removed before it ever runs.
+pub struct TaskLimiter {
+    limit: usize,
+    state: Mutex<LimiterState>,
+}
+
+impl TaskLimiter {
+    /// Create a limiter allowing `limit` concurrent tasks (at least one).
+    pub fn new(limit: usize) -> Self {
+        Self {
+            limit: limit.max(1),
+            state: Mutex::new(LimiterState {
+                active: 0,
+                queue: VecDeque::new(),
+            }),
+        }
+    }
+
+    /// Maximum number of tasks executing at once.
+    pub fn limit(&self) -> usize {
+        self.limit
+    }
+
+    /// Number of tasks currently holding a permit.
+    pub fn active(&self) -> usize {
+        self.state.lock().expect("task limiter").active
+    }
+
+    /// Number of tasks waiting for a permit.
+    pub fn queue_depth(&self) -> usize {
+        self.state.lock().expect("task limiter").queue.len()
+    }
+
+    /// Take a permit if one is free, otherwise queue `task_id` and return a
+    /// receiver that resolves once a permit is handed over. The receiver
+    /// resolves to `Canceled` if the task is removed from the queue.
+    pub(crate) fn acquire(
+        self: &Arc<Self>,
+        task_id: &str,
+    ) -> Result<TaskPermit, oneshot::Receiver<TaskPermit>> {
+        let mut state = self.state.lock().expect("task limiter");
+        if state.active < self.limit {
+            state.active += 1;
+            return Ok(TaskPermit::new(Arc::clone(self)));
+        }
+        let (tx, rx) = oneshot::channel();
+        state.queue.push_back((task_id.to_string(), tx));
+        Err(rx)
+    }
+
+    /// Remove a queued task so it never runs.
+    ///
+    /// Returns false if the task is not queued (already running or unknown).
+    pub fn remove_queued(&self, task_id: &str) -> bool {
+        let mut state = self.state.lock().expect("task limiter");
+        let before = state.queue.len();
+        state.queue.retain(|(queued, _)| queued != task_id);
+        state.queue.len() != before
+    }
+
+    /// Hand a released permit to the next waiter, or free it.
+    pub(crate) fn release(self: &Arc<Self>) {
+        loop {
+            let waiter = {
+                let mut state = self.state.lock().expect("task limiter");
+                match state.queue.pop_front() {
+                    Some((_, waiter)) => waiter,
+                    None => {
+                        state.active -= 1;
+                        return;
+                    }
+                }
+            };
+            match waiter.send(TaskPermit::new(Arc::clone(self))) {
+                Ok(()) => return,
+                // The waiter went away; the permit moves on to the next one.
+                Err(permit) => permit.forget(),
+            }
+        }
+    }
+}
@@ -0,0 +1,31 @@
+use std::sync::Arc;
+
+use super::TaskLimiter;
+
+/// A slot held by an executing task; released back to its
+/// [`TaskLimiter`] when dropped.
+pub struct TaskPermit {
+    limiter: Option<Arc<TaskLimiter>>,
+}
+
+impl TaskPermit {
+    pub(crate) fn new(limiter: Arc<TaskLimiter>) -> Self {
+        Self {
+            limiter: Some(limiter),
+        }
+    }
+
+    /// Drop the permit without releasing its slot, used when the slot is
+    /// being handed on directly.
+    pub(crate) fn forget(mut self) {
+        self.limiter = None;
+    }
+}
+
+impl Drop for TaskPermit {
+    fn drop(&mut self) {
+        if let Some(limiter) = self.limiter.take() {
+            limiter.release();
+        }
+    }
+}
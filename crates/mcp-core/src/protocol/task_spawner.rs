@@ -0,0 +1,9 @@
+use std::sync::Arc;
+
+use futures::future::BoxFuture;
+
+/// Spawns background work on the caller's async runtime.
+///
+/// When configured, task-augmented requests run detached from the request
+/// that created them, so they can be cancelled through `tasks/cancel`.
+pub type TaskSpawner = Arc<dyn Fn(BoxFuture<'static, ()>) + Send + Sync>;
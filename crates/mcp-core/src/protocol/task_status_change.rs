@@ -0,0 +1,12 @@
+use crate::types::{Task, TaskStatus};
+
+/// A task status transition reported by a [`TaskStore`](super::TaskStore).
+#[derive(Debug, Clone, PartialEq)]
+pub struct TaskStatusChange {
+    /// The task after the transition.
+    pub task: Task,
+    /// The status the task had before the transition.
+    pub previous_status: TaskStatus,
+    /// Session that created the task, if any.
+    pub session_id: Option<String>,
+}
@@ -0,0 +1,6 @@
+use std::sync::Arc;
+
+use super::TaskStatusChange;
+
+/// Callback invoked by task stores whenever a task changes status.
+pub type TaskStatusListener = Arc<dyn Fn(TaskStatusChange) + Send + Sync>;
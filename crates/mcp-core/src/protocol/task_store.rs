@@ -1,9 +1,12 @@
 use async_trait::async_trait;
 use serde_json::Value;
 
-use crate::types::{Cursor, ErrorObject, MessageId, RequestMessage, Task, TaskMetadata};
+use crate::types::{
+    Cursor, ErrorObject, ListTasksRequestParams, MessageId, RequestMessage, Task, TaskMetadata,
+    TaskResultChunk, TaskResultRange,
+};
 
-use super::ProtocolError;
+use super::{ProtocolError, StreamedResult, StreamedResultBuffer, TaskStatusListener};
 
 /// Storage backend for task-augmented requests.
 #[async_trait]
@@ -13,6 +16,7 @@ pub trait TaskStore: Send + Sync + 'static {
         params: TaskMetadata,
         request_id: MessageId,
         request: RequestMessage,
+        session_id: Option<String>,
     ) -> Result<Task, ProtocolError>;
 
     async fn set_task_result(
@@ -23,9 +27,10 @@ pub trait TaskStore: Send + Sync + 'static {
 
     async fn get_task(&self, task_id: &str) -> Result<Option<Task>, ProtocolError>;
 
+    /// List tasks matching the filters in `params`, one page at a time.
     async fn list_tasks(
         &self,
-        cursor: Option<Cursor>,
+        params: &ListTasksRequestParams,
     ) -> Result<(Vec<Task>, Option<Cursor>), ProtocolError>;
 
     async fn get_task_result(
@@ -33,5 +38,58 @@ pub trait TaskStore: Send + Sync + 'static {
         task_id: &str,
     ) -> Result<Option<Result<Value, ErrorObject>>, ProtocolError>;
 
+    /// Store a result serialized by a [`StreamedResult`] writer.
+    ///
+    /// Stores that keep results as bytes should override this to write
+    /// straight into their storage. The default buffers the bytes and parses
+    /// them for [`set_task_result`](Self::set_task_result). Writer failures,
+    /// including an exceeded byte cap, are returned so the caller can record
+    /// the task as failed.
+    async fn set_task_result_streamed(
+        &self,
+        task_id: &str,
+        result: StreamedResult,
+    ) -> Result<(), ProtocolError> {
+        let mut buffer = StreamedResultBuffer::new();
+        result.write_to(&mut buffer)?;
+        let value = serde_json::from_slice(&buffer.to_vec())?;
+        self.set_task_result(task_id, Ok(value)).await
+    }
+
+    /// Read part of a task's serialized result.
+    ///
+    /// Returns `None` while no result is available and the stored error of a
+    /// failed task. The default serializes the result from
+    /// [`get_task_result`](Self::get_task_result).
+    async fn get_task_result_range(
+        &self,
+        task_id: &str,
+        range: &TaskResultRange,
+    ) -> Result<Option<Result<TaskResultChunk, ErrorObject>>, ProtocolError> {
+        match self.get_task_result(task_id).await? {
+            Some(Ok(value)) => {
+                let mut buffer = StreamedResultBuffer::new();
+                serde_json::to_writer(&mut buffer, &value)?;
+                Ok(Some(Ok(buffer.read_range(range)?)))
+            }
+            Some(Err(error)) => Ok(Some(Err(error))),
+            None => Ok(None),
+        }
+    }
+
     async fn cancel_task(&self, task_id: &str) -> Result<Option<Task>, ProtocolError>;
+
+    /// Update a task's `statusMessage` without changing its status.
+    /// Stores that do not track status messages may ignore it.
+    async fn set_status_message(
+        &self,
+        _task_id: &str,
+        _message: Option<String>,
+    ) -> Result<(), ProtocolError> {
+        Ok(())
+    }
+
+    /// Install a listener notified on every task status transition.
+    /// Stores that cannot report transitions may ignore it.
+    fn set_status_listener(&self, _listener: Option<TaskStatusListener>) {}
 }
@@ -1,3 +1,7 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::sync::Arc;
+
 use jsonschema::{Draft, ValidationOptions};
 use schemars::JsonSchema;
 use schemars::schema::RootSchema;
@@ -10,25 +14,178 @@ pub trait SchemaValidator: Send + Sync + 'static {
 
     /// Validate the provided payload against the schema.
     fn validate(&self, schema: &Self::Schema, payload: &Value) -> Result<(), ValidationError>;
+
+    /// Check values whose schema carries `keyword` with `validator`, once
+    /// the standard keywords have passed. Registering a keyword again
+    /// replaces its validator.
+    fn register_keyword(&mut self, keyword: &str, validator: Box<dyn CustomKeywordValidator>);
 }
 
+/// Validation for a custom schema keyword such as `x-gitlab-project-id`.
+pub trait CustomKeywordValidator: Send + Sync {
+    /// Check `value` against `schema_value`, the keyword's value in the
+    /// schema that applies to it.
+    fn validate(&self, value: &Value, schema_value: &Value) -> Result<(), ValidationError>;
+}
+
+/// Deepest `$ref` chain followed when looking for custom keywords.
+const MAX_KEYWORD_DEPTH: usize = 64;
+
 /// Default validator that builds on `schemars` + `jsonschema`.
-#[derive(Debug, Clone)]
+#[derive(Clone)]
 pub struct JsonSchemaValidator {
     draft: Draft,
+    keywords: BTreeMap<String, Arc<dyn CustomKeywordValidator>>,
+}
+
+impl fmt::Debug for JsonSchemaValidator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("JsonSchemaValidator")
+            .field("draft", &self.draft)
+            .field("keywords", &self.keywords.keys().collect::<Vec<_>>())
+            .finish()
+    }
 }
 
 impl JsonSchemaValidator {
     /// Create a validator that targets `Draft 2020-12`.
     pub fn new() -> Self {
-        Self {
-            draft: Draft::Draft202012,
-        }
+        Self::with_draft(Draft::Draft202012)
     }
 
     /// Allow overriding the draft used for compilation.
     pub fn with_draft(draft: Draft) -> Self {
-        Self { draft }
+        Self {
+            draft,
+            keywords: BTreeMap::new(),
+        }
+    }
+
+    /// The draft schemas are compiled against.
+    pub fn draft(&self) -> Draft {
+        self.draft
+    }
+
+    /// Validate `payload` against a raw JSON schema, such as a tool's
+    /// `inputSchema`.
+    ///
+    /// The configured draft applies even when the schema declares another
+    /// `$schema`, so draft 2020-12 keywords (`prefixItems`, `$dynamicRef`,
+    /// `unevaluatedProperties`, ...) are honoured by default.
+    pub fn validate_value(&self, schema: &Value, payload: &Value) -> Result<(), ValidationError> {
+        let compiled = ValidationOptions::default()
+            .with_draft(self.draft)
+            .build(schema)
+            .map_err(|err| ValidationError::Schema(err.to_string()))?;
+
+        compiled
+            .validate(payload)
+            .map_err(|errors| ValidationError::Failed(errors.map(|e| e.to_string()).collect()))?;
+
+        if self.keywords.is_empty() {
+            return Ok(());
+        }
+        let mut failures = Vec::new();
+        self.check_keywords(schema, schema, payload, "", 0, &mut failures)?;
+        if failures.is_empty() {
+            Ok(())
+        } else {
+            Err(ValidationError::Failed(failures))
+        }
+    }
+
+    /// Run custom keywords found in `schema` on `value`, following
+    /// `properties`, `additionalProperties`, `prefixItems`, `items`, `allOf`
+    /// and local `$ref`s. Branches of `anyOf`/`oneOf`/`if` are skipped as
+    /// they need not apply.
+    fn check_keywords(
+        &self,
+        root: &Value,
+        schema: &Value,
+        value: &Value,
+        path: &str,
+        depth: usize,
+        failures: &mut Vec<String>,
+    ) -> Result<(), ValidationError> {
+        let Some(schema) = schema.as_object() else {
+            return Ok(());
+        };
+        if depth > MAX_KEYWORD_DEPTH {
+            return Err(ValidationError::Schema(format!(
+                "$ref chain deeper than {MAX_KEYWORD_DEPTH} at {path:?}"
+            )));
+        }
+
+        for (keyword, validator) in &self.keywords {
+            if let Some(schema_value) = schema.get(keyword) {
+                match validator.validate(value, schema_value) {
+                    Ok(()) => {}
+                    Err(ValidationError::Failed(messages)) => failures.extend(
+                        messages
+                            .into_iter()
+                            .map(|message| format!("{keyword} at {path:?}: {message}")),
+                    ),
+                    Err(err) => return Err(err),
+                }
+            }
+        }
+
+        if let Some(target) = schema
+            .get("$ref")
+            .and_then(Value::as_str)
+            .and_then(|reference| reference.strip_prefix('#'))
+            .and_then(|pointer| root.pointer(pointer))
+        {
+            self.check_keywords(root, target, value, path, depth + 1, failures)?;
+        }
+        for sub in schema
+            .get("allOf")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+        {
+            self.check_keywords(root, sub, value, path, depth, failures)?;
+        }
+
+        match value {
+            Value::Object(map) => {
+                let properties = schema.get("properties").and_then(Value::as_object);
+                let additional = schema.get("additionalProperties");
+                for (name, item) in map {
+                    let sub = match properties.and_then(|properties| properties.get(name)) {
+                        Some(sub) => sub,
+                        None => match additional {
+                            Some(sub) => sub,
+                            None => continue,
+                        },
+                    };
+                    let path = format!("{path}/{name}");
+                    self.check_keywords(root, sub, item, &path, depth, failures)?;
+                }
+            }
+            Value::Array(items) => {
+                let prefix = schema
+                    .get("prefixItems")
+                    .and_then(Value::as_array)
+                    .map(Vec::as_slice)
+                    .unwrap_or_default();
+                for (index, item) in items.iter().enumerate() {
+                    let sub = match (prefix.get(index), schema.get("items")) {
+                        (Some(sub), _) => sub,
+                        (None, Some(Value::Array(tuple))) => match tuple.get(index) {
+                            Some(sub) => sub,
+                            None => continue,
+                        },
+                        (None, Some(sub)) => sub,
+                        (None, None) => continue,
+                    };
+                    let path = format!("{path}/{index}");
+                    self.check_keywords(root, sub, item, &path, depth, failures)?;
+                }
+            }
+            _ => {}
+        }
+        Ok(())
     }
 }
 
@@ -44,15 +201,12 @@ impl SchemaValidator for JsonSchemaValidator {
     fn validate(&self, schema: &RootSchema, payload: &Value) -> Result<(), ValidationError> {
         let schema_value =
             to_value(schema).map_err(|err| ValidationError::Schema(err.to_string()))?;
+        self.validate_value(&schema_value, payload)
+    }
 
-        let compiled = ValidationOptions::default()
-            .with_draft(self.draft)
-            .build(&schema_value)
-            .map_err(|err| ValidationError::Schema(err.to_string()))?;
-
-        compiled
-            .validate(payload)
-            .map_err(|errors| ValidationError::Failed(errors.map(|e| e.to_string()).collect()))
+    fn register_keyword(&mut self, keyword: &str, validator: Box<dyn CustomKeywordValidator>) {
+        self.keywords
+            .insert(keyword.to_string(), Arc::from(validator));
     }
 }
 
@@ -72,3 +226,161 @@ pub enum ValidationError {
     #[error("validation failed: {0:?}")]
     Failed(Vec<String>),
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde_json::json;
+
+    /// Validate through `RootSchema`, as registered request handlers do.
+    fn check(schema: Value, payload: Value) -> bool {
+        let schema: RootSchema = serde_json::from_value(schema).unwrap();
+        JsonSchemaValidator::default()
+            .validate(&schema, &payload)
+            .is_ok()
+    }
+
+    #[test]
+    fn defaults_to_draft_2020_12() {
+        assert_eq!(JsonSchemaValidator::default().draft(), Draft::Draft202012);
+    }
+
+    #[test]
+    fn if_then_else() {
+        let schema = json!({
+            "type": "object",
+            "if": { "properties": { "kind": { "const": "file" } } },
+            "then": { "required": ["path"] },
+            "else": { "required": ["url"] }
+        });
+        assert!(check(
+            schema.clone(),
+            json!({ "kind": "file", "path": "/tmp" })
+        ));
+        assert!(!check(
+            schema.clone(),
+            json!({ "kind": "file", "url": "x" })
+        ));
+        assert!(check(schema.clone(), json!({ "kind": "link", "url": "x" })));
+        assert!(!check(schema, json!({ "kind": "link" })));
+    }
+
+    #[test]
+    fn vocabulary_declarations_keep_validation() {
+        let schema = json!({
+            "$schema": "https://json-schema.org/draft/2020-12/schema",
+            "$vocabulary": {
+                "https://json-schema.org/draft/2020-12/vocab/core": true,
+                "https://json-schema.org/draft/2020-12/vocab/applicator": true,
+                "https://json-schema.org/draft/2020-12/vocab/validation": true
+            },
+            "type": "object",
+            "required": ["name"]
+        });
+        assert!(check(schema.clone(), json!({ "name": "a" })));
+        assert!(!check(schema, json!({})));
+    }
+
+    #[test]
+    fn unevaluated_properties() {
+        let schema = json!({
+            "type": "object",
+            "properties": { "name": { "type": "string" } },
+            "allOf": [{ "properties": { "age": { "type": "integer" } } }],
+            "unevaluatedProperties": false
+        });
+        assert!(check(schema.clone(), json!({ "name": "a", "age": 3 })));
+        assert!(!check(schema, json!({ "name": "a", "extra": true })));
+    }
+
+    #[test]
+    fn prefix_items() {
+        let schema = json!({
+            "type": "array",
+            "prefixItems": [{ "type": "string" }, { "type": "integer" }],
+            "items": { "type": "boolean" }
+        });
+        assert!(check(schema.clone(), json!(["a", 1, true, false])));
+        assert!(!check(schema.clone(), json!([1, "a"])));
+        assert!(!check(schema, json!(["a", 1, "not a boolean"])));
+    }
+
+    #[test]
+    fn raw_tool_schemas_use_the_configured_draft() {
+        // A draft-07 `$schema` does not switch 2020-12 keywords off
+        let schema = json!({
+            "$schema": "http://json-schema.org/draft-07/schema#",
+            "type": "array",
+            "prefixItems": [{ "type": "string" }]
+        });
+        let validator = JsonSchemaValidator::default();
+        assert!(validator.validate_value(&schema, &json!(["a"])).is_ok());
+        assert!(matches!(
+            validator.validate_value(&schema, &json!([1])),
+            Err(ValidationError::Failed(_))
+        ));
+    }
+
+    /// `x-digits: true` requires a string of ASCII digits.
+    struct Digits;
+
+    impl CustomKeywordValidator for Digits {
+        fn validate(&self, value: &Value, schema_value: &Value) -> Result<(), ValidationError> {
+            let digits = value
+                .as_str()
+                .is_some_and(|s| !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit()));
+            if schema_value != &Value::Bool(true) || digits {
+                Ok(())
+            } else {
+                Err(ValidationError::Failed(vec![format!(
+                    "{value} is not a number"
+                )]))
+            }
+        }
+    }
+
+    #[test]
+    fn custom_keywords_run_after_standard_validation() {
+        let mut validator = JsonSchemaValidator::default();
+        validator.register_keyword("x-digits", Box::new(Digits));
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "id": { "type": "string", "x-digits": true },
+                "ids": { "type": "array", "items": { "$ref": "#/$defs/id" } }
+            },
+            "$defs": { "id": { "x-digits": true } }
+        });
+
+        assert!(
+            validator
+                .validate_value(&schema, &json!({ "id": "42", "ids": ["1", "2"] }))
+                .is_ok()
+        );
+        let Err(ValidationError::Failed(failures)) =
+            validator.validate_value(&schema, &json!({ "id": "4x", "ids": ["1", "y"] }))
+        else {
+            panic!("expected custom keyword failures");
+        };
+        assert_eq!(
+            failures,
+            vec![
+                "x-digits at \"/id\": \"4x\" is not a number",
+                "x-digits at \"/ids/1\": \"y\" is not a number",
+            ]
+        );
+
+        // Standard keywords are reported first
+        let Err(ValidationError::Failed(failures)) =
+            validator.validate_value(&schema, &json!({ "id": 7 }))
+        else {
+            panic!("expected a type error");
+        };
+        assert!(!failures[0].starts_with("x-digits"));
+
+        // Keywords also apply to schemas registered as `RootSchema`
+        let root: RootSchema = serde_json::from_value(schema).unwrap();
+        assert!(validator.validate(&root, &json!({ "id": "nope" })).is_err());
+    }
+}
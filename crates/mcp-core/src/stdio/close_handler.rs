@@ -0,0 +1,6 @@
+use std::sync::Arc;
+
+use super::close_reason::CloseReason;
+
+/// Callback invoked once when a transport closes, with the reason it closed.
+pub type CloseHandler = Arc<dyn Fn(CloseReason) + Send + Sync>;
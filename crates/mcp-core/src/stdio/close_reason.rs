@@ -0,0 +1,22 @@
+use std::fmt;
+
+/// Why a transport stopped delivering messages.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CloseReason {
+    /// The remote end closed the stream (EOF, close frame, or SSE stream end).
+    Eof,
+    /// The local side called `close()`.
+    Explicit,
+    /// The stream was torn down by a transport error.
+    Error(String),
+}
+
+impl fmt::Display for CloseReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Eof => write!(f, "eof"),
+            Self::Explicit => write!(f, "closed"),
+            Self::Error(message) => write!(f, "error: {message}"),
+        }
+    }
+}
@@ -39,4 +39,90 @@ mod tests {
         let parsed = deserialize_message(line.trim_end_matches('\n')).expect("should parse");
         assert_eq!(parsed, message);
     }
+
+    #[test]
+    fn responses_roundtrip_with_every_id_kind() {
+        use crate::types::{ErrorCode, ErrorObject, MessageId};
+
+        let error = ErrorObject::new(ErrorCode::ParseError as i32, "Parse error", None);
+        let messages = [
+            (ResultMessage::success(7, json!({})), json!(7)),
+            (ResultMessage::success("7", json!({})), json!("7")),
+            (ResultMessage::failure(MessageId::Null, error), json!(null)),
+        ];
+        for (response, id) in messages {
+            let message = JsonRpcMessage::Result(response);
+            let line = serialize_message(&message).expect("serialize should work");
+            let wire: serde_json::Value = serde_json::from_str(&line).unwrap();
+            assert_eq!(wire["id"], id);
+            let parsed = deserialize_message(line.trim_end_matches('\n')).expect("should parse");
+            assert_eq!(parsed, message);
+        }
+    }
+
+    #[test]
+    fn null_id_error_responses_parse() {
+        use crate::types::MessageId;
+
+        let line = r#"{"jsonrpc":"2.0","id":null,"error":{"code":-32700,"message":"Parse error"}}"#;
+        let Ok(JsonRpcMessage::Result(response)) = deserialize_message(line) else {
+            panic!("expected an error response");
+        };
+        assert_eq!(response.id, MessageId::Null);
+        assert_eq!(response.error.unwrap().code, -32700);
+    }
+
+    #[test]
+    fn binary_content_blocks_roundtrip() {
+        use crate::types::{CallToolResult, ContentBlock, ResourceContents};
+
+        let png = [0x89, b'P', b'N', b'G', 0x0d, 0x0a, 0x1a, 0x0a];
+        let result = CallToolResult {
+            content: vec![
+                ContentBlock::image(png, "image/png"),
+                ContentBlock::audio(b"RIFF", "audio/wav"),
+                ContentBlock::blob("gitlab://pipeline/1/graph", b"<svg/>", "image/svg+xml"),
+            ],
+            ..Default::default()
+        };
+        let message = JsonRpcMessage::Result(ResultMessage::success(
+            "1",
+            serde_json::to_value(&result).unwrap(),
+        ));
+
+        let line = serialize_message(&message).expect("serialize should work");
+        let wire: serde_json::Value = serde_json::from_str(&line).unwrap();
+        let image = &wire["result"]["content"][0];
+        assert_eq!(image["type"], "image");
+        assert_eq!(image["data"], "iVBORw0KGgo=");
+        assert_eq!(image["mimeType"], "image/png");
+        assert_eq!(wire["result"]["content"][2]["resource"]["blob"], "PHN2Zy8+");
+
+        let JsonRpcMessage::Result(parsed) =
+            deserialize_message(line.trim_end_matches('\n')).expect("should parse")
+        else {
+            panic!("expected a result");
+        };
+        let parsed: CallToolResult = serde_json::from_value(parsed.result.unwrap()).unwrap();
+        assert_eq!(parsed, result);
+        assert!(matches!(parsed.content[1], ContentBlock::Audio(_)));
+        assert!(matches!(
+            &parsed.content[2],
+            ContentBlock::EmbeddedResource(embedded)
+                if matches!(embedded.resource, ResourceContents::Blob(_))
+        ));
+    }
+
+    #[test]
+    fn unknown_content_block_types_are_rejected() {
+        use crate::types::ContentBlock;
+
+        let error = serde_json::from_value::<ContentBlock>(json!({ "type": "video", "data": "" }))
+            .unwrap_err();
+        assert!(
+            error
+                .to_string()
+                .contains("unknown content block type `video`")
+        );
+    }
 }
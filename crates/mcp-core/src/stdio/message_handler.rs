@@ -0,0 +1,4 @@
+use std::sync::Arc;
+
+/// Callback invoked for every message a transport receives.
+pub type MessageHandler<M> = Arc<dyn Fn(M) + Send + Sync>;
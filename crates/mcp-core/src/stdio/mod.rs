@@ -1,7 +1,17 @@
 pub mod buffer;
+pub mod close_handler;
+pub mod close_reason;
 pub mod message;
+pub mod message_handler;
 pub mod transport;
+pub mod transport_counters;
+pub mod transport_stats;
 
 pub use buffer::{ReadBuffer, ReadBufferError};
+pub use close_handler::CloseHandler;
+pub use close_reason::CloseReason;
 pub use message::{JsonRpcMessage, deserialize_message, serialize_message};
+pub use message_handler::MessageHandler;
 pub use transport::Transport;
+pub use transport_counters::TransportCounters;
+pub use transport_stats::TransportStats;
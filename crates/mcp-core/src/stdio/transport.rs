@@ -1,3 +1,7 @@
+use super::{
+    close_handler::CloseHandler, message_handler::MessageHandler, transport_stats::TransportStats,
+};
+
 /// Shared interface for transports that exchange JSON-RPC messages.
 pub trait Transport {
     /// The message type exchanged by the transport.
@@ -14,4 +18,28 @@ pub trait Transport {
 
     /// Close the transport and release its resources.
     fn close(&mut self) -> Result<(), Self::Error>;
+
+    /// Register a handler that receives every message from the remote
+    /// endpoint.
+    ///
+    /// The default implementation ignores the handler; transports whose
+    /// incoming messages are delivered some other way can keep it.
+    fn on_message(&mut self, handler: MessageHandler<Self::Message>) {
+        let _ = handler;
+    }
+
+    /// Register a handler that receives the [`CloseReason`](super::CloseReason)
+    /// when the transport closes.
+    ///
+    /// The default implementation ignores the handler.
+    fn on_close_with_reason(&mut self, handler: CloseHandler) {
+        let _ = handler;
+    }
+
+    /// Traffic counters accumulated since the transport was created.
+    ///
+    /// The default implementation reports no traffic.
+    fn stats(&self) -> TransportStats {
+        TransportStats::default()
+    }
 }
@@ -0,0 +1,56 @@
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Instant, SystemTime};
+
+use super::transport_stats::TransportStats;
+
+/// Lock-free counters backing [`TransportStats`].
+///
+/// Transports share one instance between their reader and writer paths; every
+/// update is a single relaxed atomic add. The connection time is only
+/// written on start.
+#[derive(Debug, Default)]
+pub struct TransportCounters {
+    messages_sent: AtomicU64,
+    messages_received: AtomicU64,
+    bytes_sent: AtomicU64,
+    bytes_received: AtomicU64,
+    connected: Mutex<Option<(Instant, SystemTime)>>,
+}
+
+impl TransportCounters {
+    /// Record one outgoing message of `bytes` bytes.
+    pub fn record_sent(&self, bytes: usize) {
+        self.messages_sent.fetch_add(1, Ordering::Relaxed);
+        self.bytes_sent.fetch_add(bytes as u64, Ordering::Relaxed);
+    }
+
+    /// Record raw bytes read from the remote end.
+    pub fn record_bytes_received(&self, bytes: usize) {
+        self.bytes_received
+            .fetch_add(bytes as u64, Ordering::Relaxed);
+    }
+
+    /// Record one decoded incoming message.
+    pub fn record_message_received(&self) {
+        self.messages_received.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Record that the transport (re)started now.
+    pub fn record_connected(&self) {
+        *self.connected.lock().unwrap() = Some((Instant::now(), SystemTime::now()));
+    }
+
+    /// Take a snapshot of the current counter values.
+    pub fn snapshot(&self) -> TransportStats {
+        let connected = *self.connected.lock().unwrap();
+        TransportStats {
+            messages_sent: self.messages_sent.load(Ordering::Relaxed),
+            messages_received: self.messages_received.load(Ordering::Relaxed),
+            bytes_sent: self.bytes_sent.load(Ordering::Relaxed),
+            bytes_received: self.bytes_received.load(Ordering::Relaxed),
+            connected_since: connected.map(|(_, since)| since),
+            uptime: connected.map(|(started, _)| started.elapsed()),
+        }
+    }
+}
@@ -0,0 +1,34 @@
+use std::fmt;
+use std::time::{Duration, SystemTime};
+
+/// Snapshot of the traffic a transport has carried since it was created.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TransportStats {
+    /// Number of JSON-RPC messages written to the remote end.
+    pub messages_sent: u64,
+    /// Number of JSON-RPC messages decoded from the remote end.
+    pub messages_received: u64,
+    /// Number of payload bytes written to the remote end.
+    pub bytes_sent: u64,
+    /// Number of raw bytes read from the remote end.
+    pub bytes_received: u64,
+    /// When the transport was last started, or `None` before it started.
+    pub connected_since: Option<SystemTime>,
+    /// Time since the transport was last started, measured on a monotonic
+    /// clock.
+    pub uptime: Option<Duration>,
+}
+
+impl fmt::Display for TransportStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "sent {} messages ({} bytes), received {} messages ({} bytes)",
+            self.messages_sent, self.bytes_sent, self.messages_received, self.bytes_received
+        )?;
+        if let Some(uptime) = self.uptime {
+            write!(f, ", up {}s", uptime.as_secs())?;
+        }
+        Ok(())
+    }
+}
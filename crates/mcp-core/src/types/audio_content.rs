@@ -1,3 +1,5 @@
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
@@ -28,4 +30,9 @@ impl AudioContent {
             meta: None,
         }
     }
+
+    /// Base64-encode `bytes` as the audio data.
+    pub fn from_bytes(bytes: impl AsRef<[u8]>, mime_type: impl Into<String>) -> Self {
+        Self::new(STANDARD.encode(bytes), mime_type)
+    }
 }
@@ -1,3 +1,5 @@
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
@@ -10,3 +12,25 @@ pub struct BlobResourceContents {
     pub base: ResourceContentsBase,
     pub blob: String,
 }
+
+impl BlobResourceContents {
+    pub fn new(uri: impl Into<String>, blob: impl Into<String>, mime_type: Option<String>) -> Self {
+        Self {
+            base: ResourceContentsBase {
+                uri: uri.into(),
+                mime_type,
+                meta: None,
+            },
+            blob: blob.into(),
+        }
+    }
+
+    /// Base64-encode `bytes` as the blob of the resource at `uri`.
+    pub fn from_bytes(
+        uri: impl Into<String>,
+        bytes: impl AsRef<[u8]>,
+        mime_type: impl Into<String>,
+    ) -> Self {
+        Self::new(uri, STANDARD.encode(bytes), Some(mime_type.into()))
+    }
+}
@@ -1,10 +1,17 @@
 use schemars::JsonSchema;
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize};
+use serde_json::Value;
 
-use super::{AudioContent, EmbeddedResource, ImageContent, ResourceLink, TextContent};
+use super::{
+    AudioContent, BlobResourceContents, EmbeddedResource, ImageContent, ResourceLink, TextContent,
+};
 
 /// Content block used in prompts and tool results.
-#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq)]
+///
+/// Serialized as the inner block, which carries its own `type` field.
+/// Deserialization dispatches on that field, since image and audio blocks
+/// share their shape.
+#[derive(Debug, Clone, Serialize, JsonSchema, PartialEq)]
 #[serde(untagged)]
 pub enum ContentBlock {
     Text(TextContent),
@@ -13,3 +20,83 @@ pub enum ContentBlock {
     ResourceLink(ResourceLink),
     EmbeddedResource(EmbeddedResource),
 }
+
+impl ContentBlock {
+    /// A text block.
+    pub fn text(text: impl Into<String>) -> Self {
+        ContentBlock::Text(TextContent::new(text))
+    }
+
+    /// An image block from raw bytes, e.g. a PNG, base64-encoded.
+    pub fn image(bytes: impl AsRef<[u8]>, mime_type: impl Into<String>) -> Self {
+        ContentBlock::Image(ImageContent::from_bytes(bytes, mime_type))
+    }
+
+    /// An audio block from raw bytes, base64-encoded.
+    pub fn audio(bytes: impl AsRef<[u8]>, mime_type: impl Into<String>) -> Self {
+        ContentBlock::Audio(AudioContent::from_bytes(bytes, mime_type))
+    }
+
+    /// A file embedded as a blob resource at `uri`, base64-encoded.
+    pub fn blob(
+        uri: impl Into<String>,
+        bytes: impl AsRef<[u8]>,
+        mime_type: impl Into<String>,
+    ) -> Self {
+        let contents = BlobResourceContents::from_bytes(uri, bytes, mime_type);
+        ContentBlock::EmbeddedResource(EmbeddedResource::new(contents.into()))
+    }
+}
+
+impl<'de> Deserialize<'de> for ContentBlock {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = Value::deserialize(deserializer)?;
+        let kind = value
+            .get("type")
+            .and_then(Value::as_str)
+            .unwrap_or_default();
+        let block = match kind {
+            "text" => serde_json::from_value(value).map(ContentBlock::Text),
+            "image" => serde_json::from_value(value).map(ContentBlock::Image),
+            "audio" => serde_json::from_value(value).map(ContentBlock::Audio),
+            "resource_link" => serde_json::from_value(value).map(ContentBlock::ResourceLink),
+            "resource" => serde_json::from_value(value).map(ContentBlock::EmbeddedResource),
+            other => {
+                return Err(serde::de::Error::custom(format!(
+                    "unknown content block type `{other}`"
+                )));
+            }
+        };
+        block.map_err(serde::de::Error::custom)
+    }
+}
+
+impl From<TextContent> for ContentBlock {
+    fn from(content: TextContent) -> Self {
+        ContentBlock::Text(content)
+    }
+}
+
+impl From<ImageContent> for ContentBlock {
+    fn from(content: ImageContent) -> Self {
+        ContentBlock::Image(content)
+    }
+}
+
+impl From<AudioContent> for ContentBlock {
+    fn from(content: AudioContent) -> Self {
+        ContentBlock::Audio(content)
+    }
+}
+
+impl From<ResourceLink> for ContentBlock {
+    fn from(link: ResourceLink) -> Self {
+        ContentBlock::ResourceLink(link)
+    }
+}
+
+impl From<EmbeddedResource> for ContentBlock {
+    fn from(resource: EmbeddedResource) -> Self {
+        ContentBlock::EmbeddedResource(resource)
+    }
+}
@@ -81,6 +81,18 @@ impl CreateMessageResultWithTools {
     }
 }
 
+impl From<CreateMessageResult> for CreateMessageResultWithTools {
+    fn from(result: CreateMessageResult) -> Self {
+        Self {
+            model: result.model,
+            stop_reason: result.stop_reason,
+            role: result.role,
+            content: SamplingMessageContent::from(result.content).into(),
+            meta: result.meta,
+        }
+    }
+}
+
 impl From<SamplingMessageContent> for CreateMessageContentOrArray {
     fn from(content: SamplingMessageContent) -> Self {
         CreateMessageContentOrArray::Single(content)
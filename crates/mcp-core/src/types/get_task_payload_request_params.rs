@@ -1,7 +1,7 @@
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-use super::RequestParams;
+use super::{RequestParams, TaskResultRange};
 
 /// Parameters for tasks/result.
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq)]
@@ -10,4 +10,7 @@ pub struct GetTaskPayloadRequestParams {
     pub base: RequestParams,
     #[serde(rename = "taskId")]
     pub task_id: String,
+    /// Read only part of the serialized result.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub range: Option<TaskResultRange>,
 }
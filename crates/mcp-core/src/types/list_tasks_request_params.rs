@@ -0,0 +1,17 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use super::{PaginatedRequestParams, TaskStatus};
+
+/// Parameters for tasks/list.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq, Default)]
+pub struct ListTasksRequestParams {
+    #[serde(flatten)]
+    pub pagination: PaginatedRequestParams,
+    /// Only return tasks currently in this status.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<TaskStatus>,
+    /// Only return tasks created strictly after this ISO 8601 timestamp.
+    #[serde(rename = "createdAfter", skip_serializing_if = "Option::is_none")]
+    pub created_after: Option<String>,
+}
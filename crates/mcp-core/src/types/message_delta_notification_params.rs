@@ -0,0 +1,28 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::types::{MessageId, SamplingContent};
+
+/// Parameters for a `notifications/message/delta` notification, carrying one
+/// piece of a streamed `sampling/createMessage` response.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq)]
+pub struct MessageDeltaNotificationParams {
+    /// The ID of the `sampling/createMessage` request being answered.
+    #[serde(rename = "requestId")]
+    pub request_id: MessageId,
+    /// The next piece of the generated message.
+    pub delta: SamplingContent,
+    #[serde(rename = "_meta", skip_serializing_if = "Option::is_none")]
+    pub meta: Option<Value>,
+}
+
+impl MessageDeltaNotificationParams {
+    pub fn new(request_id: MessageId, delta: SamplingContent) -> Self {
+        Self {
+            request_id,
+            delta,
+            meta: None,
+        }
+    }
+}
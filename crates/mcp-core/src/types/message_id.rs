@@ -1,3 +1,6 @@
+use std::convert::Infallible;
+use std::str::FromStr;
+
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
@@ -7,22 +10,64 @@ use serde::{Deserialize, Serialize};
 pub enum MessageId {
     String(String),
     Number(i64),
+    /// `null`, used by error responses to requests whose id could not be
+    /// read, e.g. unparseable JSON. Requests must not use it.
+    Null,
 }
 
 impl MessageId {
     pub fn as_str(&self) -> Option<&str> {
         match self {
             MessageId::String(value) => Some(value),
-            MessageId::Number(_) => None,
+            MessageId::Number(_) | MessageId::Null => None,
         }
     }
+
+    pub fn as_number(&self) -> Option<i64> {
+        match self {
+            MessageId::Number(value) => Some(*value),
+            MessageId::String(_) | MessageId::Null => None,
+        }
+    }
+
+    pub fn is_null(&self) -> bool {
+        matches!(self, MessageId::Null)
+    }
+
+    /// Parse an id, always keeping it as a string.
+    ///
+    /// Use this where ids are opaque labels, so `"42"` stays `String("42")`
+    /// instead of becoming `Number(42)` as with [`str::parse`].
+    pub fn parse_preferring_string(value: &str) -> Self {
+        MessageId::String(value.to_string())
+    }
+}
+
+impl FromStr for MessageId {
+    type Err = Infallible;
+
+    /// All-digit input that fits in an `i64` becomes `Number`; anything else,
+    /// including digits with a leading zero (which would not round-trip),
+    /// stays a `String`.
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let numeric = !value.is_empty()
+            && value.bytes().all(|byte| byte.is_ascii_digit())
+            && (value == "0" || !value.starts_with('0'));
+        Ok(match value.parse::<i64>() {
+            Ok(number) if numeric => MessageId::Number(number),
+            _ => MessageId::String(value.to_string()),
+        })
+    }
 }
 
+/// Writes string and numeric ids as they are and `Null` as `(null)`, so it
+/// cannot be mistaken for the string id `"null"`.
 impl std::fmt::Display for MessageId {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             MessageId::String(value) => write!(f, "{value}"),
             MessageId::Number(value) => write!(f, "{value}"),
+            MessageId::Null => f.write_str("(null)"),
         }
     }
 }
@@ -44,3 +89,59 @@ impl From<i64> for MessageId {
         MessageId::Number(value)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_digits_as_number() {
+        assert_eq!("42".parse(), Ok(MessageId::Number(42)));
+        assert_eq!("0".parse(), Ok(MessageId::Number(0)));
+    }
+
+    #[test]
+    fn parses_other_input_as_string() {
+        for value in ["client-initialize", "", "-1", "007", "4 2", "99999999999999999999"] {
+            assert_eq!(value.parse(), Ok(MessageId::String(value.to_string())));
+        }
+    }
+
+    #[test]
+    fn ids_round_trip_through_json() {
+        let cases = [
+            (MessageId::Number(7), "7"),
+            (MessageId::String("7".to_string()), r#""7""#),
+            (MessageId::Null, "null"),
+        ];
+        for (id, json) in cases {
+            assert_eq!(serde_json::to_string(&id).unwrap(), json);
+            assert_eq!(serde_json::from_str::<MessageId>(json).unwrap(), id);
+        }
+    }
+
+    #[test]
+    fn accessors_match_the_variant() {
+        assert_eq!(MessageId::Number(7).as_number(), Some(7));
+        assert_eq!(MessageId::Number(7).as_str(), None);
+        assert_eq!(MessageId::from("7").as_str(), Some("7"));
+        assert_eq!(MessageId::from("7").as_number(), None);
+        assert_eq!(MessageId::Null.as_str(), None);
+        assert_eq!(MessageId::Null.as_number(), None);
+        assert!(MessageId::Null.is_null());
+        assert_eq!(MessageId::Null.to_string(), "(null)");
+        assert_eq!(MessageId::from("null").to_string(), "null");
+    }
+
+    #[test]
+    fn string_preferring_parse_keeps_digits() {
+        assert_eq!(
+            MessageId::parse_preferring_string("42"),
+            MessageId::String("42".to_string())
+        );
+        assert_eq!(
+            MessageId::parse_preferring_string("client-initialize"),
+            MessageId::String("client-initialize".to_string())
+        );
+    }
+}
@@ -43,11 +43,13 @@ pub mod jsonrpc_version;
 pub mod list_prompts_result;
 pub mod list_resource_templates_result;
 pub mod list_resources_result;
+pub mod list_tasks_request_params;
 pub mod list_tasks_result;
 pub mod list_tools_result;
 pub mod logging_level;
 pub mod logging_message_params;
 pub mod message;
+pub mod message_delta_notification_params;
 pub mod message_id;
 pub mod model_hint;
 pub mod model_preferences;
@@ -94,6 +96,8 @@ pub mod stop_reason;
 pub mod task;
 pub mod task_creation_params;
 pub mod task_metadata;
+pub mod task_result_chunk;
+pub mod task_result_range;
 pub mod task_status;
 pub mod task_status_notification_params;
 pub mod task_support;
@@ -157,11 +161,13 @@ pub use jsonrpc_version::JSONRPC_VERSION;
 pub use list_prompts_result::ListPromptsResult;
 pub use list_resource_templates_result::ListResourceTemplatesResult;
 pub use list_resources_result::ListResourcesResult;
+pub use list_tasks_request_params::ListTasksRequestParams;
 pub use list_tasks_result::ListTasksResult;
 pub use list_tools_result::ListToolsResult;
 pub use logging_level::LoggingLevel;
 pub use logging_message_params::LoggingMessageParams;
 pub use message::Message;
+pub use message_delta_notification_params::MessageDeltaNotificationParams;
 pub use message_id::MessageId;
 pub use model_hint::ModelHint;
 pub use model_preferences::ModelPreferences;
@@ -211,6 +217,8 @@ pub use stop_reason::StopReason;
 pub use task::Task;
 pub use task_creation_params::TaskCreationParams;
 pub use task_metadata::TaskMetadata;
+pub use task_result_chunk::TaskResultChunk;
+pub use task_result_range::TaskResultRange;
 pub use task_status::TaskStatus;
 pub use task_status_notification_params::TaskStatusNotificationParams;
 pub use task_support::TaskSupport;
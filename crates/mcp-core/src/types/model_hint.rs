@@ -1,5 +1,7 @@
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
 
 /// Hints to use for model selection.
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq, Default)]
@@ -7,12 +9,16 @@ pub struct ModelHint {
     /// A hint for a model name.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub name: Option<String>,
+    /// Fields not known to this version, kept so relayed hints round-trip.
+    #[serde(flatten, default, skip_serializing_if = "HashMap::is_empty")]
+    pub extra: HashMap<String, Value>,
 }
 
 impl ModelHint {
     pub fn new(name: impl Into<String>) -> Self {
         Self {
             name: Some(name.into()),
+            extra: HashMap::new(),
         }
     }
 }
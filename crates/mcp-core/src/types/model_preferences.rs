@@ -1,5 +1,7 @@
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
 
 use super::ModelHint;
 
@@ -18,4 +20,45 @@ pub struct ModelPreferences {
     /// How much to prioritize intelligence and capabilities when selecting a model (0-1).
     #[serde(rename = "intelligencePriority", skip_serializing_if = "Option::is_none")]
     pub intelligence_priority: Option<f64>,
+    /// Fields not known to this version, kept so preferences relayed to a
+    /// sampling backend round-trip unchanged.
+    #[serde(flatten, default, skip_serializing_if = "HashMap::is_empty")]
+    pub extra: HashMap<String, Value>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_unknown_fields_round_trip() {
+        let value = json!({
+            "hints": [{ "name": "claude", "provider": "anthropic" }],
+            "costPriority": 0.3,
+            "reasoningPriority": 0.9,
+            "budget": { "maxUsd": 1.5 }
+        });
+
+        let preferences: ModelPreferences = serde_json::from_value(value.clone()).unwrap();
+        assert_eq!(preferences.cost_priority, Some(0.3));
+        assert_eq!(preferences.extra["reasoningPriority"], json!(0.9));
+        let hint = &preferences.hints.as_ref().unwrap()[0];
+        assert_eq!(hint.name.as_deref(), Some("claude"));
+        assert_eq!(hint.extra["provider"], json!("anthropic"));
+
+        assert_eq!(serde_json::to_value(&preferences).unwrap(), value);
+    }
+
+    #[test]
+    fn test_known_fields_only_serialize_without_extra() {
+        let preferences = ModelPreferences {
+            hints: Some(vec![ModelHint::new("gpt-4")]),
+            ..Default::default()
+        };
+        assert_eq!(
+            serde_json::to_value(&preferences).unwrap(),
+            json!({ "hints": [{ "name": "gpt-4" }] })
+        );
+    }
 }
@@ -19,4 +19,37 @@ impl NotificationMessage {
             params,
         }
     }
+
+    /// The part of the method before the first `/` (`"notifications"` for
+    /// `notifications/initialized`), or `None` for methods without a namespace.
+    pub fn method_namespace(&self) -> Option<&str> {
+        self.method.split_once('/').map(|(namespace, _)| namespace)
+    }
+
+    /// The part of the method after the first `/`, or the whole method when
+    /// it has no namespace.
+    pub fn method_action(&self) -> &str {
+        self.method
+            .split_once('/')
+            .map_or(self.method.as_str(), |(_, action)| action)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn splits_namespaced_notification() {
+        let notification = NotificationMessage::new("notifications/tasks/status", None);
+        assert_eq!(notification.method_namespace(), Some("notifications"));
+        assert_eq!(notification.method_action(), "tasks/status");
+    }
+
+    #[test]
+    fn notification_without_namespace() {
+        let notification = NotificationMessage::new("ping", None);
+        assert_eq!(notification.method_namespace(), None);
+        assert_eq!(notification.method_action(), "ping");
+    }
 }
@@ -2,6 +2,8 @@ use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
+use crate::schema::ValidationError;
+
 /// Primitive schema definition for boolean fields.
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq)]
 pub struct BooleanSchema {
@@ -208,6 +210,156 @@ pub enum PrimitiveSchemaDefinition {
     TitledEnum(TitledEnumSchema),
 }
 
+impl PrimitiveSchemaDefinition {
+    /// Check one form value against this definition, describing the
+    /// mismatch on failure.
+    pub fn check(&self, value: &ElicitationValue) -> Result<(), String> {
+        match (self, value) {
+            (Self::Boolean(_), ElicitationValue::Boolean(_)) => Ok(()),
+            (Self::Boolean(_), _) => Err("must be a boolean".to_string()),
+            (Self::String(schema), ElicitationValue::String(text)) => schema.check(text),
+            (Self::String(_), _) => Err("must be a string".to_string()),
+            (Self::Number(schema), ElicitationValue::Number(number)) => schema.check(*number),
+            (Self::Number(_), _) => Err("must be a number".to_string()),
+            (Self::UntitledEnum(schema), value) => {
+                check_choice(value, schema.enum_values.iter().map(String::as_str))
+            }
+            (Self::TitledEnum(schema), value) => check_choice(
+                value,
+                schema.one_of.iter().map(|option| option.const_value.as_str()),
+            ),
+        }
+    }
+
+    /// Convert `value` to this definition's type where the intent is
+    /// unambiguous: numeric strings for numbers and `true`/`false` for
+    /// booleans. Anything else is returned unchanged.
+    pub fn coerce(&self, value: ElicitationValue) -> ElicitationValue {
+        match (self, value) {
+            (Self::Number(_), ElicitationValue::String(text)) => match text.trim().parse::<f64>() {
+                Ok(number) if number.is_finite() => ElicitationValue::Number(number),
+                _ => ElicitationValue::String(text),
+            },
+            (Self::Boolean(_), ElicitationValue::String(text)) => {
+                match text.trim().to_ascii_lowercase().as_str() {
+                    "true" => ElicitationValue::Boolean(true),
+                    "false" => ElicitationValue::Boolean(false),
+                    _ => ElicitationValue::String(text),
+                }
+            }
+            (_, value) => value,
+        }
+    }
+}
+
+impl StringSchema {
+    fn check(&self, text: &str) -> Result<(), String> {
+        let length = text.chars().count();
+        if let Some(min) = self.min_length.filter(|min| length < *min) {
+            return Err(format!("must be at least {min} characters"));
+        }
+        if let Some(max) = self.max_length.filter(|max| length > *max) {
+            return Err(format!("must be at most {max} characters"));
+        }
+        match &self.format {
+            Some(format) if !format.matches(text) => {
+                Err(format!("must be a valid {}", format.name()))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+impl StringFormat {
+    fn name(&self) -> &'static str {
+        match self {
+            Self::Email => "email address",
+            Self::Uri => "URI",
+            Self::Date => "date",
+            Self::DateTime => "date-time",
+        }
+    }
+
+    /// Loose syntactic check, enough to catch values typed into the wrong field.
+    fn matches(&self, text: &str) -> bool {
+        match self {
+            Self::Email => text.split_once('@').is_some_and(|(local, domain)| {
+                !local.is_empty()
+                    && domain.contains('.')
+                    && !domain.contains('@')
+                    && !text.contains(char::is_whitespace)
+            }),
+            Self::Uri => text.split_once(':').is_some_and(|(scheme, _)| {
+                scheme.starts_with(|c: char| c.is_ascii_alphabetic())
+                    && scheme
+                        .chars()
+                        .all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'))
+            }),
+            Self::Date => is_date(text),
+            Self::DateTime => text
+                .split_once(['T', 't'])
+                .is_some_and(|(date, time)| is_date(date) && is_time(time)),
+        }
+    }
+}
+
+/// `YYYY-MM-DD`, as in RFC 3339 `full-date`.
+fn is_date(text: &str) -> bool {
+    let parts: Vec<&str> = text.split('-').collect();
+    let [year, month, day] = parts.as_slice() else {
+        return false;
+    };
+    year.len() == 4
+        && year.chars().all(|c| c.is_ascii_digit())
+        && in_range(month, 1, 12)
+        && in_range(day, 1, 31)
+}
+
+/// `HH:MM:SS` followed by optional fraction and offset, as in RFC 3339.
+fn is_time(text: &str) -> bool {
+    let (Some(hour), Some(minute), Some(second)) =
+        (text.get(0..2), text.get(3..5), text.get(6..8))
+    else {
+        return false;
+    };
+    text.get(2..3) == Some(":")
+        && text.get(5..6) == Some(":")
+        && in_range(hour, 0, 23)
+        && in_range(minute, 0, 59)
+        && in_range(second, 0, 60)
+}
+
+fn in_range(digits: &str, min: u32, max: u32) -> bool {
+    digits.len() == 2
+        && digits.chars().all(|c| c.is_ascii_digit())
+        && digits.parse().is_ok_and(|value: u32| (min..=max).contains(&value))
+}
+
+impl NumberSchema {
+    fn check(&self, number: f64) -> Result<(), String> {
+        if self.kind == NumberType::Integer && number.fract() != 0.0 {
+            return Err("must be an integer".to_string());
+        }
+        if let Some(minimum) = self.minimum.filter(|minimum| number < *minimum) {
+            return Err(format!("must be at least {minimum}"));
+        }
+        if let Some(maximum) = self.maximum.filter(|maximum| number > *maximum) {
+            return Err(format!("must be at most {maximum}"));
+        }
+        Ok(())
+    }
+}
+
+fn check_choice<'a>(
+    value: &ElicitationValue,
+    mut choices: impl Iterator<Item = &'a str>,
+) -> Result<(), String> {
+    match value {
+        ElicitationValue::String(text) if choices.any(|choice| choice == text) => Ok(()),
+        _ => Err("must be one of the listed options".to_string()),
+    }
+}
+
 /// A restricted subset of JSON Schema for elicitation forms.
 /// Only top-level properties are allowed, without nesting.
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq)]
@@ -241,6 +393,51 @@ impl ElicitationSchema {
         self.required = Some(fields);
         self
     }
+
+    /// Check form content returned by the client against this schema.
+    ///
+    /// Required fields must be present and each value must match its
+    /// property's type and constraints; fields the schema does not declare
+    /// are ignored. Every problem found is reported.
+    pub fn validate(
+        &self,
+        content: &HashMap<String, ElicitationValue>,
+    ) -> Result<(), ValidationError> {
+        let mut problems: Vec<String> = self
+            .required
+            .iter()
+            .flatten()
+            .filter(|field| !content.contains_key(*field))
+            .map(|field| format!("`{field}` is required"))
+            .collect();
+
+        let mut fields: Vec<_> = content.iter().collect();
+        fields.sort_by(|a, b| a.0.cmp(b.0));
+        for (name, value) in fields {
+            let Some(schema) = self.properties.get(name) else {
+                continue;
+            };
+            if let Err(problem) = schema.check(value) {
+                problems.push(format!("`{name}` {problem}"));
+            }
+        }
+
+        if problems.is_empty() {
+            Ok(())
+        } else {
+            Err(ValidationError::Failed(problems))
+        }
+    }
+
+    /// [`coerce`](PrimitiveSchemaDefinition::coerce) every field of
+    /// `content` the schema declares.
+    pub fn coerce(&self, content: &mut HashMap<String, ElicitationValue>) {
+        for (name, value) in content.iter_mut() {
+            if let Some(schema) = self.properties.get(name) {
+                *value = schema.coerce(value.clone());
+            }
+        }
+    }
 }
 
 impl Default for ElicitationSchema {
@@ -294,3 +491,119 @@ impl From<Vec<String>> for ElicitationValue {
         ElicitationValue::StringArray(arr)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn schema() -> ElicitationSchema {
+        let mut name = StringSchema::new();
+        name.min_length = Some(2);
+        name.max_length = Some(5);
+        let mut age = NumberSchema::integer();
+        age.minimum = Some(0.0);
+        age.maximum = Some(150.0);
+        ElicitationSchema::new()
+            .with_property("name", PrimitiveSchemaDefinition::String(name))
+            .with_property("age", PrimitiveSchemaDefinition::Number(age))
+            .with_property(
+                "color",
+                PrimitiveSchemaDefinition::UntitledEnum(UntitledEnumSchema::new(vec![
+                    "red".to_string(),
+                    "blue".to_string(),
+                ])),
+            )
+            .with_required(vec!["name".to_string(), "age".to_string()])
+    }
+
+    fn content(values: &[(&str, ElicitationValue)]) -> HashMap<String, ElicitationValue> {
+        values
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.clone()))
+            .collect()
+    }
+
+    fn problems(values: &[(&str, ElicitationValue)]) -> Vec<String> {
+        match schema().validate(&content(values)) {
+            Ok(()) => Vec::new(),
+            Err(ValidationError::Failed(problems)) => problems,
+            Err(other) => panic!("unexpected error: {other}"),
+        }
+    }
+
+    #[test]
+    fn accepts_matching_content() {
+        let values = [
+            ("name", "Ada".into()),
+            ("age", 36.into()),
+            ("color", "red".into()),
+            ("extra", true.into()),
+        ];
+        assert!(problems(&values).is_empty());
+    }
+
+    #[test]
+    fn reports_every_problem() {
+        let values = [("name", "A".into()), ("color", "green".into())];
+        assert_eq!(
+            problems(&values),
+            vec![
+                "`age` is required",
+                "`color` must be one of the listed options",
+                "`name` must be at least 2 characters",
+            ]
+        );
+    }
+
+    #[test]
+    fn checks_types_and_number_constraints() {
+        assert_eq!(
+            problems(&[("name", true.into()), ("age", 36.5.into())]),
+            vec!["`age` must be an integer", "`name` must be a string"]
+        );
+        assert_eq!(
+            problems(&[("name", "Ada".into()), ("age", (-1).into())]),
+            vec!["`age` must be at least 0"]
+        );
+    }
+
+    #[test]
+    fn coerces_unambiguous_strings() {
+        let mut values = content(&[
+            ("name", "42".into()),
+            ("age", " 36 ".into()),
+            ("color", "red".into()),
+            ("extra", "7".into()),
+        ]);
+        schema().coerce(&mut values);
+        assert_eq!(values["name"], "42".into());
+        assert_eq!(values["age"], 36.into());
+        assert_eq!(values["extra"], "7".into());
+
+        let flag = PrimitiveSchemaDefinition::Boolean(BooleanSchema::new());
+        assert_eq!(flag.coerce("True".into()), true.into());
+        assert_eq!(flag.coerce("yes".into()), "yes".into());
+        let number = PrimitiveSchemaDefinition::Number(NumberSchema::new());
+        assert_eq!(number.coerce("NaN".into()), "NaN".into());
+    }
+
+    #[test]
+    fn checks_string_formats() {
+        let cases = [
+            (StringFormat::Email, "ada@example.com", "ada@", "ada example@x.org"),
+            (StringFormat::Uri, "https://example.com", "example.com", "1http://x"),
+            (StringFormat::Date, "2024-02-29", "2024-13-01", "24-01-01"),
+            (
+                StringFormat::DateTime,
+                "2024-02-29T12:30:00Z",
+                "2024-02-29",
+                "2024-02-29T25:00:00Z",
+            ),
+        ];
+        for (format, valid, invalid, also_invalid) in cases {
+            assert!(format.matches(valid), "{valid}");
+            assert!(!format.matches(invalid), "{invalid}");
+            assert!(!format.matches(also_invalid), "{also_invalid}");
+        }
+    }
+}
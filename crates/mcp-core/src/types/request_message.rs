@@ -24,4 +24,44 @@ impl RequestMessage {
             params,
         }
     }
+
+    /// The part of the method before the first `/` (`"tools"` for
+    /// `tools/call`), or `None` for methods without a namespace.
+    pub fn method_namespace(&self) -> Option<&str> {
+        self.method.split_once('/').map(|(namespace, _)| namespace)
+    }
+
+    /// The part of the method after the first `/` (`"call"` for
+    /// `tools/call`), or the whole method when it has no namespace.
+    pub fn method_action(&self) -> &str {
+        self.method
+            .split_once('/')
+            .map_or(self.method.as_str(), |(_, action)| action)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn splits_standard_method() {
+        let request = RequestMessage::new("1", "tools/call", Value::Null);
+        assert_eq!(request.method_namespace(), Some("tools"));
+        assert_eq!(request.method_action(), "call");
+    }
+
+    #[test]
+    fn splits_custom_method_at_first_slash() {
+        let request = RequestMessage::new("1", "gitlab/issues/list", Value::Null);
+        assert_eq!(request.method_namespace(), Some("gitlab"));
+        assert_eq!(request.method_action(), "issues/list");
+    }
+
+    #[test]
+    fn method_without_namespace() {
+        let request = RequestMessage::new("1", "initialize", Value::Null);
+        assert_eq!(request.method_namespace(), None);
+        assert_eq!(request.method_action(), "initialize");
+    }
 }
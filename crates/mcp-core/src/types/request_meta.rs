@@ -1,5 +1,7 @@
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
 
 use super::{ProgressToken, RelatedTaskMetadata};
 
@@ -15,4 +17,7 @@ pub struct RequestMeta {
         skip_serializing_if = "Option::is_none"
     )]
     pub related_task: Option<RelatedTaskMetadata>,
+    /// Any other `_meta` entries, such as implementation-specific flags.
+    #[serde(flatten, default, skip_serializing_if = "HashMap::is_empty")]
+    pub extra: HashMap<String, Value>,
 }
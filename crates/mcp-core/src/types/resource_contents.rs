@@ -10,3 +10,15 @@ pub enum ResourceContents {
     Text(TextResourceContents),
     Blob(BlobResourceContents),
 }
+
+impl From<TextResourceContents> for ResourceContents {
+    fn from(contents: TextResourceContents) -> Self {
+        ResourceContents::Text(contents)
+    }
+}
+
+impl From<BlobResourceContents> for ResourceContents {
+    fn from(contents: BlobResourceContents) -> Self {
+        ResourceContents::Blob(contents)
+    }
+}
@@ -1,18 +1,43 @@
 use schemars::JsonSchema;
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize};
+use serde_json::Value;
 
 use super::{AudioContent, ImageContent, TextContent};
 
 /// Basic content types for sampling responses (without tool use).
 /// Used for backwards-compatible CreateMessageResult when tools are not used.
-#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq)]
-#[serde(tag = "type", rename_all = "lowercase")]
+/// Serialized as the inner block, which carries its own `type` field.
+/// Deserialization dispatches on that field, since image and audio blocks
+/// share their shape.
+#[derive(Debug, Clone, Serialize, JsonSchema, PartialEq)]
+#[serde(untagged)]
 pub enum SamplingContent {
     Text(TextContent),
     Image(ImageContent),
     Audio(AudioContent),
 }
 
+impl<'de> Deserialize<'de> for SamplingContent {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = Value::deserialize(deserializer)?;
+        let kind = value
+            .get("type")
+            .and_then(Value::as_str)
+            .unwrap_or_default();
+        let content = match kind {
+            "text" => serde_json::from_value(value).map(SamplingContent::Text),
+            "image" => serde_json::from_value(value).map(SamplingContent::Image),
+            "audio" => serde_json::from_value(value).map(SamplingContent::Audio),
+            other => {
+                return Err(serde::de::Error::custom(format!(
+                    "unknown sampling content type `{other}`"
+                )));
+            }
+        };
+        content.map_err(serde::de::Error::custom)
+    }
+}
+
 impl From<TextContent> for SamplingContent {
     fn from(content: TextContent) -> Self {
         SamplingContent::Text(content)
@@ -1,25 +1,49 @@
 use schemars::JsonSchema;
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize};
+use serde_json::Value;
 
-use super::{AudioContent, ImageContent, TextContent, ToolResultContent, ToolUseContent};
+use super::{
+    AudioContent, ImageContent, SamplingContent, TextContent, ToolResultContent, ToolUseContent,
+};
 
 /// Content block types allowed in sampling messages.
 /// This includes text, image, audio, tool use requests, and tool results.
-#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq)]
-#[serde(tag = "type")]
+/// Serialized as the inner block, which carries its own `type` field.
+/// Deserialization dispatches on that field, since image and audio blocks
+/// share their shape.
+#[derive(Debug, Clone, Serialize, JsonSchema, PartialEq)]
+#[serde(untagged)]
 pub enum SamplingMessageContent {
-    #[serde(rename = "text")]
     Text(TextContent),
-    #[serde(rename = "image")]
     Image(ImageContent),
-    #[serde(rename = "audio")]
     Audio(AudioContent),
-    #[serde(rename = "tool_use")]
     ToolUse(ToolUseContent),
-    #[serde(rename = "tool_result")]
     ToolResult(ToolResultContent),
 }
 
+impl<'de> Deserialize<'de> for SamplingMessageContent {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = Value::deserialize(deserializer)?;
+        let kind = value
+            .get("type")
+            .and_then(Value::as_str)
+            .unwrap_or_default();
+        let content = match kind {
+            "text" => serde_json::from_value(value).map(SamplingMessageContent::Text),
+            "image" => serde_json::from_value(value).map(SamplingMessageContent::Image),
+            "audio" => serde_json::from_value(value).map(SamplingMessageContent::Audio),
+            "tool_use" => serde_json::from_value(value).map(SamplingMessageContent::ToolUse),
+            "tool_result" => serde_json::from_value(value).map(SamplingMessageContent::ToolResult),
+            other => {
+                return Err(serde::de::Error::custom(format!(
+                    "unknown sampling content type `{other}`"
+                )));
+            }
+        };
+        content.map_err(serde::de::Error::custom)
+    }
+}
+
 impl From<TextContent> for SamplingMessageContent {
     fn from(content: TextContent) -> Self {
         SamplingMessageContent::Text(content)
@@ -49,3 +73,47 @@ impl From<ToolResultContent> for SamplingMessageContent {
         SamplingMessageContent::ToolResult(content)
     }
 }
+
+impl From<SamplingContent> for SamplingMessageContent {
+    fn from(content: SamplingContent) -> Self {
+        match content {
+            SamplingContent::Text(text) => SamplingMessageContent::Text(text),
+            SamplingContent::Image(image) => SamplingMessageContent::Image(image),
+            SamplingContent::Audio(audio) => SamplingMessageContent::Audio(audio),
+        }
+    }
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde_json::json;
+
+    #[test]
+    fn audio_round_trips_as_audio() {
+        let audio = json!({ "type": "audio", "data": "UklGRg==", "mimeType": "audio/wav" });
+
+        let content: SamplingContent = serde_json::from_value(audio.clone()).unwrap();
+        assert!(matches!(content, SamplingContent::Audio(_)));
+        assert_eq!(serde_json::to_value(&content).unwrap(), audio);
+
+        let content: SamplingMessageContent = serde_json::from_value(audio.clone()).unwrap();
+        assert!(matches!(content, SamplingMessageContent::Audio(_)));
+        assert_eq!(serde_json::to_value(&content).unwrap(), audio);
+    }
+
+    #[test]
+    fn dispatches_on_the_type_field() {
+        let image = json!({ "type": "image", "data": "iVBORw0KGgo=", "mimeType": "image/png" });
+        let content: SamplingMessageContent = serde_json::from_value(image).unwrap();
+        assert!(matches!(content, SamplingMessageContent::Image(_)));
+
+        let tool_use =
+            json!({ "type": "tool_use", "id": "call-1", "name": "weather", "input": {} });
+        let content: SamplingMessageContent = serde_json::from_value(tool_use).unwrap();
+        assert!(matches!(content, SamplingMessageContent::ToolUse(_)));
+
+        let unknown = json!({ "type": "video", "data": "" });
+        assert!(serde_json::from_value::<SamplingContent>(unknown).is_err());
+    }
+}
@@ -0,0 +1,17 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Piece of a serialized task result returned by a ranged tasks/result.
+///
+/// `data` is a slice of the result's JSON text. It ends on a UTF-8 character
+/// boundary, so it may be a few bytes shorter than requested.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq)]
+pub struct TaskResultChunk {
+    pub data: String,
+    pub offset: u64,
+    /// Offset to request next, absent once the end is reached.
+    #[serde(rename = "nextOffset", skip_serializing_if = "Option::is_none")]
+    pub next_offset: Option<u64>,
+    #[serde(rename = "totalBytes")]
+    pub total_bytes: u64,
+}
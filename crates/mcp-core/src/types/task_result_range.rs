@@ -0,0 +1,12 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Byte range of a stored task result, for reading large results in pieces.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq, Default)]
+pub struct TaskResultRange {
+    /// Byte offset into the serialized result.
+    pub offset: u64,
+    /// Maximum number of bytes to return; the rest of the result if omitted.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub length: Option<u64>,
+}
@@ -0,0 +1,7 @@
+//! RFC 6570 URI templates, as used by resource templates.
+
+pub mod uri_template;
+pub mod uri_template_error;
+
+pub use uri_template::UriTemplate;
+pub use uri_template_error::UriTemplateError;
@@ -0,0 +1,409 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use super::uri_template_error::UriTemplateError;
+
+/// How an expression's value is encoded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Operator {
+    /// `{var}`: everything but unreserved characters is percent-encoded.
+    Simple,
+    /// `{+var}`: reserved characters such as `/` are kept.
+    Reserved,
+    /// `{#var}`: like `Reserved`, prefixed with `#` when defined.
+    Fragment,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Part {
+    Literal(String),
+    Expression { operator: Operator, name: String },
+}
+
+/// A URI template (RFC 6570) limited to levels 1 and 2: simple string
+/// expansion `{var}`, reserved expansion `{+var}` and fragment expansion
+/// `{#var}`, one variable per expression.
+///
+/// Besides expanding variables into a URI, a template can match a concrete
+/// URI and extract the variables back out of it.
+///
+/// ```
+/// use mcp_core::uri_template::UriTemplate;
+///
+/// let template = UriTemplate::parse("gitlab://project/{id}/issues").unwrap();
+/// let variables = template.match_uri("gitlab://project/group%2Fapp/issues").unwrap();
+/// assert_eq!(variables["id"], "group/app");
+/// assert_eq!(template.expand(&variables), "gitlab://project/group%2Fapp/issues");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UriTemplate {
+    template: String,
+    parts: Vec<Part>,
+}
+
+impl UriTemplate {
+    pub fn parse(template: &str) -> Result<Self, UriTemplateError> {
+        let mut parts = Vec::new();
+        let mut literal = String::new();
+        let mut chars = template.char_indices();
+
+        while let Some((offset, ch)) = chars.next() {
+            match ch {
+                '{' => {
+                    let mut expression = String::new();
+                    let mut closed = false;
+                    for (_, ch) in chars.by_ref() {
+                        if ch == '}' {
+                            closed = true;
+                            break;
+                        }
+                        expression.push(ch);
+                    }
+                    if !closed {
+                        return Err(UriTemplateError::UnclosedExpression(offset));
+                    }
+                    if !literal.is_empty() {
+                        parts.push(Part::Literal(std::mem::take(&mut literal)));
+                    }
+                    parts.push(parse_expression(&expression)?);
+                }
+                '}' => return Err(UriTemplateError::UnexpectedBrace(offset)),
+                _ => literal.push(ch),
+            }
+        }
+        if !literal.is_empty() {
+            parts.push(Part::Literal(literal));
+        }
+
+        Ok(Self {
+            template: template.to_string(),
+            parts,
+        })
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.template
+    }
+
+    /// Variable names in the order they appear.
+    pub fn variables(&self) -> Vec<&str> {
+        self.parts
+            .iter()
+            .filter_map(|part| match part {
+                Part::Expression { name, .. } => Some(name.as_str()),
+                Part::Literal(_) => None,
+            })
+            .collect()
+    }
+
+    /// Expand the template; variables missing from `variables` expand to
+    /// nothing, as undefined variables do in RFC 6570.
+    pub fn expand(&self, variables: &HashMap<String, String>) -> String {
+        let mut uri = String::new();
+        for part in &self.parts {
+            match part {
+                Part::Literal(literal) => uri.push_str(literal),
+                Part::Expression { operator, name } => {
+                    let Some(value) = variables.get(name) else {
+                        continue;
+                    };
+                    match operator {
+                        Operator::Simple => encode(value, false, &mut uri),
+                        Operator::Reserved => encode(value, true, &mut uri),
+                        Operator::Fragment => {
+                            uri.push('#');
+                            encode(value, true, &mut uri);
+                        }
+                    }
+                }
+            }
+        }
+        uri
+    }
+
+    /// Match `uri` against the template and return the percent-decoded value
+    /// of every variable, or `None` when it does not match.
+    ///
+    /// Each variable must match at least one character. A `{var}` value
+    /// cannot span reserved characters such as `/`, so
+    /// `file:///{name}` matches `file:///a.txt` but not `file:///dir/a.txt`;
+    /// use `{+path}` for that.
+    pub fn match_uri(&self, uri: &str) -> Option<HashMap<String, String>> {
+        let mut values = Vec::new();
+        if !self.match_from(uri, 0, 0, &mut values) {
+            return None;
+        }
+        Some(values.into_iter().collect())
+    }
+
+    fn match_from(
+        &self,
+        uri: &str,
+        position: usize,
+        part: usize,
+        values: &mut Vec<(String, String)>,
+    ) -> bool {
+        let Some(current) = self.parts.get(part) else {
+            return position == uri.len();
+        };
+        let rest = &uri[position..];
+
+        match current {
+            Part::Literal(literal) => {
+                rest.starts_with(literal.as_str())
+                    && self.match_from(uri, position + literal.len(), part + 1, values)
+            }
+            Part::Expression { operator, name } => {
+                let (start, allow_reserved) = match operator {
+                    Operator::Simple => (position, false),
+                    Operator::Reserved => (position, true),
+                    Operator::Fragment => {
+                        if !rest.starts_with('#') {
+                            return false;
+                        }
+                        (position + 1, true)
+                    }
+                };
+                let available = uri[start..]
+                    .bytes()
+                    .take_while(|byte| allowed(*byte, allow_reserved))
+                    .count();
+
+                // Longest match first, backtracking when the rest fails
+                for end in (start + 1..=start + available).rev() {
+                    let Some(value) = decode(&uri[start..end]) else {
+                        continue;
+                    };
+                    values.push((name.clone(), value));
+                    if self.match_from(uri, end, part + 1, values) {
+                        return true;
+                    }
+                    values.pop();
+                }
+                false
+            }
+        }
+    }
+}
+
+impl fmt::Display for UriTemplate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.template)
+    }
+}
+
+fn parse_expression(expression: &str) -> Result<Part, UriTemplateError> {
+    let (operator, name) = match expression.chars().next() {
+        Some('+') => (Operator::Reserved, &expression[1..]),
+        Some('#') => (Operator::Fragment, &expression[1..]),
+        Some('.' | '/' | ';' | '?' | '&' | '=' | ',' | '!' | '@' | '|') => {
+            return Err(UriTemplateError::Unsupported(expression.to_string()));
+        }
+        _ => (Operator::Simple, expression),
+    };
+    if name.contains([',', ':', '*']) {
+        return Err(UriTemplateError::Unsupported(expression.to_string()));
+    }
+    let valid = !name.is_empty()
+        && !name.starts_with('.')
+        && !name.ends_with('.')
+        && !name.contains("..")
+        && name
+            .chars()
+            .all(|ch| ch.is_ascii_alphanumeric() || ch == '_' || ch == '.');
+    if !valid {
+        return Err(UriTemplateError::InvalidVariableName(name.to_string()));
+    }
+    Ok(Part::Expression {
+        operator,
+        name: name.to_string(),
+    })
+}
+
+fn is_unreserved(byte: u8) -> bool {
+    byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'.' | b'_' | b'~')
+}
+
+fn is_reserved(byte: u8) -> bool {
+    matches!(
+        byte,
+        b':' | b'/'
+            | b'?'
+            | b'#'
+            | b'['
+            | b']'
+            | b'@'
+            | b'!'
+            | b'$'
+            | b'&'
+            | b'\''
+            | b'('
+            | b')'
+            | b'*'
+            | b'+'
+            | b','
+            | b';'
+            | b'='
+    )
+}
+
+/// Characters an expanded value can contain.
+fn allowed(byte: u8, allow_reserved: bool) -> bool {
+    is_unreserved(byte) || byte == b'%' || (allow_reserved && is_reserved(byte))
+}
+
+fn encode(value: &str, allow_reserved: bool, out: &mut String) {
+    let bytes = value.as_bytes();
+    let mut index = 0;
+    while index < bytes.len() {
+        let byte = bytes[index];
+        let triplet = allow_reserved
+            && byte == b'%'
+            && bytes.get(index + 1).is_some_and(u8::is_ascii_hexdigit)
+            && bytes.get(index + 2).is_some_and(u8::is_ascii_hexdigit);
+        if triplet {
+            // Reserved expansion passes existing percent-encoding through
+            out.push_str(&value[index..index + 3]);
+            index += 3;
+            continue;
+        }
+        if is_unreserved(byte) || (allow_reserved && is_reserved(byte)) {
+            out.push(byte as char);
+        } else {
+            out.push_str(&format!("%{byte:02X}"));
+        }
+        index += 1;
+    }
+}
+
+fn decode(value: &str) -> Option<String> {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut index = 0;
+    while index < bytes.len() {
+        if bytes[index] == b'%' {
+            let hex = value.get(index + 1..index + 3)?;
+            decoded.push(u8::from_str_radix(hex, 16).ok()?);
+            index += 3;
+        } else {
+            decoded.push(bytes[index]);
+            index += 1;
+        }
+    }
+    String::from_utf8(decoded).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn variables(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn expands_rfc_level_two_examples() {
+        let values = variables(&[
+            ("var", "value"),
+            ("hello", "Hello World!"),
+            ("path", "/foo/bar"),
+        ]);
+        let cases = [
+            ("{var}", "value"),
+            ("{hello}", "Hello%20World%21"),
+            ("{+var}", "value"),
+            ("{+hello}", "Hello%20World!"),
+            ("{+path}/here", "/foo/bar/here"),
+            ("here?ref={+path}", "here?ref=/foo/bar"),
+            ("X{#var}", "X#value"),
+            ("X{#hello}", "X#Hello%20World!"),
+            ("X{undefined}Y{#undefined}", "XY"),
+        ];
+        for (template, expected) in cases {
+            let template = UriTemplate::parse(template).unwrap();
+            assert_eq!(template.expand(&values), expected, "{template}");
+        }
+    }
+
+    #[test]
+    fn matches_and_decodes_variables() {
+        let template = UriTemplate::parse("gitlab://project/{id}/issues").unwrap();
+        assert_eq!(
+            template.match_uri("gitlab://project/42/issues"),
+            Some(variables(&[("id", "42")]))
+        );
+        assert_eq!(
+            template.match_uri("gitlab://project/group%2Fapp/issues"),
+            Some(variables(&[("id", "group/app")]))
+        );
+        assert_eq!(
+            template.match_uri("gitlab://project/group/app/issues"),
+            None
+        );
+        assert_eq!(template.match_uri("gitlab://project//issues"), None);
+        assert_eq!(template.match_uri("gitlab://project/42/issues/1"), None);
+
+        let template = UriTemplate::parse("file:///{+path}").unwrap();
+        assert_eq!(
+            template.match_uri("file:///src/main.rs"),
+            Some(variables(&[("path", "src/main.rs")]))
+        );
+    }
+
+    #[test]
+    fn matching_backtracks_over_ambiguous_literals() {
+        let template = UriTemplate::parse("release/{name}-{version}.tar.gz").unwrap();
+        assert_eq!(
+            template.match_uri("release/my-app-1.2.tar.gz"),
+            Some(variables(&[("name", "my-app"), ("version", "1.2")]))
+        );
+
+        let template = UriTemplate::parse("docs/{+page}{#section}").unwrap();
+        assert_eq!(
+            template.match_uri("docs/guide/intro#setup"),
+            Some(variables(&[("page", "guide/intro"), ("section", "setup")]))
+        );
+    }
+
+    #[test]
+    fn expansion_round_trips_through_matching() {
+        let template = UriTemplate::parse("repo://{owner}/{name}/{+path}").unwrap();
+        let values = variables(&[
+            ("owner", "a b"),
+            ("name", "ü"),
+            ("path", "dir/file name.txt"),
+        ]);
+        let uri = template.expand(&values);
+        assert_eq!(uri, "repo://a%20b/%C3%BC/dir/file%20name.txt");
+        assert_eq!(template.match_uri(&uri), Some(values));
+    }
+
+    #[test]
+    fn rejects_malformed_and_unsupported_templates() {
+        assert_eq!(
+            UriTemplate::parse("file:///{path"),
+            Err(UriTemplateError::UnclosedExpression(8))
+        );
+        assert_eq!(
+            UriTemplate::parse("file:///path}"),
+            Err(UriTemplateError::UnexpectedBrace(12))
+        );
+        assert_eq!(
+            UriTemplate::parse("file:///{}"),
+            Err(UriTemplateError::InvalidVariableName(String::new()))
+        );
+        assert_eq!(
+            UriTemplate::parse("file:///{a-b}"),
+            Err(UriTemplateError::InvalidVariableName("a-b".to_string()))
+        );
+        for unsupported in ["{x,y}", "{/path}", "{?query}", "{var:3}", "{list*}"] {
+            assert!(matches!(
+                UriTemplate::parse(unsupported),
+                Err(UriTemplateError::Unsupported(_))
+            ));
+        }
+    }
+}
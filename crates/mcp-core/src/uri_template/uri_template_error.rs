@@ -0,0 +1,17 @@
+use thiserror::Error;
+
+/// Errors produced while parsing a URI template.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum UriTemplateError {
+    #[error("unclosed expression starting at offset {0}")]
+    UnclosedExpression(usize),
+
+    #[error("unexpected '}}' at offset {0}")]
+    UnexpectedBrace(usize),
+
+    #[error("invalid variable name '{0}'")]
+    InvalidVariableName(String),
+
+    #[error("unsupported expression '{{{0}}}'; only {{var}}, {{+var}} and {{#var}} are supported")]
+    Unsupported(String),
+}
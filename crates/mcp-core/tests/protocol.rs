@@ -1,3 +1,7 @@
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
 use async_trait::async_trait;
 use futures::executor::block_on;
 use schemars::JsonSchema;
@@ -5,7 +9,8 @@ use serde::{Deserialize, Serialize};
 use serde_json::json;
 
 use mcp_core::{
-    JsonSchemaValidator, Protocol, ProtocolError, RequestContext, RequestHandler, RequestMessage,
+    JsonSchemaValidator, Protocol, ProtocolError, ProtocolOptions, RequestContext, RequestHandler,
+    RequestMessage,
 };
 
 #[derive(Debug, Deserialize, Serialize, JsonSchema)]
@@ -49,3 +54,161 @@ fn rejects_unknown_method() {
     let err = block_on(protocol.handle_request(request)).expect_err("should error");
     assert!(matches!(err, ProtocolError::UnknownMethod(method) if method == "missing"));
 }
+
+/// Counts calls and answers with the call number after a short delay.
+#[derive(Clone, Default)]
+struct CountingHandler {
+    calls: Arc<AtomicUsize>,
+}
+
+#[async_trait]
+impl RequestHandler for CountingHandler {
+    async fn handle(
+        &self,
+        _request: &RequestMessage,
+        _context: &RequestContext,
+    ) -> Result<serde_json::Value, ProtocolError> {
+        let call = self.calls.fetch_add(1, Ordering::SeqCst) + 1;
+        futures_timer::Delay::new(Duration::from_millis(20)).await;
+        Ok(json!({ "call": call }))
+    }
+}
+
+fn replaying_protocol(window: Duration) -> (Protocol, Arc<AtomicUsize>) {
+    let options = ProtocolOptions {
+        idempotency_window: Some(window),
+        ..Default::default()
+    };
+    let mut protocol = Protocol::with_options(JsonSchemaValidator::default(), options);
+    let handler = CountingHandler::default();
+    let calls = Arc::clone(&handler.calls);
+    protocol.register_handler(
+        "count",
+        JsonSchemaValidator::schema_for::<serde_json::Value>(),
+        handler,
+    );
+    (protocol, calls)
+}
+
+fn count(protocol: &Protocol, id: &str, params: serde_json::Value) -> serde_json::Value {
+    let request = RequestMessage::new(id, "count", params);
+    let response = block_on(protocol.handle_request(request)).expect("valid response");
+    response.result.unwrap()["call"].clone()
+}
+
+#[test]
+fn replays_retransmitted_requests() {
+    let (protocol, calls) = replaying_protocol(Duration::from_secs(60));
+
+    assert_eq!(count(&protocol, "1", json!({})), 1);
+    assert_eq!(count(&protocol, "1", json!({})), 1);
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+    // Reusing an id for different params is a new request
+    assert_eq!(count(&protocol, "1", json!({ "other": true })), 2);
+    assert_eq!(count(&protocol, "2", json!({})), 3);
+}
+
+#[test]
+fn duplicates_wait_for_the_request_in_flight() {
+    let (protocol, calls) = replaying_protocol(Duration::from_secs(60));
+
+    let first = protocol.handle_request(RequestMessage::new("1", "count", json!({})));
+    let second = protocol.handle_request(RequestMessage::new("1", "count", json!({})));
+    let (first, second) = block_on(futures::future::join(first, second));
+
+    assert_eq!(first.unwrap().result, second.unwrap().result);
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+fn responses_expire_after_the_window() {
+    let (protocol, calls) = replaying_protocol(Duration::from_millis(1));
+
+    count(&protocol, "1", json!({}));
+    std::thread::sleep(Duration::from_millis(5));
+    assert_eq!(count(&protocol, "1", json!({})), 2);
+    assert_eq!(calls.load(Ordering::SeqCst), 2);
+}
+
+#[test]
+fn replay_filter_opts_requests_out() {
+    let (mut protocol, calls) = replaying_protocol(Duration::from_secs(60));
+    protocol.set_replay_filter(Some(Arc::new(|request: &RequestMessage| {
+        request.params.get("fresh").is_none()
+    })));
+
+    count(&protocol, "1", json!({ "fresh": true }));
+    count(&protocol, "1", json!({ "fresh": true }));
+    assert_eq!(calls.load(Ordering::SeqCst), 2);
+}
+
+fn counting_protocol() -> Protocol {
+    let mut protocol = Protocol::new(JsonSchemaValidator::default());
+    protocol.register_handler(
+        "count",
+        JsonSchemaValidator::schema_for::<serde_json::Value>(),
+        CountingHandler::default(),
+    );
+    protocol
+}
+
+fn in_session(session_id: &str) -> RequestContext {
+    RequestContext {
+        session_id: Some(session_id.to_string()),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn rejects_reused_ids_of_requests_in_flight() {
+    let protocol = counting_protocol();
+
+    let first = protocol.handle_request(RequestMessage::new("1", "count", json!({})));
+    let second = protocol.handle_request(RequestMessage::new("1", "count", json!({})));
+    let (first, second) = block_on(futures::future::join(first, second));
+
+    assert_eq!(first.unwrap().result.unwrap()["call"], 1);
+    let err = second.expect_err("the id is still in use");
+    assert!(matches!(&err, ProtocolError::DuplicateRequestId(id) if id.to_string() == "1"));
+    assert!(err.to_string().contains("already in use"));
+
+    // The id is free again once answered
+    let request = RequestMessage::new("1", "count", json!({}));
+    assert!(block_on(protocol.handle_request(request)).is_ok());
+}
+#[test]
+fn rejects_null_request_ids() {
+    let protocol = counting_protocol();
+
+    let request: RequestMessage =
+        serde_json::from_value(json!({ "jsonrpc": "2.0", "id": null, "method": "count" })).unwrap();
+    let err = block_on(protocol.handle_request(request)).expect_err("null ids are for responses");
+    assert!(matches!(err, ProtocolError::NullRequestId));
+}
+
+#[test]
+fn request_ids_are_scoped_to_sessions() {
+    let protocol = counting_protocol();
+
+    let first = protocol.handle_request_with_context(
+        RequestMessage::new("1", "count", json!({})),
+        in_session("a"),
+    );
+    let second = protocol.handle_request_with_context(
+        RequestMessage::new("1", "count", json!({})),
+        in_session("b"),
+    );
+    let stateless = protocol.handle_request_with_context(
+        RequestMessage::new("1", "count", json!({})),
+        RequestContext {
+            stateless: true,
+            ..in_session("a")
+        },
+    );
+    let (first, second, stateless) = block_on(futures::future::join3(first, second, stateless));
+
+    assert!(first.is_ok());
+    assert!(second.is_ok());
+    assert!(stateless.is_ok());
+}
@@ -0,0 +1,129 @@
+use syn::{Attribute, Expr, ExprLit, Lit, LitStr, Meta, Token};
+
+use crate::rename_rule::RenameRule;
+
+/// `#[tool_input(..)]`, `#[serde(..)]` and doc attributes of a struct or enum.
+#[derive(Default)]
+pub(crate) struct ContainerAttributes {
+    pub(crate) description: Option<String>,
+    pub(crate) rename_all: Option<RenameRule>,
+}
+
+/// `#[tool_input(..)]`, `#[serde(..)]` and doc attributes of a field or
+/// enum variant.
+#[derive(Default)]
+pub(crate) struct FieldAttributes {
+    pub(crate) description: Option<String>,
+    pub(crate) required: bool,
+    pub(crate) rename: Option<String>,
+    /// `#[serde(default)]`: serde accepts the field being absent.
+    pub(crate) default: bool,
+    /// `#[serde(skip)]` or `#[serde(skip_deserializing)]`.
+    pub(crate) skip: bool,
+}
+
+impl ContainerAttributes {
+    pub(crate) fn parse(attrs: &[Attribute]) -> syn::Result<Self> {
+        let mut parsed = Self {
+            description: doc_comment(attrs),
+            ..Default::default()
+        };
+        for attr in attrs {
+            if attr.path().is_ident("tool_input") {
+                attr.parse_nested_meta(|meta| {
+                    if meta.path.is_ident("description") {
+                        parsed.description = Some(meta.value()?.parse::<LitStr>()?.value());
+                        Ok(())
+                    } else {
+                        Err(meta.error("expected `description = \"...\"`"))
+                    }
+                })?;
+            } else if attr.path().is_ident("serde") {
+                attr.parse_nested_meta(|meta| {
+                    if meta.path.is_ident("rename_all") && meta.input.peek(Token![=]) {
+                        let rule = meta.value()?.parse::<LitStr>()?;
+                        parsed.rename_all =
+                            Some(RenameRule::from_str(&rule.value()).ok_or_else(|| {
+                                syn::Error::new(rule.span(), "unknown rename rule")
+                            })?);
+                        Ok(())
+                    } else {
+                        skip_serde_meta(&meta)
+                    }
+                })?;
+            }
+        }
+        Ok(parsed)
+    }
+}
+
+impl FieldAttributes {
+    pub(crate) fn parse(attrs: &[Attribute]) -> syn::Result<Self> {
+        let mut parsed = Self {
+            description: doc_comment(attrs),
+            ..Default::default()
+        };
+        for attr in attrs {
+            if attr.path().is_ident("tool_input") {
+                attr.parse_nested_meta(|meta| {
+                    if meta.path.is_ident("description") {
+                        parsed.description = Some(meta.value()?.parse::<LitStr>()?.value());
+                        Ok(())
+                    } else if meta.path.is_ident("required") {
+                        parsed.required = true;
+                        Ok(())
+                    } else {
+                        Err(meta.error("expected `description = \"...\"` or `required`"))
+                    }
+                })?;
+            } else if attr.path().is_ident("serde") {
+                attr.parse_nested_meta(|meta| {
+                    if meta.path.is_ident("rename") && meta.input.peek(Token![=]) {
+                        parsed.rename = Some(meta.value()?.parse::<LitStr>()?.value());
+                        Ok(())
+                    } else if meta.path.is_ident("default") {
+                        parsed.default = true;
+                        skip_serde_meta(&meta)
+                    } else if meta.path.is_ident("skip") || meta.path.is_ident("skip_deserializing")
+                    {
+                        parsed.skip = true;
+                        Ok(())
+                    } else {
+                        skip_serde_meta(&meta)
+                    }
+                })?;
+            }
+        }
+        Ok(parsed)
+    }
+}
+
+/// Consume the value of a serde attribute the schema does not depend on.
+fn skip_serde_meta(meta: &syn::meta::ParseNestedMeta) -> syn::Result<()> {
+    if meta.input.peek(Token![=]) {
+        meta.value()?.parse::<Lit>()?;
+    } else if meta.input.peek(syn::token::Paren) {
+        meta.parse_nested_meta(|nested| skip_serde_meta(&nested))?;
+    }
+    Ok(())
+}
+
+/// Doc comment lines joined by newlines, if any.
+fn doc_comment(attrs: &[Attribute]) -> Option<String> {
+    let lines: Vec<String> = attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("doc"))
+        .filter_map(|attr| match &attr.meta {
+            Meta::NameValue(doc) => match &doc.value {
+                Expr::Lit(ExprLit {
+                    lit: Lit::Str(line),
+                    ..
+                }) => Some(line.value().trim().to_string()),
+                _ => None,
+            },
+            _ => None,
+        })
+        .collect();
+    let doc = lines.join("\n").trim().to_string();
+    (!doc.is_empty()).then_some(doc)
+}
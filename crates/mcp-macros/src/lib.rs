@@ -0,0 +1,52 @@
+//! Derive macros for the MCP server SDK, re-exported by `mcp_server`.
+
+mod attributes;
+mod rename_rule;
+mod schema;
+
+use proc_macro::TokenStream;
+use syn::{DeriveInput, parse_macro_input};
+
+/// Derive a JSON Schema for a tool's typed arguments.
+///
+/// Generates `json_schema() -> serde_json::Value` and
+/// `description() -> Option<&'static str>` for the type. Structs with named
+/// fields also implement `mcp_server::ToolInput`, so they can be registered
+/// with `McpServer::register_typed_tool`; the type must also derive
+/// `serde::Deserialize`, and the crate must depend on `serde_json`.
+///
+/// - `#[tool_input(description = "...")]` on the type or a field sets its
+///   `description`, falling back to the doc comment.
+/// - Fields are required unless they are `Option<T>` or `#[serde(default)]`;
+///   `#[tool_input(required)]` makes any field required.
+/// - `String`, `bool`, integers and floats map to their JSON types,
+///   `Vec<T>` and sets to `"array"`, maps to `"object"`. Other field types
+///   must provide `json_schema()`, e.g. by deriving `ToolInput`.
+/// - Enums with unit variants map to a string `"enum"` of variant names.
+/// - `#[serde(rename)]`, `#[serde(rename_all)]` and `#[serde(skip)]` are
+///   honoured, so the schema names the keys serde reads.
+///
+/// ```ignore
+/// #[derive(Deserialize, ToolInput)]
+/// #[tool_input(description = "Search issues")]
+/// struct SearchInput {
+///     #[tool_input(description = "Text to search for")]
+///     query: String,
+///     state: Option<IssueState>,
+///     labels: Vec<String>,
+/// }
+///
+/// #[derive(Deserialize, ToolInput)]
+/// #[serde(rename_all = "lowercase")]
+/// enum IssueState {
+///     Open,
+///     Closed,
+/// }
+/// ```
+#[proc_macro_derive(ToolInput, attributes(tool_input))]
+pub fn derive_tool_input(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    schema::expand(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
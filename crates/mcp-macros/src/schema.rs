@@ -0,0 +1,244 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Data, DataEnum, DataStruct, DeriveInput, Fields, GenericArgument, PathArguments, Type};
+
+use crate::attributes::{ContainerAttributes, FieldAttributes};
+
+/// Expand `#[derive(ToolInput)]` for `input`.
+pub(crate) fn expand(input: &DeriveInput) -> syn::Result<TokenStream> {
+    let container = ContainerAttributes::parse(&input.attrs)?;
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let (schema, tool_input_impl) = match &input.data {
+        Data::Struct(data) => {
+            let schema = struct_schema(data, &container)?;
+            // Only objects can be a tool's arguments; enums describe fields
+            let tool_input_impl = quote! {
+                impl #impl_generics ::mcp_server::ToolInput for #name #ty_generics #where_clause {
+                    fn input_schema() -> ::serde_json::Value {
+                        <Self>::json_schema()
+                    }
+
+                    fn description() -> ::core::option::Option<&'static str> {
+                        <Self>::description()
+                    }
+                }
+            };
+            (schema, tool_input_impl)
+        }
+        Data::Enum(data) => (enum_schema(data, &container)?, TokenStream::new()),
+        Data::Union(_) => {
+            return Err(syn::Error::new_spanned(
+                name,
+                "ToolInput can only be derived for structs and enums",
+            ));
+        }
+    };
+
+    let description = match &container.description {
+        Some(description) => quote! { ::core::option::Option::Some(#description) },
+        None => quote! { ::core::option::Option::None },
+    };
+    Ok(quote! {
+        impl #impl_generics #name #ty_generics #where_clause {
+            /// JSON Schema generated by `#[derive(ToolInput)]`.
+            pub fn json_schema() -> ::serde_json::Value {
+                #schema
+            }
+
+            /// Description from `#[tool_input(description = "...")]` or the
+            /// doc comment.
+            pub fn description() -> ::core::option::Option<&'static str> {
+                #description
+            }
+        }
+
+        #tool_input_impl
+    })
+}
+
+/// An object schema with one property per deserialized field.
+fn struct_schema(data: &DataStruct, container: &ContainerAttributes) -> syn::Result<TokenStream> {
+    let Fields::Named(fields) = &data.fields else {
+        return Err(syn::Error::new_spanned(
+            &data.fields,
+            "ToolInput can only be derived for structs with named fields",
+        ));
+    };
+
+    let mut properties = Vec::new();
+    let mut required = Vec::new();
+    for field in &fields.named {
+        let attrs = FieldAttributes::parse(&field.attrs)?;
+        if attrs.skip {
+            continue;
+        }
+        let ident = field.ident.as_ref().expect("named field");
+        let ident = ident.to_string();
+        let ident = ident.strip_prefix("r#").unwrap_or(&ident);
+        let key = match (&attrs.rename, container.rename_all) {
+            (Some(rename), _) => rename.clone(),
+            (None, Some(rule)) => rule.apply_to_field(ident),
+            (None, None) => ident.to_string(),
+        };
+        let optional = option_inner(&field.ty).is_some();
+        if attrs.required || !(optional || attrs.default) {
+            required.push(key.clone());
+        }
+        let schema = type_schema(&field.ty);
+        let describe = attrs.description.map(|description| {
+            quote! {
+                if let ::core::option::Option::Some(object) = schema.as_object_mut() {
+                    object.insert(
+                        "description".to_string(),
+                        ::serde_json::Value::from(#description),
+                    );
+                }
+            }
+        });
+        properties.push(quote! {
+            properties.insert(#key.to_string(), {
+                let mut schema = #schema;
+                #describe
+                schema
+            });
+        });
+    }
+
+    let describe = container.description.as_ref().map(|description| {
+        quote! {
+            schema.insert("description".to_string(), ::serde_json::Value::from(#description));
+        }
+    });
+    let required = (!required.is_empty()).then(|| {
+        quote! {
+            schema.insert("required".to_string(), ::serde_json::json!([#(#required),*]));
+        }
+    });
+    Ok(quote! {
+        let mut properties = ::serde_json::Map::new();
+        #(#properties)*
+        let mut schema = ::serde_json::Map::new();
+        schema.insert("type".to_string(), ::serde_json::Value::from("object"));
+        #describe
+        schema.insert("properties".to_string(), ::serde_json::Value::Object(properties));
+        #required
+        ::serde_json::Value::Object(schema)
+    })
+}
+
+/// A string schema listing the names of unit variants as `enum`.
+fn enum_schema(data: &DataEnum, container: &ContainerAttributes) -> syn::Result<TokenStream> {
+    let mut names = Vec::new();
+    for variant in &data.variants {
+        if !matches!(variant.fields, Fields::Unit) {
+            return Err(syn::Error::new_spanned(
+                variant,
+                "ToolInput can only be derived for enums whose variants are all unit variants",
+            ));
+        }
+        let attrs = FieldAttributes::parse(&variant.attrs)?;
+        if attrs.skip {
+            continue;
+        }
+        let ident = variant.ident.to_string();
+        names.push(match (&attrs.rename, container.rename_all) {
+            (Some(rename), _) => rename.clone(),
+            (None, Some(rule)) => rule.apply_to_variant(&ident),
+            (None, None) => ident,
+        });
+    }
+
+    let describe = container.description.as_ref().map(|description| {
+        quote! {
+            schema.insert("description".to_string(), ::serde_json::Value::from(#description));
+        }
+    });
+    Ok(quote! {
+        let mut schema = ::serde_json::Map::new();
+        schema.insert("type".to_string(), ::serde_json::Value::from("string"));
+        #describe
+        schema.insert("enum".to_string(), ::serde_json::json!([#(#names),*]));
+        ::serde_json::Value::Object(schema)
+    })
+}
+
+/// Schema of a field type. Types other than primitives, strings and the
+/// standard containers must provide a `json_schema()` function, e.g. by
+/// deriving `ToolInput` themselves.
+fn type_schema(ty: &Type) -> TokenStream {
+    match ty {
+        Type::Reference(reference) => return type_schema(&reference.elem),
+        Type::Paren(paren) => return type_schema(&paren.elem),
+        Type::Group(group) => return type_schema(&group.elem),
+        Type::Slice(slice) => {
+            let items = type_schema(&slice.elem);
+            return quote! { ::serde_json::json!({ "type": "array", "items": #items }) };
+        }
+        Type::Array(array) => {
+            let items = type_schema(&array.elem);
+            return quote! { ::serde_json::json!({ "type": "array", "items": #items }) };
+        }
+        _ => {}
+    }
+
+    let Some((ident, arguments)) = last_segment(ty) else {
+        return quote! { <#ty>::json_schema() };
+    };
+    let primitive = match ident.as_str() {
+        "String" | "str" | "char" | "PathBuf" | "Path" => Some("string"),
+        "bool" => Some("boolean"),
+        "i8" | "i16" | "i32" | "i64" | "i128" | "isize" | "u8" | "u16" | "u32" | "u64" | "u128"
+        | "usize" => Some("integer"),
+        "f32" | "f64" => Some("number"),
+        _ => None,
+    };
+    if let Some(primitive) = primitive {
+        return quote! { ::serde_json::json!({ "type": #primitive }) };
+    }
+    match (ident.as_str(), arguments.as_slice()) {
+        ("Value", []) => quote! { ::serde_json::json!({}) },
+        ("Option" | "Box" | "Rc" | "Arc" | "Cow", [.., inner]) => type_schema(inner),
+        ("Vec" | "VecDeque" | "HashSet" | "BTreeSet", [inner]) => {
+            let items = type_schema(inner);
+            quote! { ::serde_json::json!({ "type": "array", "items": #items }) }
+        }
+        ("HashMap" | "BTreeMap", [_, value]) => {
+            let values = type_schema(value);
+            quote! { ::serde_json::json!({ "type": "object", "additionalProperties": #values }) }
+        }
+        _ => quote! { <#ty>::json_schema() },
+    }
+}
+
+/// `T` when `ty` is an `Option<T>`.
+fn option_inner(ty: &Type) -> Option<Type> {
+    match last_segment(ty)? {
+        (ident, mut arguments) if ident == "Option" && arguments.len() == 1 => arguments.pop(),
+        _ => None,
+    }
+}
+
+/// Name and type arguments of the last segment of a path type.
+fn last_segment(ty: &Type) -> Option<(String, Vec<Type>)> {
+    let Type::Path(path) = ty else {
+        return None;
+    };
+    if path.qself.is_some() {
+        return None;
+    }
+    let segment = path.path.segments.last()?;
+    let arguments = match &segment.arguments {
+        PathArguments::AngleBracketed(arguments) => arguments
+            .args
+            .iter()
+            .filter_map(|argument| match argument {
+                GenericArgument::Type(ty) => Some(ty.clone()),
+                _ => None,
+            })
+            .collect(),
+        _ => Vec::new(),
+    };
+    Some((segment.ident.to_string(), arguments))
+}
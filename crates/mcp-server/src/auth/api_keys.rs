@@ -0,0 +1,106 @@
+//! API key storage for shared-secret authentication.
+//!
+//! A simpler alternative to OAuth for self-hosted deployments: each key
+//! maps to a client ID and scopes, checked by `ApiKeyLayer`.
+
+use std::collections::HashMap;
+use std::sync::RwLock;
+
+use async_trait::async_trait;
+use sha2::{Digest, Sha256};
+
+use mcp_core::auth::AuthInfo;
+
+/// Error type for API key store operations.
+#[derive(Debug, thiserror::Error)]
+pub enum ApiKeyStoreError {
+    /// Storage error.
+    #[error("storage error: {0}")]
+    Storage(String),
+}
+
+/// Trait for validating API keys.
+#[async_trait]
+pub trait ApiKeyStore: Send + Sync {
+    /// The identity `key` authenticates as, or `None` for an unknown key.
+    ///
+    /// The returned `AuthInfo` is attached to the request in place of one
+    /// from an OAuth token, so its scopes feed the usual scope checks.
+    async fn verify_api_key(&self, key: &str) -> Result<Option<AuthInfo>, ApiKeyStoreError>;
+}
+
+#[derive(Debug, Clone)]
+struct ApiKeyEntry {
+    client_id: String,
+    scopes: Vec<String>,
+}
+
+/// In-memory API key store.
+///
+/// Keys are kept as SHA-256 digests, so lookups do not compare the
+/// presented key byte by byte and the keys themselves are not retained.
+#[derive(Debug, Default)]
+pub struct InMemoryApiKeyStore {
+    keys: RwLock<HashMap<[u8; 32], ApiKeyEntry>>,
+}
+
+impl InMemoryApiKeyStore {
+    /// Create an empty store.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Accept `key` as `client_id`, granting `scopes`. Replaces any
+    /// existing entry for the key.
+    pub fn add_key(&self, key: &str, client_id: impl Into<String>, scopes: Vec<String>) {
+        let entry = ApiKeyEntry {
+            client_id: client_id.into(),
+            scopes,
+        };
+        self.keys.write().unwrap().insert(digest(key), entry);
+    }
+
+    /// Stop accepting `key`. Returns whether it was known.
+    pub fn remove_key(&self, key: &str) -> bool {
+        self.keys.write().unwrap().remove(&digest(key)).is_some()
+    }
+}
+
+#[async_trait]
+impl ApiKeyStore for InMemoryApiKeyStore {
+    async fn verify_api_key(&self, key: &str) -> Result<Option<AuthInfo>, ApiKeyStoreError> {
+        let keys = self.keys.read().unwrap();
+        Ok(keys.get(&digest(key)).map(|entry| {
+            AuthInfo::new(key)
+                .with_client_id(entry.client_id.clone())
+                .with_scopes(entry.scopes.clone())
+        }))
+    }
+}
+
+fn digest(key: &str) -> [u8; 32] {
+    Sha256::digest(key.as_bytes()).into()
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::executor::block_on;
+
+    use super::*;
+
+    #[test]
+    fn test_in_memory_api_key_store() {
+        block_on(async {
+            let store = InMemoryApiKeyStore::new();
+            store.add_key("secret", "ci", vec!["tools:call".to_string()]);
+
+            let info = store.verify_api_key("secret").await.unwrap().unwrap();
+            assert_eq!(info.client_id.as_deref(), Some("ci"));
+            assert_eq!(info.scopes, vec!["tools:call"]);
+            assert!(store.verify_api_key("wrong").await.unwrap().is_none());
+
+            assert!(store.remove_key("secret"));
+            assert!(store.verify_api_key("secret").await.unwrap().is_none());
+        });
+    }
+}
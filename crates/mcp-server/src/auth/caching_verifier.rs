@@ -0,0 +1,312 @@
+//! Caching wrapper for token verifiers.
+//!
+//! Verifying a token against a remote introspection endpoint on every
+//! request is slow and loads the authorization server. `CachingTokenVerifier`
+//! remembers successful verifications until the token expires and briefly
+//! remembers rejected tokens.
+//!
+//! ## Example
+//!
+//! ```ignore
+//! use std::sync::Arc;
+//! use mcp_server::auth::{CacheConfig, CachingTokenVerifier};
+//! use mcp_server::auth::middleware::BearerAuthLayer;
+//!
+//! let verifier = Arc::new(CachingTokenVerifier::new(introspection, CacheConfig::default()));
+//! let router = router.layer(BearerAuthLayer::new(verifier));
+//! ```
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use async_trait::async_trait;
+use sha2::{Digest, Sha256};
+
+use mcp_core::auth::AuthInfo;
+
+use super::provider::{OAuthProviderError, OAuthTokenVerifier};
+
+/// Cache limits for `CachingTokenVerifier`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CacheConfig {
+    /// Maximum number of cached results; the entry closest to expiry is
+    /// dropped when a new one does not fit.
+    pub max_entries: usize,
+    /// Longest time a successful result is reused. Applies to tokens
+    /// without `expires_at` and bounds how long a revoked token stays valid.
+    pub max_ttl: Duration,
+    /// How long a rejected token is answered from the cache. Zero disables
+    /// negative caching.
+    pub negative_ttl: Duration,
+}
+
+impl Default for CacheConfig {
+    fn default() -> Self {
+        Self {
+            max_entries: 10_000,
+            max_ttl: Duration::from_secs(300),
+            negative_ttl: Duration::from_secs(10),
+        }
+    }
+}
+
+/// Cached outcome of a verification.
+enum Verdict {
+    Valid(AuthInfo),
+    /// Message of the `InvalidToken` error returned by the inner verifier.
+    Invalid(String),
+}
+
+struct CacheEntry {
+    verdict: Verdict,
+    expires: Instant,
+}
+
+/// Token verifier that memoizes the results of another verifier.
+///
+/// Entries are keyed by the SHA-256 of the token. Only `InvalidToken`
+/// rejections are cached; other errors, such as an unreachable
+/// introspection endpoint, are retried on the next request.
+pub struct CachingTokenVerifier<V> {
+    inner: V,
+    config: CacheConfig,
+    entries: Mutex<HashMap<[u8; 32], CacheEntry>>,
+}
+
+impl<V: OAuthTokenVerifier> CachingTokenVerifier<V> {
+    /// Wrap `inner` with a cache limited by `config`.
+    pub fn new(inner: V, config: CacheConfig) -> Self {
+        Self {
+            inner,
+            config,
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// The wrapped verifier.
+    pub fn inner(&self) -> &V {
+        &self.inner
+    }
+
+    /// Forget the cached result for `token`, e.g. after revoking it.
+    pub fn invalidate(&self, token: &str) {
+        self.entries.lock().unwrap().remove(&token_key(token));
+    }
+
+    /// Forget all cached results.
+    pub fn clear(&self) {
+        self.entries.lock().unwrap().clear();
+    }
+
+    /// Number of cached results, including expired ones not yet purged.
+    pub fn len(&self) -> usize {
+        self.entries.lock().unwrap().len()
+    }
+
+    /// Whether nothing is cached.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn lookup(&self, key: &[u8; 32]) -> Option<Result<AuthInfo, OAuthProviderError>> {
+        let mut entries = self.entries.lock().unwrap();
+        let entry = entries.get(key)?;
+        if entry.expires <= Instant::now() {
+            entries.remove(key);
+            return None;
+        }
+        Some(match &entry.verdict {
+            Verdict::Valid(info) => Ok(info.clone()),
+            Verdict::Invalid(message) => Err(OAuthProviderError::InvalidToken(message.clone())),
+        })
+    }
+
+    fn store(&self, key: [u8; 32], verdict: Verdict, ttl: Duration) {
+        if ttl.is_zero() || self.config.max_entries == 0 {
+            return;
+        }
+        let now = Instant::now();
+        let mut entries = self.entries.lock().unwrap();
+        if entries.len() >= self.config.max_entries && !entries.contains_key(&key) {
+            entries.retain(|_, entry| entry.expires > now);
+            if entries.len() >= self.config.max_entries {
+                let soonest = entries
+                    .iter()
+                    .min_by_key(|(_, entry)| entry.expires)
+                    .map(|(key, _)| *key);
+                if let Some(soonest) = soonest {
+                    entries.remove(&soonest);
+                }
+            }
+        }
+        entries.insert(
+            key,
+            CacheEntry {
+                verdict,
+                expires: now + ttl,
+            },
+        );
+    }
+
+    /// How long a successful result may be reused: until the token's
+    /// `expires_at`, capped at `max_ttl`.
+    fn valid_ttl(&self, info: &AuthInfo) -> Duration {
+        match info.expires_at {
+            Some(expires_at) => {
+                let now = SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .map(|d| d.as_secs())
+                    .unwrap_or(0);
+                Duration::from_secs(expires_at.saturating_sub(now)).min(self.config.max_ttl)
+            }
+            None => self.config.max_ttl,
+        }
+    }
+}
+
+#[async_trait]
+impl<V: OAuthTokenVerifier> OAuthTokenVerifier for CachingTokenVerifier<V> {
+    async fn verify_access_token(&self, token: &str) -> Result<AuthInfo, OAuthProviderError> {
+        let key = token_key(token);
+        if let Some(result) = self.lookup(&key) {
+            return result;
+        }
+
+        let result = self.inner.verify_access_token(token).await;
+        match &result {
+            Ok(info) => self.store(key, Verdict::Valid(info.clone()), self.valid_ttl(info)),
+            Err(OAuthProviderError::InvalidToken(message)) => self.store(
+                key,
+                Verdict::Invalid(message.clone()),
+                self.config.negative_ttl,
+            ),
+            Err(_) => {}
+        }
+        result
+    }
+}
+
+fn token_key(token: &str) -> [u8; 32] {
+    Sha256::digest(token.as_bytes()).into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use futures::executor::block_on;
+
+    /// Accepts tokens starting with `good`, expiring `lifetime` seconds from
+    /// now, and counts calls.
+    struct CountingVerifier {
+        calls: AtomicUsize,
+        lifetime: Option<i64>,
+    }
+
+    impl CountingVerifier {
+        fn new(lifetime: Option<i64>) -> Self {
+            Self {
+                calls: AtomicUsize::new(0),
+                lifetime,
+            }
+        }
+
+        fn calls(&self) -> usize {
+            self.calls.load(Ordering::SeqCst)
+        }
+    }
+
+    #[async_trait]
+    impl OAuthTokenVerifier for CountingVerifier {
+        async fn verify_access_token(&self, token: &str) -> Result<AuthInfo, OAuthProviderError> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            if token == "unavailable" {
+                return Err(OAuthProviderError::Server("introspection down".to_string()));
+            }
+            if !token.starts_with("good") {
+                return Err(OAuthProviderError::InvalidToken("unknown token".to_string()));
+            }
+            let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs() as i64;
+            let info = AuthInfo::new(token).with_scopes(vec!["read".to_string()]);
+            Ok(match self.lifetime {
+                Some(lifetime) => info.with_expires_at((now + lifetime) as u64),
+                None => info,
+            })
+        }
+    }
+
+    #[test]
+    fn test_caches_valid_tokens() {
+        let verifier = CachingTokenVerifier::new(CountingVerifier::new(Some(3600)), CacheConfig::default());
+
+        for _ in 0..3 {
+            let info = block_on(verifier.verify_access_token("good-1")).unwrap();
+            assert_eq!(info.scopes, vec!["read".to_string()]);
+        }
+        assert_eq!(verifier.inner().calls(), 1);
+
+        verifier.invalidate("good-1");
+        block_on(verifier.verify_access_token("good-1")).unwrap();
+        assert_eq!(verifier.inner().calls(), 2);
+    }
+
+    #[test]
+    fn test_expired_tokens_are_not_cached() {
+        let verifier = CachingTokenVerifier::new(CountingVerifier::new(Some(-10)), CacheConfig::default());
+
+        block_on(verifier.verify_access_token("good-1")).unwrap();
+        block_on(verifier.verify_access_token("good-1")).unwrap();
+        assert_eq!(verifier.inner().calls(), 2);
+        assert!(verifier.is_empty());
+    }
+
+    #[test]
+    fn test_negative_results_expire() {
+        let config = CacheConfig {
+            negative_ttl: Duration::from_millis(50),
+            ..Default::default()
+        };
+        let verifier = CachingTokenVerifier::new(CountingVerifier::new(None), config);
+
+        for _ in 0..3 {
+            let err = block_on(verifier.verify_access_token("bad")).unwrap_err();
+            assert!(matches!(err, OAuthProviderError::InvalidToken(ref m) if m == "unknown token"));
+        }
+        assert_eq!(verifier.inner().calls(), 1);
+
+        std::thread::sleep(Duration::from_millis(80));
+        block_on(verifier.verify_access_token("bad")).unwrap_err();
+        assert_eq!(verifier.inner().calls(), 2);
+    }
+
+    #[test]
+    fn test_server_errors_are_not_cached() {
+        let verifier = CachingTokenVerifier::new(CountingVerifier::new(None), CacheConfig::default());
+
+        block_on(verifier.verify_access_token("unavailable")).unwrap_err();
+        block_on(verifier.verify_access_token("unavailable")).unwrap_err();
+        assert_eq!(verifier.inner().calls(), 2);
+    }
+
+    #[test]
+    fn test_max_entries() {
+        let config = CacheConfig {
+            max_entries: 2,
+            ..Default::default()
+        };
+        let verifier = CachingTokenVerifier::new(CountingVerifier::new(None), config);
+
+        for token in ["good-1", "good-2", "good-3"] {
+            block_on(verifier.verify_access_token(token)).unwrap();
+        }
+        assert_eq!(verifier.len(), 2);
+
+        // The oldest entry expires first and was evicted
+        block_on(verifier.verify_access_token("good-3")).unwrap();
+        assert_eq!(verifier.inner().calls(), 3);
+        block_on(verifier.verify_access_token("good-1")).unwrap();
+        assert_eq!(verifier.inner().calls(), 4);
+    }
+}
@@ -46,6 +46,49 @@ pub trait OAuthRegisteredClientsStore: Send + Sync {
         let _ = client_id;
         Err(ClientStoreError::Storage("delete not supported".to_string()))
     }
+
+    /// Check the secret presented by `client`.
+    ///
+    /// Clients without a secret are always accepted. The default compares
+    /// against `client_info.client_secret`; stores that keep only a hash of
+    /// the secret override this.
+    fn verify_client_secret(&self, client: &OAuthClientInformationFull, presented: Option<&str>) -> bool {
+        match (&client.client_info.client_secret, presented) {
+            (None, _) => true,
+            (Some(expected), Some(presented)) => constant_time_eq(expected.as_bytes(), presented.as_bytes()),
+            (Some(_), None) => false,
+        }
+    }
+}
+
+/// Compare two byte strings in time independent of where they differ.
+pub(crate) fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+/// Validate `metadata` and assign a new client ID and secret (RFC 7591).
+pub(crate) fn new_registered_client(metadata: OAuthClientMetadata) -> Result<OAuthClientInformationFull, ClientStoreError> {
+    if metadata.redirect_uris.is_empty() {
+        return Err(ClientStoreError::InvalidMetadata(
+            "redirect_uris is required".to_string(),
+        ));
+    }
+
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+
+    Ok(OAuthClientInformationFull {
+        client_info: OAuthClientInformation {
+            client_id: InMemoryClientStore::generate_client_id(),
+            client_secret: Some(InMemoryClientStore::generate_client_secret()),
+            client_id_issued_at: Some(now),
+            client_secret_expires_at: None, // Non-expiring
+        },
+        metadata,
+        token_endpoint_auth_method: Some("client_secret_post".to_string()),
+    })
 }
 
 /// In-memory implementation of the client store for development/testing.
@@ -67,6 +110,11 @@ impl InMemoryClientStore {
         clients.insert(client.client_info.client_id.clone(), client);
     }
 
+    /// All registered clients, e.g. to import them into a `FileClientStore`.
+    pub fn clients(&self) -> Vec<OAuthClientInformationFull> {
+        self.clients.read().unwrap().values().cloned().collect()
+    }
+
     /// Generate a unique client ID.
     fn generate_client_id() -> String {
         uuid::Uuid::new_v4().to_string()
@@ -93,34 +141,10 @@ impl OAuthRegisteredClientsStore for InMemoryClientStore {
     }
 
     async fn register_client(&self, metadata: OAuthClientMetadata) -> Result<OAuthClientInformationFull, ClientStoreError> {
-        // Validate metadata
-        if metadata.redirect_uris.is_empty() {
-            return Err(ClientStoreError::InvalidMetadata(
-                "redirect_uris is required".to_string(),
-            ));
-        }
-
-        let client_id = Self::generate_client_id();
-        let client_secret = Self::generate_client_secret();
-
-        let now = std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .map(|d| d.as_secs())
-            .unwrap_or(0);
-
-        let client = OAuthClientInformationFull {
-            client_info: OAuthClientInformation {
-                client_id: client_id.clone(),
-                client_secret: Some(client_secret),
-                client_id_issued_at: Some(now),
-                client_secret_expires_at: None, // Non-expiring
-            },
-            metadata,
-            token_endpoint_auth_method: Some("client_secret_post".to_string()),
-        };
+        let client = new_registered_client(metadata)?;
 
         let mut clients = self.clients.write().unwrap();
-        clients.insert(client_id, client.clone());
+        clients.insert(client.client_info.client_id.clone(), client.clone());
 
         Ok(client)
     }
@@ -148,46 +172,54 @@ impl OAuthRegisteredClientsStore for InMemoryClientStore {
 
 #[cfg(test)]
 mod tests {
+    use futures::executor::block_on;
+
     use super::*;
 
-    #[tokio::test]
-    async fn test_in_memory_store_register() {
-        let store = InMemoryClientStore::new();
+    #[test]
+    fn test_in_memory_store_register() {
+        block_on(async {
+            let store = InMemoryClientStore::new();
 
-        let metadata = OAuthClientMetadata {
-            redirect_uris: vec!["http://localhost:8080/callback".to_string()],
-            client_name: Some("Test Client".to_string()),
-            ..Default::default()
-        };
+            let metadata = OAuthClientMetadata {
+                redirect_uris: vec!["http://localhost:8080/callback".to_string()],
+                client_name: Some("Test Client".to_string()),
+                ..Default::default()
+            };
 
-        let client = store.register_client(metadata).await.unwrap();
+            let client = store.register_client(metadata).await.unwrap();
 
-        assert!(!client.client_info.client_id.is_empty());
-        assert!(client.client_info.client_secret.is_some());
-        assert_eq!(client.metadata.client_name, Some("Test Client".to_string()));
+            assert!(!client.client_info.client_id.is_empty());
+            assert!(client.client_info.client_secret.is_some());
+            assert_eq!(client.metadata.client_name, Some("Test Client".to_string()));
+        });
     }
 
-    #[tokio::test]
-    async fn test_in_memory_store_get() {
-        let store = InMemoryClientStore::new();
+    #[test]
+    fn test_in_memory_store_get() {
+        block_on(async {
+            let store = InMemoryClientStore::new();
 
-        let metadata = OAuthClientMetadata {
-            redirect_uris: vec!["http://localhost:8080/callback".to_string()],
-            ..Default::default()
-        };
+            let metadata = OAuthClientMetadata {
+                redirect_uris: vec!["http://localhost:8080/callback".to_string()],
+                ..Default::default()
+            };
 
-        let registered = store.register_client(metadata).await.unwrap();
-        let retrieved = store.get_client(&registered.client_info.client_id).await.unwrap();
+            let registered = store.register_client(metadata).await.unwrap();
+            let retrieved = store.get_client(&registered.client_info.client_id).await.unwrap();
 
-        assert!(retrieved.is_some());
-        assert_eq!(retrieved.unwrap().client_info.client_id, registered.client_info.client_id);
+            assert!(retrieved.is_some());
+            assert_eq!(retrieved.unwrap().client_info.client_id, registered.client_info.client_id);
+        });
     }
 
-    #[tokio::test]
-    async fn test_in_memory_store_not_found() {
-        let store = InMemoryClientStore::new();
+    #[test]
+    fn test_in_memory_store_not_found() {
+        block_on(async {
+            let store = InMemoryClientStore::new();
 
-        let result = store.get_client("nonexistent").await.unwrap();
-        assert!(result.is_none());
+            let result = store.get_client("nonexistent").await.unwrap();
+            assert!(result.is_none());
+        });
     }
 }
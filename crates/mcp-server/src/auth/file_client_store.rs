@@ -0,0 +1,436 @@
+//! File-backed OAuth client storage.
+//!
+//! Dynamically registered clients (RFC 7591) survive restarts, so clients
+//! don't have to re-register and users don't have to consent again.
+
+use std::collections::HashMap;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::RwLock;
+
+use async_trait::async_trait;
+use base64::Engine;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use mcp_core::auth::{OAuthClientInformation, OAuthClientInformationFull, OAuthClientMetadata};
+
+use super::clients::{
+    ClientStoreError, InMemoryClientStore, OAuthRegisteredClientsStore, constant_time_eq,
+    new_registered_client,
+};
+
+/// Version of the store file layout.
+const FORMAT_VERSION: u32 = 1;
+
+/// Prefix of hashed secrets in the store file.
+const SHA256_PREFIX: &str = "sha256:";
+
+/// A client as written to the store file.
+///
+/// Kept unflattened: `OAuthClientInformationFull` flattens its metadata,
+/// which loses `metadata.token_endpoint_auth_method` on a round trip.
+#[derive(Clone, Serialize, Deserialize)]
+struct StoredClient {
+    client_info: OAuthClientInformation,
+    metadata: OAuthClientMetadata,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    token_endpoint_auth_method: Option<String>,
+}
+
+impl From<OAuthClientInformationFull> for StoredClient {
+    fn from(client: OAuthClientInformationFull) -> Self {
+        Self {
+            client_info: client.client_info,
+            metadata: client.metadata,
+            token_endpoint_auth_method: client.token_endpoint_auth_method,
+        }
+    }
+}
+
+impl From<StoredClient> for OAuthClientInformationFull {
+    fn from(client: StoredClient) -> Self {
+        Self {
+            client_info: client.client_info,
+            metadata: client.metadata,
+            token_endpoint_auth_method: client.token_endpoint_auth_method,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct StoreFile {
+    version: u32,
+    clients: HashMap<String, StoredClient>,
+}
+
+/// Layouts accepted when loading.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum AnyStoreFile {
+    Current(StoreFile),
+    /// A dump of `InMemoryClientStore`: client ID to client, in wire form.
+    Legacy(HashMap<String, OAuthClientInformationFull>),
+}
+
+/// OAuth client store persisted to a JSON file.
+///
+/// Every change rewrites the whole file through a temporary file and an
+/// atomic rename, so a crash never leaves a half-written store. Reads are
+/// served from memory. The file is written while holding the store's lock,
+/// which is fine for the handful of clients a server registers but blocks
+/// the executor thread for the duration of the write.
+///
+/// With [`open_with_secret_hashing`](Self::open_with_secret_hashing) only a
+/// SHA-256 hash of each `client_secret` is kept, and
+/// [`get_client`](OAuthRegisteredClientsStore::get_client) returns the hash
+/// in its place; secrets are then checked with
+/// [`verify_client_secret`](OAuthRegisteredClientsStore::verify_client_secret).
+/// The plain secret is only returned once, from `register_client`. Issued
+/// secrets are random, so an unsalted hash is enough.
+///
+/// ## Example
+///
+/// ```ignore
+/// use mcp_server::auth::FileClientStore;
+///
+/// let store = FileClientStore::open_with_secret_hashing("/var/lib/mcp/clients.json")?;
+/// ```
+pub struct FileClientStore {
+    path: PathBuf,
+    hash_secrets: bool,
+    clients: RwLock<HashMap<String, OAuthClientInformationFull>>,
+}
+
+impl FileClientStore {
+    /// Open the store at `path`, keeping client secrets as given.
+    ///
+    /// A missing file is an empty store. A file that cannot be read or
+    /// parsed is an error rather than silently starting over.
+    pub fn open(path: impl Into<PathBuf>) -> Result<Self, ClientStoreError> {
+        Self::open_inner(path.into(), false)
+    }
+
+    /// Open the store at `path`, keeping only hashes of client secrets.
+    ///
+    /// Plain secrets found in the file are hashed and the file rewritten.
+    pub fn open_with_secret_hashing(path: impl Into<PathBuf>) -> Result<Self, ClientStoreError> {
+        Self::open_inner(path.into(), true)
+    }
+
+    fn open_inner(path: PathBuf, hash_secrets: bool) -> Result<Self, ClientStoreError> {
+        let (clients, mut dirty) = match std::fs::read(&path) {
+            Ok(bytes) => Self::parse(&path, &bytes)?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => (HashMap::new(), false),
+            Err(e) => {
+                return Err(ClientStoreError::Storage(format!(
+                    "cannot read client store {}: {}",
+                    path.display(),
+                    e
+                )));
+            }
+        };
+
+        let store = Self {
+            path,
+            hash_secrets,
+            clients: RwLock::new(HashMap::new()),
+        };
+        {
+            let mut map = store.clients.write().unwrap();
+            for (id, client) in clients {
+                let stored = store.protect(client.clone());
+                dirty |= stored.client_info.client_secret != client.client_info.client_secret;
+                map.insert(id, stored);
+            }
+            if dirty {
+                store.persist(&map)?;
+            }
+        }
+        Ok(store)
+    }
+
+    /// Parse a store file; the flag tells whether it used the legacy layout.
+    fn parse(
+        path: &Path,
+        bytes: &[u8],
+    ) -> Result<(HashMap<String, OAuthClientInformationFull>, bool), ClientStoreError> {
+        let corrupted = |reason: String| {
+            ClientStoreError::Storage(format!("corrupted client store {}: {}", path.display(), reason))
+        };
+
+        match serde_json::from_slice::<AnyStoreFile>(bytes) {
+            Ok(AnyStoreFile::Current(file)) if file.version == FORMAT_VERSION => Ok((
+                file.clients.into_iter().map(|(id, client)| (id, client.into())).collect(),
+                false,
+            )),
+            Ok(AnyStoreFile::Current(file)) => {
+                Err(corrupted(format!("unsupported version {}", file.version)))
+            }
+            Ok(AnyStoreFile::Legacy(clients)) => Ok((clients, true)),
+            Err(_) => {
+                // Report the error against the current layout
+                let error = serde_json::from_slice::<StoreFile>(bytes)
+                    .err()
+                    .map(|e| e.to_string())
+                    .unwrap_or_default();
+                Err(corrupted(error))
+            }
+        }
+    }
+
+    /// Path of the store file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Whether only hashes of client secrets are kept.
+    pub fn hashes_secrets(&self) -> bool {
+        self.hash_secrets
+    }
+
+    /// Copy the clients of an in-memory store, replacing clients with the
+    /// same ID, and persist them.
+    pub fn import(&self, source: &InMemoryClientStore) -> Result<usize, ClientStoreError> {
+        let imported = source.clients();
+        self.update(|clients| {
+            let count = imported.len();
+            for client in imported {
+                clients.insert(client.client_info.client_id.clone(), self.protect(client));
+            }
+            Ok(count)
+        })
+    }
+
+    /// Hash the client's secret if hashing is enabled and it isn't already.
+    fn protect(&self, mut client: OAuthClientInformationFull) -> OAuthClientInformationFull {
+        let plain = client
+            .client_info
+            .client_secret
+            .as_deref()
+            .filter(|secret| self.hash_secrets && !secret.starts_with(SHA256_PREFIX));
+        if let Some(hashed) = plain.map(hash_secret) {
+            client.client_info.client_secret = Some(hashed);
+        }
+        client
+    }
+
+    /// Write `clients` to a temporary file and rename it over the store.
+    fn persist(&self, clients: &HashMap<String, OAuthClientInformationFull>) -> Result<(), ClientStoreError> {
+        let storage = |action: &str, e: std::io::Error| {
+            ClientStoreError::Storage(format!("cannot {} client store {}: {}", action, self.path.display(), e))
+        };
+
+        let file = StoreFile {
+            version: FORMAT_VERSION,
+            clients: clients
+                .iter()
+                .map(|(id, client)| (id.clone(), client.clone().into()))
+                .collect(),
+        };
+        let json = serde_json::to_vec_pretty(&file)
+            .map_err(|e| ClientStoreError::Storage(e.to_string()))?;
+
+        if let Some(dir) = self.path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+            std::fs::create_dir_all(dir).map_err(|e| storage("create directory for", e))?;
+        }
+        let mut tmp_name = self.path.file_name().unwrap_or_default().to_os_string();
+        tmp_name.push(".tmp");
+        let tmp = self.path.with_file_name(tmp_name);
+
+        let mut out = std::fs::File::create(&tmp).map_err(|e| storage("write", e))?;
+        out.write_all(&json)
+            .and_then(|_| out.sync_all())
+            .map_err(|e| storage("write", e))?;
+        std::fs::rename(&tmp, &self.path).map_err(|e| storage("replace", e))
+    }
+
+    /// Apply `change` to a copy of the clients, persist it, then commit it.
+    fn update<T>(
+        &self,
+        change: impl FnOnce(&mut HashMap<String, OAuthClientInformationFull>) -> Result<T, ClientStoreError>,
+    ) -> Result<T, ClientStoreError> {
+        let mut clients = self.clients.write().unwrap();
+        let mut updated = clients.clone();
+        let result = change(&mut updated)?;
+        self.persist(&updated)?;
+        *clients = updated;
+        Ok(result)
+    }
+}
+
+/// `sha256:` followed by the unpadded base64url SHA-256 digest.
+fn hash_secret(secret: &str) -> String {
+    format!("{}{}", SHA256_PREFIX, URL_SAFE_NO_PAD.encode(Sha256::digest(secret.as_bytes())))
+}
+
+#[async_trait]
+impl OAuthRegisteredClientsStore for FileClientStore {
+    async fn get_client(&self, client_id: &str) -> Result<Option<OAuthClientInformationFull>, ClientStoreError> {
+        let clients = self.clients.read().unwrap();
+        Ok(clients.get(client_id).cloned())
+    }
+
+    async fn register_client(&self, metadata: OAuthClientMetadata) -> Result<OAuthClientInformationFull, ClientStoreError> {
+        let client = new_registered_client(metadata)?;
+        let stored = self.protect(client.clone());
+        self.update(|clients| {
+            clients.insert(stored.client_info.client_id.clone(), stored);
+            Ok(())
+        })?;
+        Ok(client)
+    }
+
+    async fn update_client(&self, client_id: &str, metadata: OAuthClientMetadata) -> Result<OAuthClientInformationFull, ClientStoreError> {
+        self.update(|clients| match clients.get_mut(client_id) {
+            Some(existing) => {
+                existing.metadata = metadata;
+                Ok(existing.clone())
+            }
+            None => Err(ClientStoreError::NotFound(client_id.to_string())),
+        })
+    }
+
+    async fn delete_client(&self, client_id: &str) -> Result<(), ClientStoreError> {
+        self.update(|clients| match clients.remove(client_id) {
+            Some(_) => Ok(()),
+            None => Err(ClientStoreError::NotFound(client_id.to_string())),
+        })
+    }
+
+    fn verify_client_secret(&self, client: &OAuthClientInformationFull, presented: Option<&str>) -> bool {
+        match (&client.client_info.client_secret, presented) {
+            (None, _) => true,
+            (Some(expected), Some(presented)) if expected.starts_with(SHA256_PREFIX) => {
+                constant_time_eq(expected.as_bytes(), hash_secret(presented).as_bytes())
+            }
+            (Some(expected), Some(presented)) => constant_time_eq(expected.as_bytes(), presented.as_bytes()),
+            (Some(_), None) => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::executor::block_on;
+
+    use super::*;
+
+    /// A fresh path under the system temp directory.
+    fn store_path(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("mcp-client-store-{}", uuid::Uuid::new_v4()));
+        dir.join(name)
+    }
+
+    fn metadata() -> OAuthClientMetadata {
+        OAuthClientMetadata {
+            redirect_uris: vec!["http://localhost:8080/callback".to_string()],
+            client_name: Some("Test Client".to_string()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn registrations_survive_a_reload() {
+        block_on(async {
+            let path = store_path("clients.json");
+            let store = FileClientStore::open(&path).unwrap();
+            let registered = store.register_client(metadata()).await.unwrap();
+            let client_id = registered.client_info.client_id.clone();
+            drop(store);
+
+            let store = FileClientStore::open(&path).unwrap();
+            let loaded = store.get_client(&client_id).await.unwrap().unwrap();
+            assert_eq!(loaded.client_info.client_secret, registered.client_info.client_secret);
+            assert_eq!(loaded.metadata.client_name, Some("Test Client".to_string()));
+            assert!(store.verify_client_secret(&loaded, registered.client_info.client_secret.as_deref()));
+
+            store.delete_client(&client_id).await.unwrap();
+            drop(store);
+            let store = FileClientStore::open(&path).unwrap();
+            assert!(store.get_client(&client_id).await.unwrap().is_none());
+
+            let _ = std::fs::remove_dir_all(path.parent().unwrap());
+        });
+    }
+
+    #[test]
+    fn hashed_secrets_are_verified() {
+        block_on(async {
+            let path = store_path("clients.json");
+            let store = FileClientStore::open_with_secret_hashing(&path).unwrap();
+            let registered = store.register_client(metadata()).await.unwrap();
+            let secret = registered.client_info.client_secret.clone().unwrap();
+            drop(store);
+
+            let contents = std::fs::read_to_string(&path).unwrap();
+            assert!(!contents.contains(&secret));
+            assert!(contents.contains(SHA256_PREFIX));
+
+            let store = FileClientStore::open_with_secret_hashing(&path).unwrap();
+            let loaded = store
+                .get_client(&registered.client_info.client_id)
+                .await
+                .unwrap()
+                .unwrap();
+            assert_ne!(loaded.client_info.client_secret.as_deref(), Some(secret.as_str()));
+            assert!(store.verify_client_secret(&loaded, Some(&secret)));
+            assert!(!store.verify_client_secret(&loaded, Some("wrong")));
+            assert!(!store.verify_client_secret(&loaded, None));
+
+            let _ = std::fs::remove_dir_all(path.parent().unwrap());
+        });
+    }
+
+    #[test]
+    fn migrates_the_in_memory_layout() {
+        block_on(async {
+            let memory = InMemoryClientStore::new();
+            let registered = memory.register_client(metadata()).await.unwrap();
+            let client_id = registered.client_info.client_id.clone();
+
+            // A dump of the in-memory map is read and rewritten
+            let path = store_path("clients.json");
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            let legacy: HashMap<_, _> = memory
+                .clients()
+                .into_iter()
+                .map(|client| (client.client_info.client_id.clone(), client))
+                .collect();
+            std::fs::write(&path, serde_json::to_vec(&legacy).unwrap()).unwrap();
+
+            let store = FileClientStore::open_with_secret_hashing(&path).unwrap();
+            let loaded = store.get_client(&client_id).await.unwrap().unwrap();
+            assert!(store.verify_client_secret(&loaded, registered.client_info.client_secret.as_deref()));
+            let contents: serde_json::Value = serde_json::from_slice(&std::fs::read(&path).unwrap()).unwrap();
+            assert_eq!(contents["version"], FORMAT_VERSION);
+
+            // Or imported directly
+            let path = store_path("imported.json");
+            let store = FileClientStore::open(&path).unwrap();
+            assert_eq!(store.import(&memory).unwrap(), 1);
+            drop(store);
+            let store = FileClientStore::open(&path).unwrap();
+            assert!(store.get_client(&client_id).await.unwrap().is_some());
+
+            let _ = std::fs::remove_dir_all(path.parent().unwrap());
+        });
+    }
+
+    #[test]
+    fn corrupted_files_fail_to_open() {
+        let path = store_path("clients.json");
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+
+        std::fs::write(&path, "{\"version\": 1, \"clients\": ").unwrap();
+        let error = FileClientStore::open(&path).err().unwrap();
+        assert!(error.to_string().contains("corrupted client store"), "{error}");
+
+        std::fs::write(&path, "{\"version\": 2, \"clients\": {}}").unwrap();
+        let error = FileClientStore::open(&path).err().unwrap();
+        assert!(error.to_string().contains("unsupported version 2"), "{error}");
+
+        let _ = std::fs::remove_dir_all(path.parent().unwrap());
+    }
+}
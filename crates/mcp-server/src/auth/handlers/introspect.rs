@@ -0,0 +1,64 @@
+//! Token introspection endpoint handler (RFC 7662).
+
+use std::sync::Arc;
+
+use axum::extract::State;
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::{Extension, Form};
+use serde::Deserialize;
+
+use mcp_core::auth::{OAuthClientInformationFull, OAuthErrorResponse, OAuthTokenIntrospectionResponse};
+
+use crate::auth::provider::{OAuthProviderError, OAuthServerProvider};
+use crate::auth::router::OAuthRouterState;
+
+/// Token introspection request parameters.
+#[derive(Debug, Deserialize)]
+pub struct IntrospectRequest {
+    /// The token to introspect.
+    pub token: String,
+    /// A hint about the type of the token.
+    pub token_type_hint: Option<String>,
+}
+
+/// Token introspection endpoint handler.
+///
+/// The caller is authenticated by `ClientAuthMiddleware`, which places the
+/// client in the request extensions.
+pub async fn introspect_handler<P: OAuthServerProvider + 'static>(
+    State(state): State<Arc<OAuthRouterState<P>>>,
+    Extension(client): Extension<OAuthClientInformationFull>,
+    Form(request): Form<IntrospectRequest>,
+) -> Response {
+    let result = state
+        .provider
+        .introspect_token(&client, &request.token, request.token_type_hint.as_deref())
+        .await;
+
+    match result {
+        Ok(response) if response.active => axum::Json(response).into_response(),
+        // RFC 7662: inactive tokens reveal nothing beyond `active`
+        Ok(_) | Err(OAuthProviderError::InvalidToken(_)) => {
+            axum::Json(OAuthTokenIntrospectionResponse::inactive()).into_response()
+        }
+        Err(e @ OAuthProviderError::Unsupported(_)) => {
+            error_response(StatusCode::NOT_IMPLEMENTED, e.error_code(), &e.to_string())
+        }
+        Err(e @ OAuthProviderError::Server(_)) => {
+            error_response(StatusCode::INTERNAL_SERVER_ERROR, e.error_code(), &e.to_string())
+        }
+        Err(e) => error_response(StatusCode::BAD_REQUEST, e.error_code(), &e.to_string()),
+    }
+}
+
+/// Create an error response.
+fn error_response(status: StatusCode, error: &str, description: &str) -> Response {
+    let body = OAuthErrorResponse {
+        error: error.to_string(),
+        error_description: Some(description.to_string()),
+        error_uri: None,
+    };
+
+    (status, axum::Json(body)).into_response()
+}
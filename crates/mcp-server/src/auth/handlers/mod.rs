@@ -3,12 +3,14 @@
 #![cfg(feature = "axum")]
 
 mod authorize;
+mod introspect;
 mod metadata;
 mod register;
 mod revoke;
 mod token;
 
 pub use authorize::authorize_handler;
+pub use introspect::introspect_handler;
 pub use metadata::metadata_handler;
 pub use register::register_handler;
 pub use revoke::revoke_handler;
@@ -63,17 +63,16 @@ pub async fn revoke_handler<P: OAuthServerProvider + 'static>(
     };
 
     // Validate client secret if the client has one
-    if let Some(expected_secret) = &client.client_info.client_secret {
-        match &request.client_secret {
-            Some(secret) if secret == expected_secret => {}
-            _ => {
-                return error_response(
-                    StatusCode::UNAUTHORIZED,
-                    "invalid_client",
-                    "Invalid client credentials",
-                );
-            }
-        }
+    if !state
+        .provider
+        .clients_store()
+        .verify_client_secret(&client, request.client_secret.as_deref())
+    {
+        return error_response(
+            StatusCode::UNAUTHORIZED,
+            "invalid_client",
+            "Invalid client credentials",
+        );
     }
 
     // Revoke the token
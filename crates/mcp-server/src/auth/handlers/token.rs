@@ -73,17 +73,16 @@ pub async fn token_handler<P: OAuthServerProvider + 'static>(
     };
 
     // Validate client secret if the client has one
-    if let Some(expected_secret) = &client.client_info.client_secret {
-        match &request.client_secret {
-            Some(secret) if secret == expected_secret => {}
-            _ => {
-                return error_response(
-                    StatusCode::UNAUTHORIZED,
-                    "invalid_client",
-                    "Invalid client credentials",
-                );
-            }
-        }
+    if !state
+        .provider
+        .clients_store()
+        .verify_client_secret(&client, request.client_secret.as_deref())
+    {
+        return error_response(
+            StatusCode::UNAUTHORIZED,
+            "invalid_client",
+            "Invalid client credentials",
+        );
     }
 
     // Handle grant type
@@ -199,13 +198,24 @@ async fn handle_refresh_token_grant<P: OAuthServerProvider + 'static>(
         s.split_whitespace().map(|s| s.to_string()).collect::<Vec<_>>()
     });
 
-    // Exchange the refresh token
-    match state.provider.exchange_refresh_token(
-        client,
-        refresh_token,
-        scopes.as_deref(),
-        request.resource.as_deref(),
-    ).await {
+    // Exchange the refresh token, rotating it unless disabled
+    let result = if state.rotate_refresh_tokens {
+        state.provider.rotate_refresh_token(
+            client,
+            refresh_token,
+            scopes.as_deref(),
+            request.resource.as_deref(),
+        ).await
+    } else {
+        state.provider.exchange_refresh_token(
+            client,
+            refresh_token,
+            scopes.as_deref(),
+            request.resource.as_deref(),
+        ).await
+    };
+
+    match result {
         Ok(tokens) => {
             (StatusCode::OK, axum::Json(tokens)).into_response()
         }
@@ -0,0 +1,115 @@
+//! Token verifier backed by a remote introspection endpoint (RFC 7662).
+//!
+//! Resource servers that do not issue tokens themselves can ask the
+//! authorization server whether a bearer token is active. Each call is an
+//! HTTP round trip, so the verifier is normally wrapped in a
+//! `CachingTokenVerifier`.
+//!
+//! ## Example
+//!
+//! ```ignore
+//! use std::sync::Arc;
+//! use mcp_server::auth::{CacheConfig, IntrospectionTokenVerifier};
+//! use mcp_server::auth::middleware::BearerAuthLayer;
+//!
+//! let verifier = IntrospectionTokenVerifier::new("https://auth.example.com/introspect", "resource-server")
+//!     .with_client_secret("secret")
+//!     .cached(CacheConfig::default());
+//! let router = router.layer(BearerAuthLayer::new(Arc::new(verifier)));
+//! ```
+
+use std::time::Duration;
+
+use async_trait::async_trait;
+use base64::{Engine, engine::general_purpose::STANDARD};
+
+use mcp_core::auth::{AuthInfo, OAuthTokenIntrospectionResponse};
+
+use super::caching_verifier::{CacheConfig, CachingTokenVerifier};
+use super::provider::{OAuthProviderError, OAuthTokenVerifier};
+
+/// Default timeout for introspection requests.
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Verifies access tokens by calling an RFC 7662 introspection endpoint.
+///
+/// The verifier authenticates to the endpoint with HTTP Basic credentials.
+/// Inactive tokens are rejected with `InvalidToken`; transport failures and
+/// unexpected responses are reported as `Server` errors, which
+/// `CachingTokenVerifier` does not cache.
+#[derive(Debug, Clone)]
+pub struct IntrospectionTokenVerifier {
+    endpoint: String,
+    client_id: String,
+    client_secret: Option<String>,
+    timeout: Duration,
+}
+
+impl IntrospectionTokenVerifier {
+    /// Create a verifier for `endpoint`, authenticating as `client_id`.
+    pub fn new(endpoint: impl Into<String>, client_id: impl Into<String>) -> Self {
+        Self {
+            endpoint: endpoint.into(),
+            client_id: client_id.into(),
+            client_secret: None,
+            timeout: DEFAULT_TIMEOUT,
+        }
+    }
+
+    /// Set the client secret sent with each request.
+    pub fn with_client_secret(mut self, client_secret: impl Into<String>) -> Self {
+        self.client_secret = Some(client_secret.into());
+        self
+    }
+
+    /// Set the request timeout.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// The introspection endpoint URL.
+    pub fn endpoint(&self) -> &str {
+        &self.endpoint
+    }
+
+    /// Wrap the verifier in a `CachingTokenVerifier`.
+    pub fn cached(self, config: CacheConfig) -> CachingTokenVerifier<Self> {
+        CachingTokenVerifier::new(self, config)
+    }
+
+    /// Introspect `token`, blocking the current thread.
+    fn introspect(&self, token: &str) -> Result<OAuthTokenIntrospectionResponse, OAuthProviderError> {
+        let credentials = format!(
+            "{}:{}",
+            self.client_id,
+            self.client_secret.as_deref().unwrap_or_default()
+        );
+
+        let response = ureq::post(&self.endpoint)
+            .timeout(self.timeout)
+            .set("Authorization", &format!("Basic {}", STANDARD.encode(credentials)))
+            .set("Accept", "application/json")
+            .send_form(&[("token", token), ("token_type_hint", "access_token")])
+            .map_err(|e| OAuthProviderError::Server(format!("introspection request failed: {}", e)))?;
+
+        response
+            .into_json()
+            .map_err(|e| OAuthProviderError::Server(format!("invalid introspection response: {}", e)))
+    }
+}
+
+#[async_trait]
+impl OAuthTokenVerifier for IntrospectionTokenVerifier {
+    async fn verify_access_token(&self, token: &str) -> Result<AuthInfo, OAuthProviderError> {
+        let verifier = self.clone();
+        let owned = token.to_string();
+        let response = tokio::task::spawn_blocking(move || verifier.introspect(&owned))
+            .await
+            .map_err(|e| OAuthProviderError::Server(format!("introspection task failed: {}", e)))??;
+
+        response
+            .into_auth_info(token)
+            .ok_or_else(|| OAuthProviderError::InvalidToken("Token is not active".to_string()))
+    }
+}
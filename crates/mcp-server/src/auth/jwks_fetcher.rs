@@ -0,0 +1,67 @@
+//! Retrieval of JSON Web Key Sets for `JwtTokenVerifier`.
+
+use std::time::Duration;
+
+use async_trait::async_trait;
+use serde_json::Value;
+
+use super::provider::OAuthProviderError;
+
+/// Default timeout for JWKS requests.
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Fetches the JWKS document published at a URI.
+///
+/// `JwtTokenVerifier` uses [`HttpJwksFetcher`] unless another fetcher is
+/// injected, e.g. one serving a fixed key set in tests.
+#[async_trait]
+pub trait JwksFetcher: Send + Sync {
+    /// Fetch the JWKS document (`{"keys": [...]}`) at `jwks_uri`.
+    async fn fetch(&self, jwks_uri: &str) -> Result<Value, OAuthProviderError>;
+}
+
+/// Fetches key sets over HTTP.
+#[derive(Debug, Clone)]
+pub struct HttpJwksFetcher {
+    timeout: Duration,
+}
+
+impl HttpJwksFetcher {
+    /// Create a fetcher with the default timeout.
+    pub fn new() -> Self {
+        Self {
+            timeout: DEFAULT_TIMEOUT,
+        }
+    }
+
+    /// Set the request timeout.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+}
+
+impl Default for HttpJwksFetcher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl JwksFetcher for HttpJwksFetcher {
+    async fn fetch(&self, jwks_uri: &str) -> Result<Value, OAuthProviderError> {
+        let uri = jwks_uri.to_string();
+        let timeout = self.timeout;
+        tokio::task::spawn_blocking(move || {
+            ureq::get(&uri)
+                .timeout(timeout)
+                .set("Accept", "application/json")
+                .call()
+                .map_err(|e| OAuthProviderError::Server(format!("JWKS request failed: {}", e)))?
+                .into_json()
+                .map_err(|e| OAuthProviderError::Server(format!("invalid JWKS document: {}", e)))
+        })
+        .await
+        .map_err(|e| OAuthProviderError::Server(format!("JWKS task failed: {}", e)))?
+    }
+}
@@ -0,0 +1,214 @@
+//! Verification of JWT access tokens against a JWKS endpoint.
+//!
+//! External identity providers (Keycloak, Auth0, ...) issue signed JWT
+//! access tokens and publish their public keys as a JSON Web Key Set.
+//! `JwtTokenVerifier` checks the signature with the key named by the
+//! token's `kid`, then validates `exp`, `nbf`, `iss` and `aud`.
+//!
+//! ## Example
+//!
+//! ```ignore
+//! use std::sync::Arc;
+//! use std::time::Duration;
+//! use mcp_server::auth::JwtTokenVerifier;
+//! use mcp_server::auth::middleware::BearerAuthLayer;
+//!
+//! let verifier = JwtTokenVerifier::new(
+//!     "https://idp.example.com/realms/mcp",
+//!     "mcp-server",
+//!     "https://idp.example.com/realms/mcp/protocol/openid-connect/certs",
+//! )
+//! .with_leeway(Duration::from_secs(30));
+//! let router = router.layer(BearerAuthLayer::new(Arc::new(verifier)));
+//! ```
+
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use jsonwebtoken::jwk::{Jwk, JwkSet};
+use jsonwebtoken::{Algorithm, DecodingKey, Validation};
+use serde_json::Value;
+
+use mcp_core::auth::AuthInfo;
+
+use super::jwks_fetcher::{HttpJwksFetcher, JwksFetcher};
+use super::provider::{OAuthProviderError, OAuthTokenVerifier};
+
+/// Default tolerance for clock differences with the issuer.
+const DEFAULT_LEEWAY: Duration = Duration::from_secs(60);
+
+/// Default minimum time between two JWKS fetches.
+const DEFAULT_REFRESH_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Key set fetched from the JWKS URI.
+struct CachedKeys {
+    keys: JwkSet,
+    fetched_at: Instant,
+}
+
+/// Verifies JWT access tokens signed by keys from a JWKS URI.
+///
+/// Keys are fetched on first use and cached. A token whose `kid` is not in
+/// the cache triggers a refetch, so rotated keys are picked up, but at most
+/// once per refresh interval. Only asymmetric algorithms are accepted.
+///
+/// Scopes are read from the space-separated `scope` claim or the `scp`
+/// claim (string or list). `client_id` comes from `client_id` or `azp`, and
+/// all claims are kept in `AuthInfo::extra`.
+pub struct JwtTokenVerifier {
+    issuer: String,
+    audience: String,
+    jwks_uri: String,
+    leeway: Duration,
+    refresh_interval: Duration,
+    fetcher: Arc<dyn JwksFetcher>,
+    cache: RwLock<Option<CachedKeys>>,
+}
+
+impl JwtTokenVerifier {
+    /// Create a verifier accepting tokens from `issuer` for `audience`.
+    pub fn new(issuer: impl Into<String>, audience: impl Into<String>, jwks_uri: impl Into<String>) -> Self {
+        Self {
+            issuer: issuer.into(),
+            audience: audience.into(),
+            jwks_uri: jwks_uri.into(),
+            leeway: DEFAULT_LEEWAY,
+            refresh_interval: DEFAULT_REFRESH_INTERVAL,
+            fetcher: Arc::new(HttpJwksFetcher::new()),
+            cache: RwLock::new(None),
+        }
+    }
+
+    /// Set the tolerance for clock skew when checking `exp` and `nbf`.
+    pub fn with_leeway(mut self, leeway: Duration) -> Self {
+        self.leeway = leeway;
+        self
+    }
+
+    /// Set the minimum time between JWKS fetches triggered by unknown keys.
+    pub fn with_refresh_interval(mut self, interval: Duration) -> Self {
+        self.refresh_interval = interval;
+        self
+    }
+
+    /// Use `fetcher` to retrieve the key set.
+    pub fn with_fetcher(mut self, fetcher: Arc<dyn JwksFetcher>) -> Self {
+        self.fetcher = fetcher;
+        self
+    }
+
+    /// The expected `iss` claim.
+    pub fn issuer(&self) -> &str {
+        &self.issuer
+    }
+
+    /// The expected `aud` claim.
+    pub fn audience(&self) -> &str {
+        &self.audience
+    }
+
+    /// Find the key for `kid`, fetching the key set when it is unknown.
+    async fn key_for(&self, kid: Option<&str>, alg: Algorithm) -> Result<Jwk, OAuthProviderError> {
+        let stale = {
+            let cache = self.cache.read().unwrap();
+            if let Some(key) = cache.as_ref().and_then(|cached| find_key(&cached.keys, kid, alg)) {
+                return Ok(key);
+            }
+            cache
+                .as_ref()
+                .is_none_or(|cached| cached.fetched_at.elapsed() >= self.refresh_interval)
+        };
+        if !stale {
+            return Err(unknown_key(kid));
+        }
+
+        let document = self.fetcher.fetch(&self.jwks_uri).await?;
+        let keys: JwkSet = serde_json::from_value(document)
+            .map_err(|e| OAuthProviderError::Server(format!("invalid JWKS document: {}", e)))?;
+        let key = find_key(&keys, kid, alg);
+        *self.cache.write().unwrap() = Some(CachedKeys {
+            keys,
+            fetched_at: Instant::now(),
+        });
+        key.ok_or_else(|| unknown_key(kid))
+    }
+}
+
+#[async_trait]
+impl OAuthTokenVerifier for JwtTokenVerifier {
+    async fn verify_access_token(&self, token: &str) -> Result<AuthInfo, OAuthProviderError> {
+        let header = jsonwebtoken::decode_header(token).map_err(invalid_token)?;
+        if matches!(header.alg, Algorithm::HS256 | Algorithm::HS384 | Algorithm::HS512) {
+            return Err(OAuthProviderError::InvalidToken(
+                "symmetric signing algorithms are not accepted".to_string(),
+            ));
+        }
+
+        let jwk = self.key_for(header.kid.as_deref(), header.alg).await?;
+        let key = DecodingKey::from_jwk(&jwk).map_err(invalid_token)?;
+
+        let mut validation = Validation::new(header.alg);
+        validation.leeway = self.leeway.as_secs();
+        validation.validate_nbf = true;
+        validation.set_issuer(&[&self.issuer]);
+        validation.set_audience(&[&self.audience]);
+        let claims = jsonwebtoken::decode::<Value>(token, &key, &validation)
+            .map_err(invalid_token)?
+            .claims;
+
+        Ok(auth_info(token, claims))
+    }
+}
+
+/// The key named `kid`, or the only key usable with `alg` when the token
+/// has no `kid`
+fn find_key(keys: &JwkSet, kid: Option<&str>, alg: Algorithm) -> Option<Jwk> {
+    match kid {
+        Some(kid) => keys.find(kid).cloned(),
+        None => {
+            let mut usable = keys.keys.iter().filter(|key| {
+                key.common
+                    .key_algorithm
+                    .is_none_or(|key_alg| key_alg.to_string() == format!("{:?}", alg))
+            });
+            match (usable.next(), usable.next()) {
+                (Some(key), None) => Some(key.clone()),
+                _ => None,
+            }
+        }
+    }
+}
+
+fn auth_info(token: &str, claims: Value) -> AuthInfo {
+    let scopes = match claims.get("scope").or_else(|| claims.get("scp")) {
+        Some(Value::String(scopes)) => scopes.split_whitespace().map(str::to_string).collect(),
+        Some(Value::Array(scopes)) => scopes.iter().filter_map(Value::as_str).map(str::to_string).collect(),
+        _ => Vec::new(),
+    };
+
+    let mut info = AuthInfo::new(token).with_scopes(scopes);
+    if let Some(client_id) = claims
+        .get("client_id")
+        .or_else(|| claims.get("azp"))
+        .and_then(Value::as_str)
+    {
+        info = info.with_client_id(client_id);
+    }
+    if let Some(exp) = claims.get("exp").and_then(Value::as_u64) {
+        info = info.with_expires_at(exp);
+    }
+    info.extra = Some(claims);
+    info
+}
+
+fn unknown_key(kid: Option<&str>) -> OAuthProviderError {
+    OAuthProviderError::InvalidToken(match kid {
+        Some(kid) => format!("unknown signing key '{}'", kid),
+        None => "no signing key matches the token".to_string(),
+    })
+}
+
+fn invalid_token(error: jsonwebtoken::errors::Error) -> OAuthProviderError {
+    OAuthProviderError::InvalidToken(error.to_string())
+}
@@ -0,0 +1,139 @@
+//! API key authentication middleware.
+//!
+//! This middleware checks a shared secret from the `X-API-Key` header, or
+//! a Bearer token in the Authorization header, against an [`ApiKeyStore`]
+//! and adds the matching authentication information to the request
+//! extensions.
+
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use axum::body::Body;
+use axum::http::{HeaderMap, Request, Response, StatusCode, header};
+use tower::{Layer, Service};
+
+use mcp_core::types::{ErrorObject, MessageId, ResultMessage};
+
+use super::bearer_auth::parse_bearer_token;
+use crate::auth::api_keys::ApiKeyStore;
+
+/// Header carrying the API key.
+pub const API_KEY_HEADER: &str = "x-api-key";
+
+/// Layer for API key authentication.
+#[derive(Clone)]
+pub struct ApiKeyLayer {
+    store: Arc<dyn ApiKeyStore>,
+}
+
+impl ApiKeyLayer {
+    /// Create a new API key layer.
+    pub fn new(store: Arc<dyn ApiKeyStore>) -> Self {
+        Self { store }
+    }
+}
+
+impl<S> Layer<S> for ApiKeyLayer {
+    type Service = ApiKeyMiddleware<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        ApiKeyMiddleware {
+            inner,
+            store: Arc::clone(&self.store),
+        }
+    }
+}
+
+/// Middleware for API key authentication.
+#[derive(Clone)]
+pub struct ApiKeyMiddleware<S> {
+    inner: S,
+    store: Arc<dyn ApiKeyStore>,
+}
+
+impl<S> Service<Request<Body>> for ApiKeyMiddleware<S>
+where
+    S: Service<Request<Body>, Response = Response<Body>> + Clone + Send + 'static,
+    S::Future: Send,
+{
+    type Response = Response<Body>;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut req: Request<Body>) -> Self::Future {
+        let store = Arc::clone(&self.store);
+        let mut inner = self.inner.clone();
+
+        Box::pin(async move {
+            let Some(key) = presented_key(req.headers()) else {
+                return Ok(error_response(StatusCode::UNAUTHORIZED, "Missing API key"));
+            };
+
+            let auth_info = match store.verify_api_key(&key).await {
+                Ok(Some(info)) => info,
+                Ok(None) => {
+                    return Ok(error_response(StatusCode::UNAUTHORIZED, "Invalid API key"));
+                }
+                Err(e) => {
+                    return Ok(error_response(
+                        StatusCode::INTERNAL_SERVER_ERROR,
+                        &e.to_string(),
+                    ));
+                }
+            };
+
+            // Add auth info to request extensions
+            req.extensions_mut().insert(auth_info);
+
+            inner.call(req).await
+        })
+    }
+}
+
+/// The key from `X-API-Key`, falling back to a Bearer token.
+fn presented_key(headers: &HeaderMap) -> Option<String> {
+    if let Some(value) = headers.get(API_KEY_HEADER) {
+        return value.to_str().ok().map(str::to_string);
+    }
+    let authorization = headers.get(header::AUTHORIZATION)?.to_str().ok()?;
+    parse_bearer_token(authorization).map(str::to_string)
+}
+
+/// Create a JSON-RPC error response.
+fn error_response(status: StatusCode, message: &str) -> Response<Body> {
+    let body = ResultMessage::failure(MessageId::Null, ErrorObject::new(-32000, message, None));
+
+    let mut response = Response::builder()
+        .status(status)
+        .header(header::CONTENT_TYPE, "application/json");
+    if status == StatusCode::UNAUTHORIZED {
+        response = response.header(header::WWW_AUTHENTICATE, "Bearer");
+    }
+    response
+        .body(Body::from(serde_json::to_string(&body).unwrap_or_default()))
+        .unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_presented_key() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(presented_key(&headers), None);
+
+        headers.insert(header::AUTHORIZATION, "Bearer from-bearer".parse().unwrap());
+        assert_eq!(presented_key(&headers).as_deref(), Some("from-bearer"));
+
+        // X-API-Key wins over Authorization
+        headers.insert(API_KEY_HEADER, "from-header".parse().unwrap());
+        assert_eq!(presented_key(&headers).as_deref(), Some("from-header"));
+    }
+}
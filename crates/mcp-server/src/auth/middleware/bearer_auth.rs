@@ -10,12 +10,17 @@ use std::task::{Context, Poll};
 
 use axum::body::Body;
 use axum::http::{header, Request, Response, StatusCode};
+use serde_json::Value;
 use tower::{Layer, Service};
 
 use mcp_core::auth::{AuthInfo, OAuthErrorResponse};
 
+use super::scope_policy::ScopePolicy;
 use crate::auth::provider::{OAuthProviderError, OAuthTokenVerifier};
 
+/// Largest request body read to evaluate a [`ScopePolicy`].
+const MAX_INSPECTED_BODY_SIZE: usize = 4 * 1024 * 1024;
+
 /// Options for bearer authentication middleware.
 #[derive(Debug, Clone)]
 pub struct BearerAuthOptions {
@@ -23,6 +28,8 @@ pub struct BearerAuthOptions {
     pub required_scopes: Vec<String>,
     /// URL of the protected resource metadata for WWW-Authenticate header.
     pub resource_metadata_url: Option<String>,
+    /// Scopes required for particular JSON-RPC methods and tools.
+    pub scope_policy: ScopePolicy,
 }
 
 impl Default for BearerAuthOptions {
@@ -30,6 +37,7 @@ impl Default for BearerAuthOptions {
         Self {
             required_scopes: Vec::new(),
             resource_metadata_url: None,
+            scope_policy: ScopePolicy::default(),
         }
     }
 }
@@ -51,15 +59,34 @@ impl BearerAuthOptions {
         self.resource_metadata_url = Some(url.into());
         self
     }
+
+    /// Set the per-method and per-tool scope policy.
+    ///
+    /// The request body is read to find the JSON-RPC method and tool name;
+    /// a token missing any required scope gets `403 Forbidden` with
+    /// `error="insufficient_scope"` and the required scopes.
+    pub fn with_scope_policy(mut self, policy: ScopePolicy) -> Self {
+        self.scope_policy = policy;
+        self
+    }
 }
 
 /// Layer for bearer authentication.
-#[derive(Clone)]
 pub struct BearerAuthLayer<V> {
     verifier: Arc<V>,
     options: BearerAuthOptions,
 }
 
+// Not derived: the verifier is shared, so it need not be `Clone` itself
+impl<V> Clone for BearerAuthLayer<V> {
+    fn clone(&self) -> Self {
+        Self {
+            verifier: Arc::clone(&self.verifier),
+            options: self.options.clone(),
+        }
+    }
+}
+
 impl<V> BearerAuthLayer<V>
 where
     V: OAuthTokenVerifier + 'static,
@@ -94,19 +121,27 @@ where
 }
 
 /// Middleware for bearer authentication.
-#[derive(Clone)]
 pub struct BearerAuthMiddleware<S, V> {
     inner: S,
     verifier: Arc<V>,
     options: BearerAuthOptions,
 }
 
-impl<S, V, ReqBody> Service<Request<ReqBody>> for BearerAuthMiddleware<S, V>
+impl<S: Clone, V> Clone for BearerAuthMiddleware<S, V> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            verifier: Arc::clone(&self.verifier),
+            options: self.options.clone(),
+        }
+    }
+}
+
+impl<S, V> Service<Request<Body>> for BearerAuthMiddleware<S, V>
 where
-    S: Service<Request<ReqBody>, Response = Response<Body>> + Clone + Send + 'static,
+    S: Service<Request<Body>, Response = Response<Body>> + Clone + Send + 'static,
     S::Future: Send,
     V: OAuthTokenVerifier + 'static,
-    ReqBody: Send + 'static,
 {
     type Response = Response<Body>;
     type Error = S::Error;
@@ -116,7 +151,7 @@ where
         self.inner.poll_ready(cx)
     }
 
-    fn call(&mut self, mut req: Request<ReqBody>) -> Self::Future {
+    fn call(&mut self, mut req: Request<Body>) -> Self::Future {
         let verifier = Arc::clone(&self.verifier);
         let options = self.options.clone();
         let mut inner = self.inner.clone();
@@ -133,6 +168,7 @@ where
                             StatusCode::UNAUTHORIZED,
                             "invalid_token",
                             "Invalid Authorization header encoding",
+                            &options.required_scopes,
                             &options,
                         ));
                     }
@@ -142,6 +178,7 @@ where
                         StatusCode::UNAUTHORIZED,
                         "invalid_token",
                         "Missing Authorization header",
+                        &options.required_scopes,
                         &options,
                     ));
                 }
@@ -155,6 +192,7 @@ where
                         StatusCode::UNAUTHORIZED,
                         "invalid_token",
                         "Invalid Authorization header format, expected 'Bearer TOKEN'",
+                        &options.required_scopes,
                         &options,
                     ));
                 }
@@ -172,6 +210,7 @@ where
                         StatusCode::UNAUTHORIZED,
                         error,
                         &description,
+                        &options.required_scopes,
                         &options,
                     ));
                 }
@@ -183,6 +222,7 @@ where
                     StatusCode::UNAUTHORIZED,
                     "invalid_token",
                     "Token has expired",
+                    &options.required_scopes,
                     &options,
                 ));
             }
@@ -195,11 +235,43 @@ where
                         StatusCode::FORBIDDEN,
                         "insufficient_scope",
                         "Insufficient scope",
+                        &options.required_scopes,
                         &options,
                     ));
                 }
             }
 
+            // Check the scope policy against the JSON-RPC request
+            if !options.scope_policy.is_empty() {
+                let (parts, body) = req.into_parts();
+                let bytes = match axum::body::to_bytes(body, MAX_INSPECTED_BODY_SIZE).await {
+                    Ok(bytes) => bytes,
+                    Err(_) => {
+                        return Ok(Response::builder()
+                            .status(StatusCode::PAYLOAD_TOO_LARGE)
+                            .body(Body::empty())
+                            .unwrap());
+                    }
+                };
+
+                // Malformed bodies are left for the transport to reject
+                if let Ok(message) = serde_json::from_slice::<Value>(&bytes) {
+                    let required = options.scope_policy.required_scopes(&message);
+                    let scope_refs: Vec<&str> = required.iter().map(|s| s.as_str()).collect();
+                    if !auth_info.has_scopes(&scope_refs) {
+                        return Ok(error_response(
+                            StatusCode::FORBIDDEN,
+                            "insufficient_scope",
+                            "Insufficient scope",
+                            &required,
+                            &options,
+                        ));
+                    }
+                }
+
+                req = Request::from_parts(parts, Body::from(bytes));
+            }
+
             // Add auth info to request extensions
             req.extensions_mut().insert(auth_info);
 
@@ -210,7 +282,7 @@ where
 }
 
 /// Parse a Bearer token from the Authorization header.
-fn parse_bearer_token(header: &str) -> Option<&str> {
+pub(super) fn parse_bearer_token(header: &str) -> Option<&str> {
     let parts: Vec<&str> = header.splitn(2, ' ').collect();
     if parts.len() == 2 && parts[0].eq_ignore_ascii_case("bearer") {
         Some(parts[1])
@@ -224,12 +296,13 @@ fn error_response(
     status: StatusCode,
     error: &str,
     description: &str,
+    scopes: &[String],
     options: &BearerAuthOptions,
 ) -> Response<Body> {
     let mut www_auth = format!("Bearer error=\"{}\", error_description=\"{}\"", error, description);
 
-    if !options.required_scopes.is_empty() {
-        www_auth.push_str(&format!(", scope=\"{}\"", options.required_scopes.join(" ")));
+    if !scopes.is_empty() {
+        www_auth.push_str(&format!(", scope=\"{}\"", scopes.join(" ")));
     }
 
     if let Some(ref url) = options.resource_metadata_url {
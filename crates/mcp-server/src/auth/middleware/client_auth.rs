@@ -106,17 +106,12 @@ where
             };
 
             // Validate client secret if the client has one
-            if let Some(expected_secret) = &client.client_info.client_secret {
-                match &client_secret {
-                    Some(secret) if secret == expected_secret => {}
-                    _ => {
-                        return Ok(error_response(
-                            StatusCode::UNAUTHORIZED,
-                            "invalid_client",
-                            "Invalid client credentials",
-                        ));
-                    }
-                }
+            if !store.verify_client_secret(&client, client_secret.as_deref()) {
+                return Ok(error_response(
+                    StatusCode::UNAUTHORIZED,
+                    "invalid_client",
+                    "Invalid client credentials",
+                ));
             }
 
             // Add client info to request extensions
@@ -2,12 +2,20 @@
 //!
 //! This module provides middleware for OAuth authentication.
 
+#[cfg(feature = "axum")]
+mod api_key_auth;
 #[cfg(feature = "axum")]
 mod bearer_auth;
 #[cfg(feature = "axum")]
 mod client_auth;
+#[cfg(feature = "axum")]
+mod scope_policy;
 
+#[cfg(feature = "axum")]
+pub use api_key_auth::{API_KEY_HEADER, ApiKeyLayer, ApiKeyMiddleware};
 #[cfg(feature = "axum")]
 pub use bearer_auth::{BearerAuthLayer, BearerAuthMiddleware, BearerAuthOptions};
 #[cfg(feature = "axum")]
 pub use client_auth::{ClientAuthLayer, ClientAuthMiddleware};
+#[cfg(feature = "axum")]
+pub use scope_policy::ScopePolicy;
@@ -0,0 +1,128 @@
+//! Per-method and per-tool scope requirements for bearer authentication.
+
+use std::collections::HashMap;
+
+use serde_json::Value;
+
+/// Scopes a token needs for particular JSON-RPC requests.
+///
+/// Requirements can be attached to a JSON-RPC method (`resources/read`) or,
+/// for `tools/call`, to a tool name taken from `params.name`. A request
+/// needs the union of its method's and its tool's scopes; for batches, the
+/// union over every request in the batch.
+///
+/// ## Example
+///
+/// ```ignore
+/// use mcp_server::auth::middleware::{BearerAuthOptions, ScopePolicy};
+///
+/// let options = BearerAuthOptions::new().with_scope_policy(
+///     ScopePolicy::new()
+///         .require_for_method("resources/read", ["files:read"])
+///         .require_for_tool("write_file", ["files:write"]),
+/// );
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ScopePolicy {
+    methods: HashMap<String, Vec<String>>,
+    tools: HashMap<String, Vec<String>>,
+}
+
+impl ScopePolicy {
+    /// Create an empty policy.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Require `scopes` for every request with the given JSON-RPC method.
+    pub fn require_for_method<I, S>(mut self, method: impl Into<String>, scopes: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.methods
+            .entry(method.into())
+            .or_default()
+            .extend(scopes.into_iter().map(Into::into));
+        self
+    }
+
+    /// Require `scopes` for `tools/call` requests naming `tool`.
+    pub fn require_for_tool<I, S>(mut self, tool: impl Into<String>, scopes: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.tools
+            .entry(tool.into())
+            .or_default()
+            .extend(scopes.into_iter().map(Into::into));
+        self
+    }
+
+    /// Whether the policy has no requirements.
+    pub fn is_empty(&self) -> bool {
+        self.methods.is_empty() && self.tools.is_empty()
+    }
+
+    /// Scopes required by a JSON-RPC message or batch, without duplicates.
+    pub fn required_scopes(&self, message: &Value) -> Vec<String> {
+        let mut required: Vec<String> = Vec::new();
+        let messages = match message {
+            Value::Array(batch) => batch.iter().collect(),
+            single => vec![single],
+        };
+
+        for message in messages {
+            let Some(method) = message.get("method").and_then(Value::as_str) else {
+                continue;
+            };
+            let tool_scopes = (method == "tools/call")
+                .then(|| message.pointer("/params/name").and_then(Value::as_str))
+                .flatten()
+                .and_then(|tool| self.tools.get(tool));
+            let scopes = self.methods.get(method).into_iter().chain(tool_scopes).flatten();
+            for scope in scopes {
+                if !required.contains(scope) {
+                    required.push(scope.clone());
+                }
+            }
+        }
+
+        required
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn policy() -> ScopePolicy {
+        ScopePolicy::new()
+            .require_for_method("tools/call", ["mcp:call"])
+            .require_for_tool("write_file", ["files:write", "mcp:call"])
+    }
+
+    #[test]
+    fn combines_method_and_tool_scopes() {
+        let call = json!({
+            "jsonrpc": "2.0", "id": 1, "method": "tools/call",
+            "params": { "name": "write_file" }
+        });
+        assert_eq!(policy().required_scopes(&call), vec!["mcp:call", "files:write"]);
+
+        let list = json!({ "jsonrpc": "2.0", "id": 2, "method": "tools/list" });
+        assert!(policy().required_scopes(&list).is_empty());
+    }
+
+    #[test]
+    fn batches_require_the_union() {
+        let batch = json!([
+            { "jsonrpc": "2.0", "id": 1, "method": "tools/list" },
+            { "jsonrpc": "2.0", "id": 2, "method": "tools/call", "params": { "name": "write_file" } },
+            { "jsonrpc": "2.0", "id": 1, "result": {} }
+        ]);
+        assert_eq!(policy().required_scopes(&batch), vec!["mcp:call", "files:write"]);
+    }
+}
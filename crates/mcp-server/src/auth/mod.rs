@@ -3,14 +3,21 @@
 //! This module provides OAuth 2.1 authentication support for MCP servers,
 //! including:
 //!
-//! - Authorization server endpoints (authorize, token, register, revoke)
+//! - Authorization server endpoints (authorize, token, register, revoke,
+//!   introspect)
 //! - Metadata endpoints (RFC 8414, RFC 9728)
 //! - Bearer token authentication middleware
 //! - Client authentication middleware
+//! - API key authentication middleware for shared-secret deployments
+//! - Client registration persisted to a JSON file
+//! - Refresh token rotation with reuse detection
+//! - Token verification against a remote introspection endpoint (RFC 7662)
+//! - JWT access token verification with keys from a JWKS URI
 //!
 //! ## Features
 //!
 //! - `axum`: Enable axum integration for OAuth routes and middleware.
+//! - `jwt`: Enable `JwtTokenVerifier`.
 //!
 //! ## Example
 //!
@@ -35,17 +42,39 @@
 //!     .merge(mcp_router);
 //! ```
 
+mod api_keys;
+mod caching_verifier;
 mod clients;
+mod file_client_store;
 #[cfg(feature = "axum")]
 mod handlers;
+#[cfg(feature = "axum")]
+mod introspection_verifier;
+#[cfg(feature = "jwt")]
+mod jwks_fetcher;
+#[cfg(feature = "jwt")]
+mod jwt_verifier;
 pub mod middleware;
 mod provider;
+mod refresh_tokens;
 #[cfg(feature = "axum")]
 mod router;
 
+pub use api_keys::{ApiKeyStore, ApiKeyStoreError, InMemoryApiKeyStore};
+pub use caching_verifier::{CacheConfig, CachingTokenVerifier};
 pub use clients::{ClientStoreError, InMemoryClientStore, OAuthRegisteredClientsStore};
+pub use file_client_store::FileClientStore;
 pub use provider::{AuthorizeResponse, OAuthProviderError, OAuthServerProvider, OAuthTokenVerifier};
+pub use refresh_tokens::{
+    InMemoryRefreshTokenStore, IssuedRefreshToken, RefreshTokenError, RefreshTokenGrant,
+};
 
+#[cfg(feature = "axum")]
+pub use introspection_verifier::IntrospectionTokenVerifier;
+#[cfg(feature = "jwt")]
+pub use jwks_fetcher::{HttpJwksFetcher, JwksFetcher};
+#[cfg(feature = "jwt")]
+pub use jwt_verifier::JwtTokenVerifier;
 #[cfg(feature = "axum")]
 pub use router::{
     create_oauth_metadata, create_oauth_metadata_router, create_oauth_router,
@@ -5,8 +5,8 @@
 use async_trait::async_trait;
 
 use mcp_core::auth::{
-    AuthInfo, AuthorizationParams, OAuthClientInformationFull, OAuthTokenRevocationRequest,
-    OAuthTokens,
+    AuthInfo, AuthorizationParams, OAuthClientInformationFull, OAuthTokenIntrospectionResponse,
+    OAuthTokenRevocationRequest, OAuthTokens,
 };
 
 use super::clients::OAuthRegisteredClientsStore;
@@ -45,6 +45,10 @@ pub enum OAuthProviderError {
     /// Server error.
     #[error("server error: {0}")]
     Server(String),
+
+    /// The provider does not implement the requested operation.
+    #[error("unsupported: {0}")]
+    Unsupported(String),
 }
 
 impl OAuthProviderError {
@@ -59,6 +63,7 @@ impl OAuthProviderError {
             Self::AccessDenied(_) => "access_denied",
             Self::InvalidToken(_) => "invalid_token",
             Self::Server(_) => "server_error",
+            Self::Unsupported(_) => "unsupported_operation",
         }
     }
 }
@@ -128,6 +133,26 @@ pub trait OAuthServerProvider: Send + Sync {
         resource: Option<&str>,
     ) -> Result<OAuthTokens, OAuthProviderError>;
 
+    /// Exchange a refresh token for new tokens, rotating the refresh token.
+    ///
+    /// Called instead of `exchange_refresh_token` unless rotation is turned
+    /// off in `OAuthRouterOptions`. Implementations should return a new
+    /// refresh token, mark `refresh_token` consumed, and when a consumed
+    /// token is presented again revoke every token of its grant and return
+    /// `InvalidGrant`. `InMemoryRefreshTokenStore` does this bookkeeping.
+    ///
+    /// Default implementation calls `exchange_refresh_token` (no rotation).
+    async fn rotate_refresh_token(
+        &self,
+        client: &OAuthClientInformationFull,
+        refresh_token: &str,
+        scopes: Option<&[String]>,
+        resource: Option<&str>,
+    ) -> Result<OAuthTokens, OAuthProviderError> {
+        self.exchange_refresh_token(client, refresh_token, scopes, resource)
+            .await
+    }
+
     /// Verify an access token and return information about it.
     async fn verify_access_token(&self, token: &str) -> Result<AuthInfo, OAuthProviderError>;
 
@@ -142,6 +167,24 @@ pub trait OAuthServerProvider: Send + Sync {
         Ok(())
     }
 
+    /// Describe a token for the introspection endpoint (RFC 7662).
+    ///
+    /// Return [`OAuthTokenIntrospectionResponse::inactive`] for unknown,
+    /// expired or revoked tokens. `client` is the authenticated caller,
+    /// typically a resource server.
+    ///
+    /// Default implementation returns `Unsupported`.
+    async fn introspect_token(
+        &self,
+        _client: &OAuthClientInformationFull,
+        _token: &str,
+        _token_type_hint: Option<&str>,
+    ) -> Result<OAuthTokenIntrospectionResponse, OAuthProviderError> {
+        Err(OAuthProviderError::Unsupported(
+            "token introspection is not supported".to_string(),
+        ))
+    }
+
     /// Whether to skip local PKCE validation.
     ///
     /// If true, the server will not perform PKCE validation locally and will
@@ -0,0 +1,272 @@
+//! Refresh token rotation bookkeeping.
+//!
+//! OAuth 2.1 requires refresh tokens issued to public clients to be
+//! sender-constrained or rotated. With rotation, every refresh returns a new
+//! refresh token and consumes the old one; the tokens descending from one
+//! authorization grant form a *family*. Presenting a consumed token again
+//! means it leaked, so the whole family is revoked.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use super::provider::OAuthProviderError;
+
+/// What a refresh token grants, shared by every token of its family.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RefreshTokenGrant {
+    /// Client the grant was issued to.
+    pub client_id: String,
+    /// Scopes granted.
+    pub scopes: Vec<String>,
+    /// Resource indicator (RFC 8707) the grant is bound to.
+    pub resource: Option<String>,
+}
+
+/// A usable refresh token with the grant it carries.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IssuedRefreshToken {
+    /// The refresh token to return to the client.
+    pub refresh_token: String,
+    /// Identifier of the token's family.
+    pub family_id: String,
+    /// The grant of the family.
+    pub grant: RefreshTokenGrant,
+}
+
+/// Why a refresh token was rejected.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum RefreshTokenError {
+    /// The token was never issued.
+    #[error("unknown refresh token")]
+    Unknown,
+
+    /// The token was issued to another client.
+    #[error("refresh token was issued to another client")]
+    WrongClient,
+
+    /// The token's family has been revoked.
+    #[error("refresh token has been revoked")]
+    Revoked,
+
+    /// A consumed token was presented again; its family is now revoked.
+    #[error("refresh token reuse detected, grant revoked")]
+    Reused {
+        /// The revoked family.
+        family_id: String,
+    },
+}
+
+impl From<RefreshTokenError> for OAuthProviderError {
+    fn from(error: RefreshTokenError) -> Self {
+        Self::InvalidGrant(error.to_string())
+    }
+}
+
+struct TokenEntry {
+    family_id: String,
+    consumed: bool,
+}
+
+struct Family {
+    grant: RefreshTokenGrant,
+    revoked: bool,
+}
+
+#[derive(Default)]
+struct State {
+    tokens: HashMap<String, TokenEntry>,
+    families: HashMap<String, Family>,
+}
+
+/// In-memory refresh token families for development/testing.
+///
+/// A provider calls [`issue`](Self::issue) when exchanging an authorization
+/// code, [`rotate`](Self::rotate) from
+/// [`OAuthServerProvider::rotate_refresh_token`](super::OAuthServerProvider::rotate_refresh_token)
+/// and [`check`](Self::check) from `exchange_refresh_token`. Every operation
+/// holds a single lock, so of several concurrent refreshes with the same
+/// token exactly one succeeds; the others count as reuse.
+#[derive(Default)]
+pub struct InMemoryRefreshTokenStore {
+    state: Mutex<State>,
+}
+
+impl InMemoryRefreshTokenStore {
+    /// Create an empty store.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Start a new family for `grant` and return its first token.
+    pub fn issue(&self, grant: RefreshTokenGrant) -> IssuedRefreshToken {
+        let mut state = self.state.lock().unwrap();
+        let family_id = uuid::Uuid::new_v4().to_string();
+        state.families.insert(
+            family_id.clone(),
+            Family {
+                grant: grant.clone(),
+                revoked: false,
+            },
+        );
+        let refresh_token = Self::add_token(&mut state, &family_id);
+        IssuedRefreshToken {
+            refresh_token,
+            family_id,
+            grant,
+        }
+    }
+
+    /// Consume `refresh_token` and issue its successor in the same family.
+    ///
+    /// Presenting a consumed token revokes the family and returns
+    /// [`RefreshTokenError::Reused`].
+    pub fn rotate(
+        &self,
+        client_id: &str,
+        refresh_token: &str,
+    ) -> Result<IssuedRefreshToken, RefreshTokenError> {
+        let mut state = self.state.lock().unwrap();
+        let (family_id, grant) = Self::validate(&mut state, client_id, refresh_token)?;
+        if let Some(entry) = state.tokens.get_mut(refresh_token) {
+            entry.consumed = true;
+        }
+
+        let refresh_token = Self::add_token(&mut state, &family_id);
+        Ok(IssuedRefreshToken {
+            refresh_token,
+            family_id,
+            grant,
+        })
+    }
+
+    /// Check `refresh_token` without rotating it.
+    ///
+    /// Consumed tokens are rejected as reuse, as in [`rotate`](Self::rotate).
+    pub fn check(
+        &self,
+        client_id: &str,
+        refresh_token: &str,
+    ) -> Result<IssuedRefreshToken, RefreshTokenError> {
+        let mut state = self.state.lock().unwrap();
+        let (family_id, grant) = Self::validate(&mut state, client_id, refresh_token)?;
+        Ok(IssuedRefreshToken {
+            refresh_token: refresh_token.to_string(),
+            family_id,
+            grant,
+        })
+    }
+
+    /// Revoke the family `refresh_token` belongs to, returning its id.
+    pub fn revoke(&self, refresh_token: &str) -> Option<String> {
+        let mut state = self.state.lock().unwrap();
+        let family_id = state.tokens.get(refresh_token)?.family_id.clone();
+        if let Some(family) = state.families.get_mut(&family_id) {
+            family.revoked = true;
+        }
+        Some(family_id)
+    }
+
+    /// Whether the family `family_id` has been revoked.
+    pub fn is_revoked(&self, family_id: &str) -> bool {
+        let state = self.state.lock().unwrap();
+        state.families.get(family_id).is_none_or(|family| family.revoked)
+    }
+
+    /// Look up a live token. A consumed token revokes its family.
+    fn validate(
+        state: &mut State,
+        client_id: &str,
+        refresh_token: &str,
+    ) -> Result<(String, RefreshTokenGrant), RefreshTokenError> {
+        let entry = state.tokens.get(refresh_token).ok_or(RefreshTokenError::Unknown)?;
+        let family_id = entry.family_id.clone();
+        let consumed = entry.consumed;
+        let family = state
+            .families
+            .get_mut(&family_id)
+            .ok_or(RefreshTokenError::Unknown)?;
+        if family.revoked {
+            return Err(RefreshTokenError::Revoked);
+        }
+        if family.grant.client_id != client_id {
+            return Err(RefreshTokenError::WrongClient);
+        }
+        if consumed {
+            family.revoked = true;
+            return Err(RefreshTokenError::Reused { family_id });
+        }
+        Ok((family_id, family.grant.clone()))
+    }
+
+    fn add_token(state: &mut State, family_id: &str) -> String {
+        let token = uuid::Uuid::new_v4().simple().to_string();
+        state.tokens.insert(
+            token.clone(),
+            TokenEntry {
+                family_id: family_id.to_string(),
+                consumed: false,
+            },
+        );
+        token
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grant() -> RefreshTokenGrant {
+        RefreshTokenGrant {
+            client_id: "app".to_string(),
+            scopes: vec!["mcp".to_string()],
+            resource: None,
+        }
+    }
+
+    #[test]
+    fn rotation_consumes_the_old_token() {
+        let store = InMemoryRefreshTokenStore::new();
+        let first = store.issue(grant());
+
+        let second = store.rotate("app", &first.refresh_token).unwrap();
+        assert_ne!(second.refresh_token, first.refresh_token);
+        assert_eq!(second.family_id, first.family_id);
+        assert_eq!(second.grant, grant());
+
+        let third = store.rotate("app", &second.refresh_token).unwrap();
+        assert!(!store.is_revoked(&third.family_id));
+    }
+
+    #[test]
+    fn reuse_revokes_the_family() {
+        let store = InMemoryRefreshTokenStore::new();
+        let first = store.issue(grant());
+        let second = store.rotate("app", &first.refresh_token).unwrap();
+
+        assert_eq!(
+            store.rotate("app", &first.refresh_token),
+            Err(RefreshTokenError::Reused {
+                family_id: first.family_id.clone()
+            })
+        );
+        assert!(store.is_revoked(&first.family_id));
+        assert_eq!(
+            store.rotate("app", &second.refresh_token),
+            Err(RefreshTokenError::Revoked)
+        );
+    }
+
+    #[test]
+    fn rejects_unknown_tokens_and_other_clients() {
+        let store = InMemoryRefreshTokenStore::new();
+        let first = store.issue(grant());
+
+        assert_eq!(store.rotate("app", "nope"), Err(RefreshTokenError::Unknown));
+        assert_eq!(
+            store.rotate("other", &first.refresh_token),
+            Err(RefreshTokenError::WrongClient)
+        );
+        // A rejected attempt does not consume the token
+        assert_eq!(store.check("app", &first.refresh_token).unwrap(), first);
+    }
+}
@@ -6,14 +6,20 @@
 
 use std::sync::Arc;
 
+use async_trait::async_trait;
 use axum::routing::{get, post};
 use axum::Router;
 
-use mcp_core::auth::{OAuthMetadata, OAuthProtectedResourceMetadata};
+use mcp_core::auth::{
+    OAuthClientInformationFull, OAuthClientMetadata, OAuthMetadata, OAuthProtectedResourceMetadata,
+};
 
+use super::clients::{ClientStoreError, OAuthRegisteredClientsStore};
 use super::handlers::{
-    authorize_handler, metadata_handler, register_handler, revoke_handler, token_handler,
+    authorize_handler, introspect_handler, metadata_handler, register_handler, revoke_handler,
+    token_handler,
 };
+use super::middleware::ClientAuthLayer;
 use super::provider::OAuthServerProvider;
 
 /// Options for the OAuth router.
@@ -39,6 +45,10 @@ pub struct OAuthRouterOptions {
     /// The URL of the protected resource server.
     /// If not provided, falls back to base_url then issuer_url.
     pub resource_server_url: Option<String>,
+
+    /// Whether the refresh token grant rotates refresh tokens through
+    /// `OAuthServerProvider::rotate_refresh_token`. Defaults to `true`.
+    pub rotate_refresh_tokens: bool,
 }
 
 impl OAuthRouterOptions {
@@ -51,6 +61,7 @@ impl OAuthRouterOptions {
             scopes_supported: None,
             resource_name: None,
             resource_server_url: None,
+            rotate_refresh_tokens: true,
         }
     }
 
@@ -83,6 +94,12 @@ impl OAuthRouterOptions {
         self.resource_server_url = Some(url.into());
         self
     }
+
+    /// Enable or disable refresh token rotation.
+    pub fn with_refresh_token_rotation(mut self, enabled: bool) -> Self {
+        self.rotate_refresh_tokens = enabled;
+        self
+    }
 }
 
 /// State for the OAuth router.
@@ -93,6 +110,8 @@ pub struct OAuthRouterState<P: OAuthServerProvider> {
     pub metadata: OAuthMetadata,
     /// Protected resource metadata.
     pub resource_metadata: OAuthProtectedResourceMetadata,
+    /// Whether refresh tokens are rotated.
+    pub rotate_refresh_tokens: bool,
 }
 
 impl<P: OAuthServerProvider> Clone for OAuthRouterState<P> {
@@ -101,6 +120,7 @@ impl<P: OAuthServerProvider> Clone for OAuthRouterState<P> {
             provider: Arc::clone(&self.provider),
             metadata: self.metadata.clone(),
             resource_metadata: self.resource_metadata.clone(),
+            rotate_refresh_tokens: self.rotate_refresh_tokens,
         }
     }
 }
@@ -137,7 +157,7 @@ pub fn create_oauth_metadata<P: OAuthServerProvider>(
         service_documentation: options.service_documentation_url.clone(),
         revocation_endpoint: Some(format!("{}/revoke", base)),
         revocation_endpoint_auth_methods_supported: Some(vec!["client_secret_post".to_string()]),
-        introspection_endpoint: None,
+        introspection_endpoint: Some(format!("{}/introspect", base)),
         code_challenge_methods_supported: Some(vec!["S256".to_string()]),
         client_id_metadata_document_supported: None,
     }
@@ -171,6 +191,8 @@ pub fn create_protected_resource_metadata(
 /// - `POST /token` - Token endpoint
 /// - `POST /register` - Dynamic client registration (RFC 7591)
 /// - `POST /revoke` - Token revocation (RFC 7009)
+/// - `POST /introspect` - Token introspection (RFC 7662), for clients
+///   authenticated with HTTP Basic credentials
 /// - `GET /.well-known/oauth-authorization-server` - Server metadata (RFC 8414)
 /// - `GET /.well-known/oauth-protected-resource` - Resource metadata (RFC 9728)
 pub fn create_oauth_router<P: OAuthServerProvider + 'static>(
@@ -180,10 +202,13 @@ pub fn create_oauth_router<P: OAuthServerProvider + 'static>(
     let metadata = create_oauth_metadata(provider.as_ref(), &options);
     let resource_metadata = create_protected_resource_metadata(&options);
 
+    let client_auth = ClientAuthLayer::new(Arc::new(ProviderClientsStore(Arc::clone(&provider))));
+
     let state = OAuthRouterState {
         provider,
         metadata: metadata.clone(),
         resource_metadata: resource_metadata.clone(),
+        rotate_refresh_tokens: options.rotate_refresh_tokens,
     };
 
     Router::new()
@@ -191,6 +216,10 @@ pub fn create_oauth_router<P: OAuthServerProvider + 'static>(
         .route("/token", post(token_handler::<P>))
         .route("/register", post(register_handler::<P>))
         .route("/revoke", post(revoke_handler::<P>))
+        .route(
+            "/introspect",
+            post(introspect_handler::<P>).route_layer(client_auth),
+        )
         .route(
             "/.well-known/oauth-authorization-server",
             get(metadata_handler::<P>),
@@ -217,6 +246,7 @@ pub fn create_oauth_metadata_router<P: OAuthServerProvider + 'static>(
         provider,
         metadata: metadata.clone(),
         resource_metadata: resource_metadata.clone(),
+        rotate_refresh_tokens: options.rotate_refresh_tokens,
     };
 
     Router::new()
@@ -231,6 +261,27 @@ pub fn create_oauth_metadata_router<P: OAuthServerProvider + 'static>(
         .with_state(Arc::new(state))
 }
 
+/// Exposes a provider's client store to `ClientAuthLayer`, which needs an
+/// owned store.
+struct ProviderClientsStore<P>(Arc<P>);
+
+impl<P> Clone for ProviderClientsStore<P> {
+    fn clone(&self) -> Self {
+        Self(Arc::clone(&self.0))
+    }
+}
+
+#[async_trait]
+impl<P: OAuthServerProvider> OAuthRegisteredClientsStore for ProviderClientsStore<P> {
+    async fn get_client(&self, client_id: &str) -> Result<Option<OAuthClientInformationFull>, ClientStoreError> {
+        self.0.clients_store().get_client(client_id).await
+    }
+
+    async fn register_client(&self, metadata: OAuthClientMetadata) -> Result<OAuthClientInformationFull, ClientStoreError> {
+        self.0.clients_store().register_client(metadata).await
+    }
+}
+
 /// Handler for protected resource metadata.
 async fn resource_metadata_handler<P: OAuthServerProvider + 'static>(
     axum::extract::State(state): axum::extract::State<Arc<OAuthRouterState<P>>>,
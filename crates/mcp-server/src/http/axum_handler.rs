@@ -7,29 +7,40 @@
 
 use std::collections::HashMap;
 use std::convert::Infallible;
+use std::future::Future;
 use std::sync::Arc;
 use std::time::Duration;
 
 use axum::body::Body;
-use axum::extract::State;
-use axum::http::{header, HeaderMap, Method, StatusCode};
+use axum::extract::{Extension, State};
+use axum::http::{header, HeaderMap, StatusCode};
 use axum::response::sse::{Event, KeepAlive, Sse};
 use axum::response::{IntoResponse, Response};
 use axum::routing::{delete, get, post};
 use axum::Router;
 use futures::stream::Stream;
-use tokio::sync::RwLock;
-use tower_http::cors::{Any, CorsLayer};
+use tokio::sync::{mpsc, RwLock};
 
-use mcp_core::http::SseEvent;
+use mcp_core::auth::AuthInfo;
+use mcp_core::http::{SessionId, SseEvent};
+use mcp_core::protocol::{CancellationToken, PeerNotifyFn};
 use mcp_core::stdio::{deserialize_message, serialize_message, JsonRpcMessage};
+use mcp_core::types::{ErrorCode, ErrorObject, MessageId, NotificationMessage, ResultMessage};
 
 use super::broadcast::async_broadcast::SseBroadcaster;
-use super::broadcast::EventBufferConfig;
+use super::broadcast::{event_sequence, EventBufferConfig};
+use super::cors_policy::CorsPolicy;
 use super::dns_protection::{DnsProtectionConfig, DnsProtectionLayer};
 use super::error::HttpServerError;
+use super::in_memory_session_store::InMemorySessionStore;
+use super::origin_validation::{OriginValidationConfig, OriginValidationLayer};
+use super::rate_limit::RateLimitConfig;
+use super::rate_limit_layer::RateLimitLayer;
 use super::session_manager::{SessionConfig, SessionManager, SessionState};
-use crate::server::McpServer;
+use super::session_mode::SessionMode;
+use super::session_store::SessionStore;
+use crate::auth::middleware::ApiKeyLayer;
+use crate::server::{McpServer, ServerError};
 
 /// Configuration for the axum HTTP handler.
 #[derive(Debug, Clone)]
@@ -46,8 +57,10 @@ pub struct AxumHandlerConfig {
     pub keep_alive_interval: Duration,
     /// Broadcast channel capacity per session.
     pub broadcast_capacity: usize,
-    /// Enable CORS.
-    pub enable_cors: bool,
+    /// CORS policy (origins, methods, headers, credentials, max age).
+    /// The default grants no cross-origin access; with an empty origin
+    /// list, CORS follows `allowed_origins`.
+    pub cors: CorsPolicy,
     /// Enable DNS rebinding protection.
     /// When enabled, the server validates the Host header against allowed hostnames.
     pub enable_dns_rebinding_protection: bool,
@@ -55,6 +68,18 @@ pub struct AxumHandlerConfig {
     /// If `enable_dns_rebinding_protection` is true and this is None,
     /// localhost-only protection is used by default.
     pub dns_protection_config: Option<DnsProtectionConfig>,
+    /// Origins allowed to reach the server from a browser
+    /// (e.g. `https://app.example.com`, `https://*.example.com`).
+    /// When non-empty, requests with any other `Origin` header are rejected
+    /// with 403, and CORS grants these origins unless `cors` lists its own.
+    /// Empty accepts any `Origin`.
+    pub allowed_origins: Vec<String>,
+    /// Whether sessions are issued and required. `SessionMode::Stateless`
+    /// suits deployments without session affinity.
+    pub session_mode: SessionMode,
+    /// Per-client rate limit, keyed by session, token subject or peer
+    /// address. Requests over the limit get 429 with `Retry-After`.
+    pub rate_limit: Option<RateLimitConfig>,
 }
 
 impl Default for AxumHandlerConfig {
@@ -66,28 +91,119 @@ impl Default for AxumHandlerConfig {
             endpoint_path: "/mcp".to_string(),
             keep_alive_interval: Duration::from_secs(30),
             broadcast_capacity: 100,
-            enable_cors: true,
+            cors: CorsPolicy::default(),
             enable_dns_rebinding_protection: false,
             dns_protection_config: None,
+            allowed_origins: Vec::new(),
+            session_mode: SessionMode::Stateful,
+            rate_limit: None,
         }
     }
 }
 
+type BroadcasterMap = Arc<RwLock<HashMap<String, Arc<SseBroadcaster>>>>;
+
 /// Shared state for the axum handler.
 pub struct AxumHandlerState {
     server: Arc<McpServer>,
-    session_manager: SessionManager,
-    broadcasters: RwLock<HashMap<String, Arc<SseBroadcaster>>>,
+    session_manager: SessionManager<Arc<dyn SessionStore>>,
+    broadcasters: BroadcasterMap,
     config: AxumHandlerConfig,
 }
 
+/// Extra shutdown signal given to `create_router_with_shutdown`.
+#[derive(Clone)]
+struct ShutdownSignal(CancellationToken);
+
 impl AxumHandlerState {
     /// Create a new handler state.
+    ///
+    /// Server-initiated notifications (e.g. task status updates) and requests
+    /// (e.g. sampling) are routed to the SSE stream of the session they belong to;
+    /// notifications for no particular session go to every open session.
     pub fn new(server: Arc<McpServer>, config: AxumHandlerConfig) -> Self {
+        Self::with_session_store(server, config, Arc::new(InMemorySessionStore::new()))
+    }
+
+    /// Create a new handler state keeping sessions in `store`, e.g. a
+    /// `RedisSessionStore` shared with other server instances.
+    pub fn with_session_store(
+        server: Arc<McpServer>,
+        config: AxumHandlerConfig,
+        store: Arc<dyn SessionStore>,
+    ) -> Self {
+        let broadcasters: BroadcasterMap = Arc::new(RwLock::new(HashMap::new()));
+
+        let request_broadcasters = Arc::clone(&broadcasters);
+        let request_config = config.clone();
+        server
+            .server()
+            .set_request_sender(Some(Arc::new(move |session_id, request| {
+                let Some(session_id) = session_id else {
+                    return;
+                };
+                let Ok(runtime) = tokio::runtime::Handle::try_current() else {
+                    return;
+                };
+                let broadcasters = Arc::clone(&request_broadcasters);
+                let config = request_config.clone();
+                runtime.spawn(async move {
+                    let broadcaster = broadcaster_for(&broadcasters, &config, &session_id).await;
+                    let _ = broadcaster.send_message(JsonRpcMessage::Request(request));
+                });
+            })));
+
+        let sender_broadcasters = Arc::clone(&broadcasters);
+        let sender_config = config.clone();
+        server
+            .server()
+            .set_notification_sender(Some(Arc::new(move |session_id, notification| {
+                let Ok(runtime) = tokio::runtime::Handle::try_current() else {
+                    return;
+                };
+                let broadcasters = Arc::clone(&sender_broadcasters);
+                let config = sender_config.clone();
+                runtime.spawn(async move {
+                    let message = JsonRpcMessage::Notification(notification);
+                    match session_id {
+                        Some(session_id) => {
+                            let broadcaster =
+                                broadcaster_for(&broadcasters, &config, &session_id).await;
+                            let _ = broadcaster.send_message(message);
+                        }
+                        None => {
+                            for broadcaster in broadcasters.read().await.values() {
+                                let _ = broadcaster.send_message(message.clone());
+                            }
+                        }
+                    }
+                });
+            })));
+
+        // Sessions evicted or expired by the manager lose their SSE streams,
+        // replay buffer and resource subscriptions, as with DELETE.
+        let session_manager = SessionManager::with_store(config.session_config.clone(), store);
+        let close_broadcasters = Arc::clone(&broadcasters);
+        let close_server = Arc::clone(&server);
+        session_manager.set_close_listener(Some(Arc::new(move |session: &SessionState| {
+            let session_id = session.session_id.to_string();
+            close_server.remove_session_subscriptions(&session_id);
+            if let Some(metrics) = close_server.server().metrics() {
+                metrics.on_session_expired();
+            }
+            let Ok(runtime) = tokio::runtime::Handle::try_current() else {
+                return;
+            };
+            let broadcasters = Arc::clone(&close_broadcasters);
+            runtime.spawn(async move {
+                close_broadcaster(&broadcasters, &session_id).await;
+            });
+        })));
+
         Self {
             server,
-            session_manager: SessionManager::new(config.session_config.clone()),
-            broadcasters: RwLock::new(HashMap::new()),
+            session_manager,
+            broadcasters,
             config,
         }
     }
@@ -98,7 +214,7 @@ impl AxumHandlerState {
     }
 
     /// Get the session manager.
-    pub fn session_manager(&self) -> &SessionManager {
+    pub fn session_manager(&self) -> &SessionManager<Arc<dyn SessionStore>> {
         &self.session_manager
     }
 
@@ -107,34 +223,12 @@ impl AxumHandlerState {
         &self,
         session_id: &str,
     ) -> Arc<SseBroadcaster> {
-        // Try to get existing broadcaster
-        {
-            let broadcasters = self.broadcasters.read().await;
-            if let Some(broadcaster) = broadcasters.get(session_id) {
-                return Arc::clone(broadcaster);
-            }
-        }
-
-        // Create new broadcaster
-        let mut broadcasters = self.broadcasters.write().await;
-        // Double-check after acquiring write lock
-        if let Some(broadcaster) = broadcasters.get(session_id) {
-            return Arc::clone(broadcaster);
-        }
-
-        let broadcaster = Arc::new(SseBroadcaster::with_buffer_config(
-            session_id.to_string(),
-            self.config.broadcast_capacity,
-            self.config.event_buffer_config.clone(),
-        ));
-        broadcasters.insert(session_id.to_string(), Arc::clone(&broadcaster));
-        broadcaster
+        broadcaster_for(&self.broadcasters, &self.config, session_id).await
     }
 
-    /// Remove a broadcaster for a session.
+    /// Remove a broadcaster for a session, ending its open SSE streams.
     pub async fn remove_broadcaster(&self, session_id: &str) {
-        let mut broadcasters = self.broadcasters.write().await;
-        broadcasters.remove(session_id);
+        close_broadcaster(&self.broadcasters, session_id).await;
     }
 
     /// Broadcast a message to a session.
@@ -162,13 +256,72 @@ impl AxumHandlerState {
     }
 }
 
+/// Get or create the broadcaster for a session in the shared map.
+async fn broadcaster_for(
+    broadcasters: &BroadcasterMap,
+    config: &AxumHandlerConfig,
+    session_id: &str,
+) -> Arc<SseBroadcaster> {
+    // Try to get existing broadcaster
+    {
+        let broadcasters = broadcasters.read().await;
+        if let Some(broadcaster) = broadcasters.get(session_id) {
+            return Arc::clone(broadcaster);
+        }
+    }
+
+    // Create new broadcaster
+    let mut broadcasters = broadcasters.write().await;
+    // Double-check after acquiring write lock
+    if let Some(broadcaster) = broadcasters.get(session_id) {
+        return Arc::clone(broadcaster);
+    }
+
+    let broadcaster = Arc::new(SseBroadcaster::with_buffer_config(
+        session_id.to_string(),
+        config.broadcast_capacity,
+        config.event_buffer_config.clone(),
+    ));
+    broadcasters.insert(session_id.to_string(), Arc::clone(&broadcaster));
+    broadcaster
+}
+
+/// Remove a session's broadcaster from the shared map and close it.
+async fn close_broadcaster(broadcasters: &BroadcasterMap, session_id: &str) {
+    let removed = broadcasters.write().await.remove(session_id);
+    if let Some(broadcaster) = removed {
+        broadcaster.close();
+    }
+}
+
 /// Create an axum router for the MCP HTTP server.
+///
+/// Open SSE streams end with a final comment once `McpServer::shutdown`
+/// has drained the server, so `axum::serve(..).with_graceful_shutdown(..)`
+/// is not held up by them.
+///
+/// With `ServerOptions::metrics` set, `GET /metrics` serves the recorder's
+/// Prometheus text. With `ServerOptions::api_key_store` set, every request
+/// needs a valid API key (see `ApiKeyLayer`).
 pub fn create_router(state: Arc<AxumHandlerState>) -> Router {
     let mut router = Router::new()
         .route(&state.config.endpoint_path, post(handle_post))
         .route(&state.config.endpoint_path, get(handle_get))
-        .route(&state.config.endpoint_path, delete(handle_delete))
-        .with_state(state.clone());
+        .route(&state.config.endpoint_path, delete(handle_delete));
+    if state.server().server().metrics().is_some() {
+        router = router.route("/metrics", get(handle_metrics));
+    }
+    let mut router = router.with_state(state.clone());
+
+    // Apply rate limiting if configured
+    if let Some(config) = &state.config.rate_limit {
+        router = router.layer(RateLimitLayer::new(config.clone()));
+    }
+
+    // Check API keys outside the rate limiter, which keys on the client ID
+    if let Some(store) = state.server().server().api_key_store() {
+        router = router.layer(ApiKeyLayer::new(store));
+    }
 
     // Apply DNS rebinding protection if enabled
     if state.config.enable_dns_rebinding_protection {
@@ -180,30 +333,69 @@ pub fn create_router(state: Arc<AxumHandlerState>) -> Router {
         router = router.layer(DnsProtectionLayer::new(dns_config));
     }
 
-    if state.config.enable_cors {
-        router = router.layer(
-            CorsLayer::new()
-                .allow_origin(Any)
-                .allow_methods([Method::GET, Method::POST, Method::DELETE])
-                .allow_headers([
-                    header::CONTENT_TYPE,
-                    header::ACCEPT,
-                    header::HeaderName::from_static("mcp-session-id"),
-                    header::HeaderName::from_static("last-event-id"),
-                ])
-                .expose_headers([header::HeaderName::from_static("mcp-session-id")]),
-        );
+    // Apply Origin validation if an allowlist is configured
+    if !state.config.allowed_origins.is_empty() {
+        let config = OriginValidationConfig::new(state.config.allowed_origins.iter().cloned());
+        router = router.layer(OriginValidationLayer::new(config));
+    }
+
+    // CORS is always the outermost layer so it answers preflights itself
+    let mut cors = state.config.cors.clone();
+    if cors.allow_origins.is_empty() {
+        cors.allow_origins = state.config.allowed_origins.clone();
     }
+    router = router.layer(cors.layer());
 
     router
 }
 
+/// Create an axum router whose SSE streams also end when `shutdown` is
+/// cancelled, so one token can stop both the streams and `axum::serve`:
+///
+/// ```ignore
+/// let shutdown = CancellationToken::default();
+/// let router = create_router_with_shutdown(state, shutdown.clone());
+/// let signal = shutdown.clone();
+/// tokio::spawn(async move {
+///     tokio::signal::ctrl_c().await.ok();
+///     server.shutdown(Duration::from_secs(10)).await;
+///     shutdown.cancel();
+/// });
+/// axum::serve(listener, router)
+///     .with_graceful_shutdown(async move { signal.cancelled().await })
+///     .await?;
+/// ```
+pub fn create_router_with_shutdown(
+    state: Arc<AxumHandlerState>,
+    shutdown: CancellationToken,
+) -> Router {
+    create_router(state).layer(Extension(ShutdownSignal(shutdown)))
+}
+
+/// Handle `GET /metrics` with the installed recorder's text.
+async fn handle_metrics(State(state): State<Arc<AxumHandlerState>>) -> Response {
+    let Some(text) = state.server().server().metrics().and_then(|metrics| metrics.render()) else {
+        return StatusCode::NOT_FOUND.into_response();
+    };
+    Response::builder()
+        .status(StatusCode::OK)
+        .header(header::CONTENT_TYPE, "text/plain; version=0.0.4")
+        .body(Body::from(text))
+        .unwrap()
+}
+
 /// Handle POST requests (send JSON-RPC messages).
+///
+/// A token verified by `BearerAuthLayer` is passed on to request handlers
+/// as `RequestContext::auth_info`.
 async fn handle_post(
     State(state): State<Arc<AxumHandlerState>>,
     headers: HeaderMap,
+    auth_info: Option<Extension<AuthInfo>>,
     body: String,
 ) -> Response {
+    let auth_info = auth_info.map(|Extension(auth_info)| auth_info);
+
     // Validate content type
     if let Some(content_type) = headers.get(header::CONTENT_TYPE) {
         if let Ok(ct) = content_type.to_str() {
@@ -227,12 +419,21 @@ async fn handle_post(
         }
     };
 
+    let accepts_sse = headers
+        .get(header::ACCEPT)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|accept| accept.contains("text/event-stream"));
+
+    if state.config.session_mode == SessionMode::Stateless {
+        return handle_stateless_message(&state, message, accepts_sse, auth_info).await;
+    }
+
     // Get or create session
     let session_id_header = headers
         .get("mcp-session-id")
         .and_then(|v| v.to_str().ok());
 
-    let (session, is_new) = match get_or_create_session(&state, session_id_header) {
+    let (session, is_new) = match get_or_create_session(&state, session_id_header).await {
         Ok(result) => result,
         Err(e) => {
             return error_response(StatusCode::from_u16(e.status_code()).unwrap(), &e.to_string());
@@ -244,44 +445,80 @@ async fn handle_post(
     // Handle the message
     match message {
         JsonRpcMessage::Request(request) => {
-            let result = state
+            let server = Arc::clone(&state.server);
+            let session_data = Arc::clone(&session.session_data);
+            let new_session_id = is_new.then(|| session_id.clone());
+            respond_to_request(accepts_sse, request.id.clone(), new_session_id, |notifier| async move {
+                server
+                    .server()
+                    .handle_request_with_notifier(
+                        "http",
+                        request,
+                        Some(session_id),
+                        Some(session_data),
+                        notifier,
+                        auth_info,
+                    )
+                    .await
+            })
+            .await
+        }
+        JsonRpcMessage::Notification(notification) => {
+            let _ = state
                 .server
                 .server()
-                .handle_request(request, Some(session_id.clone()))
+                .handle_notification(notification, Some(session_id.clone()))
                 .await;
 
-            match result {
-                Ok(response) => {
-                    let response_msg = JsonRpcMessage::Result(response);
-                    match serialize_message(&response_msg) {
-                        Ok(body) => {
-                            let mut response = Response::builder()
-                                .status(StatusCode::OK)
-                                .header(header::CONTENT_TYPE, "application/json");
-
-                            if is_new {
-                                response = response.header("mcp-session-id", &session_id);
-                            }
-
-                            response.body(Body::from(body)).unwrap()
-                        }
-                        Err(e) => error_response(
-                            StatusCode::INTERNAL_SERVER_ERROR,
-                            &format!("Serialization error: {}", e),
-                        ),
-                    }
-                }
-                Err(e) => error_response(
-                    StatusCode::INTERNAL_SERVER_ERROR,
-                    &format!("Server error: {}", e),
-                ),
+            Response::builder()
+                .status(StatusCode::ACCEPTED)
+                .body(Body::empty())
+                .unwrap()
+        }
+        JsonRpcMessage::Result(response) => {
+            // Only responses to server-initiated requests are expected
+            if state
+                .server
+                .server()
+                .handle_response(response, Some(session_id.clone()))
+            {
+                Response::builder()
+                    .status(StatusCode::ACCEPTED)
+                    .body(Body::empty())
+                    .unwrap()
+            } else {
+                error_response(
+                    StatusCode::BAD_REQUEST,
+                    "Unexpected result message from client",
+                )
             }
         }
+    }
+}
+
+/// Handle a message in stateless mode, without a session.
+async fn handle_stateless_message(
+    state: &AxumHandlerState,
+    message: JsonRpcMessage,
+    accepts_sse: bool,
+    auth_info: Option<AuthInfo>,
+) -> Response {
+    match message {
+        JsonRpcMessage::Request(request) => {
+            let server = Arc::clone(&state.server);
+            respond_to_request(accepts_sse, request.id.clone(), None, |notifier| async move {
+                server
+                    .server()
+                    .handle_stateless_request("http", request, notifier, auth_info)
+                    .await
+            })
+            .await
+        }
         JsonRpcMessage::Notification(notification) => {
             let _ = state
                 .server
                 .server()
-                .handle_notification(notification, Some(session_id.clone()))
+                .handle_notification(notification, None)
                 .await;
 
             Response::builder()
@@ -289,20 +526,142 @@ async fn handle_post(
                 .body(Body::empty())
                 .unwrap()
         }
-        JsonRpcMessage::Result(_) => {
-            error_response(
-                StatusCode::BAD_REQUEST,
-                "Unexpected result message from client",
-            )
+        // No server-initiated request can be waiting for this
+        JsonRpcMessage::Result(_) => error_response(
+            StatusCode::BAD_REQUEST,
+            "Unexpected result message from client",
+        ),
+    }
+}
+
+/// What the handler of a POSTed request produced, in order.
+enum PostEvent {
+    Notification(NotificationMessage),
+    Result(Result<ResultMessage, ServerError>),
+}
+
+/// Run a request and choose the shape of the POST response.
+///
+/// The response is a single JSON body unless the client accepts
+/// `text/event-stream` and the handler sends notifications (e.g. progress)
+/// before its result. Those are then streamed as SSE `message` events,
+/// ending with the result.
+async fn respond_to_request<F, Fut>(
+    accepts_sse: bool,
+    request_id: MessageId,
+    new_session_id: Option<String>,
+    handle: F,
+) -> Response
+where
+    F: FnOnce(Option<PeerNotifyFn>) -> Fut,
+    Fut: Future<Output = Result<ResultMessage, ServerError>> + Send + 'static,
+{
+    if !accepts_sse {
+        return json_result_response(handle(None).await, new_session_id);
+    }
+
+    let (tx, mut rx) = mpsc::unbounded_channel();
+    let notify_tx = tx.clone();
+    let notifier: PeerNotifyFn = Arc::new(move |method: String, params| {
+        let notification = NotificationMessage::new(method, Some(params));
+        let _ = notify_tx.send(PostEvent::Notification(notification));
+    });
+    let request = handle(Some(notifier));
+    tokio::spawn(async move {
+        let _ = tx.send(PostEvent::Result(request.await));
+    });
+
+    let first = match rx.recv().await {
+        Some(PostEvent::Notification(notification)) => notification,
+        Some(PostEvent::Result(result)) => return json_result_response(result, new_session_id),
+        None => {
+            return error_response(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "Request handler stopped without a result",
+            );
+        }
+    };
+
+    let stream = async_stream::stream! {
+        yield Ok::<_, Infallible>(message_event(&JsonRpcMessage::Notification(first)));
+        while let Some(event) = rx.recv().await {
+            match event {
+                PostEvent::Notification(notification) => {
+                    yield Ok(message_event(&JsonRpcMessage::Notification(notification)));
+                }
+                PostEvent::Result(result) => {
+                    let response = result.unwrap_or_else(|e| {
+                        let error = ErrorObject::new(ErrorCode::InternalError as i32, e.to_string(), None);
+                        ResultMessage::failure(request_id.clone(), error)
+                    });
+                    yield Ok(message_event(&JsonRpcMessage::Result(response)));
+                    break;
+                }
+            }
+        }
+    };
+
+    let mut response = Sse::new(stream).into_response();
+    if let Some(session_id) = new_session_id {
+        response
+            .headers_mut()
+            .insert("mcp-session-id", session_id.parse().unwrap());
+    }
+    response
+}
+
+/// Single JSON body answering a POSTed request.
+fn json_result_response(
+    result: Result<ResultMessage, ServerError>,
+    new_session_id: Option<String>,
+) -> Response {
+    let response = match result {
+        Ok(response) => response,
+        Err(e) => {
+            return error_response(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                &format!("Server error: {}", e),
+            );
+        }
+    };
+    match serialize_message(&JsonRpcMessage::Result(response)) {
+        Ok(body) => {
+            let mut response = Response::builder()
+                .status(StatusCode::OK)
+                .header(header::CONTENT_TYPE, "application/json");
+
+            if let Some(session_id) = new_session_id {
+                response = response.header("mcp-session-id", session_id);
+            }
+
+            response.body(Body::from(body)).unwrap()
         }
+        Err(e) => error_response(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            &format!("Serialization error: {}", e),
+        ),
     }
 }
 
+/// SSE `message` event carrying a JSON-RPC message.
+fn message_event(message: &JsonRpcMessage) -> Event {
+    let data = serde_json::to_string(message).unwrap_or_default();
+    Event::default().event("message").data(data)
+}
+
 /// Handle GET requests (establish SSE connection).
 async fn handle_get(
     State(state): State<Arc<AxumHandlerState>>,
     headers: HeaderMap,
+    signal: Option<Extension<ShutdownSignal>>,
 ) -> Response {
+    if state.config.session_mode == SessionMode::Stateless {
+        return error_response(
+            StatusCode::METHOD_NOT_ALLOWED,
+            "SSE streams are not available in stateless mode",
+        );
+    }
+
     // Validate accept header
     if let Some(accept) = headers.get(header::ACCEPT) {
         if let Ok(accept_str) = accept.to_str() {
@@ -320,7 +679,7 @@ async fn handle_get(
         .get("mcp-session-id")
         .and_then(|v| v.to_str().ok());
 
-    let (session, _is_new) = match get_or_create_session(&state, session_id_header) {
+    let (session, _is_new) = match get_or_create_session(&state, session_id_header).await {
         Ok(result) => result,
         Err(e) => {
             return error_response(StatusCode::from_u16(e.status_code()).unwrap(), &e.to_string());
@@ -338,12 +697,19 @@ async fn handle_get(
     // Get or create broadcaster
     let broadcaster = state.get_or_create_broadcaster(&session_id).await;
 
+    let server_shutdown = state.server().server().shutdown_token();
+    let shutdown = match signal {
+        Some(Extension(ShutdownSignal(signal))) => server_shutdown.race(&signal),
+        None => server_shutdown,
+    };
+
     // Create SSE stream
     let stream = create_sse_stream(
         session_id.clone(),
         broadcaster,
         last_event_id,
         state.endpoint_url(),
+        shutdown,
     );
 
     let sse = Sse::new(stream).keep_alive(
@@ -361,35 +727,69 @@ async fn handle_get(
 }
 
 /// Handle DELETE requests (close session).
+///
+/// Idempotent: a well-formed ID of a session that no longer exists returns
+/// 200 rather than an error, so clients can retry. Malformed IDs are 400.
 async fn handle_delete(
     State(state): State<Arc<AxumHandlerState>>,
     headers: HeaderMap,
 ) -> Response {
-    let session_id = match headers.get("mcp-session-id").and_then(|v| v.to_str().ok()) {
-        Some(id) => id,
+    if state.config.session_mode == SessionMode::Stateless {
+        return error_response(StatusCode::METHOD_NOT_ALLOWED, "Sessions are not enabled");
+    }
+
+    let session_id = match headers.get("mcp-session-id") {
+        Some(value) => match value.to_str() {
+            Ok(id) if SessionId::is_well_formed(id) => id,
+            _ => return error_response(StatusCode::BAD_REQUEST, "Invalid session ID"),
+        },
         None => {
             return error_response(StatusCode::BAD_REQUEST, "Missing session ID");
         }
     };
 
-    // Remove session and broadcaster
-    state.session_manager().remove_session(session_id);
+    // Remove session and broadcaster; an unknown session is already deleted
+    let removed = state
+        .session_manager()
+        .remove_session(session_id)
+        .await
+        .is_some();
     state.remove_broadcaster(session_id).await;
+    state.server().remove_session_subscriptions(session_id);
+    if let (true, Some(metrics)) = (removed, state.server().server().metrics()) {
+        metrics.on_session_expired();
+    }
 
+    let status = if removed {
+        StatusCode::NO_CONTENT
+    } else {
+        StatusCode::OK
+    };
     Response::builder()
-        .status(StatusCode::NO_CONTENT)
+        .status(status)
         .body(Body::empty())
         .unwrap()
 }
 
 /// Create an SSE event stream.
+///
+/// With a Last-Event-ID, buffered events after it are replayed before live
+/// delivery continues; live events already replayed are skipped. If the
+/// buffer no longer holds everything after it, the stream starts fresh with
+/// a `gap` comment instead. Once `shutdown` is cancelled the stream ends
+/// with a `shutdown` comment.
 fn create_sse_stream(
     session_id: String,
     broadcaster: Arc<SseBroadcaster>,
     last_event_id: Option<String>,
     endpoint_url: String,
+    shutdown: CancellationToken,
 ) -> impl Stream<Item = Result<Event, Infallible>> {
     async_stream::stream! {
+        // Subscribe before anything is yielded so events sent while the
+        // client reads the preamble are not lost.
+        let mut rx = broadcaster.subscribe();
+
         // Send session ready event
         yield Ok(Event::default()
             .event("session")
@@ -400,29 +800,66 @@ fn create_sse_stream(
             .event("endpoint")
             .data(&endpoint_url));
 
+        // ID of the last message event delivered on this stream
+        let mut last_id: Option<String> = None;
+
         // Replay missed events if Last-Event-ID was provided
-        if let Some(ref last_id) = last_event_id {
-            let replay_events = broadcaster.get_replay_events(last_id);
-            for buffered in replay_events {
-                if let Some(event) = sse_event_to_axum_event(&buffered.event) {
-                    yield Ok(event);
+        if let Some(requested) = last_event_id {
+            match broadcaster.replay_after(&requested) {
+                Ok(replay_events) => {
+                    last_id = Some(requested);
+                    for buffered in replay_events {
+                        last_id = Some(buffered.id.clone());
+                        if let Some(event) = sse_event_to_axum_event(&buffered.event) {
+                            yield Ok(event);
+                        }
+                    }
                 }
+                Err(_) => yield Ok(gap_event(&requested)),
             }
         }
 
-        // Subscribe to new events
-        let mut rx = broadcaster.subscribe();
-
         loop {
-            match rx.recv().await {
+            let received = tokio::select! {
+                received = rx.recv() => received,
+                _ = broadcaster.closed() => break,
+                _ = shutdown.cancelled() => break,
+            };
+            match received {
                 Ok(event) => {
+                    if let SseEvent::Message { id: Some(id), .. } = &event {
+                        let sequence = event_sequence(id);
+                        let delivered = last_id.as_deref().and_then(event_sequence);
+                        if matches!((sequence, delivered), (Some(s), Some(d)) if s <= d) {
+                            continue;
+                        }
+                        last_id = Some(id.clone());
+                    }
                     if let Some(axum_event) = sse_event_to_axum_event(&event) {
                         yield Ok(axum_event);
                     }
                 }
                 Err(tokio::sync::broadcast::error::RecvError::Lagged(n)) => {
-                    // Log that we missed some events
-                    eprintln!("SSE stream lagged, missed {} events", n);
+                    // Recover the skipped events from the replay buffer
+                    let recovered = last_id
+                        .as_deref()
+                        .and_then(|id| broadcaster.replay_after(id).ok());
+                    match recovered {
+                        Some(replay_events) => {
+                            for buffered in replay_events {
+                                last_id = Some(buffered.id.clone());
+                                if let Some(event) = sse_event_to_axum_event(&buffered.event) {
+                                    yield Ok(event);
+                                }
+                            }
+                        }
+                        None => {
+                            eprintln!("SSE stream lagged, missed {} events", n);
+                            if let Some(id) = &last_id {
+                                yield Ok(gap_event(id));
+                            }
+                        }
+                    }
                     continue;
                 }
                 Err(tokio::sync::broadcast::error::RecvError::Closed) => {
@@ -430,9 +867,21 @@ fn create_sse_stream(
                 }
             }
         }
+
+        if shutdown.is_cancelled() {
+            yield Ok(Event::default().comment("shutdown: server is shutting down"));
+        }
     }
 }
 
+/// Comment event telling the client that events after `last_event_id` were lost.
+fn gap_event(last_event_id: &str) -> Event {
+    Event::default().comment(format!(
+        "gap: events after {} are no longer buffered",
+        last_event_id
+    ))
+}
+
 /// Convert an SseEvent to an axum Event.
 fn sse_event_to_axum_event(event: &SseEvent) -> Option<Event> {
     match event {
@@ -459,20 +908,23 @@ fn sse_event_to_axum_event(event: &SseEvent) -> Option<Event> {
 }
 
 /// Get or create a session.
-fn get_or_create_session(
+///
+/// A session ID that is unknown, evicted or expired is rejected rather than
+/// silently replaced, so the client knows to re-initialize.
+async fn get_or_create_session(
     state: &AxumHandlerState,
     session_id_header: Option<&str>,
 ) -> Result<(SessionState, bool), HttpServerError> {
     match session_id_header {
-        Some(id) => match state.session_manager().touch_session(id) {
+        Some(id) => match state.session_manager().touch_session(id).await {
             Some(session) => Ok((session, false)),
-            None => {
-                let session = state.session_manager().create_session()?;
-                Ok((session, true))
-            }
+            None => Err(HttpServerError::SessionNotFound(id.to_string())),
         },
         None => {
-            let session = state.session_manager().create_session()?;
+            let session = state.session_manager().create_session().await?;
+            if let Some(metrics) = state.server().server().metrics() {
+                metrics.on_session_created();
+            }
             Ok((session, true))
         }
     }
@@ -480,19 +932,12 @@ fn get_or_create_session(
 
 /// Create a JSON error response.
 fn error_response(status: StatusCode, message: &str) -> Response {
-    let body = serde_json::json!({
-        "jsonrpc": "2.0",
-        "id": null,
-        "error": {
-            "code": -32000,
-            "message": message
-        }
-    });
+    let body = ResultMessage::failure(MessageId::Null, ErrorObject::new(-32000, message, None));
 
     Response::builder()
         .status(status)
         .header(header::CONTENT_TYPE, "application/json")
-        .body(Body::from(body.to_string()))
+        .body(Body::from(serde_json::to_string(&body).unwrap_or_default()))
         .unwrap()
 }
 
@@ -518,7 +963,7 @@ mod tests {
         let server = Arc::new(McpServer::new(server_info, ServerOptions::default()));
         let state = AxumHandlerState::new(server, AxumHandlerConfig::default());
 
-        assert_eq!(state.session_manager().session_count(), 0);
+        assert_eq!(state.session_manager().session_count().await, 0);
     }
 
     #[tokio::test]
@@ -4,26 +4,55 @@
 //! including event buffering for reconnection support with Last-Event-ID.
 
 use std::collections::VecDeque;
+use std::time::Duration;
 
 use mcp_core::http::SseEvent;
 #[cfg(feature = "tokio")]
 use mcp_core::stdio::JsonRpcMessage;
 
+use super::ReplayError;
+
 /// Configuration for the event buffer.
 #[derive(Debug, Clone)]
 pub struct EventBufferConfig {
     /// Maximum number of events to retain for replay.
     pub max_events: usize,
-    /// Maximum age of events to retain (in seconds).
-    pub max_age_secs: u64,
+    /// Maximum total size of retained events, in serialized bytes.
+    pub max_bytes: usize,
+    /// Maximum age of events to retain; older ones are never replayed.
+    pub max_age: Duration,
+    /// How often [`SseBroadcaster`](async_broadcast::SseBroadcaster) purges
+    /// events older than `max_age` between insertions. Zero disables the
+    /// background sweep.
+    pub cleanup_interval: Duration,
 }
 
 impl Default for EventBufferConfig {
     fn default() -> Self {
         Self {
             max_events: 100,
-            max_age_secs: 300, // 5 minutes
+            max_bytes: 4 * 1024 * 1024, // 4 MiB
+            max_age: Duration::from_secs(300),
+            cleanup_interval: Duration::from_secs(30),
+        }
+    }
+}
+
+/// Sequence number of an event ID of the form `<prefix>-<n>`.
+pub(crate) fn event_sequence(id: &str) -> Option<u64> {
+    id.rsplit_once('-')?.1.parse().ok()
+}
+
+/// Approximate wire size of an event.
+fn event_size(event: &SseEvent) -> usize {
+    match event {
+        SseEvent::Message { id, data } => {
+            let data = serde_json::to_vec(data).map(|v| v.len()).unwrap_or(0);
+            data + id.as_ref().map_or(0, |id| id.len())
         }
+        SseEvent::Ping => 0,
+        SseEvent::SessionReady { session_id } => session_id.to_string().len(),
+        SseEvent::Endpoint { endpoint_url } => endpoint_url.len(),
     }
 }
 
@@ -36,6 +65,8 @@ pub struct BufferedEvent {
     pub event: SseEvent,
     /// When the event was created (Unix timestamp in milliseconds).
     pub timestamp_ms: u64,
+    /// Serialized size of the event, counted against `EventBufferConfig::max_bytes`.
+    pub size_bytes: usize,
 }
 
 impl BufferedEvent {
@@ -45,29 +76,42 @@ impl BufferedEvent {
             .duration_since(std::time::UNIX_EPOCH)
             .unwrap_or_default()
             .as_millis() as u64;
+        let size_bytes = event_size(&event);
         Self {
             id,
             event,
             timestamp_ms,
+            size_bytes,
         }
     }
 
-    /// Check if the event has expired.
-    pub fn is_expired(&self, max_age_secs: u64) -> bool {
+    /// Sequence number parsed from the event ID (`<prefix>-<n>`).
+    pub fn sequence(&self) -> Option<u64> {
+        event_sequence(&self.id)
+    }
+
+    /// Check if the event is older than `max_age`.
+    pub fn is_expired(&self, max_age: Duration) -> bool {
         let now_ms = std::time::SystemTime::now()
             .duration_since(std::time::UNIX_EPOCH)
             .unwrap_or_default()
             .as_millis() as u64;
         let age_ms = now_ms.saturating_sub(self.timestamp_ms);
-        age_ms > max_age_secs * 1000
+        u128::from(age_ms) > max_age.as_millis()
     }
 }
 
 /// Event buffer for storing recent events for replay.
+///
+/// Events are bounded by count, total size and age, and evicted oldest-first.
+/// The buffer remembers the highest sequence it has evicted, so it can tell
+/// whether a replay from a given Last-Event-ID would be complete.
 #[derive(Debug)]
 pub struct EventBuffer {
     events: VecDeque<BufferedEvent>,
     config: EventBufferConfig,
+    total_bytes: usize,
+    evicted_through: Option<u64>,
 }
 
 impl EventBuffer {
@@ -76,6 +120,8 @@ impl EventBuffer {
         Self {
             events: VecDeque::new(),
             config,
+            total_bytes: 0,
+            evicted_through: None,
         }
     }
 
@@ -84,14 +130,43 @@ impl EventBuffer {
         // Remove expired events
         self.cleanup_expired();
 
-        // Remove oldest if at capacity
-        while self.events.len() >= self.config.max_events {
-            self.events.pop_front();
+        // An event larger than the whole budget is never retained
+        if event.size_bytes > self.config.max_bytes {
+            self.mark_evicted(&event);
+            return;
+        }
+
+        // Remove oldest until the event fits
+        while !self.events.is_empty()
+            && (self.events.len() >= self.config.max_events
+                || self.total_bytes + event.size_bytes > self.config.max_bytes)
+        {
+            self.evict_oldest();
         }
 
+        self.total_bytes += event.size_bytes;
         self.events.push_back(event);
     }
 
+    /// Get the events after `last_event_id` if none of them has been evicted.
+    ///
+    /// Fails with [`ReplayError::TooOld`] when the replay would have a gap,
+    /// i.e. some event after `last_event_id` was evicted by count, size or
+    /// age, and with [`ReplayError::UnknownId`] for an ID this buffer did
+    /// not issue.
+    pub fn replay_after(&mut self, last_event_id: &str) -> Result<Vec<BufferedEvent>, ReplayError> {
+        self.cleanup_expired();
+
+        let buffered = self.events.iter().any(|e| e.id == last_event_id);
+        if !buffered {
+            let sequence = event_sequence(last_event_id).ok_or(ReplayError::UnknownId)?;
+            if self.evicted_through.is_some_and(|through| through > sequence) {
+                return Err(ReplayError::TooOld);
+            }
+        }
+        Ok(self.events_after(last_event_id))
+    }
+
     /// Get all events after the given event ID.
     pub fn events_after(&self, last_event_id: &str) -> Vec<BufferedEvent> {
         // Find the position of the last event ID
@@ -105,7 +180,7 @@ impl EventBuffer {
         self.events
             .iter()
             .skip(start_pos)
-            .filter(|e| !e.is_expired(self.config.max_age_secs))
+            .filter(|e| !e.is_expired(self.config.max_age))
             .cloned()
             .collect()
     }
@@ -114,15 +189,53 @@ impl EventBuffer {
     pub fn all_events(&self) -> Vec<BufferedEvent> {
         self.events
             .iter()
-            .filter(|e| !e.is_expired(self.config.max_age_secs))
+            .filter(|e| !e.is_expired(self.config.max_age))
             .cloned()
             .collect()
     }
 
-    /// Remove expired events.
-    fn cleanup_expired(&mut self) {
-        let max_age = self.config.max_age_secs;
-        self.events.retain(|e| !e.is_expired(max_age));
+    /// Drop all buffered events.
+    pub fn clear(&mut self) {
+        while !self.events.is_empty() {
+            self.evict_oldest();
+        }
+    }
+
+    /// Get the total size of buffered events, in bytes.
+    pub fn total_bytes(&self) -> usize {
+        self.total_bytes
+    }
+
+    /// Estimate the memory held by the buffer: the serialized size of its
+    /// events plus their IDs and the queue's allocation.
+    pub fn memory_bytes(&self) -> usize {
+        let ids: usize = self.events.iter().map(|e| e.id.len()).sum();
+        std::mem::size_of::<Self>()
+            + self.events.capacity() * std::mem::size_of::<BufferedEvent>()
+            + ids
+            + self.total_bytes
+    }
+
+    /// Remove events older than `max_age`.
+    pub fn cleanup_expired(&mut self) {
+        let max_age = self.config.max_age;
+        while self.events.front().is_some_and(|e| e.is_expired(max_age)) {
+            self.evict_oldest();
+        }
+    }
+
+    /// Remove the oldest event, remembering its sequence.
+    fn evict_oldest(&mut self) {
+        if let Some(event) = self.events.pop_front() {
+            self.total_bytes -= event.size_bytes;
+            self.mark_evicted(&event);
+        }
+    }
+
+    fn mark_evicted(&mut self, event: &BufferedEvent) {
+        if let Some(sequence) = event.sequence() {
+            self.evicted_through = self.evicted_through.max(Some(sequence));
+        }
     }
 
     /// Get the number of buffered events.
@@ -146,28 +259,27 @@ impl Default for EventBuffer {
 #[cfg(feature = "tokio")]
 pub mod async_broadcast {
     use super::*;
-    use std::sync::RwLock;
-    use tokio::sync::broadcast;
+    use std::sync::{Arc, RwLock};
+    use tokio::sync::{broadcast, watch};
 
     /// A broadcast sender for SSE events.
+    ///
+    /// Created inside a tokio runtime, it also runs a task that purges
+    /// expired events every `EventBufferConfig::cleanup_interval` until
+    /// the broadcaster is closed or dropped.
     #[derive(Debug)]
     pub struct SseBroadcaster {
         sender: broadcast::Sender<SseEvent>,
-        buffer: RwLock<EventBuffer>,
+        buffer: Arc<RwLock<EventBuffer>>,
         event_counter: std::sync::atomic::AtomicU64,
         session_id: String,
+        closed: watch::Sender<bool>,
     }
 
     impl SseBroadcaster {
         /// Create a new broadcaster with the given capacity.
         pub fn new(session_id: String, capacity: usize) -> Self {
-            let (sender, _) = broadcast::channel(capacity);
-            Self {
-                sender,
-                buffer: RwLock::new(EventBuffer::default()),
-                event_counter: std::sync::atomic::AtomicU64::new(0),
-                session_id,
-            }
+            Self::with_buffer_config(session_id, capacity, EventBufferConfig::default())
         }
 
         /// Create a new broadcaster with custom buffer configuration.
@@ -177,12 +289,41 @@ pub mod async_broadcast {
             buffer_config: EventBufferConfig,
         ) -> Self {
             let (sender, _) = broadcast::channel(capacity);
-            Self {
+            let cleanup_interval = buffer_config.cleanup_interval;
+            let broadcaster = Self {
                 sender,
-                buffer: RwLock::new(EventBuffer::new(buffer_config)),
+                buffer: Arc::new(RwLock::new(EventBuffer::new(buffer_config))),
                 event_counter: std::sync::atomic::AtomicU64::new(0),
                 session_id,
+                closed: watch::Sender::new(false),
+            };
+            broadcaster.spawn_cleanup(cleanup_interval);
+            broadcaster
+        }
+
+        /// Purge expired events every `interval` on the current runtime.
+        fn spawn_cleanup(&self, interval: Duration) {
+            if interval.is_zero() {
+                return;
             }
+            let Ok(runtime) = tokio::runtime::Handle::try_current() else {
+                return;
+            };
+            let buffer = Arc::downgrade(&self.buffer);
+            let mut closed = self.closed.subscribe();
+            runtime.spawn(async move {
+                loop {
+                    tokio::select! {
+                        _ = tokio::time::sleep(interval) => {}
+                        // Closed, or the broadcaster was dropped
+                        _ = closed.wait_for(|closed| *closed) => break,
+                    }
+                    let Some(buffer) = buffer.upgrade() else {
+                        break;
+                    };
+                    buffer.write().unwrap().cleanup_expired();
+                }
+            });
         }
 
         /// Subscribe to the broadcast.
@@ -190,6 +331,23 @@ pub mod async_broadcast {
             self.sender.subscribe()
         }
 
+        /// Close the broadcaster: end open SSE streams and drop the replay buffer.
+        pub fn close(&self) {
+            self.closed.send_replace(true);
+            self.buffer.write().unwrap().clear();
+        }
+
+        /// Whether [`close`](Self::close) has been called.
+        pub fn is_closed(&self) -> bool {
+            *self.closed.borrow()
+        }
+
+        /// Wait until the broadcaster is closed.
+        pub async fn closed(&self) {
+            let mut closed = self.closed.subscribe();
+            let _ = closed.wait_for(|closed| *closed).await;
+        }
+
         /// Get the number of active subscribers.
         pub fn receiver_count(&self) -> usize {
             self.sender.receiver_count()
@@ -205,7 +363,13 @@ pub mod async_broadcast {
         }
 
         /// Broadcast a JSON-RPC message.
+        ///
+        /// The ID is assigned, buffered and sent under the buffer lock, so
+        /// subscribers and the replay buffer see strictly increasing IDs.
+        /// Having no live subscriber is not an error: the message stays
+        /// buffered for a client reconnecting with Last-Event-ID.
         pub fn send_message(&self, message: JsonRpcMessage) -> Result<String, broadcast::error::SendError<SseEvent>> {
+            let mut buffer = self.buffer.write().unwrap();
             let event_id = self.next_event_id();
             let event = SseEvent::Message {
                 id: Some(event_id.clone()),
@@ -213,12 +377,9 @@ pub mod async_broadcast {
             };
 
             // Buffer the event for replay
-            {
-                let mut buffer = self.buffer.write().unwrap();
-                buffer.push(BufferedEvent::new(event_id.clone(), event.clone()));
-            }
+            buffer.push(BufferedEvent::new(event_id.clone(), event.clone()));
 
-            self.sender.send(event)?;
+            let _ = self.sender.send(event);
             Ok(event_id)
         }
 
@@ -248,11 +409,24 @@ pub mod async_broadcast {
             buffer.events_after(last_event_id)
         }
 
+        /// Get events after the given Last-Event-ID; see
+        /// [`EventBuffer::replay_after`].
+        pub fn replay_after(&self, last_event_id: &str) -> Result<Vec<BufferedEvent>, ReplayError> {
+            let mut buffer = self.buffer.write().unwrap();
+            buffer.replay_after(last_event_id)
+        }
+
         /// Get all buffered events.
         pub fn get_all_buffered_events(&self) -> Vec<BufferedEvent> {
             let buffer = self.buffer.read().unwrap();
             buffer.all_events()
         }
+
+        /// Estimated memory held by the replay buffer; see
+        /// [`EventBuffer::memory_bytes`].
+        pub fn buffer_memory_bytes(&self) -> usize {
+            self.buffer.read().unwrap().memory_bytes()
+        }
     }
 }
 
@@ -275,7 +449,8 @@ mod tests {
     fn test_event_buffer_capacity() {
         let config = EventBufferConfig {
             max_events: 3,
-            max_age_secs: 300,
+            max_age: Duration::from_secs(300),
+            ..Default::default()
         };
         let mut buffer = EventBuffer::new(config);
 
@@ -325,4 +500,149 @@ mod tests {
         let after = buffer.events_after("nonexistent");
         assert_eq!(after.len(), 3);
     }
+
+    fn message_event(id: &str, text: &str) -> BufferedEvent {
+        let notification = mcp_core::types::NotificationMessage::new(
+            "notifications/message",
+            Some(serde_json::json!({ "text": text })),
+        );
+        BufferedEvent::new(
+            id.to_string(),
+            SseEvent::Message {
+                id: Some(id.to_string()),
+                data: mcp_core::stdio::JsonRpcMessage::Notification(notification),
+            },
+        )
+    }
+
+    #[test]
+    fn test_event_buffer_max_bytes() {
+        let size = message_event("s-1", &"x".repeat(1000)).size_bytes;
+        let config = EventBufferConfig {
+            max_bytes: size * 3,
+            ..Default::default()
+        };
+        let mut buffer = EventBuffer::new(config);
+
+        for i in 1..=5 {
+            buffer.push(message_event(&format!("s-{}", i), &"x".repeat(1000)));
+        }
+        assert_eq!(buffer.len(), 3);
+        assert_eq!(buffer.total_bytes(), size * 3);
+        assert_eq!(buffer.all_events()[0].id, "s-3");
+
+        // An event over the whole budget is dropped without evicting others
+        buffer.push(message_event("s-6", &"x".repeat(size * 4)));
+        assert_eq!(buffer.len(), 3);
+        assert_eq!(buffer.all_events()[2].id, "s-5");
+    }
+
+    #[test]
+    fn test_replay_after_detects_gap() {
+        let config = EventBufferConfig {
+            max_events: 3,
+            ..Default::default()
+        };
+        let mut buffer = EventBuffer::new(config);
+        for i in 1..=5 {
+            buffer.push(BufferedEvent::new(format!("s-{}", i), SseEvent::Ping));
+        }
+
+        // s-1 and s-2 were evicted
+        let replay = buffer.replay_after("s-3").unwrap();
+        assert_eq!(replay.len(), 2);
+        assert_eq!(replay[0].id, "s-4");
+        assert_eq!(buffer.replay_after("s-2").unwrap().len(), 3);
+        assert_eq!(buffer.replay_after("s-1").unwrap_err(), ReplayError::TooOld);
+        assert_eq!(buffer.replay_after("nonexistent").unwrap_err(), ReplayError::UnknownId);
+        assert!(buffer.replay_after("s-5").unwrap().is_empty());
+    }
+
+    /// Event created `age` ago.
+    fn aged_event(id: &str, age: Duration) -> BufferedEvent {
+        let mut event = BufferedEvent::new(id.to_string(), SseEvent::Ping);
+        event.timestamp_ms -= age.as_millis() as u64;
+        event
+    }
+
+    #[test]
+    fn test_event_buffer_max_age() {
+        let config = EventBufferConfig {
+            max_age: Duration::from_secs(60),
+            ..Default::default()
+        };
+        let mut buffer = EventBuffer::new(config);
+        buffer.push(aged_event("s-1", Duration::from_secs(120)));
+        buffer.push(aged_event("s-2", Duration::from_secs(90)));
+        buffer.push(aged_event("s-3", Duration::from_secs(30)));
+
+        // Stale events are dropped on insertion and never replayed
+        buffer.push(aged_event("s-4", Duration::ZERO));
+        assert_eq!(buffer.len(), 2);
+        assert_eq!(buffer.all_events()[0].id, "s-3");
+
+        assert_eq!(buffer.replay_after("s-1").unwrap_err(), ReplayError::TooOld);
+        let replay = buffer.replay_after("s-2").unwrap();
+        assert_eq!(replay.len(), 2);
+        assert_eq!(replay[0].id, "s-3");
+    }
+
+    #[test]
+    fn test_memory_bytes_tracks_buffered_events() {
+        let mut buffer = EventBuffer::default();
+        let empty = buffer.memory_bytes();
+
+        buffer.push(message_event("s-1", &"x".repeat(1000)));
+        let one = buffer.memory_bytes();
+        assert!(one >= empty + buffer.total_bytes(), "{one} vs {empty}");
+        buffer.push(message_event("s-2", &"x".repeat(1000)));
+        assert!(buffer.memory_bytes() > one);
+
+        buffer.clear();
+        assert_eq!(buffer.total_bytes(), 0);
+        assert!(buffer.memory_bytes() < one);
+    }
+
+    #[test]
+    fn test_replay_after_lru_eviction_returns_survivors() {
+        let config = EventBufferConfig {
+            max_events: 2,
+            ..Default::default()
+        };
+        let mut buffer = EventBuffer::new(config);
+        for i in 1..=4 {
+            buffer.push(BufferedEvent::new(format!("s-{}", i), SseEvent::Ping));
+        }
+
+        // s-1 and s-2 were evicted, but nothing after s-2 is missing
+        let ids: Vec<String> = buffer
+            .replay_after("s-2")
+            .unwrap()
+            .into_iter()
+            .map(|e| e.id)
+            .collect();
+        assert_eq!(ids, ["s-3", "s-4"]);
+    }
+
+    #[test]
+    fn test_replay_scan_drops_expired_events() {
+        let config = EventBufferConfig {
+            max_age: Duration::from_secs(60),
+            ..Default::default()
+        };
+        let mut buffer = EventBuffer::new(config);
+        buffer.push(aged_event("s-1", Duration::from_secs(30)));
+        buffer.push(aged_event("s-2", Duration::from_secs(20)));
+        buffer.push(aged_event("s-3", Duration::from_secs(10)));
+
+        // s-1 and s-2 expire while buffered, with no insertion to evict them
+        for event in buffer.events.iter_mut().take(2) {
+            event.timestamp_ms -= 60_000;
+        }
+        assert_eq!(buffer.replay_after("s-1").unwrap_err(), ReplayError::TooOld);
+        assert_eq!(buffer.len(), 1);
+        let replay = buffer.replay_after("s-2").unwrap();
+        assert_eq!(replay.len(), 1);
+        assert_eq!(replay[0].id, "s-3");
+    }
 }
@@ -0,0 +1,147 @@
+//! CORS policy for the axum and WebSocket handlers.
+//!
+//! The default policy grants no cross-origin access; list the origins that
+//! may call the server, or use [`CorsPolicy::permissive`] for local
+//! development.
+//!
+//! ## Example
+//!
+//! ```ignore
+//! use std::time::Duration;
+//! use mcp_server::{AxumHandlerConfig, CorsPolicy};
+//!
+//! let config = AxumHandlerConfig {
+//!     cors: CorsPolicy {
+//!         allow_credentials: true,
+//!         max_age: Some(Duration::from_secs(600)),
+//!         ..CorsPolicy::new(["https://app.example.com", "https://*.example.dev"])
+//!     },
+//!     ..Default::default()
+//! };
+//! ```
+
+use std::time::Duration;
+
+use axum::http::{HeaderName, Method};
+use tower_http::cors::{AllowOrigin, CorsLayer};
+
+use super::origin_validation::OriginValidationConfig;
+
+/// Request headers every policy allows, since the transport cannot work
+/// from a browser without them.
+const REQUIRED_HEADERS: [&str; 3] = ["mcp-session-id", "last-event-id", "authorization"];
+
+/// CORS policy applied to the MCP endpoint.
+///
+/// Preflight `OPTIONS` requests are answered by the policy itself and never
+/// reach the transport, so they cannot create sessions. Origins outside the
+/// allowlist get no CORS headers.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CorsPolicy {
+    /// Allowed origins: exact (`https://app.example.com`), with a port
+    /// (`http://localhost:5173`), wildcard subdomains
+    /// (`https://*.example.com`), `null`, or `*` for any origin.
+    /// Empty grants no cross-origin access.
+    pub allow_origins: Vec<String>,
+    /// Methods allowed in cross-origin requests.
+    pub allow_methods: Vec<Method>,
+    /// Request headers allowed in cross-origin requests. `Mcp-Session-Id`,
+    /// `Last-Event-ID` and `Authorization` are always allowed.
+    pub allow_headers: Vec<String>,
+    /// Response headers exposed to browser scripts.
+    pub expose_headers: Vec<String>,
+    /// Whether to send `Access-Control-Allow-Credentials: true`.
+    ///
+    /// With `*` in `allow_origins`, the request origin is mirrored, since
+    /// browsers reject a wildcard origin on credentialed requests.
+    pub allow_credentials: bool,
+    /// How long browsers may cache preflight responses.
+    pub max_age: Option<Duration>,
+}
+
+impl CorsPolicy {
+    /// A policy allowing the given origins, with the methods and headers the
+    /// Streamable HTTP transport uses and `Mcp-Session-Id` exposed.
+    pub fn new<I, S>(origins: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self {
+            allow_origins: origins.into_iter().map(Into::into).collect(),
+            allow_methods: vec![Method::GET, Method::POST, Method::DELETE],
+            allow_headers: vec![
+                "content-type".to_string(),
+                "accept".to_string(),
+                "mcp-session-id".to_string(),
+                "last-event-id".to_string(),
+                "authorization".to_string(),
+            ],
+            expose_headers: vec!["mcp-session-id".to_string()],
+            allow_credentials: false,
+            max_age: None,
+        }
+    }
+
+    /// Allow any origin. Meant for local development only: it lets any web
+    /// page the user visits talk to the server.
+    pub fn permissive() -> Self {
+        Self::new(["*"])
+    }
+
+    /// Whether `*` is in the allowlist.
+    pub fn allows_any_origin(&self) -> bool {
+        self.allow_origins.iter().any(|origin| origin.trim() == "*")
+    }
+
+    /// Build the tower-http layer. Invalid header names are ignored.
+    pub fn layer(&self) -> CorsLayer {
+        let allow_origin = if !self.allows_any_origin() {
+            let origins = OriginValidationConfig::new(self.allow_origins.iter().cloned());
+            AllowOrigin::predicate(move |origin, _| {
+                origin
+                    .to_str()
+                    .is_ok_and(|origin| origins.is_allowed(origin))
+            })
+        } else if self.allow_credentials {
+            AllowOrigin::mirror_request()
+        } else {
+            AllowOrigin::any()
+        };
+
+        let mut allow_headers = self.allow_headers.clone();
+        for required in REQUIRED_HEADERS {
+            if !allow_headers
+                .iter()
+                .any(|header| header.eq_ignore_ascii_case(required))
+            {
+                allow_headers.push(required.to_string());
+            }
+        }
+
+        let mut layer = CorsLayer::new()
+            .allow_origin(allow_origin)
+            .allow_methods(self.allow_methods.clone())
+            .allow_headers(header_names(&allow_headers))
+            .expose_headers(header_names(&self.expose_headers))
+            .allow_credentials(self.allow_credentials);
+        if let Some(max_age) = self.max_age {
+            layer = layer.max_age(max_age);
+        }
+        layer
+    }
+}
+
+impl Default for CorsPolicy {
+    /// No cross-origin access.
+    fn default() -> Self {
+        Self::new(Vec::<String>::new())
+    }
+}
+
+fn header_names(names: &[String]) -> Vec<HeaderName> {
+    names
+        .iter()
+        .filter_map(|name| HeaderName::try_from(name.as_str()).ok())
+        .collect()
+}
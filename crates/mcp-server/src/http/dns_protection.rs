@@ -16,33 +16,31 @@
 //!
 //! // Or use the convenience function for localhost-only
 //! let layer = localhost_host_validation();
+//!
+//! // Behind a reverse proxy: exact and wildcard hosts, optionally with a port
+//! let layer = allowed_hosts_validation(vec!["mcp.example.com", "*.example.com", "[::1]:8443"]);
 //! ```
 
 use std::collections::HashSet;
 use std::future::Future;
+use std::net::Ipv6Addr;
 use std::pin::Pin;
 use std::sync::Arc;
 use std::task::{Context, Poll};
 
 use axum::body::Body;
 use axum::http::{header, Request, Response, StatusCode};
+use mcp_core::types::{ErrorObject, MessageId, ResultMessage};
 use tower::{Layer, Service};
 
 /// JSON-RPC error response for DNS rebinding protection failures.
 fn json_rpc_error_response(message: &str) -> Response<Body> {
-    let body = serde_json::json!({
-        "jsonrpc": "2.0",
-        "error": {
-            "code": -32000,
-            "message": message
-        },
-        "id": null
-    });
+    let body = ResultMessage::failure(MessageId::Null, ErrorObject::new(-32000, message, None));
 
     Response::builder()
         .status(StatusCode::FORBIDDEN)
         .header(header::CONTENT_TYPE, "application/json")
-        .body(Body::from(body.to_string()))
+        .body(Body::from(serde_json::to_string(&body).unwrap_or_default()))
         .unwrap()
 }
 
@@ -50,12 +48,49 @@ fn json_rpc_error_response(message: &str) -> Response<Body> {
 ///
 /// Handles IPv4, IPv6 (with brackets), and regular hostnames.
 fn extract_hostname(host_header: &str) -> Option<String> {
-    // Try to parse as a URL to extract hostname
-    // We prepend "http://" to make it a valid URL
-    let url_str = format!("http://{}", host_header);
-    match url::Url::parse(&url_str) {
-        Ok(url) => url.host_str().map(|s| s.to_string()),
-        Err(_) => None,
+    split_host_port(host_header).map(|(host, _)| host)
+}
+
+/// Split a `Host` header value (or allowlist entry) into a lowercase
+/// hostname and an optional port.
+///
+/// IPv6 addresses must be bracketed (`[::1]:8080`) and are normalized to
+/// their canonical form, brackets included.
+pub(super) fn split_host_port(value: &str) -> Option<(String, Option<u16>)> {
+    let value = value.trim();
+    let (host, port) = if let Some(rest) = value.strip_prefix('[') {
+        let (address, after) = rest.split_once(']')?;
+        let address: Ipv6Addr = address.parse().ok()?;
+        (format!("[{}]", address), after)
+    } else {
+        match value.split_once(':') {
+            Some((host, _)) => (host.to_ascii_lowercase(), &value[host.len()..]),
+            None => (value.to_ascii_lowercase(), ""),
+        }
+    };
+
+    let port = match port {
+        "" => None,
+        port => Some(port.strip_prefix(':')?.parse::<u16>().ok()?),
+    };
+
+    let valid_host = host.starts_with('[')
+        || (!host.is_empty()
+            && host
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_' | '*')));
+    valid_host.then_some((host, port))
+}
+
+/// Check a hostname against an allowlist entry: an exact host or a
+/// `*.domain` wildcard matching any subdomain (but not `domain` itself).
+pub(super) fn host_matches(pattern: &str, hostname: &str) -> bool {
+    match pattern.strip_prefix("*.") {
+        Some(domain) => hostname
+            .strip_suffix(domain)
+            .and_then(|prefix| prefix.strip_suffix('.'))
+            .is_some_and(|subdomain| !subdomain.is_empty()),
+        None => pattern == hostname,
     }
 }
 
@@ -65,6 +100,10 @@ pub struct DnsProtectionConfig {
     /// Set of allowed hostnames (without ports).
     /// For IPv6, include the address with brackets (e.g., "[::1]").
     pub allowed_hostnames: HashSet<String>,
+    /// Additional allowed hosts: exact hostnames or `*.example.com`
+    /// wildcards, optionally with a port (`example.com:8443`) that must then
+    /// match the request's port as well.
+    pub allowed_hosts: Vec<String>,
 }
 
 impl DnsProtectionConfig {
@@ -76,9 +115,31 @@ impl DnsProtectionConfig {
     {
         Self {
             allowed_hostnames: hostnames.into_iter().map(|s| s.into()).collect(),
+            allowed_hosts: Vec::new(),
         }
     }
 
+    /// Create a configuration that only allows the given hosts.
+    ///
+    /// See [`allowed_hosts`](Self::allowed_hosts) for the accepted forms.
+    pub fn with_allowed_hosts<I, S>(hosts: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self::new(Vec::<String>::new()).allow_hosts(hosts)
+    }
+
+    /// Add entries to the host allowlist.
+    pub fn allow_hosts<I, S>(mut self, hosts: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.allowed_hosts.extend(hosts.into_iter().map(|s| s.into()));
+        self
+    }
+
     /// Create a configuration that only allows localhost connections.
     pub fn localhost() -> Self {
         Self::new(["localhost", "127.0.0.1", "[::1]"])
@@ -86,7 +147,29 @@ impl DnsProtectionConfig {
 
     /// Check if the hostname is allowed.
     pub fn is_allowed(&self, hostname: &str) -> bool {
-        self.allowed_hostnames.contains(hostname)
+        self.allowed_hostnames.contains(hostname) || self.allowed_hosts_match(hostname, None)
+    }
+
+    /// Check a raw `Host` header value, including its port, against the
+    /// configuration.
+    pub fn is_host_allowed(&self, host_header: &str) -> bool {
+        match split_host_port(host_header) {
+            Some((hostname, port)) => {
+                self.allowed_hostnames.contains(&hostname)
+                    || self.allowed_hosts_match(&hostname, port)
+            }
+            None => false,
+        }
+    }
+
+    fn allowed_hosts_match(&self, hostname: &str, port: Option<u16>) -> bool {
+        self.allowed_hosts.iter().any(|entry| {
+            let Some((pattern, allowed_port)) = split_host_port(entry) else {
+                return false;
+            };
+            host_matches(&pattern, hostname)
+                && allowed_port.is_none_or(|allowed| port == Some(allowed))
+        })
     }
 }
 
@@ -179,8 +262,8 @@ where
                 }
             };
 
-            // Validate hostname
-            if !config.is_allowed(&hostname) {
+            // Validate hostname (and port, for allowlist entries that pin one)
+            if !config.is_host_allowed(host_header) {
                 return Ok(json_rpc_error_response(&format!(
                     "Invalid Host: {}",
                     hostname
@@ -215,6 +298,27 @@ where
     DnsProtectionLayer::new(DnsProtectionConfig::new(hostnames))
 }
 
+/// Create a DNS rebinding protection layer from a host allowlist.
+///
+/// Entries are exact hostnames or `*.example.com` wildcards, optionally with
+/// a port. IPv6 addresses must be bracketed (e.g., "[::1]:8080").
+/// Requests whose `Host` header matches no entry are rejected with 403.
+///
+/// # Example
+///
+/// ```ignore
+/// use mcp_server::http::dns_protection::allowed_hosts_validation;
+///
+/// let layer = allowed_hosts_validation(vec!["mcp.example.com", "*.internal.example.com"]);
+/// ```
+pub fn allowed_hosts_validation<I, S>(hosts: I) -> DnsProtectionLayer
+where
+    I: IntoIterator<Item = S>,
+    S: Into<String>,
+{
+    DnsProtectionLayer::new(DnsProtectionConfig::with_allowed_hosts(hosts))
+}
+
 /// Create a DNS rebinding protection layer for localhost only.
 ///
 /// Allows only localhost, 127.0.0.1, and [::1] (IPv6 localhost) hostnames.
@@ -286,6 +390,58 @@ mod tests {
         assert!(!config.is_allowed("example.com"));
     }
 
+    #[test]
+    fn test_split_host_port() {
+        assert_eq!(
+            split_host_port("Example.COM:8443"),
+            Some(("example.com".to_string(), Some(8443)))
+        );
+        assert_eq!(
+            split_host_port("[0:0:0:0:0:0:0:1]:8080"),
+            Some(("[::1]".to_string(), Some(8080)))
+        );
+        assert_eq!(split_host_port("[::1]"), Some(("[::1]".to_string(), None)));
+        assert_eq!(split_host_port("::1"), None);
+        assert_eq!(split_host_port("[::1]8080"), None);
+        assert_eq!(split_host_port("example.com:http"), None);
+        assert_eq!(split_host_port("example.com:70000"), None);
+        assert_eq!(split_host_port("exa mple.com"), None);
+        assert_eq!(split_host_port(""), None);
+    }
+
+    #[test]
+    fn test_allowed_hosts_exact_and_wildcard() {
+        let config = DnsProtectionConfig::with_allowed_hosts(["mcp.example.com", "*.internal.net"]);
+        assert!(config.is_host_allowed("mcp.example.com"));
+        assert!(config.is_host_allowed("MCP.example.com:443"));
+        assert!(config.is_host_allowed("api.internal.net"));
+        assert!(config.is_host_allowed("a.b.internal.net:8080"));
+        assert!(!config.is_host_allowed("internal.net"));
+        assert!(!config.is_host_allowed("evilinternal.net"));
+        assert!(!config.is_host_allowed("example.com"));
+        assert!(!config.is_host_allowed("localhost"));
+    }
+
+    #[test]
+    fn test_allowed_hosts_with_port_and_ipv6() {
+        let config = DnsProtectionConfig::with_allowed_hosts(["example.com:8443", "[::1]"]);
+        assert!(config.is_host_allowed("example.com:8443"));
+        assert!(!config.is_host_allowed("example.com"));
+        assert!(!config.is_host_allowed("example.com:8080"));
+        assert!(config.is_host_allowed("[::1]:3000"));
+        assert!(config.is_host_allowed("[0::1]"));
+        assert!(!config.is_host_allowed("[::2]"));
+    }
+
+    #[test]
+    fn test_localhost_with_extra_allowed_hosts() {
+        let config = DnsProtectionConfig::localhost().allow_hosts(["*.example.com"]);
+        assert!(config.is_host_allowed("localhost:3000"));
+        assert!(config.is_host_allowed("app.example.com"));
+        assert!(config.is_allowed("app.example.com"));
+        assert!(!config.is_host_allowed("example.org"));
+    }
+
     #[test]
     fn test_config_custom() {
         let config = DnsProtectionConfig::new(["localhost", "example.com"]);
@@ -52,6 +52,10 @@ pub enum HttpServerError {
     #[error("method not allowed: {0}")]
     MethodNotAllowed(String),
 
+    /// Session store failure.
+    #[error("session store error: {0}")]
+    SessionStore(String),
+
     /// Unsupported content type.
     #[error("unsupported content type: {0}")]
     UnsupportedContentType(String),
@@ -73,6 +77,7 @@ impl HttpServerError {
             Self::Transport(_) => 500,
             Self::Serialization(_) => 500,
             Self::Io(_) => 500,
+            Self::SessionStore(_) => 500,
         }
     }
 }
@@ -1,13 +1,19 @@
 //! HTTP request handler for MCP server.
 
-use std::sync::Arc;
+use std::net::IpAddr;
+use std::sync::{Arc, Mutex};
 
+use mcp_core::http::SessionId;
+use mcp_core::protocol::PeerNotifyFn;
 use mcp_core::stdio::{deserialize_message, serialize_message, JsonRpcMessage};
+use mcp_core::types::{ErrorCode, ErrorObject, MessageId, NotificationMessage, ResultMessage};
 
-use crate::server::McpServer;
+use crate::server::{McpServer, ServerError};
 
 use super::error::HttpServerError;
+use super::rate_limit::{rate_limit_key, rate_limited_body, retry_after_secs, RateLimitConfig, RateLimiter};
 use super::session_manager::{SessionConfig, SessionManager, SessionState};
+use super::session_mode::SessionMode;
 use super::sse_writer::{SseResponseBuilder, SseWriter};
 
 /// Configuration for the HTTP server handler.
@@ -23,6 +29,12 @@ pub struct HttpServerOptions {
     pub base_url: Option<String>,
     /// Endpoint path.
     pub endpoint_path: String,
+    /// Whether sessions are issued and required.
+    pub session_mode: SessionMode,
+    /// Per-client limit on POSTed messages, keyed by session ID or, for
+    /// sessionless requests, the address passed to
+    /// [`HttpServerHandler::handle_post_from`].
+    pub rate_limit: Option<RateLimitConfig>,
 }
 
 impl Default for HttpServerOptions {
@@ -33,6 +45,8 @@ impl Default for HttpServerOptions {
             enable_single_response: true,
             base_url: None,
             endpoint_path: "/mcp".to_string(),
+            session_mode: SessionMode::Stateful,
+            rate_limit: None,
         }
     }
 }
@@ -52,6 +66,15 @@ pub enum HttpResponse {
         /// Takes a writer and returns when the stream should close.
         writer_fn: Box<dyn FnOnce(Box<dyn std::io::Write + Send>) + Send>,
     },
+    /// Complete SSE response to a POSTed request: each message is sent as a
+    /// `message` event, ending with the result, after which the stream closes.
+    EventStream {
+        messages: Vec<JsonRpcMessage>,
+        session_id: Option<String>,
+    },
+    /// The client is over its rate limit: respond 429 with a
+    /// `Retry-After: {retry_after}` header and `body` as JSON.
+    RateLimited { retry_after: u64, body: String },
     /// Empty response (e.g., for DELETE).
     Empty { status: u16 },
     /// Error response.
@@ -66,6 +89,7 @@ pub enum HttpResponse {
 pub struct HttpServerHandler {
     server: Arc<McpServer>,
     session_manager: Arc<SessionManager>,
+    rate_limiter: Option<RateLimiter>,
     options: HttpServerOptions,
 }
 
@@ -73,9 +97,11 @@ impl HttpServerHandler {
     /// Create a new HTTP server handler.
     pub fn new(server: Arc<McpServer>, options: HttpServerOptions) -> Self {
         let session_manager = Arc::new(SessionManager::new(options.session_config.clone()));
+        let rate_limiter = options.rate_limit.clone().map(RateLimiter::new);
         Self {
             server,
             session_manager,
+            rate_limiter,
             options,
         }
     }
@@ -90,13 +116,57 @@ impl HttpServerHandler {
         &self.session_manager
     }
 
-    /// Handle a POST request (send message).
+    /// Get the rate limiter, if `rate_limit` is configured.
+    pub fn rate_limiter(&self) -> Option<&RateLimiter> {
+        self.rate_limiter.as_ref()
+    }
+
+    /// Handle a POST request (send message) from a client that only
+    /// accepts JSON responses.
     pub fn handle_post(
         &self,
         session_id_header: Option<&str>,
         content_type: Option<&str>,
         body: &[u8],
     ) -> HttpResponse {
+        self.handle_post_with_accept(session_id_header, content_type, None, body)
+    }
+
+    /// Handle a POST request, negotiating the response shape.
+    ///
+    /// When `accept` includes `text/event-stream` and the handler sends
+    /// notifications (e.g. progress) before its result, the response is an
+    /// [`HttpResponse::EventStream`]; otherwise it is a single JSON body.
+    pub fn handle_post_with_accept(
+        &self,
+        session_id_header: Option<&str>,
+        content_type: Option<&str>,
+        accept: Option<&str>,
+        body: &[u8],
+    ) -> HttpResponse {
+        self.handle_post_from(None, session_id_header, content_type, accept, body)
+    }
+
+    /// Handle a POST request from `client_ip`, which keys the rate limit
+    /// of requests without a session such as `initialize`.
+    pub fn handle_post_from(
+        &self,
+        client_ip: Option<IpAddr>,
+        session_id_header: Option<&str>,
+        content_type: Option<&str>,
+        accept: Option<&str>,
+        body: &[u8],
+    ) -> HttpResponse {
+        if let Some(limiter) = &self.rate_limiter
+            && let Some(key) = rate_limit_key(session_id_header, None, client_ip)
+            && let Err(retry_after) = limiter.check(&key)
+        {
+            return HttpResponse::RateLimited {
+                retry_after: retry_after_secs(retry_after),
+                body: rate_limited_body(),
+            };
+        }
+
         // Validate content type
         if let Some(ct) = content_type {
             if !ct.starts_with("application/json") {
@@ -128,6 +198,12 @@ impl HttpServerHandler {
             }
         };
 
+        let accepts_sse = accept.is_some_and(|accept| accept.contains("text/event-stream"));
+
+        if self.options.session_mode == SessionMode::Stateless {
+            return self.handle_stateless_message(message, accepts_sse);
+        }
+
         // Get or create session
         let (session, is_new) = match self.get_or_create_session(session_id_header) {
             Ok(result) => result,
@@ -144,31 +220,23 @@ impl HttpServerHandler {
         // Handle the message
         match message {
             JsonRpcMessage::Request(request) => {
+                let request_id = request.id.clone();
+                let (notifier, notifications) = notification_collector(accepts_sse);
+
                 // Process request synchronously
                 let result = futures::executor::block_on(
-                    self.server.server().handle_request(request, Some(session_id.clone())),
+                    self.server.server().handle_request_with_notifier(
+                        "http",
+                        request,
+                        Some(session_id.clone()),
+                        Some(Arc::clone(&session.session_data)),
+                        notifier,
+                        None,
+                    ),
                 );
 
-                match result {
-                    Ok(response) => {
-                        let response_msg = JsonRpcMessage::Result(response);
-                        match serialize_message(&response_msg) {
-                            Ok(body) => HttpResponse::Json {
-                                status: 200,
-                                body,
-                                session_id: if is_new { Some(session_id) } else { None },
-                            },
-                            Err(e) => HttpResponse::Error {
-                                status: 500,
-                                message: format!("Serialization error: {}", e),
-                            },
-                        }
-                    }
-                    Err(e) => HttpResponse::Error {
-                        status: 500,
-                        message: format!("Server error: {}", e),
-                    },
-                }
+                let new_session_id = if is_new { Some(session_id) } else { None };
+                request_response(request_id, result, take_notifications(&notifications), new_session_id)
             }
             JsonRpcMessage::Notification(notification) => {
                 // Process notification (no response expected)
@@ -180,16 +248,49 @@ impl HttpServerHandler {
 
                 HttpResponse::Empty { status: 202 }
             }
-            JsonRpcMessage::Result(_) => {
-                // Clients shouldn't send results
-                HttpResponse::Error {
-                    status: 400,
-                    message: "Unexpected result message from client".to_string(),
+            JsonRpcMessage::Result(response) => {
+                // Only responses to server-initiated requests are expected
+                if self
+                    .server
+                    .server()
+                    .handle_response(response, Some(session_id.clone()))
+                {
+                    HttpResponse::Empty { status: 202 }
+                } else {
+                    HttpResponse::Error {
+                        status: 400,
+                        message: "Unexpected result message from client".to_string(),
+                    }
                 }
             }
         }
     }
 
+    /// Handle a message in stateless mode, without a session.
+    fn handle_stateless_message(&self, message: JsonRpcMessage, accepts_sse: bool) -> HttpResponse {
+        match message {
+            JsonRpcMessage::Request(request) => {
+                let request_id = request.id.clone();
+                let (notifier, notifications) = notification_collector(accepts_sse);
+                let result = futures::executor::block_on(
+                    self.server.server().handle_stateless_request("http", request, notifier, None),
+                );
+                request_response(request_id, result, take_notifications(&notifications), None)
+            }
+            JsonRpcMessage::Notification(notification) => {
+                let _ = futures::executor::block_on(
+                    self.server.server().handle_notification(notification, None),
+                );
+                HttpResponse::Empty { status: 202 }
+            }
+            // No server-initiated request can be waiting for this
+            JsonRpcMessage::Result(_) => HttpResponse::Error {
+                status: 400,
+                message: "Unexpected result message from client".to_string(),
+            },
+        }
+    }
+
     /// Handle a GET request (establish SSE connection).
     pub fn handle_get(
         &self,
@@ -198,7 +299,7 @@ impl HttpServerHandler {
         accept: Option<&str>,
     ) -> HttpResponse {
         // Check if SSE is enabled
-        if !self.options.enable_sse {
+        if !self.options.enable_sse || self.options.session_mode == SessionMode::Stateless {
             return HttpResponse::Error {
                 status: 405,
                 message: "SSE not enabled".to_string(),
@@ -252,7 +353,18 @@ impl HttpServerHandler {
     }
 
     /// Handle a DELETE request (close session).
+    ///
+    /// Idempotent: closing a session returns 204, and a well-formed ID of a
+    /// session that does not exist (already deleted or expired) returns 200.
+    /// A missing or malformed ID is 400.
     pub fn handle_delete(&self, session_id_header: Option<&str>) -> HttpResponse {
+        if self.options.session_mode == SessionMode::Stateless {
+            return HttpResponse::Error {
+                status: 405,
+                message: "Sessions are not enabled".to_string(),
+            };
+        }
+
         let session_id = match session_id_header {
             Some(id) => id,
             None => {
@@ -263,12 +375,16 @@ impl HttpServerHandler {
             }
         };
 
-        match self.session_manager.remove_session(session_id) {
+        if !SessionId::is_well_formed(session_id) {
+            return HttpResponse::Error {
+                status: 400,
+                message: "Invalid session ID".to_string(),
+            };
+        }
+
+        match futures::executor::block_on(self.session_manager.remove_session(session_id)) {
             Some(_) => HttpResponse::Empty { status: 204 },
-            None => HttpResponse::Error {
-                status: 404,
-                message: format!("Session not found: {}", session_id),
-            },
+            None => HttpResponse::Empty { status: 200 },
         }
     }
 
@@ -280,18 +396,15 @@ impl HttpServerHandler {
         match session_id_header {
             Some(id) => {
                 // Try to get existing session
-                match self.session_manager.touch_session(id) {
+                match futures::executor::block_on(self.session_manager.touch_session(id)) {
                     Some(session) => Ok((session, false)),
-                    None => {
-                        // Session not found, create new one
-                        let session = self.session_manager.create_session()?;
-                        Ok((session, true))
-                    }
+                    // Unknown, evicted or expired: the client must re-initialize
+                    None => Err(HttpServerError::SessionNotFound(id.to_string())),
                 }
             }
             None => {
                 // No session ID, create new one
-                let session = self.session_manager.create_session()?;
+                let session = futures::executor::block_on(self.session_manager.create_session())?;
                 Ok((session, true))
             }
         }
@@ -307,7 +420,68 @@ impl HttpServerHandler {
 
     /// Clean up expired sessions.
     pub fn cleanup_sessions(&self) -> usize {
-        self.session_manager.cleanup_expired()
+        futures::executor::block_on(self.session_manager.cleanup_expired())
+    }
+}
+
+type NotificationLog = Arc<Mutex<Vec<NotificationMessage>>>;
+
+/// Notifier recording what a request's handler sends, when the client can
+/// receive it on the POST response.
+fn notification_collector(accepts_sse: bool) -> (Option<PeerNotifyFn>, NotificationLog) {
+    let notifications: NotificationLog = Arc::new(Mutex::new(Vec::new()));
+    let sink = Arc::clone(&notifications);
+    let notifier: PeerNotifyFn = Arc::new(move |method: String, params| {
+        let notification = NotificationMessage::new(method, Some(params));
+        sink.lock().expect("notification log").push(notification);
+    });
+    (accepts_sse.then_some(notifier), notifications)
+}
+
+fn take_notifications(notifications: &NotificationLog) -> Vec<NotificationMessage> {
+    std::mem::take(&mut *notifications.lock().expect("notification log"))
+}
+
+/// Build the response to a POSTed request: a JSON body, or an event stream
+/// when the handler sent notifications before its result.
+fn request_response(
+    request_id: MessageId,
+    result: Result<ResultMessage, ServerError>,
+    notifications: Vec<NotificationMessage>,
+    session_id: Option<String>,
+) -> HttpResponse {
+    if !notifications.is_empty() {
+        let response = result.unwrap_or_else(|e| {
+            let error = ErrorObject::new(ErrorCode::InternalError as i32, e.to_string(), None);
+            ResultMessage::failure(request_id, error)
+        });
+        let mut messages: Vec<JsonRpcMessage> = notifications
+            .into_iter()
+            .map(JsonRpcMessage::Notification)
+            .collect();
+        messages.push(JsonRpcMessage::Result(response));
+        return HttpResponse::EventStream { messages, session_id };
+    }
+
+    match result {
+        Ok(response) => {
+            let response_msg = JsonRpcMessage::Result(response);
+            match serialize_message(&response_msg) {
+                Ok(body) => HttpResponse::Json {
+                    status: 200,
+                    body,
+                    session_id,
+                },
+                Err(e) => HttpResponse::Error {
+                    status: 500,
+                    message: format!("Serialization error: {}", e),
+                },
+            }
+        }
+        Err(e) => HttpResponse::Error {
+            status: 500,
+            message: format!("Server error: {}", e),
+        },
     }
 }
 
@@ -337,6 +511,7 @@ impl<'a> RequestHeaders<'a> {
 mod tests {
     use super::*;
     use crate::server::{ServerOptions, McpServer};
+    use futures::executor::block_on;
     use mcp_core::types::{BaseMetadata, Icons, Implementation, RequestMessage};
 
     fn create_test_handler() -> HttpServerHandler {
@@ -404,10 +579,42 @@ mod tests {
         let response = handler.handle_delete(Some("nonexistent"));
 
         match response {
-            HttpResponse::Error { status, .. } => {
-                assert_eq!(status, 404);
+            HttpResponse::Empty { status } => {
+                assert_eq!(status, 200);
+            }
+            _ => panic!("Expected empty response"),
+        }
+    }
+
+    #[test]
+    fn test_handle_delete_existing_session_twice() {
+        let handler = create_test_handler();
+        let session_id = block_on(handler.session_manager.create_session())
+            .unwrap()
+            .session_id
+            .to_string();
+
+        match handler.handle_delete(Some(&session_id)) {
+            HttpResponse::Empty { status } => assert_eq!(status, 204),
+            _ => panic!("Expected empty response"),
+        }
+        assert_eq!(block_on(handler.session_manager.session_count()), 0);
+
+        match handler.handle_delete(Some(&session_id)) {
+            HttpResponse::Empty { status } => assert_eq!(status, 200),
+            _ => panic!("Expected empty response"),
+        }
+    }
+
+    #[test]
+    fn test_handle_delete_malformed_session() {
+        let handler = create_test_handler();
+
+        for id in ["", "has space", "caf\u{e9}"] {
+            match handler.handle_delete(Some(id)) {
+                HttpResponse::Error { status, .. } => assert_eq!(status, 400),
+                _ => panic!("Expected error response for {:?}", id),
             }
-            _ => panic!("Expected error response"),
         }
     }
 
@@ -427,6 +634,6 @@ mod tests {
 
         // Should get a response (might be an error since "ping" isn't registered,
         // but session should still be created)
-        assert!(handler.session_manager.session_count() > 0);
+        assert!(block_on(handler.session_manager.session_count()) > 0);
     }
 }
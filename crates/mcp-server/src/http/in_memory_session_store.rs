@@ -0,0 +1,101 @@
+//! Process-local session store.
+
+use std::collections::HashMap;
+use std::sync::RwLock;
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+
+use super::error::HttpServerError;
+use super::session_manager::SessionState;
+use super::session_store::{SessionStore, SessionUpdate};
+
+/// Keeps sessions in a map owned by this process; the default store of
+/// `SessionManager`.
+#[derive(Debug, Default)]
+pub struct InMemorySessionStore {
+    sessions: RwLock<HashMap<String, SessionState>>,
+}
+
+impl InMemorySessionStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl SessionStore for InMemorySessionStore {
+    async fn insert(&self, state: SessionState) -> Result<(), HttpServerError> {
+        let mut sessions = self.sessions.write().unwrap();
+        sessions.insert(state.session_id.to_string(), state);
+        Ok(())
+    }
+
+    async fn get(&self, session_id: &str) -> Result<Option<SessionState>, HttpServerError> {
+        let sessions = self.sessions.read().unwrap();
+        Ok(sessions.get(session_id).cloned())
+    }
+
+    async fn update(
+        &self,
+        session_id: &str,
+        ttl: Duration,
+        f: SessionUpdate<'_>,
+    ) -> Result<Option<SessionState>, HttpServerError> {
+        let mut sessions = self.sessions.write().unwrap();
+        let Some(state) = sessions.get_mut(session_id) else {
+            return Ok(None);
+        };
+        f(state);
+        state.touch();
+        state.expires_at = state.expires_at.max(state.last_activity + ttl);
+        Ok(Some(state.clone()))
+    }
+
+    async fn remove(&self, session_id: &str) -> Result<Option<SessionState>, HttpServerError> {
+        let mut sessions = self.sessions.write().unwrap();
+        Ok(sessions.remove(session_id))
+    }
+
+    async fn remove_expired(&self) -> Result<Vec<SessionState>, HttpServerError> {
+        let mut sessions = self.sessions.write().unwrap();
+        let expired: Vec<String> = sessions
+            .iter()
+            .filter(|(_, state)| state.has_expired())
+            .map(|(id, _)| id.clone())
+            .collect();
+        Ok(expired
+            .iter()
+            .filter_map(|id| sessions.remove(id))
+            .collect())
+    }
+
+    async fn count(&self) -> Result<usize, HttpServerError> {
+        Ok(self.sessions.read().unwrap().len())
+    }
+
+    async fn session_ids(&self) -> Result<Vec<String>, HttpServerError> {
+        Ok(self.sessions.read().unwrap().keys().cloned().collect())
+    }
+
+    async fn least_recently_active(&self) -> Result<Option<String>, HttpServerError> {
+        let sessions = self.sessions.read().unwrap();
+        Ok(sessions
+            .iter()
+            .min_by_key(|(_, state)| state.last_activity)
+            .map(|(id, _)| id.clone()))
+    }
+
+    async fn extend_ttl(
+        &self,
+        session_id: &str,
+        new_ttl: Duration,
+    ) -> Result<bool, HttpServerError> {
+        let mut sessions = self.sessions.write().unwrap();
+        let Some(state) = sessions.get_mut(session_id) else {
+            return Ok(false);
+        };
+        state.expires_at = Instant::now() + new_ttl;
+        Ok(true)
+    }
+}
@@ -20,6 +20,7 @@
 use std::collections::HashMap;
 use std::sync::Arc;
 
+use mcp_core::protocol::SessionData;
 use mcp_core::stdio::{deserialize_message, serialize_message, JsonRpcMessage};
 
 use super::error::HttpServerError;
@@ -51,6 +52,8 @@ pub struct LegacySseSession {
     /// Sender for SSE messages
     #[cfg(feature = "tokio")]
     pub tx: tokio::sync::mpsc::Sender<JsonRpcMessage>,
+    /// Storage exposed to handlers through `RequestContext::session_data`
+    pub session_data: Arc<SessionData>,
 }
 
 /// State manager for legacy SSE transport.
@@ -97,7 +100,11 @@ impl LegacySseState {
         let mut sessions = self.sessions.write().await;
         sessions.insert(
             session_id.clone(),
-            LegacySseSession { session_id, tx },
+            LegacySseSession {
+                session_id,
+                tx,
+                session_data: Arc::new(SessionData::default()),
+            },
         );
     }
 
@@ -118,6 +125,13 @@ impl LegacySseState {
         sessions.get(session_id).map(|s| s.tx.clone())
     }
 
+    /// Get the handler storage of a session.
+    #[cfg(feature = "tokio")]
+    pub async fn session_data(&self, session_id: &str) -> Option<Arc<SessionData>> {
+        let sessions = self.sessions.read().await;
+        sessions.get(session_id).map(|s| Arc::clone(&s.session_data))
+    }
+
     /// Send a message to a session.
     #[cfg(feature = "tokio")]
     pub async fn send_to_session(
@@ -170,6 +184,7 @@ pub mod axum_legacy {
     use axum::routing::{get, post};
     use axum::Router;
     use futures::stream::Stream;
+    use mcp_core::types::{ErrorObject, MessageId, ResultMessage};
     use tower_http::cors::{Any, CorsLayer};
 
     /// Query parameters for legacy SSE.
@@ -255,7 +270,12 @@ pub mod axum_legacy {
                 let result = state
                     .server
                     .server()
-                    .handle_request(request, Some(session_id.clone()))
+                    .handle_transport_request(
+                        "sse",
+                        request,
+                        Some(session_id.clone()),
+                        state.session_data(&session_id).await,
+                    )
                     .await;
 
                 match result {
@@ -276,8 +296,12 @@ pub mod axum_legacy {
                     .handle_notification(notification, Some(session_id.clone()))
                     .await;
             }
-            JsonRpcMessage::Result(_) => {
-                // Ignore unexpected result from client
+            JsonRpcMessage::Result(response) => {
+                // Responses to server-initiated requests; anything else is ignored
+                state
+                    .server
+                    .server()
+                    .handle_response(response, Some(session_id.clone()));
             }
         }
 
@@ -313,19 +337,12 @@ pub mod axum_legacy {
 
     /// Create a JSON error response.
     fn error_response(status: StatusCode, message: &str) -> Response {
-        let body = serde_json::json!({
-            "jsonrpc": "2.0",
-            "id": null,
-            "error": {
-                "code": -32000,
-                "message": message
-            }
-        });
+        let body = ResultMessage::failure(MessageId::Null, ErrorObject::new(-32000, message, None));
 
         Response::builder()
             .status(status)
             .header(header::CONTENT_TYPE, "application/json")
-            .body(Body::from(body.to_string()))
+            .body(Body::from(serde_json::to_string(&body).unwrap_or_default()))
             .unwrap()
     }
 }
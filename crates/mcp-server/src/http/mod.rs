@@ -23,11 +23,27 @@
 
 mod broadcast;
 #[cfg(feature = "axum")]
+mod cors_policy;
+#[cfg(feature = "axum")]
 mod dns_protection;
 mod error;
 mod handler;
+mod in_memory_session_store;
 mod legacy_sse;
+#[cfg(feature = "axum")]
+mod origin_validation;
+mod rate_limit;
+#[cfg(feature = "axum")]
+mod rate_limit_layer;
+#[cfg(feature = "redis")]
+mod redis_session_store;
+mod replay_error;
+mod session_close_listener;
+mod session_eviction_policy;
 mod session_manager;
+mod session_mode;
+mod session_stats;
+mod session_store;
 mod sse_writer;
 
 #[cfg(feature = "axum")]
@@ -36,17 +52,35 @@ pub mod axum_handler;
 pub use broadcast::{BufferedEvent, EventBuffer, EventBufferConfig};
 pub use error::HttpServerError;
 pub use handler::{HttpResponse, HttpServerHandler, HttpServerOptions, RequestHeaders};
+pub use in_memory_session_store::InMemorySessionStore;
 pub use legacy_sse::{LegacySseConfig, LegacySseState, generate_session_id};
+pub use rate_limit::{rate_limit_key, RateLimitConfig, RateLimiter};
+pub use replay_error::ReplayError;
+pub use session_close_listener::SessionCloseListener;
+pub use session_eviction_policy::SessionEvictionPolicy;
 pub use session_manager::{SessionConfig, SessionManager, SessionState};
+pub use session_mode::SessionMode;
+pub use session_stats::SessionStats;
+pub use session_store::{SessionStore, SessionUpdate};
 pub use sse_writer::{SseResponseBuilder, SseWriter};
 
 #[cfg(feature = "tokio")]
 pub use broadcast::async_broadcast::SseBroadcaster;
 
+#[cfg(feature = "axum")]
+pub use cors_policy::CorsPolicy;
 #[cfg(feature = "axum")]
 pub use dns_protection::{
-    host_header_validation, localhost_host_validation, DnsProtectionConfig, DnsProtectionLayer,
+    allowed_hosts_validation, host_header_validation, localhost_host_validation, DnsProtectionConfig, DnsProtectionLayer,
     DnsProtectionService,
 };
 #[cfg(feature = "axum")]
 pub use legacy_sse::create_legacy_sse_router;
+#[cfg(feature = "axum")]
+pub use origin_validation::{
+    origin_validation, OriginValidationConfig, OriginValidationLayer, OriginValidationService,
+};
+#[cfg(feature = "axum")]
+pub use rate_limit_layer::{RateLimitLayer, RateLimitService};
+#[cfg(feature = "redis")]
+pub use redis_session_store::{DEFAULT_SESSION_KEY_PREFIX, RedisSessionStore};
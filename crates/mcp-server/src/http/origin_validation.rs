@@ -0,0 +1,254 @@
+//! Origin header validation middleware.
+//!
+//! Browsers attach an `Origin` header to cross-origin requests, including
+//! `fetch` calls and `EventSource` connections. Validating it against an
+//! allowlist keeps arbitrary web pages from driving a local MCP server on
+//! the user's behalf (a CSRF-style attack that Host checks alone do not stop).
+//!
+//! Requests without an `Origin` header (non-browser clients) are passed through.
+//!
+//! ## Example
+//!
+//! ```ignore
+//! use mcp_server::http::origin_validation;
+//!
+//! // Exact origins and wildcard subdomains; default ports may be omitted
+//! let layer = origin_validation(vec!["https://app.example.com", "https://*.example.dev", "http://localhost:5173"]);
+//! ```
+
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use axum::body::Body;
+use axum::http::{header, Request, Response, StatusCode};
+use mcp_core::types::{ErrorObject, MessageId, ResultMessage};
+use tower::{Layer, Service};
+
+use super::dns_protection::{host_matches, split_host_port};
+
+/// JSON-RPC error response for rejected origins.
+fn json_rpc_error_response(message: &str) -> Response<Body> {
+    let body = ResultMessage::failure(MessageId::Null, ErrorObject::new(-32000, message, None));
+
+    Response::builder()
+        .status(StatusCode::FORBIDDEN)
+        .header(header::CONTENT_TYPE, "application/json")
+        .body(Body::from(serde_json::to_string(&body).unwrap_or_default()))
+        .unwrap()
+}
+
+/// An origin split into lowercase scheme, host and effective port.
+#[derive(Debug, PartialEq)]
+struct ParsedOrigin {
+    scheme: String,
+    host: String,
+    port: Option<u16>,
+}
+
+/// Parse `scheme://host[:port]`, as sent in the `Origin` header or written in
+/// the allowlist. Default ports for http/https/ws/wss are normalized away.
+fn parse_origin(value: &str) -> Option<ParsedOrigin> {
+    let (scheme, authority) = value.trim().split_once("://")?;
+    let authority = authority.strip_suffix('/').unwrap_or(authority);
+    if scheme.is_empty() || authority.contains(['/', '?', '#', '@']) {
+        return None;
+    }
+    let scheme = scheme.to_ascii_lowercase();
+    let (host, port) = split_host_port(authority)?;
+    let default_port = match scheme.as_str() {
+        "http" | "ws" => Some(80),
+        "https" | "wss" => Some(443),
+        _ => None,
+    };
+    let port = port.filter(|port| Some(*port) != default_port);
+    Some(ParsedOrigin { scheme, host, port })
+}
+
+/// Configuration for Origin header validation.
+#[derive(Debug, Clone, Default)]
+pub struct OriginValidationConfig {
+    /// Allowed origins: exact (`https://app.example.com`), with a port
+    /// (`http://localhost:5173`), wildcard subdomains
+    /// (`https://*.example.com`), or `null` for opaque origins.
+    pub allowed_origins: Vec<String>,
+}
+
+impl OriginValidationConfig {
+    /// Create a configuration allowing the given origins.
+    pub fn new<I, S>(origins: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self {
+            allowed_origins: origins.into_iter().map(|s| s.into()).collect(),
+        }
+    }
+
+    /// Check an `Origin` header value against the allowlist.
+    pub fn is_allowed(&self, origin: &str) -> bool {
+        let origin = origin.trim();
+        if origin == "null" {
+            return self
+                .allowed_origins
+                .iter()
+                .any(|entry| entry.trim() == "null");
+        }
+        let Some(origin) = parse_origin(origin) else {
+            return false;
+        };
+        self.allowed_origins.iter().any(|entry| {
+            parse_origin(entry).is_some_and(|allowed| {
+                allowed.scheme == origin.scheme
+                    && allowed.port == origin.port
+                    && host_matches(&allowed.host, &origin.host)
+            })
+        })
+    }
+}
+
+/// Layer for Origin header validation.
+///
+/// Rejects requests whose `Origin` header is not in the allowlist with
+/// 403 Forbidden and a JSON-RPC error body.
+#[derive(Debug, Clone)]
+pub struct OriginValidationLayer {
+    config: Arc<OriginValidationConfig>,
+}
+
+impl OriginValidationLayer {
+    /// Create a new Origin validation layer with the given configuration.
+    pub fn new(config: OriginValidationConfig) -> Self {
+        Self {
+            config: Arc::new(config),
+        }
+    }
+}
+
+impl<S> Layer<S> for OriginValidationLayer {
+    type Service = OriginValidationService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        OriginValidationService {
+            inner,
+            config: self.config.clone(),
+        }
+    }
+}
+
+/// Service for Origin header validation.
+#[derive(Debug, Clone)]
+pub struct OriginValidationService<S> {
+    inner: S,
+    config: Arc<OriginValidationConfig>,
+}
+
+impl<S, ReqBody> Service<Request<ReqBody>> for OriginValidationService<S>
+where
+    S: Service<Request<ReqBody>, Response = Response<Body>> + Clone + Send + 'static,
+    S::Future: Send,
+    ReqBody: Send + 'static,
+{
+    type Response = Response<Body>;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Request<ReqBody>) -> Self::Future {
+        let config = self.config.clone();
+        let mut inner = self.inner.clone();
+
+        Box::pin(async move {
+            // Non-browser clients do not send Origin
+            if let Some(origin) = req.headers().get(header::ORIGIN) {
+                let origin = match origin.to_str() {
+                    Ok(s) => s,
+                    Err(_) => {
+                        return Ok(json_rpc_error_response("Invalid Origin header encoding"));
+                    }
+                };
+                if !config.is_allowed(origin) {
+                    return Ok(json_rpc_error_response(&format!(
+                        "Invalid Origin: {}",
+                        origin
+                    )));
+                }
+            }
+
+            inner.call(req).await
+        })
+    }
+}
+
+/// Create an Origin validation layer from an allowlist.
+///
+/// # Example
+///
+/// ```ignore
+/// use mcp_server::http::origin_validation;
+///
+/// let layer = origin_validation(vec!["https://app.example.com"]);
+/// ```
+pub fn origin_validation<I, S>(origins: I) -> OriginValidationLayer
+where
+    I: IntoIterator<Item = S>,
+    S: Into<String>,
+{
+    OriginValidationLayer::new(OriginValidationConfig::new(origins))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_origin() {
+        assert_eq!(
+            parse_origin("HTTPS://App.Example.com:443"),
+            Some(ParsedOrigin {
+                scheme: "https".to_string(),
+                host: "app.example.com".to_string(),
+                port: None,
+            })
+        );
+        assert_eq!(
+            parse_origin("http://[::1]:8080/"),
+            Some(ParsedOrigin {
+                scheme: "http".to_string(),
+                host: "[::1]".to_string(),
+                port: Some(8080),
+            })
+        );
+        assert_eq!(parse_origin("example.com"), None);
+        assert_eq!(parse_origin("https://example.com/path"), None);
+        assert_eq!(parse_origin("https://user@example.com"), None);
+    }
+
+    #[test]
+    fn test_exact_origins() {
+        let config =
+            OriginValidationConfig::new(["https://app.example.com", "http://localhost:5173"]);
+        assert!(config.is_allowed("https://app.example.com"));
+        assert!(config.is_allowed("https://app.example.com:443"));
+        assert!(config.is_allowed("http://localhost:5173"));
+        assert!(!config.is_allowed("http://app.example.com"));
+        assert!(!config.is_allowed("https://app.example.com:8443"));
+        assert!(!config.is_allowed("http://localhost:3000"));
+        assert!(!config.is_allowed("https://evil.example"));
+        assert!(!config.is_allowed("null"));
+    }
+
+    #[test]
+    fn test_wildcard_and_null_origins() {
+        let config = OriginValidationConfig::new(["https://*.example.dev", "null"]);
+        assert!(config.is_allowed("https://preview.example.dev"));
+        assert!(!config.is_allowed("https://example.dev"));
+        assert!(!config.is_allowed("https://evilexample.dev"));
+        assert!(config.is_allowed("null"));
+    }
+}
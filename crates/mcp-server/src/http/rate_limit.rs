@@ -0,0 +1,252 @@
+//! Token-bucket rate limiting shared by the axum layer and
+//! [`HttpServerHandler`](super::HttpServerHandler).
+//!
+//! Each client gets a bucket holding up to `burst` requests, refilled at
+//! `requests_per_window` per `window`. Clients are told apart by session ID,
+//! falling back to the bearer token subject and then to the peer address.
+
+use std::collections::HashMap;
+use std::net::IpAddr;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use mcp_core::auth::AuthInfo;
+use mcp_core::types::{ErrorObject, MessageId, ResultMessage};
+
+/// Configuration for per-client rate limiting.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RateLimitConfig {
+    /// Requests regained per `window` (at least one).
+    pub requests_per_window: u32,
+    /// Length of the refill window.
+    pub window: Duration,
+    /// Requests a client may send back to back (at least one).
+    pub burst: u32,
+}
+
+impl RateLimitConfig {
+    /// Allow `requests_per_window` requests per `window`, all of which may
+    /// arrive at once.
+    pub fn new(requests_per_window: u32, window: Duration) -> Self {
+        Self {
+            requests_per_window,
+            window,
+            burst: requests_per_window,
+        }
+    }
+
+    /// Set how many requests may arrive back to back.
+    pub fn with_burst(mut self, burst: u32) -> Self {
+        self.burst = burst;
+        self
+    }
+
+    fn capacity(&self) -> f64 {
+        f64::from(self.burst.max(1))
+    }
+
+    /// Time to regain one request.
+    fn refill_interval(&self) -> Duration {
+        self.window / self.requests_per_window.max(1)
+    }
+
+    /// Time for an empty bucket to fill up again, after which it is
+    /// indistinguishable from a new one.
+    fn refill_time(&self) -> Duration {
+        self.refill_interval().mul_f64(self.capacity())
+    }
+}
+
+#[derive(Debug)]
+struct Bucket {
+    tokens: f64,
+    updated: Instant,
+}
+
+#[derive(Debug)]
+struct LimiterState {
+    buckets: HashMap<String, Bucket>,
+    last_prune: Instant,
+}
+
+/// Per-key token buckets.
+///
+/// Buckets that have filled up again are pruned every `window` while
+/// requests keep arriving, so idle clients don't accumulate; call
+/// [`prune`](Self::prune) to drop them on a timer as well.
+#[derive(Debug)]
+pub struct RateLimiter {
+    config: RateLimitConfig,
+    state: Mutex<LimiterState>,
+}
+
+impl RateLimiter {
+    /// Create a limiter with no clients yet.
+    pub fn new(config: RateLimitConfig) -> Self {
+        Self {
+            config,
+            state: Mutex::new(LimiterState {
+                buckets: HashMap::new(),
+                last_prune: Instant::now(),
+            }),
+        }
+    }
+
+    /// The limiter's configuration.
+    pub fn config(&self) -> &RateLimitConfig {
+        &self.config
+    }
+
+    /// Take one request from `key`'s bucket.
+    ///
+    /// Returns how long to wait before retrying when the bucket is empty.
+    pub fn check(&self, key: &str) -> Result<(), Duration> {
+        let now = Instant::now();
+        let mut state = self.state.lock().expect("rate limiter");
+        if now.duration_since(state.last_prune) >= self.config.window {
+            self.prune_locked(&mut state, now);
+        }
+
+        let capacity = self.config.capacity();
+        let interval = self.config.refill_interval().as_secs_f64();
+        let bucket = state.buckets.entry(key.to_string()).or_insert(Bucket {
+            tokens: capacity,
+            updated: now,
+        });
+        if interval > 0.0 {
+            let regained = now.duration_since(bucket.updated).as_secs_f64() / interval;
+            bucket.tokens = (bucket.tokens + regained).min(capacity);
+        } else {
+            bucket.tokens = capacity;
+        }
+        bucket.updated = now;
+
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            Ok(())
+        } else {
+            Err(Duration::from_secs_f64((1.0 - bucket.tokens) * interval))
+        }
+    }
+
+    /// Drop buckets that have filled up again, returning how many were
+    /// removed.
+    pub fn prune(&self) -> usize {
+        let mut state = self.state.lock().expect("rate limiter");
+        self.prune_locked(&mut state, Instant::now())
+    }
+
+    /// Number of clients currently tracked.
+    pub fn len(&self) -> usize {
+        self.state.lock().expect("rate limiter").buckets.len()
+    }
+
+    /// Whether no client is tracked.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn prune_locked(&self, state: &mut LimiterState, now: Instant) -> usize {
+        let refill_time = self.config.refill_time();
+        let before = state.buckets.len();
+        state
+            .buckets
+            .retain(|_, bucket| now.duration_since(bucket.updated) < refill_time);
+        state.last_prune = now;
+        before - state.buckets.len()
+    }
+}
+
+/// Key identifying a client: its session, else the subject of its bearer
+/// token, else its address. `None` when none of them is known, in which
+/// case the request is not limited.
+pub fn rate_limit_key(
+    session_id: Option<&str>,
+    auth_info: Option<&AuthInfo>,
+    client_ip: Option<IpAddr>,
+) -> Option<String> {
+    if let Some(session_id) = session_id {
+        return Some(format!("session:{session_id}"));
+    }
+    let subject = auth_info.and_then(|auth| {
+        auth.extra
+            .as_ref()
+            .and_then(|extra| extra.get("sub"))
+            .and_then(|sub| sub.as_str())
+            .or(auth.client_id.as_deref())
+    });
+    if let Some(subject) = subject {
+        return Some(format!("subject:{subject}"));
+    }
+    client_ip.map(|ip| format!("ip:{ip}"))
+}
+
+/// Whole seconds for a `Retry-After` header, rounded up.
+pub(crate) fn retry_after_secs(retry_after: Duration) -> u64 {
+    retry_after.as_secs_f64().ceil().max(1.0) as u64
+}
+
+/// JSON-RPC error body sent with 429 responses.
+pub(crate) fn rate_limited_body() -> String {
+    let error = ErrorObject::new(-32000, "Rate limit exceeded", None);
+    serde_json::to_string(&ResultMessage::failure(MessageId::Null, error)).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn buckets_refill_over_the_window() {
+        let limiter = RateLimiter::new(RateLimitConfig::new(2, Duration::from_millis(100)));
+        assert!(limiter.check("a").is_ok());
+        assert!(limiter.check("a").is_ok());
+        let retry = limiter.check("a").unwrap_err();
+        assert!(retry <= Duration::from_millis(50));
+
+        // Other clients have their own bucket
+        assert!(limiter.check("b").is_ok());
+
+        std::thread::sleep(Duration::from_millis(60));
+        assert!(limiter.check("a").is_ok());
+    }
+
+    #[test]
+    fn full_buckets_are_pruned() {
+        let limiter = RateLimiter::new(RateLimitConfig::new(10, Duration::from_millis(50)));
+        limiter.check("a").unwrap();
+        limiter.check("b").unwrap();
+        assert_eq!(limiter.len(), 2);
+
+        std::thread::sleep(Duration::from_millis(60));
+        // Pruned on the next check once a window has passed
+        limiter.check("c").unwrap();
+        assert_eq!(limiter.len(), 1);
+        assert_eq!(limiter.prune(), 0);
+    }
+
+    #[test]
+    fn keys_prefer_the_session_then_the_subject() {
+        let ip: IpAddr = "127.0.0.1".parse().unwrap();
+        let auth = AuthInfo {
+            token: "t".to_string(),
+            client_id: Some("client".to_string()),
+            scopes: Vec::new(),
+            expires_at: None,
+            extra: Some(serde_json::json!({ "sub": "alice" })),
+        };
+        assert_eq!(
+            rate_limit_key(Some("s1"), Some(&auth), Some(ip)).as_deref(),
+            Some("session:s1")
+        );
+        assert_eq!(
+            rate_limit_key(None, Some(&auth), Some(ip)).as_deref(),
+            Some("subject:alice")
+        );
+        assert_eq!(
+            rate_limit_key(None, None, Some(ip)).as_deref(),
+            Some("ip:127.0.0.1")
+        );
+        assert_eq!(rate_limit_key(None, None, None), None);
+    }
+}
@@ -0,0 +1,123 @@
+//! Rate limiting middleware for the axum router.
+//!
+//! Requests are keyed by `mcp-session-id`, falling back to the subject of
+//! the token verified by `BearerAuthLayer` (when that layer wraps this one)
+//! and then to the peer address from [`ConnectInfo`]. Serve the router with
+//! `into_make_service_with_connect_info::<SocketAddr>()` so sessionless
+//! requests such as `initialize` are limited too.
+//!
+//! ## Example
+//!
+//! ```ignore
+//! use std::time::Duration;
+//! use mcp_server::http::{RateLimitConfig, RateLimitLayer};
+//!
+//! // 60 requests a minute, at most 10 back to back
+//! let config = RateLimitConfig::new(60, Duration::from_secs(60)).with_burst(10);
+//! let router = router.layer(RateLimitLayer::new(config));
+//! ```
+
+use std::future::Future;
+use std::net::SocketAddr;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+use axum::body::Body;
+use axum::extract::ConnectInfo;
+use axum::http::{header, Request, Response, StatusCode};
+use tower::{Layer, Service};
+
+use mcp_core::auth::AuthInfo;
+
+use super::rate_limit::{rate_limit_key, rate_limited_body, retry_after_secs, RateLimitConfig, RateLimiter};
+
+/// 429 response with `Retry-After` and a JSON-RPC error body.
+fn too_many_requests(retry_after: Duration) -> Response<Body> {
+    Response::builder()
+        .status(StatusCode::TOO_MANY_REQUESTS)
+        .header(header::CONTENT_TYPE, "application/json")
+        .header(header::RETRY_AFTER, retry_after_secs(retry_after))
+        .body(Body::from(rate_limited_body()))
+        .unwrap()
+}
+
+/// Layer limiting how often each client may call the server.
+///
+/// Clones share one [`RateLimiter`].
+#[derive(Debug, Clone)]
+pub struct RateLimitLayer {
+    limiter: Arc<RateLimiter>,
+}
+
+impl RateLimitLayer {
+    /// Create a rate limiting layer with the given configuration.
+    pub fn new(config: RateLimitConfig) -> Self {
+        Self::with_limiter(Arc::new(RateLimiter::new(config)))
+    }
+
+    /// Create a layer sharing `limiter`, e.g. with a task pruning it.
+    pub fn with_limiter(limiter: Arc<RateLimiter>) -> Self {
+        Self { limiter }
+    }
+
+    /// The limiter shared by services of this layer.
+    pub fn limiter(&self) -> &Arc<RateLimiter> {
+        &self.limiter
+    }
+}
+
+impl<S> Layer<S> for RateLimitLayer {
+    type Service = RateLimitService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        RateLimitService {
+            inner,
+            limiter: self.limiter.clone(),
+        }
+    }
+}
+
+/// Service for rate limiting.
+#[derive(Debug, Clone)]
+pub struct RateLimitService<S> {
+    inner: S,
+    limiter: Arc<RateLimiter>,
+}
+
+impl<S, ReqBody> Service<Request<ReqBody>> for RateLimitService<S>
+where
+    S: Service<Request<ReqBody>, Response = Response<Body>> + Clone + Send + 'static,
+    S::Future: Send,
+    ReqBody: Send + 'static,
+{
+    type Response = Response<Body>;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Request<ReqBody>) -> Self::Future {
+        let key = rate_limit_key(
+            req.headers()
+                .get("mcp-session-id")
+                .and_then(|value| value.to_str().ok()),
+            req.extensions().get::<AuthInfo>(),
+            req.extensions()
+                .get::<ConnectInfo<SocketAddr>>()
+                .map(|ConnectInfo(addr)| addr.ip()),
+        );
+        let checked = key.map_or(Ok(()), |key| self.limiter.check(&key));
+        let mut inner = self.inner.clone();
+
+        Box::pin(async move {
+            if let Err(retry_after) = checked {
+                return Ok(too_many_requests(retry_after));
+            }
+            inner.call(req).await
+        })
+    }
+}
@@ -0,0 +1,598 @@
+//! Session store shared between server instances through Redis.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use async_trait::async_trait;
+use mcp_core::http::SessionId;
+use mcp_core::protocol::SessionData;
+use redis::aio::MultiplexedConnection;
+use redis::{AsyncCommands, RedisError, Script};
+
+use super::error::HttpServerError;
+use super::session_manager::SessionState;
+use super::session_store::{SessionStore, SessionUpdate};
+
+/// Default prefix of the keys holding sessions.
+pub const DEFAULT_SESSION_KEY_PREFIX: &str = "mcp:session:";
+
+/// Writes the fields and TTL of a session only if its hash exists.
+///
+/// `KEYS` are the hash and the activity and expiry indexes. `ARGV[1]` is
+/// the TTL in milliseconds, `ARGV[2]` the session ID, `ARGV[3..4]` its
+/// index scores and the rest field/value pairs. Returns 1 when written, 0
+/// when the session is gone.
+const UPDATE_SCRIPT: &str = r"
+if redis.call('EXISTS', KEYS[1]) == 0 then
+    return 0
+end
+redis.call('HSET', KEYS[1], unpack(ARGV, 5))
+redis.call('PEXPIRE', KEYS[1], ARGV[1])
+redis.call('ZADD', KEYS[2], ARGV[3], ARGV[2])
+redis.call('ZADD', KEYS[3], ARGV[4], ARGV[2])
+return 1
+";
+
+/// Moves the expiry of an existing session.
+///
+/// `KEYS` are the hash and the expiry index, `ARGV` the TTL in
+/// milliseconds, the session ID and its new expiry. Returns whether the
+/// session exists.
+const EXTEND_TTL_SCRIPT: &str = r"
+if redis.call('PEXPIRE', KEYS[1], ARGV[1]) == 0 then
+    return 0
+end
+redis.call('ZADD', KEYS[2], ARGV[3], ARGV[2])
+return 1
+";
+
+/// Drops the sessions that expired by `ARGV[1]` from the activity
+/// (`KEYS[1]`) and expiry (`KEYS[2]`) indexes and returns how many remain.
+const PRUNE_SCRIPT: &str = r"
+local expired = redis.call('ZRANGEBYSCORE', KEYS[2], '-inf', ARGV[1])
+for _, session_id in ipairs(expired) do
+    redis.call('ZREM', KEYS[1], session_id)
+end
+redis.call('ZREMRANGEBYSCORE', KEYS[2], '-inf', ARGV[1])
+return redis.call('ZCARD', KEYS[1])
+";
+
+/// Keeps sessions in Redis so several server instances share them.
+///
+/// Each session is a hash under `{prefix}{session_id}` whose key TTL is the
+/// session's remaining lifetime, so Redis drops expired sessions itself.
+/// Timestamps are stored as Unix milliseconds and `data` as JSON.
+///
+/// Two sorted sets index the sessions by ID: `{prefix}~by-activity`, scored
+/// by last activity, picks the session to evict and is counted for the
+/// session limit, and `{prefix}~by-expiry` lets expired sessions be
+/// dropped from it without scanning the keyspace.
+///
+/// `session_data` cannot be serialized and stays local to the instance
+/// that handled the request. Updates read, change and write back the hash,
+/// so concurrent updates of one session from different instances keep the
+/// last write. The write back only happens while the hash still exists, so
+/// an update never brings back a session deleted or expired meanwhile.
+pub struct RedisSessionStore {
+    connection: MultiplexedConnection,
+    prefix: String,
+    /// Handler storage of the sessions this instance has seen.
+    session_data: Mutex<HashMap<String, Arc<SessionData>>>,
+}
+
+impl RedisSessionStore {
+    /// Connect to the Redis server at `url`, e.g. `redis://127.0.0.1/`.
+    pub async fn open(url: &str) -> Result<Self, HttpServerError> {
+        let client = redis::Client::open(url).map_err(store_error)?;
+        let connection = client
+            .get_multiplexed_async_connection()
+            .await
+            .map_err(store_error)?;
+        Ok(Self::new(connection))
+    }
+
+    /// Use an existing connection, with the default key prefix.
+    pub fn new(connection: MultiplexedConnection) -> Self {
+        Self {
+            connection,
+            prefix: DEFAULT_SESSION_KEY_PREFIX.to_string(),
+            session_data: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Keep sessions under `prefix` instead of `mcp:session:`.
+    pub fn with_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.prefix = prefix.into();
+        self
+    }
+
+    fn key(&self, session_id: &str) -> String {
+        format!("{}{}", self.prefix, session_id)
+    }
+
+    fn activity_key(&self) -> String {
+        format!("{}~by-activity", self.prefix)
+    }
+
+    fn expiry_key(&self) -> String {
+        format!("{}~by-expiry", self.prefix)
+    }
+
+    fn local_data(&self, session_id: &str) -> Arc<SessionData> {
+        let mut session_data = self.session_data.lock().unwrap();
+        Arc::clone(session_data.entry(session_id.to_string()).or_default())
+    }
+
+    fn forget(&self, session_id: &str) -> Option<Arc<SessionData>> {
+        self.session_data.lock().unwrap().remove(session_id)
+    }
+
+    async fn read(&self, session_id: &str) -> Result<Option<SessionState>, HttpServerError> {
+        let key = self.key(session_id);
+        let (fields, ttl): (HashMap<String, String>, i64) = redis::pipe()
+            .hgetall(&key)
+            .pttl(&key)
+            .query_async(&mut self.connection.clone())
+            .await
+            .map_err(store_error)?;
+        if fields.is_empty() {
+            self.forget(session_id);
+            return Ok(None);
+        }
+        self.decode(session_id, &fields, ttl).map(Some)
+    }
+
+    async fn write(&self, state: &SessionState) -> Result<(), HttpServerError> {
+        let session_id = state.session_id.as_str();
+        let key = self.key(session_id);
+        let fields = encode(state)?;
+        let ttl = remaining_millis(state);
+        redis::pipe()
+            .atomic()
+            .hset_multiple(&key, &fields)
+            .ignore()
+            .pexpire(&key, ttl)
+            .ignore()
+            .zadd(
+                self.activity_key(),
+                session_id,
+                unix_millis(state.last_activity),
+            )
+            .ignore()
+            .zadd(self.expiry_key(), session_id, expiry_millis(ttl))
+            .ignore()
+            .query_async::<()>(&mut self.connection.clone())
+            .await
+            .map_err(store_error)
+    }
+
+    /// Write back a session read earlier, unless it has been removed or has
+    /// expired since. Returns whether it was written.
+    async fn write_existing(&self, state: &SessionState) -> Result<bool, HttpServerError> {
+        let script = Script::new(UPDATE_SCRIPT);
+        let mut invocation = script.prepare_invoke();
+        let session_id = state.session_id.as_str();
+        let ttl = remaining_millis(state);
+        invocation
+            .key(self.key(session_id))
+            .key(self.activity_key())
+            .key(self.expiry_key())
+            .arg(ttl)
+            .arg(session_id)
+            .arg(unix_millis(state.last_activity))
+            .arg(expiry_millis(ttl));
+        for (field, value) in encode(state)? {
+            invocation.arg(field).arg(value);
+        }
+        invocation
+            .invoke_async(&mut self.connection.clone())
+            .await
+            .map_err(store_error)
+    }
+
+    /// Rebuild a session from its hash. A hash without a TTL counts as
+    /// expired.
+    fn decode(
+        &self,
+        session_id: &str,
+        fields: &HashMap<String, String>,
+        ttl: i64,
+    ) -> Result<SessionState, HttpServerError> {
+        let field = |name: &str| {
+            fields.get(name).ok_or_else(|| {
+                HttpServerError::SessionStore(format!("session {session_id} has no {name}"))
+            })
+        };
+        let number = |name: &str| {
+            field(name)?.parse::<u64>().map_err(|_| {
+                HttpServerError::SessionStore(format!(
+                    "session {session_id} has a malformed {name}"
+                ))
+            })
+        };
+        let now = Instant::now();
+        Ok(SessionState {
+            session_id: SessionId::from_string(session_id),
+            created_at: instant_at(number("created_at")?),
+            last_activity: instant_at(number("last_activity")?),
+            expires_at: now + Duration::from_millis(ttl.max(0) as u64),
+            initialized: field("initialized")? == "true",
+            event_counter: number("event_counter")?,
+            data: serde_json::from_str(field("data")?)?,
+            session_data: self.local_data(session_id),
+        })
+    }
+
+    /// Drop expired sessions from the indexes and count the rest.
+    async fn prune(&self) -> Result<usize, HttpServerError> {
+        Script::new(PRUNE_SCRIPT)
+            .key(self.activity_key())
+            .key(self.expiry_key())
+            .arg(unix_millis(Instant::now()))
+            .invoke_async(&mut self.connection.clone())
+            .await
+            .map_err(store_error)
+    }
+}
+
+#[async_trait]
+impl SessionStore for RedisSessionStore {
+    async fn insert(&self, state: SessionState) -> Result<(), HttpServerError> {
+        self.write(&state).await?;
+        self.session_data
+            .lock()
+            .unwrap()
+            .insert(state.session_id.to_string(), state.session_data);
+        Ok(())
+    }
+
+    async fn get(&self, session_id: &str) -> Result<Option<SessionState>, HttpServerError> {
+        self.read(session_id).await
+    }
+
+    async fn update(
+        &self,
+        session_id: &str,
+        ttl: Duration,
+        f: SessionUpdate<'_>,
+    ) -> Result<Option<SessionState>, HttpServerError> {
+        let Some(mut state) = self.read(session_id).await? else {
+            return Ok(None);
+        };
+        f(&mut state);
+        state.touch();
+        state.expires_at = state.expires_at.max(state.last_activity + ttl);
+        if !self.write_existing(&state).await? {
+            self.forget(session_id);
+            return Ok(None);
+        }
+        Ok(Some(state))
+    }
+
+    async fn remove(&self, session_id: &str) -> Result<Option<SessionState>, HttpServerError> {
+        let state = self.read(session_id).await?;
+        redis::pipe()
+            .atomic()
+            .del(self.key(session_id))
+            .ignore()
+            .zrem(self.activity_key(), session_id)
+            .ignore()
+            .zrem(self.expiry_key(), session_id)
+            .ignore()
+            .query_async::<()>(&mut self.connection.clone())
+            .await
+            .map_err(store_error)?;
+        self.forget(session_id);
+        Ok(state)
+    }
+
+    /// Redis drops expired hashes itself. This reports the sessions this
+    /// instance has seen whose hash is gone; only their ID and
+    /// `session_data` are known.
+    async fn remove_expired(&self) -> Result<Vec<SessionState>, HttpServerError> {
+        let known: Vec<String> = self.session_data.lock().unwrap().keys().cloned().collect();
+        if known.is_empty() {
+            return Ok(Vec::new());
+        }
+        let mut pipe = redis::pipe();
+        for session_id in &known {
+            pipe.exists(self.key(session_id));
+        }
+        let exists: Vec<bool> = pipe
+            .query_async(&mut self.connection.clone())
+            .await
+            .map_err(store_error)?;
+
+        let now = Instant::now();
+        let expired = known
+            .into_iter()
+            .zip(exists)
+            .filter(|(_, exists)| !exists)
+            .filter_map(|(session_id, _)| {
+                let session_data = self.forget(&session_id)?;
+                Some(SessionState {
+                    session_id: SessionId::from_string(session_id),
+                    created_at: now,
+                    last_activity: now,
+                    expires_at: now,
+                    initialized: false,
+                    event_counter: 0,
+                    data: HashMap::new(),
+                    session_data,
+                })
+            })
+            .collect();
+        Ok(expired)
+    }
+
+    async fn count(&self) -> Result<usize, HttpServerError> {
+        self.prune().await
+    }
+
+    async fn session_ids(&self) -> Result<Vec<String>, HttpServerError> {
+        self.prune().await?;
+        let mut connection = self.connection.clone();
+        connection
+            .zrange(self.activity_key(), 0, -1)
+            .await
+            .map_err(store_error)
+    }
+
+    async fn least_recently_active(&self) -> Result<Option<String>, HttpServerError> {
+        self.prune().await?;
+        let mut connection = self.connection.clone();
+        let oldest: Vec<String> = connection
+            .zrange(self.activity_key(), 0, 0)
+            .await
+            .map_err(store_error)?;
+        Ok(oldest.into_iter().next())
+    }
+
+    async fn extend_ttl(
+        &self,
+        session_id: &str,
+        new_ttl: Duration,
+    ) -> Result<bool, HttpServerError> {
+        let ttl = millis(new_ttl).max(1);
+        Script::new(EXTEND_TTL_SCRIPT)
+            .key(self.key(session_id))
+            .key(self.expiry_key())
+            .arg(ttl)
+            .arg(session_id)
+            .arg(expiry_millis(ttl))
+            .invoke_async(&mut self.connection.clone())
+            .await
+            .map_err(store_error)
+    }
+}
+
+fn store_error(error: RedisError) -> HttpServerError {
+    HttpServerError::SessionStore(error.to_string())
+}
+
+/// The hash fields a session is stored as.
+fn encode(state: &SessionState) -> Result<[(&'static str, String); 5], HttpServerError> {
+    Ok([
+        ("created_at", unix_millis(state.created_at).to_string()),
+        (
+            "last_activity",
+            unix_millis(state.last_activity).to_string(),
+        ),
+        ("initialized", state.initialized.to_string()),
+        ("event_counter", state.event_counter.to_string()),
+        ("data", serde_json::to_string(&state.data)?),
+    ])
+}
+
+/// Time left until the session expires, as a key TTL.
+fn remaining_millis(state: &SessionState) -> i64 {
+    millis(state.expires_at.saturating_duration_since(Instant::now())).max(1)
+}
+
+/// Unix milliseconds at which a key with `ttl` milliseconds left expires.
+fn expiry_millis(ttl: i64) -> u64 {
+    unix_millis(Instant::now()).saturating_add(ttl as u64)
+}
+
+fn millis(duration: Duration) -> i64 {
+    i64::try_from(duration.as_millis()).unwrap_or(i64::MAX)
+}
+
+/// Wall-clock time of `instant` in Unix milliseconds.
+fn unix_millis(instant: Instant) -> u64 {
+    let time = SystemTime::now() - instant.elapsed();
+    time.duration_since(UNIX_EPOCH)
+        .map_or(0, |since| since.as_millis() as u64)
+}
+
+/// The instant at `millis` Unix milliseconds, or now if that is in the
+/// future or before this process could represent it.
+fn instant_at(millis: u64) -> Instant {
+    let now = Instant::now();
+    let age = unix_millis(now).saturating_sub(millis);
+    now.checked_sub(Duration::from_millis(age)).unwrap_or(now)
+}
+
+/// These need a Redis server, at `MCP_TEST_REDIS_URL` or
+/// `redis://127.0.0.1/`; run them with `cargo test --features redis --
+/// --ignored`. Each test keeps its sessions under a fresh prefix.
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::http::{SessionConfig, SessionEvictionPolicy, SessionManager};
+
+    async fn store() -> RedisSessionStore {
+        let url = std::env::var("MCP_TEST_REDIS_URL")
+            .unwrap_or_else(|_| "redis://127.0.0.1/".to_string());
+        RedisSessionStore::open(&url)
+            .await
+            .expect("Redis server")
+            .with_prefix(format!("mcp-test:{}:", SessionId::new()))
+    }
+
+    fn session(ttl: Duration) -> SessionState {
+        let now = Instant::now();
+        SessionState {
+            session_id: SessionId::new(),
+            created_at: now,
+            last_activity: now,
+            expires_at: now + ttl,
+            initialized: false,
+            event_counter: 0,
+            data: HashMap::new(),
+            session_data: Arc::default(),
+        }
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a Redis server"]
+    async fn sessions_round_trip_through_redis() {
+        let store = store().await;
+        let mut state = session(Duration::from_secs(60));
+        state.initialized = true;
+        state.event_counter = 3;
+        state.data.insert("user".to_string(), json!({ "id": 7 }));
+        let session_id = state.session_id.to_string();
+        store.insert(state.clone()).await.unwrap();
+
+        let stored = store.get(&session_id).await.unwrap().expect("session");
+        assert_eq!(stored.session_id, state.session_id);
+        assert!(stored.initialized);
+        assert_eq!(stored.event_counter, 3);
+        assert_eq!(stored.data, state.data);
+        assert!(Arc::ptr_eq(&stored.session_data, &state.session_data));
+        assert_eq!(store.count().await.unwrap(), 1);
+        assert_eq!(store.session_ids().await.unwrap(), vec![session_id]);
+        assert!(store.get("unknown").await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a Redis server"]
+    async fn update_changes_the_stored_session() {
+        let store = store().await;
+        let state = session(Duration::from_secs(60));
+        let session_id = state.session_id.to_string();
+        store.insert(state).await.unwrap();
+
+        let updated = store
+            .update(
+                &session_id,
+                Duration::from_secs(120),
+                Box::new(|state| state.initialized = true),
+            )
+            .await
+            .unwrap()
+            .expect("session");
+        assert!(updated.initialized);
+        assert!(store.get(&session_id).await.unwrap().unwrap().initialized);
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a Redis server"]
+    async fn update_does_not_bring_back_a_removed_session() {
+        let store = store().await;
+        let state = session(Duration::from_secs(60));
+        let session_id = state.session_id.to_string();
+        store.insert(state).await.unwrap();
+        assert!(store.remove(&session_id).await.unwrap().is_some());
+
+        let updated = store
+            .update(&session_id, Duration::from_secs(60), Box::new(|_| {}))
+            .await
+            .unwrap();
+        assert!(updated.is_none());
+        assert!(store.get(&session_id).await.unwrap().is_none());
+        assert_eq!(store.count().await.unwrap(), 0);
+        assert!(store.remove(&session_id).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a Redis server"]
+    async fn expired_sessions_leave_the_store() {
+        let store = store().await;
+        let state = session(Duration::from_millis(50));
+        let session_id = state.session_id.to_string();
+        store.insert(state).await.unwrap();
+        assert_eq!(store.count().await.unwrap(), 1);
+
+        tokio::time::sleep(Duration::from_millis(150)).await;
+        assert!(store.get(&session_id).await.unwrap().is_none());
+        assert_eq!(store.count().await.unwrap(), 0);
+        assert!(store.least_recently_active().await.unwrap().is_none());
+        assert!(
+            !store
+                .extend_ttl(&session_id, Duration::from_secs(60))
+                .await
+                .unwrap()
+        );
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a Redis server"]
+    async fn extend_ttl_keeps_a_session_alive() {
+        let store = store().await;
+        let state = session(Duration::from_millis(50));
+        let session_id = state.session_id.to_string();
+        store.insert(state).await.unwrap();
+
+        assert!(
+            store
+                .extend_ttl(&session_id, Duration::from_secs(60))
+                .await
+                .unwrap()
+        );
+        tokio::time::sleep(Duration::from_millis(150)).await;
+        assert!(store.get(&session_id).await.unwrap().is_some());
+        assert_eq!(store.count().await.unwrap(), 1);
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a Redis server"]
+    async fn the_least_recently_active_session_is_evicted() {
+        let config = SessionConfig {
+            max_sessions: 2,
+            eviction_policy: SessionEvictionPolicy::EvictLru,
+            ..Default::default()
+        };
+        let manager = SessionManager::with_store(config, store().await);
+        let first = manager
+            .create_session()
+            .await
+            .unwrap()
+            .session_id
+            .to_string();
+        tokio::time::sleep(Duration::from_millis(5)).await;
+        let second = manager
+            .create_session()
+            .await
+            .unwrap()
+            .session_id
+            .to_string();
+        tokio::time::sleep(Duration::from_millis(5)).await;
+        manager
+            .store()
+            .update(&first, Duration::from_secs(60), Box::new(|_| {}))
+            .await
+            .unwrap();
+        assert_eq!(
+            manager.store().least_recently_active().await.unwrap(),
+            Some(second.clone())
+        );
+
+        let third = manager
+            .create_session()
+            .await
+            .unwrap()
+            .session_id
+            .to_string();
+        let mut remaining = manager.store().session_ids().await.unwrap();
+        remaining.sort();
+        let mut expected = vec![first, third];
+        expected.sort();
+        assert_eq!(remaining, expected);
+        assert!(manager.store().get(&second).await.unwrap().is_none());
+    }
+}
@@ -0,0 +1,14 @@
+//! Why a replay from a Last-Event-ID is impossible.
+
+/// Why the events after a Last-Event-ID cannot be replayed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+pub enum ReplayError {
+    /// Some events after the ID were evicted, by count, size or age, so the
+    /// client is further behind than the buffer reaches.
+    #[error("events after the requested ID are no longer buffered")]
+    TooOld,
+
+    /// The ID was not issued by this buffer.
+    #[error("unknown event ID")]
+    UnknownId,
+}
@@ -0,0 +1,12 @@
+//! Callback for sessions closed by the session manager itself.
+
+use std::sync::Arc;
+
+use super::session_manager::SessionState;
+
+/// Called with each session the manager evicts or expires, after it has
+/// been removed, so transports can tear down the session's streams.
+///
+/// Sessions removed explicitly through `SessionManager::remove_session`
+/// are not reported.
+pub type SessionCloseListener = Arc<dyn Fn(&SessionState) + Send + Sync>;
@@ -0,0 +1,11 @@
+//! What the session manager does when `max_sessions` is reached.
+
+/// Policy applied when a new session would exceed `SessionConfig::max_sessions`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SessionEvictionPolicy {
+    /// Reject the new session with `SessionLimitReached`.
+    #[default]
+    RejectNew,
+    /// Close the least-recently-active session to make room.
+    EvictLru,
+}
@@ -1,12 +1,21 @@
 //! Session management for HTTP server.
 
 use std::collections::HashMap;
+use std::fmt;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
 use std::sync::{Arc, RwLock};
 use std::time::{Duration, Instant};
 
+use futures::lock::Mutex;
 use mcp_core::http::{ResumptionToken, SessionId};
+use mcp_core::protocol::SessionData;
 
 use super::error::HttpServerError;
+use super::in_memory_session_store::InMemorySessionStore;
+use super::session_close_listener::SessionCloseListener;
+use super::session_eviction_policy::SessionEvictionPolicy;
+use super::session_stats::SessionStats;
+use super::session_store::SessionStore;
 
 /// Configuration for session management.
 #[derive(Debug, Clone)]
@@ -17,6 +26,8 @@ pub struct SessionConfig {
     pub session_timeout: Duration,
     /// How often to clean up expired sessions.
     pub cleanup_interval: Duration,
+    /// What to do when `max_sessions` is reached.
+    pub eviction_policy: SessionEvictionPolicy,
 }
 
 impl Default for SessionConfig {
@@ -25,6 +36,7 @@ impl Default for SessionConfig {
             max_sessions: 1000,
             session_timeout: Duration::from_secs(30 * 60), // 30 minutes
             cleanup_interval: Duration::from_secs(60),      // 1 minute
+            eviction_policy: SessionEvictionPolicy::default(),
         }
     }
 }
@@ -38,33 +50,47 @@ pub struct SessionState {
     pub created_at: Instant,
     /// When the session was last active.
     pub last_activity: Instant,
+    /// When the session expires unless it is active again or its TTL is
+    /// extended.
+    pub expires_at: Instant,
     /// Whether the session has been initialized.
     pub initialized: bool,
     /// Counter for SSE event IDs.
     pub event_counter: u64,
     /// Custom data associated with the session.
     pub data: HashMap<String, serde_json::Value>,
+    /// Storage exposed to handlers through `RequestContext::session_data`.
+    ///
+    /// Shared by all clones of this state and dropped with the session.
+    pub session_data: Arc<SessionData>,
 }
 
 impl SessionState {
-    /// Create a new session state.
-    fn new(session_id: SessionId) -> Self {
+    /// Create a new session state that expires after `ttl` without activity.
+    fn new(session_id: SessionId, ttl: Duration) -> Self {
         let now = Instant::now();
         Self {
             session_id,
             created_at: now,
             last_activity: now,
+            expires_at: now + ttl,
             initialized: false,
             event_counter: 0,
             data: HashMap::new(),
+            session_data: Arc::new(SessionData::default()),
         }
     }
 
-    /// Check if the session has expired.
+    /// Check if the session has been idle for longer than `timeout`.
     pub fn is_expired(&self, timeout: Duration) -> bool {
         self.last_activity.elapsed() > timeout
     }
 
+    /// Check if the session is past its `expires_at`.
+    pub fn has_expired(&self) -> bool {
+        Instant::now() >= self.expires_at
+    }
+
     /// Update the last activity timestamp.
     pub fn touch(&mut self) {
         self.last_activity = Instant::now();
@@ -83,144 +109,234 @@ impl SessionState {
 }
 
 /// Thread-safe session manager.
-#[derive(Debug)]
-pub struct SessionManager {
+///
+/// Sessions live in a [`SessionStore`], in this process by default. With a
+/// shared store such as `RedisSessionStore`, several server instances see
+/// the same sessions; the counters in [`stats`](Self::stats) and the close
+/// listener stay per instance.
+///
+/// Store failures make the `Result` methods fail. The others log them and
+/// answer as if the session did not exist.
+pub struct SessionManager<S = InMemorySessionStore> {
     config: SessionConfig,
-    sessions: Arc<RwLock<HashMap<String, SessionState>>>,
+    store: S,
+    /// Serializes the limit check and insert of `create_session`.
+    creating: Mutex<()>,
+    evictions: AtomicU64,
+    expirations: AtomicU64,
+    peak: AtomicUsize,
+    close_listener: RwLock<Option<SessionCloseListener>>,
 }
 
 impl SessionManager {
-    /// Create a new session manager.
+    /// Create a new session manager keeping sessions in memory.
     pub fn new(config: SessionConfig) -> Self {
+        Self::with_store(config, InMemorySessionStore::new())
+    }
+}
+
+impl<S: SessionStore> SessionManager<S> {
+    /// Create a new session manager keeping sessions in `store`.
+    pub fn with_store(config: SessionConfig, store: S) -> Self {
         Self {
             config,
-            sessions: Arc::new(RwLock::new(HashMap::new())),
+            store,
+            creating: Mutex::new(()),
+            evictions: AtomicU64::new(0),
+            expirations: AtomicU64::new(0),
+            peak: AtomicUsize::new(0),
+            close_listener: RwLock::new(None),
+        }
+    }
+
+    /// Get the session store.
+    pub fn store(&self) -> &S {
+        &self.store
+    }
+
+    /// Install a listener for sessions the manager evicts or expires.
+    pub fn set_close_listener(&self, listener: Option<SessionCloseListener>) {
+        *self.close_listener.write().unwrap() = listener;
+    }
+
+    /// Report sessions dropped by the manager.
+    fn notify_closed(&self, closed: &[SessionState]) {
+        if closed.is_empty() {
+            return;
+        }
+        let listener = self.close_listener.read().unwrap().clone();
+        if let Some(listener) = listener {
+            for state in closed {
+                listener(state);
+            }
         }
     }
 
     /// Create a new session.
-    pub fn create_session(&self) -> Result<SessionState, HttpServerError> {
-        let mut sessions = self.sessions.write().unwrap();
+    ///
+    /// When `max_sessions` is reached, the session either is rejected or
+    /// replaces the least-recently-active one, depending on
+    /// [`SessionConfig::eviction_policy`].
+    pub async fn create_session(&self) -> Result<SessionState, HttpServerError> {
+        let mut evicted = Vec::new();
+        let created = self.insert_session(&mut evicted).await;
+        self.notify_closed(&evicted);
+        created
+    }
+
+    async fn insert_session(
+        &self,
+        evicted: &mut Vec<SessionState>,
+    ) -> Result<SessionState, HttpServerError> {
+        let _creating = self.creating.lock().await;
 
         // Check session limit
-        if sessions.len() >= self.config.max_sessions {
-            return Err(HttpServerError::SessionLimitReached {
-                max: self.config.max_sessions,
-            });
+        while self.store.count().await? >= self.config.max_sessions {
+            let lru = match self.config.eviction_policy {
+                SessionEvictionPolicy::RejectNew => None,
+                SessionEvictionPolicy::EvictLru => self.store.least_recently_active().await?,
+            };
+            let Some(lru) = lru else {
+                return Err(HttpServerError::SessionLimitReached {
+                    max: self.config.max_sessions,
+                });
+            };
+            if let Some(state) = self.store.remove(&lru).await? {
+                evicted.push(state);
+                self.evictions.fetch_add(1, Ordering::Relaxed);
+            }
         }
 
-        let session_id = SessionId::new();
-        let state = SessionState::new(session_id.clone());
-        sessions.insert(session_id.to_string(), state.clone());
-
+        let state = SessionState::new(SessionId::new(), self.config.session_timeout);
+        self.store.insert(state.clone()).await?;
+        let count = self.store.count().await?;
+        self.peak.fetch_max(count, Ordering::Relaxed);
         Ok(state)
     }
 
     /// Get a session by ID.
-    pub fn get_session(&self, session_id: &str) -> Option<SessionState> {
-        let sessions = self.sessions.read().unwrap();
-        sessions.get(session_id).cloned()
+    pub async fn get_session(&self, session_id: &str) -> Option<SessionState> {
+        logged(self.store.get(session_id).await).flatten()
     }
 
     /// Get a session by ID, updating its last activity timestamp.
-    pub fn touch_session(&self, session_id: &str) -> Option<SessionState> {
-        let mut sessions = self.sessions.write().unwrap();
-        if let Some(state) = sessions.get_mut(session_id) {
-            state.touch();
-            Some(state.clone())
-        } else {
-            None
+    ///
+    /// An expired session is removed instead and `None` is returned.
+    pub async fn touch_session(&self, session_id: &str) -> Option<SessionState> {
+        let state = self.get_session(session_id).await?;
+        if !state.has_expired() {
+            return self.update_session(session_id, |_| {}).await;
+        }
+        self.expire_session(session_id).await;
+        None
+    }
+
+    /// Remove an expired session, counting it and reporting it to the
+    /// close listener.
+    async fn expire_session(&self, session_id: &str) {
+        if let Some(state) = self.remove_session(session_id).await {
+            self.expirations.fetch_add(1, Ordering::Relaxed);
+            self.notify_closed(&[state]);
         }
     }
 
     /// Update a session's state.
-    pub fn update_session<F>(&self, session_id: &str, f: F) -> Option<SessionState>
+    pub async fn update_session<F>(&self, session_id: &str, f: F) -> Option<SessionState>
     where
-        F: FnOnce(&mut SessionState),
+        F: FnOnce(&mut SessionState) + Send,
     {
-        let mut sessions = self.sessions.write().unwrap();
-        if let Some(state) = sessions.get_mut(session_id) {
-            f(state);
-            state.touch();
-            Some(state.clone())
-        } else {
-            None
-        }
+        let ttl = self.config.session_timeout;
+        logged(self.store.update(session_id, ttl, Box::new(f)).await).flatten()
     }
 
     /// Mark a session as initialized.
-    pub fn mark_initialized(&self, session_id: &str) -> Option<SessionState> {
+    pub async fn mark_initialized(&self, session_id: &str) -> Option<SessionState> {
         self.update_session(session_id, |state| {
             state.initialized = true;
         })
+        .await
+    }
+
+    /// Keep a session for `new_ttl` from now, e.g. while a long-running
+    /// stream is open. Returns `false` for an unknown session.
+    pub async fn extend_ttl(
+        &self,
+        session_id: &str,
+        new_ttl: Duration,
+    ) -> Result<bool, HttpServerError> {
+        self.store.extend_ttl(session_id, new_ttl).await
     }
 
     /// Remove a session.
-    pub fn remove_session(&self, session_id: &str) -> Option<SessionState> {
-        let mut sessions = self.sessions.write().unwrap();
-        sessions.remove(session_id)
+    pub async fn remove_session(&self, session_id: &str) -> Option<SessionState> {
+        logged(self.store.remove(session_id).await).flatten()
     }
 
     /// Validate a session ID and return the session if valid.
-    pub fn validate_session(&self, session_id: &str) -> Result<SessionState, HttpServerError> {
-        let sessions = self.sessions.read().unwrap();
-
-        let state = sessions
+    pub async fn validate_session(
+        &self,
+        session_id: &str,
+    ) -> Result<SessionState, HttpServerError> {
+        let state = self
+            .store
             .get(session_id)
+            .await?
             .ok_or_else(|| HttpServerError::SessionNotFound(session_id.to_string()))?;
 
-        if state.is_expired(self.config.session_timeout) {
+        if state.has_expired() {
             return Err(HttpServerError::SessionExpired(session_id.to_string()));
         }
 
-        Ok(state.clone())
+        Ok(state)
     }
 
     /// Clean up expired sessions.
-    pub fn cleanup_expired(&self) -> usize {
-        let mut sessions = self.sessions.write().unwrap();
-        let timeout = self.config.session_timeout;
-
-        let expired: Vec<String> = sessions
-            .iter()
-            .filter(|(_, state)| state.is_expired(timeout))
-            .map(|(id, _)| id.clone())
-            .collect();
+    pub async fn cleanup_expired(&self) -> usize {
+        let expired = logged(self.store.remove_expired().await).unwrap_or_default();
 
         let count = expired.len();
-        for id in expired {
-            sessions.remove(&id);
-        }
-
+        self.expirations.fetch_add(count as u64, Ordering::Relaxed);
+        self.notify_closed(&expired);
         count
     }
 
     /// Get the number of active sessions.
-    pub fn session_count(&self) -> usize {
-        let sessions = self.sessions.read().unwrap();
-        sessions.len()
+    pub async fn session_count(&self) -> usize {
+        logged(self.store.count().await).unwrap_or_default()
+    }
+
+    /// Get occupancy counters for this manager.
+    pub async fn stats(&self) -> SessionStats {
+        SessionStats {
+            active: self.session_count().await,
+            evictions: self.evictions.load(Ordering::Relaxed),
+            expirations: self.expirations.load(Ordering::Relaxed),
+            peak: self.peak.load(Ordering::Relaxed),
+        }
     }
 
     /// Get all session IDs.
-    pub fn session_ids(&self) -> Vec<String> {
-        let sessions = self.sessions.read().unwrap();
-        sessions.keys().cloned().collect()
+    pub async fn session_ids(&self) -> Vec<String> {
+        logged(self.store.session_ids().await).unwrap_or_default()
     }
 
     /// Try to resume a session from a resumption token.
-    pub fn resume_session(&self, token: &ResumptionToken) -> Result<SessionState, HttpServerError> {
+    pub async fn resume_session(
+        &self,
+        token: &ResumptionToken,
+    ) -> Result<SessionState, HttpServerError> {
         let session_id = token.session_id.as_str();
 
         // Try to get and validate the session
-        match self.validate_session(session_id) {
+        match self.validate_session(session_id).await {
             Ok(state) => {
                 // Touch the session to update last activity
-                self.touch_session(session_id);
+                self.touch_session(session_id).await;
                 Ok(state)
             }
             Err(HttpServerError::SessionExpired(_)) => {
                 // Session expired, remove it
-                self.remove_session(session_id);
+                self.expire_session(session_id).await;
                 Err(HttpServerError::SessionExpired(session_id.to_string()))
             }
             Err(e) => Err(e),
@@ -228,89 +344,280 @@ impl SessionManager {
     }
 }
 
+/// Log a store failure and drop it, for methods that cannot report one.
+fn logged<T>(result: Result<T, HttpServerError>) -> Option<T> {
+    result
+        .map_err(|error| tracing::warn!("Session store error: {}", error))
+        .ok()
+}
+
 impl Default for SessionManager {
     fn default() -> Self {
         Self::new(SessionConfig::default())
     }
 }
 
+impl<S> fmt::Debug for SessionManager<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SessionManager")
+            .field("config", &self.config)
+            .field("evictions", &self.evictions.load(Ordering::Relaxed))
+            .field("expirations", &self.expirations.load(Ordering::Relaxed))
+            .field("peak", &self.peak.load(Ordering::Relaxed))
+            .finish_non_exhaustive()
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use futures::executor::block_on;
+
     use super::*;
 
     #[test]
     fn test_create_session() {
-        let manager = SessionManager::default();
-        let session = manager.create_session().unwrap();
+        block_on(async {
+            let manager = SessionManager::default();
+            let session = manager.create_session().await.unwrap();
 
-        assert!(!session.initialized);
-        assert_eq!(manager.session_count(), 1);
+            assert!(!session.initialized);
+            assert_eq!(manager.session_count().await, 1);
+        });
     }
 
     #[test]
     fn test_get_session() {
-        let manager = SessionManager::default();
-        let session = manager.create_session().unwrap();
-        let session_id = session.session_id.to_string();
-
-        let retrieved = manager.get_session(&session_id).unwrap();
-        assert_eq!(retrieved.session_id, session.session_id);
+        block_on(async {
+            let manager = SessionManager::default();
+            let session = manager.create_session().await.unwrap();
+            let session_id = session.session_id.to_string();
+
+            let retrieved = manager.get_session(&session_id).await.unwrap();
+            assert_eq!(retrieved.session_id, session.session_id);
+        });
     }
 
     #[test]
     fn test_remove_session() {
-        let manager = SessionManager::default();
-        let session = manager.create_session().unwrap();
-        let session_id = session.session_id.to_string();
-
-        assert_eq!(manager.session_count(), 1);
-        manager.remove_session(&session_id);
-        assert_eq!(manager.session_count(), 0);
+        block_on(async {
+            let manager = SessionManager::default();
+            let session = manager.create_session().await.unwrap();
+            let session_id = session.session_id.to_string();
+
+            assert_eq!(manager.session_count().await, 1);
+            manager.remove_session(&session_id).await;
+            assert_eq!(manager.session_count().await, 0);
+        });
     }
 
     #[test]
     fn test_session_limit() {
-        let config = SessionConfig {
-            max_sessions: 2,
-            ..Default::default()
-        };
-        let manager = SessionManager::new(config);
+        block_on(async {
+            let config = SessionConfig {
+                max_sessions: 2,
+                ..Default::default()
+            };
+            let manager = SessionManager::new(config);
+
+            manager.create_session().await.unwrap();
+            manager.create_session().await.unwrap();
+
+            let result = manager.create_session().await;
+            assert!(matches!(
+                result,
+                Err(HttpServerError::SessionLimitReached { max: 2 })
+            ));
+        });
+    }
 
-        manager.create_session().unwrap();
-        manager.create_session().unwrap();
+    #[test]
+    fn test_evict_lru_session() {
+        block_on(async {
+            let config = SessionConfig {
+                max_sessions: 2,
+                eviction_policy: SessionEvictionPolicy::EvictLru,
+                ..Default::default()
+            };
+            let manager = SessionManager::new(config);
+            let closed = Arc::new(RwLock::new(Vec::new()));
+            let listener_closed = Arc::clone(&closed);
+            manager.set_close_listener(Some(Arc::new(move |state: &SessionState| {
+                listener_closed
+                    .write()
+                    .unwrap()
+                    .push(state.session_id.to_string());
+            })));
+
+            let first = manager
+                .create_session()
+                .await
+                .unwrap()
+                .session_id
+                .to_string();
+            std::thread::sleep(Duration::from_millis(2));
+            let second = manager
+                .create_session()
+                .await
+                .unwrap()
+                .session_id
+                .to_string();
+            std::thread::sleep(Duration::from_millis(2));
+
+            // Activity on the first session makes the second the LRU one
+            manager.touch_session(&first).await.unwrap();
+            let third = manager
+                .create_session()
+                .await
+                .unwrap()
+                .session_id
+                .to_string();
+
+            assert!(manager.get_session(&first).await.is_some());
+            assert!(manager.get_session(&second).await.is_none());
+            assert!(manager.get_session(&third).await.is_some());
+            assert_eq!(*closed.read().unwrap(), vec![second]);
+            assert_eq!(
+                manager.stats().await,
+                SessionStats {
+                    active: 2,
+                    evictions: 1,
+                    expirations: 0,
+                    peak: 2,
+                }
+            );
+        });
+    }
 
-        let result = manager.create_session();
-        assert!(matches!(
-            result,
-            Err(HttpServerError::SessionLimitReached { max: 2 })
-        ));
+    #[test]
+    fn test_expired_session_is_not_touched() {
+        block_on(async {
+            let config = SessionConfig {
+                session_timeout: Duration::from_millis(10),
+                ..Default::default()
+            };
+            let manager = SessionManager::new(config);
+            let session_id = manager
+                .create_session()
+                .await
+                .unwrap()
+                .session_id
+                .to_string();
+
+            std::thread::sleep(Duration::from_millis(20));
+            assert!(manager.touch_session(&session_id).await.is_none());
+            assert_eq!(manager.session_count().await, 0);
+            assert_eq!(manager.stats().await.expirations, 1);
+            assert_eq!(manager.stats().await.peak, 1);
+        });
+    }
+
+    #[test]
+    fn test_extend_ttl_keeps_session_past_timeout() {
+        block_on(async {
+            let config = SessionConfig {
+                session_timeout: Duration::from_millis(10),
+                ..Default::default()
+            };
+            let manager = SessionManager::new(config);
+            let session_id = manager
+                .create_session()
+                .await
+                .unwrap()
+                .session_id
+                .to_string();
+
+            assert!(
+                manager
+                    .extend_ttl(&session_id, Duration::from_secs(60))
+                    .await
+                    .unwrap()
+            );
+            assert!(
+                !manager
+                    .extend_ttl("missing", Duration::from_secs(60))
+                    .await
+                    .unwrap()
+            );
+
+            std::thread::sleep(Duration::from_millis(20));
+            assert!(manager.validate_session(&session_id).await.is_ok());
+            assert_eq!(manager.cleanup_expired().await, 0);
+
+            // Activity does not cut an extended TTL short
+            let touched = manager.touch_session(&session_id).await.unwrap();
+            assert!(touched.expires_at > Instant::now() + Duration::from_secs(30));
+        });
     }
 
     #[test]
     fn test_mark_initialized() {
-        let manager = SessionManager::default();
-        let session = manager.create_session().unwrap();
-        let session_id = session.session_id.to_string();
+        block_on(async {
+            let manager = SessionManager::default();
+            let session = manager.create_session().await.unwrap();
+            let session_id = session.session_id.to_string();
 
-        assert!(!session.initialized);
+            assert!(!session.initialized);
 
-        let updated = manager.mark_initialized(&session_id).unwrap();
-        assert!(updated.initialized);
+            let updated = manager.mark_initialized(&session_id).await.unwrap();
+            assert!(updated.initialized);
+        });
+    }
+
+    #[test]
+    fn test_session_data_is_shared_and_dropped_on_expiry() {
+        block_on(async {
+            let config = SessionConfig {
+                session_timeout: Duration::from_millis(10),
+                ..Default::default()
+            };
+            let manager = SessionManager::new(config);
+            let session = manager.create_session().await.unwrap();
+            let session_id = session.session_id.to_string();
+
+            session.session_data.insert(42u32);
+            let fetched = manager.get_session(&session_id).await.unwrap();
+            assert_eq!(*fetched.session_data.get::<u32>().unwrap(), 42);
+
+            let weak = Arc::downgrade(&fetched.session_data);
+            drop(session);
+            drop(fetched);
+
+            std::thread::sleep(Duration::from_millis(20));
+            assert_eq!(manager.cleanup_expired().await, 1);
+            assert!(weak.upgrade().is_none());
+        });
     }
 
     #[test]
     fn test_next_event_id() {
-        let manager = SessionManager::default();
-        let session = manager.create_session().unwrap();
-        let session_id = session.session_id.to_string();
-
-        let id1 = manager
-            .update_session(&session_id, |s| {
-                s.next_event_id();
-            })
-            .unwrap();
-
-        let state = manager.get_session(&session_id).unwrap();
-        assert_eq!(state.event_counter, 1);
+        block_on(async {
+            let manager = SessionManager::default();
+            let session = manager.create_session().await.unwrap();
+            let session_id = session.session_id.to_string();
+
+            let id1 = manager
+                .update_session(&session_id, |s| {
+                    s.next_event_id();
+                })
+                .await
+                .unwrap();
+
+            let state = manager.get_session(&session_id).await.unwrap();
+            assert_eq!(state.event_counter, 1);
+        });
+    }
+
+    #[test]
+    fn test_shared_store_sees_sessions_of_other_managers() {
+        block_on(async {
+            let store: Arc<dyn SessionStore> = Arc::new(InMemorySessionStore::new());
+            let first = SessionManager::with_store(SessionConfig::default(), Arc::clone(&store));
+            let second = SessionManager::with_store(SessionConfig::default(), store);
+
+            let session_id = first.create_session().await.unwrap().session_id.to_string();
+            assert!(second.touch_session(&session_id).await.is_some());
+            assert!(second.remove_session(&session_id).await.is_some());
+            assert_eq!(first.session_count().await, 0);
+        });
     }
 }
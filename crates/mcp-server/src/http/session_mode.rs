@@ -0,0 +1,15 @@
+//! Whether the Streamable HTTP handlers keep sessions.
+
+/// Session handling of `AxumHandlerState` and `HttpServerHandler`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SessionMode {
+    /// Issue `Mcp-Session-Id` on the first request and require it afterwards.
+    #[default]
+    Stateful,
+    /// Handle every POST on its own, without issuing or reading a session
+    /// header, so requests can land on any instance behind a load balancer.
+    ///
+    /// GET and DELETE answer 405, and requests to the client (sampling,
+    /// elicitation) fail with a capability error.
+    Stateless,
+}
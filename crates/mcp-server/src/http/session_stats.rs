@@ -0,0 +1,14 @@
+//! Occupancy counters reported by the session manager.
+
+/// Snapshot of session occupancy, returned by `SessionManager::stats`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SessionStats {
+    /// Sessions currently held.
+    pub active: usize,
+    /// Sessions closed to make room under `SessionEvictionPolicy::EvictLru`.
+    pub evictions: u64,
+    /// Sessions dropped after idling past the session timeout.
+    pub expirations: u64,
+    /// Highest number of sessions held at once.
+    pub peak: usize,
+}
@@ -0,0 +1,107 @@
+//! Storage backend for the session manager.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+
+use super::error::HttpServerError;
+use super::session_manager::SessionState;
+
+/// Change applied to a stored session by [`SessionStore::update`].
+pub type SessionUpdate<'a> = Box<dyn FnOnce(&mut SessionState) + Send + 'a>;
+
+/// Where `SessionManager` keeps its sessions.
+///
+/// The manager applies the session limit, eviction policy and close
+/// listener; a store only holds sessions and when they expire. A session
+/// expires at its [`SessionState::expires_at`], which the store moves
+/// forward on [`update`](Self::update) and [`extend_ttl`](Self::extend_ttl).
+/// Stores may keep expired sessions until the manager removes them.
+#[async_trait]
+pub trait SessionStore: Send + Sync + 'static {
+    /// Add a new session, expiring at its `expires_at`.
+    async fn insert(&self, state: SessionState) -> Result<(), HttpServerError>;
+
+    async fn get(&self, session_id: &str) -> Result<Option<SessionState>, HttpServerError>;
+
+    /// Apply `f` to a session and mark it active, keeping it for at least
+    /// `ttl` from now. Returns the updated state, or `None` for an unknown
+    /// session.
+    async fn update(
+        &self,
+        session_id: &str,
+        ttl: Duration,
+        f: SessionUpdate<'_>,
+    ) -> Result<Option<SessionState>, HttpServerError>;
+
+    async fn remove(&self, session_id: &str) -> Result<Option<SessionState>, HttpServerError>;
+
+    /// Remove and return every expired session.
+    async fn remove_expired(&self) -> Result<Vec<SessionState>, HttpServerError>;
+
+    /// Number of sessions held, expired or not.
+    async fn count(&self) -> Result<usize, HttpServerError>;
+
+    async fn session_ids(&self) -> Result<Vec<String>, HttpServerError>;
+
+    /// ID of the session with the oldest `last_activity`.
+    async fn least_recently_active(&self) -> Result<Option<String>, HttpServerError>;
+
+    /// Keep a session for `new_ttl` from now without marking it active, so
+    /// a long-running stream outlives the idle timeout. Returns `false` for
+    /// an unknown session.
+    async fn extend_ttl(
+        &self,
+        session_id: &str,
+        new_ttl: Duration,
+    ) -> Result<bool, HttpServerError>;
+}
+
+#[async_trait]
+impl<S: SessionStore + ?Sized> SessionStore for Arc<S> {
+    async fn insert(&self, state: SessionState) -> Result<(), HttpServerError> {
+        (**self).insert(state).await
+    }
+
+    async fn get(&self, session_id: &str) -> Result<Option<SessionState>, HttpServerError> {
+        (**self).get(session_id).await
+    }
+
+    async fn update(
+        &self,
+        session_id: &str,
+        ttl: Duration,
+        f: SessionUpdate<'_>,
+    ) -> Result<Option<SessionState>, HttpServerError> {
+        (**self).update(session_id, ttl, f).await
+    }
+
+    async fn remove(&self, session_id: &str) -> Result<Option<SessionState>, HttpServerError> {
+        (**self).remove(session_id).await
+    }
+
+    async fn remove_expired(&self) -> Result<Vec<SessionState>, HttpServerError> {
+        (**self).remove_expired().await
+    }
+
+    async fn count(&self) -> Result<usize, HttpServerError> {
+        (**self).count().await
+    }
+
+    async fn session_ids(&self) -> Result<Vec<String>, HttpServerError> {
+        (**self).session_ids().await
+    }
+
+    async fn least_recently_active(&self) -> Result<Option<String>, HttpServerError> {
+        (**self).least_recently_active().await
+    }
+
+    async fn extend_ttl(
+        &self,
+        session_id: &str,
+        new_ttl: Duration,
+    ) -> Result<bool, HttpServerError> {
+        (**self).extend_ttl(session_id, new_ttl).await
+    }
+}
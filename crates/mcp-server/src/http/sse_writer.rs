@@ -1,6 +1,7 @@
 //! SSE response writer for HTTP server.
 
 use std::io::Write;
+use std::time::{Duration, Instant};
 
 use mcp_core::http::SseEvent;
 use mcp_core::stdio::JsonRpcMessage;
@@ -13,6 +14,8 @@ pub struct SseWriter<W: Write> {
     writer: W,
     event_counter: u64,
     session_id: Option<String>,
+    keep_alive_interval: Option<Duration>,
+    last_write: Instant,
 }
 
 impl<W: Write> SseWriter<W> {
@@ -22,6 +25,8 @@ impl<W: Write> SseWriter<W> {
             writer,
             event_counter: 0,
             session_id: None,
+            keep_alive_interval: None,
+            last_write: Instant::now(),
         }
     }
 
@@ -31,9 +36,17 @@ impl<W: Write> SseWriter<W> {
             writer,
             event_counter: session.event_counter,
             session_id: Some(session.session_id.to_string()),
+            keep_alive_interval: None,
+            last_write: Instant::now(),
         }
     }
 
+    /// Set how long the stream may stay silent before
+    /// [`keep_alive`](Self::keep_alive) writes a ping comment.
+    pub fn set_keep_alive_interval(&mut self, interval: Option<Duration>) {
+        self.keep_alive_interval = interval;
+    }
+
     /// Generate the next event ID.
     fn next_event_id(&mut self) -> String {
         self.event_counter += 1;
@@ -43,15 +56,72 @@ impl<W: Write> SseWriter<W> {
         }
     }
 
-    /// Write a raw SSE event.
-    pub fn write_event(&mut self, event: &SseEvent) -> Result<(), HttpServerError> {
-        let sse_string = event.to_sse_string();
+    /// Write an MCP SSE event.
+    pub fn write_sse_event(&mut self, event: &SseEvent) -> Result<(), HttpServerError> {
+        self.write_raw(&event.to_sse_string())
+    }
+
+    /// Write an event named `name`, e.g. `progress`.
+    ///
+    /// Each line of `data` goes on its own `data:` line, so a client joins
+    /// them back with `\n`. `name` and `id` must be single lines.
+    pub fn write_event(
+        &mut self,
+        name: &str,
+        data: &str,
+        id: Option<&str>,
+    ) -> Result<(), HttpServerError> {
+        for (field, value) in [("event name", Some(name)), ("event id", id)] {
+            if value.is_some_and(|value| value.contains(['\n', '\r'])) {
+                return Err(HttpServerError::InvalidRequest(format!(
+                    "{field} must not contain line breaks"
+                )));
+            }
+        }
+
+        let mut frame = String::new();
+        if let Some(id) = id {
+            frame.push_str(&format!("id: {id}\n"));
+        }
+        frame.push_str(&format!("event: {name}\n"));
+        for line in lines(data) {
+            frame.push_str(&format!("data: {line}\n"));
+        }
+        frame.push('\n');
+        self.write_raw(&frame)
+    }
+
+    /// Write a comment, which clients ignore; each line of `text` becomes
+    /// its own comment line.
+    pub fn write_comment(&mut self, text: &str) -> Result<(), HttpServerError> {
+        let mut frame = String::new();
+        for line in lines(text) {
+            frame.push_str(&format!(": {line}\n"));
+        }
+        frame.push('\n');
+        self.write_raw(&frame)
+    }
+
+    /// Write a ping comment if nothing was written for the keep-alive
+    /// interval. Returns whether one was written.
+    pub fn keep_alive(&mut self) -> Result<bool, HttpServerError> {
+        match self.keep_alive_interval {
+            Some(interval) if self.last_write.elapsed() >= interval => {
+                self.write_ping()?;
+                Ok(true)
+            }
+            _ => Ok(false),
+        }
+    }
+
+    fn write_raw(&mut self, frame: &str) -> Result<(), HttpServerError> {
         self.writer
-            .write_all(sse_string.as_bytes())
+            .write_all(frame.as_bytes())
             .map_err(|e| HttpServerError::Io(e.to_string()))?;
         self.writer
             .flush()
             .map_err(|e| HttpServerError::Io(e.to_string()))?;
+        self.last_write = Instant::now();
         Ok(())
     }
 
@@ -62,18 +132,18 @@ impl<W: Write> SseWriter<W> {
             id: Some(event_id.clone()),
             data: message.clone(),
         };
-        self.write_event(&event)?;
+        self.write_sse_event(&event)?;
         Ok(event_id)
     }
 
-    /// Write a ping event.
+    /// Write a ping comment.
     pub fn write_ping(&mut self) -> Result<(), HttpServerError> {
-        self.write_event(&SseEvent::Ping)
+        self.write_sse_event(&SseEvent::Ping)
     }
 
     /// Write an endpoint event.
     pub fn write_endpoint(&mut self, url: &str) -> Result<(), HttpServerError> {
-        self.write_event(&SseEvent::Endpoint {
+        self.write_sse_event(&SseEvent::Endpoint {
             endpoint_url: url.to_string(),
         })
     }
@@ -83,7 +153,7 @@ impl<W: Write> SseWriter<W> {
         &mut self,
         session_id: &mcp_core::http::SessionId,
     ) -> Result<(), HttpServerError> {
-        self.write_event(&SseEvent::SessionReady {
+        self.write_sse_event(&SseEvent::SessionReady {
             session_id: session_id.clone(),
         })
     }
@@ -99,6 +169,11 @@ impl<W: Write> SseWriter<W> {
     }
 }
 
+/// Lines of `text`, split at any SSE line ending (`\r\n`, `\n` or `\r`).
+fn lines(text: &str) -> impl Iterator<Item = &str> {
+    text.split("\r\n").flat_map(|line| line.split(['\n', '\r']))
+}
+
 /// Builder for SSE responses.
 #[derive(Debug, Clone)]
 pub struct SseResponseBuilder {
@@ -157,12 +232,15 @@ impl SseResponseBuilder {
         self
     }
 
-    /// Initialize an SSE writer with the configured options.
+    /// Initialize an SSE writer with the configured options, including
+    /// its keep-alive interval.
     pub fn initialize<W: Write>(
         &self,
         writer: &mut SseWriter<W>,
         session: &SessionState,
     ) -> Result<(), HttpServerError> {
+        writer.set_keep_alive_interval(self.keep_alive_interval.map(Duration::from_secs));
+
         // Send session ready event
         if self.send_session_ready {
             writer.write_session_ready(&session.session_id)?;
@@ -215,6 +293,81 @@ mod tests {
         assert!(output.contains(":ping"));
     }
 
+    fn written(write: impl FnOnce(&mut SseWriter<&mut Vec<u8>>)) -> String {
+        let mut buffer = Vec::new();
+        write(&mut SseWriter::new(&mut buffer));
+        String::from_utf8(buffer).unwrap()
+    }
+
+    #[test]
+    fn test_sse_writer_named_event() {
+        let output = written(|writer| {
+            writer
+                .write_event("progress", "{\"done\":3}", Some("7"))
+                .unwrap()
+        });
+        assert_eq!(output, "id: 7\nevent: progress\ndata: {\"done\":3}\n\n");
+    }
+
+    #[test]
+    fn test_sse_writer_multi_line_data() {
+        let output = written(|writer| {
+            writer
+                .write_event("log", "first\nsecond\r\nthird\rfourth", None)
+                .unwrap()
+        });
+        assert_eq!(
+            output,
+            "event: log\ndata: first\ndata: second\ndata: third\ndata: fourth\n\n"
+        );
+
+        // Clients join the lines back together
+        let mut parser = mcp_core::http::SseParser::new();
+        parser.append(&output);
+        let event = parser.next_event().unwrap();
+        assert_eq!(event.event.as_deref(), Some("log"));
+        assert_eq!(event.data, "first\nsecond\nthird\nfourth");
+    }
+
+    #[test]
+    fn test_sse_writer_empty_and_trailing_lines() {
+        let output = written(|writer| writer.write_event("empty", "", None).unwrap());
+        assert_eq!(output, "event: empty\ndata: \n\n");
+
+        let output = written(|writer| writer.write_event("tail", "line\n", None).unwrap());
+        assert_eq!(output, "event: tail\ndata: line\ndata: \n\n");
+    }
+
+    #[test]
+    fn test_sse_writer_rejects_multi_line_name_and_id() {
+        let mut buffer = Vec::new();
+        let mut writer = SseWriter::new(&mut buffer);
+        assert!(writer.write_event("a\nb", "data", None).is_err());
+        assert!(writer.write_event("a", "data", Some("1\r\n")).is_err());
+        assert!(buffer.is_empty());
+    }
+
+    #[test]
+    fn test_sse_writer_comment() {
+        let output = written(|writer| writer.write_comment("one\ntwo").unwrap());
+        assert_eq!(output, ": one\n: two\n\n");
+
+        // Comments never surface as events
+        let mut parser = mcp_core::http::SseParser::new();
+        parser.append(&output);
+        assert!(parser.next_event().is_none());
+    }
+
+    #[test]
+    fn test_sse_writer_keep_alive() {
+        let output = written(|writer| {
+            assert!(!writer.keep_alive().unwrap());
+            writer.set_keep_alive_interval(Some(Duration::ZERO));
+            assert!(writer.keep_alive().unwrap());
+        });
+        assert_eq!(output, ":ping\n\n");
+    }
+
     #[test]
     fn test_sse_writer_with_session() {
         let session_id = SessionId::from_string("test-session");
@@ -222,9 +375,11 @@ mod tests {
             session_id: session_id.clone(),
             created_at: std::time::Instant::now(),
             last_activity: std::time::Instant::now(),
+            expires_at: std::time::Instant::now(),
             initialized: true,
             event_counter: 10,
             data: std::collections::HashMap::new(),
+            session_data: Default::default(),
         };
 
         let mut buffer = Vec::new();
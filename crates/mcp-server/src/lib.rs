@@ -1,32 +1,65 @@
 pub mod auth;
 pub mod http;
 pub mod server;
+pub mod stdio;
 pub mod websocket;
 
-pub use server::{InMemoryTaskStore, McpServer, Server, ServerError, ServerOptions};
+pub use mcp_macros::ToolInput;
+
+pub use server::{
+    AuditLogMiddleware, BoxedToolHandler, CircuitBreaker, CircuitBreakerConfig, CircuitState, DEFAULT_DEDUP_MAX_ENTRIES, DEFAULT_PEER_REQUEST_TIMEOUT, InMemoryTaskStore, McpServer, McpServerBuilder, NotificationSender,
+    PendingRequests, RequestSender, ResultTransform, Server, ServerError, ServerMetrics, ServerOptions,
+    SummaryClock, SummaryKind, SummaryReport, SummaryRow, ToolInput, ToolMiddleware, ToolOptions,
+    TypedTool, input_schema_for,
+};
 
 pub use http::{
     BufferedEvent, EventBuffer, EventBufferConfig, HttpResponse, HttpServerError,
-    HttpServerHandler, HttpServerOptions, LegacySseConfig, LegacySseState, RequestHeaders,
-    SessionConfig, SessionManager, SessionState, SseResponseBuilder, SseWriter,
-    generate_session_id,
+    HttpServerHandler, HttpServerOptions, InMemorySessionStore, LegacySseConfig, LegacySseState, RateLimitConfig, RateLimiter, ReplayError,
+    RequestHeaders, SessionCloseListener, SessionConfig, SessionEvictionPolicy, SessionManager, SessionMode,
+    SessionState, SessionStats, SessionStore, SseResponseBuilder, SseWriter, generate_session_id,
 };
 
 #[cfg(feature = "tokio")]
 pub use http::SseBroadcaster;
 
+#[cfg(feature = "redis")]
+pub use http::RedisSessionStore;
+
+#[cfg(feature = "tokio")]
+pub use server::tokio_task_spawner;
+
+pub use stdio::{BeforeRequest, StdioOptions};
+
+#[cfg(feature = "prometheus")]
+pub use server::PrometheusMetrics;
+
+#[cfg(feature = "metrics")]
+pub use server::ToolStatsRow;
+
 #[cfg(feature = "axum")]
-pub use http::axum_handler::{AxumHandlerConfig, AxumHandlerState, create_router};
+pub use http::axum_handler::{
+    AxumHandlerConfig, AxumHandlerState, create_router, create_router_with_shutdown,
+};
 
 #[cfg(feature = "axum")]
 pub use http::create_legacy_sse_router;
 
+#[cfg(feature = "axum")]
+pub use http::CorsPolicy;
+
 #[cfg(feature = "axum")]
 pub use http::{
-    host_header_validation, localhost_host_validation, DnsProtectionConfig, DnsProtectionLayer,
+    allowed_hosts_validation, host_header_validation, localhost_host_validation, DnsProtectionConfig, DnsProtectionLayer,
     DnsProtectionService,
 };
 
+#[cfg(feature = "axum")]
+pub use http::{origin_validation, OriginValidationConfig, OriginValidationLayer, OriginValidationService};
+
+#[cfg(feature = "axum")]
+pub use http::{RateLimitLayer, RateLimitService};
+
 #[cfg(feature = "axum")]
 pub use auth::{
     create_oauth_metadata, create_oauth_metadata_router, create_oauth_router,
@@ -34,4 +67,4 @@ pub use auth::{
 };
 
 #[cfg(feature = "websocket")]
-pub use websocket::{WebSocketConfig, WebSocketError, WebSocketState, create_websocket_router, handle_websocket};
+pub use websocket::{ConnectionCloseListener, MCP_SUBPROTOCOL, MCP_SUBPROTOCOL_V1, WebSocketConfig, WebSocketError, WebSocketState, create_websocket_router, handle_websocket};
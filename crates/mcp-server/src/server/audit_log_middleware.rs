@@ -0,0 +1,60 @@
+use std::time::Duration;
+
+use mcp_core::protocol::RequestContext;
+use mcp_core::types::CallToolResult;
+
+use crate::server::{ServerError, ToolMiddleware};
+
+/// Tool middleware logging each call's tool name, caller session, duration
+/// and error status through `tracing`, under the `mcp_server::audit` target.
+///
+/// Successful calls are logged at `INFO`; failed calls and results flagged
+/// `isError` at `WARN`. Arguments and results are never logged.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct AuditLogMiddleware;
+
+impl AuditLogMiddleware {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl ToolMiddleware for AuditLogMiddleware {
+    fn after(
+        &self,
+        tool_name: &str,
+        result: &Result<CallToolResult, ServerError>,
+        elapsed: Duration,
+        context: &RequestContext,
+    ) {
+        let session = context.session_id.as_deref().unwrap_or("-");
+        let duration_ms = elapsed.as_millis() as u64;
+        match result {
+            Ok(result) if result.is_error != Some(true) => tracing::info!(
+                target: "mcp_server::audit",
+                tool = tool_name,
+                session,
+                duration_ms,
+                is_error = false,
+                "tool call"
+            ),
+            Ok(_) => tracing::warn!(
+                target: "mcp_server::audit",
+                tool = tool_name,
+                session,
+                duration_ms,
+                is_error = true,
+                "tool call returned an error result"
+            ),
+            Err(err) => tracing::warn!(
+                target: "mcp_server::audit",
+                tool = tool_name,
+                session,
+                duration_ms,
+                is_error = true,
+                error = %err,
+                "tool call failed"
+            ),
+        }
+    }
+}
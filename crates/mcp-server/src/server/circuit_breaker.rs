@@ -0,0 +1,101 @@
+use std::sync::Mutex;
+use std::time::Instant;
+
+use crate::server::circuit_breaker_config::CircuitBreakerConfig;
+use crate::server::circuit_state::CircuitState;
+
+#[derive(Debug)]
+struct BreakerState {
+    state: CircuitState,
+    consecutive_failures: u32,
+    opened_at: Option<Instant>,
+    /// Start of the half-open trial call, if one is running.
+    trial_started: Option<Instant>,
+}
+
+/// Stops calling a tool handler that keeps failing.
+///
+/// The circuit opens after `failure_threshold` consecutive failures and
+/// rejects calls until `reset_timeout` has passed. The next call is then a
+/// trial: success closes the circuit, failure opens it again. A trial that
+/// never reports back (its request was cancelled) is replaced by a new one
+/// after another `reset_timeout`.
+#[derive(Debug)]
+pub struct CircuitBreaker {
+    config: CircuitBreakerConfig,
+    inner: Mutex<BreakerState>,
+}
+
+impl CircuitBreaker {
+    pub fn new(config: CircuitBreakerConfig) -> Self {
+        Self {
+            config,
+            inner: Mutex::new(BreakerState {
+                state: CircuitState::Closed,
+                consecutive_failures: 0,
+                opened_at: None,
+                trial_started: None,
+            }),
+        }
+    }
+
+    pub fn config(&self) -> CircuitBreakerConfig {
+        self.config
+    }
+
+    /// Current state; an open circuit whose reset timeout has passed
+    /// reports `HalfOpen`.
+    pub fn state(&self) -> CircuitState {
+        let inner = self.inner.lock().expect("circuit breaker");
+        match inner.state {
+            CircuitState::Open if self.elapsed(inner.opened_at) => CircuitState::HalfOpen,
+            state => state,
+        }
+    }
+
+    /// Whether a call may run now. Every permitted call must be followed
+    /// by `record`.
+    pub fn try_acquire(&self) -> bool {
+        let mut inner = self.inner.lock().expect("circuit breaker");
+        match inner.state {
+            CircuitState::Closed => true,
+            CircuitState::Open if self.elapsed(inner.opened_at) => {
+                inner.state = CircuitState::HalfOpen;
+                inner.trial_started = Some(Instant::now());
+                true
+            }
+            CircuitState::Open => false,
+            CircuitState::HalfOpen => {
+                if inner.trial_started.is_none() || self.elapsed(inner.trial_started) {
+                    inner.trial_started = Some(Instant::now());
+                    true
+                } else {
+                    false
+                }
+            }
+        }
+    }
+
+    /// Report the outcome of a call permitted by `try_acquire`.
+    pub fn record(&self, success: bool) {
+        let mut inner = self.inner.lock().expect("circuit breaker");
+        inner.trial_started = None;
+        if success {
+            inner.state = CircuitState::Closed;
+            inner.consecutive_failures = 0;
+            inner.opened_at = None;
+            return;
+        }
+        inner.consecutive_failures = inner.consecutive_failures.saturating_add(1);
+        let tripped = inner.state == CircuitState::HalfOpen
+            || inner.consecutive_failures >= self.config.failure_threshold.max(1);
+        if tripped {
+            inner.state = CircuitState::Open;
+            inner.opened_at = Some(Instant::now());
+        }
+    }
+
+    fn elapsed(&self, since: Option<Instant>) -> bool {
+        since.is_some_and(|since| since.elapsed() >= self.config.reset_timeout)
+    }
+}
@@ -0,0 +1,21 @@
+use std::time::Duration;
+
+/// When a tool's circuit breaker opens and how long it stays open; set
+/// through `ToolOptions::with_circuit_breaker`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CircuitBreakerConfig {
+    /// Consecutive failed calls that open the circuit. Zero is treated as one.
+    pub failure_threshold: u32,
+    /// How long an open circuit rejects calls before letting a trial call
+    /// through.
+    pub reset_timeout: Duration,
+}
+
+impl CircuitBreakerConfig {
+    pub fn new(failure_threshold: u32, reset_timeout: Duration) -> Self {
+        Self {
+            failure_threshold,
+            reset_timeout,
+        }
+    }
+}
@@ -0,0 +1,23 @@
+use std::fmt;
+
+/// State of a tool's circuit breaker.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CircuitState {
+    /// Calls reach the handler; failures are counted.
+    Closed,
+    /// Calls are rejected without running the handler.
+    Open,
+    /// The reset timeout has passed; the next call is a trial that closes
+    /// the circuit on success and reopens it on failure.
+    HalfOpen,
+}
+
+impl fmt::Display for CircuitState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Closed => "closed",
+            Self::Open => "open",
+            Self::HalfOpen => "half-open",
+        })
+    }
+}
@@ -2,10 +2,16 @@ pub mod notification_handler_fn;
 pub mod prompt_handler;
 pub mod request_handler_fn;
 pub mod resource_handler;
+pub mod resource_template_handler;
 pub mod tool_handler;
+pub mod tool_input;
+pub mod typed_tool;
 
 pub use notification_handler_fn::NotificationHandlerFn;
 pub use prompt_handler::PromptHandler;
 pub use request_handler_fn::RequestHandlerFn;
 pub use resource_handler::ResourceHandler;
-pub use tool_handler::ToolHandler;
+pub use resource_template_handler::ResourceTemplateHandler;
+pub use tool_handler::{BoxedToolHandler, ToolHandler};
+pub use tool_input::{ToolInput, input_schema_for};
+pub use typed_tool::TypedTool;
@@ -0,0 +1,38 @@
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+
+use mcp_core::protocol::RequestContext;
+use mcp_core::types::ReadResourceResult;
+
+use crate::server::ServerError;
+
+/// Handler for reading resources that match a resource template.
+///
+/// Receives the requested URI and the template variables extracted from
+/// it, percent-decoded.
+#[async_trait]
+pub trait ResourceTemplateHandler: Send + Sync + 'static {
+    async fn read(
+        &self,
+        uri: String,
+        variables: HashMap<String, String>,
+        context: RequestContext,
+    ) -> Result<ReadResourceResult, ServerError>;
+}
+
+#[async_trait]
+impl<F, Fut> ResourceTemplateHandler for F
+where
+    F: Send + Sync + 'static + Fn(String, HashMap<String, String>, RequestContext) -> Fut,
+    Fut: std::future::Future<Output = Result<ReadResourceResult, ServerError>> + Send,
+{
+    async fn read(
+        &self,
+        uri: String,
+        variables: HashMap<String, String>,
+        context: RequestContext,
+    ) -> Result<ReadResourceResult, ServerError> {
+        (self)(uri, variables, context).await
+    }
+}
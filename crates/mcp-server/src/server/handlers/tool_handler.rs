@@ -6,6 +6,10 @@ use mcp_core::types::CallToolResult;
 
 use crate::server::ServerError;
 
+/// A type-erased tool handler, for registering tools in bulk with
+/// `McpServer::register_tool_group`.
+pub type BoxedToolHandler = Box<dyn ToolHandler>;
+
 /// Handler for tool execution.
 #[async_trait]
 pub trait ToolHandler: Send + Sync + 'static {
@@ -0,0 +1,48 @@
+use schemars::JsonSchema;
+use serde::de::DeserializeOwned;
+use serde_json::{Map, Value};
+
+use mcp_core::schema::JsonSchemaValidator;
+
+/// Typed arguments of a tool registered with `McpServer::register_typed_tool`.
+///
+/// The same type provides the advertised `inputSchema` and parses the
+/// `tools/call` arguments, so the two cannot drift apart. Types deriving
+/// `schemars::JsonSchema` can implement `input_schema` with
+/// [`input_schema_for`]; `#[derive(ToolInput)]` implements it from the
+/// struct's fields and `#[tool_input(..)]` attributes.
+///
+/// ```ignore
+/// #[derive(Deserialize, JsonSchema)]
+/// struct AddInput {
+///     a: i64,
+///     b: i64,
+/// }
+///
+/// impl ToolInput for AddInput {
+///     fn input_schema() -> Value {
+///         input_schema_for::<Self>()
+///     }
+/// }
+/// ```
+pub trait ToolInput: DeserializeOwned + Send + 'static {
+    /// JSON Schema advertised as the tool's `inputSchema`.
+    fn input_schema() -> Value;
+
+    /// Description used for tools registered without one.
+    fn description() -> Option<&'static str> {
+        None
+    }
+
+    /// Parse `tools/call` arguments; missing arguments are parsed as `{}`.
+    fn from_arguments(arguments: Option<Value>) -> Result<Self, String> {
+        let arguments = arguments.unwrap_or_else(|| Value::Object(Map::new()));
+        serde_json::from_value(arguments).map_err(|err| err.to_string())
+    }
+}
+
+/// Input schema generated from a `schemars::JsonSchema` type.
+pub fn input_schema_for<T: JsonSchema>() -> Value {
+    serde_json::to_value(JsonSchemaValidator::schema_for::<T>())
+        .unwrap_or_else(|_| Value::Object(Map::new()))
+}
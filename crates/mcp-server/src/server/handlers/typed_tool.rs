@@ -0,0 +1,69 @@
+use std::future::Future;
+
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+
+use mcp_core::protocol::RequestContext;
+
+use crate::server::ServerError;
+
+/// A tool implemented as a type, registered with
+/// `McpServer::register_tool_type::<T>()`.
+///
+/// The `tools/call` arguments are deserialized into `Args` before `call`
+/// runs; arguments that do not parse produce an `isError` result naming the
+/// problem, so the model can retry. The `Output` is returned as JSON text and, when it serializes to
+/// an object, as `structuredContent`. When `Args` implements `ToolInput`,
+/// `input_schema` can return `Args::input_schema()`.
+///
+/// ```ignore
+/// struct Echo;
+///
+/// impl TypedTool for Echo {
+///     type Args = EchoArgs;
+///     type Output = EchoOutput;
+///
+///     fn name() -> &'static str {
+///         "echo"
+///     }
+///
+///     fn description() -> &'static str {
+///         "Echo the message back"
+///     }
+///
+///     fn input_schema() -> Value {
+///         EchoArgs::input_schema()
+///     }
+///
+///     async fn call(args: EchoArgs, _ctx: RequestContext) -> Result<EchoOutput, ServerError> {
+///         Ok(EchoOutput { message: args.message })
+///     }
+/// }
+/// ```
+pub trait TypedTool: 'static {
+    /// Arguments the tool is called with.
+    type Args: DeserializeOwned + Send + 'static;
+    /// Result returned to the client.
+    type Output: Serialize;
+
+    /// Name the tool is listed and called by.
+    fn name() -> &'static str;
+
+    /// Human-readable title advertised in `tools/list`, if any.
+    fn title() -> Option<&'static str> {
+        None
+    }
+
+    /// Description advertised in `tools/list`.
+    fn description() -> &'static str;
+
+    /// JSON Schema advertised as the tool's `inputSchema`.
+    fn input_schema() -> Value;
+
+    /// Run the tool.
+    fn call(
+        args: Self::Args,
+        ctx: RequestContext,
+    ) -> impl Future<Output = Result<Self::Output, ServerError>> + Send;
+}
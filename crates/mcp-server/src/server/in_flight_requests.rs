@@ -0,0 +1,58 @@
+use std::sync::Mutex;
+
+use futures::channel::oneshot;
+
+/// Requests currently being handled, so shutdown can wait for them.
+#[derive(Default)]
+pub(crate) struct InFlightRequests {
+    state: Mutex<InFlightState>,
+}
+
+#[derive(Default)]
+struct InFlightState {
+    count: usize,
+    /// Woken when `count` drops to zero.
+    idle: Vec<oneshot::Sender<()>>,
+}
+
+impl InFlightRequests {
+    /// Count a request as in flight until the guard is dropped.
+    pub(crate) fn enter(&self) -> InFlightGuard<'_> {
+        self.state.lock().expect("in-flight requests").count += 1;
+        InFlightGuard { requests: self }
+    }
+
+    /// Resolve once no request is in flight.
+    pub(crate) async fn idle(&self) {
+        let idle = {
+            let mut state = self.state.lock().expect("in-flight requests");
+            if state.count == 0 {
+                return;
+            }
+            let (tx, rx) = oneshot::channel();
+            state.idle.push(tx);
+            rx
+        };
+        let _ = idle.await;
+    }
+}
+
+pub(crate) struct InFlightGuard<'a> {
+    requests: &'a InFlightRequests,
+}
+
+impl Drop for InFlightGuard<'_> {
+    fn drop(&mut self) {
+        let waiters = {
+            let mut state = self.requests.state.lock().expect("in-flight requests");
+            state.count -= 1;
+            if state.count > 0 {
+                return;
+            }
+            std::mem::take(&mut state.idle)
+        };
+        for waiter in waiters {
+            let _ = waiter.send(());
+        }
+    }
+}
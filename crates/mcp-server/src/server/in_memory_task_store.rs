@@ -1,39 +1,151 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::sync::{
-    Mutex,
+    Arc, Mutex,
     atomic::{AtomicU64, Ordering},
 };
 
 use async_trait::async_trait;
 use serde_json::Value;
+use time::OffsetDateTime;
 use time::format_description::well_known::Rfc3339;
 
-use mcp_core::protocol::{ProtocolError, TaskStore};
+use mcp_core::protocol::{
+    ProtocolError, StreamedResult, StreamedResultBuffer, TaskStatusChange, TaskStatusListener,
+    TaskStore,
+};
+use mcp_core::schema::ValidationError;
 use mcp_core::types::{
-    Cursor, ErrorObject, MessageId, RequestMessage, Task, TaskMetadata, TaskStatus,
+    Cursor, ErrorObject, ListTasksRequestParams, MessageId, RequestMessage, Task, TaskMetadata,
+    TaskResultChunk, TaskResultRange, TaskStatus,
 };
 
+/// Default number of tasks returned per `tasks/list` page.
+pub const DEFAULT_TASK_PAGE_SIZE: usize = 50;
+
+struct TaskEntry {
+    task: Task,
+    session_id: Option<String>,
+}
+
+/// A finished task's outcome: a value, or the bytes of a streamed result.
+#[derive(Clone)]
+enum StoredResult {
+    Value(Result<Value, ErrorObject>),
+    Streamed(Arc<StreamedResultBuffer>),
+}
+
 /// Simple in-memory TaskStore implementation.
+///
+/// Tasks are kept in creation order, so `tasks/list` cursors stay stable
+/// while new tasks are created between pages. Streamed results are kept as
+/// serialized bytes and only parsed when read without a range.
 pub struct InMemoryTaskStore {
     counter: AtomicU64,
-    tasks: Mutex<HashMap<String, Task>>,
-    results: Mutex<HashMap<String, Result<Value, ErrorObject>>>,
+    page_size: usize,
+    tasks: Mutex<BTreeMap<u64, TaskEntry>>,
+    results: Mutex<HashMap<String, StoredResult>>,
+    status_listener: Mutex<Option<TaskStatusListener>>,
 }
 
 impl InMemoryTaskStore {
     pub fn new() -> Self {
+        Self::with_page_size(DEFAULT_TASK_PAGE_SIZE)
+    }
+
+    /// Create a store that returns at most `page_size` tasks per `tasks/list` page.
+    pub fn with_page_size(page_size: usize) -> Self {
         Self {
             counter: AtomicU64::new(1),
-            tasks: Mutex::new(HashMap::new()),
+            page_size: page_size.max(1),
+            tasks: Mutex::new(BTreeMap::new()),
             results: Mutex::new(HashMap::new()),
+            status_listener: Mutex::new(None),
         }
     }
 
     fn now_timestamp() -> String {
-        time::OffsetDateTime::now_utc()
+        OffsetDateTime::now_utc()
             .format(&Rfc3339)
             .unwrap_or_else(|_| "1970-01-01T00:00:00Z".to_string())
     }
+
+    fn sequence(task_id: &str) -> Option<u64> {
+        task_id.strip_prefix("task-")?.parse().ok()
+    }
+
+    fn parse_timestamp(value: &str) -> Result<OffsetDateTime, ProtocolError> {
+        OffsetDateTime::parse(value, &Rfc3339)
+            .map_err(|err| invalid_params(format!("invalid timestamp `{value}`: {err}")))
+    }
+
+    /// Store the result of a task and move it to its final status with
+    /// `update`, unless it was cancelled first.
+    ///
+    /// A cancelled task keeps its status; late results from a handler that
+    /// finished during the grace period are discarded. The check, the result
+    /// and the transition happen under the task lock, so a concurrent cancel
+    /// either comes first or finds the task finished.
+    fn finish(&self, task_id: &str, result: StoredResult, update: impl FnOnce(&mut Task)) {
+        self.transition_if(task_id, |task| {
+            if task.status == TaskStatus::Cancelled {
+                return false;
+            }
+            self.results
+                .lock()
+                .expect("result mutex")
+                .insert(task_id.to_string(), result);
+            update(task);
+            true
+        });
+    }
+
+    fn stored_result(&self, task_id: &str) -> Option<StoredResult> {
+        self.results
+            .lock()
+            .expect("result mutex")
+            .get(task_id)
+            .cloned()
+    }
+
+    /// Apply a status transition and report it to the listener when the status changed.
+    fn transition(&self, task_id: &str, update: impl FnOnce(&mut Task)) -> Option<Task> {
+        self.transition_if(task_id, |task| {
+            update(task);
+            true
+        })
+    }
+
+    /// Like [`transition`](Self::transition), but `update` may leave the task
+    /// untouched by returning `false`, decided under the same lock.
+    fn transition_if(
+        &self,
+        task_id: &str,
+        update: impl FnOnce(&mut Task) -> bool,
+    ) -> Option<Task> {
+        let (task, change) = {
+            let mut tasks = self.tasks.lock().expect("task mutex");
+            let entry = tasks.get_mut(&Self::sequence(task_id)?)?;
+            let previous_status = entry.task.status.clone();
+            if !update(&mut entry.task) {
+                return None;
+            }
+            entry.task.last_updated_at = Self::now_timestamp();
+            let change = (entry.task.status != previous_status).then(|| TaskStatusChange {
+                task: entry.task.clone(),
+                previous_status,
+                session_id: entry.session_id.clone(),
+            });
+            (entry.task.clone(), change)
+        };
+
+        if let Some(change) = change {
+            let listener = self.status_listener.lock().expect("listener mutex").clone();
+            if let Some(listener) = listener {
+                listener(change);
+            }
+        }
+        Some(task)
+    }
 }
 
 impl Default for InMemoryTaskStore {
@@ -49,12 +161,13 @@ impl TaskStore for InMemoryTaskStore {
         params: TaskMetadata,
         _request_id: MessageId,
         _request: RequestMessage,
+        session_id: Option<String>,
     ) -> Result<Task, ProtocolError> {
         let id = self.counter.fetch_add(1, Ordering::SeqCst);
         let task_id = format!("task-{id}");
         let now = Self::now_timestamp();
         let task = Task {
-            task_id: task_id.clone(),
+            task_id,
             status: TaskStatus::Working,
             ttl: params.ttl,
             created_at: now.clone(),
@@ -63,10 +176,13 @@ impl TaskStore for InMemoryTaskStore {
             status_message: None,
             meta: None,
         };
-        self.tasks
-            .lock()
-            .expect("task mutex")
-            .insert(task_id, task.clone());
+        self.tasks.lock().expect("task mutex").insert(
+            id,
+            TaskEntry {
+                task: task.clone(),
+                session_id,
+            },
+        );
         Ok(task)
     }
 
@@ -75,61 +191,142 @@ impl TaskStore for InMemoryTaskStore {
         task_id: &str,
         result: Result<Value, ErrorObject>,
     ) -> Result<(), ProtocolError> {
-        let mut tasks = self.tasks.lock().expect("task mutex");
-        if let Some(task) = tasks.get_mut(task_id) {
-            match &result {
-                Ok(_) => task.status = TaskStatus::Completed,
-                Err(err) => {
-                    task.status = TaskStatus::Failed;
-                    task.status_message = Some(err.message.clone());
-                }
+        let failure = result.as_ref().err().map(|err| err.message.clone());
+        self.finish(task_id, StoredResult::Value(result), |task| match failure {
+            None => task.status = TaskStatus::Completed,
+            Some(message) => {
+                task.status = TaskStatus::Failed;
+                task.status_message = Some(message);
             }
-            task.last_updated_at = Self::now_timestamp();
-        }
-        self.results
-            .lock()
-            .expect("result mutex")
-            .insert(task_id.to_string(), result);
+        });
+        Ok(())
+    }
+
+    async fn set_task_result_streamed(
+        &self,
+        task_id: &str,
+        result: StreamedResult,
+    ) -> Result<(), ProtocolError> {
+        let mut buffer = StreamedResultBuffer::new();
+        result.write_to(&mut buffer)?;
+        self.finish(task_id, StoredResult::Streamed(Arc::new(buffer)), |task| {
+            task.status = TaskStatus::Completed
+        });
         Ok(())
     }
 
     async fn get_task(&self, task_id: &str) -> Result<Option<Task>, ProtocolError> {
-        Ok(self.tasks.lock().expect("task mutex").get(task_id).cloned())
+        let Some(sequence) = Self::sequence(task_id) else {
+            return Ok(None);
+        };
+        Ok(self
+            .tasks
+            .lock()
+            .expect("task mutex")
+            .get(&sequence)
+            .map(|entry| entry.task.clone()))
     }
 
     async fn list_tasks(
         &self,
-        _cursor: Option<Cursor>,
+        params: &ListTasksRequestParams,
     ) -> Result<(Vec<Task>, Option<Cursor>), ProtocolError> {
-        let tasks = self
-            .tasks
-            .lock()
-            .expect("task mutex")
-            .values()
-            .cloned()
+        let start = match params.pagination.cursor.as_ref() {
+            Some(cursor) => cursor
+                .as_ref()
+                .parse::<u64>()
+                .map_err(|_| invalid_params(format!("invalid cursor `{}`", cursor.0)))?,
+            None => 0,
+        };
+        let created_after = params
+            .created_after
+            .as_deref()
+            .map(Self::parse_timestamp)
+            .transpose()?;
+
+        let tasks = self.tasks.lock().expect("task mutex");
+        let mut matching = tasks
+            .range(start.saturating_add(1)..)
+            .filter(|(_, entry)| {
+                params
+                    .status
+                    .as_ref()
+                    .is_none_or(|status| entry.task.status == *status)
+            })
+            .filter(|(_, entry)| {
+                created_after.is_none_or(|after| {
+                    Self::parse_timestamp(&entry.task.created_at)
+                        .map(|created| created > after)
+                        .unwrap_or(false)
+                })
+            });
+
+        let page: Vec<(u64, Task)> = matching
+            .by_ref()
+            .take(self.page_size)
+            .map(|(sequence, entry)| (*sequence, entry.task.clone()))
             .collect();
-        Ok((tasks, None))
+        let next_cursor = match (page.last(), matching.next()) {
+            (Some((sequence, _)), Some(_)) => Some(Cursor(sequence.to_string())),
+            _ => None,
+        };
+
+        Ok((page.into_iter().map(|(_, task)| task).collect(), next_cursor))
     }
 
     async fn get_task_result(
         &self,
         task_id: &str,
     ) -> Result<Option<Result<Value, ErrorObject>>, ProtocolError> {
-        Ok(self
-            .results
-            .lock()
-            .expect("result mutex")
-            .get(task_id)
-            .cloned())
+        match self.stored_result(task_id) {
+            Some(StoredResult::Value(result)) => Ok(Some(result)),
+            Some(StoredResult::Streamed(buffer)) => {
+                Ok(Some(Ok(serde_json::from_slice(&buffer.to_vec())?)))
+            }
+            None => Ok(None),
+        }
+    }
+
+    async fn get_task_result_range(
+        &self,
+        task_id: &str,
+        range: &TaskResultRange,
+    ) -> Result<Option<Result<TaskResultChunk, ErrorObject>>, ProtocolError> {
+        let buffer = match self.stored_result(task_id) {
+            Some(StoredResult::Streamed(buffer)) => buffer,
+            Some(StoredResult::Value(Ok(value))) => {
+                let mut buffer = StreamedResultBuffer::new();
+                serde_json::to_writer(&mut buffer, &value)?;
+                Arc::new(buffer)
+            }
+            Some(StoredResult::Value(Err(error))) => return Ok(Some(Err(error))),
+            None => return Ok(None),
+        };
+        Ok(Some(Ok(buffer.read_range(range)?)))
     }
 
     async fn cancel_task(&self, task_id: &str) -> Result<Option<Task>, ProtocolError> {
-        let mut tasks = self.tasks.lock().expect("task mutex");
-        if let Some(task) = tasks.get_mut(task_id) {
+        Ok(self.transition(task_id, |task| {
             task.status = TaskStatus::Cancelled;
-            task.last_updated_at = Self::now_timestamp();
-            return Ok(Some(task.clone()));
-        }
-        Ok(None)
+            task.status_message = None;
+        }))
     }
+
+    async fn set_status_message(
+        &self,
+        task_id: &str,
+        message: Option<String>,
+    ) -> Result<(), ProtocolError> {
+        self.transition(task_id, |task| task.status_message = message);
+        Ok(())
+    }
+
+    fn set_status_listener(&self, listener: Option<TaskStatusListener>) {
+        *self.status_listener.lock().expect("listener mutex") = listener;
+    }
+}
+
+/// A `tasks/list` parameter the store cannot read, reported as invalid params.
+fn invalid_params(message: String) -> ProtocolError {
+    ProtocolError::Validation(ValidationError::Failed(vec![message]))
 }
@@ -0,0 +1,136 @@
+use std::time::Duration;
+
+/// Sub-buckets per power of two; bounds the relative error to 1/16.
+const SUB_BUCKET_BITS: u32 = 4;
+const SUB_BUCKETS: usize = 1 << SUB_BUCKET_BITS;
+/// Values are clamped to 2^40 µs (about 12 days).
+const MAX_MAGNITUDE: u32 = 40;
+const BUCKETS: usize = SUB_BUCKETS + (MAX_MAGNITUDE - SUB_BUCKET_BITS) as usize * SUB_BUCKETS;
+
+/// Fixed-size, log-linear latency histogram in microseconds.
+///
+/// Works like an HDR histogram with one significant hex digit: values below
+/// 16 µs are exact, larger values share a bucket with neighbours within
+/// 6.25%. Recording never allocates.
+#[derive(Clone)]
+pub struct LatencyHistogram {
+    counts: [u64; BUCKETS],
+    total: u64,
+    max: u64,
+}
+
+impl LatencyHistogram {
+    pub fn new() -> Self {
+        Self {
+            counts: [0; BUCKETS],
+            total: 0,
+            max: 0,
+        }
+    }
+
+    pub fn record(&mut self, latency: Duration) {
+        let micros = u64::try_from(latency.as_micros()).unwrap_or(u64::MAX);
+        let micros = micros.min((1 << MAX_MAGNITUDE) - 1);
+        self.counts[bucket_index(micros)] += 1;
+        self.total += 1;
+        self.max = self.max.max(micros);
+    }
+
+    /// Number of recorded values.
+    pub fn count(&self) -> u64 {
+        self.total
+    }
+
+    /// Largest recorded value.
+    pub fn max(&self) -> Duration {
+        Duration::from_micros(self.max)
+    }
+
+    /// Upper bound of the bucket holding the `percentile`-th value, capped
+    /// at the largest recorded value. Zero when nothing was recorded.
+    pub fn percentile(&self, percentile: f64) -> Duration {
+        if self.total == 0 {
+            return Duration::ZERO;
+        }
+        let rank = ((percentile.clamp(0.0, 100.0) / 100.0) * self.total as f64).ceil() as u64;
+        let rank = rank.max(1);
+        let mut seen = 0;
+        for (index, count) in self.counts.iter().enumerate() {
+            seen += count;
+            if seen >= rank {
+                return Duration::from_micros(bucket_upper_bound(index).min(self.max));
+            }
+        }
+        self.max()
+    }
+}
+
+impl Default for LatencyHistogram {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn bucket_index(value: u64) -> usize {
+    if value < SUB_BUCKETS as u64 {
+        return value as usize;
+    }
+    let magnitude = 63 - value.leading_zeros();
+    let shift = magnitude - SUB_BUCKET_BITS;
+    let sub_bucket = (value >> shift) as usize - SUB_BUCKETS;
+    SUB_BUCKETS + shift as usize * SUB_BUCKETS + sub_bucket
+}
+
+fn bucket_upper_bound(index: usize) -> u64 {
+    if index < SUB_BUCKETS {
+        return index as u64;
+    }
+    let shift = (index - SUB_BUCKETS) / SUB_BUCKETS;
+    let sub_bucket = ((index - SUB_BUCKETS) % SUB_BUCKETS + SUB_BUCKETS) as u64;
+    ((sub_bucket + 1) << shift) - 1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bucket_bounds_contain_value() {
+        for value in [
+            0,
+            1,
+            15,
+            16,
+            17,
+            31,
+            32,
+            1_000,
+            12_345,
+            999_999,
+            (1 << 40) - 1,
+        ] {
+            let index = bucket_index(value);
+            assert!(index < BUCKETS);
+            let upper = bucket_upper_bound(index);
+            assert!(upper >= value, "value {value} above bucket bound {upper}");
+            assert!(upper - value <= value / 16, "bucket for {value} too wide");
+        }
+    }
+
+    #[test]
+    fn test_percentiles() {
+        let mut histogram = LatencyHistogram::new();
+        for ms in 1..=100 {
+            histogram.record(Duration::from_millis(ms));
+        }
+        assert_eq!(histogram.count(), 100);
+        assert_eq!(histogram.max(), Duration::from_millis(100));
+
+        let p50 = histogram.percentile(50.0);
+        assert!(p50 >= Duration::from_millis(50) && p50 <= Duration::from_millis(54));
+        let p99 = histogram.percentile(99.0);
+        assert!(p99 >= Duration::from_millis(99) && p99 <= Duration::from_millis(100));
+        assert_eq!(histogram.percentile(100.0), Duration::from_millis(100));
+        assert_eq!(LatencyHistogram::new().percentile(50.0), Duration::ZERO);
+    }
+}
@@ -0,0 +1,38 @@
+use serde_json::Value;
+
+use mcp_core::protocol::ProtocolError;
+use mcp_core::types::{Cursor, PaginatedRequestParams, PaginatedResult};
+
+/// The cursor of a `*/list` request, if any.
+pub(crate) fn request_cursor(params: Value) -> Result<Option<Cursor>, ProtocolError> {
+    let params: Option<PaginatedRequestParams> = serde_json::from_value(params)?;
+    Ok(params.and_then(|params| params.cursor))
+}
+
+/// Cut one page of a `*/list` response out of `items`.
+///
+/// Items are ordered by `key` and the cursor is the key of the last item on
+/// the previous page, so a walk resumes after it even when entries are
+/// registered or removed in between: nothing is listed twice, and entries
+/// present for the whole walk are listed once. Without a page size all
+/// items fit on one page.
+pub(crate) fn list_page<T>(
+    mut items: Vec<T>,
+    key: impl Fn(&T) -> &str,
+    cursor: Option<&Cursor>,
+    page_size: Option<usize>,
+) -> (Vec<T>, PaginatedResult) {
+    items.sort_by(|a, b| key(a).cmp(key(b)));
+    if let Some(cursor) = cursor {
+        items.retain(|item| key(item) > cursor.as_ref());
+    }
+
+    let mut pagination = PaginatedResult::default();
+    if let Some(page_size) = page_size.map(|size| size.max(1))
+        && items.len() > page_size
+    {
+        items.truncate(page_size);
+        pagination.next_cursor = items.last().map(|item| Cursor::from(key(item)));
+    }
+    (items, pagination)
+}
@@ -1,21 +1,42 @@
+use std::collections::HashSet;
 use std::sync::{Arc, Mutex};
+use std::time::Duration;
+#[cfg(feature = "metrics")]
+use std::time::Instant;
 
 use futures::future::BoxFuture;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
 use serde_json::Value;
 
+use mcp_core::auth::InsufficientScopeError;
 use mcp_core::protocol::{ProtocolError, RequestContext};
-use mcp_core::schema::JsonSchemaValidator;
+use mcp_core::schema::{JsonSchemaValidator, ValidationError};
 use mcp_core::types::{
-    CallToolRequestParams, CreateMessageRequestParams, ElicitRequestFormParams,
+    CallToolRequestParams, CallToolResult, ContentBlock, CreateMessageRequestParams, ElicitRequestFormParams,
     ElicitRequestUrlParams, GetPromptRequestParams, ListPromptsResult, ListResourceTemplatesResult,
-    ListResourcesResult, ListToolsResult, MessageId, NotificationMessage, PaginatedRequestParams,
-    PaginatedResult, PromptCapabilities, RequestMessage, ResourceCapabilities, ResourceRequestParams,
-    ServerCapabilities, ToolCapabilities,
+    ListResourcesResult, ListToolsResult, MessageId, NotificationMessage, NotificationParams,
+    PaginatedRequestParams, PromptCapabilities, RequestMessage,
+    ResourceCapabilities, ResourceRequestParams, ResourceUpdatedNotificationParams,
+    ServerCapabilities, TextContent, ToolCapabilities,
 };
+use mcp_core::uri_template::UriTemplate;
 
-use crate::server::handlers::{PromptHandler, RequestHandlerFn, ResourceHandler, ToolHandler};
-use crate::server::registries::{PromptRegistry, ResourceRegistry, ToolRegistry};
-use crate::server::{Server, ServerError, ServerOptions};
+use crate::server::handlers::{
+    BoxedToolHandler, PromptHandler, RequestHandlerFn, ResourceHandler, ResourceTemplateHandler,
+    ToolHandler, ToolInput, TypedTool,
+};
+use crate::server::list_page::{list_page, request_cursor};
+use crate::server::registries::{
+    PromptRegistry, ResourceRegistry, SubscriptionRegistry, ToolRegistry,
+};
+use crate::server::tool_middleware::call_with_middleware;
+use crate::server::{
+    CircuitState, McpServerBuilder, ResultTransform, Server, ServerError, ServerOptions, SummaryReport,
+    ToolMiddleware, ToolOptions,
+};
+#[cfg(feature = "metrics")]
+use crate::server::{ToolStatsRow, tool_stats::ToolStats};
 
 /// High-level MCP server with tool/resource/prompt registries.
 pub struct McpServer {
@@ -23,24 +44,46 @@ pub struct McpServer {
     tools: Arc<Mutex<ToolRegistry>>,
     resources: Arc<Mutex<ResourceRegistry>>,
     prompts: Arc<Mutex<PromptRegistry>>,
+    subscriptions: Arc<Mutex<SubscriptionRegistry>>,
+    tool_middleware: Arc<Mutex<Vec<Arc<dyn ToolMiddleware>>>>,
     tool_handlers_initialized: bool,
     resource_handlers_initialized: bool,
     prompt_handlers_initialized: bool,
+    result_transform: Option<ResultTransform>,
+    tool_input_validator: Option<Arc<JsonSchemaValidator>>,
+    list_page_size: Option<usize>,
+    #[cfg(feature = "metrics")]
+    tool_stats: Arc<ToolStats>,
 }
 
 impl McpServer {
     pub fn new(server_info: mcp_core::types::Implementation, options: ServerOptions) -> Self {
+        let result_transform = options.result_transform.clone();
+        let tool_input_validator = options.tool_input_validator.clone();
+        let list_page_size = options.list_page_size;
         Self {
             server: Server::new(server_info, options),
             tools: Arc::new(Mutex::new(ToolRegistry::default())),
             resources: Arc::new(Mutex::new(ResourceRegistry::default())),
             prompts: Arc::new(Mutex::new(PromptRegistry::default())),
+            subscriptions: Arc::new(Mutex::new(SubscriptionRegistry::default())),
+            tool_middleware: Arc::new(Mutex::new(Vec::new())),
             tool_handlers_initialized: false,
             resource_handlers_initialized: false,
             prompt_handlers_initialized: false,
+            result_transform,
+            tool_input_validator,
+            list_page_size,
+            #[cfg(feature = "metrics")]
+            tool_stats: Arc::new(ToolStats::default()),
         }
     }
 
+    /// Start building a server named `name`; see `McpServerBuilder`.
+    pub fn builder(name: impl Into<String>, version: impl Into<String>) -> McpServerBuilder {
+        McpServerBuilder::new(name, version)
+    }
+
     pub fn server(&self) -> &Server {
         &self.server
     }
@@ -49,15 +92,68 @@ impl McpServer {
         &mut self.server
     }
 
+    /// Request statistics collected so far; see `Server::summary_report`.
+    pub fn summary_report(&self) -> SummaryReport {
+        self.server.summary_report()
+    }
+
+    /// Calls and latency percentiles per tool since the last reset, as
+    /// returned by `tools/stats`.
+    #[cfg(feature = "metrics")]
+    pub fn tool_stats(&self) -> Vec<ToolStatsRow> {
+        self.tool_stats.rows()
+    }
+
+    /// Clear the per-tool statistics, as `tools/stats/reset` does.
+    #[cfg(feature = "metrics")]
+    pub fn reset_tool_stats(&self) {
+        self.tool_stats.reset()
+    }
+
+    /// State of the circuit breaker guarding `name`, or `None` when the
+    /// tool is unknown or was registered without one.
+    pub fn circuit_state(&self, name: &str) -> Option<CircuitState> {
+        let tools = self.tools.lock().expect("tool registry");
+        tools.breaker(name).map(|breaker| breaker.state())
+    }
+
+    /// Refuse new requests and give in-flight handlers and tasks up to
+    /// `grace` to finish; see `Server::shutdown`.
+    pub async fn shutdown(&self, grace: Duration) {
+        self.server.shutdown(grace).await
+    }
+
+    /// Refuse new requests and wait up to `timeout` for in-flight handlers
+    /// and tasks, cancelling the tasks left over; see `Server::drain`.
+    pub async fn drain(&self, timeout: Duration) {
+        self.server.drain(timeout).await
+    }
+
     pub fn register_tool(
         &mut self,
         tool: mcp_core::types::Tool,
         handler: impl ToolHandler,
+    ) -> Result<(), ServerError> {
+        self.register_tool_with_options(tool, ToolOptions::default(), handler)
+    }
+
+    /// Register a tool with per-tool options such as required scopes.
+    ///
+    /// A `tools/call` for a tool with `required_scopes` fails with an
+    /// `insufficient_scope` error unless the request's
+    /// `RequestContext::auth_info` carries all of them. With a
+    /// `circuit_breaker`, calls made while the circuit is open get an error
+    /// result without running the handler.
+    pub fn register_tool_with_options(
+        &mut self,
+        tool: mcp_core::types::Tool,
+        options: ToolOptions,
+        handler: impl ToolHandler,
     ) -> Result<(), ServerError> {
         self.tools
             .lock()
             .expect("tool registry")
-            .register_tool(tool, handler);
+            .register_tool_with_options(tool, options, handler);
         self.server.register_capabilities(ServerCapabilities {
             tools: Some(ToolCapabilities {
                 list_changed: Some(true),
@@ -68,6 +164,205 @@ impl McpServer {
         Ok(())
     }
 
+    /// Register `tools` all at once, naming each `{prefix}_{name}` when a
+    /// prefix is given, and return how many were registered.
+    ///
+    /// The group is checked before anything is registered: if any name is
+    /// already taken or repeated within the group, or any `inputSchema` is
+    /// not an object schema, none of the tools are registered.
+    pub fn register_tool_group(
+        &mut self,
+        prefix: Option<&str>,
+        tools: Vec<(mcp_core::types::Tool, BoxedToolHandler)>,
+    ) -> Result<usize, ServerError> {
+        let tools: Vec<_> = tools
+            .into_iter()
+            .map(|(mut tool, handler)| {
+                if let Some(prefix) = prefix {
+                    tool.base.name = format!("{prefix}_{}", tool.base.name);
+                }
+                (tool, handler)
+            })
+            .collect();
+        {
+            let registry = self.tools.lock().expect("tool registry");
+            let mut names = HashSet::new();
+            for (tool, _) in &tools {
+                let name = tool.base.name.as_str();
+                if registry.contains(name) || !names.insert(name) {
+                    return Err(ServerError::InvalidTool(format!(
+                        "tool `{name}` is already registered"
+                    )));
+                }
+                if tool.input_schema.get("type").and_then(Value::as_str) != Some("object") {
+                    return Err(ServerError::InvalidTool(format!(
+                        "inputSchema of `{name}` must have type \"object\""
+                    )));
+                }
+            }
+        }
+        if tools.is_empty() {
+            return Ok(0);
+        }
+
+        self.server.register_capabilities(ServerCapabilities {
+            tools: Some(ToolCapabilities {
+                list_changed: Some(true),
+            }),
+            ..Default::default()
+        })?;
+        self.ensure_tool_handlers()?;
+        let count = tools.len();
+        let mut registry = self.tools.lock().expect("tool registry");
+        for (tool, handler) in tools {
+            registry.register_boxed_tool(tool, handler);
+        }
+        Ok(count)
+    }
+
+    /// Register a tool whose arguments are parsed into `I` and whose result
+    /// is serialized from `O`.
+    ///
+    /// The tool's `inputSchema` is replaced with `I::input_schema()`, and
+    /// a missing `description` is taken from `I::description()`.
+    /// Arguments that fail to parse produce an `isError` result naming the
+    /// problem, so the model can retry. The output is returned as JSON text
+    /// and, when it serializes to an object, as `structuredContent`.
+    pub fn register_typed_tool<I, O, F, Fut>(
+        &mut self,
+        mut tool: mcp_core::types::Tool,
+        handler: F,
+    ) -> Result<(), ServerError>
+    where
+        I: ToolInput,
+        O: Serialize,
+        F: Fn(I, RequestContext) -> Fut + Send + Sync + 'static,
+        Fut: std::future::Future<Output = Result<O, ServerError>> + Send + 'static,
+    {
+        tool.input_schema = I::input_schema();
+        if tool.description.is_none() {
+            tool.description = I::description().map(str::to_string);
+        }
+        self.register_parsed_tool(tool, I::from_arguments, tool_error_arguments, handler)
+    }
+
+    /// Register the tool implemented by `T`, named, titled and described by
+    /// it.
+    ///
+    /// Arguments are deserialized into `T::Args` and handled as in
+    /// [`register_typed_tool`](Self::register_typed_tool): arguments that
+    /// fail to parse produce an `isError` result, and the `T::Output` is
+    /// returned as JSON text and, when it serializes to an object, as
+    /// `structuredContent`.
+    pub fn register_tool_type<T: TypedTool>(&mut self) -> Result<(), ServerError> {
+        let tool = mcp_core::types::Tool {
+            base: mcp_core::types::BaseMetadata {
+                name: T::name().to_string(),
+                title: T::title().map(str::to_string),
+            },
+            icons: mcp_core::types::Icons { icons: None },
+            description: Some(T::description().to_string()),
+            input_schema: T::input_schema(),
+            output_schema: None,
+            annotations: None,
+            execution: None,
+            meta: None,
+        };
+        self.register_parsed_tool(
+            tool,
+            parse_arguments::<T::Args>,
+            tool_error_arguments,
+            T::call,
+        )
+    }
+
+    /// Register a tool whose arguments are deserialized into `A` and whose
+    /// result is serialized from `R`, keeping the tool's own `inputSchema`.
+    ///
+    /// Arguments that fail to deserialize fail the call with an
+    /// invalid-params error instead of reaching the handler, where
+    /// [`register_typed_tool`](Self::register_typed_tool) and
+    /// [`register_tool_type`](Self::register_tool_type) answer with an
+    /// `isError` result. The output is returned as in `register_typed_tool`,
+    /// which also derives the schema from the argument type.
+    pub fn register_tool_typed<A, R, F, Fut>(
+        &mut self,
+        tool: mcp_core::types::Tool,
+        handler: F,
+    ) -> Result<(), ServerError>
+    where
+        A: DeserializeOwned + Send + 'static,
+        R: Serialize,
+        F: Fn(A, RequestContext) -> Fut + Send + Sync + 'static,
+        Fut: std::future::Future<Output = Result<R, ServerError>> + Send + 'static,
+    {
+        self.register_parsed_tool(
+            tool,
+            parse_arguments::<A>,
+            invalid_params_arguments,
+            handler,
+        )
+    }
+
+    /// Glue shared by the typed registrations: arguments are parsed with
+    /// `parse`, a parse failure is answered by `reject`, and the output is
+    /// serialized into the result.
+    fn register_parsed_tool<A, R, F, Fut>(
+        &mut self,
+        tool: mcp_core::types::Tool,
+        parse: fn(Option<Value>) -> Result<A, String>,
+        reject: fn(String) -> Result<CallToolResult, ServerError>,
+        handler: F,
+    ) -> Result<(), ServerError>
+    where
+        A: Send + 'static,
+        R: Serialize,
+        F: Fn(A, RequestContext) -> Fut + Send + Sync + 'static,
+        Fut: std::future::Future<Output = Result<R, ServerError>> + Send + 'static,
+    {
+        let handler = Arc::new(handler);
+        let typed = move |arguments: Option<Value>, context: RequestContext| {
+            let handler = Arc::clone(&handler);
+            async move {
+                let input = match parse(arguments) {
+                    Ok(input) => input,
+                    Err(message) => return reject(message),
+                };
+                let output = serde_json::to_value(handler(input, context).await?)?;
+                Ok(output_result(output))
+            }
+        };
+        self.register_tool(tool, typed)
+    }
+
+    /// Run `middleware` around every tool call, after the middleware added
+    /// before it; see `ToolMiddleware` for the order hooks run in.
+    pub fn add_tool_middleware(&mut self, middleware: impl ToolMiddleware + 'static) {
+        self.tool_middleware
+            .lock()
+            .expect("tool middleware")
+            .push(Arc::new(middleware));
+    }
+
+    /// Tools registered so far.
+    pub fn list_tools(&self) -> Vec<mcp_core::types::Tool> {
+        self.tools.lock().expect("tool registry").list_tools()
+    }
+
+    /// Add a tool while the server is running and tell every session that
+    /// the tool list changed.
+    ///
+    /// The tools capability must already be set up by `register_tool`, as
+    /// capabilities are locked after initialization.
+    pub fn add_tool_after_init(&self, tool: mcp_core::types::Tool, handler: impl ToolHandler) {
+        self.tools
+            .lock()
+            .expect("tool registry")
+            .register_tool(tool, handler);
+        self.server
+            .broadcast_notification(self.tool_list_changed_notification());
+    }
+
     pub fn register_resource(
         &mut self,
         resource: mcp_core::types::Resource,
@@ -79,7 +374,7 @@ impl McpServer {
             .register_resource(resource, handler);
         self.server.register_capabilities(ServerCapabilities {
             resources: Some(ResourceCapabilities {
-                subscribe: None,
+                subscribe: Some(true),
                 list_changed: Some(true),
             }),
             ..Default::default()
@@ -88,17 +383,27 @@ impl McpServer {
         Ok(())
     }
 
+    /// Register a resource template, listed by `resources/templates/list`.
+    ///
+    /// `resources/read` requests for a URI that no concrete resource has are
+    /// matched against the templates' `uriTemplate` (RFC 6570, see
+    /// `UriTemplate`) in registration order; the first match is read by its
+    /// handler with the extracted variables.
     pub fn register_resource_template(
         &mut self,
         template: mcp_core::types::ResourceTemplate,
+        handler: impl ResourceTemplateHandler,
     ) -> Result<(), ServerError> {
+        let uri_template = UriTemplate::parse(&template.uri_template).map_err(|err| {
+            ServerError::InvalidResourceTemplate(format!("{}: {err}", template.uri_template))
+        })?;
         self.resources
             .lock()
             .expect("resource registry")
-            .register_template(template);
+            .register_template(template, uri_template, handler);
         self.server.register_capabilities(ServerCapabilities {
             resources: Some(ResourceCapabilities {
-                subscribe: None,
+                subscribe: Some(true),
                 list_changed: Some(true),
             }),
             ..Default::default()
@@ -138,6 +443,40 @@ impl McpServer {
         self.server.prompt_list_changed_notification()
     }
 
+    /// Send `notifications/resources/updated` to the sessions subscribed to
+    /// `uri`, returning how many were notified.
+    pub fn notify_resource_updated(&self, uri: &str) -> usize {
+        let subscribers = self
+            .subscriptions
+            .lock()
+            .expect("subscription registry")
+            .subscribers(uri);
+        let params = ResourceUpdatedNotificationParams {
+            base: NotificationParams::default(),
+            uri: uri.to_string(),
+        };
+        let Ok(params) = serde_json::to_value(params) else {
+            return 0;
+        };
+        let mut notified = 0;
+        for session_id in subscribers {
+            let notification =
+                NotificationMessage::new("notifications/resources/updated", Some(params.clone()));
+            if self.server.send_notification(session_id, notification) {
+                notified += 1;
+            }
+        }
+        notified
+    }
+
+    /// Drop the resource subscriptions of a closed session.
+    pub fn remove_session_subscriptions(&self, session_id: &str) {
+        self.subscriptions
+            .lock()
+            .expect("subscription registry")
+            .remove_session(&Some(session_id.to_string()));
+    }
+
     /// Add a resource to the registry after initialization without modifying capabilities.
     /// This is useful when resources are discovered dynamically after the client has initialized.
     pub fn add_resource_after_init(
@@ -223,23 +562,25 @@ impl McpServer {
         self.server.client_supports_url_elicitation()
     }
 
-    fn ensure_tool_handlers(&mut self) -> Result<(), ServerError> {
+    pub(crate) fn ensure_tool_handlers(&mut self) -> Result<(), ServerError> {
         if self.tool_handlers_initialized {
             return Ok(());
         }
 
         let tools = self.tools.clone();
+        let page_size = self.list_page_size;
         let list_handler = RequestHandlerFn::new(
-            move |_request: &RequestMessage,
+            move |request: &RequestMessage,
                   _context: &RequestContext|
                   -> BoxFuture<'static, Result<Value, ProtocolError>> {
                 let tools = tools.clone();
+                let params_value = request.params.clone();
                 Box::pin(async move {
+                    let cursor = request_cursor(params_value)?;
                     let tools = tools.lock().expect("tool registry").list_tools();
-                    let result = ListToolsResult {
-                        pagination: PaginatedResult::default(),
-                        tools,
-                    };
+                    let (tools, pagination) =
+                        list_page(tools, |tool| &tool.base.name, cursor.as_ref(), page_size);
+                    let result = ListToolsResult { pagination, tools };
                     Ok(serde_json::to_value(result)?)
                 })
             },
@@ -252,25 +593,92 @@ impl McpServer {
         );
 
         let tools = self.tools.clone();
+        let tool_middleware = self.tool_middleware.clone();
+        let result_transform = self.result_transform.clone();
+        let tool_input_validator = self.tool_input_validator.clone();
+        #[cfg(feature = "metrics")]
+        let tool_stats = self.tool_stats.clone();
         let call_handler = RequestHandlerFn::new(
             move |request: &RequestMessage,
                   context: &RequestContext|
                   -> BoxFuture<'static, Result<Value, ProtocolError>> {
                 let tools = tools.clone();
+                let tool_middleware = tool_middleware.lock().expect("tool middleware").clone();
+                let result_transform = result_transform.clone();
+                let tool_input_validator = tool_input_validator.clone();
+                #[cfg(feature = "metrics")]
+                let tool_stats = tool_stats.clone();
                 let params_value = request.params.clone();
                 let context = context.clone();
                 Box::pin(async move {
                     let params: CallToolRequestParams = serde_json::from_value(params_value)?;
-                    let handler = tools
-                        .lock()
-                        .expect("tool registry")
-                        .handler(&params.name)
-                        .ok_or_else(|| ProtocolError::Handler("tool not found".to_string()))?;
-                    let result = handler
-                        .call(params.arguments, context)
-                        .await
-                        .map_err(|err| ProtocolError::Handler(err.to_string()))?;
-                    Ok(serde_json::to_value(result)?)
+                    let (handler, options, input_schema, breaker) = {
+                        let tools = tools.lock().expect("tool registry");
+                        let handler = tools
+                            .handler(&params.name)
+                            .ok_or_else(|| ProtocolError::Handler("tool not found".to_string()))?;
+                        let input_schema = tool_input_validator
+                            .as_ref()
+                            .and_then(|_| tools.tool(&params.name))
+                            .map(|tool| tool.input_schema);
+                        (
+                            handler,
+                            tools.options(&params.name).unwrap_or_default(),
+                            input_schema,
+                            tools.breaker(&params.name),
+                        )
+                    };
+                    #[cfg(feature = "metrics")]
+                    let (name, started) = (params.name.clone(), Instant::now());
+                    let outcome = async move {
+                        check_required_scopes(&params.name, &options, &context)?;
+                        if let (Some(validator), Some(schema)) =
+                            (&tool_input_validator, &input_schema)
+                        {
+                            let arguments = params
+                                .arguments
+                                .clone()
+                                .unwrap_or_else(|| Value::Object(Default::default()));
+                            if let Err(err) = validator.validate_value(schema, &arguments) {
+                                return Ok(invalid_arguments(describe(err)));
+                            }
+                        }
+                        if let Some(breaker) = &breaker
+                            && !breaker.try_acquire()
+                        {
+                            return Ok(circuit_open(&params.name, breaker.config().reset_timeout));
+                        }
+                        let result = call_with_middleware(
+                            &tool_middleware,
+                            &params.name,
+                            handler,
+                            params.arguments,
+                            context,
+                        )
+                        .await;
+                        if let Some(breaker) = &breaker {
+                            breaker.record(
+                                matches!(&result, Ok(result) if result.is_error != Some(true)),
+                            );
+                        }
+                        let mut result = result.map_err(|err| match err {
+                            // Keeps the code of e.g. invalid-params errors
+                            ServerError::Protocol(err) => err,
+                            err => ProtocolError::Handler(err.to_string()),
+                        })?;
+                        if let Some(transform) = &result_transform {
+                            transform(&mut result);
+                        }
+                        Ok::<_, ProtocolError>(result)
+                    }
+                    .await;
+                    #[cfg(feature = "metrics")]
+                    tool_stats.record(
+                        &name,
+                        started.elapsed(),
+                        !matches!(&outcome, Ok(result) if result.is_error != Some(true)),
+                    );
+                    Ok(serde_json::to_value(outcome?)?)
                 })
             },
         );
@@ -281,28 +689,100 @@ impl McpServer {
             call_handler,
         );
 
+        #[cfg(feature = "metrics")]
+        self.register_tool_stats_handlers();
+
+        // Calls to tools annotated `idempotentHint: false` always run
+        let inner = self.server.replay_filter();
+        let tools = self.tools.clone();
+        self.server.set_replay_filter(Some(Arc::new(move |request: &RequestMessage| {
+            let opted_out = request.method == "tools/call"
+                && request
+                    .params
+                    .get("name")
+                    .and_then(Value::as_str)
+                    .and_then(|name| tools.lock().expect("tool registry").tool(name))
+                    .and_then(|tool| tool.annotations)
+                    .and_then(|annotations| annotations.idempotent_hint)
+                    == Some(false);
+            !opted_out && inner.as_ref().is_none_or(|filter| filter(request))
+        })));
+
         self.tool_handlers_initialized = true;
         Ok(())
     }
 
-    fn ensure_resource_handlers(&mut self) -> Result<(), ServerError> {
+    /// `tools/stats` answers with the per-tool statistics as structured
+    /// content; `tools/stats/reset` clears them.
+    #[cfg(feature = "metrics")]
+    fn register_tool_stats_handlers(&mut self) {
+        let tool_stats = self.tool_stats.clone();
+        let stats_handler = RequestHandlerFn::new(
+            move |_request: &RequestMessage,
+                  _context: &RequestContext|
+                  -> BoxFuture<'static, Result<Value, ProtocolError>> {
+                let rows = tool_stats.rows();
+                Box::pin(async move {
+                    let stats = serde_json::json!({ "tools": rows });
+                    let result = CallToolResult {
+                        content: vec![ContentBlock::Text(TextContent::new(stats.to_string()))],
+                        structured_content: Some(stats),
+                        is_error: None,
+                        meta: None,
+                    };
+                    Ok(serde_json::to_value(result)?)
+                })
+            },
+        );
+        self.server.register_request_handler(
+            "tools/stats",
+            JsonSchemaValidator::schema_for::<Option<Value>>(),
+            stats_handler,
+        );
+
+        let tool_stats = self.tool_stats.clone();
+        let reset_handler = RequestHandlerFn::new(
+            move |_request: &RequestMessage,
+                  _context: &RequestContext|
+                  -> BoxFuture<'static, Result<Value, ProtocolError>> {
+                tool_stats.reset();
+                Box::pin(async move { Ok(serde_json::json!({})) })
+            },
+        );
+        self.server.register_request_handler(
+            "tools/stats/reset",
+            JsonSchemaValidator::schema_for::<Option<Value>>(),
+            reset_handler,
+        );
+    }
+
+    pub(crate) fn ensure_resource_handlers(&mut self) -> Result<(), ServerError> {
         if self.resource_handlers_initialized {
             return Ok(());
         }
 
         let resources = self.resources.clone();
+        let page_size = self.list_page_size;
         let list_handler = RequestHandlerFn::new(
-            move |_request: &RequestMessage,
+            move |request: &RequestMessage,
                   _context: &RequestContext|
                   -> BoxFuture<'static, Result<Value, ProtocolError>> {
                 let resources = resources.clone();
+                let params_value = request.params.clone();
                 Box::pin(async move {
+                    let cursor = request_cursor(params_value)?;
                     let resources = resources
                         .lock()
                         .expect("resource registry")
                         .list_resources();
+                    let (resources, pagination) = list_page(
+                        resources,
+                        |resource| &resource.uri,
+                        cursor.as_ref(),
+                        page_size,
+                    );
                     let result = ListResourcesResult {
-                        pagination: PaginatedResult::default(),
+                        pagination,
                         resources,
                     };
                     Ok(serde_json::to_value(result)?)
@@ -318,17 +798,25 @@ impl McpServer {
 
         let templates = self.resources.clone();
         let template_handler = RequestHandlerFn::new(
-            move |_request: &RequestMessage,
+            move |request: &RequestMessage,
                   _context: &RequestContext|
                   -> BoxFuture<'static, Result<Value, ProtocolError>> {
                 let templates = templates.clone();
+                let params_value = request.params.clone();
                 Box::pin(async move {
+                    let cursor = request_cursor(params_value)?;
                     let templates = templates
                         .lock()
                         .expect("resource registry")
                         .list_templates();
+                    let (templates, pagination) = list_page(
+                        templates,
+                        |template| &template.base.name,
+                        cursor.as_ref(),
+                        page_size,
+                    );
                     let result = ListResourceTemplatesResult {
-                        pagination: PaginatedResult::default(),
+                        pagination,
                         resource_templates: templates,
                     };
                     Ok(serde_json::to_value(result)?)
@@ -342,6 +830,34 @@ impl McpServer {
             template_handler,
         );
 
+        for (method, subscribe) in [("resources/subscribe", true), ("resources/unsubscribe", false)] {
+            let subscriptions = self.subscriptions.clone();
+            let handler = RequestHandlerFn::new(
+                move |request: &RequestMessage,
+                      context: &RequestContext|
+                      -> BoxFuture<'static, Result<Value, ProtocolError>> {
+                    let subscriptions = subscriptions.clone();
+                    let params_value = request.params.clone();
+                    let session_id = context.session_id.clone();
+                    Box::pin(async move {
+                        let params: ResourceRequestParams = serde_json::from_value(params_value)?;
+                        let mut subscriptions = subscriptions.lock().expect("subscription registry");
+                        if subscribe {
+                            subscriptions.subscribe(session_id, params.uri);
+                        } else {
+                            subscriptions.unsubscribe(&session_id, &params.uri);
+                        }
+                        Ok(serde_json::json!({}))
+                    })
+                },
+            );
+            self.server.register_request_handler(
+                method,
+                JsonSchemaValidator::schema_for::<ResourceRequestParams>(),
+                handler,
+            );
+        }
+
         let resources = self.resources.clone();
         let read_handler = RequestHandlerFn::new(
             move |request: &RequestMessage,
@@ -352,15 +868,24 @@ impl McpServer {
                 let context = context.clone();
                 Box::pin(async move {
                     let params: ResourceRequestParams = serde_json::from_value(params_value)?;
-                    let handler = resources
-                        .lock()
-                        .expect("resource registry")
-                        .handler(&params.uri)
-                        .ok_or_else(|| ProtocolError::Handler("resource not found".to_string()))?;
-                    let result = handler
-                        .read(params.uri.clone(), context)
-                        .await
-                        .map_err(|err| ProtocolError::Handler(err.to_string()))?;
+                    // Concrete resources take precedence over templates
+                    let (handler, template) = {
+                        let resources = resources.lock().expect("resource registry");
+                        match resources.handler(&params.uri) {
+                            Some(handler) => (Some(handler), None),
+                            None => (None, resources.template_handler(&params.uri)),
+                        }
+                    };
+                    let result = match (handler, template) {
+                        (Some(handler), _) => handler.read(params.uri.clone(), context).await,
+                        (None, Some((handler, variables))) => {
+                            handler.read(params.uri.clone(), variables, context).await
+                        }
+                        (None, None) => {
+                            return Err(ProtocolError::Handler("resource not found".to_string()));
+                        }
+                    }
+                    .map_err(|err| ProtocolError::Handler(err.to_string()))?;
                     Ok(serde_json::to_value(result)?)
                 })
             },
@@ -376,21 +901,30 @@ impl McpServer {
         Ok(())
     }
 
-    fn ensure_prompt_handlers(&mut self) -> Result<(), ServerError> {
+    pub(crate) fn ensure_prompt_handlers(&mut self) -> Result<(), ServerError> {
         if self.prompt_handlers_initialized {
             return Ok(());
         }
 
         let prompts = self.prompts.clone();
+        let page_size = self.list_page_size;
         let list_handler = RequestHandlerFn::new(
-            move |_request: &RequestMessage,
+            move |request: &RequestMessage,
                   _context: &RequestContext|
                   -> BoxFuture<'static, Result<Value, ProtocolError>> {
                 let prompts = prompts.clone();
+                let params_value = request.params.clone();
                 Box::pin(async move {
+                    let cursor = request_cursor(params_value)?;
                     let prompts = prompts.lock().expect("prompt registry").list_prompts();
+                    let (prompts, pagination) = list_page(
+                        prompts,
+                        |prompt| &prompt.base.name,
+                        cursor.as_ref(),
+                        page_size,
+                    );
                     let result = ListPromptsResult {
-                        pagination: PaginatedResult::default(),
+                        pagination,
                         prompts,
                     };
                     Ok(serde_json::to_value(result)?)
@@ -438,3 +972,98 @@ impl McpServer {
         Ok(())
     }
 }
+
+/// Reject a `tools/call` whose access token lacks any of the tool's required scopes.
+fn check_required_scopes(
+    name: &str,
+    options: &ToolOptions,
+    context: &RequestContext,
+) -> Result<(), ProtocolError> {
+    if options.required_scopes.is_empty() {
+        return Ok(());
+    }
+    let granted = context
+        .auth_info
+        .as_ref()
+        .map(|info| info.scopes.as_slice())
+        .unwrap_or_default();
+    let missing: Vec<&str> = options
+        .required_scopes
+        .iter()
+        .filter(|scope| !granted.contains(scope))
+        .map(String::as_str)
+        .collect();
+    if missing.is_empty() {
+        return Ok(());
+    }
+    Err(InsufficientScopeError::new()
+        .with_description(format!(
+            "tool `{name}` requires scope(s): {}",
+            missing.join(" ")
+        ))
+        .into())
+}
+
+/// Tool result reporting arguments that were rejected before the handler ran.
+fn invalid_arguments(message: impl std::fmt::Display) -> CallToolResult {
+    CallToolResult {
+        content: vec![ContentBlock::Text(TextContent::new(format!(
+            "invalid arguments: {message}"
+        )))],
+        structured_content: None,
+        is_error: Some(true),
+        meta: None,
+    }
+}
+
+/// Tool result for a call rejected by the tool's open circuit breaker.
+fn circuit_open(name: &str, reset_timeout: Duration) -> CallToolResult {
+    CallToolResult {
+        content: vec![ContentBlock::Text(TextContent::new(format!(
+            "tool `{name}` is unavailable: its circuit breaker is open after repeated failures; \
+             retry after {}ms",
+            reset_timeout.as_millis()
+        )))],
+        structured_content: None,
+        is_error: Some(true),
+        meta: None,
+    }
+}
+
+/// Tool result carrying a typed handler's output as JSON text and, for
+/// objects, as `structuredContent`.
+fn output_result(output: Value) -> CallToolResult {
+    CallToolResult {
+        content: vec![ContentBlock::Text(TextContent::new(output.to_string()))],
+        structured_content: output.is_object().then_some(output),
+        is_error: None,
+        meta: None,
+    }
+}
+
+/// Parse `tools/call` arguments into `A`; missing arguments are parsed as `{}`.
+fn parse_arguments<A: DeserializeOwned>(arguments: Option<Value>) -> Result<A, String> {
+    let arguments = arguments.unwrap_or_else(|| Value::Object(Default::default()));
+    serde_json::from_value(arguments).map_err(|err| err.to_string())
+}
+
+/// Answer arguments that do not parse with an `isError` result, so the
+/// model can retry.
+fn tool_error_arguments(message: String) -> Result<CallToolResult, ServerError> {
+    Ok(invalid_arguments(message))
+}
+
+/// Answer arguments that do not parse with an invalid-params error.
+fn invalid_params_arguments(message: String) -> Result<CallToolResult, ServerError> {
+    let error = ProtocolError::Validation(ValidationError::Failed(vec![format!(
+        "invalid arguments: {message}"
+    )]));
+    Err(error.into())
+}
+
+fn describe(err: ValidationError) -> String {
+    match err {
+        ValidationError::Failed(failures) => failures.join("; "),
+        other => other.to_string(),
+    }
+}
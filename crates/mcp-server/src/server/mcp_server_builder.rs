@@ -0,0 +1,189 @@
+use std::sync::Arc;
+
+use mcp_core::protocol::TaskStore;
+use mcp_core::types::{
+    BaseMetadata, CapabilityFlag, Icons, Implementation, Prompt, PromptCapabilities, Resource,
+    ResourceCapabilities, ResourceTemplate, Tool, ToolCapabilities,
+};
+
+use crate::server::handlers::{
+    PromptHandler, ResourceHandler, ResourceTemplateHandler, ToolHandler,
+};
+use crate::server::{McpServer, ServerError, ServerOptions};
+
+type Registration = Box<dyn FnOnce(&mut McpServer) -> Result<(), ServerError>>;
+
+/// Fluent construction of an `McpServer`, created with `McpServer::builder`.
+///
+/// Capabilities are inferred from what is registered, the way
+/// `McpServer::register_tool` and friends infer them: tools and prompts
+/// advertise `listChanged`, resources `subscribe` and `listChanged`.
+/// `enable_tools`, `enable_resources` and `enable_prompts` override the
+/// inferred flags, and also advertise the capability when nothing of that
+/// kind is registered yet.
+///
+/// ```ignore
+/// let server = McpServer::builder("prompts-server", "0.1.0")
+///     .instructions("Prompts for code review")
+///     .prompt(review_prompt, ReviewPrompt)
+///     .enable_logging()
+///     .build()?;
+/// ```
+pub struct McpServerBuilder {
+    server_info: Implementation,
+    options: ServerOptions,
+    tools: Option<bool>,
+    resources: Option<(bool, bool)>,
+    prompts: Option<bool>,
+    logging: bool,
+    registrations: Vec<Registration>,
+}
+
+impl McpServerBuilder {
+    pub fn new(name: impl Into<String>, version: impl Into<String>) -> Self {
+        Self {
+            server_info: Implementation {
+                base: BaseMetadata {
+                    name: name.into(),
+                    title: None,
+                },
+                icons: Icons { icons: None },
+                version: version.into(),
+                website_url: None,
+                description: None,
+            },
+            options: ServerOptions::default(),
+            tools: None,
+            resources: None,
+            prompts: None,
+            logging: false,
+            registrations: Vec::new(),
+        }
+    }
+
+    pub fn title(mut self, title: impl Into<String>) -> Self {
+        self.server_info.base.title = Some(title.into());
+        self
+    }
+
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.server_info.description = Some(description.into());
+        self
+    }
+
+    pub fn instructions(mut self, instructions: impl Into<String>) -> Self {
+        self.options.instructions = Some(instructions.into());
+        self
+    }
+
+    /// Replace the remaining server options. Settings made through other
+    /// builder methods before this call are overwritten.
+    pub fn options(mut self, options: ServerOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Store task-augmented requests in `store`, which also advertises the
+    /// `tasks` capability.
+    pub fn task_store(mut self, store: Arc<dyn TaskStore>) -> Self {
+        self.options
+            .protocol_options
+            .get_or_insert_with(Default::default)
+            .task_store = Some(store);
+        self
+    }
+
+    pub fn enable_tools(mut self, list_changed: bool) -> Self {
+        self.tools = Some(list_changed);
+        self
+    }
+
+    pub fn enable_resources(mut self, subscribe: bool, list_changed: bool) -> Self {
+        self.resources = Some((subscribe, list_changed));
+        self
+    }
+
+    pub fn enable_prompts(mut self, list_changed: bool) -> Self {
+        self.prompts = Some(list_changed);
+        self
+    }
+
+    /// Advertise the `logging` capability and accept `logging/setLevel`.
+    pub fn enable_logging(mut self) -> Self {
+        self.logging = true;
+        self
+    }
+
+    pub fn tool(mut self, tool: Tool, handler: impl ToolHandler) -> Self {
+        self.registrations
+            .push(Box::new(move |server| server.register_tool(tool, handler)));
+        self
+    }
+
+    pub fn resource(mut self, resource: Resource, handler: impl ResourceHandler) -> Self {
+        self.registrations.push(Box::new(move |server| {
+            server.register_resource(resource, handler)
+        }));
+        self
+    }
+
+    pub fn resource_template(
+        mut self,
+        template: ResourceTemplate,
+        handler: impl ResourceTemplateHandler,
+    ) -> Self {
+        self.registrations.push(Box::new(move |server| {
+            server.register_resource_template(template, handler)
+        }));
+        self
+    }
+
+    pub fn prompt(mut self, prompt: Prompt, handler: impl PromptHandler) -> Self {
+        self.registrations.push(Box::new(move |server| {
+            server.register_prompt(prompt, handler)
+        }));
+        self
+    }
+
+    pub fn build(self) -> Result<McpServer, ServerError> {
+        let mut server = McpServer::new(self.server_info, self.options);
+        for register in self.registrations {
+            register(&mut server)?;
+        }
+
+        let mut capabilities = server.server().get_capabilities();
+        if let Some(list_changed) = self.tools {
+            capabilities.tools = Some(ToolCapabilities {
+                list_changed: Some(list_changed),
+            });
+        }
+        if let Some((subscribe, list_changed)) = self.resources {
+            capabilities.resources = Some(ResourceCapabilities {
+                subscribe: Some(subscribe),
+                list_changed: Some(list_changed),
+            });
+        }
+        if let Some(list_changed) = self.prompts {
+            capabilities.prompts = Some(PromptCapabilities {
+                list_changed: Some(list_changed),
+            });
+        }
+        if self.logging {
+            capabilities.logging = Some(CapabilityFlag::default());
+        }
+        server.server_mut().set_capabilities(capabilities)?;
+
+        // Capabilities enabled without registrations still answer the list
+        // requests; handlers can only be added once the capability is set
+        if self.tools.is_some() {
+            server.ensure_tool_handlers()?;
+        }
+        if self.resources.is_some() {
+            server.ensure_resource_handlers()?;
+        }
+        if self.prompts.is_some() {
+            server.ensure_prompt_handlers()?;
+        }
+        Ok(server)
+    }
+}
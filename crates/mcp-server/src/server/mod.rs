@@ -1,15 +1,67 @@
+pub mod audit_log_middleware;
+pub mod circuit_breaker;
+pub mod circuit_breaker_config;
+pub mod circuit_state;
 pub mod handlers;
+pub(crate) mod in_flight_requests;
 pub mod in_memory_task_store;
+pub mod latency_histogram;
+pub(crate) mod list_page;
 pub mod mcp_server;
+pub mod mcp_server_builder;
+pub mod notification_sender;
+pub mod pending_requests;
+#[cfg(feature = "prometheus")]
+pub mod prometheus_metrics;
 pub mod registries;
+pub mod request_sender;
+pub mod result_transform;
 pub mod server;
 pub mod server_capability_checker;
 pub mod server_error;
+pub mod server_metrics;
 pub mod server_options;
 pub mod server_state;
+pub mod summary_clock;
+pub(crate) mod summary_collector;
+pub mod summary_kind;
+pub mod summary_report;
+pub mod summary_row;
+pub mod tool_middleware;
+pub mod tool_options;
+#[cfg(feature = "metrics")]
+pub(crate) mod tool_stats;
+#[cfg(feature = "metrics")]
+pub mod tool_stats_row;
+#[cfg(feature = "tokio")]
+pub mod tokio_task_spawner;
 
+pub use audit_log_middleware::AuditLogMiddleware;
+pub use circuit_breaker::CircuitBreaker;
+pub use circuit_breaker_config::CircuitBreakerConfig;
+pub use circuit_state::CircuitState;
+pub use handlers::{BoxedToolHandler, ToolInput, TypedTool, input_schema_for};
 pub use in_memory_task_store::InMemoryTaskStore;
+pub use latency_histogram::LatencyHistogram;
 pub use mcp_server::McpServer;
+pub use mcp_server_builder::McpServerBuilder;
+pub use notification_sender::NotificationSender;
+pub use pending_requests::PendingRequests;
+#[cfg(feature = "prometheus")]
+pub use prometheus_metrics::PrometheusMetrics;
+pub use request_sender::RequestSender;
+pub use result_transform::ResultTransform;
 pub use server::Server;
 pub use server_error::ServerError;
-pub use server_options::ServerOptions;
+pub use server_metrics::ServerMetrics;
+pub use server_options::{DEFAULT_DEDUP_MAX_ENTRIES, DEFAULT_PEER_REQUEST_TIMEOUT, ServerOptions};
+pub use summary_clock::SummaryClock;
+pub use summary_kind::SummaryKind;
+pub use summary_report::SummaryReport;
+pub use summary_row::SummaryRow;
+pub use tool_middleware::ToolMiddleware;
+pub use tool_options::ToolOptions;
+#[cfg(feature = "metrics")]
+pub use tool_stats_row::ToolStatsRow;
+#[cfg(feature = "tokio")]
+pub use tokio_task_spawner::tokio_task_spawner;
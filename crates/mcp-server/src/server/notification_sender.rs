@@ -0,0 +1,10 @@
+use std::sync::Arc;
+
+use mcp_core::types::NotificationMessage;
+
+/// Callback installed by a transport to deliver server-initiated notifications.
+///
+/// Receives the target session id and the notification to deliver. `None`
+/// marks a notification not tied to a session, such as a list change, which
+/// transports with several sessions deliver to all of them or drop.
+pub type NotificationSender = Arc<dyn Fn(Option<String>, NotificationMessage) + Send + Sync>;
@@ -0,0 +1,70 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use futures::channel::oneshot;
+
+use mcp_core::types::{MessageId, ResultMessage};
+
+/// Requests are told apart by the session they were sent to and their id.
+type PendingKey = (Option<String>, MessageId);
+
+/// Server-initiated requests waiting for the client's response.
+///
+/// Each request belongs to the session it was sent to, and only a response
+/// arriving on that session completes it: request ids are sequential, so a
+/// client could otherwise answer another session's request by guessing one.
+#[derive(Default)]
+pub struct PendingRequests {
+    counter: AtomicU64,
+    waiting: Mutex<HashMap<PendingKey, oneshot::Sender<ResultMessage>>>,
+}
+
+impl PendingRequests {
+    /// Allocate a request id for a request sent to `session_id` and a
+    /// receiver for its response.
+    pub fn register(
+        &self,
+        session_id: Option<String>,
+    ) -> (MessageId, oneshot::Receiver<ResultMessage>) {
+        let sequence = self.counter.fetch_add(1, Ordering::SeqCst) + 1;
+        let id = MessageId::String(format!("server-{sequence}"));
+        let (tx, rx) = oneshot::channel();
+        self.waiting
+            .lock()
+            .expect("pending requests")
+            .insert((session_id, id.clone()), tx);
+        (id, rx)
+    }
+
+    /// Deliver a response received on `session_id` to its waiting request.
+    ///
+    /// Returns false when no request with this id is pending for the session.
+    pub fn complete(&self, session_id: Option<String>, response: ResultMessage) -> bool {
+        let sender = self
+            .waiting
+            .lock()
+            .expect("pending requests")
+            .remove(&(session_id, response.id.clone()));
+        match sender {
+            Some(sender) => sender.send(response).is_ok(),
+            None => false,
+        }
+    }
+
+    /// Stop waiting for a response, e.g. after a timeout.
+    pub fn cancel(&self, session_id: Option<String>, id: &MessageId) {
+        self.waiting
+            .lock()
+            .expect("pending requests")
+            .remove(&(session_id, id.clone()));
+    }
+
+    pub fn len(&self) -> usize {
+        self.waiting.lock().expect("pending requests").len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
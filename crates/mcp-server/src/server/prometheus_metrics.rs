@@ -0,0 +1,326 @@
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use mcp_core::protocol::TaskStatusChange;
+use mcp_core::types::TaskStatus;
+
+use crate::server::server_metrics::ServerMetrics;
+
+/// Upper bounds of the latency histogram buckets, in seconds; the defaults
+/// of the Prometheus client libraries.
+const BUCKETS: [f64; 11] = [
+    0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0,
+];
+
+/// [`ServerMetrics`] recorder rendering the Prometheus text format.
+///
+/// Install it as `ServerOptions::metrics`; the axum router then serves it
+/// on `/metrics`. Exported series:
+///
+/// - `mcp_requests_total{method,status}` and `mcp_requests_in_flight{method}`
+/// - `mcp_request_duration_seconds{method}` histogram
+/// - `mcp_tool_calls_total{tool,status}` and
+///   `mcp_tool_call_duration_seconds{tool}` histogram
+/// - `mcp_sessions_created_total`, `mcp_sessions_expired_total` and
+///   `mcp_sessions_active`
+/// - `mcp_task_status_changes_total{status}`
+///
+/// `status` is `ok` or `error` for requests and tool calls.
+#[derive(Default)]
+pub struct PrometheusMetrics {
+    state: Mutex<MetricsState>,
+}
+
+#[derive(Default)]
+struct MetricsState {
+    requests: BTreeMap<(String, &'static str), u64>,
+    in_flight: BTreeMap<String, u64>,
+    request_durations: BTreeMap<String, Histogram>,
+    tool_calls: BTreeMap<(String, &'static str), u64>,
+    tool_durations: BTreeMap<String, Histogram>,
+    sessions_created: u64,
+    sessions_expired: u64,
+    task_changes: BTreeMap<&'static str, u64>,
+}
+
+/// Cumulative-bucket histogram as Prometheus exposes it.
+#[derive(Default)]
+struct Histogram {
+    /// Observations per bucket of `BUCKETS`, not cumulated.
+    counts: [u64; BUCKETS.len()],
+    count: u64,
+    sum: f64,
+}
+
+impl Histogram {
+    fn observe(&mut self, latency: Duration) {
+        let seconds = latency.as_secs_f64();
+        if let Some(bucket) = BUCKETS.iter().position(|bound| seconds <= *bound) {
+            self.counts[bucket] += 1;
+        }
+        self.count += 1;
+        self.sum += seconds;
+    }
+}
+
+impl PrometheusMetrics {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn state(&self) -> std::sync::MutexGuard<'_, MetricsState> {
+        self.state.lock().expect("prometheus metrics")
+    }
+}
+
+impl ServerMetrics for PrometheusMetrics {
+    fn on_request_start(&self, method: &str) {
+        *self
+            .state()
+            .in_flight
+            .entry(method.to_string())
+            .or_default() += 1;
+    }
+
+    fn on_request_finish(&self, method: &str, latency: Duration, is_error: bool) {
+        let mut state = self.state();
+        if let Some(in_flight) = state.in_flight.get_mut(method) {
+            *in_flight = in_flight.saturating_sub(1);
+        }
+        *state
+            .requests
+            .entry((method.to_string(), outcome(is_error)))
+            .or_default() += 1;
+        state
+            .request_durations
+            .entry(method.to_string())
+            .or_default()
+            .observe(latency);
+    }
+
+    fn on_tool_call(&self, tool: &str, latency: Duration, is_error: bool) {
+        let mut state = self.state();
+        *state
+            .tool_calls
+            .entry((tool.to_string(), outcome(is_error)))
+            .or_default() += 1;
+        state
+            .tool_durations
+            .entry(tool.to_string())
+            .or_default()
+            .observe(latency);
+    }
+
+    fn on_session_created(&self) {
+        self.state().sessions_created += 1;
+    }
+
+    fn on_session_expired(&self) {
+        self.state().sessions_expired += 1;
+    }
+
+    fn on_task_state_change(&self, change: &TaskStatusChange) {
+        *self
+            .state()
+            .task_changes
+            .entry(status_label(&change.task.status))
+            .or_default() += 1;
+    }
+
+    fn render(&self) -> Option<String> {
+        let state = self.state();
+        let mut out = String::new();
+
+        header(
+            &mut out,
+            "mcp_requests_total",
+            "counter",
+            "Requests handled, by method and outcome.",
+        );
+        for ((method, status), value) in &state.requests {
+            let _ = writeln!(
+                out,
+                "mcp_requests_total{{method=\"{}\",status=\"{status}\"}} {value}",
+                escape(method)
+            );
+        }
+        header(
+            &mut out,
+            "mcp_requests_in_flight",
+            "gauge",
+            "Requests currently being handled.",
+        );
+        for (method, value) in &state.in_flight {
+            let _ = writeln!(
+                out,
+                "mcp_requests_in_flight{{method=\"{}\"}} {value}",
+                escape(method)
+            );
+        }
+        header(
+            &mut out,
+            "mcp_request_duration_seconds",
+            "histogram",
+            "Request handling latency, by method.",
+        );
+        for (method, histogram) in &state.request_durations {
+            let label = format!("method=\"{}\"", escape(method));
+            write_histogram(&mut out, "mcp_request_duration_seconds", &label, histogram);
+        }
+
+        header(
+            &mut out,
+            "mcp_tool_calls_total",
+            "counter",
+            "Tool calls, by tool and outcome.",
+        );
+        for ((tool, status), value) in &state.tool_calls {
+            let _ = writeln!(
+                out,
+                "mcp_tool_calls_total{{tool=\"{}\",status=\"{status}\"}} {value}",
+                escape(tool)
+            );
+        }
+        header(
+            &mut out,
+            "mcp_tool_call_duration_seconds",
+            "histogram",
+            "Tool call latency, by tool.",
+        );
+        for (tool, histogram) in &state.tool_durations {
+            let label = format!("tool=\"{}\"", escape(tool));
+            write_histogram(
+                &mut out,
+                "mcp_tool_call_duration_seconds",
+                &label,
+                histogram,
+            );
+        }
+
+        header(
+            &mut out,
+            "mcp_sessions_created_total",
+            "counter",
+            "Sessions issued.",
+        );
+        let _ = writeln!(out, "mcp_sessions_created_total {}", state.sessions_created);
+        header(
+            &mut out,
+            "mcp_sessions_expired_total",
+            "counter",
+            "Sessions that ended.",
+        );
+        let _ = writeln!(out, "mcp_sessions_expired_total {}", state.sessions_expired);
+        header(
+            &mut out,
+            "mcp_sessions_active",
+            "gauge",
+            "Sessions currently open.",
+        );
+        let _ = writeln!(
+            out,
+            "mcp_sessions_active {}",
+            state
+                .sessions_created
+                .saturating_sub(state.sessions_expired)
+        );
+
+        header(
+            &mut out,
+            "mcp_task_status_changes_total",
+            "counter",
+            "Task status transitions, by new status.",
+        );
+        for (status, value) in &state.task_changes {
+            let _ = writeln!(
+                out,
+                "mcp_task_status_changes_total{{status=\"{status}\"}} {value}"
+            );
+        }
+        Some(out)
+    }
+}
+
+fn outcome(is_error: bool) -> &'static str {
+    if is_error { "error" } else { "ok" }
+}
+
+fn status_label(status: &TaskStatus) -> &'static str {
+    match status {
+        TaskStatus::Working => "working",
+        TaskStatus::InputRequired => "input_required",
+        TaskStatus::Completed => "completed",
+        TaskStatus::Failed => "failed",
+        TaskStatus::Cancelled => "cancelled",
+    }
+}
+
+fn header(out: &mut String, name: &str, kind: &str, help: &str) {
+    let _ = writeln!(out, "# HELP {name} {help}");
+    let _ = writeln!(out, "# TYPE {name} {kind}");
+}
+
+fn write_histogram(out: &mut String, name: &str, label: &str, histogram: &Histogram) {
+    let mut cumulative = 0;
+    for (bound, count) in BUCKETS.iter().zip(histogram.counts) {
+        cumulative += count;
+        let _ = writeln!(out, "{name}_bucket{{{label},le=\"{bound}\"}} {cumulative}");
+    }
+    let _ = writeln!(
+        out,
+        "{name}_bucket{{{label},le=\"+Inf\"}} {}",
+        histogram.count
+    );
+    let _ = writeln!(out, "{name}_sum{{{label}}} {}", histogram.sum);
+    let _ = writeln!(out, "{name}_count{{{label}}} {}", histogram.count);
+}
+
+/// Escape a label value: backslash, double quote and line feed.
+fn escape(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn histogram_buckets_are_cumulative() {
+        let metrics = PrometheusMetrics::new();
+        metrics.on_request_finish("ping", Duration::from_millis(3), false);
+        metrics.on_request_finish("ping", Duration::from_millis(300), true);
+        metrics.on_request_finish("ping", Duration::from_secs(60), false);
+
+        let text = metrics.render().unwrap();
+        assert!(
+            text.contains("mcp_request_duration_seconds_bucket{method=\"ping\",le=\"0.005\"} 1\n")
+        );
+        assert!(
+            text.contains("mcp_request_duration_seconds_bucket{method=\"ping\",le=\"0.5\"} 2\n")
+        );
+        assert!(
+            text.contains("mcp_request_duration_seconds_bucket{method=\"ping\",le=\"10\"} 2\n")
+        );
+        assert!(
+            text.contains("mcp_request_duration_seconds_bucket{method=\"ping\",le=\"+Inf\"} 3\n")
+        );
+        assert!(text.contains("mcp_requests_total{method=\"ping\",status=\"error\"} 1\n"));
+        assert!(text.contains("mcp_requests_total{method=\"ping\",status=\"ok\"} 2\n"));
+    }
+
+    #[test]
+    fn label_values_are_escaped() {
+        let metrics = PrometheusMetrics::new();
+        metrics.on_tool_call("say \"hi\"\n", Duration::ZERO, false);
+
+        let text = metrics.render().unwrap();
+        assert!(
+            text.contains("mcp_tool_calls_total{tool=\"say \\\"hi\\\"\\n\",status=\"ok\"} 1\n")
+        );
+    }
+}
@@ -1,7 +1,9 @@
 pub mod prompt_registry;
 pub mod resource_registry;
+pub mod subscription_registry;
 pub mod tool_registry;
 
 pub use prompt_registry::PromptRegistry;
 pub use resource_registry::ResourceRegistry;
+pub use subscription_registry::SubscriptionRegistry;
 pub use tool_registry::ToolRegistry;
@@ -2,15 +2,24 @@ use std::collections::HashMap;
 use std::sync::Arc;
 
 use mcp_core::types::{Resource, ResourceTemplate};
+use mcp_core::uri_template::UriTemplate;
 
-use crate::server::handlers::ResourceHandler;
+use crate::server::handlers::{ResourceHandler, ResourceTemplateHandler};
+
+/// A registered template with its parsed `uriTemplate` and handler.
+struct TemplateEntry {
+    template: ResourceTemplate,
+    uri_template: UriTemplate,
+    handler: Arc<dyn ResourceTemplateHandler>,
+}
 
 /// In-memory registry for resources and resource templates.
 #[derive(Default)]
 pub struct ResourceRegistry {
     resources: HashMap<String, Resource>,
     handlers: HashMap<String, Arc<dyn ResourceHandler>>,
-    templates: HashMap<String, ResourceTemplate>,
+    /// Kept in registration order, which is the order URIs are matched in.
+    templates: Vec<TemplateEntry>,
 }
 
 impl ResourceRegistry {
@@ -20,9 +29,27 @@ impl ResourceRegistry {
         self.handlers.insert(uri, Arc::new(handler));
     }
 
-    pub fn register_template(&mut self, template: ResourceTemplate) {
-        let name = template.base.name.clone();
-        self.templates.insert(name, template);
+    /// Register a template under its name, replacing a template registered
+    /// earlier with the same name.
+    pub fn register_template(
+        &mut self,
+        template: ResourceTemplate,
+        uri_template: UriTemplate,
+        handler: impl ResourceTemplateHandler,
+    ) {
+        let entry = TemplateEntry {
+            template,
+            uri_template,
+            handler: Arc::new(handler),
+        };
+        let existing = self
+            .templates
+            .iter_mut()
+            .find(|existing| existing.template.base.name == entry.template.base.name);
+        match existing {
+            Some(existing) => *existing = entry,
+            None => self.templates.push(entry),
+        }
     }
 
     pub fn list_resources(&self) -> Vec<Resource> {
@@ -30,10 +57,25 @@ impl ResourceRegistry {
     }
 
     pub fn list_templates(&self) -> Vec<ResourceTemplate> {
-        self.templates.values().cloned().collect()
+        self.templates
+            .iter()
+            .map(|entry| entry.template.clone())
+            .collect()
     }
 
     pub fn handler(&self, uri: &str) -> Option<Arc<dyn ResourceHandler>> {
         self.handlers.get(uri).cloned()
     }
+
+    /// Handler of the first template matching `uri`, with the variables
+    /// extracted from it.
+    pub fn template_handler(
+        &self,
+        uri: &str,
+    ) -> Option<(Arc<dyn ResourceTemplateHandler>, HashMap<String, String>)> {
+        self.templates.iter().find_map(|entry| {
+            let variables = entry.uri_template.match_uri(uri)?;
+            Some((Arc::clone(&entry.handler), variables))
+        })
+    }
 }
@@ -0,0 +1,39 @@
+use std::collections::{HashMap, HashSet};
+
+/// In-memory registry of the resources each session subscribed to.
+///
+/// Sessions are keyed like `RequestContext::session_id`, so `None` stands
+/// for the single session of a transport without session ids.
+#[derive(Default)]
+pub struct SubscriptionRegistry {
+    sessions: HashMap<Option<String>, HashSet<String>>,
+}
+
+impl SubscriptionRegistry {
+    pub fn subscribe(&mut self, session_id: Option<String>, uri: impl Into<String>) {
+        self.sessions.entry(session_id).or_default().insert(uri.into());
+    }
+
+    pub fn unsubscribe(&mut self, session_id: &Option<String>, uri: &str) {
+        if let Some(uris) = self.sessions.get_mut(session_id) {
+            uris.remove(uri);
+            if uris.is_empty() {
+                self.sessions.remove(session_id);
+            }
+        }
+    }
+
+    /// Drop every subscription of a closed session.
+    pub fn remove_session(&mut self, session_id: &Option<String>) {
+        self.sessions.remove(session_id);
+    }
+
+    /// Sessions subscribed to `uri`.
+    pub fn subscribers(&self, uri: &str) -> Vec<Option<String>> {
+        self.sessions
+            .iter()
+            .filter(|(_, uris)| uris.contains(uri))
+            .map(|(session_id, _)| session_id.clone())
+            .collect()
+    }
+}
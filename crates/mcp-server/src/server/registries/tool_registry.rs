@@ -3,20 +3,55 @@ use std::sync::Arc;
 
 use mcp_core::types::Tool;
 
-use crate::server::handlers::ToolHandler;
+use crate::server::handlers::{BoxedToolHandler, ToolHandler};
+use crate::server::{CircuitBreaker, ToolOptions};
 
 /// In-memory registry for tools.
 #[derive(Default)]
 pub struct ToolRegistry {
     tools: HashMap<String, Tool>,
     handlers: HashMap<String, Arc<dyn ToolHandler>>,
+    options: HashMap<String, ToolOptions>,
+    breakers: HashMap<String, Arc<CircuitBreaker>>,
 }
 
 impl ToolRegistry {
     pub fn register_tool(&mut self, tool: Tool, handler: impl ToolHandler) {
+        self.register_tool_with_options(tool, ToolOptions::default(), handler);
+    }
+
+    pub fn register_tool_with_options(
+        &mut self,
+        tool: Tool,
+        options: ToolOptions,
+        handler: impl ToolHandler,
+    ) {
+        self.insert(tool, options, Arc::new(handler));
+    }
+
+    pub fn register_boxed_tool(&mut self, tool: Tool, handler: BoxedToolHandler) {
+        self.insert(tool, ToolOptions::default(), Arc::from(handler));
+    }
+
+    fn insert(&mut self, tool: Tool, options: ToolOptions, handler: Arc<dyn ToolHandler>) {
         let name = tool.base.name.clone();
         self.tools.insert(name.clone(), tool);
-        self.handlers.insert(name, Arc::new(handler));
+        self.handlers.insert(name.clone(), handler);
+        // Re-registering a tool starts it with a closed circuit
+        match options.circuit_breaker {
+            Some(config) => {
+                self.breakers
+                    .insert(name.clone(), Arc::new(CircuitBreaker::new(config)));
+            }
+            None => {
+                self.breakers.remove(&name);
+            }
+        }
+        self.options.insert(name, options);
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.tools.contains_key(name)
     }
 
     pub fn list_tools(&self) -> Vec<Tool> {
@@ -30,4 +65,12 @@ impl ToolRegistry {
     pub fn handler(&self, name: &str) -> Option<Arc<dyn ToolHandler>> {
         self.handlers.get(name).cloned()
     }
+
+    pub fn options(&self, name: &str) -> Option<ToolOptions> {
+        self.options.get(name).cloned()
+    }
+
+    pub fn breaker(&self, name: &str) -> Option<Arc<CircuitBreaker>> {
+        self.breakers.get(name).cloned()
+    }
 }
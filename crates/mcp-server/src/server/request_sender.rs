@@ -0,0 +1,10 @@
+use std::sync::Arc;
+
+use mcp_core::types::RequestMessage;
+
+/// Callback installed by a transport to deliver server-initiated requests.
+///
+/// Receives the target session id (`None` for single-connection transports
+/// such as stdio) and the request to deliver. Responses are fed back through
+/// `Server::handle_response`.
+pub type RequestSender = Arc<dyn Fn(Option<String>, RequestMessage) + Send + Sync>;
@@ -0,0 +1,7 @@
+use std::sync::Arc;
+
+use mcp_core::types::CallToolResult;
+
+/// Hook applied to every successful `tools/call` result before it is sent,
+/// e.g. to redact secrets from text content.
+pub type ResultTransform = Arc<dyn Fn(&mut CallToolResult) + Send + Sync>;
@@ -1,41 +1,71 @@
 use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 
 use futures::future::BoxFuture;
+use futures::{FutureExt, select};
 use schemars::schema::RootSchema;
 use serde_json::Value;
 
 use mcp_core::protocol::{
-    NotificationContext, NotificationHandler, Protocol, ProtocolError, RequestContext,
-    RequestHandler, TaskStore,
+    CancellationToken, NotificationContext, NotificationHandler, Peer, PeerNotifyFn, Protocol,
+    ProtocolError, ReplayFilter, RequestContext, RequestHandler, SessionData, TaskStatusChange,
+    TaskStore,
 };
+use mcp_core::auth::{AuthInfo, InsufficientScopeError};
 use mcp_core::schema::JsonSchemaValidator;
 use mcp_core::types::{
     CancelTaskRequestParams, CancelTaskResult, CapabilityFlag, ClientCapabilities,
-    CreateMessageRequestParams, ElicitRequestFormParams, ElicitRequestUrlParams,
+    CreateMessageRequestParams, ElicitRequestFormParams, ElicitRequestUrlParams, ElicitationMode,
     ElicitationCompleteNotificationParams, ErrorCode, ErrorObject, GetTaskPayloadRequestParams,
-    GetTaskRequestParams, GetTaskResult, InitializeRequestParams, InitializeResult, ListTasksResult,
-    MessageId, NotificationMessage, PaginatedRequestParams, PaginatedResult, RequestMessage,
-    ResultMessage, SUPPORTED_PROTOCOL_VERSIONS, ServerCapabilities, ServerTasksCapability,
-    ServerTasksRequestCapabilities, ServerTasksToolCapabilities, SetLevelRequestParams, Task,
+    GetTaskRequestParams, GetTaskResult, InitializeRequestParams, InitializeResult,
+    ListTasksRequestParams, ListTasksResult, LoggingLevel, LoggingMessageParams, MessageId,
+    NotificationMessage, NotificationParams, PaginatedResult,
+    RelatedTaskMetadata, RequestMessage, RequestMeta, ResultMessage, SUPPORTED_PROTOCOL_VERSIONS,
+    ServerCapabilities, ServerTasksCapability, ServerTasksRequestCapabilities,
+    ServerTasksToolCapabilities, SetLevelRequestParams, Task, TaskStatus,
     TaskStatusNotificationParams,
 };
 
+use crate::auth::ApiKeyStore;
 use crate::server::handlers::{NotificationHandlerFn, RequestHandlerFn};
+use crate::server::in_flight_requests::InFlightRequests;
+use crate::server::notification_sender::NotificationSender;
+use crate::server::pending_requests::PendingRequests;
+use crate::server::request_sender::RequestSender;
 use crate::server::server_capability_checker::ServerCapabilityChecker;
 use crate::server::server_error::ServerError;
-use crate::server::server_options::ServerOptions;
+use crate::server::server_metrics::ServerMetrics;
+use crate::server::server_options::{DEFAULT_PEER_REQUEST_TIMEOUT, ServerOptions};
 use crate::server::server_state::ServerState;
+use crate::server::summary_collector::{RequestRecord, SummaryCollector, serialized_size};
+use crate::server::summary_report::SummaryReport;
+
+/// Error message for requests refused or cut short by `shutdown` or `drain`.
+const SHUTDOWN_MESSAGE: &str = "server shutting down";
 
 /// Low-level MCP server wrapper around the protocol runtime.
 pub struct Server {
     protocol: Protocol,
     state: Arc<Mutex<ServerState>>,
-    server_info: mcp_core::types::Implementation,
+    server_info: Arc<Mutex<mcp_core::types::Implementation>>,
     on_initialized: Arc<Mutex<Option<Arc<dyn Fn() + Send + Sync>>>>,
+    notification_sender: Arc<Mutex<Option<NotificationSender>>>,
+    request_sender: Arc<Mutex<Option<RequestSender>>>,
+    pending_requests: Arc<PendingRequests>,
+    peer_request_timeout: Duration,
+    log_handler_errors: bool,
     task_store: Option<Arc<dyn TaskStore>>,
+    session_data: Arc<SessionData>,
     logging_handler_registered: bool,
     task_handlers_registered: bool,
+    summary: Option<Arc<SummaryCollector>>,
+    metrics: Option<Arc<dyn ServerMetrics>>,
+    api_key_store: Option<Arc<dyn ApiKeyStore>>,
+    shutting_down: AtomicBool,
+    in_flight: InFlightRequests,
+    shutdown_token: CancellationToken,
 }
 
 impl Server {
@@ -46,10 +76,15 @@ impl Server {
             options.instructions.clone(),
         )));
 
-        let mut protocol = Protocol::with_options(
-            JsonSchemaValidator::default(),
-            options.protocol_options.clone().unwrap_or_default(),
-        );
+        let mut protocol_options = options.protocol_options.clone().unwrap_or_default();
+        if options.max_concurrent_tasks.is_some() {
+            protocol_options.max_concurrent_tasks = options.max_concurrent_tasks;
+        }
+        if options.dedup_window.is_some() {
+            protocol_options.idempotency_window = options.dedup_window;
+            protocol_options.idempotency_capacity = Some(options.dedup_max_entries);
+        }
+        let mut protocol = Protocol::with_options(JsonSchemaValidator::default(), protocol_options);
 
         let task_store = options
             .protocol_options
@@ -60,28 +95,271 @@ impl Server {
             .set_capability_checker(Some(Arc::new(ServerCapabilityChecker::new(state.clone()))));
 
         let on_initialized = Arc::new(Mutex::new(None));
+        let notification_sender = Arc::new(Mutex::new(None));
 
         let mut server = Self {
             protocol,
             state,
-            server_info,
+            server_info: Arc::new(Mutex::new(server_info)),
             on_initialized,
+            notification_sender,
+            request_sender: Arc::new(Mutex::new(None)),
+            pending_requests: Arc::new(PendingRequests::default()),
+            peer_request_timeout: options
+                .peer_request_timeout
+                .unwrap_or(DEFAULT_PEER_REQUEST_TIMEOUT),
+            log_handler_errors: options.log_handler_errors,
             task_store,
+            session_data: Arc::new(SessionData::default()),
             logging_handler_registered: false,
             task_handlers_registered: false,
+            summary: options
+                .collect_summary
+                .then(|| Arc::new(SummaryCollector::new(options.summary_clock.clone()))),
+            metrics: options.metrics.clone(),
+            api_key_store: options.api_key_store.clone(),
+            shutting_down: AtomicBool::new(false),
+            in_flight: InFlightRequests::default(),
+            shutdown_token: CancellationToken::default(),
         };
 
         server.register_initialize_handlers();
+        server.register_ping_handler();
         server.register_logging_handler_if_needed();
         server.register_task_handlers_if_needed();
 
         server
     }
 
+    /// Process-wide session storage used for requests handled without a
+    /// transport session store (e.g. stdio).
+    pub fn session_data(&self) -> Arc<SessionData> {
+        Arc::clone(&self.session_data)
+    }
+
+    /// Implementation info returned by `initialize`.
+    pub fn server_info(&self) -> mcp_core::types::Implementation {
+        self.server_info.lock().expect("server info").clone()
+    }
+
+    /// Replace the implementation info returned by subsequent `initialize` requests.
+    pub fn set_server_info(&self, server_info: mcp_core::types::Implementation) {
+        *self.server_info.lock().expect("server info") = server_info;
+    }
+
+    /// Replace the instructions returned by subsequent `initialize` requests.
+    pub fn set_instructions(&self, instructions: Option<String>) {
+        self.state.lock().expect("server state").instructions = instructions;
+    }
+
     pub fn set_on_initialized(&mut self, callback: Option<Arc<dyn Fn() + Send + Sync>>) {
         *self.on_initialized.lock().expect("init callback") = callback;
     }
 
+    /// Install the transport callback used to push server-initiated notifications.
+    pub fn set_notification_sender(&self, sender: Option<NotificationSender>) {
+        *self.notification_sender.lock().expect("notification sender") = sender;
+    }
+
+    /// Push a notification to a session through the installed transport.
+    /// Returns `false` when no transport has installed a sender.
+    pub fn send_notification(
+        &self,
+        session_id: Option<String>,
+        notification: NotificationMessage,
+    ) -> bool {
+        send_via(&self.notification_sender, session_id, notification)
+    }
+
+    /// Push a notification to every session through the installed
+    /// transport, e.g. `notifications/tools/list_changed`. Returns `false`
+    /// when no transport has installed a sender.
+    pub fn broadcast_notification(&self, notification: NotificationMessage) -> bool {
+        send_via(&self.notification_sender, None, notification)
+    }
+
+    /// Install the transport callback used to send server-initiated requests.
+    pub fn set_request_sender(&self, sender: Option<RequestSender>) {
+        *self.request_sender.lock().expect("request sender") = sender;
+    }
+
+    /// Route a client response received on `session_id` to the
+    /// server-initiated request awaiting it. Returns `false` when no request
+    /// with that id was sent to the session.
+    pub fn handle_response(&self, response: ResultMessage, session_id: Option<String>) -> bool {
+        self.pending_requests.complete(session_id, response)
+    }
+
+    /// Number of task-augmented requests waiting for a concurrency slot.
+    pub fn task_queue_depth(&self) -> usize {
+        self.protocol.task_queue_depth()
+    }
+
+    /// Filter deciding which requests may be answered from the idempotency
+    /// cache; see `ProtocolOptions::idempotency_window`.
+    pub fn replay_filter(&self) -> Option<ReplayFilter> {
+        self.protocol.replay_filter()
+    }
+
+    /// Override which requests may be answered from the idempotency cache.
+    pub fn set_replay_filter(&mut self, filter: Option<ReplayFilter>) {
+        self.protocol.set_replay_filter(filter);
+    }
+
+    /// Server-initiated requests still waiting for a response.
+    pub fn pending_requests(&self) -> &PendingRequests {
+        &self.pending_requests
+    }
+
+    /// Whether `shutdown` or `drain` has been called.
+    pub fn is_shutting_down(&self) -> bool {
+        self.shutting_down.load(Ordering::SeqCst)
+    }
+
+    /// Token cancelled once `shutdown` has drained the server. Transports
+    /// end their open streams on it.
+    pub fn shutdown_token(&self) -> CancellationToken {
+        self.shutdown_token.clone()
+    }
+
+    /// Stop accepting requests and drain the ones in flight.
+    ///
+    /// From now on new requests are answered with a "server shutting down"
+    /// error; responses to server-initiated requests are still routed.
+    /// Handlers and background tasks get up to `grace` to finish. Tasks
+    /// still running then are marked failed and aborted, remaining handlers
+    /// are cancelled, and the shutdown token is cancelled.
+    pub async fn shutdown(&self, grace: Duration) {
+        self.stop_and_wait(grace).await;
+
+        let running_tasks = self.protocol.running_tasks();
+        let task_limiter = self.protocol.task_limiter();
+        for task_id in running_tasks.task_ids() {
+            if let Some(store) = self.task_store.as_ref() {
+                let error =
+                    ErrorObject::new(ErrorCode::ConnectionClosed as i32, SHUTDOWN_MESSAGE, None);
+                let _ = store.set_task_result(&task_id, Err(error)).await;
+            }
+            if let Some(limiter) = task_limiter.as_ref() {
+                limiter.remove_queued(&task_id);
+            }
+            running_tasks.cancel(&task_id, Duration::ZERO).await;
+        }
+
+        self.shutdown_token.cancel();
+    }
+
+    /// Stop accepting requests and wait for the dispatched ones to finish.
+    ///
+    /// Like `shutdown`, new requests are refused from now on and handlers
+    /// and background tasks get up to `timeout` to finish. Tasks still
+    /// running then are cancelled (`TaskStatus::Cancelled`) rather than
+    /// failed, and the shutdown token is left alone so the transport decides
+    /// when to close its streams.
+    pub async fn drain(&self, timeout: Duration) {
+        self.stop_and_wait(timeout).await;
+
+        let running_tasks = self.protocol.running_tasks();
+        let task_limiter = self.protocol.task_limiter();
+        for task_id in running_tasks.task_ids() {
+            if let Some(store) = self.task_store.as_ref() {
+                let _ = store.cancel_task(&task_id).await;
+            }
+            if let Some(limiter) = task_limiter.as_ref() {
+                limiter.remove_queued(&task_id);
+            }
+            running_tasks.cancel(&task_id, Duration::ZERO).await;
+        }
+    }
+
+    /// Refuse new requests, then wait until no handler or task is running or
+    /// `timeout` elapses.
+    async fn stop_and_wait(&self, timeout: Duration) {
+        self.shutting_down.store(true, Ordering::SeqCst);
+
+        let running_tasks = self.protocol.running_tasks();
+        let drained = futures::future::join(self.in_flight.idle(), running_tasks.wait_until_empty());
+        let drained = drained.fuse();
+        let delay = futures_timer::Delay::new(timeout).fuse();
+        futures::pin_mut!(drained, delay);
+        select! {
+            _ = drained => {},
+            _ = delay => {},
+        }
+    }
+
+    /// Build the peer handle given to request handlers of a session, also
+    /// usable to reach the client outside a handler, e.g. from the
+    /// `on_initialized` callback.
+    ///
+    /// Notifications go to the session through the installed notification
+    /// sender unless the transport replaces the notifier.
+    pub fn peer(&self, session_id: Option<String>) -> Peer {
+        let state = Arc::clone(&self.state);
+        let request_sender = Arc::clone(&self.request_sender);
+        let notification_sender = Arc::clone(&self.notification_sender);
+        let pending = Arc::clone(&self.pending_requests);
+        let timeout = self.peer_request_timeout;
+        let notify_session_id = session_id.clone();
+        let notifier: PeerNotifyFn = Arc::new(move |method: String, params: Value| {
+            let notification = NotificationMessage::new(method, Some(params));
+            send_via(&notification_sender, notify_session_id.clone(), notification);
+        });
+        Peer::new(Arc::new(move |method: String, params: Value| {
+            let state = Arc::clone(&state);
+            let request_sender = Arc::clone(&request_sender);
+            let pending = Arc::clone(&pending);
+            let session_id = session_id.clone();
+            Box::pin(async move {
+                if method == "sampling/createMessage" {
+                    let wants_tools =
+                        params.get("tools").is_some() || params.get("toolChoice").is_some();
+                    check_sampling_capability(&state, wants_tools)
+                        .map_err(|err| ProtocolError::Capability(err.to_string()))?;
+                }
+                if method == "elicitation/create" {
+                    let mode = params
+                        .get("mode")
+                        .and_then(|mode| serde_json::from_value(mode.clone()).ok())
+                        .unwrap_or_default();
+                    check_elicitation_capability(&state, mode)
+                        .map_err(|err| ProtocolError::Capability(err.to_string()))?;
+                }
+                let sender = request_sender
+                    .lock()
+                    .expect("request sender")
+                    .clone()
+                    .ok_or_else(|| {
+                        ProtocolError::Capability(
+                            "transport does not support server-initiated requests".to_string(),
+                        )
+                    })?;
+
+                let (id, response) = pending.register(session_id.clone());
+                sender(
+                    session_id.clone(),
+                    RequestMessage::new(id.clone(), method, params),
+                );
+
+                let mut response = response.fuse();
+                let mut delay = futures_timer::Delay::new(timeout).fuse();
+                let response = select! {
+                    response = response => response.map_err(|_| ProtocolError::Cancelled),
+                    _ = delay => Err(ProtocolError::Timeout),
+                };
+                if response.is_err() {
+                    pending.cancel(session_id, &id);
+                }
+                let response = response?;
+                match response.error {
+                    Some(error) => Err(ProtocolError::Handler(error.message)),
+                    None => Ok(response.result.unwrap_or(Value::Null)),
+                }
+            })
+        }))
+        .with_notifier(notifier)
+    }
+
     pub fn register_capabilities(
         &mut self,
         capabilities: ServerCapabilities,
@@ -97,6 +375,23 @@ impl Server {
         Ok(())
     }
 
+    /// Replace the capabilities instead of merging into them, so flags set
+    /// by registering tools, resources or prompts can be turned off again.
+    pub(crate) fn set_capabilities(
+        &mut self,
+        capabilities: ServerCapabilities,
+    ) -> Result<(), ServerError> {
+        let mut state = self.state.lock().expect("server state");
+        if state.capabilities_locked {
+            return Err(ServerError::CapabilitiesLocked);
+        }
+        state.capabilities = capabilities;
+        drop(state);
+        self.register_logging_handler_if_needed();
+        self.register_task_handlers_if_needed();
+        Ok(())
+    }
+
     pub fn get_capabilities(&self) -> ServerCapabilities {
         self.state
             .lock()
@@ -130,11 +425,7 @@ impl Server {
     }
 
     pub fn task_status_notification(&self, task: Task) -> Result<NotificationMessage, ServerError> {
-        let params = TaskStatusNotificationParams { task };
-        Ok(NotificationMessage::new(
-            "notifications/tasks/status",
-            Some(serde_json::to_value(params)?),
-        ))
+        Ok(build_task_status_notification(task)?)
     }
 
     /// Create a sampling/createMessage request to send to the client.
@@ -147,31 +438,10 @@ impl Server {
         id: MessageId,
         params: CreateMessageRequestParams,
     ) -> Result<RequestMessage, ServerError> {
-        // Check client capability
-        let state = self.state.lock().expect("server state");
-        let client_caps = state.client_capabilities.as_ref().ok_or_else(|| {
-            ServerError::Capability("client capabilities not available (not initialized)".into())
-        })?;
-
-        if client_caps.sampling.is_none() {
-            return Err(ServerError::Capability(
-                "client does not support sampling capability".into(),
-            ));
-        }
-
-        // Check tools capability if tools are provided
-        if params.tools.is_some() || params.tool_choice.is_some() {
-            if client_caps
-                .sampling
-                .as_ref()
-                .and_then(|s| s.tools.as_ref())
-                .is_none()
-            {
-                return Err(ServerError::Capability(
-                    "client does not support sampling tools capability".into(),
-                ));
-            }
-        }
+        check_sampling_capability(
+            &self.state,
+            params.tools.is_some() || params.tool_choice.is_some(),
+        )?;
 
         let params_value = serde_json::to_value(&params)?;
         Ok(RequestMessage::new(id, "sampling/createMessage", params_value))
@@ -187,26 +457,7 @@ impl Server {
         id: MessageId,
         params: ElicitRequestFormParams,
     ) -> Result<RequestMessage, ServerError> {
-        let state = self.state.lock().expect("server state");
-        let client_caps = state.client_capabilities.as_ref().ok_or_else(|| {
-            ServerError::Capability("client capabilities not available (not initialized)".into())
-        })?;
-
-        // Check elicitation capability - form is default when elicitation is declared
-        let elicitation = client_caps.elicitation.as_ref().ok_or_else(|| {
-            ServerError::Capability("client does not support elicitation capability".into())
-        })?;
-
-        // Form mode is supported if elicitation is declared (form is the default)
-        // unless only url is explicitly declared
-        let has_form = elicitation.form.is_some();
-        let has_url = elicitation.url.is_some();
-
-        if !has_form && has_url {
-            return Err(ServerError::Capability(
-                "client does not support form elicitation (only url mode)".into(),
-            ));
-        }
+        check_elicitation_capability(&self.state, ElicitationMode::Form)?;
 
         let params_value = serde_json::to_value(&params)?;
         Ok(RequestMessage::new(id, "elicitation/create", params_value))
@@ -222,20 +473,7 @@ impl Server {
         id: MessageId,
         params: ElicitRequestUrlParams,
     ) -> Result<RequestMessage, ServerError> {
-        let state = self.state.lock().expect("server state");
-        let client_caps = state.client_capabilities.as_ref().ok_or_else(|| {
-            ServerError::Capability("client capabilities not available (not initialized)".into())
-        })?;
-
-        let elicitation = client_caps.elicitation.as_ref().ok_or_else(|| {
-            ServerError::Capability("client does not support elicitation capability".into())
-        })?;
-
-        if elicitation.url.is_none() {
-            return Err(ServerError::Capability(
-                "client does not support URL elicitation".into(),
-            ));
-        }
+        check_elicitation_capability(&self.state, ElicitationMode::Url)?;
 
         let params_value = serde_json::to_value(&params)?;
         Ok(RequestMessage::new(id, "elicitation/create", params_value))
@@ -326,18 +564,267 @@ impl Server {
         &self,
         request: RequestMessage,
         session_id: Option<String>,
+    ) -> Result<ResultMessage, ServerError> {
+        self.handle_transport_request("stdio", request, session_id, None).await
+    }
+
+    /// Handle a request with the transport's per-session storage.
+    ///
+    /// Without `session_data` the request sees the process-wide store, which
+    /// is what single-session transports such as stdio want. The request is
+    /// reported under the `http` transport in the summary.
+    pub async fn handle_request_with_session_data(
+        &self,
+        request: RequestMessage,
+        session_id: Option<String>,
+        session_data: Option<Arc<SessionData>>,
+    ) -> Result<ResultMessage, ServerError> {
+        self.handle_transport_request("http", request, session_id, session_data)
+            .await
+    }
+
+    /// Handle a request that arrived on `transport`, recording its latency
+    /// and payload sizes for `summary_report` under that transport name.
+    pub async fn handle_transport_request(
+        &self,
+        transport: &'static str,
+        request: RequestMessage,
+        session_id: Option<String>,
+        session_data: Option<Arc<SessionData>>,
+    ) -> Result<ResultMessage, ServerError> {
+        let context = RequestContext {
+            peer: Some(self.peer(session_id.clone())),
+            session_id,
+            session_data,
+            ..Default::default()
+        };
+        self.record_request(transport, request, context).await
+    }
+
+    /// Handle a request whose notifications go to `notifier` rather than
+    /// the session, e.g. onto the response stream of the request itself.
+    /// Without a notifier this is `handle_transport_request`.
+    ///
+    /// `auth_info` is the token the transport verified for the request;
+    /// handlers see it as `RequestContext::auth_info`.
+    pub async fn handle_request_with_notifier(
+        &self,
+        transport: &'static str,
+        request: RequestMessage,
+        session_id: Option<String>,
+        session_data: Option<Arc<SessionData>>,
+        notifier: Option<PeerNotifyFn>,
+        auth_info: Option<AuthInfo>,
+    ) -> Result<ResultMessage, ServerError> {
+        let mut peer = self.peer(session_id.clone());
+        if let Some(notifier) = notifier {
+            peer = peer.with_notifier(notifier);
+        }
+        let context = RequestContext {
+            peer: Some(peer),
+            session_id,
+            session_data,
+            auth_info,
+            ..Default::default()
+        };
+        self.record_request(transport, request, context).await
+    }
+
+    /// Handle a self-contained request from a transport without sessions.
+    ///
+    /// The request gets empty session storage and no session ID. Requests
+    /// its handler sends to the client (sampling, elicitation) fail with a
+    /// capability error, since there is no stream to deliver them on.
+    /// Notifications reach the client only through `notifier`.
+    pub async fn handle_stateless_request(
+        &self,
+        transport: &'static str,
+        request: RequestMessage,
+        notifier: Option<PeerNotifyFn>,
+        auth_info: Option<AuthInfo>,
+    ) -> Result<ResultMessage, ServerError> {
+        let mut peer = Peer::new(Arc::new(|method: String, _params: Value| {
+            Box::pin(async move {
+                Err(ProtocolError::Capability(format!(
+                    "{} is not supported by stateless transports",
+                    method
+                )))
+            })
+        }));
+        if let Some(notifier) = notifier {
+            peer = peer.with_notifier(notifier);
+        }
+        let context = RequestContext {
+            peer: Some(peer),
+            session_data: Some(Arc::new(SessionData::default())),
+            auth_info,
+            stateless: true,
+            ..Default::default()
+        };
+        self.record_request(transport, request, context).await
+    }
+
+    async fn record_request(
+        &self,
+        transport: &'static str,
+        request: RequestMessage,
+        context: RequestContext,
+    ) -> Result<ResultMessage, ServerError> {
+        let Some(metrics) = self.metrics.as_ref() else {
+            return self.summarize_request(transport, request, context).await;
+        };
+
+        let method = request.method.clone();
+        let tool = tool_name(&request);
+        metrics.on_request_start(&method);
+        let started = Instant::now();
+        let response = self.summarize_request(transport, request, context).await;
+        let latency = started.elapsed();
+        let is_error = match &response {
+            Ok(response) => is_error_response(response),
+            Err(_) => true,
+        };
+        metrics.on_request_finish(&method, latency, is_error);
+        if let Some(tool) = tool {
+            metrics.on_tool_call(&tool, latency, is_error);
+        }
+        response
+    }
+
+    async fn summarize_request(
+        &self,
+        transport: &'static str,
+        request: RequestMessage,
+        context: RequestContext,
+    ) -> Result<ResultMessage, ServerError> {
+        let Some(summary) = self.summary.as_ref() else {
+            return self.dispatch_request(request, context).await;
+        };
+
+        let started = summary.now();
+        let method = request.method.clone();
+        let tool = tool_name(&request);
+        let request_bytes = serialized_size(&request);
+        let response = self.dispatch_request(request, context).await?;
+        summary.record(RequestRecord {
+            transport,
+            method: &method,
+            tool: tool.as_deref(),
+            latency: summary.now().saturating_duration_since(started),
+            request_bytes,
+            response_bytes: serialized_size(&response),
+            is_error: is_error_response(&response),
+        });
+        Ok(response)
+    }
+
+    /// The metrics recorder installed through `ServerOptions::metrics`.
+    pub fn metrics(&self) -> Option<Arc<dyn ServerMetrics>> {
+        self.metrics.clone()
+    }
+
+    /// The API key store installed through `ServerOptions::api_key_store`.
+    pub fn api_key_store(&self) -> Option<Arc<dyn ApiKeyStore>> {
+        self.api_key_store.clone()
+    }
+
+    /// Per-transport, per-method and per-tool request statistics collected
+    /// since the server was created. Empty when `collect_summary` is off.
+    pub fn summary_report(&self) -> SummaryReport {
+        self.summary
+            .as_ref()
+            .map(|summary| summary.report())
+            .unwrap_or_default()
+    }
+
+    async fn dispatch_request(
+        &self,
+        request: RequestMessage,
+        mut context: RequestContext,
     ) -> Result<ResultMessage, ServerError> {
         let id = request.id.clone();
-        let mut context = RequestContext::default();
-        context.session_id = session_id;
+        if self.is_shutting_down() {
+            let error = ErrorObject::new(ErrorCode::ConnectionClosed as i32, SHUTDOWN_MESSAGE, None);
+            return Ok(ResultMessage::failure(id, error));
+        }
+        let _in_flight = self.in_flight.enter();
+
+        let method = request.method.clone();
+        let session_id = context.session_id.clone();
+        if context.session_data.is_none() {
+            context.session_data = Some(Arc::clone(&self.session_data));
+        }
+        // Handlers still running when shutdown gives up on them are cancelled
+        let cancel_token = match context.options.cancel_token.take() {
+            Some(token) => token.race(&self.shutdown_token),
+            None => self.shutdown_token.child(),
+        };
+        context.options.cancel_token = Some(cancel_token);
         match self
             .protocol
             .handle_request_with_context(request, context)
             .await
         {
             Ok(result) => Ok(result),
-            Err(err) => Ok(ResultMessage::failure(id, map_protocol_error(err))),
+            Err(err) => {
+                if let (true, ProtocolError::Handler(message)) = (self.log_handler_errors, &err) {
+                    self.log_handler_error(session_id, &method, message);
+                }
+                Ok(ResultMessage::failure(id, map_protocol_error(err)))
+            }
+        }
+    }
+
+    /// Send a `notifications/message` log entry to a session.
+    ///
+    /// Returns `false` when logging is not enabled, the message is below the
+    /// level the session asked for via `logging/setLevel`, or no transport
+    /// has installed a notification sender.
+    pub fn send_log_message(
+        &self,
+        session_id: Option<String>,
+        params: LoggingMessageParams,
+    ) -> Result<bool, ServerError> {
+        {
+            let state = self.state.lock().expect("server state");
+            if state.capabilities.logging.is_none() {
+                return Ok(false);
+            }
+            let below_minimum = state
+                .logging_levels
+                .get(&session_id)
+                .is_some_and(|minimum| params.level < *minimum);
+            if below_minimum {
+                return Ok(false);
+            }
         }
+        let notification =
+            NotificationMessage::new("notifications/message", Some(serde_json::to_value(params)?));
+        Ok(self.send_notification(session_id, notification))
+    }
+
+    /// Mirror a failed handler into the session's log stream. Only sessions
+    /// that set a logging level receive it, so it never arrives unasked.
+    fn log_handler_error(&self, session_id: Option<String>, method: &str, message: &str) {
+        let level_set = self
+            .state
+            .lock()
+            .expect("server state")
+            .logging_levels
+            .contains_key(&session_id);
+        if !level_set {
+            return;
+        }
+        let params = LoggingMessageParams {
+            base: NotificationParams::default(),
+            level: LoggingLevel::Error,
+            logger: Some(self.server_info().base.name),
+            data: serde_json::json!({
+                "method": method,
+                "error": message,
+            }),
+        };
+        let _ = self.send_log_message(session_id, params);
     }
 
     pub async fn handle_notification(
@@ -385,7 +872,7 @@ impl Server {
                   _context: &RequestContext|
                   -> BoxFuture<'static, Result<Value, ProtocolError>> {
                 let state = state.clone();
-                let server_info = server_info.clone();
+                let server_info = server_info.lock().expect("server info").clone();
                 let params_value = request.params.clone();
                 Box::pin(async move {
                     let params: InitializeRequestParams = serde_json::from_value(params_value)?;
@@ -444,6 +931,24 @@ impl Server {
         );
     }
 
+    /// Answer `ping` with an empty result, on every transport and before
+    /// initialization too.
+    fn register_ping_handler(&mut self) {
+        let handler = RequestHandlerFn::new(
+            |_request: &RequestMessage,
+             _context: &RequestContext|
+             -> BoxFuture<'static, Result<Value, ProtocolError>> {
+                Box::pin(async { Ok(Value::Object(Default::default())) })
+            },
+        );
+
+        self.protocol.register_request_handler(
+            "ping",
+            JsonSchemaValidator::schema_for::<Value>(),
+            handler,
+        );
+    }
+
     fn register_logging_handler_if_needed(&mut self) {
         if self.logging_handler_registered {
             return;
@@ -497,6 +1002,17 @@ impl Server {
 
         ensure_task_capabilities(self.state.clone());
 
+        let sender = self.notification_sender.clone();
+        let metrics = self.metrics.clone();
+        task_store.set_status_listener(Some(Arc::new(move |change: TaskStatusChange| {
+            if let Some(metrics) = metrics.as_ref() {
+                metrics.on_task_state_change(&change);
+            }
+            if let Ok(notification) = build_task_status_notification(change.task) {
+                send_via(&sender, change.session_id, notification);
+            }
+        })));
+
         let store_for_get = task_store.clone();
         let get_handler = RequestHandlerFn::new(
             move |request: &RequestMessage,
@@ -530,12 +1046,12 @@ impl Server {
                 let store = store_for_list.clone();
                 let params_value = request.params.clone();
                 Box::pin(async move {
-                    let params: PaginatedRequestParams = if params_value.is_null() {
-                        PaginatedRequestParams::default()
+                    let params: ListTasksRequestParams = if params_value.is_null() {
+                        ListTasksRequestParams::default()
                     } else {
                         serde_json::from_value(params_value)?
                     };
-                    let (tasks, next_cursor) = store.list_tasks(params.cursor).await?;
+                    let (tasks, next_cursor) = store.list_tasks(&params).await?;
                     let result = ListTasksResult {
                         pagination: PaginatedResult {
                             next_cursor,
@@ -550,7 +1066,7 @@ impl Server {
 
         self.protocol.register_request_handler(
             "tasks/list",
-            JsonSchemaValidator::schema_for::<Option<PaginatedRequestParams>>(),
+            JsonSchemaValidator::schema_for::<Option<ListTasksRequestParams>>(),
             list_handler,
         );
 
@@ -563,13 +1079,29 @@ impl Server {
                 let params_value = request.params.clone();
                 Box::pin(async move {
                     let params: GetTaskPayloadRequestParams = serde_json::from_value(params_value)?;
-                    let result =
-                        store
-                            .get_task_result(&params.task_id)
+                    let cancelled = store
+                        .get_task(&params.task_id)
+                        .await?
+                        .is_some_and(|task| task.status == TaskStatus::Cancelled);
+                    if cancelled {
+                        return Err(ProtocolError::Cancelled);
+                    }
+                    let not_available =
+                        || ProtocolError::Handler("task result not available".to_string());
+                    // Ranged reads return slices of the serialized result
+                    // without materializing it as a value.
+                    if let Some(range) = params.range.as_ref() {
+                        let chunk = store
+                            .get_task_result_range(&params.task_id, range)
                             .await?
-                            .ok_or_else(|| {
-                                ProtocolError::Handler("task result not available".to_string())
-                            })?;
+                            .ok_or_else(not_available)?
+                            .map_err(|error| ProtocolError::Handler(error.message))?;
+                        return Ok(serde_json::to_value(chunk)?);
+                    }
+                    let result = store
+                        .get_task_result(&params.task_id)
+                        .await?
+                        .ok_or_else(not_available)?;
                     match result {
                         Ok(value) => Ok(value),
                         Err(error) => Err(ProtocolError::Handler(error.message)),
@@ -585,11 +1117,16 @@ impl Server {
         );
 
         let store_for_cancel = task_store.clone();
+        let running_tasks = self.protocol.running_tasks();
+        let task_limiter = self.protocol.task_limiter();
+        let grace_period = self.protocol.task_cancel_grace_period();
         let cancel_handler = RequestHandlerFn::new(
             move |request: &RequestMessage,
                   _context: &RequestContext|
                   -> BoxFuture<'static, Result<Value, ProtocolError>> {
                 let store = store_for_cancel.clone();
+                let running_tasks = running_tasks.clone();
+                let task_limiter = task_limiter.clone();
                 let params_value = request.params.clone();
                 Box::pin(async move {
                     let params: CancelTaskRequestParams = serde_json::from_value(params_value)?;
@@ -597,6 +1134,17 @@ impl Server {
                         .cancel_task(&params.task_id)
                         .await?
                         .ok_or_else(|| ProtocolError::Handler("task not found".to_string()))?;
+                    // A queued task leaves the queue without ever running,
+                    // so there is nothing to wait for before aborting it.
+                    let dequeued = task_limiter
+                        .as_ref()
+                        .is_some_and(|limiter| limiter.remove_queued(&params.task_id));
+                    let grace_period = if dequeued {
+                        Duration::ZERO
+                    } else {
+                        grace_period
+                    };
+                    running_tasks.cancel(&params.task_id, grace_period).await;
                     let result = CancelTaskResult { task };
                     Ok(serde_json::to_value(result)?)
                 })
@@ -613,6 +1161,109 @@ impl Server {
     }
 }
 
+/// Check that the client declared `elicitation` with support for `mode`.
+///
+/// Form mode is the default when elicitation is declared, unless the client
+/// only declared url mode.
+fn check_elicitation_capability(
+    state: &Mutex<ServerState>,
+    mode: ElicitationMode,
+) -> Result<(), ServerError> {
+    let state = state.lock().expect("server state");
+    let client_caps = state.client_capabilities.as_ref().ok_or_else(|| {
+        ServerError::Capability("client capabilities not available (not initialized)".into())
+    })?;
+
+    let elicitation = client_caps.elicitation.as_ref().ok_or_else(|| {
+        ServerError::Capability("client does not support elicitation capability".into())
+    })?;
+
+    match mode {
+        ElicitationMode::Form if elicitation.form.is_none() && elicitation.url.is_some() => Err(
+            ServerError::Capability("client does not support form elicitation (only url mode)".into()),
+        ),
+        ElicitationMode::Url if elicitation.url.is_none() => Err(ServerError::Capability(
+            "client does not support URL elicitation".into(),
+        )),
+        _ => Ok(()),
+    }
+}
+
+/// Check that the client declared `sampling` (and sampling tools when the
+/// request offers tools).
+fn check_sampling_capability(
+    state: &Mutex<ServerState>,
+    wants_tools: bool,
+) -> Result<(), ServerError> {
+    let state = state.lock().expect("server state");
+    let client_caps = state.client_capabilities.as_ref().ok_or_else(|| {
+        ServerError::Capability("client capabilities not available (not initialized)".into())
+    })?;
+
+    let sampling = client_caps.sampling.as_ref().ok_or_else(|| {
+        ServerError::Capability("client does not support sampling capability".into())
+    })?;
+
+    // Check tools capability if tools are provided
+    if wants_tools && sampling.tools.is_none() {
+        return Err(ServerError::Capability(
+            "client does not support sampling tools capability".into(),
+        ));
+    }
+    Ok(())
+}
+
+fn send_via(
+    sender: &Mutex<Option<NotificationSender>>,
+    session_id: Option<String>,
+    notification: NotificationMessage,
+) -> bool {
+    let sender = sender.lock().expect("notification sender").clone();
+    match sender {
+        Some(sender) => {
+            sender(session_id, notification);
+            true
+        }
+        None => false,
+    }
+}
+
+/// Name of the tool a `tools/call` request calls.
+fn tool_name(request: &RequestMessage) -> Option<String> {
+    (request.method == "tools/call")
+        .then(|| request.params.get("name").and_then(Value::as_str))
+        .flatten()
+        .map(str::to_string)
+}
+
+/// Whether a response is an error or a tool result flagged `isError`.
+fn is_error_response(response: &ResultMessage) -> bool {
+    response.error.is_some()
+        || response
+            .result
+            .as_ref()
+            .and_then(|result| result.get("isError"))
+            .and_then(Value::as_bool)
+            .unwrap_or(false)
+}
+
+/// Build `notifications/tasks/status`, tagging it with the related-task metadata.
+fn build_task_status_notification(
+    mut task: Task,
+) -> Result<NotificationMessage, serde_json::Error> {
+    let meta = task.meta.get_or_insert_with(RequestMeta::default);
+    if meta.related_task.is_none() {
+        meta.related_task = Some(RelatedTaskMetadata {
+            task_id: task.task_id.clone(),
+        });
+    }
+    let params = TaskStatusNotificationParams { task };
+    Ok(NotificationMessage::new(
+        "notifications/tasks/status",
+        Some(serde_json::to_value(params)?),
+    ))
+}
+
 fn ensure_task_capabilities(state: Arc<Mutex<ServerState>>) {
     let mut state = state.lock().expect("server state");
     if state.capabilities.tasks.is_some() {
@@ -650,11 +1301,22 @@ fn map_protocol_error(error: ProtocolError) -> ErrorObject {
         ProtocolError::Capability(message) => {
             ErrorObject::new(ErrorCode::InvalidRequest as i32, message, None)
         }
+        error @ ProtocolError::CapabilityNotEnabled(_) => {
+            ErrorObject::new(ErrorCode::MethodNotFound as i32, error.to_string(), None)
+        }
         ProtocolError::TaskUnsupported => ErrorObject::new(
             ErrorCode::InvalidRequest as i32,
             "task support not available",
             None,
         ),
+        error @ (ProtocolError::DuplicateRequestId(_) | ProtocolError::NullRequestId) => {
+            ErrorObject::new(ErrorCode::InvalidRequest as i32, error.to_string(), None)
+        }
+        ProtocolError::InsufficientScope(err) => ErrorObject::new(
+            ErrorCode::InvalidRequest as i32,
+            err.to_string(),
+            Some(serde_json::json!({ "error": InsufficientScopeError::CODE })),
+        ),
         ProtocolError::Handler(message) => {
             ErrorObject::new(ErrorCode::InternalError as i32, message, None)
         }
@@ -1,6 +1,8 @@
 use std::sync::{Arc, Mutex};
 
-use mcp_core::protocol::{CapabilityChecker, ProtocolError};
+use mcp_core::protocol::{
+    CapabilityChecker, ProtocolError, RequiredCapability, required_capability,
+};
 use mcp_core::types::ServerCapabilities;
 
 use super::server_state::ServerState;
@@ -25,48 +27,15 @@ impl ServerCapabilityChecker {
 }
 
 impl CapabilityChecker for ServerCapabilityChecker {
+    /// Reject requests for capabilities the server did not advertise with
+    /// `CapabilityNotEnabled`, which clients see as `MethodNotFound`.
     fn assert_request(&self, method: &str) -> Result<(), ProtocolError> {
-        let capabilities = self.capabilities();
-        match method {
-            "logging/setLevel" => {
-                if capabilities.logging.is_none() {
-                    return Err(ProtocolError::Capability(
-                        "logging capability not enabled".to_string(),
-                    ));
-                }
-            }
-            "prompts/get" | "prompts/list" => {
-                if capabilities.prompts.is_none() {
-                    return Err(ProtocolError::Capability(
-                        "prompts capability not enabled".to_string(),
-                    ));
-                }
-            }
-            "resources/list" | "resources/templates/list" | "resources/read" => {
-                if capabilities.resources.is_none() {
-                    return Err(ProtocolError::Capability(
-                        "resources capability not enabled".to_string(),
-                    ));
-                }
-            }
-            "tools/list" | "tools/call" => {
-                if capabilities.tools.is_none() {
-                    return Err(ProtocolError::Capability(
-                        "tools capability not enabled".to_string(),
-                    ));
-                }
-            }
-            "tasks/get" | "tasks/list" | "tasks/result" | "tasks/cancel" => {
-                if capabilities.tasks.is_none() {
-                    return Err(ProtocolError::Capability(
-                        "tasks capability not enabled".to_string(),
-                    ));
-                }
-            }
-            "initialize" | "ping" => {}
-            _ => {}
+        match required_capability(method) {
+            Some(required) if !is_enabled(&self.capabilities(), required) => Err(
+                ProtocolError::CapabilityNotEnabled(required.name().to_string()),
+            ),
+            _ => Ok(()),
         }
-        Ok(())
     }
 
     fn assert_notification(&self, _method: &str) -> Result<(), ProtocolError> {
@@ -74,10 +43,43 @@ impl CapabilityChecker for ServerCapabilityChecker {
     }
 
     fn assert_request_handler(&self, method: &str) -> Result<(), ProtocolError> {
-        self.assert_request(method)
+        // Subscription handlers are registered with the other resource
+        // handlers, even when `resources.subscribe` is turned off
+        let required = match required_capability(method) {
+            Some(RequiredCapability::ResourceSubscriptions) => RequiredCapability::Resources,
+            Some(required) => required,
+            None => return Ok(()),
+        };
+        if is_enabled(&self.capabilities(), required) {
+            Ok(())
+        } else {
+            Err(ProtocolError::CapabilityNotEnabled(
+                required.name().to_string(),
+            ))
+        }
     }
 
     fn assert_notification_handler(&self, _method: &str) -> Result<(), ProtocolError> {
         Ok(())
     }
 }
+
+/// Whether `capabilities` advertise `required`. Client capabilities are
+/// never needed to handle a request, so they always pass.
+fn is_enabled(capabilities: &ServerCapabilities, required: RequiredCapability) -> bool {
+    match required {
+        RequiredCapability::Tools => capabilities.tools.is_some(),
+        RequiredCapability::Prompts => capabilities.prompts.is_some(),
+        RequiredCapability::Resources => capabilities.resources.is_some(),
+        RequiredCapability::ResourceSubscriptions => capabilities
+            .resources
+            .as_ref()
+            .is_some_and(|resources| resources.subscribe == Some(true)),
+        RequiredCapability::Logging => capabilities.logging.is_some(),
+        RequiredCapability::Completions => capabilities.completions.is_some(),
+        RequiredCapability::Tasks => capabilities.tasks.is_some(),
+        RequiredCapability::Sampling
+        | RequiredCapability::Elicitation
+        | RequiredCapability::Roots => true,
+    }
+}
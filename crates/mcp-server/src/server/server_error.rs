@@ -17,6 +17,15 @@ pub enum ServerError {
     #[error("serialization failed: {0}")]
     Serialization(#[from] serde_json::Error),
 
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+
     #[error("handler error: {0}")]
     Handler(String),
+
+    #[error("invalid tool: {0}")]
+    InvalidTool(String),
+
+    #[error("invalid resource template: {0}")]
+    InvalidResourceTemplate(String),
 }
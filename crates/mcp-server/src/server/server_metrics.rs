@@ -0,0 +1,44 @@
+use std::time::Duration;
+
+use mcp_core::protocol::TaskStatusChange;
+
+/// Hooks for exporting request, tool, session and task metrics, set as
+/// `ServerOptions::metrics`.
+///
+/// Every hook defaults to doing nothing, so a recorder only implements the
+/// ones it exports. Hooks run on the request path and should not block.
+pub trait ServerMetrics: Send + Sync {
+    /// A request for `method` is about to be handled.
+    fn on_request_start(&self, method: &str) {
+        let _ = method;
+    }
+
+    /// A request for `method` was answered after `latency`. `is_error` is
+    /// set for error responses and tool results flagged `isError`.
+    fn on_request_finish(&self, method: &str, latency: Duration, is_error: bool) {
+        let _ = (method, latency, is_error);
+    }
+
+    /// A `tools/call` for `tool` was answered after `latency`; reported
+    /// after `on_request_finish`.
+    fn on_tool_call(&self, tool: &str, latency: Duration, is_error: bool) {
+        let _ = (tool, latency, is_error);
+    }
+
+    /// A transport issued a new session.
+    fn on_session_created(&self) {}
+
+    /// A session ended: it expired, was evicted or was deleted by the client.
+    fn on_session_expired(&self) {}
+
+    /// A task moved to a new status.
+    fn on_task_state_change(&self, change: &TaskStatusChange) {
+        let _ = change;
+    }
+
+    /// Metrics in the Prometheus text format, served on `/metrics` by the
+    /// axum router. `None`, the default, answers that route with 404.
+    fn render(&self) -> Option<String> {
+        None
+    }
+}
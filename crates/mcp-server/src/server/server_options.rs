@@ -1,10 +1,85 @@
+use std::sync::Arc;
+use std::time::Duration;
+
 use mcp_core::protocol::ProtocolOptions;
+use mcp_core::schema::JsonSchemaValidator;
 use mcp_core::types::ServerCapabilities;
 
+use crate::auth::ApiKeyStore;
+use crate::server::result_transform::ResultTransform;
+use crate::server::server_metrics::ServerMetrics;
+use crate::server::summary_clock::SummaryClock;
+
+/// How long a server-initiated request waits for the client's response.
+pub const DEFAULT_PEER_REQUEST_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Number of responses remembered when `ServerOptions::dedup_window` is set.
+pub const DEFAULT_DEDUP_MAX_ENTRIES: usize = 512;
+
 /// Configuration options for an MCP server.
-#[derive(Clone, Default)]
+#[derive(Clone)]
 pub struct ServerOptions {
     pub capabilities: Option<ServerCapabilities>,
     pub instructions: Option<String>,
     pub protocol_options: Option<ProtocolOptions>,
+    /// Timeout for requests sent to the client (e.g. sampling); defaults to
+    /// `DEFAULT_PEER_REQUEST_TIMEOUT`.
+    pub peer_request_timeout: Option<Duration>,
+    /// Also report handler errors to the client as `notifications/message`
+    /// at `error` level (requires the logging capability).
+    pub log_handler_errors: bool,
+    /// Maximum number of task-augmented requests executing at once; overrides
+    /// `ProtocolOptions::max_concurrent_tasks`. Excess tasks are queued.
+    pub max_concurrent_tasks: Option<usize>,
+    /// Post-processing applied to every successful `tools/call` result.
+    pub result_transform: Option<ResultTransform>,
+    /// Record per-method and per-tool latency and payload statistics for
+    /// `Server::summary_report`. Enabled by default.
+    pub collect_summary: bool,
+    /// Time source for the summary; defaults to `Instant::now`.
+    pub summary_clock: Option<SummaryClock>,
+    /// Recorder for request, tool, session and task metrics, e.g.
+    /// `PrometheusMetrics`. Without one, metrics cost a single branch.
+    pub metrics: Option<Arc<dyn ServerMetrics>>,
+    /// Validate `tools/call` arguments against the tool's `inputSchema`
+    /// before its handler runs, including any custom keywords registered
+    /// on the validator. Invalid arguments produce an `isError` result.
+    pub tool_input_validator: Option<Arc<JsonSchemaValidator>>,
+    /// Maximum number of entries per `tools/list`, `resources/list`,
+    /// `resources/templates/list` and `prompts/list` page; further pages are
+    /// reached through `nextCursor`. Without it every entry is listed at once.
+    pub list_page_size: Option<usize>,
+    /// Answer a request retransmitted with the same `MessageId` within this
+    /// long from the cached response instead of running the handler again;
+    /// overrides `ProtocolOptions::idempotency_window`.
+    pub dedup_window: Option<Duration>,
+    /// How many responses `dedup_window` keeps, least recently used first
+    /// out. Defaults to `DEFAULT_DEDUP_MAX_ENTRIES`.
+    pub dedup_max_entries: usize,
+    /// Require a key from this store on every request to the axum router
+    /// (`X-API-Key` or `Authorization: Bearer`). Requests without a valid
+    /// key get `401 Unauthorized`.
+    pub api_key_store: Option<Arc<dyn ApiKeyStore>>,
+}
+
+impl Default for ServerOptions {
+    fn default() -> Self {
+        Self {
+            capabilities: None,
+            instructions: None,
+            protocol_options: None,
+            peer_request_timeout: None,
+            log_handler_errors: false,
+            max_concurrent_tasks: None,
+            result_transform: None,
+            collect_summary: true,
+            summary_clock: None,
+            metrics: None,
+            tool_input_validator: None,
+            list_page_size: None,
+            dedup_window: None,
+            dedup_max_entries: DEFAULT_DEDUP_MAX_ENTRIES,
+            api_key_store: None,
+        }
+    }
 }
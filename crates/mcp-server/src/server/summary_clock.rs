@@ -0,0 +1,6 @@
+use std::sync::Arc;
+use std::time::Instant;
+
+/// Time source for the request summary; tests install a manual clock to get
+/// deterministic latencies.
+pub type SummaryClock = Arc<dyn Fn() -> Instant + Send + Sync>;
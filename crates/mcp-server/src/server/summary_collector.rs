@@ -0,0 +1,127 @@
+use std::collections::HashMap;
+use std::io;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+
+use crate::server::latency_histogram::LatencyHistogram;
+use crate::server::summary_clock::SummaryClock;
+use crate::server::summary_kind::SummaryKind;
+use crate::server::summary_report::SummaryReport;
+use crate::server::summary_row::SummaryRow;
+
+/// Entries of one transport and kind, by method or tool name.
+type SummaryEntries = HashMap<String, SummaryEntry>;
+
+#[derive(Default)]
+struct SummaryEntry {
+    errors: u64,
+    latency: LatencyHistogram,
+    max_request_bytes: usize,
+    max_response_bytes: usize,
+}
+
+/// Outcome of one dispatched request.
+pub(crate) struct RequestRecord<'a> {
+    pub transport: &'static str,
+    pub method: &'a str,
+    pub tool: Option<&'a str>,
+    pub latency: Duration,
+    pub request_bytes: usize,
+    pub response_bytes: usize,
+    pub is_error: bool,
+}
+
+/// Collects the statistics behind `Server::summary_report`.
+///
+/// Entries are created on the first request for a method or tool; later
+/// requests only update fixed-size counters.
+pub(crate) struct SummaryCollector {
+    clock: Option<SummaryClock>,
+    started: Instant,
+    entries: Mutex<HashMap<(&'static str, SummaryKind), SummaryEntries>>,
+}
+
+impl SummaryCollector {
+    pub fn new(clock: Option<SummaryClock>) -> Self {
+        let started = clock.as_ref().map_or_else(Instant::now, |clock| clock());
+        Self {
+            clock,
+            started,
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn now(&self) -> Instant {
+        self.clock
+            .as_ref()
+            .map_or_else(Instant::now, |clock| clock())
+    }
+
+    pub fn record(&self, record: RequestRecord<'_>) {
+        let mut entries = self.entries.lock().expect("summary entries");
+        let mut update = |kind: SummaryKind, name: &str| {
+            let names = entries.entry((record.transport, kind)).or_default();
+            if !names.contains_key(name) {
+                names.insert(name.to_string(), SummaryEntry::default());
+            }
+            let entry = names.get_mut(name).expect("summary entry");
+            entry.latency.record(record.latency);
+            entry.errors += u64::from(record.is_error);
+            entry.max_request_bytes = entry.max_request_bytes.max(record.request_bytes);
+            entry.max_response_bytes = entry.max_response_bytes.max(record.response_bytes);
+        };
+        update(SummaryKind::Method, record.method);
+        if let Some(tool) = record.tool {
+            update(SummaryKind::Tool, tool);
+        }
+    }
+
+    pub fn report(&self) -> SummaryReport {
+        let entries = self.entries.lock().expect("summary entries");
+        let mut rows: Vec<SummaryRow> = entries
+            .iter()
+            .flat_map(|((transport, kind), names)| {
+                names.iter().map(|(name, entry)| SummaryRow {
+                    transport: transport.to_string(),
+                    kind: *kind,
+                    name: name.clone(),
+                    count: entry.latency.count(),
+                    errors: entry.errors,
+                    p50: entry.latency.percentile(50.0),
+                    p95: entry.latency.percentile(95.0),
+                    p99: entry.latency.percentile(99.0),
+                    max_latency: entry.latency.max(),
+                    max_request_bytes: entry.max_request_bytes,
+                    max_response_bytes: entry.max_response_bytes,
+                })
+            })
+            .collect();
+        rows.sort_by(|a, b| (&a.transport, a.kind, &a.name).cmp(&(&b.transport, b.kind, &b.name)));
+        SummaryReport {
+            uptime: self.now().saturating_duration_since(self.started),
+            rows,
+        }
+    }
+}
+
+/// Serialized size of `value` in bytes, measured without buffering it.
+pub(crate) fn serialized_size(value: &impl Serialize) -> usize {
+    let mut counter = ByteCounter(0);
+    let _ = serde_json::to_writer(&mut counter, value);
+    counter.0
+}
+
+struct ByteCounter(usize);
+
+impl io::Write for ByteCounter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0 += buf.len();
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
@@ -0,0 +1,19 @@
+use std::fmt;
+
+/// What a summary row aggregates: a JSON-RPC method or a single tool.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum SummaryKind {
+    /// All requests with this JSON-RPC method.
+    Method,
+    /// `tools/call` requests for this tool name.
+    Tool,
+}
+
+impl fmt::Display for SummaryKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Method => "method",
+            Self::Tool => "tool",
+        })
+    }
+}
@@ -0,0 +1,128 @@
+use std::fmt;
+use std::time::Duration;
+
+use crate::server::summary_kind::SummaryKind;
+use crate::server::summary_row::SummaryRow;
+
+const HEADERS: [&str; 11] = [
+    "TRANSPORT",
+    "KIND",
+    "NAME",
+    "COUNT",
+    "ERRORS",
+    "P50",
+    "P95",
+    "P99",
+    "MAX",
+    "MAX REQ",
+    "MAX RESP",
+];
+/// Columns before this index are left-aligned text, the rest are numbers.
+const FIRST_NUMERIC_COLUMN: usize = 3;
+
+/// Per-transport, per-method and per-tool request statistics.
+///
+/// `Display` renders an aligned table, suitable for printing to stderr when
+/// a server shuts down.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SummaryReport {
+    /// Time since the server was created.
+    pub uptime: Duration,
+    /// Rows sorted by transport, kind and name.
+    pub rows: Vec<SummaryRow>,
+}
+
+impl SummaryReport {
+    /// The row for `name` on `transport`, if any request was recorded.
+    pub fn row(&self, transport: &str, kind: SummaryKind, name: &str) -> Option<&SummaryRow> {
+        self.rows
+            .iter()
+            .find(|row| row.transport == transport && row.kind == kind && row.name == name)
+    }
+
+    /// Total number of requests across all transports.
+    pub fn total_requests(&self) -> u64 {
+        self.rows
+            .iter()
+            .filter(|row| row.kind == SummaryKind::Method)
+            .map(|row| row.count)
+            .sum()
+    }
+}
+
+impl fmt::Display for SummaryReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "MCP request summary: {} requests in {}",
+            self.total_requests(),
+            format_duration(self.uptime)
+        )?;
+        if self.rows.is_empty() {
+            return Ok(());
+        }
+
+        let cells: Vec<[String; 11]> = self
+            .rows
+            .iter()
+            .map(|row| {
+                [
+                    row.transport.clone(),
+                    row.kind.to_string(),
+                    row.name.clone(),
+                    row.count.to_string(),
+                    row.errors.to_string(),
+                    format_duration(row.p50),
+                    format_duration(row.p95),
+                    format_duration(row.p99),
+                    format_duration(row.max_latency),
+                    format_bytes(row.max_request_bytes),
+                    format_bytes(row.max_response_bytes),
+                ]
+            })
+            .collect();
+        let mut widths = HEADERS.map(str::len);
+        for row in &cells {
+            for (width, cell) in widths.iter_mut().zip(row) {
+                *width = (*width).max(cell.chars().count());
+            }
+        }
+
+        let header = HEADERS.map(str::to_string);
+        for row in std::iter::once(&header).chain(&cells) {
+            writeln!(f)?;
+            for (column, (cell, width)) in row.iter().zip(widths).enumerate() {
+                if column > 0 {
+                    f.write_str("  ")?;
+                }
+                if column < FIRST_NUMERIC_COLUMN {
+                    write!(f, "{cell:<width$}")?;
+                } else {
+                    write!(f, "{cell:>width$}")?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+fn format_duration(duration: Duration) -> String {
+    let micros = duration.as_micros();
+    if micros < 1_000 {
+        format!("{micros}µs")
+    } else if micros < 1_000_000 {
+        format!("{:.1}ms", micros as f64 / 1_000.0)
+    } else {
+        format!("{:.2}s", duration.as_secs_f64())
+    }
+}
+
+fn format_bytes(bytes: usize) -> String {
+    if bytes < 1024 {
+        format!("{bytes} B")
+    } else if bytes < 1024 * 1024 {
+        format!("{:.1} KiB", bytes as f64 / 1024.0)
+    } else {
+        format!("{:.1} MiB", bytes as f64 / (1024.0 * 1024.0))
+    }
+}
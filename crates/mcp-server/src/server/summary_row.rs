@@ -0,0 +1,24 @@
+use std::time::Duration;
+
+use crate::server::summary_kind::SummaryKind;
+
+/// Aggregated statistics for one method or tool on one transport.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SummaryRow {
+    /// Transport the requests arrived on (`stdio`, `http`, `sse`, `websocket`).
+    pub transport: String,
+    pub kind: SummaryKind,
+    /// JSON-RPC method or tool name.
+    pub name: String,
+    pub count: u64,
+    /// Requests answered with a JSON-RPC error or a tool result with `isError`.
+    pub errors: u64,
+    pub p50: Duration,
+    pub p95: Duration,
+    pub p99: Duration,
+    pub max_latency: Duration,
+    /// Largest serialized request, in bytes.
+    pub max_request_bytes: usize,
+    /// Largest serialized response, in bytes.
+    pub max_response_bytes: usize,
+}
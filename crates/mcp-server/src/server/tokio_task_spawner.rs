@@ -0,0 +1,13 @@
+use std::sync::Arc;
+
+use mcp_core::protocol::TaskSpawner;
+
+/// Task spawner that runs task-augmented requests on the current Tokio runtime.
+///
+/// Set it as `ProtocolOptions::task_spawner` so `tasks/cancel` can abort the
+/// running handler instead of only flipping its status.
+pub fn tokio_task_spawner() -> TaskSpawner {
+    Arc::new(|future| {
+        tokio::spawn(future);
+    })
+}
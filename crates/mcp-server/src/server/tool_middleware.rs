@@ -0,0 +1,101 @@
+use std::any::Any;
+use std::panic::{AssertUnwindSafe, catch_unwind};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use serde_json::Value;
+
+use mcp_core::protocol::RequestContext;
+use mcp_core::types::CallToolResult;
+
+use crate::server::ServerError;
+use crate::server::handlers::ToolHandler;
+
+/// Hooks run around every tool invocation, added with
+/// `McpServer::add_tool_middleware`.
+///
+/// `before` hooks run in the order the middleware was added and `after`
+/// hooks in reverse, so the first middleware added wraps all the others.
+/// Task-augmented calls run through the same chain inside their task.
+/// A panicking hook fails the call with a handler error.
+pub trait ToolMiddleware: Send + Sync {
+    /// Called before the tool handler. Returning a result skips the handler
+    /// and the `before` hooks of later middleware; the `after` hooks of this
+    /// and earlier middleware still run.
+    fn before(
+        &self,
+        tool_name: &str,
+        arguments: Option<&Value>,
+        context: &RequestContext,
+    ) -> Result<Option<CallToolResult>, ServerError> {
+        let _ = (tool_name, arguments, context);
+        Ok(None)
+    }
+
+    /// Called with the outcome of the call, `elapsed` after the first
+    /// `before` hook started.
+    fn after(
+        &self,
+        tool_name: &str,
+        result: &Result<CallToolResult, ServerError>,
+        elapsed: Duration,
+        context: &RequestContext,
+    ) {
+        let _ = (tool_name, result, elapsed, context);
+    }
+}
+
+/// Call `handler` wrapped in `chain`.
+pub(crate) async fn call_with_middleware(
+    chain: &[Arc<dyn ToolMiddleware>],
+    tool_name: &str,
+    handler: Arc<dyn ToolHandler>,
+    arguments: Option<Value>,
+    context: RequestContext,
+) -> Result<CallToolResult, ServerError> {
+    let started = Instant::now();
+    let mut entered = 0;
+    let mut outcome = None;
+    for middleware in chain {
+        entered += 1;
+        let before = catch_unwind(AssertUnwindSafe(|| {
+            middleware.before(tool_name, arguments.as_ref(), &context)
+        }))
+        .unwrap_or_else(|panic| Err(panicked(panic)));
+        match before {
+            Ok(None) => {}
+            Ok(Some(result)) => {
+                outcome = Some(Ok(result));
+                break;
+            }
+            Err(err) => {
+                outcome = Some(Err(err));
+                break;
+            }
+        }
+    }
+
+    let mut result = match outcome {
+        Some(result) => result,
+        None => handler.call(arguments, context.clone()).await,
+    };
+    let elapsed = started.elapsed();
+    for middleware in chain[..entered].iter().rev() {
+        let after = catch_unwind(AssertUnwindSafe(|| {
+            middleware.after(tool_name, &result, elapsed, &context)
+        }));
+        if let Err(panic) = after {
+            result = Err(panicked(panic));
+        }
+    }
+    result
+}
+
+fn panicked(panic: Box<dyn Any + Send>) -> ServerError {
+    let message = panic
+        .downcast_ref::<&str>()
+        .map(|message| message.to_string())
+        .or_else(|| panic.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "unknown panic".to_string());
+    ServerError::Handler(format!("tool middleware panicked: {message}"))
+}
@@ -0,0 +1,30 @@
+use crate::server::CircuitBreakerConfig;
+
+/// Options for `McpServer::register_tool_with_options`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ToolOptions {
+    /// Scopes the caller's access token must all carry for `tools/call`.
+    /// Empty leaves the tool public.
+    pub required_scopes: Vec<String>,
+    /// Stop calling the handler after repeated failures. `None` never
+    /// trips.
+    pub circuit_breaker: Option<CircuitBreakerConfig>,
+}
+
+impl ToolOptions {
+    /// Require the given scopes to call the tool.
+    pub fn with_required_scopes<I, S>(mut self, scopes: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.required_scopes = scopes.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Guard the tool's handler with a circuit breaker.
+    pub fn with_circuit_breaker(mut self, config: CircuitBreakerConfig) -> Self {
+        self.circuit_breaker = Some(config);
+        self
+    }
+}
@@ -0,0 +1,56 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use crate::server::latency_histogram::LatencyHistogram;
+use crate::server::tool_stats_row::ToolStatsRow;
+
+#[derive(Default)]
+struct ToolStatsEntry {
+    errors: u64,
+    latency: LatencyHistogram,
+}
+
+/// Per-tool call counts and latencies behind `tools/stats`.
+///
+/// Entries are created on the first call of a registered tool; later calls
+/// only update fixed-size counters.
+#[derive(Default)]
+pub(crate) struct ToolStats {
+    entries: Mutex<HashMap<String, ToolStatsEntry>>,
+}
+
+impl ToolStats {
+    pub fn record(&self, tool: &str, latency: Duration, is_error: bool) {
+        let mut entries = self.entries.lock().expect("tool stats");
+        let entry = entries.entry(tool.to_string()).or_default();
+        entry.latency.record(latency);
+        entry.errors += u64::from(is_error);
+    }
+
+    /// One row per tool called since the last reset, ordered by name.
+    pub fn rows(&self) -> Vec<ToolStatsRow> {
+        let entries = self.entries.lock().expect("tool stats");
+        let mut rows: Vec<ToolStatsRow> = entries
+            .iter()
+            .map(|(name, entry)| ToolStatsRow {
+                name: name.clone(),
+                call_count: entry.latency.count(),
+                error_count: entry.errors,
+                p50_ms: millis(entry.latency.percentile(50.0)),
+                p95_ms: millis(entry.latency.percentile(95.0)),
+                p99_ms: millis(entry.latency.percentile(99.0)),
+            })
+            .collect();
+        rows.sort_by(|a, b| a.name.cmp(&b.name));
+        rows
+    }
+
+    pub fn reset(&self) {
+        self.entries.lock().expect("tool stats").clear();
+    }
+}
+
+fn millis(latency: Duration) -> f64 {
+    latency.as_secs_f64() * 1000.0
+}
@@ -0,0 +1,14 @@
+use serde::Serialize;
+
+/// Calls and latency percentiles of one tool, as returned by `tools/stats`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ToolStatsRow {
+    /// Tool name.
+    pub name: String,
+    pub call_count: u64,
+    /// Calls that failed or returned a result with `isError`.
+    pub error_count: u64,
+    pub p50_ms: f64,
+    pub p95_ms: f64,
+    pub p99_ms: f64,
+}
@@ -0,0 +1,8 @@
+use std::sync::Arc;
+
+use futures::future::BoxFuture;
+use mcp_core::types::RequestMessage;
+
+/// Hook awaited before each request is dispatched, e.g. to refresh server
+/// info ahead of `initialize`.
+pub type BeforeRequest = Arc<dyn Fn(&RequestMessage) -> BoxFuture<'static, ()> + Send + Sync>;
@@ -0,0 +1,19 @@
+//! Stdio transport for MCP server.
+//!
+//! Reads newline-delimited JSON-RPC messages from stdin and writes responses
+//! and server-initiated messages to stdout, one per line. Requests are
+//! handled concurrently, so a handler waiting on the client (e.g. for
+//! `roots/list`) does not stall the read loop.
+
+mod before_request;
+mod outgoing;
+mod run;
+#[cfg(feature = "tokio")]
+mod run_async;
+mod stdio_options;
+
+pub use before_request::BeforeRequest;
+pub use run::{run, run_with};
+#[cfg(feature = "tokio")]
+pub use run_async::{run_async, run_async_with};
+pub use stdio_options::StdioOptions;
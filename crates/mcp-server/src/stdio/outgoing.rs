@@ -0,0 +1,66 @@
+use std::sync::Arc;
+
+use mcp_core::stdio::{JsonRpcMessage, deserialize_message, serialize_message};
+use mcp_core::types::ResultMessage;
+
+use crate::server::{McpServer, ServerError};
+
+/// Queues a message for the writer. Returns `false` once the writer stopped.
+pub(crate) type Outgoing = Arc<dyn Fn(JsonRpcMessage) -> bool + Send + Sync>;
+
+/// Route server-initiated requests and notifications through the writer.
+pub(crate) fn install_senders(server: &McpServer, outgoing: &Outgoing) {
+    let send = Arc::clone(outgoing);
+    server
+        .server()
+        .set_notification_sender(Some(Arc::new(move |_session_id, notification| {
+            send(JsonRpcMessage::Notification(notification));
+        })));
+    let send = Arc::clone(outgoing);
+    server
+        .server()
+        .set_request_sender(Some(Arc::new(move |_session_id, request| {
+            send(JsonRpcMessage::Request(request));
+        })));
+}
+
+pub(crate) fn uninstall_senders(server: &McpServer) {
+    server.server().set_notification_sender(None);
+    server.server().set_request_sender(None);
+}
+
+/// Parse one line read from the client; blank and malformed lines are
+/// skipped.
+pub(crate) fn parse_line(line: &str) -> Option<JsonRpcMessage> {
+    let line = line.trim();
+    if line.is_empty() {
+        return None;
+    }
+    match deserialize_message(line) {
+        Ok(message) => Some(message),
+        Err(error) => {
+            tracing::warn!("Ignoring malformed message: {}", error);
+            None
+        }
+    }
+}
+
+/// Serialize a message as one newline-terminated line.
+pub(crate) fn serialize_line(message: &JsonRpcMessage) -> Option<String> {
+    match serialize_message(message) {
+        Ok(serialized) => Some(serialized),
+        Err(error) => {
+            tracing::error!("Error serializing message: {}", error);
+            None
+        }
+    }
+}
+
+pub(crate) fn respond(outgoing: &Outgoing, response: Result<ResultMessage, ServerError>) {
+    match response {
+        Ok(response) => {
+            outgoing(JsonRpcMessage::Result(response));
+        }
+        Err(error) => tracing::error!("Error handling request: {}", error),
+    }
+}
@@ -0,0 +1,114 @@
+use std::io::{self, BufRead, Write};
+use std::sync::{Arc, mpsc};
+use std::thread::{self, JoinHandle};
+
+use futures::executor::block_on;
+use mcp_core::stdio::JsonRpcMessage;
+
+use crate::server::{McpServer, ServerError};
+use crate::stdio::StdioOptions;
+use crate::stdio::outgoing::{
+    Outgoing, install_senders, parse_line, respond, serialize_line, uninstall_senders,
+};
+
+/// Serve `server` over the process's stdin and stdout until stdin closes.
+pub fn run(server: Arc<McpServer>) -> Result<(), ServerError> {
+    run_with(
+        server,
+        StdioOptions::default(),
+        io::stdin().lock(),
+        io::stdout(),
+    )
+}
+
+/// Serve `server` over `reader` and `writer` until `reader` reaches EOF.
+///
+/// Each request is handled on its own thread. A writer thread owns
+/// `writer` and writes responses together with the requests and
+/// notifications the server sends through its request and notification
+/// senders, which are installed for the duration of the call. Returns once
+/// the requests in flight at EOF have been answered, or after the first
+/// read or write error.
+pub fn run_with<R, W>(
+    server: Arc<McpServer>,
+    options: StdioOptions,
+    mut reader: R,
+    writer: W,
+) -> Result<(), ServerError>
+where
+    R: BufRead,
+    W: Write + Send + 'static,
+{
+    let (tx, rx) = mpsc::channel();
+    let writer = thread::spawn(move || write_messages(rx, writer));
+    let outgoing: Outgoing = Arc::new(move |message| tx.send(message).is_ok());
+
+    install_senders(&server, &outgoing);
+    let read = read_messages(&server, &options, &mut reader, &outgoing, &writer);
+    uninstall_senders(&server);
+    drop(outgoing);
+
+    let written = writer.join().expect("stdio writer thread panicked");
+    read.and(written)
+}
+
+fn read_messages<R: BufRead>(
+    server: &Arc<McpServer>,
+    options: &StdioOptions,
+    reader: &mut R,
+    outgoing: &Outgoing,
+    writer: &JoinHandle<Result<(), ServerError>>,
+) -> Result<(), ServerError> {
+    let mut line = String::new();
+    loop {
+        line.clear();
+        if reader.read_line(&mut line)? == 0 {
+            return Ok(());
+        }
+        // The writer only stops early when writing failed
+        if writer.is_finished() {
+            return Ok(());
+        }
+        let Some(message) = parse_line(&line) else {
+            continue;
+        };
+
+        match message {
+            JsonRpcMessage::Request(request) => {
+                if let Some(before_request) = &options.before_request {
+                    block_on(before_request(&request));
+                }
+                let server = Arc::clone(server);
+                let outgoing = Arc::clone(outgoing);
+                thread::spawn(move || {
+                    let response = block_on(server.server().handle_request(request, None));
+                    respond(&outgoing, response);
+                });
+            }
+            JsonRpcMessage::Notification(notification) => {
+                if let Err(error) =
+                    block_on(server.server().handle_notification(notification, None))
+                {
+                    tracing::error!("Error handling notification: {}", error);
+                }
+            }
+            JsonRpcMessage::Result(response) => {
+                server.server().handle_response(response, None);
+            }
+        }
+    }
+}
+
+fn write_messages<W: Write>(
+    messages: mpsc::Receiver<JsonRpcMessage>,
+    mut writer: W,
+) -> Result<(), ServerError> {
+    for message in messages {
+        let Some(serialized) = serialize_line(&message) else {
+            continue;
+        };
+        writer.write_all(serialized.as_bytes())?;
+        writer.flush()?;
+    }
+    Ok(())
+}
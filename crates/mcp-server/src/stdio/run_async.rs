@@ -0,0 +1,115 @@
+use std::sync::Arc;
+
+use mcp_core::stdio::JsonRpcMessage;
+use tokio::io::{AsyncBufRead, AsyncBufReadExt, AsyncWrite, AsyncWriteExt, BufReader};
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+
+use crate::server::{McpServer, ServerError};
+use crate::stdio::StdioOptions;
+use crate::stdio::outgoing::{
+    Outgoing, install_senders, parse_line, respond, serialize_line, uninstall_senders,
+};
+
+/// Serve `server` over the process's stdin and stdout until stdin closes,
+/// handling requests as tokio tasks.
+pub async fn run_async(server: Arc<McpServer>) -> Result<(), ServerError> {
+    run_async_with(
+        server,
+        StdioOptions::default(),
+        BufReader::new(tokio::io::stdin()),
+        tokio::io::stdout(),
+    )
+    .await
+}
+
+/// Serve `server` over `reader` and `writer` until `reader` reaches EOF.
+///
+/// The async counterpart of `run_with`: each request is handled on its own
+/// task and a writer task owns `writer`. Must be called within a tokio
+/// runtime.
+pub async fn run_async_with<R, W>(
+    server: Arc<McpServer>,
+    options: StdioOptions,
+    mut reader: R,
+    writer: W,
+) -> Result<(), ServerError>
+where
+    R: AsyncBufRead + Unpin,
+    W: AsyncWrite + Unpin + Send + 'static,
+{
+    let (tx, rx) = mpsc::unbounded_channel();
+    let writer = tokio::spawn(write_messages(rx, writer));
+    let outgoing: Outgoing = Arc::new(move |message| tx.send(message).is_ok());
+
+    install_senders(&server, &outgoing);
+    let read = read_messages(&server, &options, &mut reader, &outgoing, &writer).await;
+    uninstall_senders(&server);
+    drop(outgoing);
+
+    let written = writer.await.expect("stdio writer task panicked");
+    read.and(written)
+}
+
+async fn read_messages<R: AsyncBufRead + Unpin>(
+    server: &Arc<McpServer>,
+    options: &StdioOptions,
+    reader: &mut R,
+    outgoing: &Outgoing,
+    writer: &JoinHandle<Result<(), ServerError>>,
+) -> Result<(), ServerError> {
+    let mut line = String::new();
+    loop {
+        line.clear();
+        if reader.read_line(&mut line).await? == 0 {
+            return Ok(());
+        }
+        // The writer only stops early when writing failed
+        if writer.is_finished() {
+            return Ok(());
+        }
+        let Some(message) = parse_line(&line) else {
+            continue;
+        };
+
+        match message {
+            JsonRpcMessage::Request(request) => {
+                if let Some(before_request) = &options.before_request {
+                    before_request(&request).await;
+                }
+                let server = Arc::clone(server);
+                let outgoing = Arc::clone(outgoing);
+                tokio::spawn(async move {
+                    let response = server.server().handle_request(request, None).await;
+                    respond(&outgoing, response);
+                });
+            }
+            JsonRpcMessage::Notification(notification) => {
+                if let Err(error) = server
+                    .server()
+                    .handle_notification(notification, None)
+                    .await
+                {
+                    tracing::error!("Error handling notification: {}", error);
+                }
+            }
+            JsonRpcMessage::Result(response) => {
+                server.server().handle_response(response, None);
+            }
+        }
+    }
+}
+
+async fn write_messages<W: AsyncWrite + Unpin>(
+    mut messages: mpsc::UnboundedReceiver<JsonRpcMessage>,
+    mut writer: W,
+) -> Result<(), ServerError> {
+    while let Some(message) = messages.recv().await {
+        let Some(serialized) = serialize_line(&message) else {
+            continue;
+        };
+        writer.write_all(serialized.as_bytes()).await?;
+        writer.flush().await?;
+    }
+    Ok(())
+}
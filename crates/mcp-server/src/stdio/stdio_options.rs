@@ -0,0 +1,9 @@
+use crate::stdio::before_request::BeforeRequest;
+
+/// Configuration options for the stdio run loop.
+#[derive(Clone, Default)]
+pub struct StdioOptions {
+    /// Awaited on the read loop before a request is handed to the server;
+    /// messages read after it wait until it completes.
+    pub before_request: Option<BeforeRequest>,
+}
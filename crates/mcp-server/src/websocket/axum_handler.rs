@@ -3,68 +3,152 @@
 //! Provides a full-duplex WebSocket transport for MCP communication.
 
 use std::collections::HashMap;
-use std::sync::Arc;
+use std::error::Error as _;
+use std::sync::{Arc, RwLock as StdRwLock};
+use std::time::Duration;
 
 use axum::body::Body;
-use axum::extract::ws::{Message, WebSocket, WebSocketUpgrade};
+use axum::extract::ws::{close_code, CloseFrame, Message, WebSocket, WebSocketUpgrade};
 use axum::extract::State;
-use axum::http::{header, Method, StatusCode};
+use axum::http::{header, HeaderMap, StatusCode};
 use axum::response::Response;
 use axum::routing::get;
 use axum::Router;
 use futures::stream::{SplitSink, SplitStream};
 use futures::{SinkExt, StreamExt};
 use tokio::sync::{mpsc, RwLock};
-use tower_http::cors::{Any, CorsLayer};
+use tokio::time::Interval;
+use tokio_tungstenite::tungstenite;
 
+use mcp_core::protocol::SessionData;
 use mcp_core::stdio::{deserialize_message, serialize_message, JsonRpcMessage};
+use mcp_core::types::{ErrorCode, ErrorObject, MessageId, ResultMessage};
 
+use super::connection_close_listener::ConnectionCloseListener;
+use crate::http::CorsPolicy;
 use crate::server::McpServer;
 
 /// MCP WebSocket subprotocol identifier.
 pub const MCP_SUBPROTOCOL: &str = "mcp";
 
+/// Versioned MCP WebSocket subprotocol identifier.
+pub const MCP_SUBPROTOCOL_V1: &str = "mcp.v1";
+
+/// How long a closing connection waits for the client's close frame.
+const CLOSE_TIMEOUT: Duration = Duration::from_secs(5);
+
 /// Configuration for the WebSocket handler.
 #[derive(Debug, Clone)]
 pub struct WebSocketConfig {
     /// Endpoint path (default: "/ws").
     pub endpoint_path: String,
-    /// Enable CORS.
-    pub enable_cors: bool,
+    /// CORS policy for the upgrade request. The default grants no
+    /// cross-origin access.
+    pub cors: CorsPolicy,
     /// Channel buffer size for outgoing messages.
     pub channel_buffer_size: usize,
+    /// Interval between pings, so proxies don't drop quiet connections
+    /// (default: 30 seconds). `None` disables pings.
+    pub ping_interval: Option<Duration>,
+    /// Close the connection with code 1001 after this long without any
+    /// frame from the client, pongs included (default: 5 minutes).
+    /// `None` keeps idle connections open.
+    pub idle_timeout: Option<Duration>,
+    /// Largest message accepted, in bytes (default: 16 MiB). Larger ones
+    /// are rejected with a -32600 error and close code 1009.
+    pub max_message_size: usize,
+    /// Largest single frame accepted, in bytes (default: 4 MiB).
+    pub max_frame_size: usize,
+    /// Subprotocols the server speaks, most preferred first (default:
+    /// `mcp.v1`, then `mcp`). The first one the client offers is echoed in
+    /// the handshake; an upgrade offering none of them is rejected with 400.
+    /// Clients offering no subprotocol at all are accepted without one.
+    pub subprotocols: Vec<String>,
 }
 
 impl Default for WebSocketConfig {
     fn default() -> Self {
         Self {
             endpoint_path: "/ws".to_string(),
-            enable_cors: true,
+            cors: CorsPolicy::default(),
             channel_buffer_size: 100,
+            ping_interval: Some(Duration::from_secs(30)),
+            idle_timeout: Some(Duration::from_secs(300)),
+            max_message_size: 16 << 20,
+            max_frame_size: 4 << 20,
+            subprotocols: vec![MCP_SUBPROTOCOL_V1.to_string(), MCP_SUBPROTOCOL.to_string()],
         }
     }
 }
 
+/// Frames queued for a connection's writer.
+enum Outgoing {
+    /// A JSON-RPC message, sent as a text frame.
+    Json(JsonRpcMessage),
+    /// A raw frame; the writer stops after a close frame.
+    Frame(Message),
+}
+
 /// Per-connection state.
 struct ConnectionState {
     /// Sender for outgoing messages.
-    tx: mpsc::Sender<JsonRpcMessage>,
+    tx: mpsc::Sender<Outgoing>,
+    /// Subprotocol negotiated in the handshake.
+    subprotocol: Option<String>,
 }
 
+/// Open connections by id, shared with the server's notification sender.
+type ConnectionMap = Arc<RwLock<HashMap<String, ConnectionState>>>;
+
 /// Shared state for the WebSocket handler.
 pub struct WebSocketState {
     server: Arc<McpServer>,
-    connections: RwLock<HashMap<String, ConnectionState>>,
+    connections: ConnectionMap,
     config: WebSocketConfig,
+    close_listener: StdRwLock<Option<ConnectionCloseListener>>,
 }
 
 impl WebSocketState {
     /// Create a new WebSocket handler state.
+    ///
+    /// Server-initiated notifications are routed to the connection they
+    /// belong to; notifications for no particular connection, such as list
+    /// changes, go to every connection.
     pub fn new(server: Arc<McpServer>, config: WebSocketConfig) -> Self {
+        let connections: ConnectionMap = Arc::new(RwLock::new(HashMap::new()));
+
+        let sender_connections = Arc::clone(&connections);
+        server
+            .server()
+            .set_notification_sender(Some(Arc::new(move |connection_id, notification| {
+                let Ok(runtime) = tokio::runtime::Handle::try_current() else {
+                    return;
+                };
+                let connections = Arc::clone(&sender_connections);
+                runtime.spawn(async move {
+                    let targets: Vec<_> = {
+                        let connections = connections.read().await;
+                        match &connection_id {
+                            Some(id) => connections
+                                .get(id)
+                                .map(|conn| conn.tx.clone())
+                                .into_iter()
+                                .collect(),
+                            None => connections.values().map(|conn| conn.tx.clone()).collect(),
+                        }
+                    };
+                    let message = JsonRpcMessage::Notification(notification);
+                    for tx in targets {
+                        let _ = tx.send(Outgoing::Json(message.clone())).await;
+                    }
+                });
+            })));
+
         Self {
             server,
-            connections: RwLock::new(HashMap::new()),
+            connections,
             config,
+            close_listener: StdRwLock::new(None),
         }
     }
 
@@ -78,16 +162,40 @@ impl WebSocketState {
         &self.config
     }
 
+    /// Set the callback run when a connection closes, replacing any
+    /// previous one.
+    pub fn on_connection_closed(&self, listener: ConnectionCloseListener) {
+        *self.close_listener.write().unwrap() = Some(listener);
+    }
+
+    /// Subprotocol negotiated by an open connection, e.g. `mcp.v1`, so
+    /// handlers can branch on the protocol version. The connection id is the
+    /// `session_id` of its requests.
+    pub async fn subprotocol(&self, connection_id: &str) -> Option<String> {
+        let connections = self.connections.read().await;
+        connections.get(connection_id)?.subprotocol.clone()
+    }
+
     /// Register a new connection.
-    async fn register_connection(&self, connection_id: String, tx: mpsc::Sender<JsonRpcMessage>) {
+    async fn register_connection(
+        &self,
+        connection_id: String,
+        tx: mpsc::Sender<Outgoing>,
+        subprotocol: Option<String>,
+    ) {
         let mut connections = self.connections.write().await;
-        connections.insert(connection_id, ConnectionState { tx });
+        connections.insert(connection_id, ConnectionState { tx, subprotocol });
     }
 
-    /// Unregister a connection.
+    /// Unregister a connection, dropping its outgoing sender and resource
+    /// subscriptions, and notify the close listener.
     async fn unregister_connection(&self, connection_id: &str) {
-        let mut connections = self.connections.write().await;
-        connections.remove(connection_id);
+        let removed = self.connections.write().await.remove(connection_id);
+        self.server.remove_session_subscriptions(connection_id);
+        let listener = self.close_listener.read().unwrap().clone();
+        if let (Some(_), Some(listener)) = (removed, listener) {
+            listener(connection_id);
+        }
     }
 
     /// Send a message to a specific connection.
@@ -99,7 +207,7 @@ impl WebSocketState {
         let connections = self.connections.read().await;
         if let Some(conn) = connections.get(connection_id) {
             conn.tx
-                .send(message)
+                .send(Outgoing::Json(message))
                 .await
                 .map_err(|_| WebSocketError::ConnectionClosed)?;
             Ok(())
@@ -112,7 +220,7 @@ impl WebSocketState {
     pub async fn broadcast(&self, message: JsonRpcMessage) {
         let connections = self.connections.read().await;
         for conn in connections.values() {
-            let _ = conn.tx.send(message.clone()).await;
+            let _ = conn.tx.send(Outgoing::Json(message.clone())).await;
         }
     }
 
@@ -137,39 +245,50 @@ pub enum WebSocketError {
 
 /// Create an axum router for WebSocket MCP server.
 pub fn create_websocket_router(state: Arc<WebSocketState>) -> Router {
-    let mut router = Router::new()
+    Router::new()
         .route(&state.config.endpoint_path, get(handle_websocket_upgrade))
-        .with_state(state.clone());
-
-    if state.config.enable_cors {
-        router = router.layer(
-            CorsLayer::new()
-                .allow_origin(Any)
-                .allow_methods([Method::GET])
-                .allow_headers([
-                    header::UPGRADE,
-                    header::CONNECTION,
-                    header::SEC_WEBSOCKET_KEY,
-                    header::SEC_WEBSOCKET_VERSION,
-                    header::SEC_WEBSOCKET_PROTOCOL,
-                ]),
-        );
-    }
-
-    router
+        .with_state(state.clone())
+        .layer(state.config.cors.layer())
 }
 
 /// Handle WebSocket upgrade request.
 async fn handle_websocket_upgrade(
     State(state): State<Arc<WebSocketState>>,
+    headers: HeaderMap,
     ws: WebSocketUpgrade,
 ) -> Response {
-    // Accept the WebSocket upgrade with MCP subprotocol
-    ws.protocols([MCP_SUBPROTOCOL])
+    let supported = &state.config.subprotocols;
+    let ws = match headers.get(header::SEC_WEBSOCKET_PROTOCOL) {
+        None => ws,
+        Some(offered) => match select_subprotocol(offered.to_str().unwrap_or_default(), supported) {
+            Some(protocol) => ws.protocols([protocol.clone()]),
+            None => {
+                return error_response(
+                    StatusCode::BAD_REQUEST,
+                    &format!("Unsupported WebSocket subprotocol; supported: {}", supported.join(", ")),
+                );
+            }
+        },
+    };
+    ws.max_message_size(state.config.max_message_size)
+        .max_frame_size(state.config.max_frame_size)
         .on_upgrade(move |socket| handle_websocket(state, socket))
 }
 
+/// The first of `supported` listed in an offered `Sec-WebSocket-Protocol`.
+fn select_subprotocol<'a>(offered: &str, supported: &'a [String]) -> Option<&'a String> {
+    supported
+        .iter()
+        .find(|protocol| offered.split(',').any(|offer| offer.trim() == protocol.as_str()))
+}
+
 /// Handle an established WebSocket connection.
+///
+/// Pings, the idle timeout and the message size limit of the state's
+/// configuration are enforced here; the frame size limit only applies
+/// when the socket was upgraded with it, as `create_websocket_router` does.
+/// The subprotocol the socket was upgraded with is recorded for
+/// `WebSocketState::subprotocol`.
 pub async fn handle_websocket(state: Arc<WebSocketState>, socket: WebSocket) {
     // Generate a unique connection ID
     let connection_id = generate_connection_id();
@@ -178,7 +297,13 @@ pub async fn handle_websocket(state: Arc<WebSocketState>, socket: WebSocket) {
     let (tx, rx) = mpsc::channel(state.config.channel_buffer_size);
 
     // Register the connection
-    state.register_connection(connection_id.clone(), tx).await;
+    let subprotocol = socket
+        .protocol()
+        .and_then(|protocol| protocol.to_str().ok())
+        .map(str::to_string);
+    state
+        .register_connection(connection_id.clone(), tx.clone(), subprotocol)
+        .await;
 
     // Split the WebSocket
     let (ws_sink, ws_stream) = socket.split();
@@ -188,9 +313,10 @@ pub async fn handle_websocket(state: Arc<WebSocketState>, socket: WebSocket) {
         state.clone(),
         connection_id.clone(),
         ws_stream,
+        tx,
     ));
 
-    let write_task = tokio::spawn(handle_outgoing(ws_sink, rx));
+    let write_task = tokio::spawn(handle_outgoing(ws_sink, rx, state.config.ping_interval));
 
     // Wait for either task to complete
     tokio::select! {
@@ -207,27 +333,94 @@ async fn handle_incoming(
     state: Arc<WebSocketState>,
     connection_id: String,
     mut stream: SplitStream<WebSocket>,
+    tx: mpsc::Sender<Outgoing>,
 ) {
-    while let Some(result) = stream.next().await {
-        match result {
-            Ok(msg) => {
-                if let Err(e) = process_message(&state, &connection_id, msg).await {
+    // Handler storage for this connection, dropped when it closes
+    let session_data = Arc::new(SessionData::default());
+    let max_message_size = state.config.max_message_size;
+    let close = loop {
+        let next = match state.config.idle_timeout {
+            Some(idle_timeout) => match tokio::time::timeout(idle_timeout, stream.next()).await {
+                Ok(next) => next,
+                Err(_) => break Some((close_code::AWAY, "idle timeout")),
+            },
+            None => stream.next().await,
+        };
+        match next {
+            Some(Ok(msg)) if message_len(&msg) > max_message_size => {
+                break Some(reject_oversized(&tx, max_message_size).await);
+            }
+            Some(Ok(msg)) => {
+                if let Err(e) = process_message(&state, &connection_id, &session_data, msg).await {
                     eprintln!("Error processing message: {}", e);
-                    break;
+                    break None;
                 }
             }
-            Err(e) => {
+            Some(Err(e)) if is_capacity_error(&e) => {
+                break Some(reject_oversized(&tx, max_message_size).await);
+            }
+            Some(Err(e)) => {
                 eprintln!("WebSocket receive error: {}", e);
-                break;
+                break None;
             }
+            None => break None,
+        }
+    };
+
+    if let Some((code, reason)) = close {
+        let frame = CloseFrame {
+            code,
+            reason: reason.into(),
+        };
+        if tx.send(Outgoing::Frame(Message::Close(Some(frame)))).await.is_ok() {
+            // Wait for the client's close frame; dropping the socket with
+            // unread data could reset the connection before ours arrives
+            let _ = tokio::time::timeout(CLOSE_TIMEOUT, async {
+                while let Some(Ok(msg)) = stream.next().await {
+                    if matches!(msg, Message::Close(_)) {
+                        break;
+                    }
+                }
+            })
+            .await;
         }
     }
 }
 
+/// Payload size of a data message.
+fn message_len(msg: &Message) -> usize {
+    match msg {
+        Message::Text(text) => text.len(),
+        Message::Binary(data) => data.len(),
+        _ => 0,
+    }
+}
+
+/// Whether a receive error is a message or frame exceeding the limits.
+fn is_capacity_error(error: &axum::Error) -> bool {
+    matches!(
+        error.source().and_then(|source| source.downcast_ref::<tungstenite::Error>()),
+        Some(tungstenite::Error::Capacity(_))
+    )
+}
+
+/// Answer an oversized message with an Invalid Request error, returning
+/// the close code to end the connection with.
+async fn reject_oversized(tx: &mpsc::Sender<Outgoing>, max_message_size: usize) -> (u16, &'static str) {
+    // The request id is unknown, as the message was not parsed
+    let message = format!("Message exceeds the maximum size of {max_message_size} bytes");
+    let error = ErrorObject::new(ErrorCode::InvalidRequest as i32, message, None);
+    let response = ResultMessage::failure(MessageId::Null, error);
+    let body = serde_json::to_string(&response).unwrap_or_default();
+    let _ = tx.send(Outgoing::Frame(Message::Text(body))).await;
+    (close_code::SIZE, "message too big")
+}
+
 /// Process a single WebSocket message.
 async fn process_message(
     state: &WebSocketState,
     connection_id: &str,
+    session_data: &Arc<SessionData>,
     msg: Message,
 ) -> Result<(), WebSocketError> {
     match msg {
@@ -242,7 +435,12 @@ async fn process_message(
                     let result = state
                         .server
                         .server()
-                        .handle_request(request, Some(connection_id.to_string()))
+                        .handle_transport_request(
+                            "websocket",
+                            request,
+                            Some(connection_id.to_string()),
+                            Some(Arc::clone(session_data)),
+                        )
                         .await;
 
                     match result {
@@ -262,8 +460,12 @@ async fn process_message(
                         .handle_notification(notification, Some(connection_id.to_string()))
                         .await;
                 }
-                JsonRpcMessage::Result(_) => {
-                    // Unexpected result from client, ignore
+                JsonRpcMessage::Result(response) => {
+                    // Responses to server-initiated requests; anything else is ignored
+                    state
+                        .server
+                        .server()
+                        .handle_response(response, Some(connection_id.to_string()));
                 }
             }
         }
@@ -273,6 +475,7 @@ async fn process_message(
                 return Box::pin(process_message(
                     state,
                     connection_id,
+                    session_data,
                     Message::Text(text.into()),
                 ))
                 .await;
@@ -289,22 +492,48 @@ async fn process_message(
     Ok(())
 }
 
-/// Handle outgoing WebSocket messages.
+/// Handle outgoing WebSocket messages, pinging the client every
+/// `ping_interval`.
 async fn handle_outgoing(
     mut sink: SplitSink<WebSocket, Message>,
-    mut rx: mpsc::Receiver<JsonRpcMessage>,
+    mut rx: mpsc::Receiver<Outgoing>,
+    ping_interval: Option<Duration>,
 ) {
-    while let Some(message) = rx.recv().await {
-        match serialize_message(&message) {
-            Ok(text) => {
-                if sink.send(Message::Text(text.into())).await.is_err() {
-                    break;
+    let mut pings = ping_interval.map(|period| {
+        tokio::time::interval_at(tokio::time::Instant::now() + period, period)
+    });
+    loop {
+        let outgoing = tokio::select! {
+            outgoing = rx.recv() => match outgoing {
+                Some(outgoing) => outgoing,
+                None => break,
+            },
+            _ = next_ping(&mut pings) => Outgoing::Frame(Message::Ping(Vec::new())),
+        };
+        let frame = match outgoing {
+            Outgoing::Json(message) => match serialize_message(&message) {
+                Ok(text) => Message::Text(text),
+                Err(e) => {
+                    eprintln!("Serialization error: {}", e);
+                    continue;
                 }
-            }
-            Err(e) => {
-                eprintln!("Serialization error: {}", e);
-            }
+            },
+            Outgoing::Frame(frame) => frame,
+        };
+        let closing = matches!(frame, Message::Close(_));
+        if sink.send(frame).await.is_err() || closing {
+            break;
+        }
+    }
+}
+
+/// Wait for the next ping, forever when pings are disabled.
+async fn next_ping(pings: &mut Option<Interval>) {
+    match pings {
+        Some(pings) => {
+            pings.tick().await;
         }
+        None => std::future::pending().await,
     }
 }
 
@@ -319,21 +548,13 @@ fn generate_connection_id() -> String {
 }
 
 /// Create a JSON error response.
-#[allow(dead_code)]
 fn error_response(status: StatusCode, message: &str) -> Response {
-    let body = serde_json::json!({
-        "jsonrpc": "2.0",
-        "id": null,
-        "error": {
-            "code": -32000,
-            "message": message
-        }
-    });
+    let body = ResultMessage::failure(MessageId::Null, ErrorObject::new(-32000, message, None));
 
     Response::builder()
         .status(status)
         .header(header::CONTENT_TYPE, "application/json")
-        .body(Body::from(body.to_string()))
+        .body(Body::from(serde_json::to_string(&body).unwrap_or_default()))
         .unwrap()
 }
 
@@ -376,7 +597,20 @@ mod tests {
     fn test_config_default() {
         let config = WebSocketConfig::default();
         assert_eq!(config.endpoint_path, "/ws");
-        assert!(config.enable_cors);
+        assert!(config.cors.allow_origins.is_empty());
         assert_eq!(config.channel_buffer_size, 100);
+        assert_eq!(config.ping_interval, Some(Duration::from_secs(30)));
+        assert_eq!(config.idle_timeout, Some(Duration::from_secs(300)));
+        assert!(config.max_frame_size <= config.max_message_size);
+        assert_eq!(config.subprotocols, ["mcp.v1", "mcp"]);
+    }
+
+    #[test]
+    fn test_select_subprotocol_prefers_server_order() {
+        let supported = WebSocketConfig::default().subprotocols;
+        assert_eq!(select_subprotocol("mcp, mcp.v1", &supported).unwrap(), "mcp.v1");
+        assert_eq!(select_subprotocol("graphql-ws,mcp", &supported).unwrap(), "mcp");
+        assert_eq!(select_subprotocol("mcp.v2", &supported), None);
+        assert_eq!(select_subprotocol("", &supported), None);
     }
 }
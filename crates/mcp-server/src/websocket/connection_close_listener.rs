@@ -0,0 +1,10 @@
+//! Callback for closed WebSocket connections.
+
+use std::sync::Arc;
+
+/// Called with the id of each connection once it has closed and been
+/// unregistered.
+///
+/// The id is also the session id requests on the connection were handled
+/// with, so servers can drop per-connection data keyed by it.
+pub type ConnectionCloseListener = Arc<dyn Fn(&str) + Send + Sync>;
@@ -5,8 +5,13 @@
 
 #[cfg(feature = "websocket")]
 mod axum_handler;
+#[cfg(feature = "websocket")]
+mod connection_close_listener;
 
 #[cfg(feature = "websocket")]
 pub use axum_handler::{
-    WebSocketConfig, WebSocketError, WebSocketState, create_websocket_router, handle_websocket,
+    MCP_SUBPROTOCOL, MCP_SUBPROTOCOL_V1, WebSocketConfig, WebSocketError, WebSocketState,
+    create_websocket_router, handle_websocket,
 };
+#[cfg(feature = "websocket")]
+pub use connection_close_listener::ConnectionCloseListener;
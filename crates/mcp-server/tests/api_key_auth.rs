@@ -0,0 +1,142 @@
+//! API key authentication configured through `ServerOptions::api_key_store`.
+
+#![cfg(feature = "axum")]
+
+mod support;
+
+use std::sync::Arc;
+
+use axum::body::Body;
+use axum::http::{Request, StatusCode, header};
+use serde_json::{Value, json};
+use tower::util::ServiceExt;
+
+use mcp_core::protocol::RequestContext;
+use mcp_core::types::{CallToolResult, ContentBlock, TextContent};
+use mcp_server::auth::InMemoryApiKeyStore;
+use mcp_server::{
+    AxumHandlerConfig, AxumHandlerState, McpServer, ServerOptions, SessionMode, ToolOptions,
+    create_router,
+};
+
+fn text_result(text: String) -> CallToolResult {
+    CallToolResult {
+        content: vec![ContentBlock::Text(TextContent::new(text))],
+        structured_content: None,
+        is_error: None,
+        meta: None,
+    }
+}
+
+/// `ci-key` may only read; `admin-key` may also deploy.
+fn router() -> axum::Router {
+    let store = InMemoryApiKeyStore::new();
+    store.add_key("ci-key", "ci", vec!["read".to_string()]);
+    store.add_key(
+        "admin-key",
+        "admin",
+        vec!["read".to_string(), "deploy".to_string()],
+    );
+    let options = ServerOptions {
+        api_key_store: Some(Arc::new(store)),
+        ..Default::default()
+    };
+
+    let mut server = McpServer::new(support::implementation("keyed-server"), options);
+    server
+        .register_tool(
+            support::tool("whoami"),
+            |_args: Option<Value>, ctx: RequestContext| async move {
+                let client_id = ctx
+                    .auth_info
+                    .and_then(|info| info.client_id)
+                    .unwrap_or_default();
+                Ok(text_result(client_id))
+            },
+        )
+        .expect("register whoami");
+    server
+        .register_tool_with_options(
+            support::tool("deploy"),
+            ToolOptions::default().with_required_scopes(["deploy"]),
+            |_args: Option<Value>, _ctx: RequestContext| async move {
+                Ok(text_result("deployed".to_string()))
+            },
+        )
+        .expect("register deploy");
+
+    let config = AxumHandlerConfig {
+        session_mode: SessionMode::Stateless,
+        ..Default::default()
+    };
+    create_router(Arc::new(AxumHandlerState::new(Arc::new(server), config)))
+}
+
+async fn call_tool(auth: Option<(&str, String)>, name: &str) -> (StatusCode, Value) {
+    let body = json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": "tools/call",
+        "params": { "name": name, "arguments": {} }
+    });
+    let mut request = Request::builder()
+        .method("POST")
+        .uri("/mcp")
+        .header(header::CONTENT_TYPE, "application/json");
+    if let Some((name, value)) = auth {
+        request = request.header(name, value);
+    }
+    let request = request.body(Body::from(body.to_string())).unwrap();
+    let response = router().oneshot(request).await.unwrap();
+    let status = response.status();
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    (status, serde_json::from_slice(&bytes).unwrap())
+}
+
+fn api_key(key: &str) -> Option<(&'static str, String)> {
+    Some(("x-api-key", key.to_string()))
+}
+
+#[tokio::test]
+async fn missing_key_is_unauthorized() {
+    let (status, body) = call_tool(None, "whoami").await;
+
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    assert_eq!(body["jsonrpc"], "2.0");
+    assert_eq!(body["id"], Value::Null);
+    assert_eq!(body["error"]["message"], "Missing API key");
+}
+
+#[tokio::test]
+async fn wrong_key_is_unauthorized() {
+    let (status, body) = call_tool(api_key("guess"), "whoami").await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    assert_eq!(body["error"]["message"], "Invalid API key");
+
+    let bearer = Some(("authorization", "Bearer guess".to_string()));
+    let (status, _) = call_tool(bearer, "whoami").await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn valid_key_reaches_tool_as_its_client() {
+    let (status, body) = call_tool(api_key("ci-key"), "whoami").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["result"]["content"][0]["text"], "ci");
+
+    let bearer = Some(("authorization", "Bearer admin-key".to_string()));
+    let (status, body) = call_tool(bearer, "whoami").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["result"]["content"][0]["text"], "admin");
+}
+
+#[tokio::test]
+async fn key_scopes_feed_tool_scope_checks() {
+    let (_, body) = call_tool(api_key("ci-key"), "deploy").await;
+    assert_eq!(body["error"]["data"]["error"], "insufficient_scope");
+
+    let (_, body) = call_tool(api_key("admin-key"), "deploy").await;
+    assert_eq!(body["result"]["content"][0]["text"], "deployed");
+}
@@ -0,0 +1,116 @@
+//! Servers constructed with `McpServer::builder`.
+
+mod support;
+
+use std::sync::Arc;
+
+use futures::executor::block_on;
+use serde_json::json;
+
+use mcp_core::protocol::RequestContext;
+use mcp_core::types::{CallToolResult, InitializeResult, LATEST_PROTOCOL_VERSION, Prompt, RequestMessage, Resource};
+use mcp_server::{InMemoryTaskStore, McpServer};
+
+fn resource(uri: &str) -> Resource {
+    serde_json::from_value(json!({ "uri": uri, "name": uri })).unwrap()
+}
+
+fn prompt(name: &str) -> Prompt {
+    serde_json::from_value(json!({ "name": name })).unwrap()
+}
+
+fn capabilities(server: &McpServer) -> serde_json::Value {
+    serde_json::to_value(server.server().get_capabilities()).unwrap()
+}
+
+#[test]
+fn capabilities_are_inferred_from_registrations() {
+    let server = McpServer::builder("builder-server", "0.1.0")
+        .tool(support::tool("echo"), |_args, _ctx: RequestContext| async move {
+            Ok(CallToolResult::default())
+        })
+        .resource(
+            resource("file:///a"),
+            |uri: String, _ctx: RequestContext| async move {
+                Ok(
+                    serde_json::from_value(json!({ "contents": [{ "uri": uri, "text": "a" }] }))
+                        .unwrap(),
+                )
+            },
+        )
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        capabilities(&server),
+        json!({
+            "tools": { "listChanged": true },
+            "resources": { "subscribe": true, "listChanged": true }
+        })
+    );
+    assert_eq!(server.list_tools().len(), 1);
+}
+
+#[test]
+fn enable_overrides_the_inferred_flags() {
+    let server = McpServer::builder("builder-server", "0.1.0")
+        .prompt(prompt("greet"), |_args, _ctx: RequestContext| async move {
+            Ok(serde_json::from_value(json!({ "messages": [] })).unwrap())
+        })
+        .enable_prompts(false)
+        .enable_tools(false)
+        .enable_resources(false, true)
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        capabilities(&server),
+        json!({
+            "prompts": { "listChanged": false },
+            "tools": { "listChanged": false },
+            "resources": { "subscribe": false, "listChanged": true }
+        })
+    );
+
+    // Enabled without registrations, the list handlers still answer
+    let request = RequestMessage::new("1", "tools/list", json!({}));
+    let response = block_on(server.server().handle_request(request, None)).expect("response");
+    assert_eq!(response.result.unwrap()["tools"], json!([]));
+}
+
+#[test]
+fn initialize_reports_the_builder_settings() {
+    let server = McpServer::builder("builder-server", "1.2.3")
+        .title("Builder Server")
+        .instructions("Use the tools")
+        .enable_logging()
+        .task_store(Arc::new(InMemoryTaskStore::default()))
+        .build()
+        .unwrap();
+
+    let request = RequestMessage::new(
+        "1",
+        "initialize",
+        json!({
+            "protocolVersion": LATEST_PROTOCOL_VERSION,
+            "capabilities": {},
+            "clientInfo": { "name": "client", "version": "0.1.0" }
+        }),
+    );
+    let response = block_on(server.server().handle_request(request, None)).expect("response");
+    let result: InitializeResult = serde_json::from_value(response.result.unwrap()).unwrap();
+    assert_eq!(result.server_info.base.name, "builder-server");
+    assert_eq!(
+        result.server_info.base.title.as_deref(),
+        Some("Builder Server")
+    );
+    assert_eq!(result.server_info.version, "1.2.3");
+    assert_eq!(result.instructions.as_deref(), Some("Use the tools"));
+    assert!(result.capabilities.logging.is_some());
+    assert!(result.capabilities.tasks.is_some());
+    assert!(result.capabilities.tools.is_none());
+
+    let request = RequestMessage::new("2", "logging/setLevel", json!({ "level": "debug" }));
+    let response = block_on(server.server().handle_request(request, None)).expect("response");
+    assert!(response.error.is_none());
+}
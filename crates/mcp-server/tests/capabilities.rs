@@ -1,12 +1,13 @@
 mod support;
 
 use futures::executor::block_on;
+use serde_json::json;
 
 use mcp_core::types::{
-    CapabilityFlag, ClientCapabilities, InitializeRequestParams, LATEST_PROTOCOL_VERSION,
-    RequestMessage, RequestParams, ServerCapabilities, ToolCapabilities,
+    CapabilityFlag, ClientCapabilities, ErrorCode, InitializeRequestParams,
+    LATEST_PROTOCOL_VERSION, RequestMessage, RequestParams, ServerCapabilities, ToolCapabilities,
 };
-use mcp_server::{Server, ServerError, ServerOptions};
+use mcp_server::{McpServer, Server, ServerError, ServerOptions};
 
 #[test]
 fn register_capabilities_merges_and_locks() {
@@ -47,3 +48,89 @@ fn register_capabilities_merges_and_locks() {
         .expect_err("capabilities locked");
     assert!(matches!(err, ServerError::CapabilitiesLocked));
 }
+
+/// A server advertising tools, resources, prompts and logging, except
+/// `disabled`.
+fn server_without(disabled: &str) -> McpServer {
+    let mut builder = McpServer::builder("test-server", "0.1.0");
+    if disabled != "tools" {
+        builder = builder.enable_tools(true);
+    }
+    if disabled != "resources" {
+        builder = builder.enable_resources(true, true);
+    }
+    if disabled != "prompts" {
+        builder = builder.enable_prompts(true);
+    }
+    if disabled != "logging" {
+        builder = builder.enable_logging();
+    }
+    builder.build().expect("build server")
+}
+
+fn assert_capability_not_enabled(server: &McpServer, method: &str, params: serde_json::Value) {
+    let capability = method.split('/').next().unwrap();
+    let request = RequestMessage::new("1", method, params);
+    let response = block_on(server.server().handle_request(request, None)).expect("response");
+    let error = response.error.expect("capability error");
+    assert_eq!(error.code, ErrorCode::MethodNotFound as i32, "{method}");
+    assert_eq!(
+        error.message,
+        format!("{capability} capability not enabled")
+    );
+}
+
+fn assert_dispatched(server: &McpServer, method: &str, params: serde_json::Value) {
+    let request = RequestMessage::new("1", method, params);
+    let response = block_on(server.server().handle_request(request, None)).expect("response");
+    if let Some(error) = response.error {
+        assert_ne!(error.code, ErrorCode::MethodNotFound as i32, "{method}");
+    }
+}
+
+#[test]
+fn requests_for_disabled_tools_name_the_capability() {
+    let server = server_without("tools");
+    assert_capability_not_enabled(&server, "tools/list", json!({}));
+    assert_capability_not_enabled(&server, "tools/call", json!({ "name": "echo" }));
+    assert_dispatched(&server, "prompts/list", json!({}));
+}
+
+#[test]
+fn requests_for_disabled_resources_name_the_capability() {
+    let server = server_without("resources");
+    assert_capability_not_enabled(&server, "resources/list", json!({}));
+    assert_capability_not_enabled(&server, "resources/templates/list", json!({}));
+    assert_capability_not_enabled(&server, "resources/read", json!({ "uri": "file:///a" }));
+    assert_dispatched(&server, "tools/list", json!({}));
+}
+
+#[test]
+fn requests_for_disabled_prompts_name_the_capability() {
+    let server = server_without("prompts");
+    assert_capability_not_enabled(&server, "prompts/list", json!({}));
+    assert_capability_not_enabled(&server, "prompts/get", json!({ "name": "greet" }));
+    assert_dispatched(&server, "resources/list", json!({}));
+}
+
+#[test]
+fn requests_for_disabled_logging_name_the_capability() {
+    let server = server_without("logging");
+    assert_capability_not_enabled(&server, "logging/setLevel", json!({ "level": "info" }));
+    assert_dispatched(&server, "tools/list", json!({}));
+}
+
+#[test]
+fn subscriptions_need_the_subscribe_flag() {
+    let server = McpServer::builder("test-server", "0.1.0")
+        .enable_resources(false, true)
+        .build()
+        .expect("build server");
+    let request = RequestMessage::new("1", "resources/subscribe", json!({ "uri": "file:///a" }));
+    let response = block_on(server.server().handle_request(request, None)).expect("response");
+    let error = response.error.expect("capability error");
+    assert_eq!(error.code, ErrorCode::MethodNotFound as i32);
+    assert_eq!(error.message, "resources.subscribe capability not enabled");
+
+    assert_dispatched(&server, "resources/list", json!({}));
+}
@@ -0,0 +1,151 @@
+//! Per-tool circuit breakers configured through `ToolOptions`.
+
+mod support;
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::thread::sleep;
+use std::time::Duration;
+
+use futures::executor::block_on;
+use serde_json::json;
+
+use mcp_core::protocol::RequestContext;
+use mcp_core::types::{CallToolResult, ContentBlock, RequestMessage};
+use mcp_server::{
+    CircuitBreakerConfig, CircuitState, McpServer, ServerError, ServerOptions, ToolOptions,
+};
+
+const RESET_TIMEOUT: Duration = Duration::from_millis(50);
+
+/// A server with a `backend` tool that fails while `healthy` is false and
+/// counts how often its handler runs.
+fn server(healthy: Arc<AtomicBool>, calls: Arc<AtomicUsize>) -> McpServer {
+    let mut server = McpServer::new(
+        support::implementation("breaker-server"),
+        ServerOptions::default(),
+    );
+    server
+        .register_tool_with_options(
+            support::tool("backend"),
+            ToolOptions::default()
+                .with_circuit_breaker(CircuitBreakerConfig::new(2, RESET_TIMEOUT)),
+            move |_args, _ctx: RequestContext| {
+                let healthy = healthy.load(Ordering::SeqCst);
+                calls.fetch_add(1, Ordering::SeqCst);
+                async move {
+                    if healthy {
+                        Ok(CallToolResult::default())
+                    } else {
+                        Err(ServerError::Handler("backend unavailable".to_string()))
+                    }
+                }
+            },
+        )
+        .unwrap();
+    server
+}
+
+/// Call `backend`, returning the tool result or `None` for a protocol error.
+fn call(server: &McpServer) -> Option<CallToolResult> {
+    let request = RequestMessage::new(
+        "1",
+        "tools/call",
+        json!({ "name": "backend", "arguments": {} }),
+    );
+    let response = block_on(server.server().handle_request(request, None)).unwrap();
+    response
+        .result
+        .map(|result| serde_json::from_value(result).unwrap())
+}
+
+#[test]
+fn consecutive_failures_open_the_circuit() {
+    let healthy = Arc::new(AtomicBool::new(false));
+    let calls = Arc::new(AtomicUsize::new(0));
+    let server = server(healthy, calls.clone());
+    assert_eq!(server.circuit_state("backend"), Some(CircuitState::Closed));
+
+    assert!(call(&server).is_none());
+    assert_eq!(server.circuit_state("backend"), Some(CircuitState::Closed));
+    assert!(call(&server).is_none());
+    assert_eq!(server.circuit_state("backend"), Some(CircuitState::Open));
+    assert_eq!(calls.load(Ordering::SeqCst), 2);
+
+    // Rejected without running the handler
+    let result = call(&server).expect("tool result");
+    assert_eq!(result.is_error, Some(true));
+    let ContentBlock::Text(text) = &result.content[0] else {
+        panic!("expected text content");
+    };
+    assert!(
+        text.text.contains("circuit breaker is open"),
+        "{}",
+        text.text
+    );
+    assert_eq!(calls.load(Ordering::SeqCst), 2);
+}
+
+#[test]
+fn a_success_resets_the_failure_count() {
+    let healthy = Arc::new(AtomicBool::new(false));
+    let calls = Arc::new(AtomicUsize::new(0));
+    let server = server(healthy.clone(), calls);
+
+    call(&server);
+    healthy.store(true, Ordering::SeqCst);
+    call(&server);
+    healthy.store(false, Ordering::SeqCst);
+    call(&server);
+    assert_eq!(server.circuit_state("backend"), Some(CircuitState::Closed));
+}
+
+#[test]
+fn a_successful_trial_closes_the_circuit() {
+    let healthy = Arc::new(AtomicBool::new(false));
+    let calls = Arc::new(AtomicUsize::new(0));
+    let server = server(healthy.clone(), calls.clone());
+    call(&server);
+    call(&server);
+    assert_eq!(server.circuit_state("backend"), Some(CircuitState::Open));
+
+    sleep(RESET_TIMEOUT);
+    assert_eq!(
+        server.circuit_state("backend"),
+        Some(CircuitState::HalfOpen)
+    );
+
+    healthy.store(true, Ordering::SeqCst);
+    let result = call(&server).expect("tool result");
+    assert_ne!(result.is_error, Some(true));
+    assert_eq!(calls.load(Ordering::SeqCst), 3);
+    assert_eq!(server.circuit_state("backend"), Some(CircuitState::Closed));
+}
+
+#[test]
+fn a_failed_trial_reopens_the_circuit() {
+    let healthy = Arc::new(AtomicBool::new(false));
+    let calls = Arc::new(AtomicUsize::new(0));
+    let server = server(healthy, calls.clone());
+    call(&server);
+    call(&server);
+
+    sleep(RESET_TIMEOUT);
+    assert_eq!(
+        server.circuit_state("backend"),
+        Some(CircuitState::HalfOpen)
+    );
+
+    // A single failed trial is enough, whatever the threshold
+    assert!(call(&server).is_none());
+    assert_eq!(calls.load(Ordering::SeqCst), 3);
+    assert_eq!(server.circuit_state("backend"), Some(CircuitState::Open));
+    assert_eq!(call(&server).unwrap().is_error, Some(true));
+    assert_eq!(calls.load(Ordering::SeqCst), 3);
+}
+
+#[test]
+fn unknown_tools_report_no_state() {
+    let server = server(Arc::default(), Arc::default());
+    assert_eq!(server.circuit_state("missing"), None);
+}
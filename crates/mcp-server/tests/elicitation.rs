@@ -0,0 +1,190 @@
+mod support;
+
+use std::sync::{Arc, Mutex};
+
+use futures::channel::mpsc;
+use futures::executor::block_on;
+use futures::{StreamExt, join};
+use serde_json::{Value, json};
+
+use mcp_core::protocol::{ProtocolError, RequestContext};
+use mcp_core::types::{
+    BooleanSchema, CallToolResult, ContentBlock, ElicitAction, ElicitationSchema, ElicitationValue,
+    PrimitiveSchemaDefinition, RequestMessage, ResultMessage, TextContent,
+};
+use mcp_server::{McpServer, ServerOptions};
+
+/// Server with a tool that asks the user to confirm before acting, asking
+/// again up to `retries` times when the answer doesn't match the form.
+fn elicitation_server(retries: usize) -> McpServer {
+    let mut server = McpServer::new(
+        support::implementation("elicitation-server"),
+        ServerOptions::default(),
+    );
+    server
+        .register_tool(
+            support::tool("confirm"),
+            move |_args, ctx: RequestContext| async move {
+                let schema = ElicitationSchema::new()
+                    .with_property(
+                        "confirm",
+                        PrimitiveSchemaDefinition::Boolean(BooleanSchema::new()),
+                    )
+                    .with_required(vec!["confirm".to_string()]);
+                let elicited = if retries == 0 {
+                    ctx.elicit_form(schema, "Create the issue?").await
+                } else {
+                    ctx.elicit_form_with_retries(schema, "Create the issue?", retries)
+                        .await
+                };
+                let text = match elicited {
+                    Ok(result) => match result.action {
+                        ElicitAction::Accept => {
+                            let confirmed = result
+                                .content
+                                .and_then(|content| content.get("confirm").cloned());
+                            match confirmed {
+                                Some(ElicitationValue::Boolean(true)) => "created".to_string(),
+                                _ => "not confirmed".to_string(),
+                            }
+                        }
+                        ElicitAction::Decline => "declined".to_string(),
+                        ElicitAction::Cancel => "cancelled".to_string(),
+                    },
+                    Err(ProtocolError::Capability(_)) => "unsupported".to_string(),
+                    Err(err) => format!("error: {err}"),
+                };
+                Ok(CallToolResult {
+                    content: vec![ContentBlock::Text(TextContent::new(text))],
+                    structured_content: None,
+                    is_error: None,
+                    meta: None,
+                })
+            },
+        )
+        .expect("register tool");
+    server
+}
+
+fn initialize(server: &McpServer, capabilities: Value) {
+    let request = RequestMessage::new(
+        "init",
+        "initialize",
+        json!({
+            "protocolVersion": mcp_core::types::LATEST_PROTOCOL_VERSION,
+            "capabilities": capabilities,
+            "clientInfo": { "name": "client", "version": "0.1.0" }
+        }),
+    );
+    block_on(server.server().handle_request(request, None)).expect("initialize response");
+}
+
+async fn call_confirm(server: &McpServer) -> String {
+    let request = RequestMessage::new("call", "tools/call", json!({ "name": "confirm" }));
+    let response = server
+        .server()
+        .handle_request(request, None)
+        .await
+        .expect("tools/call response");
+    let result: CallToolResult = serde_json::from_value(response.result.unwrap()).unwrap();
+    match &result.content[0] {
+        ContentBlock::Text(text) => text.text.clone(),
+        other => panic!("unexpected content: {other:?}"),
+    }
+}
+
+/// Run the tool while a scripted client answers the elicitation with `answer`.
+fn call_with_answer(answer: Value) -> String {
+    call_with_answers(0, vec![answer]).0
+}
+
+/// Run the tool while a scripted client answers each elicitation with the
+/// next of `answers`, returning the tool's text and the messages shown.
+fn call_with_answers(retries: usize, answers: Vec<Value>) -> (String, Vec<String>) {
+    let server = elicitation_server(retries);
+    initialize(&server, json!({ "elicitation": {} }));
+
+    let (tx, mut rx) = mpsc::unbounded::<RequestMessage>();
+    server
+        .server()
+        .set_request_sender(Some(Arc::new(move |_session_id, request| {
+            let _ = tx.unbounded_send(request);
+        })));
+
+    let client = async {
+        let mut messages = Vec::new();
+        for answer in answers {
+            let request = rx.next().await.expect("elicitation request");
+            assert_eq!(request.method, "elicitation/create");
+            assert_eq!(request.params["mode"], json!("form"));
+            assert_eq!(
+                request.params["requestedSchema"]["properties"]["confirm"]["type"],
+                json!("boolean")
+            );
+            messages.push(request.params["message"].as_str().unwrap().to_string());
+            let response = ResultMessage::success(request.id, answer);
+            assert!(server.server().handle_response(response, None));
+        }
+        messages
+    };
+
+    let (text, messages) = block_on(async { join!(call_confirm(&server), client) });
+    assert_eq!(messages[0], "Create the issue?");
+    (text, messages)
+}
+
+#[test]
+fn tool_receives_accepted_form_values() {
+    let text = call_with_answer(json!({ "action": "accept", "content": { "confirm": true } }));
+    assert_eq!(text, "created");
+}
+
+#[test]
+fn tool_sees_decline_and_cancel() {
+    assert_eq!(call_with_answer(json!({ "action": "decline" })), "declined");
+    assert_eq!(call_with_answer(json!({ "action": "cancel" })), "cancelled");
+}
+
+#[test]
+fn tool_gets_an_error_for_content_not_matching_the_form() {
+    let text = call_with_answer(json!({ "action": "accept", "content": { "confirm": "yes" } }));
+    assert!(text.starts_with("error: "), "{text}");
+    assert!(text.contains("`confirm` must be a boolean"), "{text}");
+
+    let text = call_with_answer(json!({ "action": "accept", "content": {} }));
+    assert!(text.contains("`confirm` is required"), "{text}");
+}
+
+#[test]
+fn invalid_content_can_be_asked_for_again() {
+    let (text, messages) = call_with_answers(
+        1,
+        vec![
+            json!({ "action": "accept", "content": { "confirm": "yes" } }),
+            json!({ "action": "accept", "content": { "confirm": true } }),
+        ],
+    );
+    assert_eq!(text, "created");
+    assert_eq!(messages.len(), 2);
+    assert_eq!(
+        messages[1],
+        "Create the issue?\n\n`confirm` must be a boolean"
+    );
+}
+
+#[test]
+fn elicitation_requires_client_capability() {
+    let server = elicitation_server(0);
+    initialize(&server, json!({ "sampling": {} }));
+
+    let sent = Arc::new(Mutex::new(Vec::new()));
+    let sink = sent.clone();
+    server
+        .server()
+        .set_request_sender(Some(Arc::new(move |_session_id, request| {
+            sink.lock().unwrap().push(request);
+        })));
+
+    assert_eq!(block_on(call_confirm(&server)), "unsupported");
+    assert!(sent.lock().unwrap().is_empty());
+}
@@ -0,0 +1,277 @@
+//! Draining a server with `McpServer::shutdown` and `McpServer::drain`, and
+//! ending SSE streams of the axum router on shutdown.
+
+#![cfg(feature = "axum")]
+
+mod support;
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use axum::body::Body;
+use axum::http::{Request, StatusCode, header};
+use serde_json::json;
+use tokio::sync::Notify;
+use tower::util::ServiceExt;
+
+use mcp_core::protocol::{CancellationToken, ProtocolOptions, RequestContext, TaskStore};
+use mcp_core::types::{
+    CallToolResult, ContentBlock, CreateTaskResult, RequestMessage, TaskStatus, TextContent,
+};
+use mcp_server::{
+    AxumHandlerConfig, AxumHandlerState, InMemoryTaskStore, McpServer, ServerOptions,
+    create_router, create_router_with_shutdown, tokio_task_spawner,
+};
+
+/// Register a tool sleeping for `duration`, notifying `started` first.
+fn register_sleeper(server: &mut McpServer, duration: Duration, started: Arc<Notify>) {
+    server
+        .register_tool(
+            support::tool("sleep"),
+            move |_args, _ctx: RequestContext| {
+                let started = started.clone();
+                async move {
+                    started.notify_one();
+                    tokio::time::sleep(duration).await;
+                    Ok(CallToolResult {
+                        content: vec![ContentBlock::Text(TextContent::new("woke up"))],
+                        structured_content: None,
+                        is_error: None,
+                        meta: None,
+                    })
+                }
+            },
+        )
+        .expect("register tool");
+}
+
+fn call_sleep(params: serde_json::Value) -> RequestMessage {
+    RequestMessage::new("call", "tools/call", params)
+}
+
+#[tokio::test]
+async fn in_flight_calls_finish_within_the_grace_period() {
+    let started = Arc::new(Notify::new());
+    let mut server = McpServer::new(
+        support::implementation("shutdown-server"),
+        ServerOptions::default(),
+    );
+    register_sleeper(&mut server, Duration::from_millis(500), started.clone());
+    let server = Arc::new(server);
+
+    let call = tokio::spawn({
+        let server = Arc::clone(&server);
+        async move {
+            server
+                .server()
+                .handle_request(call_sleep(json!({ "name": "sleep" })), None)
+                .await
+        }
+    });
+    started.notified().await;
+
+    let shutdown = tokio::spawn({
+        let server = Arc::clone(&server);
+        async move { server.shutdown(Duration::from_secs(10)).await }
+    });
+    while !server.server().is_shutting_down() {
+        tokio::task::yield_now().await;
+    }
+
+    // New requests are refused while the call drains
+    let refused = server
+        .server()
+        .handle_request(RequestMessage::new("ping", "ping", json!({})), None)
+        .await
+        .unwrap();
+    assert_eq!(refused.error.unwrap().message, "server shutting down");
+
+    let response = call.await.unwrap().unwrap();
+    let result: CallToolResult = serde_json::from_value(response.result.unwrap()).unwrap();
+    assert!(matches!(&result.content[0], ContentBlock::Text(text) if text.text == "woke up"));
+
+    // Shutdown resolves as soon as the call is done, well before the grace period
+    tokio::time::timeout(Duration::from_secs(5), shutdown)
+        .await
+        .expect("shutdown should finish once drained")
+        .unwrap();
+    assert!(server.server().shutdown_token().is_cancelled());
+}
+
+#[tokio::test]
+async fn tasks_still_running_after_the_grace_period_fail() {
+    let store = Arc::new(InMemoryTaskStore::default());
+    let options = ServerOptions {
+        protocol_options: Some(ProtocolOptions {
+            task_store: Some(store.clone()),
+            task_spawner: Some(tokio_task_spawner()),
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+    let mut server = McpServer::new(support::implementation("shutdown-server"), options);
+    register_sleeper(
+        &mut server,
+        Duration::from_secs(30),
+        Arc::new(Notify::new()),
+    );
+
+    let response = server
+        .server()
+        .handle_request(
+            call_sleep(json!({ "name": "sleep", "task": { "ttl": 60000 } })),
+            None,
+        )
+        .await
+        .unwrap();
+    let created: CreateTaskResult = serde_json::from_value(response.result.unwrap()).unwrap();
+    let task_id = created.task.task_id;
+
+    tokio::time::timeout(
+        Duration::from_secs(5),
+        server.shutdown(Duration::from_millis(100)),
+    )
+    .await
+    .expect("shutdown should give up after the grace period");
+
+    let task = store.get_task(&task_id).await.unwrap().unwrap();
+    assert_eq!(task.status, TaskStatus::Failed);
+    assert_eq!(task.status_message.as_deref(), Some("server shutting down"));
+}
+
+#[tokio::test]
+async fn drain_waits_for_in_flight_calls_and_keeps_streams_open() {
+    let started = Arc::new(Notify::new());
+    let mut server = McpServer::new(
+        support::implementation("drain-server"),
+        ServerOptions::default(),
+    );
+    register_sleeper(&mut server, Duration::from_millis(200), started.clone());
+    let server = Arc::new(server);
+
+    let call = tokio::spawn({
+        let server = Arc::clone(&server);
+        async move {
+            server
+                .server()
+                .handle_request(call_sleep(json!({ "name": "sleep" })), None)
+                .await
+        }
+    });
+    started.notified().await;
+
+    tokio::time::timeout(
+        Duration::from_secs(5),
+        server.drain(Duration::from_secs(10)),
+    )
+    .await
+    .expect("drain should finish once the call is done");
+    let response = call.await.unwrap().unwrap();
+    assert!(response.error.is_none());
+
+    let refused = server
+        .server()
+        .handle_request(RequestMessage::new("ping", "ping", json!({})), None)
+        .await
+        .unwrap();
+    assert_eq!(refused.error.unwrap().message, "server shutting down");
+    assert!(!server.server().shutdown_token().is_cancelled());
+}
+
+#[tokio::test]
+async fn tasks_still_running_after_drain_are_cancelled() {
+    let store = Arc::new(InMemoryTaskStore::default());
+    let options = ServerOptions {
+        protocol_options: Some(ProtocolOptions {
+            task_store: Some(store.clone()),
+            task_spawner: Some(tokio_task_spawner()),
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+    let mut server = McpServer::new(support::implementation("drain-server"), options);
+    register_sleeper(
+        &mut server,
+        Duration::from_secs(30),
+        Arc::new(Notify::new()),
+    );
+
+    let response = server
+        .server()
+        .handle_request(
+            call_sleep(json!({ "name": "sleep", "task": { "ttl": 60000 } })),
+            None,
+        )
+        .await
+        .unwrap();
+    let created: CreateTaskResult = serde_json::from_value(response.result.unwrap()).unwrap();
+    let task_id = created.task.task_id;
+
+    tokio::time::timeout(
+        Duration::from_secs(5),
+        server.drain(Duration::from_millis(100)),
+    )
+    .await
+    .expect("drain should give up after the timeout");
+
+    let task = store.get_task(&task_id).await.unwrap().unwrap();
+    assert_eq!(task.status, TaskStatus::Cancelled);
+    assert!(!server.server().shutdown_token().is_cancelled());
+}
+
+/// Open a GET stream on `router`, returning its body.
+async fn open_stream(router: axum::Router) -> Body {
+    let request = Request::get("/mcp")
+        .header(header::ACCEPT, "text/event-stream")
+        .body(Body::empty())
+        .unwrap();
+    let response = router.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    response.into_body()
+}
+
+/// Read the rest of an SSE body, failing if it does not end.
+async fn read_to_end(body: Body) -> String {
+    let bytes = tokio::time::timeout(
+        Duration::from_secs(5),
+        axum::body::to_bytes(body, usize::MAX),
+    )
+    .await
+    .expect("stream should end")
+    .unwrap();
+    String::from_utf8(bytes.to_vec()).unwrap()
+}
+
+#[tokio::test]
+async fn streams_end_when_the_server_shuts_down() {
+    let server = Arc::new(McpServer::new(
+        support::implementation("shutdown-server"),
+        ServerOptions::default(),
+    ));
+    let state = Arc::new(AxumHandlerState::new(
+        Arc::clone(&server),
+        AxumHandlerConfig::default(),
+    ));
+    let body = open_stream(create_router(state)).await;
+
+    server.shutdown(Duration::from_secs(1)).await;
+    assert!(
+        read_to_end(body)
+            .await
+            .ends_with(": shutdown: server is shutting down\n\n")
+    );
+}
+
+#[tokio::test]
+async fn streams_end_on_the_router_shutdown_signal() {
+    let server = Arc::new(McpServer::new(
+        support::implementation("shutdown-server"),
+        ServerOptions::default(),
+    ));
+    let state = Arc::new(AxumHandlerState::new(server, AxumHandlerConfig::default()));
+    let signal = CancellationToken::default();
+    let body = open_stream(create_router_with_shutdown(state, signal.clone())).await;
+
+    signal.cancel();
+    assert!(read_to_end(body).await.contains(": shutdown"));
+}
@@ -15,7 +15,7 @@ use axum::body::Body;
 use axum::http::{header, Request, StatusCode};
 use mcp_core::types::{BaseMetadata, Icons, Implementation, ServerCapabilities};
 use mcp_server::{
-    AxumHandlerConfig, AxumHandlerState, McpServer, ServerOptions, create_router,
+    AxumHandlerConfig, AxumHandlerState, CorsPolicy, McpServer, ServerOptions, create_router,
 };
 use tower::util::ServiceExt;
 
@@ -49,7 +49,7 @@ fn create_test_state() -> Arc<AxumHandlerState> {
         endpoint_path: "/mcp".to_string(),
         keep_alive_interval: Duration::from_secs(30),
         broadcast_capacity: 100,
-        enable_cors: true,
+        cors: CorsPolicy::permissive(),
         ..Default::default()
     };
     Arc::new(AxumHandlerState::new(server, config))
@@ -134,6 +134,39 @@ async fn test_post_invalid_json() {
     assert_eq!(response.status(), StatusCode::BAD_REQUEST);
 }
 
+async fn delete_session(state: &Arc<AxumHandlerState>, session_id: &str) -> StatusCode {
+    let request = Request::builder()
+        .method("DELETE")
+        .uri("/mcp")
+        .header("mcp-session-id", session_id)
+        .body(Body::empty())
+        .unwrap();
+    create_router(state.clone()).oneshot(request).await.unwrap().status()
+}
+
+#[tokio::test]
+async fn test_delete_is_idempotent() {
+    let state = create_test_state();
+    let session_id = state.session_manager().create_session().await.unwrap().session_id.to_string();
+
+    assert_eq!(delete_session(&state, &session_id).await, StatusCode::NO_CONTENT);
+    assert_eq!(state.session_manager().session_count().await, 0);
+
+    // Already deleted, and never existed
+    assert_eq!(delete_session(&state, &session_id).await, StatusCode::OK);
+    assert_eq!(delete_session(&state, "unknown-session").await, StatusCode::OK);
+}
+
+#[tokio::test]
+async fn test_delete_malformed_session_id() {
+    let state = create_test_state();
+    let session_id = state.session_manager().create_session().await.unwrap().session_id.to_string();
+
+    assert_eq!(delete_session(&state, "has space").await, StatusCode::BAD_REQUEST);
+    assert_eq!(delete_session(&state, "").await, StatusCode::BAD_REQUEST);
+    assert!(state.session_manager().get_session(&session_id).await.is_some());
+}
+
 #[tokio::test]
 async fn test_get_sse_connection() {
     let state = create_test_state();
@@ -313,7 +346,8 @@ async fn test_event_buffer() {
 
     let config = EventBufferConfig {
         max_events: 5,
-        max_age_secs: 300,
+        max_age: Duration::from_secs(300),
+        ..Default::default()
     };
     let mut buffer = EventBuffer::new(config);
 
@@ -378,6 +412,38 @@ async fn test_broadcaster() {
     assert_eq!(replay.len(), 1);
 }
 
+#[tokio::test]
+async fn test_broadcaster_sweeps_expired_events() {
+    use mcp_core::stdio::JsonRpcMessage;
+    use mcp_core::types::NotificationMessage;
+    use mcp_server::{EventBufferConfig, SseBroadcaster};
+
+    let config = EventBufferConfig {
+        max_age: Duration::from_millis(50),
+        cleanup_interval: Duration::from_millis(10),
+        ..Default::default()
+    };
+    let broadcaster = SseBroadcaster::with_buffer_config("sweep".to_string(), 10, config);
+    let message = |seq: u64| {
+        let params = serde_json::json!({ "seq": seq });
+        let notification = NotificationMessage::new("notifications/message", Some(params));
+        JsonRpcMessage::Notification(notification)
+    };
+
+    let first = broadcaster.send_message(message(1)).unwrap();
+    let buffered = broadcaster.buffer_memory_bytes();
+
+    // The background sweep drops the expired event without an insertion
+    tokio::time::sleep(Duration::from_millis(150)).await;
+    assert!(broadcaster.buffer_memory_bytes() < buffered);
+
+    // A reconnect after the eviction replays what survived, not an error
+    let second = broadcaster.send_message(message(2)).unwrap();
+    let replay = broadcaster.replay_after(&first).unwrap();
+    assert_eq!(replay.len(), 1);
+    assert_eq!(replay[0].id, second);
+}
+
 #[tokio::test]
 async fn test_cors_headers() {
     let state = create_test_state();
@@ -398,3 +464,594 @@ async fn test_cors_headers() {
     assert!(response.headers().contains_key("access-control-allow-origin"));
     assert!(response.headers().contains_key("access-control-allow-methods"));
 }
+
+/// Send a CORS preflight for a POST from `origin`.
+async fn preflight(app: &axum::Router, origin: &str) -> axum::response::Response {
+    let request = Request::builder()
+        .method("OPTIONS")
+        .uri("/mcp")
+        .header(header::ORIGIN, origin)
+        .header(header::ACCESS_CONTROL_REQUEST_METHOD, "POST")
+        .header(header::ACCESS_CONTROL_REQUEST_HEADERS, "content-type,mcp-session-id")
+        .body(Body::empty())
+        .unwrap();
+    app.clone().oneshot(request).await.unwrap()
+}
+
+#[tokio::test]
+async fn test_cors_preflight_permissive() {
+    let app = create_router(create_test_state());
+    let response = preflight(&app, "http://example.com").await;
+    let headers = response.headers();
+
+    assert_eq!(headers[header::ACCESS_CONTROL_ALLOW_ORIGIN], "*");
+    assert_eq!(headers[header::ACCESS_CONTROL_ALLOW_METHODS], "GET,POST,DELETE");
+    assert_eq!(
+        headers[header::ACCESS_CONTROL_ALLOW_HEADERS],
+        "content-type,accept,mcp-session-id,last-event-id,authorization"
+    );
+    assert!(!headers.contains_key(header::ACCESS_CONTROL_ALLOW_CREDENTIALS));
+    assert!(!headers.contains_key(header::ACCESS_CONTROL_MAX_AGE));
+
+    // Mcp-Session-Id is exposed on actual responses
+    let request = Request::builder()
+        .method("POST")
+        .uri("/mcp")
+        .header(header::ORIGIN, "http://example.com")
+        .header(header::CONTENT_TYPE, "application/json")
+        .body(Body::from(
+            serde_json::json!({ "jsonrpc": "2.0", "id": 1, "method": "ping" }).to_string(),
+        ))
+        .unwrap();
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(
+        response.headers()[header::ACCESS_CONTROL_EXPOSE_HEADERS],
+        "mcp-session-id"
+    );
+}
+
+#[tokio::test]
+async fn test_cors_preflight_restricted() {
+    use axum::http::Method;
+
+    let config = AxumHandlerConfig {
+        cors: CorsPolicy {
+            allow_methods: vec![Method::POST],
+            allow_headers: vec!["content-type".to_string(), "mcp-session-id".to_string()],
+            allow_credentials: true,
+            max_age: Some(Duration::from_secs(600)),
+            ..CorsPolicy::new(["https://app.example.com"])
+        },
+        ..Default::default()
+    };
+    let app = create_router(Arc::new(AxumHandlerState::new(create_test_server(), config)));
+
+    let response = preflight(&app, "https://app.example.com").await;
+    let headers = response.headers();
+    assert_eq!(
+        headers[header::ACCESS_CONTROL_ALLOW_ORIGIN],
+        "https://app.example.com"
+    );
+    assert_eq!(headers[header::ACCESS_CONTROL_ALLOW_METHODS], "POST");
+    // The transport's own headers are always allowed
+    assert_eq!(
+        headers[header::ACCESS_CONTROL_ALLOW_HEADERS],
+        "content-type,mcp-session-id,last-event-id,authorization"
+    );
+    assert_eq!(headers[header::ACCESS_CONTROL_ALLOW_CREDENTIALS], "true");
+    assert_eq!(headers[header::ACCESS_CONTROL_MAX_AGE], "600");
+
+    // Other origins get no CORS grant
+    let response = preflight(&app, "https://evil.example").await;
+    assert!(!response
+        .headers()
+        .contains_key(header::ACCESS_CONTROL_ALLOW_ORIGIN));
+}
+
+#[tokio::test]
+async fn test_cors_credentials_mirror_origin() {
+    let config = AxumHandlerConfig {
+        cors: CorsPolicy {
+            allow_credentials: true,
+            ..CorsPolicy::permissive()
+        },
+        ..Default::default()
+    };
+    let app = create_router(Arc::new(AxumHandlerState::new(create_test_server(), config)));
+
+    // A wildcard is not valid with credentials, so the origin is mirrored
+    let response = preflight(&app, "https://any.example").await;
+    assert_eq!(
+        response.headers()[header::ACCESS_CONTROL_ALLOW_ORIGIN],
+        "https://any.example"
+    );
+    assert_eq!(
+        response.headers()[header::ACCESS_CONTROL_ALLOW_CREDENTIALS],
+        "true"
+    );
+}
+
+#[tokio::test]
+async fn test_cors_default_grants_no_origin() {
+    let state = Arc::new(AxumHandlerState::new(
+        create_test_server(),
+        AxumHandlerConfig::default(),
+    ));
+    let app = create_router(state.clone());
+
+    let response = preflight(&app, "http://example.com").await;
+    assert!(!response
+        .headers()
+        .contains_key(header::ACCESS_CONTROL_ALLOW_ORIGIN));
+    // Answered by the CORS layer without reaching the transport
+    assert_eq!(state.session_manager().session_count().await, 0);
+}
+
+/// Open an SSE stream from `origin`, returning the response headers.
+async fn sse_get(app: &axum::Router, origin: &str) -> axum::http::HeaderMap {
+    let request = Request::builder()
+        .method("GET")
+        .uri("/mcp")
+        .header(header::ORIGIN, origin)
+        .header(header::ACCEPT, "text/event-stream")
+        .body(Body::empty())
+        .unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    response.headers().clone()
+}
+
+#[tokio::test]
+async fn test_cors_origin_allowlist() {
+    let config = AxumHandlerConfig {
+        cors: CorsPolicy::new(["https://app.example.com", "https://*.example.dev"]),
+        ..Default::default()
+    };
+    let state = Arc::new(AxumHandlerState::new(create_test_server(), config));
+    let app = create_router(state.clone());
+
+    for origin in ["https://app.example.com", "https://staging.example.dev"] {
+        let response = preflight(&app, origin).await;
+        assert_eq!(response.headers()[header::ACCESS_CONTROL_ALLOW_ORIGIN], origin);
+    }
+    for origin in ["https://evil.example", "https://example.dev.evil.example", "http://app.example.com"] {
+        let response = preflight(&app, origin).await;
+        assert!(
+            !response.headers().contains_key(header::ACCESS_CONTROL_ALLOW_ORIGIN),
+            "{origin}"
+        );
+    }
+    assert_eq!(state.session_manager().session_count().await, 0);
+
+    // The SSE GET route carries the same grants
+    let headers = sse_get(&app, "https://staging.example.dev").await;
+    assert_eq!(
+        headers[header::ACCESS_CONTROL_ALLOW_ORIGIN],
+        "https://staging.example.dev"
+    );
+    assert_eq!(headers[header::ACCESS_CONTROL_EXPOSE_HEADERS], "mcp-session-id");
+    let headers = sse_get(&app, "https://evil.example").await;
+    assert!(!headers.contains_key(header::ACCESS_CONTROL_ALLOW_ORIGIN));
+}
+
+#[tokio::test]
+async fn test_allowed_origins() {
+    let config = AxumHandlerConfig {
+        allowed_origins: vec!["https://app.example.com".to_string()],
+        ..Default::default()
+    };
+    let app = create_router(Arc::new(AxumHandlerState::new(create_test_server(), config)));
+
+    let request = |origin: Option<&str>| {
+        let mut builder = Request::builder()
+            .method("POST")
+            .uri("/mcp")
+            .header(header::CONTENT_TYPE, "application/json")
+            .header(header::ACCEPT, "application/json, text/event-stream");
+        if let Some(origin) = origin {
+            builder = builder.header(header::ORIGIN, origin);
+        }
+        let body = serde_json::json!({ "jsonrpc": "2.0", "id": 1, "method": "ping" });
+        builder.body(Body::from(body.to_string())).unwrap()
+    };
+
+    // Disallowed origin is rejected before reaching the handler
+    let response = app.clone().oneshot(request(Some("https://evil.example"))).await.unwrap();
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    assert!(!response.headers().contains_key("access-control-allow-origin"));
+
+    // Allowed origin passes and is reflected by CORS
+    let response = app.clone().oneshot(request(Some("https://app.example.com"))).await.unwrap();
+    assert_ne!(response.status(), StatusCode::FORBIDDEN);
+    assert_eq!(
+        response.headers().get("access-control-allow-origin").unwrap(),
+        "https://app.example.com"
+    );
+
+    // Non-browser clients without Origin are unaffected
+    let response = app.oneshot(request(None)).await.unwrap();
+    assert_ne!(response.status(), StatusCode::FORBIDDEN);
+}
+
+#[tokio::test]
+async fn test_task_status_notification_on_sse_stream() {
+    use futures::StreamExt;
+    use mcp_core::protocol::{ProtocolOptions, RequestContext};
+    use mcp_core::types::{CallToolResult, ContentBlock, TextContent, Tool};
+    use mcp_server::InMemoryTaskStore;
+
+    let server_info = Implementation {
+        base: BaseMetadata {
+            name: "task-server".to_string(),
+            title: None,
+        },
+        icons: Icons::default(),
+        version: "0.1.0".to_string(),
+        website_url: None,
+        description: None,
+    };
+    let options = ServerOptions {
+        protocol_options: Some(ProtocolOptions {
+            task_store: Some(Arc::new(InMemoryTaskStore::default())),
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+    let mut server = McpServer::new(server_info, options);
+    server
+        .register_tool(
+            Tool {
+                base: BaseMetadata {
+                    name: "echo".to_string(),
+                    title: None,
+                },
+                icons: Icons::default(),
+                description: None,
+                input_schema: serde_json::json!({ "type": "object" }),
+                output_schema: None,
+                annotations: None,
+                execution: None,
+                meta: None,
+            },
+            |_args, _ctx: RequestContext| async move {
+                Ok(CallToolResult {
+                    content: vec![ContentBlock::Text(TextContent::new("done"))],
+                    structured_content: None,
+                    is_error: None,
+                    meta: None,
+                })
+            },
+        )
+        .expect("register tool");
+    let state = Arc::new(AxumHandlerState::new(
+        Arc::new(server),
+        AxumHandlerConfig::default(),
+    ));
+
+    // Open an SSE stream for a fresh session.
+    let request = Request::builder()
+        .method("GET")
+        .uri("/mcp")
+        .header(header::ACCEPT, "text/event-stream")
+        .body(Body::empty())
+        .unwrap();
+    let response = create_router(state.clone()).oneshot(request).await.unwrap();
+    let session_id = response
+        .headers()
+        .get("mcp-session-id")
+        .unwrap()
+        .to_str()
+        .unwrap()
+        .to_string();
+    let mut stream = response.into_body().into_data_stream();
+    // Reading the first event guarantees the stream has subscribed.
+    stream.next().await.unwrap().unwrap();
+
+    // Run a tool call as a task on the same session.
+    let call_request = serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": "tools/call",
+        "params": { "name": "echo", "arguments": {}, "task": { "ttl": 1000 } }
+    });
+    let request = Request::builder()
+        .method("POST")
+        .uri("/mcp")
+        .header(header::CONTENT_TYPE, "application/json")
+        .header("mcp-session-id", &session_id)
+        .body(Body::from(call_request.to_string()))
+        .unwrap();
+    let response = create_router(state).oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let received = tokio::time::timeout(Duration::from_secs(5), async {
+        let mut received = String::new();
+        while let Some(Ok(chunk)) = stream.next().await {
+            received.push_str(&String::from_utf8_lossy(&chunk));
+            if received.contains("notifications/tasks/status") {
+                break;
+            }
+        }
+        received
+    })
+    .await
+    .expect("status notification on the SSE stream");
+
+    assert!(received.contains("notifications/tasks/status"));
+    assert!(received.contains("\"status\":\"completed\""));
+    assert!(received.contains("io.modelcontextprotocol/related-task"));
+}
+
+/// Open an SSE stream, optionally resuming a session from a Last-Event-ID.
+async fn open_sse(
+    state: &Arc<AxumHandlerState>,
+    session_id: Option<&str>,
+    last_event_id: Option<&str>,
+) -> (String, axum::body::BodyDataStream) {
+    let mut request = Request::builder()
+        .method("GET")
+        .uri("/mcp")
+        .header(header::ACCEPT, "text/event-stream");
+    if let Some(session_id) = session_id {
+        request = request.header("mcp-session-id", session_id);
+    }
+    if let Some(last_event_id) = last_event_id {
+        request = request.header("last-event-id", last_event_id);
+    }
+    let response = create_router(state.clone())
+        .oneshot(request.body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let session_id = response
+        .headers()
+        .get("mcp-session-id")
+        .unwrap()
+        .to_str()
+        .unwrap()
+        .to_string();
+    (session_id, response.into_body().into_data_stream())
+}
+
+/// Messages and comments read from an SSE stream.
+#[derive(Default)]
+struct SseLog {
+    pending: String,
+    events: usize,
+    /// `(event id, params.seq)` of each message event.
+    messages: Vec<(String, u64)>,
+    comments: Vec<String>,
+}
+
+impl SseLog {
+    /// Read from the stream until `done` holds for the log.
+    async fn read_until(
+        &mut self,
+        stream: &mut axum::body::BodyDataStream,
+        done: impl Fn(&SseLog) -> bool,
+    ) {
+        use futures::StreamExt;
+
+        tokio::time::timeout(Duration::from_secs(5), async {
+            while !done(self) {
+                let chunk = stream.next().await.expect("stream ended").unwrap();
+                self.pending.push_str(&String::from_utf8_lossy(&chunk));
+                while let Some(end) = self.pending.find("\n\n") {
+                    let event: String = self.pending.drain(..end + 2).collect();
+                    self.record(&event);
+                }
+            }
+        })
+        .await
+        .expect("expected SSE events");
+    }
+
+    fn record(&mut self, event: &str) {
+        self.events += 1;
+        let mut id = None;
+        let mut data = None;
+        for line in event.lines() {
+            if let Some(comment) = line.strip_prefix(':') {
+                self.comments.push(comment.trim().to_string());
+            } else if let Some(value) = line.strip_prefix("id:") {
+                id = Some(value.trim().to_string());
+            } else if let Some(value) = line.strip_prefix("data:") {
+                data = serde_json::from_str::<serde_json::Value>(value.trim()).ok();
+            }
+        }
+        if let (Some(id), Some(data)) = (id, data) {
+            let seq = data["params"]["seq"].as_u64().unwrap();
+            self.messages.push((id, seq));
+        }
+    }
+
+    fn last_id(&self) -> &str {
+        &self.messages.last().unwrap().0
+    }
+
+    fn has_seq(&self, seq: u64) -> bool {
+        self.messages.iter().any(|(_, s)| *s == seq)
+    }
+}
+
+async fn send_seq(state: &AxumHandlerState, session_id: &str, seq: u64) {
+    use mcp_core::stdio::JsonRpcMessage;
+    use mcp_core::types::NotificationMessage;
+
+    let notification =
+        NotificationMessage::new("notifications/message", Some(serde_json::json!({ "seq": seq })));
+    state
+        .broadcast_to_session(session_id, JsonRpcMessage::Notification(notification))
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn test_last_event_id_replay_after_reconnect() {
+    let state = create_test_state();
+    let (session_id, mut stream) = open_sse(&state, None, None).await;
+    let mut first = SseLog::default();
+    // Reading the first event guarantees the stream has subscribed.
+    first.read_until(&mut stream, |log| log.events > 0).await;
+
+    // Disconnect mid-stream, after the second of three messages.
+    for seq in 1..=3 {
+        send_seq(&state, &session_id, seq).await;
+    }
+    first.read_until(&mut stream, |log| log.has_seq(2)).await;
+    drop(stream);
+
+    // Messages sent while disconnected are buffered.
+    for seq in 4..=6 {
+        send_seq(&state, &session_id, seq).await;
+    }
+
+    let (resumed_id, mut stream) =
+        open_sse(&state, Some(&session_id), Some(first.last_id())).await;
+    assert_eq!(resumed_id, session_id);
+    let mut second = SseLog::default();
+    second.read_until(&mut stream, |log| log.events > 0).await;
+    for seq in 7..=8 {
+        send_seq(&state, &session_id, seq).await;
+    }
+    second.read_until(&mut stream, |log| log.has_seq(8)).await;
+
+    // Every message arrives exactly once, in order, with increasing IDs.
+    let delivered: Vec<&(String, u64)> = first.messages.iter().chain(&second.messages).collect();
+    let seqs: Vec<u64> = delivered.iter().map(|(_, seq)| *seq).collect();
+    assert_eq!(seqs, (1..=8).collect::<Vec<_>>());
+    let ids: Vec<u64> = delivered
+        .iter()
+        .map(|(id, _)| id.rsplit_once('-').unwrap().1.parse().unwrap())
+        .collect();
+    assert!(ids.windows(2).all(|pair| pair[0] < pair[1]), "{ids:?}");
+    assert!(second.comments.iter().all(|c| !c.starts_with("gap")));
+}
+
+#[tokio::test]
+async fn test_last_event_id_evicted_reports_gap() {
+    use mcp_server::EventBufferConfig;
+
+    let config = AxumHandlerConfig {
+        event_buffer_config: EventBufferConfig {
+            max_events: 2,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let state = Arc::new(AxumHandlerState::new(create_test_server(), config));
+    let (session_id, mut stream) = open_sse(&state, None, None).await;
+    let mut first = SseLog::default();
+    first.read_until(&mut stream, |log| log.events > 0).await;
+    send_seq(&state, &session_id, 1).await;
+    first.read_until(&mut stream, |log| log.has_seq(1)).await;
+    drop(stream);
+
+    // Messages 2..4 push message 2 out of the two-event buffer.
+    for seq in 2..=4 {
+        send_seq(&state, &session_id, seq).await;
+    }
+
+    let (_, mut stream) = open_sse(&state, Some(&session_id), Some(first.last_id())).await;
+    let mut second = SseLog::default();
+    second.read_until(&mut stream, |log| log.events > 0).await;
+    send_seq(&state, &session_id, 5).await;
+    second.read_until(&mut stream, |log| log.has_seq(5)).await;
+
+    // A fresh stream: the gap is reported and nothing stale is replayed.
+    assert!(
+        second.comments.iter().any(|c| c.starts_with("gap:")),
+        "{:?}",
+        second.comments
+    );
+    let seqs: Vec<u64> = second.messages.iter().map(|(_, seq)| *seq).collect();
+    assert_eq!(seqs, vec![5]);
+}
+
+#[tokio::test]
+async fn test_lru_session_eviction() {
+    use futures::StreamExt;
+    use mcp_server::{SessionConfig, SessionEvictionPolicy, SessionStats};
+
+    let config = AxumHandlerConfig {
+        session_config: SessionConfig {
+            max_sessions: 2,
+            eviction_policy: SessionEvictionPolicy::EvictLru,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let state = Arc::new(AxumHandlerState::new(create_test_server(), config));
+
+    let open_stream = || {
+        let state = state.clone();
+        async move {
+            let request = Request::builder()
+                .method("GET")
+                .uri("/mcp")
+                .header(header::ACCEPT, "text/event-stream")
+                .body(Body::empty())
+                .unwrap();
+            let response = create_router(state).oneshot(request).await.unwrap();
+            let session_id = response
+                .headers()
+                .get("mcp-session-id")
+                .unwrap()
+                .to_str()
+                .unwrap()
+                .to_string();
+            (session_id, response.into_body().into_data_stream())
+        }
+    };
+
+    // Fill the manager; the first session is the least recently active.
+    let (oldest, mut oldest_stream) = open_stream().await;
+    oldest_stream.next().await.unwrap().unwrap();
+    tokio::time::sleep(Duration::from_millis(5)).await;
+    let (newer, _newer_stream) = open_stream().await;
+    tokio::time::sleep(Duration::from_millis(5)).await;
+
+    // One more session evicts the oldest and ends its SSE stream.
+    let (newest, _newest_stream) = open_stream().await;
+    tokio::time::timeout(Duration::from_secs(5), async {
+        while oldest_stream.next().await.is_some() {}
+    })
+    .await
+    .expect("evicted session's SSE stream ends");
+
+    let sessions = state.session_manager().session_ids().await;
+    assert!(!sessions.contains(&oldest));
+    assert!(sessions.contains(&newer));
+    assert!(sessions.contains(&newest));
+    assert_eq!(
+        state.session_manager().stats().await,
+        SessionStats {
+            active: 2,
+            evictions: 1,
+            expirations: 0,
+            peak: 2,
+        }
+    );
+
+    // The evicted session is gone rather than silently recreated.
+    let ping = serde_json::json!({ "jsonrpc": "2.0", "id": 1, "method": "ping" });
+    let request = Request::builder()
+        .method("POST")
+        .uri("/mcp")
+        .header(header::CONTENT_TYPE, "application/json")
+        .header("mcp-session-id", &oldest)
+        .body(Body::from(ping.to_string()))
+        .unwrap();
+    let response = create_router(state.clone()).oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    let request = Request::builder()
+        .method("DELETE")
+        .uri("/mcp")
+        .header("mcp-session-id", &oldest)
+        .body(Body::empty())
+        .unwrap();
+    let response = create_router(state.clone()).oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(state.session_manager().session_count().await, 2);
+}
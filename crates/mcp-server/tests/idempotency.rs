@@ -0,0 +1,153 @@
+//! Retransmitted `tools/call` requests with `ProtocolOptions::idempotency_window`
+//! and `ServerOptions::dedup_window`.
+
+mod support;
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
+use futures::executor::block_on;
+use serde_json::json;
+
+use mcp_core::protocol::{ProtocolOptions, RequestContext};
+use mcp_core::types::{
+    BaseMetadata, CallToolResult, ContentBlock, Icons, RequestMessage, TextContent, Tool,
+    ToolAnnotations,
+};
+use mcp_server::{McpServer, ServerOptions};
+
+fn tool(name: &str, idempotent: Option<bool>) -> Tool {
+    Tool {
+        base: BaseMetadata {
+            name: name.to_string(),
+            title: None,
+        },
+        icons: Icons { icons: None },
+        description: None,
+        input_schema: json!({ "type": "object" }),
+        output_schema: None,
+        annotations: idempotent.map(|idempotent| ToolAnnotations {
+            idempotent_hint: Some(idempotent),
+            ..Default::default()
+        }),
+        execution: None,
+        meta: None,
+    }
+}
+
+/// Server with a `get` tool and a `create` tool annotated as not idempotent,
+/// both counting their calls.
+fn server(window: Option<Duration>) -> (McpServer, Arc<AtomicUsize>) {
+    server_with(ServerOptions {
+        protocol_options: Some(ProtocolOptions {
+            idempotency_window: window,
+            ..Default::default()
+        }),
+        ..Default::default()
+    })
+}
+
+fn server_with(options: ServerOptions) -> (McpServer, Arc<AtomicUsize>) {
+    let mut server = McpServer::new(support::implementation("idempotency-server"), options);
+    let calls = Arc::new(AtomicUsize::new(0));
+
+    for (name, idempotent) in [("get", None), ("create", Some(false))] {
+        let calls = Arc::clone(&calls);
+        server
+            .register_tool(tool(name, idempotent), move |_args, _ctx: RequestContext| {
+                let call = calls.fetch_add(1, Ordering::SeqCst) + 1;
+                async move {
+                    Ok(CallToolResult {
+                        content: vec![ContentBlock::Text(TextContent::new(format!("call {call}")))],
+                        structured_content: None,
+                        is_error: None,
+                        meta: None,
+                    })
+                }
+            })
+            .expect("register tool");
+    }
+    (server, calls)
+}
+
+fn call(server: &McpServer, id: &str, tool: &str) -> String {
+    let request = RequestMessage::new(id, "tools/call", json!({ "name": tool, "arguments": {} }));
+    let response = block_on(server.server().handle_request(request, None)).expect("response");
+    response.result.expect("result")["content"][0]["text"]
+        .as_str()
+        .unwrap()
+        .to_string()
+}
+
+#[test]
+fn retransmitted_call_replays_the_response() {
+    let (server, calls) = server(Some(Duration::from_secs(60)));
+
+    assert_eq!(call(&server, "1", "get"), "call 1");
+    assert_eq!(call(&server, "1", "get"), "call 1");
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+    // A new id is a new request
+    assert_eq!(call(&server, "2", "get"), "call 2");
+}
+
+#[test]
+fn non_idempotent_tools_opt_out() {
+    let (server, calls) = server(Some(Duration::from_secs(60)));
+
+    assert_eq!(call(&server, "1", "create"), "call 1");
+    assert_eq!(call(&server, "1", "create"), "call 2");
+    assert_eq!(calls.load(Ordering::SeqCst), 2);
+}
+
+#[test]
+fn replay_is_off_by_default() {
+    let (server, calls) = server(None);
+
+    call(&server, "1", "get");
+    call(&server, "1", "get");
+    assert_eq!(calls.load(Ordering::SeqCst), 2);
+}
+
+#[test]
+fn dedup_window_runs_the_handler_once_per_id() {
+    let (server, calls) = server_with(ServerOptions {
+        dedup_window: Some(Duration::from_secs(60)),
+        ..Default::default()
+    });
+
+    assert_eq!(call(&server, "1", "get"), "call 1");
+    assert_eq!(call(&server, "1", "get"), "call 1");
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+fn dedup_entries_expire_after_the_window() {
+    let (server, calls) = server_with(ServerOptions {
+        dedup_window: Some(Duration::from_millis(50)),
+        ..Default::default()
+    });
+
+    call(&server, "1", "get");
+    std::thread::sleep(Duration::from_millis(100));
+    assert_eq!(call(&server, "1", "get"), "call 2");
+    assert_eq!(calls.load(Ordering::SeqCst), 2);
+}
+
+#[test]
+fn dedup_max_entries_bounds_the_cache() {
+    let (server, calls) = server_with(ServerOptions {
+        dedup_window: Some(Duration::from_secs(60)),
+        dedup_max_entries: 2,
+        ..Default::default()
+    });
+
+    for id in ["1", "2", "3"] {
+        call(&server, id, "get");
+    }
+    // "1" was the least recently used entry and has been evicted
+    assert_eq!(call(&server, "3", "get"), "call 3");
+    assert_eq!(call(&server, "1", "get"), "call 4");
+    assert_eq!(calls.load(Ordering::SeqCst), 4);
+}
@@ -28,3 +28,39 @@ fn initialize_returns_version_and_sets_client_info() {
     assert!(server.get_client_capabilities().is_some());
     assert!(server.get_client_info().is_some());
 }
+
+#[test]
+fn initialize_reflects_updated_server_info_and_instructions() {
+    let server = Server::new(
+        support::implementation("test-server"),
+        ServerOptions {
+            instructions: Some("original".to_string()),
+            ..Default::default()
+        },
+    );
+
+    let mut updated = support::implementation("test-server");
+    updated.description = Some("reloaded description".to_string());
+    server.set_server_info(updated);
+    server.set_instructions(Some("reloaded instructions".to_string()));
+
+    let params = InitializeRequestParams {
+        base: RequestParams { meta: None },
+        protocol_version: LATEST_PROTOCOL_VERSION.to_string(),
+        capabilities: ClientCapabilities::default(),
+        client_info: support::implementation("test-client"),
+    };
+    let request = RequestMessage::new("1", "initialize", serde_json::to_value(params).unwrap());
+    let response = block_on(server.handle_request(request, None)).expect("initialize response");
+    let result: InitializeResult = serde_json::from_value(response.result.unwrap()).unwrap();
+
+    assert_eq!(
+        result.server_info.description.as_deref(),
+        Some("reloaded description")
+    );
+    assert_eq!(result.instructions.as_deref(), Some("reloaded instructions"));
+    assert_eq!(
+        server.server_info().description.as_deref(),
+        Some("reloaded description")
+    );
+}
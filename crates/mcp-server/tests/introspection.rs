@@ -0,0 +1,248 @@
+//! Token introspection (RFC 7662): the `/introspect` route of
+//! `create_oauth_router` and `IntrospectionTokenVerifier` calling it.
+
+#![cfg(feature = "axum")]
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use async_trait::async_trait;
+use axum::body::Body;
+use axum::http::{Request, StatusCode, header};
+use base64::{Engine, engine::general_purpose::STANDARD};
+use serde_json::{Value, json};
+use tower::util::ServiceExt;
+
+use mcp_core::auth::{
+    AuthInfo, AuthorizationParams, OAuthClientInformation, OAuthClientInformationFull,
+    OAuthClientMetadata, OAuthTokenIntrospectionResponse, OAuthTokenRevocationRequest, OAuthTokens,
+};
+use mcp_server::auth::{
+    AuthorizeResponse, InMemoryClientStore, IntrospectionTokenVerifier, OAuthProviderError,
+    OAuthRegisteredClientsStore, OAuthRouterOptions, OAuthServerProvider, OAuthTokenVerifier,
+    create_oauth_router,
+};
+
+const CLIENT_ID: &str = "resource-server";
+const CLIENT_SECRET: &str = "rs-secret";
+
+/// Provider holding issued access tokens in memory.
+struct MemoryProvider {
+    clients: InMemoryClientStore,
+    tokens: Mutex<HashMap<String, AuthInfo>>,
+}
+
+impl MemoryProvider {
+    fn new() -> Self {
+        let clients = InMemoryClientStore::new();
+        clients.add_client(OAuthClientInformationFull {
+            client_info: OAuthClientInformation {
+                client_id: CLIENT_ID.to_string(),
+                client_secret: Some(CLIENT_SECRET.to_string()),
+                client_id_issued_at: None,
+                client_secret_expires_at: None,
+            },
+            metadata: OAuthClientMetadata::default(),
+            token_endpoint_auth_method: Some("client_secret_basic".to_string()),
+        });
+
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs();
+        let mut active = AuthInfo::new("active")
+            .with_client_id("app")
+            .with_scopes(vec!["mcp:read".to_string(), "mcp:write".to_string()])
+            .with_expires_at(now + 3600);
+        active.extra = Some(json!({ "sub": "alice" }));
+        let expired = AuthInfo::new("expired").with_client_id("app").with_expires_at(now - 60);
+
+        let tokens = [active, expired].into_iter().map(|info| (info.token.clone(), info)).collect();
+        Self {
+            clients,
+            tokens: Mutex::new(tokens),
+        }
+    }
+}
+
+#[async_trait]
+impl OAuthServerProvider for MemoryProvider {
+    fn clients_store(&self) -> &dyn OAuthRegisteredClientsStore {
+        &self.clients
+    }
+
+    async fn authorize(
+        &self,
+        _client: &OAuthClientInformationFull,
+        _params: AuthorizationParams,
+    ) -> Result<AuthorizeResponse, OAuthProviderError> {
+        Err(OAuthProviderError::AccessDenied("not used".to_string()))
+    }
+
+    async fn challenge_for_authorization_code(
+        &self,
+        _client: &OAuthClientInformationFull,
+        _authorization_code: &str,
+    ) -> Result<String, OAuthProviderError> {
+        Err(OAuthProviderError::InvalidGrant("not used".to_string()))
+    }
+
+    async fn exchange_authorization_code(
+        &self,
+        _client: &OAuthClientInformationFull,
+        _authorization_code: &str,
+        _code_verifier: Option<&str>,
+        _redirect_uri: Option<&str>,
+        _resource: Option<&str>,
+    ) -> Result<OAuthTokens, OAuthProviderError> {
+        Err(OAuthProviderError::InvalidGrant("not used".to_string()))
+    }
+
+    async fn exchange_refresh_token(
+        &self,
+        _client: &OAuthClientInformationFull,
+        _refresh_token: &str,
+        _scopes: Option<&[String]>,
+        _resource: Option<&str>,
+    ) -> Result<OAuthTokens, OAuthProviderError> {
+        Err(OAuthProviderError::InvalidGrant("not used".to_string()))
+    }
+
+    async fn verify_access_token(&self, token: &str) -> Result<AuthInfo, OAuthProviderError> {
+        self.tokens
+            .lock()
+            .unwrap()
+            .get(token)
+            .cloned()
+            .ok_or_else(|| OAuthProviderError::InvalidToken("unknown token".to_string()))
+    }
+
+    async fn revoke_token(
+        &self,
+        _client: &OAuthClientInformationFull,
+        request: OAuthTokenRevocationRequest,
+    ) -> Result<(), OAuthProviderError> {
+        self.tokens.lock().unwrap().remove(&request.token);
+        Ok(())
+    }
+
+    async fn introspect_token(
+        &self,
+        _client: &OAuthClientInformationFull,
+        token: &str,
+        _token_type_hint: Option<&str>,
+    ) -> Result<OAuthTokenIntrospectionResponse, OAuthProviderError> {
+        Ok(match self.tokens.lock().unwrap().get(token) {
+            Some(info) => OAuthTokenIntrospectionResponse::from_auth_info(info),
+            None => OAuthTokenIntrospectionResponse::inactive(),
+        })
+    }
+}
+
+fn router() -> axum::Router {
+    create_oauth_router(
+        Arc::new(MemoryProvider::new()),
+        OAuthRouterOptions::new("https://auth.example.com"),
+    )
+}
+
+fn basic(id: &str, secret: &str) -> String {
+    format!("Basic {}", STANDARD.encode(format!("{id}:{secret}")))
+}
+
+async fn introspect(router: &axum::Router, token: &str, authorization: Option<String>) -> (StatusCode, Value) {
+    let mut request = Request::post("/introspect")
+        .header(header::CONTENT_TYPE, "application/x-www-form-urlencoded");
+    if let Some(authorization) = authorization {
+        request = request.header(header::AUTHORIZATION, authorization);
+    }
+    let request = request.body(Body::from(format!("token={token}"))).unwrap();
+
+    let response = router.clone().oneshot(request).await.unwrap();
+    let status = response.status();
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (status, serde_json::from_slice(&body).unwrap())
+}
+
+#[tokio::test]
+async fn active_token_is_described() {
+    let (status, body) = introspect(&router(), "active", Some(basic(CLIENT_ID, CLIENT_SECRET))).await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["active"], true);
+    assert_eq!(body["scope"], "mcp:read mcp:write");
+    assert_eq!(body["client_id"], "app");
+    assert_eq!(body["sub"], "alice");
+    assert!(body["exp"].is_u64());
+}
+
+#[tokio::test]
+async fn expired_and_unknown_tokens_are_inactive() {
+    let router = router();
+    for token in ["expired", "unknown"] {
+        let (status, body) = introspect(&router, token, Some(basic(CLIENT_ID, CLIENT_SECRET))).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body, json!({ "active": false }), "{token}");
+    }
+}
+
+#[tokio::test]
+async fn revoked_token_is_inactive() {
+    let router = router();
+    let revoke = Request::post("/revoke")
+        .header(header::CONTENT_TYPE, "application/x-www-form-urlencoded")
+        .body(Body::from(format!(
+            "token=active&client_id={CLIENT_ID}&client_secret={CLIENT_SECRET}"
+        )))
+        .unwrap();
+    let response = router.clone().oneshot(revoke).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let (_, body) = introspect(&router, "active", Some(basic(CLIENT_ID, CLIENT_SECRET))).await;
+    assert_eq!(body, json!({ "active": false }));
+}
+
+#[tokio::test]
+async fn callers_must_authenticate() {
+    let router = router();
+
+    let (status, body) = introspect(&router, "active", None).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    assert_eq!(body["error"], "invalid_client");
+
+    let (status, _) = introspect(&router, "active", Some(basic(CLIENT_ID, "wrong"))).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn metadata_advertises_introspection_endpoint() {
+    let request = Request::get("/.well-known/oauth-authorization-server")
+        .body(Body::empty())
+        .unwrap();
+    let response = router().oneshot(request).await.unwrap();
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let metadata: Value = serde_json::from_slice(&body).unwrap();
+
+    assert_eq!(metadata["introspection_endpoint"], "https://auth.example.com/introspect");
+}
+
+#[tokio::test]
+async fn verifier_calls_remote_endpoint() {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move { axum::serve(listener, router()).await });
+
+    let endpoint = format!("http://{addr}/introspect");
+    let verifier = IntrospectionTokenVerifier::new(&endpoint, CLIENT_ID).with_client_secret(CLIENT_SECRET);
+
+    let info = verifier.verify_access_token("active").await.unwrap();
+    assert_eq!(info.token, "active");
+    assert_eq!(info.client_id.as_deref(), Some("app"));
+    assert_eq!(info.scopes, vec!["mcp:read", "mcp:write"]);
+
+    let err = verifier.verify_access_token("expired").await.unwrap_err();
+    assert!(matches!(err, OAuthProviderError::InvalidToken(_)));
+
+    // Wrong credentials are a server-side failure, not a rejected token
+    let unauthorized = IntrospectionTokenVerifier::new(&endpoint, CLIENT_ID).with_client_secret("wrong");
+    let err = unauthorized.verify_access_token("active").await.unwrap_err();
+    assert!(matches!(err, OAuthProviderError::Server(_)));
+}
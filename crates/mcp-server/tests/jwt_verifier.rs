@@ -0,0 +1,191 @@
+//! `JwtTokenVerifier`: signature, claim and key rotation checks against
+//! key sets served by an injected fetcher.
+
+#![cfg(feature = "jwt")]
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use async_trait::async_trait;
+use jsonwebtoken::{Algorithm, EncodingKey, Header};
+use serde_json::{Value, json};
+
+use mcp_server::auth::{JwksFetcher, JwtTokenVerifier, OAuthProviderError, OAuthTokenVerifier};
+
+const ISSUER: &str = "https://idp.example.com";
+const AUDIENCE: &str = "mcp-server";
+
+const KEY_A: &str = include_str!("fixtures/jwt_key_a.pem");
+const KEY_B: &str = include_str!("fixtures/jwt_key_b.pem");
+const MODULUS_A: &str = "rBrWUYSCSVXsNiF1-eGlFCeyPEiuOhcwP6QgtbwqS2K7AnKZKV9NQnwyQD-LrjMMepxAKyJiYeUlGKkOAcO2HzV0Iyc0IpzdNGHjLr_Oe43Ps1c9ZBcCFRs5ZpDVoK8Rc0NH11LWM91P6YJic-BbJiPfya9m-QK001LqX80Q27X6ANUO-MX5mqNNkPdo4gsBIwwnFujES-CtxAllvknu6sLWhix4Nz7QB2wMF_n3BX0sDJ5Xg-T03Sk-BT62ylbSmbs4ndW1huSwIIMoDgmTvACdExECQd-_N-BV_pq3wb4ctiyZOyhJ4r04zRdq8hkMoxlxyuYkSUITo3Ke0t2uKQ";
+const MODULUS_B: &str = "rYQ0gqTdC_RLFBpv-UTaDwi6YEetQCY3HRkReJCACLL66FKn8d8el1pedfjQfDyhPpl2A7C8ZlqIospPTFgTQ7xxYTwsGaGvXV1NezeVrtgIIC-Yf8x96RoiXC8VwyrcVTFBDY7homQBJEOoJe-Rb0JrfIwmxvZgdYmGXk03ep1aBeH-zHgjYvJQTHNxAssFqYXad3TG_2Ig649jNdwa56mc5S0v_cPauYE4AiBaRqjPP19heCw9TmuqoWAd-DBEtEzdzMzBGMB7AksZPuY_rutJkhtHfhxVyktYSMYCXcKuNUBChTV4bQOSmaaJh4NST8bORdiC_ypQJZbr7atj6Q";
+
+/// Serves a replaceable key set and counts fetches.
+struct StaticFetcher {
+    keys: Mutex<Value>,
+    calls: AtomicUsize,
+}
+
+impl StaticFetcher {
+    fn new(keys: Value) -> Arc<Self> {
+        Arc::new(Self {
+            keys: Mutex::new(keys),
+            calls: AtomicUsize::new(0),
+        })
+    }
+
+    fn calls(&self) -> usize {
+        self.calls.load(Ordering::SeqCst)
+    }
+}
+
+#[async_trait]
+impl JwksFetcher for StaticFetcher {
+    async fn fetch(&self, _jwks_uri: &str) -> Result<Value, OAuthProviderError> {
+        self.calls.fetch_add(1, Ordering::SeqCst);
+        Ok(self.keys.lock().unwrap().clone())
+    }
+}
+
+fn jwk(kid: &str, modulus: &str) -> Value {
+    json!({ "kty": "RSA", "kid": kid, "use": "sig", "alg": "RS256", "n": modulus, "e": "AQAB" })
+}
+
+fn now() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs()
+}
+
+fn claims() -> Value {
+    json!({
+        "iss": ISSUER,
+        "aud": AUDIENCE,
+        "sub": "alice",
+        "azp": "desktop-app",
+        "scope": "mcp:read mcp:write",
+        "iat": now(),
+        "exp": now() + 300,
+    })
+}
+
+fn mint(kid: &str, pem: &str, claims: &Value) -> String {
+    let mut header = Header::new(Algorithm::RS256);
+    header.kid = Some(kid.to_string());
+    jsonwebtoken::encode(&header, claims, &EncodingKey::from_rsa_pem(pem.as_bytes()).unwrap()).unwrap()
+}
+
+fn verifier(fetcher: Arc<StaticFetcher>) -> JwtTokenVerifier {
+    JwtTokenVerifier::new(ISSUER, AUDIENCE, "https://idp.example.com/jwks").with_fetcher(fetcher)
+}
+
+fn is_invalid_token(result: Result<mcp_core::auth::AuthInfo, OAuthProviderError>) -> bool {
+    matches!(result, Err(OAuthProviderError::InvalidToken(_)))
+}
+
+#[tokio::test]
+async fn valid_token_maps_claims() {
+    let verifier = verifier(StaticFetcher::new(json!({ "keys": [jwk("a", MODULUS_A)] })));
+    let token = mint("a", KEY_A, &claims());
+
+    let info = verifier.verify_access_token(&token).await.unwrap();
+    assert_eq!(info.scopes, ["mcp:read", "mcp:write"]);
+    assert_eq!(info.client_id.as_deref(), Some("desktop-app"));
+    assert_eq!(info.expires_at, claims()["exp"].as_u64());
+    assert_eq!(info.extra.unwrap()["sub"], "alice");
+
+    let mut scp = claims();
+    scp.as_object_mut().unwrap().remove("scope");
+    scp["scp"] = json!(["files:read"]);
+    let info = verifier.verify_access_token(&mint("a", KEY_A, &scp)).await.unwrap();
+    assert_eq!(info.scopes, ["files:read"]);
+}
+
+#[tokio::test]
+async fn tampered_signature_is_rejected() {
+    let verifier = verifier(StaticFetcher::new(json!({ "keys": [jwk("a", MODULUS_A)] })));
+    let token = mint("a", KEY_A, &claims());
+
+    let (signed, signature) = token.rsplit_once('.').unwrap();
+    let flipped = if signature.starts_with('A') { "B" } else { "A" };
+    let tampered = format!("{}.{}{}", signed, flipped, &signature[1..]);
+    assert!(is_invalid_token(verifier.verify_access_token(&tampered).await));
+
+    // Claims changed after signing
+    let mut forged = claims();
+    forged["scope"] = json!("admin");
+    let forged = mint("a", KEY_B, &forged);
+    let parts: Vec<&str> = token.split('.').collect();
+    let swapped = format!("{}.{}.{}", parts[0], forged.split('.').nth(1).unwrap(), parts[2]);
+    assert!(is_invalid_token(verifier.verify_access_token(&swapped).await));
+}
+
+#[tokio::test]
+async fn time_claims_honour_leeway() {
+    let fetcher = StaticFetcher::new(json!({ "keys": [jwk("a", MODULUS_A)] }));
+    let mut expired = claims();
+    expired["exp"] = json!(now() - 30);
+    let expired = mint("a", KEY_A, &expired);
+
+    let lenient = verifier(fetcher.clone()).with_leeway(Duration::from_secs(60));
+    assert!(lenient.verify_access_token(&expired).await.is_ok());
+
+    let strict = verifier(fetcher.clone()).with_leeway(Duration::ZERO);
+    assert!(is_invalid_token(strict.verify_access_token(&expired).await));
+
+    let mut early = claims();
+    early["nbf"] = json!(now() + 600);
+    assert!(is_invalid_token(strict.verify_access_token(&mint("a", KEY_A, &early)).await));
+}
+
+#[tokio::test]
+async fn issuer_and_audience_must_match() {
+    let verifier = verifier(StaticFetcher::new(json!({ "keys": [jwk("a", MODULUS_A)] })));
+
+    for (claim, value) in [("iss", "https://evil.example.com"), ("aud", "other-service")] {
+        let mut claims = claims();
+        claims[claim] = json!(value);
+        assert!(is_invalid_token(verifier.verify_access_token(&mint("a", KEY_A, &claims)).await), "{claim}");
+    }
+}
+
+#[tokio::test]
+async fn unknown_kid_refreshes_keys() {
+    let fetcher = StaticFetcher::new(json!({ "keys": [jwk("a", MODULUS_A)] }));
+    let verifier = verifier(fetcher.clone()).with_refresh_interval(Duration::ZERO);
+    verifier.verify_access_token(&mint("a", KEY_A, &claims())).await.unwrap();
+    assert_eq!(fetcher.calls(), 1);
+
+    // The issuer rotates to key b
+    *fetcher.keys.lock().unwrap() = json!({ "keys": [jwk("a", MODULUS_A), jwk("b", MODULUS_B)] });
+    verifier.verify_access_token(&mint("b", KEY_B, &claims())).await.unwrap();
+    assert_eq!(fetcher.calls(), 2);
+
+    // Cached keys need no fetch
+    verifier.verify_access_token(&mint("a", KEY_A, &claims())).await.unwrap();
+    assert_eq!(fetcher.calls(), 2);
+}
+
+#[tokio::test]
+async fn refetches_are_rate_limited() {
+    let fetcher = StaticFetcher::new(json!({ "keys": [jwk("a", MODULUS_A)] }));
+    let verifier = verifier(fetcher.clone()).with_refresh_interval(Duration::from_secs(3600));
+    verifier.verify_access_token(&mint("a", KEY_A, &claims())).await.unwrap();
+
+    for _ in 0..3 {
+        assert!(is_invalid_token(verifier.verify_access_token(&mint("unknown", KEY_B, &claims())).await));
+    }
+    assert_eq!(fetcher.calls(), 1);
+}
+
+#[tokio::test]
+async fn symmetric_algorithms_are_rejected() {
+    let fetcher = StaticFetcher::new(json!({ "keys": [jwk("a", MODULUS_A)] }));
+    let verifier = verifier(fetcher.clone());
+
+    let mut header = Header::new(Algorithm::HS256);
+    header.kid = Some("a".to_string());
+    let token = jsonwebtoken::encode(&header, &claims(), &EncodingKey::from_secret(MODULUS_A.as_bytes())).unwrap();
+
+    assert!(is_invalid_token(verifier.verify_access_token(&token).await));
+    assert_eq!(fetcher.calls(), 0);
+}
@@ -0,0 +1,89 @@
+mod support;
+
+use std::sync::{Arc, Mutex};
+
+use futures::executor::block_on;
+use serde_json::json;
+
+use mcp_core::protocol::RequestContext;
+use mcp_core::types::{
+    CallToolResult, CapabilityFlag, LoggingLevel, LoggingMessageParams, NotificationMessage,
+    RequestMessage, ServerCapabilities,
+};
+use mcp_server::{McpServer, ServerError, ServerOptions};
+
+fn failing_server(log_handler_errors: bool) -> (McpServer, Arc<Mutex<Vec<NotificationMessage>>>) {
+    let options = ServerOptions {
+        capabilities: Some(ServerCapabilities {
+            logging: Some(CapabilityFlag::default()),
+            ..Default::default()
+        }),
+        log_handler_errors,
+        ..Default::default()
+    };
+    let mut server = McpServer::new(support::implementation("logging-server"), options);
+
+    let tool = support::tool("explode");
+    server
+        .register_tool(tool, |_args, _ctx: RequestContext| async move {
+            Err::<CallToolResult, _>(ServerError::Handler("disk on fire".to_string()))
+        })
+        .expect("register tool");
+
+    let sent = Arc::new(Mutex::new(Vec::new()));
+    let sink = sent.clone();
+    server
+        .server()
+        .set_notification_sender(Some(Arc::new(move |_session_id, notification| {
+            sink.lock().unwrap().push(notification);
+        })));
+    (server, sent)
+}
+
+fn set_level(server: &McpServer, level: &str) {
+    let request = RequestMessage::new("level", "logging/setLevel", json!({ "level": level }));
+    let response = block_on(server.server().handle_request(request, None)).expect("response");
+    assert!(response.error.is_none());
+}
+
+fn call_explode(server: &McpServer) {
+    let request = RequestMessage::new("call", "tools/call", json!({ "name": "explode" }));
+    let response = block_on(server.server().handle_request(request, None)).expect("response");
+    let error = response.error.expect("tools/call should fail");
+    assert!(error.message.contains("disk on fire"));
+}
+
+#[test]
+fn handler_error_is_logged_when_enabled() {
+    let (server, sent) = failing_server(true);
+    set_level(&server, "error");
+
+    call_explode(&server);
+
+    let sent = sent.lock().unwrap();
+    assert_eq!(sent.len(), 1);
+    assert_eq!(sent[0].method, "notifications/message");
+    let params: LoggingMessageParams =
+        serde_json::from_value(sent[0].params.clone().unwrap()).unwrap();
+    assert_eq!(params.level, LoggingLevel::Error);
+    assert_eq!(params.data["method"], json!("tools/call"));
+    assert!(
+        params.data["error"]
+            .as_str()
+            .unwrap()
+            .contains("disk on fire")
+    );
+}
+
+#[test]
+fn handler_error_respects_level_and_opt_in() {
+    let (server, sent) = failing_server(true);
+    set_level(&server, "critical");
+    call_explode(&server);
+    assert!(sent.lock().unwrap().is_empty());
+
+    let (server, sent) = failing_server(false);
+    set_level(&server, "error");
+    call_explode(&server);
+    assert!(sent.lock().unwrap().is_empty());
+}
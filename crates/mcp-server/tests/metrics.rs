@@ -0,0 +1,139 @@
+//! Request, tool and session metrics scraped from the axum router's
+//! `/metrics` route.
+
+#![cfg(all(feature = "axum", feature = "prometheus"))]
+
+mod support;
+
+use std::sync::Arc;
+
+use axum::body::Body;
+use axum::http::{Request, StatusCode, header};
+use serde_json::{Value, json};
+use tower::util::ServiceExt;
+
+use mcp_core::protocol::RequestContext;
+use mcp_core::types::CallToolResult;
+use mcp_server::{
+    AxumHandlerConfig, AxumHandlerState, McpServer, PrometheusMetrics, ServerOptions, create_router,
+};
+
+fn tool_result(is_error: bool) -> CallToolResult {
+    CallToolResult {
+        content: Vec::new(),
+        structured_content: None,
+        is_error: Some(is_error),
+        meta: None,
+    }
+}
+
+fn router(options: ServerOptions) -> axum::Router {
+    let mut server = McpServer::new(support::implementation("metrics-server"), options);
+    server
+        .register_tool(support::tool("echo"), |_args, _ctx: RequestContext| async move {
+            Ok(tool_result(false))
+        })
+        .expect("register tool");
+    server
+        .register_tool(support::tool("fail"), |_args, _ctx: RequestContext| async move {
+            Ok(tool_result(true))
+        })
+        .expect("register tool");
+    let state = AxumHandlerState::new(Arc::new(server), AxumHandlerConfig::default());
+    create_router(Arc::new(state))
+}
+
+/// POST a JSON-RPC request, returning the session id of the response.
+async fn post(app: &axum::Router, session_id: Option<&str>, body: Value) -> Option<String> {
+    let mut request = Request::post("/mcp")
+        .header(header::CONTENT_TYPE, "application/json")
+        .header(header::ACCEPT, "application/json, text/event-stream");
+    if let Some(session_id) = session_id {
+        request = request.header("mcp-session-id", session_id);
+    }
+    let request = request.body(Body::from(body.to_string())).unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    response
+        .headers()
+        .get("mcp-session-id")
+        .map(|value| value.to_str().unwrap().to_string())
+}
+
+async fn call_tool(app: &axum::Router, session_id: &str, name: &str) {
+    let body = json!({
+        "jsonrpc": "2.0",
+        "id": 2,
+        "method": "tools/call",
+        "params": { "name": name }
+    });
+    post(app, Some(session_id), body).await;
+}
+
+async fn scrape(app: &axum::Router) -> (StatusCode, String) {
+    let request = Request::get("/metrics").body(Body::empty()).unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    let status = response.status();
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    (status, String::from_utf8(bytes.to_vec()).unwrap())
+}
+
+#[tokio::test]
+async fn metrics_count_requests_tool_calls_and_sessions() {
+    let app = router(ServerOptions {
+        metrics: Some(Arc::new(PrometheusMetrics::new())),
+        ..Default::default()
+    });
+
+    let initialize = json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": "initialize",
+        "params": {
+            "protocolVersion": "2025-03-26",
+            "capabilities": {},
+            "clientInfo": { "name": "test-client", "version": "0.1.0" }
+        }
+    });
+    let session_id = post(&app, None, initialize).await.expect("new session id");
+    call_tool(&app, &session_id, "echo").await;
+    call_tool(&app, &session_id, "echo").await;
+    call_tool(&app, &session_id, "fail").await;
+
+    let delete = Request::delete("/mcp")
+        .header("mcp-session-id", &session_id)
+        .body(Body::empty())
+        .unwrap();
+    app.clone().oneshot(delete).await.unwrap();
+
+    let (status, text) = scrape(&app).await;
+    assert_eq!(status, StatusCode::OK);
+    for line in [
+        "mcp_requests_total{method=\"initialize\",status=\"ok\"} 1",
+        "mcp_requests_total{method=\"tools/call\",status=\"ok\"} 2",
+        "mcp_requests_total{method=\"tools/call\",status=\"error\"} 1",
+        "mcp_request_duration_seconds_count{method=\"tools/call\"} 3",
+        "mcp_tool_calls_total{tool=\"echo\",status=\"ok\"} 2",
+        "mcp_tool_calls_total{tool=\"fail\",status=\"error\"} 1",
+        "mcp_tool_call_duration_seconds_bucket{tool=\"echo\",le=\"+Inf\"} 2",
+        "mcp_requests_in_flight{method=\"tools/call\"} 0",
+        "mcp_sessions_created_total 1",
+        "mcp_sessions_expired_total 1",
+        "mcp_sessions_active 0",
+    ] {
+        assert!(
+            text.lines().any(|l| l == line),
+            "missing `{line}` in:\n{text}"
+        );
+    }
+}
+
+#[tokio::test]
+async fn metrics_route_needs_a_recorder() {
+    let app = router(ServerOptions::default());
+
+    let (status, _) = scrape(&app).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
@@ -0,0 +1,147 @@
+//! Cursor pagination of the `*/list` methods.
+
+mod support;
+
+use std::collections::HashSet;
+
+use futures::executor::block_on;
+use serde_json::{Value, json};
+
+use mcp_core::protocol::RequestContext;
+use mcp_core::types::{CallToolResult, ReadResourceResult, RequestMessage, Resource};
+use mcp_server::{McpServer, ServerOptions};
+
+fn resource(uri: &str) -> Resource {
+    serde_json::from_value(json!({ "uri": uri, "name": uri })).unwrap()
+}
+
+fn paged_server(page_size: Option<usize>) -> McpServer {
+    McpServer::new(
+        support::implementation("pagination-server"),
+        ServerOptions {
+            list_page_size: page_size,
+            ..Default::default()
+        },
+    )
+}
+
+fn register_tool(server: &mut McpServer, name: &str) {
+    server
+        .register_tool(support::tool(name), |_args, _ctx: RequestContext| async move {
+            Ok(CallToolResult::default())
+        })
+        .unwrap();
+}
+
+fn register_resource(server: &mut McpServer, uri: &str) {
+    server
+        .register_resource(
+            resource(uri),
+            |_uri: String, _ctx: RequestContext| async move {
+                Ok(ReadResourceResult {
+                    contents: Vec::new(),
+                    meta: None,
+                })
+            },
+        )
+        .unwrap();
+}
+
+/// Request one page of `method`, returning its result.
+fn list(server: &McpServer, method: &str, cursor: Option<&Value>) -> Value {
+    let params = match cursor {
+        Some(cursor) => json!({ "cursor": cursor }),
+        None => json!({}),
+    };
+    let response = block_on(
+        server
+            .server()
+            .handle_request(RequestMessage::new("list", method, params), None),
+    )
+    .unwrap();
+    response.result.expect("list result")
+}
+
+fn names(page: &Value, field: &str, key: &str) -> Vec<String> {
+    page[field]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|entry| entry[key].as_str().unwrap().to_string())
+        .collect()
+}
+
+#[test]
+fn tool_pages_cover_every_tool_once() {
+    let mut server = paged_server(Some(5));
+    for i in 0..23 {
+        register_tool(&mut server, &format!("tool_{i:02}"));
+    }
+
+    let mut seen = Vec::new();
+    let mut cursor = None;
+    let mut pages = 0;
+    loop {
+        let page = list(&server, "tools/list", cursor.as_ref());
+        let names = names(&page, "tools", "name");
+        assert!(names.len() <= 5);
+        seen.extend(names);
+        pages += 1;
+        match page.get("nextCursor") {
+            Some(next) => cursor = Some(next.clone()),
+            None => break,
+        }
+    }
+
+    assert_eq!(pages, 5);
+    assert_eq!(seen.len(), 23);
+    assert_eq!(seen.iter().collect::<HashSet<_>>().len(), 23);
+}
+
+#[test]
+fn registrations_between_pages_are_not_listed_twice() {
+    let mut server = paged_server(Some(3));
+    for name in ["b", "d", "f", "h", "j"] {
+        register_tool(&mut server, name);
+    }
+
+    let first = list(&server, "tools/list", None);
+    assert_eq!(names(&first, "tools", "name"), ["b", "d", "f"]);
+
+    // One tool sorts before the cursor, one after it
+    register_tool(&mut server, "a");
+    register_tool(&mut server, "g");
+
+    let second = list(&server, "tools/list", first.get("nextCursor"));
+    assert_eq!(names(&second, "tools", "name"), ["g", "h", "j"]);
+    assert!(second.get("nextCursor").is_none());
+}
+
+#[test]
+fn resources_are_paged_by_uri() {
+    let mut server = paged_server(Some(2));
+    for uri in ["file:///c", "file:///a", "file:///b"] {
+        register_resource(&mut server, uri);
+    }
+
+    let first = list(&server, "resources/list", None);
+    assert_eq!(
+        names(&first, "resources", "uri"),
+        ["file:///a", "file:///b"]
+    );
+    let second = list(&server, "resources/list", first.get("nextCursor"));
+    assert_eq!(names(&second, "resources", "uri"), ["file:///c"]);
+    assert!(second.get("nextCursor").is_none());
+}
+
+#[test]
+fn without_a_page_size_everything_is_listed_at_once() {
+    let mut server = paged_server(None);
+    for i in 0..150 {
+        register_tool(&mut server, &format!("tool_{i}"));
+    }
+
+    let page = list(&server, "tools/list", None);
+    assert_eq!(names(&page, "tools", "name").len(), 150);
+    assert!(page.get("nextCursor").is_none());
+}
@@ -0,0 +1,170 @@
+//! POST response negotiation on the Streamable HTTP transport.
+//!
+//! A client accepting `text/event-stream` gets the notifications a handler
+//! sends before its result on the POST response itself; otherwise the
+//! response is a single JSON body.
+
+#![cfg(feature = "axum")]
+
+mod support;
+
+use std::sync::Arc;
+
+use axum::body::Body;
+use axum::http::{Request, StatusCode, header};
+use axum::response::Response;
+use serde_json::{Value, json};
+use tower::util::ServiceExt;
+
+use mcp_core::http::{SseEvent, SseParser};
+use mcp_core::protocol::RequestContext;
+use mcp_core::stdio::JsonRpcMessage;
+use mcp_core::types::{CallToolResult, ContentBlock, Progress, TextContent};
+use mcp_server::{
+    AxumHandlerConfig, AxumHandlerState, HttpResponse, HttpServerHandler, McpServer,
+    ServerOptions, create_router,
+};
+
+fn text_result(text: &str) -> CallToolResult {
+    CallToolResult {
+        content: vec![ContentBlock::Text(TextContent::new(text))],
+        structured_content: None,
+        is_error: None,
+        meta: None,
+    }
+}
+
+/// Server with a `work` tool reporting two progress steps and a silent `echo` tool.
+fn server() -> Arc<McpServer> {
+    let mut server = McpServer::new(support::implementation("progress-server"), ServerOptions::default());
+    server
+        .register_tool(support::tool("work"), |_args: Option<Value>, ctx: RequestContext| async move {
+            for step in 1..=2 {
+                ctx.send_progress(Progress {
+                    progress: step as f64,
+                    total: Some(2.0),
+                    message: Some(format!("step {step}")),
+                })
+                .expect("send progress");
+            }
+            Ok(text_result("done"))
+        })
+        .expect("register work");
+    server
+        .register_tool(support::tool("echo"), |_args: Option<Value>, _ctx: RequestContext| async move {
+            Ok(text_result("echo"))
+        })
+        .expect("register echo");
+    Arc::new(server)
+}
+
+fn call_tool(name: &str) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "id": 7,
+        "method": "tools/call",
+        "params": { "name": name, "arguments": {}, "_meta": { "progressToken": "job-1" } }
+    })
+}
+
+async fn post(accept: &str, body: Value) -> Response {
+    let state = Arc::new(AxumHandlerState::new(server(), AxumHandlerConfig::default()));
+    let request = Request::builder()
+        .method("POST")
+        .uri("/mcp")
+        .header(header::CONTENT_TYPE, "application/json")
+        .header(header::ACCEPT, accept)
+        .body(Body::from(body.to_string()))
+        .unwrap();
+    create_router(state).oneshot(request).await.unwrap()
+}
+
+async fn body_text(response: Response) -> String {
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    String::from_utf8(bytes.to_vec()).unwrap()
+}
+
+fn sse_messages(body: &str) -> Vec<JsonRpcMessage> {
+    let mut parser = SseParser::new();
+    parser.append(body);
+    let mut messages = Vec::new();
+    while let Some(event) = parser.next_event() {
+        if let Ok(SseEvent::Message { data, .. }) = event.to_mcp_event() {
+            messages.push(data);
+        }
+    }
+    messages
+}
+
+fn assert_progress_then_result(messages: &[JsonRpcMessage]) {
+    assert_eq!(messages.len(), 3, "{messages:?}");
+    for (step, message) in messages[..2].iter().enumerate() {
+        let JsonRpcMessage::Notification(notification) = message else {
+            panic!("expected progress notification, got {message:?}");
+        };
+        assert_eq!(notification.method, "notifications/progress");
+        let params = serde_json::to_value(&notification.params).unwrap();
+        assert_eq!(params["progressToken"], "job-1");
+        assert_eq!(params["progress"], json!((step + 1) as f64));
+    }
+    let JsonRpcMessage::Result(result) = &messages[2] else {
+        panic!("expected result, got {:?}", messages[2]);
+    };
+    assert_eq!(result.result.as_ref().unwrap()["content"][0]["text"], "done");
+}
+
+#[tokio::test]
+async fn progress_is_streamed_on_the_post_response() {
+    let response = post("application/json, text/event-stream", call_tool("work")).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let content_type = response.headers()[header::CONTENT_TYPE].to_str().unwrap().to_string();
+    assert!(content_type.starts_with("text/event-stream"), "{content_type}");
+    assert!(response.headers().get("mcp-session-id").is_some());
+
+    assert_progress_then_result(&sse_messages(&body_text(response).await));
+}
+
+#[tokio::test]
+async fn json_only_clients_get_a_json_body() {
+    let response = post("application/json", call_tool("work")).await;
+    let content_type = response.headers()[header::CONTENT_TYPE].to_str().unwrap().to_string();
+    assert_eq!(content_type, "application/json");
+
+    let body: Value = serde_json::from_str(&body_text(response).await).unwrap();
+    assert_eq!(body["id"], 7);
+    assert_eq!(body["result"]["content"][0]["text"], "done");
+}
+
+#[tokio::test]
+async fn requests_without_notifications_get_a_json_body() {
+    let response = post("application/json, text/event-stream", call_tool("echo")).await;
+    let content_type = response.headers()[header::CONTENT_TYPE].to_str().unwrap().to_string();
+    assert_eq!(content_type, "application/json");
+
+    let body: Value = serde_json::from_str(&body_text(response).await).unwrap();
+    assert_eq!(body["result"]["content"][0]["text"], "echo");
+}
+
+#[test]
+fn http_server_handler_negotiates_event_stream() {
+    let handler = HttpServerHandler::with_server(server());
+    let body = serde_json::to_vec(&call_tool("work")).unwrap();
+
+    match handler.handle_post_with_accept(
+        None,
+        Some("application/json"),
+        Some("application/json, text/event-stream"),
+        &body,
+    ) {
+        HttpResponse::EventStream { messages, session_id } => {
+            assert!(session_id.is_some());
+            assert_progress_then_result(&messages);
+        }
+        _ => panic!("Expected event stream response"),
+    }
+
+    match handler.handle_post(None, Some("application/json"), &body) {
+        HttpResponse::Json { status, .. } => assert_eq!(status, 200),
+        _ => panic!("Expected JSON response"),
+    }
+}
@@ -0,0 +1,119 @@
+//! Per-client rate limiting on the axum router and `HttpServerHandler`.
+
+#![cfg(feature = "axum")]
+
+mod support;
+
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::Duration;
+
+use axum::body::Body;
+use axum::extract::ConnectInfo;
+use axum::http::{Request, StatusCode, header};
+use serde_json::{Value, json};
+use tower::util::ServiceExt;
+
+use mcp_server::{
+    AxumHandlerConfig, AxumHandlerState, HttpResponse, HttpServerHandler, HttpServerOptions,
+    McpServer, RateLimitConfig, ServerOptions, SessionMode, create_router,
+};
+
+fn server() -> Arc<McpServer> {
+    Arc::new(McpServer::new(
+        support::implementation("rate-limited-server"),
+        ServerOptions::default(),
+    ))
+}
+
+fn ping(id: i64) -> Vec<u8> {
+    serde_json::to_vec(&json!({ "jsonrpc": "2.0", "id": id, "method": "ping" })).unwrap()
+}
+
+/// A window long enough that no request is regained mid-burst.
+fn config() -> RateLimitConfig {
+    RateLimitConfig::new(1, Duration::from_millis(500)).with_burst(3)
+}
+
+#[tokio::test]
+async fn router_answers_429_past_the_burst() {
+    let state = Arc::new(AxumHandlerState::new(
+        server(),
+        AxumHandlerConfig {
+            session_mode: SessionMode::Stateless,
+            rate_limit: Some(config()),
+            ..Default::default()
+        },
+    ));
+    let router = create_router(state);
+    let peer: SocketAddr = "192.0.2.7:4000".parse().unwrap();
+    let post = |id| {
+        let mut request = Request::post("/mcp")
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(Body::from(ping(id)))
+            .unwrap();
+        request.extensions_mut().insert(ConnectInfo(peer));
+        router.clone().oneshot(request)
+    };
+
+    for id in 0..3 {
+        assert_eq!(post(id).await.unwrap().status(), StatusCode::OK);
+    }
+    let response = post(3).await.unwrap();
+    assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+    assert_eq!(response.headers()[header::RETRY_AFTER], "1");
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let body: Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(body["error"]["code"], -32000);
+    assert_eq!(body["id"], Value::Null);
+
+    tokio::time::sleep(Duration::from_millis(550)).await;
+    assert_eq!(post(4).await.unwrap().status(), StatusCode::OK);
+}
+
+#[test]
+fn handler_limits_each_session() {
+    let handler = HttpServerHandler::new(
+        server(),
+        HttpServerOptions {
+            rate_limit: Some(config()),
+            ..Default::default()
+        },
+    );
+    let initialize = json!({
+        "jsonrpc": "2.0",
+        "id": 0,
+        "method": "initialize",
+        "params": {
+            "protocolVersion": "2025-06-18",
+            "capabilities": {},
+            "clientInfo": { "name": "test-client", "version": "1.0.0" }
+        }
+    });
+    let body = serde_json::to_vec(&initialize).unwrap();
+    let HttpResponse::Json {
+        session_id: Some(session_id),
+        ..
+    } = handler.handle_post(None, Some("application/json"), &body)
+    else {
+        panic!("Expected a new session");
+    };
+    let post = |id| handler.handle_post(Some(&session_id), Some("application/json"), &ping(id));
+
+    for id in 1..4 {
+        assert!(matches!(post(id), HttpResponse::Json { status: 200, .. }));
+    }
+    match post(4) {
+        HttpResponse::RateLimited { retry_after, body } => {
+            assert_eq!(retry_after, 1);
+            let body: Value = serde_json::from_str(&body).unwrap();
+            assert_eq!(body["error"]["message"], "Rate limit exceeded");
+        }
+        _ => panic!("Expected a rate limited response"),
+    }
+
+    std::thread::sleep(Duration::from_millis(550));
+    assert!(matches!(post(5), HttpResponse::Json { status: 200, .. }));
+}
@@ -0,0 +1,262 @@
+//! Refresh token rotation and reuse detection through the `/token` route of
+//! `create_oauth_router`.
+
+#![cfg(feature = "axum")]
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use async_trait::async_trait;
+use axum::body::Body;
+use axum::http::{Request, StatusCode, header};
+use serde_json::Value;
+use tower::util::ServiceExt;
+
+use mcp_core::auth::{
+    AuthInfo, AuthorizationParams, OAuthClientInformation, OAuthClientInformationFull,
+    OAuthClientMetadata, OAuthTokens,
+};
+use mcp_server::auth::{
+    AuthorizeResponse, InMemoryClientStore, InMemoryRefreshTokenStore, IssuedRefreshToken,
+    OAuthProviderError, OAuthRouterOptions, OAuthServerProvider, OAuthRegisteredClientsStore,
+    RefreshTokenError, RefreshTokenGrant, create_oauth_router,
+};
+
+/// A public client, as OAuth 2.1 requires rotation for those
+const CLIENT_ID: &str = "public-app";
+
+/// Provider issuing access tokens tied to a refresh token family.
+struct MemoryProvider {
+    clients: InMemoryClientStore,
+    refresh_tokens: InMemoryRefreshTokenStore,
+    /// Access token to the family it was issued from
+    access_tokens: Mutex<HashMap<String, String>>,
+}
+
+impl MemoryProvider {
+    /// Provider with one grant; returns the grant's first refresh token.
+    fn new() -> (Self, String) {
+        let clients = InMemoryClientStore::new();
+        clients.add_client(OAuthClientInformationFull {
+            client_info: OAuthClientInformation {
+                client_id: CLIENT_ID.to_string(),
+                client_secret: None,
+                client_id_issued_at: None,
+                client_secret_expires_at: None,
+            },
+            metadata: OAuthClientMetadata::default(),
+            token_endpoint_auth_method: Some("none".to_string()),
+        });
+
+        let refresh_tokens = InMemoryRefreshTokenStore::new();
+        let first = refresh_tokens.issue(RefreshTokenGrant {
+            client_id: CLIENT_ID.to_string(),
+            scopes: vec!["mcp".to_string()],
+            resource: None,
+        });
+
+        let provider = Self {
+            clients,
+            refresh_tokens,
+            access_tokens: Mutex::new(HashMap::new()),
+        };
+        (provider, first.refresh_token)
+    }
+
+    fn tokens(&self, issued: IssuedRefreshToken) -> OAuthTokens {
+        let access_token = uuid::Uuid::new_v4().to_string();
+        self.access_tokens
+            .lock()
+            .unwrap()
+            .insert(access_token.clone(), issued.family_id);
+        OAuthTokens {
+            access_token,
+            token_type: "Bearer".to_string(),
+            expires_in: Some(3600),
+            refresh_token: Some(issued.refresh_token),
+            scope: Some(issued.grant.scopes.join(" ")),
+            id_token: None,
+        }
+    }
+}
+
+#[async_trait]
+impl OAuthServerProvider for MemoryProvider {
+    fn clients_store(&self) -> &dyn OAuthRegisteredClientsStore {
+        &self.clients
+    }
+
+    async fn authorize(
+        &self,
+        _client: &OAuthClientInformationFull,
+        _params: AuthorizationParams,
+    ) -> Result<AuthorizeResponse, OAuthProviderError> {
+        Err(OAuthProviderError::AccessDenied("not used".to_string()))
+    }
+
+    async fn challenge_for_authorization_code(
+        &self,
+        _client: &OAuthClientInformationFull,
+        _authorization_code: &str,
+    ) -> Result<String, OAuthProviderError> {
+        Err(OAuthProviderError::InvalidGrant("not used".to_string()))
+    }
+
+    async fn exchange_authorization_code(
+        &self,
+        _client: &OAuthClientInformationFull,
+        _authorization_code: &str,
+        _code_verifier: Option<&str>,
+        _redirect_uri: Option<&str>,
+        _resource: Option<&str>,
+    ) -> Result<OAuthTokens, OAuthProviderError> {
+        Err(OAuthProviderError::InvalidGrant("not used".to_string()))
+    }
+
+    async fn exchange_refresh_token(
+        &self,
+        client: &OAuthClientInformationFull,
+        refresh_token: &str,
+        _scopes: Option<&[String]>,
+        _resource: Option<&str>,
+    ) -> Result<OAuthTokens, OAuthProviderError> {
+        let issued = self
+            .refresh_tokens
+            .check(&client.client_info.client_id, refresh_token)?;
+        Ok(self.tokens(issued))
+    }
+
+    async fn rotate_refresh_token(
+        &self,
+        client: &OAuthClientInformationFull,
+        refresh_token: &str,
+        _scopes: Option<&[String]>,
+        _resource: Option<&str>,
+    ) -> Result<OAuthTokens, OAuthProviderError> {
+        match self.refresh_tokens.rotate(&client.client_info.client_id, refresh_token) {
+            Ok(issued) => Ok(self.tokens(issued)),
+            Err(error) => {
+                if let RefreshTokenError::Reused { family_id } = &error {
+                    self.access_tokens
+                        .lock()
+                        .unwrap()
+                        .retain(|_, family| family != family_id);
+                }
+                Err(error.into())
+            }
+        }
+    }
+
+    async fn verify_access_token(&self, token: &str) -> Result<AuthInfo, OAuthProviderError> {
+        let family = self.access_tokens.lock().unwrap().get(token).cloned();
+        match family {
+            Some(family) if !self.refresh_tokens.is_revoked(&family) => {
+                Ok(AuthInfo::new(token).with_client_id(CLIENT_ID))
+            }
+            _ => Err(OAuthProviderError::InvalidToken("unknown token".to_string())),
+        }
+    }
+}
+
+fn router(options: OAuthRouterOptions) -> (axum::Router, Arc<MemoryProvider>, String) {
+    let (provider, refresh_token) = MemoryProvider::new();
+    let provider = Arc::new(provider);
+    (create_oauth_router(Arc::clone(&provider), options), provider, refresh_token)
+}
+
+fn options() -> OAuthRouterOptions {
+    OAuthRouterOptions::new("https://auth.example.com")
+}
+
+async fn refresh(router: &axum::Router, refresh_token: &str) -> (StatusCode, Value) {
+    let request = Request::post("/token")
+        .header(header::CONTENT_TYPE, "application/x-www-form-urlencoded")
+        .body(Body::from(format!(
+            "grant_type=refresh_token&client_id={CLIENT_ID}&refresh_token={refresh_token}"
+        )))
+        .unwrap();
+
+    let response = router.clone().oneshot(request).await.unwrap();
+    let status = response.status();
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (status, serde_json::from_slice(&body).unwrap())
+}
+
+#[tokio::test]
+async fn refresh_rotates_the_refresh_token() {
+    let (router, _, first) = router(options());
+
+    let (status, body) = refresh(&router, &first).await;
+    assert_eq!(status, StatusCode::OK);
+    let second = body["refresh_token"].as_str().unwrap().to_string();
+    assert_ne!(second, first);
+    assert_eq!(body["scope"], "mcp");
+
+    // The new token keeps working, one use at a time
+    let (status, body) = refresh(&router, &second).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_ne!(body["refresh_token"], second.as_str());
+}
+
+#[tokio::test]
+async fn replaying_a_rotated_token_revokes_the_grant() {
+    let (router, provider, first) = router(options());
+
+    let (_, body) = refresh(&router, &first).await;
+    let second = body["refresh_token"].as_str().unwrap().to_string();
+    let access_token = body["access_token"].as_str().unwrap().to_string();
+    assert!(provider.verify_access_token(&access_token).await.is_ok());
+
+    let (status, body) = refresh(&router, &first).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["error"], "invalid_grant");
+
+    // Everything issued from the grant is gone, including the legitimate
+    // holder's latest tokens
+    let (status, body) = refresh(&router, &second).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["error"], "invalid_grant");
+    assert!(provider.verify_access_token(&access_token).await.is_err());
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn concurrent_refreshes_with_one_token() {
+    let (router, _, first) = router(options());
+
+    let attempts = (0..8).map(|_| {
+        let router = router.clone();
+        let first = first.clone();
+        tokio::spawn(async move { refresh(&router, &first).await })
+    });
+    let results: Vec<(StatusCode, Value)> = futures::future::join_all(attempts)
+        .await
+        .into_iter()
+        .map(Result::unwrap)
+        .collect();
+
+    let winners: Vec<&Value> = results
+        .iter()
+        .filter(|(status, _)| *status == StatusCode::OK)
+        .map(|(_, body)| body)
+        .collect();
+    assert_eq!(winners.len(), 1);
+    assert!(results
+        .iter()
+        .filter(|(status, _)| *status != StatusCode::OK)
+        .all(|(status, body)| *status == StatusCode::BAD_REQUEST && body["error"] == "invalid_grant"));
+
+    // The losers presented a consumed token, so the winner's grant is revoked
+    let (status, _) = refresh(&router, winners[0]["refresh_token"].as_str().unwrap()).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn rotation_can_be_disabled() {
+    let (router, _, first) = router(options().with_refresh_token_rotation(false));
+
+    for _ in 0..2 {
+        let (status, body) = refresh(&router, &first).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["refresh_token"], first.as_str());
+    }
+}
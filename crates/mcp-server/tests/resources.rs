@@ -1,14 +1,18 @@
 mod support;
 
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
 use futures::executor::block_on;
 use serde_json::json;
 
+use mcp_core::protocol::RequestContext;
 use mcp_core::types::{
     BaseMetadata, Icons, ReadResourceResult, RequestMessage, RequestParams, Resource,
     ResourceContents, ResourceContentsBase, ResourceRequestParams, ResourceTemplate,
     TextResourceContents,
 };
-use mcp_server::{McpServer, ServerOptions};
+use mcp_server::{McpServer, NotificationSender, ServerError, ServerOptions};
 
 #[test]
 fn resources_list_templates_and_read_work() {
@@ -61,8 +65,16 @@ fn resources_list_templates_and_read_work() {
         meta: None,
     };
 
+    // Concrete resources take precedence over a matching template
     server
-        .register_resource_template(template)
+        .register_resource_template(
+            template,
+            |_uri: String, _variables: HashMap<String, String>, _ctx: RequestContext| async move {
+                Err::<ReadResourceResult, _>(ServerError::Handler(
+                    "template should not be read".to_string(),
+                ))
+            },
+        )
         .expect("register template");
 
     let list_request = RequestMessage::new("1", "resources/list", json!({}));
@@ -97,3 +109,146 @@ fn resources_list_templates_and_read_work() {
     let notification = server.resource_list_changed_notification();
     assert_eq!(notification.method, "notifications/resources/list_changed");
 }
+
+#[test]
+fn resource_updates_reach_subscribed_sessions_only() {
+    let server = McpServer::builder("resource-server", "0.1.0")
+        .enable_resources(true, true)
+        .build()
+        .expect("build server");
+
+    let sent = Arc::new(Mutex::new(Vec::new()));
+    let sink = sent.clone();
+    let sender: NotificationSender = Arc::new(move |session_id, notification| {
+        sink.lock().unwrap().push((session_id, notification));
+    });
+    server.server().set_notification_sender(Some(sender));
+
+    let subscribe = |id: &str, method: &str, session: &str, uri: &str| {
+        let request = RequestMessage::new(id, method, json!({ "uri": uri }));
+        let response = block_on(
+            server
+                .server()
+                .handle_request(request, Some(session.to_string())),
+        )
+        .expect("response");
+        assert!(response.error.is_none(), "{method}");
+    };
+    subscribe("1", "resources/subscribe", "a", "file:///watched.txt");
+    subscribe("2", "resources/subscribe", "b", "file:///other.txt");
+
+    assert_eq!(server.notify_resource_updated("file:///watched.txt"), 1);
+    {
+        let sent = sent.lock().unwrap();
+        assert_eq!(sent.len(), 1);
+        let (session_id, notification) = &sent[0];
+        assert_eq!(session_id.as_deref(), Some("a"));
+        assert_eq!(notification.method, "notifications/resources/updated");
+        assert_eq!(
+            notification.params.as_ref().unwrap()["uri"],
+            "file:///watched.txt"
+        );
+    }
+
+    // After unsubscribing, nobody is notified
+    subscribe("3", "resources/unsubscribe", "a", "file:///watched.txt");
+    assert_eq!(server.notify_resource_updated("file:///watched.txt"), 0);
+    assert_eq!(sent.lock().unwrap().len(), 1);
+}
+
+fn issues_template(uri_template: &str) -> ResourceTemplate {
+    serde_json::from_value(json!({
+        "name": "project-issues",
+        "uriTemplate": uri_template,
+        "mimeType": "text/plain"
+    }))
+    .unwrap()
+}
+
+fn read(server: &McpServer, uri: &str) -> mcp_core::types::ResultMessage {
+    let request = RequestMessage::new("read", "resources/read", json!({ "uri": uri }));
+    block_on(server.server().handle_request(request, None)).expect("resources/read response")
+}
+
+#[test]
+fn template_reads_receive_the_extracted_variables() {
+    let server = McpServer::builder("resource-server", "0.1.0")
+        .resource_template(
+            issues_template("gitlab://project/{id}/issues{#section}"),
+            |uri: String, variables: HashMap<String, String>, _ctx: RequestContext| async move {
+                let mut names: Vec<_> = variables.into_iter().collect();
+                names.sort();
+                Ok(ReadResourceResult {
+                    contents: vec![ResourceContents::Text(TextResourceContents {
+                        base: ResourceContentsBase {
+                            uri,
+                            mime_type: Some("text/plain".to_string()),
+                            meta: None,
+                        },
+                        text: format!("{names:?}"),
+                    })],
+                    meta: None,
+                })
+            },
+        )
+        .build()
+        .expect("build server");
+
+    let request = RequestMessage::new("1", "resources/templates/list", json!({}));
+    let response = block_on(server.server().handle_request(request, None)).expect("response");
+    assert_eq!(
+        response.result.unwrap()["resourceTemplates"][0]["uriTemplate"],
+        "gitlab://project/{id}/issues{#section}"
+    );
+
+    let response = read(&server, "gitlab://project/group%2Fapp/issues#open");
+    let result: ReadResourceResult = serde_json::from_value(response.result.unwrap()).unwrap();
+    let ResourceContents::Text(contents) = &result.contents[0] else {
+        panic!("expected text contents");
+    };
+    assert_eq!(
+        contents.base.uri,
+        "gitlab://project/group%2Fapp/issues#open"
+    );
+    assert_eq!(
+        contents.text,
+        r#"[("id", "group/app"), ("section", "open")]"#
+    );
+
+    // A simple `{id}` cannot span a `/`, and every variable is required
+    for uri in [
+        "gitlab://project/group/app/issues#open",
+        "gitlab://project/42/issues",
+        "gitlab://project/42/merge_requests#open",
+    ] {
+        let response = read(&server, uri);
+        let error = response.error.expect(uri);
+        assert!(error.message.contains("resource not found"), "{uri}");
+    }
+}
+
+#[test]
+fn invalid_uri_templates_are_rejected() {
+    let mut server = McpServer::new(
+        support::implementation("resource-server"),
+        ServerOptions::default(),
+    );
+    for uri_template in ["file:///{path", "file:///{a,b}", "file:///{?query}"] {
+        let err = server
+            .register_resource_template(
+                issues_template(uri_template),
+                |_uri: String, _variables: HashMap<String, String>, _ctx: RequestContext| async move {
+                    Ok(ReadResourceResult {
+                        contents: vec![],
+                        meta: None,
+                    })
+                },
+            )
+            .unwrap_err();
+        assert!(
+            matches!(&err, ServerError::InvalidResourceTemplate(message) if message.contains(uri_template)),
+            "{err}"
+        );
+    }
+    assert!(server.server().get_capabilities().resources.is_none());
+}
@@ -0,0 +1,194 @@
+mod support;
+
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use futures::channel::mpsc;
+use futures::executor::block_on;
+use futures::{StreamExt, join};
+use serde_json::{Value, json};
+
+use mcp_core::protocol::{ProtocolError, RequestContext};
+use mcp_core::types::{
+    CallToolResult, ContentBlock, CreateMessageRequestParams, RequestMessage, ResultMessage,
+    SamplingContent, SamplingMessage, TextContent,
+};
+use mcp_server::{McpServer, ServerOptions};
+
+/// Server with a tool that asks the client to sample a completion and echoes it.
+fn sampling_server(options: ServerOptions) -> McpServer {
+    let mut server = McpServer::new(support::implementation("sampling-server"), options);
+    server
+        .register_tool(
+            support::tool("ask"),
+            |_args, ctx: RequestContext| async move {
+                let params = CreateMessageRequestParams::new(
+                    vec![SamplingMessage::user(TextContent::new("2 + 2?"))],
+                    16,
+                );
+                let text = match ctx.create_message(params).await {
+                    Ok(result) => match result.content {
+                        SamplingContent::Text(text) => text.text,
+                        _ => "non-text".to_string(),
+                    },
+                    Err(ProtocolError::Timeout) => "timeout".to_string(),
+                    Err(ProtocolError::Capability(_)) => "unsupported".to_string(),
+                    Err(err) => format!("error: {err}"),
+                };
+                Ok(CallToolResult {
+                    content: vec![ContentBlock::Text(TextContent::new(text))],
+                    structured_content: None,
+                    is_error: None,
+                    meta: None,
+                })
+            },
+        )
+        .expect("register tool");
+    server
+}
+
+fn initialize(server: &McpServer, capabilities: Value) {
+    let request = RequestMessage::new(
+        "init",
+        "initialize",
+        json!({
+            "protocolVersion": mcp_core::types::LATEST_PROTOCOL_VERSION,
+            "capabilities": capabilities,
+            "clientInfo": { "name": "client", "version": "0.1.0" }
+        }),
+    );
+    block_on(server.server().handle_request(request, None)).expect("initialize response");
+}
+
+async fn call_ask(server: &McpServer) -> String {
+    call_ask_in(server, None).await
+}
+
+async fn call_ask_in(server: &McpServer, session_id: Option<&str>) -> String {
+    let request = RequestMessage::new("call", "tools/call", json!({ "name": "ask" }));
+    let response = server
+        .server()
+        .handle_request(request, session_id.map(str::to_string))
+        .await
+        .expect("tools/call response");
+    let result: CallToolResult = serde_json::from_value(response.result.unwrap()).unwrap();
+    match &result.content[0] {
+        ContentBlock::Text(text) => text.text.clone(),
+        other => panic!("unexpected content: {other:?}"),
+    }
+}
+
+#[test]
+fn tool_samples_completion_from_client() {
+    let server = sampling_server(ServerOptions::default());
+    initialize(&server, json!({ "sampling": {} }));
+
+    let (tx, mut rx) = mpsc::unbounded::<RequestMessage>();
+    server
+        .server()
+        .set_request_sender(Some(Arc::new(move |_session_id, request| {
+            let _ = tx.unbounded_send(request);
+        })));
+
+    let client = async {
+        let request = rx.next().await.expect("sampling request");
+        assert_eq!(request.method, "sampling/createMessage");
+        assert_eq!(request.params["maxTokens"], json!(16));
+        let response = ResultMessage::success(
+            request.id,
+            json!({
+                "model": "test-model",
+                "role": "assistant",
+                "content": { "type": "text", "text": "4" }
+            }),
+        );
+        assert!(server.server().handle_response(response, None));
+    };
+
+    let (text, ()) = block_on(async { join!(call_ask(&server), client) });
+    assert_eq!(text, "4");
+    assert!(server.server().pending_requests().is_empty());
+}
+
+#[test]
+fn responses_only_complete_requests_sent_to_their_session() {
+    let server = sampling_server(ServerOptions::default());
+    initialize(&server, json!({ "sampling": {} }));
+
+    let (tx, mut rx) = mpsc::unbounded::<(Option<String>, RequestMessage)>();
+    server
+        .server()
+        .set_request_sender(Some(Arc::new(move |session_id, request| {
+            let _ = tx.unbounded_send((session_id, request));
+        })));
+
+    let clients = async {
+        let (session_id, request) = rx.next().await.expect("sampling request");
+        assert_eq!(session_id.as_deref(), Some("alice"));
+        let answer = |text: &str| {
+            ResultMessage::success(
+                request.id.clone(),
+                json!({
+                    "model": "test-model",
+                    "role": "assistant",
+                    "content": { "type": "text", "text": text }
+                }),
+            )
+        };
+
+        // Another session guessing the id cannot answer alice's request
+        let forged = answer("forged");
+        assert!(
+            !server
+                .server()
+                .handle_response(forged.clone(), Some("mallory".to_string()))
+        );
+        assert!(!server.server().handle_response(forged, None));
+        assert!(
+            server
+                .server()
+                .handle_response(answer("4"), Some("alice".to_string()))
+        );
+    };
+
+    let (text, ()) = block_on(async { join!(call_ask_in(&server, Some("alice")), clients) });
+    assert_eq!(text, "4");
+    assert!(server.server().pending_requests().is_empty());
+}
+
+#[test]
+fn sampling_requires_client_capability() {
+    let server = sampling_server(ServerOptions::default());
+    initialize(&server, json!({}));
+
+    let sent = Arc::new(Mutex::new(Vec::new()));
+    let sink = sent.clone();
+    server
+        .server()
+        .set_request_sender(Some(Arc::new(move |_session_id, request| {
+            sink.lock().unwrap().push(request);
+        })));
+
+    assert_eq!(block_on(call_ask(&server)), "unsupported");
+    assert!(sent.lock().unwrap().is_empty());
+}
+
+#[test]
+fn sampling_times_out_when_client_never_responds() {
+    let server = sampling_server(ServerOptions {
+        peer_request_timeout: Some(Duration::from_millis(50)),
+        ..Default::default()
+    });
+    initialize(&server, json!({ "sampling": {} }));
+
+    // A stdio client that reads the request but never answers.
+    server
+        .server()
+        .set_request_sender(Some(Arc::new(|_session_id, _request| {})));
+
+    assert_eq!(block_on(call_ask(&server)), "timeout");
+    assert!(server.server().pending_requests().is_empty());
+
+    let late = ResultMessage::success("server-1", json!({}));
+    assert!(!server.server().handle_response(late, None));
+}
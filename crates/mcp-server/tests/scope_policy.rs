@@ -0,0 +1,134 @@
+//! `ScopePolicy` on `BearerAuthLayer`: per-method and per-tool scopes
+//! enforced at the HTTP layer with RFC 6750 `insufficient_scope` errors.
+
+#![cfg(feature = "axum")]
+
+mod support;
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use axum::body::Body;
+use axum::http::{Request, StatusCode, header};
+use serde_json::{Value, json};
+use tower::util::ServiceExt;
+
+use mcp_core::auth::AuthInfo;
+use mcp_core::protocol::RequestContext;
+use mcp_core::types::{CallToolResult, ContentBlock, TextContent};
+use mcp_server::auth::middleware::{BearerAuthLayer, BearerAuthOptions, ScopePolicy};
+use mcp_server::auth::{OAuthProviderError, OAuthTokenVerifier};
+use mcp_server::{AxumHandlerConfig, AxumHandlerState, McpServer, ServerOptions, SessionMode, create_router};
+
+/// `reader` holds `files:read`; `writer` also holds `files:write`.
+#[derive(Clone)]
+struct StaticVerifier;
+
+#[async_trait]
+impl OAuthTokenVerifier for StaticVerifier {
+    async fn verify_access_token(&self, token: &str) -> Result<AuthInfo, OAuthProviderError> {
+        let scopes = match token {
+            "reader" => vec!["files:read"],
+            "writer" => vec!["files:read", "files:write"],
+            _ => return Err(OAuthProviderError::InvalidToken("unknown token".to_string())),
+        };
+        Ok(AuthInfo::new(token).with_scopes(scopes.into_iter().map(String::from).collect()))
+    }
+}
+
+fn router() -> axum::Router {
+    let mut server = McpServer::new(support::implementation("files-server"), ServerOptions::default());
+    server
+        .register_tool(support::tool("write_file"), |_args: Option<Value>, ctx: RequestContext| async move {
+            let token = ctx.auth_info.map(|info| info.token).unwrap_or_default();
+            Ok(CallToolResult {
+                content: vec![ContentBlock::Text(TextContent::new(format!("written by {token}")))],
+                structured_content: None,
+                is_error: None,
+                meta: None,
+            })
+        })
+        .expect("register write_file");
+
+    let config = AxumHandlerConfig {
+        session_mode: SessionMode::Stateless,
+        ..Default::default()
+    };
+    let options = BearerAuthOptions::new()
+        .with_resource_metadata_url("https://files.example.com/.well-known/oauth-protected-resource")
+        .with_scope_policy(
+            ScopePolicy::new()
+                .require_for_method("tools/list", ["files:read"])
+                .require_for_tool("write_file", ["files:write"]),
+        );
+    create_router(Arc::new(AxumHandlerState::new(Arc::new(server), config)))
+        .layer(BearerAuthLayer::with_options(Arc::new(StaticVerifier), options))
+}
+
+async fn post(token: &str, body: Value) -> axum::response::Response {
+    let request = Request::builder()
+        .method("POST")
+        .uri("/mcp")
+        .header(header::CONTENT_TYPE, "application/json")
+        .header(header::ACCEPT, "application/json, text/event-stream")
+        .header(header::AUTHORIZATION, format!("Bearer {token}"))
+        .body(Body::from(body.to_string()))
+        .unwrap();
+    router().oneshot(request).await.unwrap()
+}
+
+async fn json_body(response: axum::response::Response) -> Value {
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    serde_json::from_slice(&bytes).unwrap()
+}
+
+fn call_write_file() -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "id": 2,
+        "method": "tools/call",
+        "params": { "name": "write_file", "arguments": {} }
+    })
+}
+
+#[tokio::test]
+async fn read_scope_can_list_tools() {
+    let response = post("reader", json!({ "jsonrpc": "2.0", "id": 1, "method": "tools/list" })).await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = json_body(response).await;
+    assert_eq!(body["result"]["tools"][0]["name"], "write_file");
+}
+
+#[tokio::test]
+async fn read_scope_is_forbidden_from_write_tool() {
+    let response = post("reader", call_write_file()).await;
+
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    let challenge = response.headers()[header::WWW_AUTHENTICATE].to_str().unwrap().to_string();
+    assert!(challenge.starts_with("Bearer error=\"insufficient_scope\""), "{challenge}");
+    assert!(challenge.contains("scope=\"files:write\""), "{challenge}");
+    assert!(challenge.contains("resource_metadata=\"https://files.example.com/"), "{challenge}");
+    assert_eq!(json_body(response).await["error"], "insufficient_scope");
+}
+
+#[tokio::test]
+async fn write_scope_reaches_tool_with_auth_info() {
+    let response = post("writer", call_write_file()).await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = json_body(response).await;
+    assert_eq!(body["result"]["content"][0]["text"], "written by writer");
+}
+
+#[tokio::test]
+async fn batch_needs_every_scope() {
+    let batch = json!([
+        { "jsonrpc": "2.0", "id": 1, "method": "tools/list" },
+        call_write_file()
+    ]);
+
+    let response = post("reader", batch).await;
+
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+}
@@ -0,0 +1,142 @@
+#![cfg(feature = "axum")]
+
+mod support;
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use axum::body::Body;
+use axum::http::{Request, header};
+use serde_json::{Value, json};
+use tower::util::ServiceExt;
+
+use mcp_core::protocol::RequestContext;
+use mcp_core::types::{CallToolResult, ContentBlock, RequestMessage, TextContent};
+use mcp_server::{AxumHandlerConfig, AxumHandlerState, McpServer, ServerOptions, create_router};
+
+/// Per-session call counter kept in the typed session store.
+#[derive(Default)]
+struct CallCount(AtomicU64);
+
+fn counter_server() -> Arc<McpServer> {
+    let mut server = McpServer::new(
+        support::implementation("session-server"),
+        ServerOptions::default(),
+    );
+    let tool = support::tool("count");
+    server
+        .register_tool(tool, |_args, ctx: RequestContext| async move {
+            let data = ctx.session_data();
+            let count = data
+                .get_or_insert_with(CallCount::default)
+                .0
+                .fetch_add(1, Ordering::SeqCst)
+                + 1;
+            data.set_value("last", json!(count));
+            Ok(CallToolResult {
+                content: vec![ContentBlock::Text(TextContent::new(count.to_string()))],
+                structured_content: None,
+                is_error: None,
+                meta: None,
+            })
+        })
+        .expect("register tool");
+    Arc::new(server)
+}
+
+/// POST a JSON-RPC request, returning the response body and session id.
+async fn post(
+    app: &axum::Router,
+    session_id: Option<&str>,
+    body: Value,
+) -> (Value, Option<String>) {
+    let mut request = Request::builder()
+        .method("POST")
+        .uri("/mcp")
+        .header(header::CONTENT_TYPE, "application/json")
+        .header(header::ACCEPT, "application/json, text/event-stream");
+    if let Some(session_id) = session_id {
+        request = request.header("mcp-session-id", session_id);
+    }
+    let request = request.body(Body::from(body.to_string())).unwrap();
+
+    let response = app.clone().oneshot(request).await.unwrap();
+    let session_id = response
+        .headers()
+        .get("mcp-session-id")
+        .map(|v| v.to_str().unwrap().to_string());
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    (serde_json::from_slice(&bytes).unwrap(), session_id)
+}
+
+async fn open_session(app: &axum::Router) -> String {
+    let (_, session_id) = post(
+        app,
+        None,
+        json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "initialize",
+            "params": {
+                "protocolVersion": "2025-03-26",
+                "capabilities": {},
+                "clientInfo": { "name": "test-client", "version": "0.1.0" }
+            }
+        }),
+    )
+    .await;
+    session_id.expect("new session id")
+}
+
+async fn call_count(app: &axum::Router, session_id: &str) -> String {
+    let (body, _) = post(
+        app,
+        Some(session_id),
+        json!({ "jsonrpc": "2.0", "id": 2, "method": "tools/call", "params": { "name": "count" } }),
+    )
+    .await;
+    body["result"]["content"][0]["text"]
+        .as_str()
+        .unwrap()
+        .to_string()
+}
+
+#[tokio::test]
+async fn http_sessions_have_isolated_data() {
+    let state = Arc::new(AxumHandlerState::new(
+        counter_server(),
+        AxumHandlerConfig::default(),
+    ));
+    let app = create_router(state.clone());
+
+    let first = open_session(&app).await;
+    let second = open_session(&app).await;
+    assert_ne!(first, second);
+
+    assert_eq!(call_count(&app, &first).await, "1");
+    assert_eq!(call_count(&app, &first).await, "2");
+    assert_eq!(call_count(&app, &second).await, "1");
+    assert_eq!(call_count(&app, &first).await, "3");
+
+    let session = state.session_manager().get_session(&second).await.unwrap();
+    assert_eq!(session.session_data.get_value("last"), Some(json!(1)));
+}
+
+#[tokio::test]
+async fn requests_without_session_share_process_data() {
+    let server = counter_server();
+    for expected in 1..=2 {
+        let request = RequestMessage::new("call", "tools/call", json!({ "name": "count" }));
+        let response = server.server().handle_request(request, None).await.unwrap();
+        assert_eq!(
+            response.result.unwrap()["content"][0]["text"],
+            json!(expected.to_string())
+        );
+    }
+    assert_eq!(
+        server.server().session_data().get_value("last"),
+        Some(json!(2))
+    );
+}
@@ -0,0 +1,212 @@
+//! Stateless Streamable HTTP mode.
+//!
+//! Simulates a load-balanced deployment: every request is sent to a different
+//! handler instance, each with its own server, and still succeeds.
+
+#![cfg(feature = "axum")]
+
+mod support;
+
+use std::sync::Arc;
+
+use axum::body::Body;
+use axum::http::{Request, StatusCode, header};
+use axum::response::Response;
+use futures::executor::block_on;
+use serde_json::{Value, json};
+use tower::util::ServiceExt;
+
+use mcp_core::protocol::{ProtocolError, RequestContext};
+use mcp_core::types::{CallToolResult, ContentBlock, CreateMessageRequestParams, SamplingMessage, TextContent};
+use mcp_server::{
+    AxumHandlerConfig, AxumHandlerState, HttpResponse, HttpServerHandler, HttpServerOptions,
+    McpServer, ServerOptions, SessionMode, create_router,
+};
+
+fn text_result(text: String) -> CallToolResult {
+    CallToolResult {
+        content: vec![ContentBlock::Text(TextContent::new(text))],
+        structured_content: None,
+        is_error: None,
+        meta: None,
+    }
+}
+
+/// Server with an `add` tool and an `ask` tool that tries to sample from the client.
+fn server() -> Arc<McpServer> {
+    let mut server = McpServer::new(support::implementation("stateless-server"), ServerOptions::default());
+    server
+        .register_tool(support::tool("add"), |args: Option<Value>, _ctx: RequestContext| async move {
+            let args = args.unwrap_or_default();
+            let a = args.get("a").and_then(Value::as_i64).unwrap_or(0);
+            let b = args.get("b").and_then(Value::as_i64).unwrap_or(0);
+            Ok(text_result((a + b).to_string()))
+        })
+        .expect("register add");
+    server
+        .register_tool(support::tool("ask"), |_args, ctx: RequestContext| async move {
+            let params = CreateMessageRequestParams::new(
+                vec![SamplingMessage::user(TextContent::new("2 + 2?"))],
+                16,
+            );
+            let text = match ctx.create_message(params).await {
+                Ok(_) => "sampled".to_string(),
+                Err(ProtocolError::Capability(message)) => format!("unsupported: {message}"),
+                Err(err) => format!("error: {err}"),
+            };
+            Ok(text_result(text))
+        })
+        .expect("register ask");
+    Arc::new(server)
+}
+
+fn stateless_node() -> axum::Router {
+    let config = AxumHandlerConfig {
+        session_mode: SessionMode::Stateless,
+        ..Default::default()
+    };
+    create_router(Arc::new(AxumHandlerState::new(server(), config)))
+}
+
+async fn post(node: &axum::Router, body: Value) -> Response {
+    let request = Request::builder()
+        .method("POST")
+        .uri("/mcp")
+        .header(header::CONTENT_TYPE, "application/json")
+        .body(Body::from(body.to_string()))
+        .unwrap();
+    node.clone().oneshot(request).await.unwrap()
+}
+
+async fn json_body(response: Response) -> Value {
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    serde_json::from_slice(&bytes).unwrap()
+}
+
+fn initialize(id: u64) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "method": "initialize",
+        "params": {
+            "protocolVersion": "2025-06-18",
+            "capabilities": { "sampling": {} },
+            "clientInfo": { "name": "test-client", "version": "0.1.0" }
+        }
+    })
+}
+
+fn call_tool(id: u64, name: &str, arguments: Value) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "method": "tools/call",
+        "params": { "name": name, "arguments": arguments }
+    })
+}
+
+#[tokio::test]
+async fn stateless_requests_work_on_any_node() {
+    let nodes = [stateless_node(), stateless_node(), stateless_node()];
+
+    let response = post(&nodes[0], initialize(1)).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert!(response.headers().get("mcp-session-id").is_none());
+    let body = json_body(response).await;
+    assert_eq!(body["result"]["serverInfo"]["name"], "stateless-server");
+
+    let initialized = json!({ "jsonrpc": "2.0", "method": "notifications/initialized" });
+    let response = post(&nodes[1], initialized).await;
+    assert_eq!(response.status(), StatusCode::ACCEPTED);
+
+    // No session header is sent, and none of the other nodes saw initialize
+    let response = post(&nodes[2], call_tool(2, "add", json!({ "a": 2, "b": 3 }))).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert!(response.headers().get("mcp-session-id").is_none());
+    let body = json_body(response).await;
+    assert_eq!(body["result"]["content"][0]["text"], "5");
+
+    let list = json!({ "jsonrpc": "2.0", "id": 3, "method": "tools/list" });
+    let body = json_body(post(&nodes[0], list).await).await;
+    assert_eq!(body["result"]["tools"].as_array().unwrap().len(), 2);
+}
+
+#[tokio::test]
+async fn stateless_mode_rejects_server_initiated_requests() {
+    let node = stateless_node();
+
+    // Even a client that declared sampling cannot be reached without a stream
+    post(&node, initialize(1)).await;
+    let body = json_body(post(&node, call_tool(2, "ask", json!({}))).await).await;
+    let text = body["result"]["content"][0]["text"].as_str().unwrap();
+    assert!(text.starts_with("unsupported: sampling/createMessage"), "{text}");
+}
+
+#[tokio::test]
+async fn stateless_mode_ignores_session_header_and_disables_get_and_delete() {
+    let node = stateless_node();
+
+    let request = Request::builder()
+        .method("POST")
+        .uri("/mcp")
+        .header(header::CONTENT_TYPE, "application/json")
+        .header("mcp-session-id", "from-another-node")
+        .body(Body::from(call_tool(1, "add", json!({ "a": 1, "b": 1 })).to_string()))
+        .unwrap();
+    let response = node.clone().oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let request = Request::builder()
+        .method("GET")
+        .uri("/mcp")
+        .header(header::ACCEPT, "text/event-stream")
+        .body(Body::empty())
+        .unwrap();
+    let response = node.clone().oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::METHOD_NOT_ALLOWED);
+
+    let request = Request::builder()
+        .method("DELETE")
+        .uri("/mcp")
+        .header("mcp-session-id", "from-another-node")
+        .body(Body::empty())
+        .unwrap();
+    let response = node.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::METHOD_NOT_ALLOWED);
+}
+
+#[test]
+fn http_server_handler_stateless_mode() {
+    let options = HttpServerOptions {
+        session_mode: SessionMode::Stateless,
+        ..Default::default()
+    };
+    let handlers: Vec<HttpServerHandler> =
+        (0..3).map(|_| HttpServerHandler::new(server(), options.clone())).collect();
+
+    let bodies = [
+        initialize(1),
+        call_tool(2, "add", json!({ "a": 20, "b": 22 })),
+        call_tool(3, "add", json!({ "a": 1, "b": 2 })),
+    ];
+    let mut texts = Vec::new();
+    for (handler, body) in handlers.iter().zip(bodies) {
+        let body = serde_json::to_vec(&body).unwrap();
+        match handler.handle_post(None, Some("application/json"), &body) {
+            HttpResponse::Json { status, body, session_id } => {
+                assert_eq!(status, 200);
+                assert!(session_id.is_none());
+                let body: Value = serde_json::from_str(&body).unwrap();
+                texts.push(body["result"]["content"][0]["text"].clone());
+            }
+            _ => panic!("Expected JSON response"),
+        }
+        assert_eq!(block_on(handler.session_manager().session_count()), 0);
+    }
+    assert_eq!(texts[1..], [json!("42"), json!("3")]);
+
+    match handlers[0].handle_get(None, None, Some("text/event-stream")) {
+        HttpResponse::Error { status, .. } => assert_eq!(status, 405),
+        _ => panic!("Expected error response"),
+    }
+}
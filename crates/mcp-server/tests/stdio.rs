@@ -0,0 +1,319 @@
+//! The stdio run loop, driven over in-memory pipes.
+
+mod support;
+
+use std::io::{BufRead, BufReader, Lines, PipeReader, PipeWriter, Write};
+use std::sync::Arc;
+use std::thread;
+
+use serde_json::{Value, json};
+
+use mcp_core::protocol::RequestContext;
+use mcp_core::types::{CallToolResult, ErrorCode, LATEST_PROTOCOL_VERSION};
+use mcp_server::{McpServer, ServerError, StdioOptions, stdio};
+
+/// A tool that logs to the client and then asks it for its roots.
+fn roots_server() -> Arc<McpServer> {
+    let server = McpServer::builder("stdio-server", "0.1.0")
+        .tool(
+            support::tool("list_roots"),
+            |_args, context: RequestContext| async move {
+                let peer = context.peer.expect("peer");
+                peer.notify(
+                    "notifications/message",
+                    json!({ "level": "info", "data": "listing roots" }),
+                )?;
+                let roots = peer.request("roots/list", json!({})).await?;
+                Ok(CallToolResult {
+                    structured_content: Some(roots),
+                    ..Default::default()
+                })
+            },
+        )
+        .build()
+        .unwrap();
+    Arc::new(server)
+}
+
+fn send(writer: &mut PipeWriter, message: Value) {
+    writeln!(writer, "{message}").unwrap();
+}
+
+fn receive(lines: &mut Lines<BufReader<PipeReader>>) -> Value {
+    serde_json::from_str(&lines.next().expect("line").unwrap()).unwrap()
+}
+
+#[test]
+fn run_with_multiplexes_server_initiated_messages() {
+    let (server_reader, mut client_writer) = std::io::pipe().unwrap();
+    let (client_reader, server_writer) = std::io::pipe().unwrap();
+    let server = roots_server();
+    let running = thread::spawn(move || {
+        stdio::run_with(
+            server,
+            StdioOptions::default(),
+            BufReader::new(server_reader),
+            server_writer,
+        )
+    });
+    let mut lines = BufReader::new(client_reader).lines();
+
+    send(
+        &mut client_writer,
+        json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "initialize",
+            "params": {
+                "protocolVersion": LATEST_PROTOCOL_VERSION,
+                "capabilities": { "roots": {} },
+                "clientInfo": { "name": "client", "version": "0.1.0" }
+            }
+        }),
+    );
+    let initialized = receive(&mut lines);
+    assert_eq!(initialized["id"], 1);
+    assert_eq!(initialized["result"]["serverInfo"]["name"], "stdio-server");
+
+    send(
+        &mut client_writer,
+        json!({
+            "jsonrpc": "2.0",
+            "id": 2,
+            "method": "tools/call",
+            "params": { "name": "list_roots" }
+        }),
+    );
+    let log = receive(&mut lines);
+    assert_eq!(log["method"], "notifications/message");
+    assert_eq!(log["params"]["data"], "listing roots");
+
+    // The handler is still waiting; the read loop must route the answer
+    let roots_request = receive(&mut lines);
+    assert_eq!(roots_request["method"], "roots/list");
+    send(
+        &mut client_writer,
+        json!({
+            "jsonrpc": "2.0",
+            "id": roots_request["id"],
+            "result": { "roots": [{ "uri": "file:///workspace", "name": "workspace" }] }
+        }),
+    );
+
+    let called = receive(&mut lines);
+    assert_eq!(called["id"], 2);
+    assert_eq!(
+        called["result"]["structuredContent"]["roots"][0]["uri"],
+        "file:///workspace"
+    );
+
+    drop(client_writer);
+    running.join().unwrap().unwrap();
+    assert!(lines.next().is_none());
+}
+
+#[test]
+fn run_with_skips_malformed_lines_and_answers_in_flight_requests_at_eof() {
+    let (server_reader, mut client_writer) = std::io::pipe().unwrap();
+    let (client_reader, server_writer) = std::io::pipe().unwrap();
+    let server = roots_server();
+
+    writeln!(client_writer, "not json").unwrap();
+    writeln!(client_writer).unwrap();
+    send(
+        &mut client_writer,
+        json!({ "jsonrpc": "2.0", "id": "list-1", "method": "tools/list" }),
+    );
+    drop(client_writer);
+
+    let result = stdio::run_with(
+        Arc::clone(&server),
+        StdioOptions::default(),
+        BufReader::new(server_reader),
+        server_writer,
+    );
+    assert!(result.is_ok());
+
+    let mut lines = BufReader::new(client_reader).lines();
+    let listed = receive(&mut lines);
+    assert_eq!(listed["id"], "list-1");
+    assert_eq!(listed["result"]["tools"][0]["name"], "list_roots");
+    assert!(lines.next().is_none());
+
+    // The senders are removed once the loop returns
+    assert!(
+        !server
+            .server()
+            .broadcast_notification(server.tool_list_changed_notification())
+    );
+}
+
+#[test]
+fn before_request_runs_ahead_of_dispatch() {
+    let (server_reader, mut client_writer) = std::io::pipe().unwrap();
+    let (client_reader, server_writer) = std::io::pipe().unwrap();
+    let server = roots_server();
+
+    let hook_server = Arc::clone(&server);
+    let options = StdioOptions {
+        before_request: Some(Arc::new(move |request| {
+            let server = Arc::clone(&hook_server);
+            let is_initialize = request.method == "initialize";
+            Box::pin(async move {
+                if is_initialize {
+                    server
+                        .server()
+                        .set_instructions(Some("refreshed".to_string()));
+                }
+            })
+        })),
+    };
+
+    send(
+        &mut client_writer,
+        json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "initialize",
+            "params": {
+                "protocolVersion": LATEST_PROTOCOL_VERSION,
+                "capabilities": {},
+                "clientInfo": { "name": "client", "version": "0.1.0" }
+            }
+        }),
+    );
+    drop(client_writer);
+
+    stdio::run_with(
+        server,
+        options,
+        BufReader::new(server_reader),
+        server_writer,
+    )
+    .unwrap();
+
+    let mut lines = BufReader::new(client_reader).lines();
+    assert_eq!(receive(&mut lines)["result"]["instructions"], "refreshed");
+}
+
+#[test]
+fn write_errors_end_the_loop() {
+    let (server_reader, mut client_writer) = std::io::pipe().unwrap();
+    let (client_reader, server_writer) = std::io::pipe().unwrap();
+    drop(client_reader);
+
+    send(
+        &mut client_writer,
+        json!({ "jsonrpc": "2.0", "id": 1, "method": "tools/list" }),
+    );
+    drop(client_writer);
+
+    let result = stdio::run_with(
+        roots_server(),
+        StdioOptions::default(),
+        BufReader::new(server_reader),
+        server_writer,
+    );
+    assert!(matches!(result, Err(ServerError::Io(_))));
+}
+
+#[test]
+fn ping_is_answered_before_initialization() {
+    let (server_reader, mut client_writer) = std::io::pipe().unwrap();
+    let (client_reader, server_writer) = std::io::pipe().unwrap();
+    let mut responses = BufReader::new(client_reader).lines();
+
+    send(
+        &mut client_writer,
+        json!({ "jsonrpc": "2.0", "id": "ping-1", "method": "ping" }),
+    );
+    drop(client_writer);
+
+    stdio::run_with(
+        roots_server(),
+        StdioOptions::default(),
+        BufReader::new(server_reader),
+        server_writer,
+    )
+    .unwrap();
+
+    let pong = receive(&mut responses);
+    assert_eq!(pong["id"], "ping-1");
+    assert_eq!(pong["result"], json!({}));
+}
+
+#[cfg(feature = "tokio")]
+#[tokio::test]
+async fn run_async_with_routes_client_responses() {
+    use tokio::io::{AsyncBufReadExt, AsyncWriteExt};
+
+    let (client, server_side) = tokio::io::duplex(4096);
+    let (server_reader, server_writer) = tokio::io::split(server_side);
+    let (client_reader, mut client_writer) = tokio::io::split(client);
+    let running = tokio::spawn(stdio::run_async_with(
+        roots_server(),
+        StdioOptions::default(),
+        tokio::io::BufReader::new(server_reader),
+        server_writer,
+    ));
+    let mut lines = tokio::io::BufReader::new(client_reader).lines();
+
+    let call = json!({
+        "jsonrpc": "2.0",
+        "id": 7,
+        "method": "tools/call",
+        "params": { "name": "list_roots" }
+    });
+    client_writer
+        .write_all(format!("{call}\n").as_bytes())
+        .await
+        .unwrap();
+
+    let mut next = async || -> Value {
+        serde_json::from_str(&lines.next_line().await.unwrap().expect("line")).unwrap()
+    };
+    assert_eq!(next().await["method"], "notifications/message");
+    let roots_request = next().await;
+    assert_eq!(roots_request["method"], "roots/list");
+    let answer = json!({
+        "jsonrpc": "2.0",
+        "id": roots_request["id"],
+        "result": { "roots": [] }
+    });
+    client_writer
+        .write_all(format!("{answer}\n").as_bytes())
+        .await
+        .unwrap();
+
+    let called = next().await;
+    assert_eq!(called["id"], 7);
+    assert_eq!(called["result"]["structuredContent"]["roots"], json!([]));
+
+    client_writer.shutdown().await.unwrap();
+    running.await.unwrap().unwrap();
+}
+
+#[test]
+fn requests_with_null_ids_are_invalid() {
+    let (server_reader, mut client_writer) = std::io::pipe().unwrap();
+    let (client_reader, server_writer) = std::io::pipe().unwrap();
+    let mut responses = BufReader::new(client_reader).lines();
+
+    send(
+        &mut client_writer,
+        json!({ "jsonrpc": "2.0", "id": null, "method": "ping" }),
+    );
+    drop(client_writer);
+
+    stdio::run_with(
+        roots_server(),
+        StdioOptions::default(),
+        BufReader::new(server_reader),
+        server_writer,
+    )
+    .unwrap();
+
+    let rejected = receive(&mut responses);
+    assert_eq!(rejected["id"], Value::Null);
+    assert_eq!(rejected["error"]["code"], ErrorCode::InvalidRequest as i32);
+}
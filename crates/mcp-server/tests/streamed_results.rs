@@ -0,0 +1,260 @@
+mod support;
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::io::{self, Write};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use futures::executor::block_on;
+use serde::Serialize;
+use serde::ser::Serializer;
+use serde_json::{Value, json};
+
+use mcp_core::protocol::{ProtocolOptions, RequestContext, StreamedResult};
+use mcp_core::types::{
+    CallToolResult, CreateTaskResult, GetTaskResult, RequestMessage, TaskResultChunk, TaskStatus,
+};
+use mcp_server::{InMemoryTaskStore, McpServer, ServerOptions};
+
+/// Global allocator tracking live and peak heap bytes.
+struct CountingAllocator;
+
+static CURRENT: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = unsafe { System.alloc(layout) };
+        if !ptr.is_null() {
+            let now = CURRENT.fetch_add(layout.size(), Ordering::SeqCst) + layout.size();
+            PEAK.fetch_max(now, Ordering::SeqCst);
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) };
+        CURRENT.fetch_sub(layout.size(), Ordering::SeqCst);
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+/// Reset the peak to the current usage and return it.
+fn reset_peak() -> usize {
+    let now = CURRENT.load(Ordering::SeqCst);
+    PEAK.store(now, Ordering::SeqCst);
+    now
+}
+
+const MIB: usize = 1024 * 1024;
+/// Number of entries in the synthetic listing; about 50MB of JSON.
+const ENTRIES: u64 = 850_000;
+
+#[derive(Serialize)]
+struct Entry {
+    path: String,
+    size: u64,
+}
+
+/// Tree listing generated while it is serialized.
+struct Listing(u64);
+
+impl Serialize for Listing {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq((0..self.0).map(|i| Entry {
+            path: format!("src/generated/module_{i:07}/mod.rs"),
+            size: i * 7,
+        }))
+    }
+}
+
+fn write_listing(writer: &mut dyn Write, entries: u64) -> io::Result<()> {
+    writer.write_all(
+        br#"{"content":[{"type":"text","text":"tree listing"}],"structuredContent":{"entries":"#,
+    )?;
+    serde_json::to_writer(&mut *writer, &Listing(entries)).map_err(io::Error::from)?;
+    writer.write_all(b"}}")
+}
+
+/// Length and FNV-1a hash of everything written.
+struct Fnv {
+    hash: u64,
+    len: usize,
+}
+
+impl Fnv {
+    fn new() -> Self {
+        Self {
+            hash: 0xcbf29ce484222325,
+            len: 0,
+        }
+    }
+}
+
+impl Write for Fnv {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        for byte in buf {
+            self.hash = (self.hash ^ u64::from(*byte)).wrapping_mul(0x100000001b3);
+        }
+        self.len += buf.len();
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+fn listing_server(entries: u64, max_bytes: u64) -> McpServer {
+    let options = ServerOptions {
+        protocol_options: Some(ProtocolOptions {
+            task_store: Some(Arc::new(InMemoryTaskStore::default())),
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+    let mut server = McpServer::new(support::implementation("streamed-server"), options);
+    let tool = support::tool("tree");
+    server
+        .register_tool(tool, move |_args, ctx: RequestContext| async move {
+            ctx.stream_result(
+                StreamedResult::new(move |writer| write_listing(writer, entries))
+                    .with_max_bytes(max_bytes),
+            )?;
+            Ok(CallToolResult::default())
+        })
+        .expect("register tool");
+    server
+}
+
+fn request(server: &McpServer, method: &str, params: Value) -> Result<Value, String> {
+    let request = RequestMessage::new("1", method, params);
+    let response =
+        block_on(server.server().handle_request(request, None)).map_err(|e| e.to_string())?;
+    match response.error {
+        Some(error) => Err(error.message),
+        None => Ok(response.result.unwrap()),
+    }
+}
+
+fn start_task(server: &McpServer) -> String {
+    let result = request(
+        server,
+        "tools/call",
+        json!({ "name": "tree", "task": { "ttl": 60000 } }),
+    )
+    .unwrap();
+    let created: CreateTaskResult = serde_json::from_value(result).unwrap();
+    created.task.task_id
+}
+
+fn read_range(server: &McpServer, task_id: &str, offset: u64, length: u64) -> TaskResultChunk {
+    let result = request(
+        server,
+        "tasks/result",
+        json!({ "taskId": task_id, "range": { "offset": offset, "length": length } }),
+    )
+    .unwrap();
+    serde_json::from_value(result).unwrap()
+}
+
+#[test]
+fn large_streamed_result_stays_within_memory_ceiling() {
+    let server = listing_server(ENTRIES, u64::MAX);
+
+    let before = reset_peak();
+    let task_id = start_task(&server);
+    let retained = CURRENT.load(Ordering::SeqCst);
+    let peak = PEAK.load(Ordering::SeqCst);
+
+    let mut expected = Fnv::new();
+    write_listing(&mut expected, ENTRIES).unwrap();
+    assert!(
+        expected.len >= 50_000_000,
+        "payload is {} bytes",
+        expected.len
+    );
+
+    // Only the serialized bytes are kept, and writing them needs no more
+    // than a chunk on top, where a Value plus String would need several
+    // times the payload.
+    let stored = retained - before;
+    assert!(stored - expected.len < MIB, "stored {stored} bytes");
+    assert!(
+        peak - retained < 2 * MIB,
+        "transient peak {} bytes",
+        peak - retained
+    );
+
+    // Read it back in 4 MiB ranges; each read only touches its own range.
+    let mut actual = Fnv::new();
+    let mut offset = 0;
+    loop {
+        let before = reset_peak();
+        let chunk = read_range(&server, &task_id, offset, 4 * MIB as u64);
+        let peak = PEAK.load(Ordering::SeqCst);
+        assert!(
+            peak - before < 16 * MIB,
+            "range read peak {} bytes",
+            peak - before
+        );
+
+        assert_eq!(chunk.offset, offset);
+        assert_eq!(chunk.total_bytes, expected.len as u64);
+        actual.write_all(chunk.data.as_bytes()).unwrap();
+        match chunk.next_offset {
+            Some(next) => offset = next,
+            None => break,
+        }
+    }
+    assert_eq!(actual.len, expected.len);
+    assert_eq!(actual.hash, expected.hash);
+
+    let status = request(&server, "tasks/get", json!({ "taskId": task_id })).unwrap();
+    let status: GetTaskResult = serde_json::from_value(status).unwrap();
+    assert_eq!(status.task.status, TaskStatus::Completed);
+}
+
+#[test]
+fn streamed_result_is_readable_without_range() {
+    let server = listing_server(3, u64::MAX);
+    let task_id = start_task(&server);
+
+    let result = request(&server, "tasks/result", json!({ "taskId": task_id })).unwrap();
+    let result: CallToolResult = serde_json::from_value(result).unwrap();
+    let entries = &result.structured_content.unwrap()["entries"];
+    assert_eq!(
+        entries[2],
+        json!({ "path": "src/generated/module_0000002/mod.rs", "size": 14 })
+    );
+
+    let chunk = read_range(&server, &task_id, 0, 11);
+    assert_eq!(chunk.data, r#"{"content":"#);
+    assert_eq!(chunk.next_offset, Some(11));
+}
+
+#[test]
+fn streamed_result_over_cap_fails_task() {
+    let server = listing_server(1000, 1024);
+    let task_id = start_task(&server);
+
+    let status = request(&server, "tasks/get", json!({ "taskId": task_id })).unwrap();
+    let status: GetTaskResult = serde_json::from_value(status).unwrap();
+    assert_eq!(status.task.status, TaskStatus::Failed);
+    assert_eq!(
+        status.task.status_message.as_deref(),
+        Some("handler failed: streamed result exceeds 1024 bytes")
+    );
+
+    let err = request(&server, "tasks/result", json!({ "taskId": task_id })).unwrap_err();
+    assert!(err.contains("exceeds 1024 bytes"), "{err}");
+}
+
+#[test]
+fn stream_result_requires_task() {
+    let server = listing_server(1, u64::MAX);
+    let err = request(&server, "tools/call", json!({ "name": "tree" })).unwrap_err();
+    assert!(err.contains("task-augmented"), "{err}");
+}
@@ -0,0 +1,165 @@
+mod support;
+
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use futures::executor::block_on;
+use serde_json::{Value, json};
+
+use mcp_core::types::{CallToolResult, ContentBlock, RequestMessage, TextContent};
+use mcp_server::{McpServer, ServerOptions, SummaryClock, SummaryKind};
+
+/// Clock that only moves when a tool handler advances it.
+fn manual_clock() -> (Arc<Mutex<Instant>>, SummaryClock) {
+    let now = Arc::new(Mutex::new(Instant::now()));
+    let clock_now = Arc::clone(&now);
+    let clock: SummaryClock = Arc::new(move || *clock_now.lock().unwrap());
+    (now, clock)
+}
+
+/// Server with a `sleep` tool that advances the clock by `ms` and a `fail`
+/// tool that returns an error result.
+fn summary_server(options: ServerOptions, now: Arc<Mutex<Instant>>) -> McpServer {
+    let mut server = McpServer::new(support::implementation("summary-server"), options);
+    server
+        .register_tool(
+            support::tool("sleep"),
+            move |args: Option<Value>, _ctx: mcp_core::protocol::RequestContext| {
+                let now = Arc::clone(&now);
+                async move {
+                    let ms = args
+                        .as_ref()
+                        .and_then(|args| args.get("ms"))
+                        .and_then(Value::as_u64)
+                        .unwrap_or(0);
+                    *now.lock().unwrap() += Duration::from_millis(ms);
+                    Ok(CallToolResult {
+                        content: vec![ContentBlock::Text(TextContent::new("x".repeat(ms as usize)))],
+                        structured_content: None,
+                        is_error: None,
+                        meta: None,
+                    })
+                }
+            },
+        )
+        .expect("register sleep");
+    server
+        .register_tool(
+            support::tool("fail"),
+            |_args, _ctx: mcp_core::protocol::RequestContext| async move {
+                Ok(CallToolResult {
+                    content: vec![ContentBlock::Text(TextContent::new("failed"))],
+                    structured_content: None,
+                    is_error: Some(true),
+                    meta: None,
+                })
+            },
+        )
+        .expect("register fail");
+    server
+}
+
+fn call(server: &McpServer, id: usize, name: &str, arguments: Value) {
+    let request = RequestMessage::new(
+        id.to_string(),
+        "tools/call",
+        json!({ "name": name, "arguments": arguments }),
+    );
+    block_on(server.server().handle_request(request, None)).expect("tools/call response");
+}
+
+#[test]
+fn summary_counts_methods_and_tools() {
+    let (now, clock) = manual_clock();
+    let options = ServerOptions {
+        summary_clock: Some(clock),
+        ..Default::default()
+    };
+    let server = summary_server(options, Arc::clone(&now));
+
+    for id in 0..3 {
+        let request = RequestMessage::new(format!("list-{id}"), "tools/list", json!({}));
+        block_on(server.server().handle_request(request, None)).expect("tools/list response");
+    }
+    // 1ms..=100ms, one request each
+    for ms in 1..=100 {
+        call(&server, ms, "sleep", json!({ "ms": ms }));
+    }
+    call(&server, 200, "fail", json!({}));
+    let unknown = RequestMessage::new("unknown", "does/not/exist", json!({}));
+    block_on(server.server().handle_request(unknown, None)).expect("error response");
+
+    let report = server.summary_report();
+    assert_eq!(report.total_requests(), 105);
+    assert_eq!(report.uptime, Duration::from_millis(5050));
+
+    let list = report
+        .row("stdio", SummaryKind::Method, "tools/list")
+        .expect("tools/list row");
+    assert_eq!((list.count, list.errors), (3, 0));
+    assert_eq!(list.p99, Duration::ZERO);
+
+    let calls = report
+        .row("stdio", SummaryKind::Method, "tools/call")
+        .expect("tools/call row");
+    assert_eq!((calls.count, calls.errors), (101, 1));
+
+    let sleep = report
+        .row("stdio", SummaryKind::Tool, "sleep")
+        .expect("sleep row");
+    assert_eq!((sleep.count, sleep.errors), (100, 0));
+    // Buckets are at most 1/16 wider than the values they hold
+    assert!(sleep.p50 >= Duration::from_millis(50) && sleep.p50 <= Duration::from_millis(54));
+    assert!(sleep.p95 >= Duration::from_millis(95) && sleep.p95 <= Duration::from_millis(100));
+    assert!(sleep.p99 >= Duration::from_millis(99) && sleep.p99 <= Duration::from_millis(100));
+    assert_eq!(sleep.max_latency, Duration::from_millis(100));
+    assert!(sleep.max_response_bytes > 100);
+    assert!(sleep.max_request_bytes > 0);
+
+    let fail = report
+        .row("stdio", SummaryKind::Tool, "fail")
+        .expect("fail row");
+    assert_eq!((fail.count, fail.errors), (1, 1));
+
+    let unknown = report
+        .row("stdio", SummaryKind::Method, "does/not/exist")
+        .expect("unknown method row");
+    assert_eq!((unknown.count, unknown.errors), (1, 1));
+}
+
+#[test]
+fn summary_report_renders_aligned_table() {
+    let (now, clock) = manual_clock();
+    let options = ServerOptions {
+        summary_clock: Some(clock),
+        ..Default::default()
+    };
+    let server = summary_server(options, Arc::clone(&now));
+    call(&server, 1, "sleep", json!({ "ms": 1500 }));
+    call(&server, 2, "fail", json!({}));
+
+    let output = server.summary_report().to_string();
+    let lines: Vec<&str> = output.lines().collect();
+    assert_eq!(lines[0], "MCP request summary: 2 requests in 1.50s");
+    assert!(lines[1].starts_with("TRANSPORT"));
+    assert_eq!(lines.len(), 5, "header, tools/call, fail and sleep rows");
+    let width = lines[1].chars().count();
+    assert!(lines[2..].iter().all(|line| line.chars().count() == width));
+    assert!(lines[4].contains("sleep") && lines[4].contains("1.50s"));
+}
+
+#[test]
+fn summary_disabled_reports_nothing() {
+    let (now, clock) = manual_clock();
+    let options = ServerOptions {
+        collect_summary: false,
+        summary_clock: Some(clock),
+        ..Default::default()
+    };
+    let server = summary_server(options, now);
+    call(&server, 1, "sleep", json!({ "ms": 5 }));
+
+    let report = server.summary_report();
+    assert!(report.rows.is_empty());
+    assert_eq!(report.total_requests(), 0);
+}
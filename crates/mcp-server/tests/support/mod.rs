@@ -1,14 +1,37 @@
-use mcp_core::types::{BaseMetadata, Icons, Implementation};
-
-pub fn implementation(name: &str) -> Implementation {
-    Implementation {
-        base: BaseMetadata {
-            name: name.to_string(),
-            title: None,
-        },
-        icons: Icons { icons: None },
-        version: "0.1.0".to_string(),
-        website_url: None,
-        description: None,
-    }
-}
+//! Fixtures shared by the integration tests. Each test crate uses only some
+//! of them.
+#![allow(dead_code)]
+
+use serde_json::json;
+
+use mcp_core::types::{BaseMetadata, Icons, Implementation, Tool};
+
+pub fn implementation(name: &str) -> Implementation {
+    Implementation {
+        base: BaseMetadata {
+            name: name.to_string(),
+            title: None,
+        },
+        icons: Icons { icons: None },
+        version: "0.1.0".to_string(),
+        website_url: None,
+        description: None,
+    }
+}
+
+/// A tool taking any object, with no description.
+pub fn tool(name: &str) -> Tool {
+    Tool {
+        base: BaseMetadata {
+            name: name.to_string(),
+            title: None,
+        },
+        icons: Icons { icons: None },
+        description: None,
+        input_schema: json!({ "type": "object" }),
+        output_schema: None,
+        annotations: None,
+        execution: None,
+        meta: None,
+    }
+}
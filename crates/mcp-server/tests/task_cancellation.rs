@@ -0,0 +1,177 @@
+#![cfg(feature = "tokio")]
+
+mod support;
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+use serde_json::json;
+
+use mcp_core::protocol::{ProtocolOptions, RequestContext};
+use mcp_core::types::{
+    CallToolRequestParams, CallToolResult, CancelTaskResult, ContentBlock, CreateTaskResult,
+    GetTaskResult, RequestMessage, RequestParams, TaskMetadata, TaskStatus, TextContent,
+};
+use mcp_server::{InMemoryTaskStore, McpServer, ServerOptions, tokio_task_spawner};
+
+fn task_server(grace_period: Duration) -> McpServer {
+    let options = ServerOptions {
+        protocol_options: Some(ProtocolOptions {
+            task_store: Some(Arc::new(InMemoryTaskStore::default())),
+            task_spawner: Some(tokio_task_spawner()),
+            task_cancel_grace_period: Some(grace_period),
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+    McpServer::new(support::implementation("task-cancel-server"), options)
+}
+
+async fn start_task(server: &McpServer) -> String {
+    let params = CallToolRequestParams {
+        base: RequestParams { meta: None },
+        name: "sleep".to_string(),
+        arguments: None,
+        task: Some(TaskMetadata { ttl: Some(60_000) }),
+    };
+    let request = RequestMessage::new("call", "tools/call", serde_json::to_value(params).unwrap());
+    let response = server
+        .server()
+        .handle_request(request, None)
+        .await
+        .expect("tools/call response");
+    let created: CreateTaskResult = serde_json::from_value(response.result.unwrap()).unwrap();
+    created.task.task_id
+}
+
+async fn cancel_task(server: &McpServer, task_id: &str) -> CancelTaskResult {
+    let request = RequestMessage::new("cancel", "tasks/cancel", json!({ "taskId": task_id }));
+    let response = server
+        .server()
+        .handle_request(request, None)
+        .await
+        .expect("tasks/cancel response");
+    serde_json::from_value(response.result.unwrap()).unwrap()
+}
+
+#[tokio::test]
+async fn cancel_aborts_running_task() {
+    let mut server = task_server(Duration::from_millis(50));
+    server
+        .register_tool(
+            support::tool("sleep"),
+            |_args, _ctx: RequestContext| async move {
+                tokio::time::sleep(Duration::from_secs(30)).await;
+                Ok(CallToolResult {
+                    content: vec![ContentBlock::Text(TextContent::new("woke up"))],
+                    structured_content: None,
+                    is_error: None,
+                    meta: None,
+                })
+            },
+        )
+        .expect("register tool");
+
+    let metrics = tokio::runtime::Handle::current().metrics();
+    let baseline = metrics.num_alive_tasks();
+
+    let task_id = start_task(&server).await;
+    assert_eq!(metrics.num_alive_tasks(), baseline + 1);
+
+    let cancelled = cancel_task(&server, &task_id).await;
+    assert_eq!(cancelled.task.status, TaskStatus::Cancelled);
+
+    tokio::time::timeout(Duration::from_secs(5), async {
+        while metrics.num_alive_tasks() > baseline {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    })
+    .await
+    .expect("task future should be aborted");
+
+    let request = RequestMessage::new("result", "tasks/result", json!({ "taskId": task_id }));
+    let response = server
+        .server()
+        .handle_request(request, None)
+        .await
+        .expect("tasks/result response");
+    assert!(response.result.is_none());
+    assert!(response.error.is_some());
+}
+
+#[tokio::test]
+async fn cancel_trips_token_before_abort() {
+    let cleaned_up = Arc::new(AtomicBool::new(false));
+    let mut server = task_server(Duration::from_secs(5));
+    let flag = cleaned_up.clone();
+    server
+        .register_tool(support::tool("sleep"), move |_args, ctx: RequestContext| {
+            let flag = flag.clone();
+            async move {
+                if let Some(token) = ctx.options.cancel_token.as_ref() {
+                    token.cancelled().await;
+                }
+                flag.store(true, Ordering::SeqCst);
+                Ok(CallToolResult {
+                    content: vec![ContentBlock::Text(TextContent::new("cleaned up"))],
+                    structured_content: None,
+                    is_error: None,
+                    meta: None,
+                })
+            }
+        })
+        .expect("register tool");
+
+    let task_id = start_task(&server).await;
+    let cancelled = cancel_task(&server, &task_id).await;
+    assert_eq!(cancelled.task.status, TaskStatus::Cancelled);
+    assert!(cleaned_up.load(Ordering::SeqCst));
+
+    // The late result must not overwrite the cancelled status.
+    let request = RequestMessage::new("get", "tasks/get", json!({ "taskId": task_id }));
+    let response = server
+        .server()
+        .handle_request(request, None)
+        .await
+        .expect("tasks/get response");
+    let task: GetTaskResult = serde_json::from_value(response.result.unwrap()).unwrap();
+    assert_eq!(task.task.status, TaskStatus::Cancelled);
+}
+
+
+#[tokio::test]
+async fn cancel_propagates_to_child_tokens() {
+    let subtask_stopped = Arc::new(AtomicBool::new(false));
+    let mut server = task_server(Duration::from_secs(5));
+    let flag = subtask_stopped.clone();
+    server
+        .register_tool(support::tool("sleep"), move |_args, ctx: RequestContext| {
+            let flag = flag.clone();
+            async move {
+                // A subtask with its own token, derived from the task's
+                let child = ctx.options.cancel_token.as_ref().map(|token| token.child());
+                let subtask = tokio::spawn(async move {
+                    if let Some(child) = child {
+                        child.cancelled().await;
+                    }
+                    flag.store(true, Ordering::SeqCst);
+                });
+                let _ = subtask.await;
+                Ok(CallToolResult {
+                    content: vec![ContentBlock::Text(TextContent::new("subtask stopped"))],
+                    structured_content: None,
+                    is_error: None,
+                    meta: None,
+                })
+            }
+        })
+        .expect("register tool");
+
+    let task_id = start_task(&server).await;
+    assert!(!subtask_stopped.load(Ordering::SeqCst));
+
+    let cancelled = cancel_task(&server, &task_id).await;
+    assert_eq!(cancelled.task.status, TaskStatus::Cancelled);
+    assert!(subtask_stopped.load(Ordering::SeqCst));
+}
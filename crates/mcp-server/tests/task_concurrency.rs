@@ -0,0 +1,159 @@
+#![cfg(feature = "tokio")]
+
+mod support;
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
+use serde_json::json;
+
+use mcp_core::protocol::{ProtocolOptions, QUEUED_STATUS_MESSAGE, RequestContext};
+use mcp_core::types::{
+    CallToolRequestParams, CallToolResult, CancelTaskResult, ContentBlock, CreateTaskResult,
+    GetTaskResult, RequestMessage, RequestParams, TaskMetadata, TaskStatus, TextContent,
+};
+use mcp_server::{InMemoryTaskStore, McpServer, ServerOptions, tokio_task_spawner};
+
+#[derive(Default)]
+struct Gauge {
+    running: AtomicUsize,
+    peak: AtomicUsize,
+    started: AtomicUsize,
+}
+
+fn limited_server(limit: usize, gauge: Arc<Gauge>) -> McpServer {
+    let options = ServerOptions {
+        protocol_options: Some(ProtocolOptions {
+            task_store: Some(Arc::new(InMemoryTaskStore::default())),
+            task_spawner: Some(tokio_task_spawner()),
+            ..Default::default()
+        }),
+        max_concurrent_tasks: Some(limit),
+        ..Default::default()
+    };
+    let mut server = McpServer::new(support::implementation("task-limit-server"), options);
+    server
+        .register_tool(support::tool("slow"), move |_args, _ctx: RequestContext| {
+            let gauge = gauge.clone();
+            async move {
+                gauge.started.fetch_add(1, Ordering::SeqCst);
+                let running = gauge.running.fetch_add(1, Ordering::SeqCst) + 1;
+                gauge.peak.fetch_max(running, Ordering::SeqCst);
+                tokio::time::sleep(Duration::from_millis(100)).await;
+                gauge.running.fetch_sub(1, Ordering::SeqCst);
+                Ok(CallToolResult {
+                    content: vec![ContentBlock::Text(TextContent::new("done"))],
+                    structured_content: None,
+                    is_error: None,
+                    meta: None,
+                })
+            }
+        })
+        .expect("register tool");
+    server
+}
+
+async fn start_task(server: &McpServer) -> CreateTaskResult {
+    let params = CallToolRequestParams {
+        base: RequestParams { meta: None },
+        name: "slow".to_string(),
+        arguments: None,
+        task: Some(TaskMetadata { ttl: Some(60_000) }),
+    };
+    let request = RequestMessage::new("call", "tools/call", serde_json::to_value(params).unwrap());
+    let response = server
+        .server()
+        .handle_request(request, None)
+        .await
+        .expect("tools/call response");
+    serde_json::from_value(response.result.unwrap()).unwrap()
+}
+
+async fn get_task(server: &McpServer, task_id: &str) -> GetTaskResult {
+    let request = RequestMessage::new("get", "tasks/get", json!({ "taskId": task_id }));
+    let response = server
+        .server()
+        .handle_request(request, None)
+        .await
+        .expect("tasks/get response");
+    serde_json::from_value(response.result.unwrap()).unwrap()
+}
+
+async fn wait_until_finished(server: &McpServer, task_ids: &[&str]) {
+    tokio::time::timeout(Duration::from_secs(5), async {
+        for task_id in task_ids {
+            while get_task(server, task_id).await.task.status == TaskStatus::Working {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        }
+    })
+    .await
+    .expect("tasks should finish");
+}
+
+#[tokio::test]
+async fn at_most_limit_tasks_run_concurrently() {
+    let gauge = Arc::new(Gauge::default());
+    let server = limited_server(2, gauge.clone());
+
+    let mut created = Vec::new();
+    for _ in 0..5 {
+        created.push(start_task(&server).await);
+    }
+    assert_eq!(server.server().task_queue_depth(), 3);
+    let queued = created
+        .iter()
+        .filter(|created| created.task.status_message.as_deref() == Some(QUEUED_STATUS_MESSAGE))
+        .count();
+    assert_eq!(queued, 3);
+
+    let task_ids: Vec<&str> = created.iter().map(|c| c.task.task_id.as_str()).collect();
+    wait_until_finished(&server, &task_ids).await;
+
+    assert_eq!(gauge.started.load(Ordering::SeqCst), 5);
+    assert_eq!(gauge.peak.load(Ordering::SeqCst), 2);
+    assert_eq!(server.server().task_queue_depth(), 0);
+    for created in &created {
+        let task = get_task(&server, &created.task.task_id).await;
+        assert_eq!(task.task.status, TaskStatus::Completed);
+        assert_eq!(task.task.status_message, None);
+    }
+}
+
+#[tokio::test]
+async fn cancelling_queued_task_never_runs_it() {
+    let gauge = Arc::new(Gauge::default());
+    let server = limited_server(1, gauge.clone());
+
+    let first = start_task(&server).await;
+    let queued = start_task(&server).await;
+    assert_eq!(
+        queued.task.status_message.as_deref(),
+        Some(QUEUED_STATUS_MESSAGE)
+    );
+
+    let request = RequestMessage::new(
+        "cancel",
+        "tasks/cancel",
+        json!({ "taskId": queued.task.task_id }),
+    );
+    let response = server
+        .server()
+        .handle_request(request, None)
+        .await
+        .expect("tasks/cancel response");
+    let cancelled: CancelTaskResult = serde_json::from_value(response.result.unwrap()).unwrap();
+    assert_eq!(cancelled.task.status, TaskStatus::Cancelled);
+    assert_eq!(server.server().task_queue_depth(), 0);
+
+    wait_until_finished(&server, &[&first.task.task_id]).await;
+    // Give a wrongly dequeued task the chance to start.
+    tokio::time::sleep(Duration::from_millis(50)).await;
+
+    assert_eq!(gauge.started.load(Ordering::SeqCst), 1);
+    let task = get_task(&server, &first.task.task_id).await;
+    assert_eq!(task.task.status, TaskStatus::Completed);
+    let task = get_task(&server, &queued.task.task_id).await;
+    assert_eq!(task.task.status, TaskStatus::Cancelled);
+}
@@ -5,11 +5,12 @@ use std::sync::Arc;
 use futures::executor::block_on;
 use serde_json::json;
 
-use mcp_core::protocol::ProtocolOptions;
+use mcp_core::protocol::{ProtocolOptions, TaskStore};
 use mcp_core::types::{
     BaseMetadata, CallToolRequestParams, CallToolResult, ContentBlock, CreateTaskResult,
-    GetTaskPayloadRequestParams, GetTaskRequestParams, GetTaskResult, Icons, RequestMessage,
-    RequestParams, TaskMetadata, TextContent, Tool,
+    ErrorCode, ErrorObject, GetTaskPayloadRequestParams, GetTaskRequestParams, GetTaskResult,
+    Icons, ListTasksResult, RequestMessage, RequestParams, TaskMetadata, TaskStatus, TextContent,
+    Tool,
 };
 use mcp_server::{InMemoryTaskStore, McpServer, ServerOptions};
 
@@ -88,6 +89,7 @@ fn tasks_flow_returns_result() {
     let result_params = GetTaskPayloadRequestParams {
         base: RequestParams { meta: None },
         task_id: task_id.clone(),
+        range: None,
     };
     let result_request = RequestMessage::new(
         "3",
@@ -107,3 +109,98 @@ fn tasks_flow_returns_result() {
         serde_json::from_value(list_response.result.unwrap()).unwrap();
     assert!(!list_result.tasks.is_empty());
 }
+
+#[test]
+fn tasks_list_filters_by_status_page_by_page() {
+    let server_info = support::implementation("task-server");
+    let task_store = Arc::new(InMemoryTaskStore::with_page_size(2));
+
+    let options = ServerOptions {
+        protocol_options: Some(ProtocolOptions {
+            task_store: Some(task_store.clone()),
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+    let server = McpServer::new(server_info, options);
+
+    // Five tasks: completed, failed, completed, working, completed.
+    let mut ids = Vec::new();
+    for index in 0..5 {
+        let request = RequestMessage::new(index as i64, "tools/call", json!({}));
+        let task = block_on(task_store.create_task(
+            TaskMetadata::default(),
+            request.id.clone(),
+            request,
+            None,
+        ))
+        .expect("create task");
+        ids.push(task.task_id);
+    }
+    for index in [0, 2, 4] {
+        block_on(task_store.set_task_result(&ids[index], Ok(json!({}))))
+            .expect("complete task");
+    }
+    let failure = ErrorObject::new(ErrorCode::InternalError as i32, "boom", None);
+    block_on(task_store.set_task_result(&ids[1], Err(failure))).expect("fail task");
+
+    let mut listed = Vec::new();
+    let mut cursor: Option<String> = None;
+    let mut pages = 0;
+    loop {
+        let mut params = json!({ "status": "completed" });
+        if let Some(cursor) = cursor.take() {
+            params["cursor"] = json!(cursor);
+        }
+        let request = RequestMessage::new(format!("list-{pages}"), "tasks/list", params);
+        let response =
+            block_on(server.server().handle_request(request, None)).expect("tasks/list response");
+        let result: ListTasksResult = serde_json::from_value(response.result.unwrap()).unwrap();
+        assert!(result.tasks.len() <= 2);
+        assert!(
+            result
+                .tasks
+                .iter()
+                .all(|task| task.status == TaskStatus::Completed)
+        );
+        listed.extend(result.tasks.into_iter().map(|task| task.task_id));
+        pages += 1;
+        match result.pagination.next_cursor {
+            Some(next) => cursor = Some(next.0),
+            None => break,
+        }
+    }
+
+    assert_eq!(pages, 2);
+    assert_eq!(listed, vec![ids[0].clone(), ids[2].clone(), ids[4].clone()]);
+
+    let request = RequestMessage::new(
+        "future",
+        "tasks/list",
+        json!({ "createdAfter": "2999-01-01T00:00:00Z" }),
+    );
+    let response = block_on(server.server().handle_request(request, None)).expect("response");
+    let result: ListTasksResult = serde_json::from_value(response.result.unwrap()).unwrap();
+    assert!(result.tasks.is_empty());
+}
+#[test]
+fn tasks_list_rejects_unreadable_parameters_as_invalid_params() {
+    let options = ServerOptions {
+        protocol_options: Some(ProtocolOptions {
+            task_store: Some(Arc::new(InMemoryTaskStore::new())),
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+    let server = McpServer::new(support::implementation("task-server"), options);
+
+    for params in [
+        json!({ "cursor": "not-a-cursor" }),
+        json!({ "createdAfter": "yesterday" }),
+    ] {
+        let request = RequestMessage::new("list", "tasks/list", params);
+        let response = block_on(server.server().handle_request(request, None)).expect("response");
+        let error = response.error.expect("invalid params error");
+        assert_eq!(error.code, ErrorCode::InvalidParams as i32);
+    }
+}
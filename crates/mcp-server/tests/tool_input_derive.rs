@@ -0,0 +1,160 @@
+//! Schemas generated by `#[derive(ToolInput)]`.
+
+mod support;
+
+use std::collections::BTreeMap;
+
+use futures::executor::block_on;
+use serde::Deserialize;
+use serde_json::json;
+
+use mcp_core::types::{BaseMetadata, CallToolResult, Icons, RequestMessage, Tool};
+use mcp_server::{McpServer, ServerOptions, ToolInput};
+
+/// Search the issues of a project
+#[allow(dead_code)] // Most fields are only read through the schema
+#[derive(Debug, Deserialize, ToolInput)]
+#[serde(rename_all = "camelCase")]
+struct SearchInput {
+    /// Project to search
+    project_id: u64,
+    #[tool_input(description = "Text to search for")]
+    query: String,
+    state: Option<IssueState>,
+    #[tool_input(required)]
+    assignee: Option<String>,
+    labels: Vec<String>,
+    #[serde(default)]
+    include_closed: bool,
+    #[serde(rename = "perPage")]
+    page_size: Option<u32>,
+    weights: BTreeMap<String, f64>,
+    #[serde(skip)]
+    cache: Option<String>,
+}
+
+#[derive(Debug, Deserialize, PartialEq, ToolInput)]
+#[serde(rename_all = "snake_case")]
+#[tool_input(description = "Issue state")]
+enum IssueState {
+    Opened,
+    Closed,
+    #[serde(rename = "all")]
+    Any,
+}
+
+#[test]
+fn structs_map_fields_to_properties() {
+    let schema = SearchInput::json_schema();
+    assert_eq!(schema["type"], "object");
+    assert_eq!(schema["description"], "Search the issues of a project");
+    let properties = &schema["properties"];
+    assert_eq!(
+        properties["projectId"],
+        json!({ "type": "integer", "description": "Project to search" })
+    );
+    assert_eq!(
+        properties["query"],
+        json!({ "type": "string", "description": "Text to search for" })
+    );
+    assert_eq!(
+        properties["state"]["enum"],
+        json!(["opened", "closed", "all"])
+    );
+    assert_eq!(properties["assignee"], json!({ "type": "string" }));
+    assert_eq!(
+        properties["labels"],
+        json!({ "type": "array", "items": { "type": "string" } })
+    );
+    assert_eq!(properties["includeClosed"], json!({ "type": "boolean" }));
+    assert_eq!(properties["perPage"], json!({ "type": "integer" }));
+    assert_eq!(
+        properties["weights"],
+        json!({ "type": "object", "additionalProperties": { "type": "number" } })
+    );
+    assert!(properties.get("cache").is_none());
+    assert_eq!(
+        schema["required"],
+        json!(["projectId", "query", "assignee", "labels", "weights"])
+    );
+
+    assert_eq!(
+        <SearchInput as ToolInput>::description(),
+        Some("Search the issues of a project")
+    );
+    assert_eq!(<SearchInput as ToolInput>::input_schema(), schema);
+}
+
+#[test]
+fn enums_map_to_string_enums() {
+    assert_eq!(
+        IssueState::json_schema(),
+        json!({
+            "type": "string",
+            "description": "Issue state",
+            "enum": ["opened", "closed", "all"]
+        })
+    );
+    assert_eq!(IssueState::description(), Some("Issue state"));
+}
+
+fn untitled_tool(name: &str) -> Tool {
+    Tool {
+        base: BaseMetadata {
+            name: name.to_string(),
+            title: None,
+        },
+        icons: Icons { icons: None },
+        description: None,
+        input_schema: json!({}),
+        output_schema: None,
+        annotations: None,
+        execution: None,
+        meta: None,
+    }
+}
+
+#[test]
+fn typed_tools_use_the_derived_schema_and_description() {
+    let mut server = McpServer::new(
+        support::implementation("derive-server"),
+        ServerOptions::default(),
+    );
+    server
+        .register_typed_tool(
+            untitled_tool("search"),
+            |input: SearchInput, _ctx| async move {
+                Ok(json!({ "query": input.query, "state": format!("{:?}", input.state) }))
+            },
+        )
+        .unwrap();
+
+    let tool = &server.list_tools()[0];
+    assert_eq!(
+        tool.description.as_deref(),
+        Some("Search the issues of a project")
+    );
+    assert_eq!(tool.input_schema, SearchInput::json_schema());
+
+    let request = RequestMessage::new(
+        "1",
+        "tools/call",
+        json!({
+            "name": "search",
+            "arguments": {
+                "projectId": 7,
+                "query": "crash",
+                "state": "all",
+                "assignee": null,
+                "labels": [],
+                "weights": {}
+            }
+        }),
+    );
+    let response = block_on(server.server().handle_request(request, None)).expect("response");
+    let result: CallToolResult = serde_json::from_value(response.result.unwrap()).unwrap();
+    assert_eq!(
+        result.structured_content,
+        Some(json!({ "query": "crash", "state": "Some(Any)" }))
+    );
+}
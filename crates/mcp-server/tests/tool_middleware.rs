@@ -0,0 +1,213 @@
+//! Tool middleware ordering, short-circuiting, panics and task-mode calls.
+
+mod support;
+
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use futures::executor::block_on;
+use serde_json::{Value, json};
+
+use mcp_core::protocol::{ProtocolOptions, RequestContext};
+use mcp_core::types::{
+    CallToolResult, ContentBlock, CreateTaskResult, RequestMessage, TextContent,
+};
+use mcp_server::{
+    AuditLogMiddleware, InMemoryTaskStore, McpServer, ServerError, ServerOptions, ToolMiddleware,
+};
+
+type Log = Arc<Mutex<Vec<String>>>;
+
+fn text(text: &str) -> CallToolResult {
+    CallToolResult {
+        content: vec![ContentBlock::Text(TextContent::new(text))],
+        structured_content: None,
+        is_error: None,
+        meta: None,
+    }
+}
+
+/// Records its hooks in `log`, optionally short-circuiting or panicking.
+struct Recorder {
+    name: &'static str,
+    log: Log,
+    short_circuit: bool,
+    panic_in: Option<&'static str>,
+}
+
+impl Recorder {
+    fn new(name: &'static str, log: &Log) -> Self {
+        Self {
+            name,
+            log: log.clone(),
+            short_circuit: false,
+            panic_in: None,
+        }
+    }
+}
+
+impl ToolMiddleware for Recorder {
+    fn before(
+        &self,
+        tool_name: &str,
+        arguments: Option<&Value>,
+        _context: &RequestContext,
+    ) -> Result<Option<CallToolResult>, ServerError> {
+        self.log.lock().unwrap().push(format!(
+            "{} before {tool_name} {}",
+            self.name,
+            arguments.cloned().unwrap_or(Value::Null)
+        ));
+        if self.panic_in == Some("before") {
+            panic!("{} broke", self.name);
+        }
+        Ok(self.short_circuit.then(|| text("cached")))
+    }
+
+    fn after(
+        &self,
+        _tool_name: &str,
+        result: &Result<CallToolResult, ServerError>,
+        _elapsed: Duration,
+        _context: &RequestContext,
+    ) {
+        self.log
+            .lock()
+            .unwrap()
+            .push(format!("{} after ok={}", self.name, result.is_ok()));
+        if self.panic_in == Some("after") {
+            panic!("{} broke", self.name);
+        }
+    }
+}
+
+fn server_with(options: ServerOptions, log: &Log) -> McpServer {
+    let mut server = McpServer::new(support::implementation("middleware-server"), options);
+    let log = log.clone();
+    server
+        .register_tool(support::tool("echo"), move |_args, _ctx: RequestContext| {
+            let log = log.clone();
+            async move {
+                log.lock().unwrap().push("handler".to_string());
+                Ok(text("handled"))
+            }
+        })
+        .expect("register tool");
+    server
+}
+
+fn call(server: &McpServer, params: Value) -> mcp_core::types::ResultMessage {
+    let request = RequestMessage::new("1", "tools/call", params);
+    block_on(server.server().handle_request(request, None)).expect("response")
+}
+
+fn result_text(result: Value) -> String {
+    let result: CallToolResult = serde_json::from_value(result).unwrap();
+    match &result.content[0] {
+        ContentBlock::Text(text) => text.text.clone(),
+        other => panic!("unexpected content: {other:?}"),
+    }
+}
+
+#[test]
+fn middleware_wraps_the_handler_in_order() {
+    let log = Log::default();
+    let mut server = server_with(ServerOptions::default(), &log);
+    server.add_tool_middleware(Recorder::new("outer", &log));
+    server.add_tool_middleware(Recorder::new("inner", &log));
+    server.add_tool_middleware(AuditLogMiddleware::new());
+
+    let response = call(&server, json!({ "name": "echo", "arguments": { "x": 1 } }));
+    assert_eq!(result_text(response.result.unwrap()), "handled");
+    assert_eq!(
+        *log.lock().unwrap(),
+        [
+            r#"outer before echo {"x":1}"#,
+            r#"inner before echo {"x":1}"#,
+            "handler",
+            "inner after ok=true",
+            "outer after ok=true",
+        ]
+    );
+}
+
+#[test]
+fn before_hooks_can_short_circuit() {
+    let log = Log::default();
+    let mut server = server_with(ServerOptions::default(), &log);
+    server.add_tool_middleware(Recorder::new("outer", &log));
+    server.add_tool_middleware(Recorder {
+        short_circuit: true,
+        ..Recorder::new("cache", &log)
+    });
+    server.add_tool_middleware(Recorder::new("inner", &log));
+
+    let response = call(&server, json!({ "name": "echo" }));
+    assert_eq!(result_text(response.result.unwrap()), "cached");
+    assert_eq!(
+        *log.lock().unwrap(),
+        [
+            "outer before echo null",
+            "cache before echo null",
+            "cache after ok=true",
+            "outer after ok=true",
+        ]
+    );
+}
+
+#[test]
+fn panics_in_middleware_become_handler_errors() {
+    for hook in ["before", "after"] {
+        let log = Log::default();
+        let mut server = server_with(ServerOptions::default(), &log);
+        server.add_tool_middleware(Recorder::new("outer", &log));
+        server.add_tool_middleware(Recorder {
+            panic_in: Some(hook),
+            ..Recorder::new("faulty", &log)
+        });
+
+        let response = call(&server, json!({ "name": "echo" }));
+        let error = response.error.expect("handler error");
+        assert!(
+            error
+                .message
+                .contains("tool middleware panicked: faulty broke"),
+            "{}",
+            error.message
+        );
+        // The outer middleware sees the failure either way
+        assert_eq!(log.lock().unwrap().last().unwrap(), "outer after ok=false");
+        assert_eq!(
+            log.lock().unwrap().contains(&"handler".to_string()),
+            hook == "after"
+        );
+    }
+}
+
+#[test]
+fn middleware_runs_inside_task_mode_calls() {
+    let log = Log::default();
+    let options = ServerOptions {
+        protocol_options: Some(ProtocolOptions {
+            task_store: Some(Arc::new(InMemoryTaskStore::default())),
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+    let mut server = server_with(options, &log);
+    server.add_tool_middleware(Recorder::new("outer", &log));
+
+    let response = call(&server, json!({ "name": "echo", "task": { "ttl": 1000 } }));
+    let created: CreateTaskResult = serde_json::from_value(response.result.unwrap()).unwrap();
+    let request = RequestMessage::new(
+        "2",
+        "tasks/result",
+        json!({ "taskId": created.task.task_id }),
+    );
+    let response = block_on(server.server().handle_request(request, None)).expect("response");
+    assert_eq!(result_text(response.result.unwrap()), "handled");
+    assert_eq!(
+        *log.lock().unwrap(),
+        ["outer before echo null", "handler", "outer after ok=true"]
+    );
+}
@@ -0,0 +1,122 @@
+//! Per-tool required scopes on `tools/call`.
+//!
+//! The bearer middleware verifies the token; the server then checks the
+//! verified scopes against the tool's `ToolOptions::required_scopes`.
+
+#![cfg(feature = "axum")]
+
+mod support;
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use axum::body::Body;
+use axum::http::{Request, StatusCode, header};
+use serde_json::{Value, json};
+use tower::util::ServiceExt;
+
+use mcp_core::auth::AuthInfo;
+use mcp_core::protocol::RequestContext;
+use mcp_core::types::{CallToolResult, ContentBlock, TextContent};
+use mcp_server::auth::middleware::BearerAuthLayer;
+use mcp_server::auth::{OAuthProviderError, OAuthTokenVerifier};
+use mcp_server::{
+    AxumHandlerConfig, AxumHandlerState, McpServer, ServerOptions, SessionMode, ToolOptions,
+    create_router,
+};
+
+/// `reader` may only read; `admin` may also write.
+#[derive(Clone)]
+struct StaticVerifier;
+
+#[async_trait]
+impl OAuthTokenVerifier for StaticVerifier {
+    async fn verify_access_token(&self, token: &str) -> Result<AuthInfo, OAuthProviderError> {
+        let scopes = match token {
+            "reader" => vec!["repo:read"],
+            "admin" => vec!["repo:read", "repo:write"],
+            _ => return Err(OAuthProviderError::InvalidToken("unknown token".to_string())),
+        };
+        Ok(AuthInfo::new(token).with_scopes(scopes.into_iter().map(String::from).collect()))
+    }
+}
+
+fn text_result(text: String) -> CallToolResult {
+    CallToolResult {
+        content: vec![ContentBlock::Text(TextContent::new(text))],
+        structured_content: None,
+        is_error: None,
+        meta: None,
+    }
+}
+
+/// Server with a public `whoami` tool and a `delete_repo` tool requiring `repo:write`.
+fn server() -> Arc<McpServer> {
+    let mut server = McpServer::new(support::implementation("scoped-server"), ServerOptions::default());
+    server
+        .register_tool(support::tool("whoami"), |_args: Option<Value>, ctx: RequestContext| async move {
+            let token = ctx.auth_info.map(|info| info.token).unwrap_or_default();
+            Ok(text_result(token))
+        })
+        .expect("register whoami");
+    server
+        .register_tool_with_options(
+            support::tool("delete_repo"),
+            ToolOptions::default().with_required_scopes(["repo:write"]),
+            |_args: Option<Value>, _ctx: RequestContext| async move {
+                Ok(text_result("deleted".to_string()))
+            },
+        )
+        .expect("register delete_repo");
+    Arc::new(server)
+}
+
+fn router() -> axum::Router {
+    let config = AxumHandlerConfig {
+        session_mode: SessionMode::Stateless,
+        ..Default::default()
+    };
+    create_router(Arc::new(AxumHandlerState::new(server(), config)))
+        .layer(BearerAuthLayer::new(Arc::new(StaticVerifier)))
+}
+
+async fn call_tool(token: &str, name: &str) -> Value {
+    let body = json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": "tools/call",
+        "params": { "name": name, "arguments": {} }
+    });
+    let request = Request::builder()
+        .method("POST")
+        .uri("/mcp")
+        .header(header::CONTENT_TYPE, "application/json")
+        .header(header::AUTHORIZATION, format!("Bearer {token}"))
+        .body(Body::from(body.to_string()))
+        .unwrap();
+    let response = router().oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    serde_json::from_slice(&bytes).unwrap()
+}
+
+#[tokio::test]
+async fn token_missing_required_scope_is_rejected() {
+    let body = call_tool("reader", "delete_repo").await;
+    assert!(body.get("result").is_none(), "{body}");
+    assert_eq!(body["error"]["data"]["error"], "insufficient_scope");
+    let message = body["error"]["message"].as_str().unwrap();
+    assert!(message.contains("repo:write"), "{message}");
+}
+
+#[tokio::test]
+async fn token_with_required_scope_is_allowed() {
+    let body = call_tool("admin", "delete_repo").await;
+    assert_eq!(body["result"]["content"][0]["text"], "deleted");
+}
+
+#[tokio::test]
+async fn tools_without_scopes_stay_public_and_see_auth_info() {
+    let body = call_tool("reader", "whoami").await;
+    assert_eq!(body["result"]["content"][0]["text"], "reader");
+}
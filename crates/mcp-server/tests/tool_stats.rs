@@ -0,0 +1,109 @@
+//! Per-tool call counts and latencies served by `tools/stats`.
+
+#![cfg(feature = "metrics")]
+
+mod support;
+
+use futures::executor::block_on;
+use serde_json::{Value, json};
+
+use mcp_core::protocol::RequestContext;
+use mcp_core::types::{CallToolResult, RequestMessage, ResultMessage};
+use mcp_server::{McpServer, ServerError, ServerOptions};
+
+fn server() -> McpServer {
+    let mut server = McpServer::new(
+        support::implementation("stats-server"),
+        ServerOptions::default(),
+    );
+    server
+        .register_tool(support::tool("echo"), |_args, _ctx: RequestContext| async move {
+            Ok(CallToolResult::default())
+        })
+        .unwrap();
+    server
+        .register_tool(support::tool("flaky"), |_args, _ctx: RequestContext| async move {
+            Ok(CallToolResult {
+                is_error: Some(true),
+                ..Default::default()
+            })
+        })
+        .unwrap();
+    server
+        .register_tool(support::tool("broken"), |_args, _ctx: RequestContext| async move {
+            Err(ServerError::Handler("backend unavailable".to_string()))
+        })
+        .unwrap();
+    server
+}
+
+fn request(server: &McpServer, method: &str, params: Value) -> ResultMessage {
+    let request = RequestMessage::new("1", method, params);
+    block_on(server.server().handle_request(request, None)).unwrap()
+}
+
+fn call(server: &McpServer, name: &str) {
+    request(
+        server,
+        "tools/call",
+        json!({ "name": name, "arguments": {} }),
+    );
+}
+
+fn stats(server: &McpServer) -> Value {
+    let response = request(server, "tools/stats", json!({}));
+    response.result.expect("tools/stats result")["structuredContent"]["tools"].clone()
+}
+
+#[test]
+fn calls_and_errors_are_counted_per_tool() {
+    let server = server();
+    for _ in 0..3 {
+        call(&server, "echo");
+    }
+    call(&server, "flaky");
+    call(&server, "broken");
+    call(&server, "missing");
+
+    let tools = stats(&server);
+    let tools = tools.as_array().unwrap();
+    let names: Vec<&str> = tools
+        .iter()
+        .map(|row| row["name"].as_str().unwrap())
+        .collect();
+    // Unknown tools are not tracked
+    assert_eq!(names, ["broken", "echo", "flaky"]);
+
+    assert_eq!(tools[1]["call_count"], 3);
+    assert_eq!(tools[1]["error_count"], 0);
+    assert_eq!(tools[2]["call_count"], 1);
+    assert_eq!(tools[2]["error_count"], 1);
+    assert_eq!(tools[0]["error_count"], 1);
+
+    let echo = &tools[1];
+    let (p50, p95, p99) = (
+        echo["p50_ms"].as_f64().unwrap(),
+        echo["p95_ms"].as_f64().unwrap(),
+        echo["p99_ms"].as_f64().unwrap(),
+    );
+    assert!(p50 >= 0.0 && p50 <= p95 && p95 <= p99);
+
+    let rows = server.tool_stats();
+    assert_eq!(rows.len(), 3);
+    assert_eq!(rows[1].name, "echo");
+    assert_eq!(rows[1].call_count, 3);
+}
+
+#[test]
+fn reset_clears_the_counters() {
+    let server = server();
+    call(&server, "echo");
+    assert_eq!(stats(&server).as_array().unwrap().len(), 1);
+
+    let response = request(&server, "tools/stats/reset", json!({}));
+    assert_eq!(response.result, Some(json!({})));
+    assert_eq!(stats(&server), json!([]));
+
+    call(&server, "echo");
+    assert_eq!(stats(&server)[0]["call_count"], 1);
+}
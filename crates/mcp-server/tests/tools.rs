@@ -1,13 +1,23 @@
 mod support;
 
+use std::sync::Arc;
+
 use futures::executor::block_on;
-use serde_json::json;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
 
+use mcp_core::protocol::ProtocolError;
 use mcp_core::types::{
-    BaseMetadata, CallToolRequestParams, CallToolResult, ContentBlock, Icons, RequestMessage,
-    RequestParams, TextContent, Tool,
+    BaseMetadata, CallToolRequestParams, CallToolResult, ContentBlock, ErrorCode, Icons,
+    RequestMessage, RequestParams, TextContent, Tool,
+};
+use mcp_core::schema::{
+    CustomKeywordValidator, JsonSchemaValidator, SchemaValidator, ValidationError,
+};
+use mcp_server::{
+    BoxedToolHandler, McpServer, ServerError, ServerOptions, ToolInput, input_schema_for,
 };
-use mcp_server::{McpServer, ServerOptions};
 
 #[test]
 fn tools_list_and_call_work() {
@@ -69,3 +79,414 @@ fn tools_list_and_call_work() {
     let notification = server.tool_list_changed_notification();
     assert_eq!(notification.method, "notifications/tools/list_changed");
 }
+
+/// Mask every `glpat-` token up to the next non-token character.
+fn mask_tokens(text: &str) -> String {
+    let mut masked = String::new();
+    let mut rest = text;
+    while let Some(start) = rest.find("glpat-") {
+        masked.push_str(&rest[..start]);
+        masked.push_str("[REDACTED]");
+        let token = &rest[start + "glpat-".len()..];
+        let end = token
+            .find(|c: char| !(c.is_ascii_alphanumeric() || c == '-' || c == '_'))
+            .unwrap_or(token.len());
+        rest = &token[end..];
+    }
+    masked.push_str(rest);
+    masked
+}
+
+#[test]
+fn result_transform_rewrites_tool_output() {
+    let options = ServerOptions {
+        result_transform: Some(Arc::new(|result: &mut CallToolResult| {
+            for block in &mut result.content {
+                if let ContentBlock::Text(text) = block {
+                    text.text = mask_tokens(&text.text);
+                }
+            }
+        })),
+        ..Default::default()
+    };
+    let mut server = McpServer::new(support::implementation("tool-server"), options);
+    server
+        .register_tool(
+            support::tool("read_file"),
+            |_args, _ctx: mcp_core::protocol::RequestContext| async move {
+                Ok(CallToolResult {
+                    content: vec![ContentBlock::Text(TextContent::new(
+                        "GITLAB_TOKEN=glpat-AbC123_xyz\nurl=https://gitlab.com",
+                    ))],
+                    structured_content: None,
+                    is_error: None,
+                    meta: None,
+                })
+            },
+        )
+        .expect("register tool");
+
+    let request = RequestMessage::new("1", "tools/call", json!({ "name": "read_file" }));
+    let response = block_on(server.server().handle_request(request, None)).expect("response");
+    let result: CallToolResult = serde_json::from_value(response.result.unwrap()).unwrap();
+    match &result.content[0] {
+        ContentBlock::Text(text) => {
+            assert_eq!(text.text, "GITLAB_TOKEN=[REDACTED]\nurl=https://gitlab.com")
+        }
+        other => panic!("unexpected content: {other:?}"),
+    }
+}
+
+#[test]
+fn result_transform_skips_failed_calls() {
+    let options = ServerOptions {
+        result_transform: Some(Arc::new(|_result: &mut CallToolResult| {
+            panic!("transform must not run for failed calls")
+        })),
+        ..Default::default()
+    };
+    let mut server = McpServer::new(support::implementation("tool-server"), options);
+    server
+        .register_tool(
+            support::tool("read_file"),
+            |_args, _ctx: mcp_core::protocol::RequestContext| async move {
+                Err::<CallToolResult, _>(ServerError::Handler("missing file".to_string()))
+            },
+        )
+        .expect("register tool");
+
+    let request = RequestMessage::new("1", "tools/call", json!({ "name": "read_file" }));
+    let response = block_on(server.server().handle_request(request, None)).expect("response");
+    assert!(response.error.is_some());
+}
+
+#[derive(Deserialize, JsonSchema)]
+struct AddInput {
+    a: i64,
+    b: i64,
+}
+
+impl ToolInput for AddInput {
+    fn input_schema() -> Value {
+        input_schema_for::<Self>()
+    }
+}
+
+#[derive(Serialize)]
+struct AddOutput {
+    sum: i64,
+}
+
+fn call_add(server: &McpServer, arguments: Value) -> CallToolResult {
+    let request = RequestMessage::new(
+        "1",
+        "tools/call",
+        json!({ "name": "add", "arguments": arguments }),
+    );
+    let response = block_on(server.server().handle_request(request, None)).expect("response");
+    serde_json::from_value(response.result.expect("tool result")).unwrap()
+}
+
+#[test]
+fn typed_tool_derives_schema_and_parses_arguments() {
+    let mut server = McpServer::new(support::implementation("tool-server"), ServerOptions::default());
+    let mut tool = support::tool("read_file");
+    tool.base.name = "add".to_string();
+    server
+        .register_typed_tool(
+            tool,
+            |input: AddInput, _ctx: mcp_core::protocol::RequestContext| async move {
+                Ok(AddOutput {
+                    sum: input.a + input.b,
+                })
+            },
+        )
+        .expect("register typed tool");
+
+    let schema = &server.list_tools()[0].input_schema;
+    assert_eq!(schema["type"], "object");
+    assert_eq!(schema["properties"]["a"]["type"], "integer");
+    let mut required: Vec<&str> = schema["required"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .filter_map(Value::as_str)
+        .collect();
+    required.sort();
+    assert_eq!(required, ["a", "b"]);
+
+    let result = call_add(&server, json!({ "a": 2, "b": 3 }));
+    assert_eq!(result.is_error, None);
+    assert_eq!(result.structured_content, Some(json!({ "sum": 5 })));
+    match &result.content[0] {
+        ContentBlock::Text(text) => assert_eq!(text.text, r#"{"sum":5}"#),
+        other => panic!("unexpected content: {other:?}"),
+    }
+
+    for arguments in [json!({ "a": 2 }), json!({ "a": 2, "b": "three" })] {
+        let result = call_add(&server, arguments);
+        assert_eq!(result.is_error, Some(true));
+        match &result.content[0] {
+            ContentBlock::Text(text) => assert!(text.text.starts_with("invalid arguments:")),
+            other => panic!("unexpected content: {other:?}"),
+        }
+    }
+}
+
+#[test]
+fn tool_typed_keeps_the_schema_and_rejects_invalid_params() {
+    let mut server = McpServer::new(support::implementation("tool-server"), ServerOptions::default());
+    let mut tool = support::tool("read_file");
+    tool.base.name = "add".to_string();
+    let declared_schema = tool.input_schema.clone();
+    server
+        .register_tool_typed(
+            tool,
+            |input: AddInput, _ctx: mcp_core::protocol::RequestContext| async move {
+                Ok(AddOutput {
+                    sum: input.a + input.b,
+                })
+            },
+        )
+        .expect("register typed tool");
+
+    assert_eq!(server.list_tools()[0].input_schema, declared_schema);
+
+    let result = call_add(&server, json!({ "a": 2, "b": 3 }));
+    assert_eq!(result.is_error, None);
+    assert_eq!(result.structured_content, Some(json!({ "sum": 5 })));
+
+    let request = RequestMessage::new(
+        "2",
+        "tools/call",
+        json!({ "name": "add", "arguments": { "a": 2, "b": "three" } }),
+    );
+    let response = block_on(server.server().handle_request(request, None)).expect("response");
+    let error = response.error.expect("invalid params error");
+    assert_eq!(error.code, ErrorCode::InvalidParams as i32);
+    assert!(error.message.contains("invalid arguments:"), "{}", error.message);
+}
+
+#[test]
+fn tool_typed_handler_errors_keep_their_protocol_code() {
+    let mut server = McpServer::new(support::implementation("tool-server"), ServerOptions::default());
+    let mut tool = support::tool("read_file");
+    tool.base.name = "add".to_string();
+    server
+        .register_tool_typed(
+            tool,
+            |_input: Value, _ctx: mcp_core::protocol::RequestContext| async move {
+                Err::<Value, _>(ServerError::Protocol(ProtocolError::Timeout))
+            },
+        )
+        .expect("register typed tool");
+
+    let request = RequestMessage::new("1", "tools/call", json!({ "name": "add" }));
+    let response = block_on(server.server().handle_request(request, None)).expect("response");
+    assert_eq!(response.error.unwrap().code, ErrorCode::RequestTimeout as i32);
+}
+
+/// `x-even: true` accepts even integers only.
+struct Even;
+
+impl CustomKeywordValidator for Even {
+    fn validate(&self, value: &Value, _schema_value: &Value) -> Result<(), ValidationError> {
+        match value.as_i64() {
+            Some(n) if n % 2 == 0 => Ok(()),
+            _ => Err(ValidationError::Failed(vec![format!("{value} is not even")])),
+        }
+    }
+}
+
+#[test]
+fn tool_input_validator_rejects_arguments_before_the_handler() {
+    let mut validator = JsonSchemaValidator::default();
+    validator.register_keyword("x-even", Box::new(Even));
+    let options = ServerOptions {
+        tool_input_validator: Some(Arc::new(validator)),
+        ..Default::default()
+    };
+    let mut server = McpServer::new(support::implementation("tool-server"), options);
+    let mut tool = support::tool("read_file");
+    tool.base.name = "add".to_string();
+    tool.input_schema = json!({
+        "type": "object",
+        "properties": { "a": { "type": "integer", "x-even": true } },
+        "required": ["a"]
+    });
+    server
+        .register_tool(
+            tool,
+            |_args, _ctx: mcp_core::protocol::RequestContext| async move {
+                Ok(CallToolResult {
+                    content: vec![ContentBlock::Text(TextContent::new("ok"))],
+                    structured_content: None,
+                    is_error: None,
+                    meta: None,
+                })
+            },
+        )
+        .expect("register tool");
+
+    assert_eq!(call_add(&server, json!({ "a": 4 })).is_error, None);
+
+    for (arguments, expected) in [
+        (json!({}), "\"a\" is a required property"),
+        (json!({ "a": 3 }), "x-even at \"/a\": 3 is not even"),
+    ] {
+        let result = call_add(&server, arguments);
+        assert_eq!(result.is_error, Some(true));
+        match &result.content[0] {
+            ContentBlock::Text(text) => {
+                assert!(text.text.starts_with("invalid arguments:"), "{}", text.text);
+                assert!(text.text.contains(expected), "{}", text.text);
+            }
+            other => panic!("unexpected content: {other:?}"),
+        }
+    }
+}
+
+fn named_tool(name: &str) -> Tool {
+    Tool {
+        base: BaseMetadata {
+            name: name.to_string(),
+            title: None,
+        },
+        ..support::tool("read_file")
+    }
+}
+
+fn echo_handler(text: &'static str) -> BoxedToolHandler {
+    Box::new(
+        move |_args, _ctx: mcp_core::protocol::RequestContext| async move {
+            Ok(CallToolResult {
+                content: vec![ContentBlock::Text(TextContent::new(text))],
+                ..Default::default()
+            })
+        },
+    )
+}
+
+#[test]
+fn tool_group_registers_prefixed_names() {
+    let mut server = McpServer::new(
+        support::implementation("tool-server"),
+        ServerOptions::default(),
+    );
+    let count = server
+        .register_tool_group(
+            Some("gitlab"),
+            vec![
+                (named_tool("list_issues"), echo_handler("issues")),
+                (named_tool("get_file"), echo_handler("file")),
+            ],
+        )
+        .unwrap();
+    assert_eq!(count, 2);
+
+    let mut names: Vec<_> = server
+        .list_tools()
+        .into_iter()
+        .map(|tool| tool.base.name)
+        .collect();
+    names.sort();
+    assert_eq!(names, ["gitlab_get_file", "gitlab_list_issues"]);
+    assert!(server.server().get_capabilities().tools.is_some());
+
+    let request = RequestMessage::new(
+        "1",
+        "tools/call",
+        json!({ "name": "gitlab_list_issues", "arguments": {} }),
+    );
+    let response = block_on(server.server().handle_request(request, None)).expect("response");
+    let result: CallToolResult = serde_json::from_value(response.result.unwrap()).unwrap();
+    assert!(matches!(&result.content[0], ContentBlock::Text(text) if text.text == "issues"));
+}
+
+#[test]
+fn tool_group_is_rolled_back_when_any_tool_is_invalid() {
+    let mut server = McpServer::new(
+        support::implementation("tool-server"),
+        ServerOptions::default(),
+    );
+    server
+        .register_tool_group(
+            None,
+            vec![(named_tool("gitlab_get_file"), echo_handler("file"))],
+        )
+        .unwrap();
+
+    // Clashes with a registered tool once prefixed
+    let err = server
+        .register_tool_group(
+            Some("gitlab"),
+            vec![
+                (named_tool("list_issues"), echo_handler("issues")),
+                (named_tool("get_file"), echo_handler("file")),
+            ],
+        )
+        .unwrap_err();
+    assert!(
+        matches!(err, ServerError::InvalidTool(message) if message.contains("gitlab_get_file"))
+    );
+
+    // Repeated within the group
+    let err = server
+        .register_tool_group(
+            None,
+            vec![
+                (named_tool("list_issues"), echo_handler("issues")),
+                (named_tool("list_issues"), echo_handler("issues")),
+            ],
+        )
+        .unwrap_err();
+    assert!(matches!(err, ServerError::InvalidTool(_)));
+
+    // Not an object schema
+    let mut array_tool = named_tool("list_pipelines");
+    array_tool.input_schema = json!({ "type": "array" });
+    let err = server
+        .register_tool_group(
+            None,
+            vec![
+                (named_tool("list_issues"), echo_handler("issues")),
+                (array_tool, echo_handler("pipelines")),
+            ],
+        )
+        .unwrap_err();
+    assert!(matches!(err, ServerError::InvalidTool(message) if message.contains("list_pipelines")));
+
+    let names: Vec<_> = server
+        .list_tools()
+        .into_iter()
+        .map(|tool| tool.base.name)
+        .collect();
+    assert_eq!(names, ["gitlab_get_file"]);
+}
+
+#[test]
+fn image_content_is_returned_as_base64() {
+    let mut server = McpServer::new(
+        support::implementation("tool-server"),
+        ServerOptions::default(),
+    );
+    server
+        .register_tool(
+            named_tool("avatar"),
+            |_args, _ctx: mcp_core::protocol::RequestContext| async move {
+                Ok(CallToolResult {
+                    content: vec![ContentBlock::image(b"GIF89a", "image/gif")],
+                    ..Default::default()
+                })
+            },
+        )
+        .expect("register tool");
+
+    let request = RequestMessage::new("1", "tools/call", json!({ "name": "avatar" }));
+    let response = block_on(server.server().handle_request(request, None)).unwrap();
+    let image = &response.result.unwrap()["content"][0];
+    assert_eq!(
+        image,
+        &json!({ "type": "image", "data": "R0lGODlh", "mimeType": "image/gif" })
+    );
+}
@@ -0,0 +1,191 @@
+//! Tools implemented as a `TypedTool` and registered with
+//! `McpServer::register_tool_type`.
+
+mod support;
+
+use futures::executor::block_on;
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+
+use mcp_core::protocol::RequestContext;
+use mcp_core::types::{CallToolResult, ContentBlock, RequestMessage};
+use mcp_server::{McpServer, ServerError, ServerOptions, TypedTool};
+
+#[derive(Deserialize)]
+struct EchoArgs {
+    message: String,
+}
+
+#[derive(Serialize)]
+struct EchoOutput {
+    message: String,
+}
+
+struct Echo;
+
+impl TypedTool for Echo {
+    type Args = EchoArgs;
+    type Output = EchoOutput;
+
+    fn name() -> &'static str {
+        "echo"
+    }
+
+    fn title() -> Option<&'static str> {
+        Some("Echo Tool")
+    }
+
+    fn description() -> &'static str {
+        "Echo the message back"
+    }
+
+    fn input_schema() -> Value {
+        json!({
+            "type": "object",
+            "properties": { "message": { "type": "string" } },
+            "required": ["message"]
+        })
+    }
+
+    async fn call(args: EchoArgs, _ctx: RequestContext) -> Result<EchoOutput, ServerError> {
+        Ok(EchoOutput {
+            message: args.message,
+        })
+    }
+}
+
+#[derive(Deserialize)]
+struct ProcessDataArgs {
+    items: Vec<String>,
+}
+
+#[derive(Serialize)]
+struct ProcessDataOutput {
+    total_items: usize,
+    results: Vec<String>,
+}
+
+struct ProcessData;
+
+impl TypedTool for ProcessData {
+    type Args = ProcessDataArgs;
+    type Output = ProcessDataOutput;
+
+    fn name() -> &'static str {
+        "process_data"
+    }
+
+    fn description() -> &'static str {
+        "Process a batch of data items"
+    }
+
+    fn input_schema() -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "items": { "type": "array", "items": { "type": "string" } }
+            },
+            "required": ["items"]
+        })
+    }
+
+    async fn call(
+        args: ProcessDataArgs,
+        _ctx: RequestContext,
+    ) -> Result<ProcessDataOutput, ServerError> {
+        if args.items.is_empty() {
+            return Err(ServerError::Handler("nothing to process".to_string()));
+        }
+        Ok(ProcessDataOutput {
+            total_items: args.items.len(),
+            results: args.items.iter().map(|item| item.to_uppercase()).collect(),
+        })
+    }
+}
+
+fn server() -> McpServer {
+    let mut server = McpServer::new(
+        support::implementation("typed-server"),
+        ServerOptions::default(),
+    );
+    server.register_tool_type::<Echo>().unwrap();
+    server.register_tool_type::<ProcessData>().unwrap();
+    server
+}
+
+fn call(server: &McpServer, name: &str, arguments: Value) -> mcp_core::types::ResultMessage {
+    let request = RequestMessage::new(
+        "1",
+        "tools/call",
+        json!({ "name": name, "arguments": arguments }),
+    );
+    block_on(server.server().handle_request(request, None)).expect("response")
+}
+
+#[test]
+fn typed_tools_are_listed_from_the_trait() {
+    let server = server();
+    let mut tools = server.list_tools();
+    tools.sort_by(|a, b| a.base.name.cmp(&b.base.name));
+
+    assert_eq!(tools[0].base.name, "echo");
+    assert_eq!(tools[0].base.title.as_deref(), Some("Echo Tool"));
+    assert_eq!(
+        tools[0].description.as_deref(),
+        Some("Echo the message back")
+    );
+    assert_eq!(tools[0].input_schema, Echo::input_schema());
+    assert_eq!(tools[1].base.name, "process_data");
+    assert_eq!(tools[1].base.title, None);
+}
+
+#[test]
+fn typed_tool_output_becomes_structured_content() {
+    let server = server();
+
+    let response = call(&server, "echo", json!({ "message": "hello" }));
+    let result: CallToolResult = serde_json::from_value(response.result.unwrap()).unwrap();
+    assert_eq!(
+        result.structured_content,
+        Some(json!({ "message": "hello" }))
+    );
+    match &result.content[0] {
+        ContentBlock::Text(text) => assert_eq!(text.text, r#"{"message":"hello"}"#),
+        other => panic!("unexpected content: {other:?}"),
+    }
+
+    let response = call(&server, "process_data", json!({ "items": ["a", "b"] }));
+    let result: CallToolResult = serde_json::from_value(response.result.unwrap()).unwrap();
+    assert_eq!(
+        result.structured_content,
+        Some(json!({ "total_items": 2, "results": ["A", "B"] }))
+    );
+}
+
+#[test]
+fn typed_tool_rejects_arguments_that_do_not_parse() {
+    let server = server();
+
+    let response = call(&server, "echo", json!({ "message": 7 }));
+    let result: CallToolResult = serde_json::from_value(response.result.unwrap()).unwrap();
+    assert_eq!(result.is_error, Some(true));
+    match &result.content[0] {
+        ContentBlock::Text(text) => {
+            assert!(text.text.starts_with("invalid arguments:"), "{}", text.text)
+        }
+        other => panic!("unexpected content: {other:?}"),
+    }
+}
+
+#[test]
+fn typed_tool_errors_fail_the_call() {
+    let server = server();
+
+    let response = call(&server, "process_data", json!({ "items": [] }));
+    let error = response.error.expect("handler error");
+    assert!(
+        error.message.contains("nothing to process"),
+        "{}",
+        error.message
+    );
+}
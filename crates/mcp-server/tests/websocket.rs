@@ -0,0 +1,388 @@
+//! WebSocket keepalive, idle timeout, message size limits and
+//! server-initiated notifications, driven by `tokio-tungstenite` clients.
+
+#![cfg(feature = "websocket")]
+
+mod support;
+
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::Duration;
+
+use futures::{SinkExt, StreamExt};
+use serde_json::{Value, json};
+use tokio::net::TcpStream;
+use tokio::sync::mpsc;
+use tokio_tungstenite::tungstenite::Message;
+use tokio_tungstenite::tungstenite::client::IntoClientRequest;
+use tokio_tungstenite::tungstenite::protocol::frame::coding::CloseCode;
+use tokio_tungstenite::{MaybeTlsStream, WebSocketStream, connect_async};
+
+use mcp_core::protocol::RequestContext;
+use mcp_core::types::{BaseMetadata, CallToolResult, ContentBlock, Icons, ReadResourceResult, Resource, TextContent};
+use mcp_server::{
+    McpServer, ServerError, ServerOptions, WebSocketConfig, WebSocketState, create_websocket_router,
+};
+
+type Client = WebSocketStream<MaybeTlsStream<TcpStream>>;
+
+/// Serve `server` on a local port, returning its address and a channel
+/// receiving the ids of closed connections.
+async fn serve(
+    server: Arc<McpServer>,
+    config: WebSocketConfig,
+) -> (SocketAddr, mpsc::UnboundedReceiver<String>) {
+    let state = Arc::new(WebSocketState::new(server, config));
+    let (closed_tx, closed_rx) = mpsc::unbounded_channel();
+    state.on_connection_closed(Arc::new(move |id: &str| {
+        let _ = closed_tx.send(id.to_string());
+    }));
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move { axum::serve(listener, create_websocket_router(state)).await });
+    (addr, closed_rx)
+}
+
+async fn client(addr: SocketAddr) -> Client {
+    connect_async(format!("ws://{addr}/ws")).await.unwrap().0
+}
+
+/// Serve an empty server with `config`, returning a connected client.
+async fn connect(config: WebSocketConfig) -> (Client, mpsc::UnboundedReceiver<String>) {
+    let server = McpServer::new(
+        support::implementation("websocket-server"),
+        ServerOptions::default(),
+    );
+    let (addr, closed) = serve(Arc::new(server), config).await;
+    (client(addr).await, closed)
+}
+
+/// Next JSON-RPC message, skipping pings.
+async fn next_json(client: &mut Client) -> Value {
+    loop {
+        if let Message::Text(text) = next(client).await {
+            return serde_json::from_str(&text).unwrap();
+        }
+    }
+}
+
+/// Send a request and wait for its response.
+async fn request(client: &mut Client, id: i64, method: &str, params: Value) -> Value {
+    let request = json!({ "jsonrpc": "2.0", "id": id, "method": method, "params": params });
+    client
+        .send(Message::Text(request.to_string()))
+        .await
+        .unwrap();
+    loop {
+        let message = next_json(client).await;
+        if message["id"] == id {
+            return message;
+        }
+    }
+}
+
+async fn no_op(_args: Option<Value>, _ctx: RequestContext) -> Result<CallToolResult, ServerError> {
+    Ok(CallToolResult {
+        content: Vec::new(),
+        structured_content: None,
+        is_error: None,
+        meta: None,
+    })
+}
+
+fn resource(uri: &str) -> Resource {
+    Resource {
+        base: BaseMetadata {
+            name: uri.to_string(),
+            title: None,
+        },
+        icons: Icons { icons: None },
+        uri: uri.to_string(),
+        description: None,
+        mime_type: None,
+        annotations: None,
+        meta: None,
+    }
+}
+
+async fn empty(_uri: String, _ctx: RequestContext) -> Result<ReadResourceResult, ServerError> {
+    Ok(ReadResourceResult {
+        contents: Vec::new(),
+        meta: None,
+    })
+}
+
+/// Next frame from the server, failing the test if none arrives in time.
+async fn next(client: &mut Client) -> Message {
+    tokio::time::timeout(Duration::from_secs(5), client.next())
+        .await
+        .expect("no frame from the server")
+        .expect("connection ended")
+        .unwrap()
+}
+
+fn close_code(message: Message) -> CloseCode {
+    match message {
+        Message::Close(Some(frame)) => frame.code,
+        other => panic!("expected a close frame, got {other:?}"),
+    }
+}
+
+#[tokio::test]
+async fn idle_connections_are_closed() {
+    let (mut client, mut closed) = connect(WebSocketConfig {
+        ping_interval: None,
+        idle_timeout: Some(Duration::from_millis(200)),
+        ..Default::default()
+    })
+    .await;
+
+    assert_eq!(close_code(next(&mut client).await), CloseCode::Away);
+
+    let id = tokio::time::timeout(Duration::from_secs(5), closed.recv())
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(id.starts_with("ws-"));
+}
+
+#[tokio::test]
+async fn pongs_keep_connections_alive() {
+    let (mut client, _closed) = connect(WebSocketConfig {
+        ping_interval: Some(Duration::from_millis(50)),
+        idle_timeout: Some(Duration::from_millis(300)),
+        ..Default::default()
+    })
+    .await;
+
+    // Reading answers the server's pings, well past the idle timeout
+    let deadline = tokio::time::Instant::now() + Duration::from_millis(600);
+    while tokio::time::Instant::now() < deadline {
+        assert!(matches!(next(&mut client).await, Message::Ping(_)));
+    }
+
+    // The connection still answers requests
+    let request = json!({ "jsonrpc": "2.0", "id": 1, "method": "ping" });
+    client
+        .send(Message::Text(request.to_string()))
+        .await
+        .unwrap();
+    let response = loop {
+        if let Message::Text(text) = next(&mut client).await {
+            break serde_json::from_str::<Value>(&text).unwrap();
+        }
+    };
+    assert_eq!(response["id"], 1);
+}
+
+#[tokio::test]
+async fn connections_are_closed_once_pongs_stop() {
+    let (mut client, mut closed) = connect(WebSocketConfig {
+        ping_interval: Some(Duration::from_millis(50)),
+        idle_timeout: Some(Duration::from_millis(300)),
+        ..Default::default()
+    })
+    .await;
+
+    // Reading answers the server's pings for a while
+    let deadline = tokio::time::Instant::now() + Duration::from_millis(400);
+    while tokio::time::Instant::now() < deadline {
+        assert!(matches!(next(&mut client).await, Message::Ping(_)));
+    }
+
+    // Then the client goes quiet without closing its socket
+    tokio::time::sleep(Duration::from_millis(500)).await;
+    let close = loop {
+        match next(&mut client).await {
+            Message::Ping(_) => continue,
+            other => break other,
+        }
+    };
+    assert_eq!(close_code(close), CloseCode::Away);
+    assert!(
+        tokio::time::timeout(Duration::from_secs(5), closed.recv())
+            .await
+            .unwrap()
+            .is_some()
+    );
+}
+
+#[tokio::test]
+async fn oversized_messages_are_rejected() {
+    let (mut client, mut closed) = connect(WebSocketConfig {
+        max_message_size: 1024,
+        ..Default::default()
+    })
+    .await;
+
+    let request = json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": "ping",
+        "params": { "padding": "x".repeat(4096) }
+    });
+    client
+        .send(Message::Text(request.to_string()))
+        .await
+        .unwrap();
+
+    let Message::Text(text) = next(&mut client).await else {
+        panic!("expected an error response");
+    };
+    let error: Value = serde_json::from_str(&text).unwrap();
+    assert_eq!(error["id"], Value::Null);
+    assert_eq!(error["error"]["code"], -32600);
+
+    assert_eq!(close_code(next(&mut client).await), CloseCode::Size);
+    assert!(
+        tokio::time::timeout(Duration::from_secs(5), closed.recv())
+            .await
+            .unwrap()
+            .is_some()
+    );
+}
+
+#[tokio::test]
+async fn list_changes_reach_every_connection() {
+    let mut server = McpServer::new(
+        support::implementation("websocket-server"),
+        ServerOptions::default(),
+    );
+    server.register_tool(support::tool("first"), no_op).unwrap();
+    let server = Arc::new(server);
+    let (addr, _closed) = serve(Arc::clone(&server), WebSocketConfig::default()).await;
+
+    let mut a = client(addr).await;
+    let mut b = client(addr).await;
+    // A round trip makes sure both connections are registered
+    request(&mut a, 1, "tools/list", json!({})).await;
+    request(&mut b, 1, "tools/list", json!({})).await;
+
+    server.add_tool_after_init(support::tool("second"), no_op);
+
+    for client in [&mut a, &mut b] {
+        let notification = next_json(client).await;
+        assert_eq!(notification["method"], "notifications/tools/list_changed");
+        let tools = request(client, 2, "tools/list", json!({})).await;
+        assert_eq!(tools["result"]["tools"].as_array().unwrap().len(), 2);
+    }
+}
+
+#[tokio::test]
+async fn resource_updates_reach_subscribers_only() {
+    let mut server = McpServer::new(
+        support::implementation("websocket-server"),
+        ServerOptions::default(),
+    );
+    server
+        .register_resource(resource("file:///watched"), empty)
+        .unwrap();
+    let server = Arc::new(server);
+    let (addr, mut closed) = serve(Arc::clone(&server), WebSocketConfig::default()).await;
+
+    let mut subscriber = client(addr).await;
+    let mut bystander = client(addr).await;
+    let response = request(
+        &mut subscriber,
+        1,
+        "resources/subscribe",
+        json!({ "uri": "file:///watched" }),
+    )
+    .await;
+    assert!(response.get("error").is_none(), "{response}");
+    request(&mut bystander, 1, "resources/list", json!({})).await;
+
+    assert_eq!(server.notify_resource_updated("file:///watched"), 1);
+    let notification = next_json(&mut subscriber).await;
+    assert_eq!(notification["method"], "notifications/resources/updated");
+    assert_eq!(notification["params"]["uri"], "file:///watched");
+
+    // The bystander's next message is the response to its own request
+    let list = json!({ "jsonrpc": "2.0", "id": 2, "method": "resources/list" });
+    bystander.send(Message::Text(list.to_string())).await.unwrap();
+    assert_eq!(next_json(&mut bystander).await["id"], 2);
+
+    // Closing the connection drops its subscriptions
+    subscriber.close(None).await.unwrap();
+    tokio::time::timeout(Duration::from_secs(5), closed.recv())
+        .await
+        .unwrap();
+    assert_eq!(server.notify_resource_updated("file:///watched"), 0);
+}
+
+/// Connect to `addr` offering `protocols` as `Sec-WebSocket-Protocol`.
+async fn connect_offering(
+    addr: SocketAddr,
+    protocols: &str,
+) -> Result<(Client, Option<String>), tokio_tungstenite::tungstenite::Error> {
+    let mut request = format!("ws://{addr}/ws").into_client_request().unwrap();
+    request
+        .headers_mut()
+        .insert("Sec-WebSocket-Protocol", protocols.parse().unwrap());
+    let (client, response) = connect_async(request).await?;
+    let selected = response
+        .headers()
+        .get("Sec-WebSocket-Protocol")
+        .map(|protocol| protocol.to_str().unwrap().to_string());
+    Ok((client, selected))
+}
+
+#[tokio::test]
+async fn the_preferred_offered_subprotocol_is_negotiated() {
+    let mut server = McpServer::new(
+        support::implementation("websocket-server"),
+        ServerOptions::default(),
+    );
+    // Answers with the connection id its requests carry
+    server
+        .register_tool(support::tool("whoami"), |_args, ctx: RequestContext| async move {
+            Ok(CallToolResult {
+                content: vec![ContentBlock::Text(TextContent::new(
+                    ctx.session_id.unwrap_or_default(),
+                ))],
+                structured_content: None,
+                is_error: None,
+                meta: None,
+            })
+        })
+        .unwrap();
+    let state = Arc::new(WebSocketState::new(
+        Arc::new(server),
+        WebSocketConfig::default(),
+    ));
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let router = create_websocket_router(Arc::clone(&state));
+    tokio::spawn(async move { axum::serve(listener, router).await });
+
+    let (mut client, selected) = connect_offering(addr, "mcp,mcp.v1").await.unwrap();
+    assert_eq!(selected.as_deref(), Some("mcp.v1"));
+
+    let response = request(&mut client, 1, "tools/call", json!({ "name": "whoami" })).await;
+    let connection_id = response["result"]["content"][0]["text"].as_str().unwrap();
+    assert_eq!(
+        state.subprotocol(connection_id).await.as_deref(),
+        Some("mcp.v1")
+    );
+
+    let (_client, selected) = connect_offering(addr, "mcp").await.unwrap();
+    assert_eq!(selected.as_deref(), Some("mcp"));
+}
+
+#[tokio::test]
+async fn unsupported_subprotocols_are_rejected() {
+    let server = McpServer::new(
+        support::implementation("websocket-server"),
+        ServerOptions::default(),
+    );
+    let (addr, _closed) = serve(Arc::new(server), WebSocketConfig::default()).await;
+
+    match connect_offering(addr, "mcp.v2, graphql-ws").await {
+        Err(tokio_tungstenite::tungstenite::Error::Http(response)) => {
+            assert_eq!(response.status(), 400);
+        }
+        Ok(_) => panic!("upgrade should be rejected"),
+        Err(other) => panic!("unexpected error: {other}"),
+    }
+}
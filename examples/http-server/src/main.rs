@@ -40,15 +40,20 @@
 use std::sync::Arc;
 use std::time::Duration;
 
-use mcp_core::protocol::RequestContext;
+use mcp_core::protocol::{CancellationToken, RequestContext};
 use mcp_core::types::{
     BaseMetadata, CallToolResult, ContentBlock, Icons, Implementation, ResourceLink,
     ServerCapabilities, TextContent, Tool,
 };
 use mcp_server::{
-    AxumHandlerConfig, AxumHandlerState, McpServer, ServerError, ServerOptions, create_router,
+    AxumHandlerConfig, AxumHandlerState, CorsPolicy, McpServer, ServerError, ServerOptions,
+    ToolInput, TypedTool, create_router_with_shutdown,
 };
-use serde_json::json;
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+
+/// How long in-flight requests get to finish once a shutdown signal arrives.
+const DRAIN_TIMEOUT: Duration = Duration::from_secs(10);
 
 #[tokio::main]
 async fn main() {
@@ -95,13 +100,24 @@ async fn run() -> Result<(), Box<dyn std::error::Error>> {
         endpoint_path: "/mcp".to_string(),
         keep_alive_interval: Duration::from_secs(30),
         broadcast_capacity: 100,
-        enable_cors: true,
+        cors: CorsPolicy::permissive(),
         ..Default::default()
     };
 
     // Create handler state and router
-    let state = Arc::new(AxumHandlerState::new(mcp_server, config));
-    let app = create_router(state);
+    let state = Arc::new(AxumHandlerState::new(Arc::clone(&mcp_server), config));
+    let shutdown = CancellationToken::default();
+    let app = create_router_with_shutdown(state, shutdown.clone());
+
+    // On Ctrl-C or SIGTERM, let in-flight requests finish before the open
+    // streams are ended
+    let signal = shutdown.clone();
+    tokio::spawn(async move {
+        shutdown_signal().await;
+        println!("Draining in-flight requests...");
+        mcp_server.drain(DRAIN_TIMEOUT).await;
+        shutdown.cancel();
+    });
 
     // Start server
     let addr = "0.0.0.0:8080";
@@ -131,59 +147,104 @@ async fn run() -> Result<(), Box<dyn std::error::Error>> {
     println!(r#"  curl -N http://localhost:8080/mcp -H "Accept: text/event-stream""#);
     println!();
 
-    axum::serve(listener, app).await?;
+    axum::serve(listener, app)
+        .with_graceful_shutdown(async move { signal.cancelled().await })
+        .await?;
 
     Ok(())
 }
 
-fn register_tools(server: &mut McpServer) -> Result<(), Box<dyn std::error::Error>> {
-    // Register an echo tool
-    server.register_tool(
-        Tool {
-            base: BaseMetadata {
-                name: "echo".to_string(),
-                title: Some("Echo Tool".to_string()),
-            },
-            icons: Icons::default(),
-            description: Some("Echoes back the input message".to_string()),
-            input_schema: json!({
-                "type": "object",
-                "properties": {
-                    "message": {
-                        "type": "string",
-                        "description": "The message to echo"
-                    }
-                },
-                "required": ["message"]
-            }),
-            output_schema: None,
-            annotations: None,
-            execution: None,
-            meta: None,
-        },
-        |params: Option<serde_json::Value>, _context: RequestContext| {
-            Box::pin(async move {
-                let message = params
-                    .as_ref()
-                    .and_then(|p| p.get("message"))
-                    .and_then(|v| v.as_str())
-                    .unwrap_or("(no message)");
+/// Resolve on Ctrl-C, or on SIGTERM where available.
+async fn shutdown_signal() {
+    let ctrl_c = async {
+        tokio::signal::ctrl_c().await.ok();
+    };
 
-                Ok::<_, ServerError>(CallToolResult {
-                    content: vec![ContentBlock::Text(TextContent::new(format!(
-                        "Echo: {}",
-                        message
-                    )))],
-                    structured_content: None,
-                    is_error: None,
-                    meta: None,
-                })
-            })
-        },
-    )?;
+    #[cfg(unix)]
+    let terminate = async {
+        match tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate()) {
+            Ok(mut sigterm) => {
+                sigterm.recv().await;
+            }
+            Err(_) => std::future::pending::<()>().await,
+        }
+    };
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
 
-    // Register a greeting tool
-    server.register_tool(
+    tokio::select! {
+        _ = ctrl_c => {},
+        _ = terminate => {},
+    }
+}
+
+#[derive(Deserialize, ToolInput)]
+struct EchoInput {
+    #[tool_input(description = "The message to echo")]
+    message: String,
+}
+
+#[derive(Serialize)]
+struct EchoOutput {
+    echo: String,
+}
+
+/// Echoes back the input message.
+struct Echo;
+
+impl TypedTool for Echo {
+    type Args = EchoInput;
+    type Output = EchoOutput;
+
+    fn name() -> &'static str {
+        "echo"
+    }
+
+    fn title() -> Option<&'static str> {
+        Some("Echo Tool")
+    }
+
+    fn description() -> &'static str {
+        "Echoes back the input message"
+    }
+
+    fn input_schema() -> Value {
+        EchoInput::input_schema()
+    }
+
+    async fn call(args: EchoInput, _ctx: RequestContext) -> Result<EchoOutput, ServerError> {
+        Ok(EchoOutput { echo: args.message })
+    }
+}
+
+#[derive(Deserialize, ToolInput)]
+struct GreetInput {
+    #[tool_input(description = "Name to greet")]
+    name: String,
+}
+
+#[derive(Serialize)]
+struct GreetOutput {
+    greeting: String,
+}
+
+#[derive(Deserialize, ToolInput)]
+struct ListFilesInput {
+    #[tool_input(description = "Whether to include descriptions in the resource links")]
+    include_descriptions: Option<bool>,
+}
+
+#[derive(Serialize)]
+struct ListFilesOutput {
+    files: Vec<ResourceLink>,
+}
+
+fn register_tools(server: &mut McpServer) -> Result<(), Box<dyn std::error::Error>> {
+    // Register an echo tool, statically typed
+    server.register_tool_type::<Echo>()?;
+
+    // Register a greeting tool, with arguments parsed into GreetInput
+    server.register_typed_tool(
         Tool {
             base: BaseMetadata {
                 name: "greet".to_string(),
@@ -191,38 +252,18 @@ fn register_tools(server: &mut McpServer) -> Result<(), Box<dyn std::error::Erro
             },
             icons: Icons::default(),
             description: Some("Generates a greeting message".to_string()),
-            input_schema: json!({
-                "type": "object",
-                "properties": {
-                    "name": {
-                        "type": "string",
-                        "description": "Name to greet"
-                    }
-                },
-                "required": ["name"]
-            }),
+            input_schema: GreetInput::input_schema(),
             output_schema: None,
             annotations: None,
             execution: None,
             meta: None,
         },
-        |params: Option<serde_json::Value>, _context: RequestContext| {
-            Box::pin(async move {
-                let name = params
-                    .as_ref()
-                    .and_then(|p| p.get("name"))
-                    .and_then(|v| v.as_str())
-                    .unwrap_or("World");
-
-                Ok::<_, ServerError>(CallToolResult {
-                    content: vec![ContentBlock::Text(TextContent::new(format!(
-                        "Hello, {}! Welcome to MCP over HTTP with SSE streaming.",
-                        name
-                    )))],
-                    structured_content: None,
-                    is_error: None,
-                    meta: None,
-                })
+        |input: GreetInput, _context: RequestContext| async move {
+            Ok::<_, ServerError>(GreetOutput {
+                greeting: format!(
+                    "Hello, {}! Welcome to MCP over HTTP with SSE streaming.",
+                    input.name
+                ),
             })
         },
     )?;
@@ -265,8 +306,9 @@ fn register_tools(server: &mut McpServer) -> Result<(), Box<dyn std::error::Erro
         },
     )?;
 
-    // Register a tool that returns ResourceLinks
-    server.register_tool(
+    // Register a tool that returns ResourceLinks, with arguments parsed into
+    // ListFilesInput
+    server.register_typed_tool(
         Tool {
             base: BaseMetadata {
                 name: "list_files".to_string(),
@@ -274,55 +316,31 @@ fn register_tools(server: &mut McpServer) -> Result<(), Box<dyn std::error::Erro
             },
             icons: Icons::default(),
             description: Some("Returns a list of files as ResourceLinks without embedding their content".to_string()),
-            input_schema: json!({
-                "type": "object",
-                "properties": {
-                    "include_descriptions": {
-                        "type": "boolean",
-                        "description": "Whether to include descriptions in the resource links"
-                    }
-                }
-            }),
+            input_schema: ListFilesInput::input_schema(),
             output_schema: None,
             annotations: None,
             execution: None,
             meta: None,
         },
-        |params: Option<serde_json::Value>, _context: RequestContext| {
-            Box::pin(async move {
-                let include_descriptions = params
-                    .as_ref()
-                    .and_then(|p| p.get("include_descriptions"))
-                    .and_then(|v| v.as_bool())
-                    .unwrap_or(true);
-
-                // Create ResourceLinks for example files
-                let mut link1 = ResourceLink::with_uri("file:///example/file1.txt", "example-file-1")
-                    .mime_type("text/plain");
-                let mut link2 = ResourceLink::with_uri("file:///example/file2.txt", "example-file-2")
-                    .mime_type("text/plain");
-                let mut link3 = ResourceLink::with_uri("https://example.com/data.json", "remote-data")
-                    .mime_type("application/json");
-
-                if include_descriptions {
-                    link1 = link1.description("First example file");
-                    link2 = link2.description("Second example file");
-                    link3 = link3.description("Remote JSON data");
-                }
+        |input: ListFilesInput, _context: RequestContext| async move {
+            let include_descriptions = input.include_descriptions.unwrap_or(true);
 
-                Ok::<_, ServerError>(CallToolResult {
-                    content: vec![
-                        ContentBlock::Text(TextContent::new(
-                            "Here are the available files as resource links:"
-                        )),
-                        ContentBlock::ResourceLink(link1),
-                        ContentBlock::ResourceLink(link2),
-                        ContentBlock::ResourceLink(link3),
-                    ],
-                    structured_content: None,
-                    is_error: None,
-                    meta: None,
-                })
+            // Create ResourceLinks for example files
+            let mut link1 = ResourceLink::with_uri("file:///example/file1.txt", "example-file-1")
+                .mime_type("text/plain");
+            let mut link2 = ResourceLink::with_uri("file:///example/file2.txt", "example-file-2")
+                .mime_type("text/plain");
+            let mut link3 = ResourceLink::with_uri("https://example.com/data.json", "remote-data")
+                .mime_type("application/json");
+
+            if include_descriptions {
+                link1 = link1.description("First example file");
+                link2 = link2.description("Second example file");
+                link3 = link3.description("Remote JSON data");
+            }
+
+            Ok::<_, ServerError>(ListFilesOutput {
+                files: vec![link1, link2, link3],
             })
         },
     )?;
@@ -43,7 +43,7 @@ use mcp_core::types::{
     ServerCapabilities, TextContent, Tool,
 };
 use mcp_server::{
-    AxumHandlerConfig, AxumHandlerState, McpServer, ServerError, ServerOptions, create_router,
+    AxumHandlerConfig, AxumHandlerState, CorsPolicy, McpServer, ServerError, ServerOptions, create_router,
 };
 use serde_json::json;
 
@@ -96,7 +96,7 @@ async fn run() -> Result<(), Box<dyn std::error::Error>> {
         endpoint_path: "/mcp".to_string(),
         keep_alive_interval: Duration::from_secs(30),
         broadcast_capacity: 100,
-        enable_cors: true,
+        cors: CorsPolicy::permissive(),
         ..Default::default()
     };
 
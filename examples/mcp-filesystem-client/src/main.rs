@@ -116,6 +116,7 @@ fn run() -> Result<(), Box<dyn std::error::Error>> {
     }
 
     transport.close()?;
+    println!("Transport stats: {}", transport.stats());
     Ok(())
 }
 
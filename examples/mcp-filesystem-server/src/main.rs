@@ -1,19 +1,49 @@
-use std::io::{self, BufRead, BufReader, Write};
+mod resource_watcher;
+
 use std::path::{Path, PathBuf};
-use std::sync::{Arc, Mutex};
+use std::sync::{Arc, Weak};
+use std::time::Duration;
 
 use futures::executor::block_on;
-use mcp_core::stdio::{JsonRpcMessage, serialize_message};
 use mcp_core::types::{
-    BaseMetadata, CallToolResult, ContentBlock, Icons, Implementation, ReadResourceResult,
-    RequestMessage, Resource, ServerCapabilities, TextContent, Tool,
+    BaseMetadata, CallToolResult, ContentBlock, Icons, ReadResourceResult, Resource, TextContent,
+    Tool,
 };
-use mcp_server::{McpServer, ServerError, ServerOptions};
+use mcp_server::{McpServer, ServerError, ToolInput, stdio};
+use serde::Deserialize;
 use serde_json::{Value, json};
 
-struct FilesystemState {
-    roots: Vec<Value>,
-    initialized: bool,
+use resource_watcher::ResourceWatcher;
+
+/// How often watched roots are checked for changes.
+const WATCH_INTERVAL: Duration = Duration::from_secs(1);
+/// Read the contents of a file
+#[derive(Deserialize, ToolInput)]
+struct ReadFileInput {
+    #[tool_input(description = "The path to the file to read")]
+    path: String,
+}
+
+/// Write contents to a file
+#[derive(Deserialize, ToolInput)]
+struct WriteFileInput {
+    #[tool_input(description = "The path to the file to write")]
+    path: String,
+    #[tool_input(description = "The contents to write to the file")]
+    contents: String,
+}
+
+/// List the contents of a directory
+#[derive(Deserialize, ToolInput)]
+struct ListDirectoryInput {
+    #[tool_input(description = "The path to the directory to list")]
+    path: String,
+}
+
+/// Parse tool arguments into `I`.
+fn parse_arguments<I: ToolInput>(arguments: Option<Value>) -> Result<I, ServerError> {
+    I::from_arguments(arguments)
+        .map_err(|message| ServerError::Handler(format!("invalid arguments: {message}")))
 }
 
 fn main() {
@@ -24,112 +54,68 @@ fn main() {
 }
 
 fn run() -> Result<(), Box<dyn std::error::Error>> {
-    let server_info = Implementation {
-        base: BaseMetadata {
-            name: "mcp-filesystem-server".to_string(),
-            title: None,
-        },
-        icons: Icons::default(),
-        version: "0.1.0".to_string(),
-        website_url: None,
-        description: None,
-    };
-
-    let mut server_options = ServerOptions::default();
-    server_options.capabilities = Some(ServerCapabilities {
-        resources: Some(mcp_core::types::ResourceCapabilities {
-            subscribe: None,
-            list_changed: Some(true),
-        }),
-        ..Default::default()
-    });
-    server_options.instructions =
-        Some("Filesystem MCP server that provides file operations.".to_string());
-
-    let mut server = McpServer::new(server_info.clone(), server_options);
+    // Resources are registered once the client reports its roots
+    let mut server = McpServer::builder("mcp-filesystem-server", "0.1.0")
+        .instructions("Filesystem MCP server that provides file operations.")
+        .enable_resources(true, true)
+        .build()?;
 
     register_filesystem_tools(&mut server)?;
     register_filesystem_resources(&mut server)?;
 
-    let state = Arc::new(Mutex::new(FilesystemState {
-        roots: Vec::new(),
-        initialized: false,
-    }));
-
-    let state_for_init = state.clone();
-    server
-        .server_mut()
-        .set_on_initialized(Some(Arc::new(move || {
-            let mut state = state_for_init.lock().unwrap();
-            state.initialized = true;
-        })));
-
-    let stdin = io::stdin();
-    let mut stdout = io::stdout();
-    let mut reader = BufReader::new(stdin.lock());
-    let mut buffer = String::new();
-    let mut read_buffer = mcp_core::stdio::ReadBuffer::default();
-
-    loop {
-        buffer.clear();
-        let bytes_read = reader.read_line(&mut buffer)?;
-        if bytes_read == 0 {
-            break;
-        }
-
-        read_buffer.append(buffer.as_bytes());
-        while let Ok(Some(message)) = read_buffer.read_message() {
-            match message {
-                JsonRpcMessage::Request(request) => {
-                    let response = block_on(server.server().handle_request(request, None))?;
-                    let response_msg = JsonRpcMessage::Result(response);
-                    let serialized = serialize_message(&response_msg)?;
-                    stdout.write_all(serialized.as_bytes())?;
-                    stdout.flush()?;
+    let watcher = Arc::new(ResourceWatcher::default());
+    let server = Arc::new_cyclic(|weak: &Weak<McpServer>| {
+        let weak = weak.clone();
+        let watcher = watcher.clone();
+        server
+            .server_mut()
+            .set_on_initialized(Some(Arc::new(move || {
+                if let Some(server) = weak.upgrade() {
+                    spawn_roots_request(server, watcher.clone());
                 }
-                JsonRpcMessage::Notification(notification) => {
-                    let method = notification.method.clone();
-                    block_on(server.server().handle_notification(notification, None))?;
-
-                    let state = state.lock().unwrap();
-                    if state.initialized && method == "notifications/initialized" {
-                        drop(state);
-                        send_roots_list_request(&mut stdout)?;
-                    }
-                }
-                JsonRpcMessage::Result(result) => {
-                    let state = state.lock().unwrap();
-                    if let Some(result_value) = &result.result {
-                        if let Some(roots) = result_value.get("roots") {
-                            if let Some(roots_array) = roots.as_array() {
-                                let mut state = state;
-                                state.roots = roots_array.clone();
-                                // Register resources directly without modifying capabilities
-                                register_resources_from_roots_after_init(&server, &state.roots);
-                                // Send resource list changed notification
-                                let notification = server.resource_list_changed_notification();
-                                let notification_msg = JsonRpcMessage::Notification(notification);
-                                let serialized = serialize_message(&notification_msg)?;
-                                stdout.write_all(serialized.as_bytes())?;
-                                stdout.flush()?;
-                            }
-                        }
-                    }
-                }
-            }
-        }
-    }
+            })));
+        server
+    });
+    spawn_watch_loop(server.clone(), watcher);
 
+    stdio::run(server.clone())?;
+
+    eprintln!("{}", server.summary_report());
     Ok(())
 }
 
-fn send_roots_list_request(stdout: &mut io::Stdout) -> Result<(), Box<dyn std::error::Error>> {
-    let request = RequestMessage::new("server-roots-list", "roots/list", json!({}));
-    let request_msg = JsonRpcMessage::Request(request);
-    let serialized = serialize_message(&request_msg)?;
-    stdout.write_all(serialized.as_bytes())?;
-    stdout.flush()?;
-    Ok(())
+/// Ask the client for its roots and expose them as resources. Runs on its
+/// own thread because the response arrives through the stdio read loop.
+fn spawn_roots_request(server: Arc<McpServer>, watcher: Arc<ResourceWatcher>) {
+    std::thread::spawn(move || {
+        let peer = server.server().peer(None);
+        let result = match block_on(peer.request("roots/list", json!({}))) {
+            Ok(result) => result,
+            Err(error) => {
+                eprintln!("roots/list failed: {error}");
+                return;
+            }
+        };
+        if let Some(roots) = result.get("roots").and_then(|roots| roots.as_array()) {
+            // Register resources directly without modifying capabilities
+            register_resources_from_roots_after_init(&server, &watcher, roots);
+            server
+                .server()
+                .broadcast_notification(server.resource_list_changed_notification());
+        }
+    });
+}
+
+/// Tell subscribed clients about roots whose files changed on disk.
+fn spawn_watch_loop(server: Arc<McpServer>, watcher: Arc<ResourceWatcher>) {
+    std::thread::spawn(move || {
+        loop {
+            std::thread::sleep(WATCH_INTERVAL);
+            for uri in watcher.poll() {
+                server.notify_resource_updated(&uri);
+            }
+        }
+    });
 }
 
 fn register_filesystem_tools(server: &mut McpServer) -> Result<(), ServerError> {
@@ -139,17 +125,8 @@ fn register_filesystem_tools(server: &mut McpServer) -> Result<(), ServerError>
             title: None,
         },
         icons: Icons::default(),
-        description: Some("Read the contents of a file".to_string()),
-        input_schema: json!({
-            "type": "object",
-            "properties": {
-                "path": {
-                    "type": "string",
-                    "description": "The path to the file to read"
-                }
-            },
-            "required": ["path"]
-        }),
+        description: ReadFileInput::description().map(str::to_string),
+        input_schema: ReadFileInput::json_schema(),
         output_schema: None,
         annotations: None,
         execution: None,
@@ -160,12 +137,7 @@ fn register_filesystem_tools(server: &mut McpServer) -> Result<(), ServerError>
         read_file_tool,
         |arguments: Option<Value>, _context: mcp_core::protocol::RequestContext| {
             Box::pin(async move {
-                let args = arguments.as_ref().and_then(|a| a.as_object());
-                let path = args
-                    .and_then(|a| a.get("path"))
-                    .and_then(|v| v.as_str())
-                    .map(|s| s.to_string())
-                    .ok_or_else(|| ServerError::Handler("missing path argument".to_string()))?;
+                let ReadFileInput { path } = parse_arguments(arguments)?;
 
                 let path = uri_to_path(&path)?;
                 let contents = std::fs::read_to_string(&path)
@@ -192,21 +164,8 @@ fn register_filesystem_tools(server: &mut McpServer) -> Result<(), ServerError>
             title: None,
         },
         icons: Icons::default(),
-        description: Some("Write contents to a file".to_string()),
-        input_schema: json!({
-            "type": "object",
-            "properties": {
-                "path": {
-                    "type": "string",
-                    "description": "The path to the file to write"
-                },
-                "contents": {
-                    "type": "string",
-                    "description": "The contents to write to the file"
-                }
-            },
-            "required": ["path", "contents"]
-        }),
+        description: WriteFileInput::description().map(str::to_string),
+        input_schema: WriteFileInput::json_schema(),
         output_schema: None,
         annotations: None,
         execution: None,
@@ -217,18 +176,7 @@ fn register_filesystem_tools(server: &mut McpServer) -> Result<(), ServerError>
         write_file_tool,
         |arguments: Option<Value>, _context: mcp_core::protocol::RequestContext| {
             Box::pin(async move {
-                let args = arguments.as_ref().and_then(|a| a.as_object());
-                let path = args
-                    .and_then(|a| a.get("path"))
-                    .and_then(|v| v.as_str())
-                    .map(|s| s.to_string())
-                    .ok_or_else(|| ServerError::Handler("missing path argument".to_string()))?;
-
-                let contents = args
-                    .and_then(|a| a.get("contents"))
-                    .and_then(|v| v.as_str())
-                    .map(|s| s.to_string())
-                    .ok_or_else(|| ServerError::Handler("missing contents argument".to_string()))?;
+                let WriteFileInput { path, contents } = parse_arguments(arguments)?;
 
                 let path = uri_to_path(&path)?;
                 std::fs::write(&path, contents)
@@ -255,17 +203,8 @@ fn register_filesystem_tools(server: &mut McpServer) -> Result<(), ServerError>
             title: None,
         },
         icons: Icons::default(),
-        description: Some("List the contents of a directory".to_string()),
-        input_schema: json!({
-            "type": "object",
-            "properties": {
-                "path": {
-                    "type": "string",
-                    "description": "The path to the directory to list"
-                }
-            },
-            "required": ["path"]
-        }),
+        description: ListDirectoryInput::description().map(str::to_string),
+        input_schema: ListDirectoryInput::json_schema(),
         output_schema: None,
         annotations: None,
         execution: None,
@@ -276,12 +215,7 @@ fn register_filesystem_tools(server: &mut McpServer) -> Result<(), ServerError>
         list_directory_tool,
         |arguments: Option<Value>, _context: mcp_core::protocol::RequestContext| {
             Box::pin(async move {
-                let args = arguments.as_ref().and_then(|a| a.as_object());
-                let path = args
-                    .and_then(|a| a.get("path"))
-                    .and_then(|v| v.as_str())
-                    .map(|s| s.to_string())
-                    .ok_or_else(|| ServerError::Handler("missing path argument".to_string()))?;
+                let ListDirectoryInput { path } = parse_arguments(arguments)?;
 
                 let path = uri_to_path(&path)?;
                 let entries = std::fs::read_dir(&path)
@@ -323,7 +257,11 @@ fn register_filesystem_resources(_server: &mut McpServer) -> Result<(), ServerEr
     Ok(())
 }
 
-fn register_resources_from_roots_after_init(server: &McpServer, roots: &[Value]) {
+fn register_resources_from_roots_after_init(
+    server: &McpServer,
+    watcher: &ResourceWatcher,
+    roots: &[Value],
+) {
     for root in roots {
         if let Some(uri) = root.get("uri").and_then(|u| u.as_str()) {
             if let Ok(path) = uri_to_path(uri) {
@@ -350,6 +288,7 @@ fn register_resources_from_roots_after_init(server: &McpServer, roots: &[Value])
                         meta: None,
                     };
 
+                    watcher.watch(uri, path.clone());
                     let uri = uri.to_string();
                     server.add_resource_after_init(resource, move |_uri, _context| {
                         let uri = uri.clone();
@@ -417,6 +356,7 @@ fn register_resources_from_roots_after_init(server: &McpServer, roots: &[Value])
                         meta: None,
                     };
 
+                    watcher.watch(uri, path.clone());
                     let uri = uri.to_string();
                     server.add_resource_after_init(resource, move |_uri, _context| {
                         let uri = uri.clone();
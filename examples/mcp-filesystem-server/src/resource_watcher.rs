@@ -0,0 +1,73 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::SystemTime;
+
+/// Modification time and size; a directory's changes when entries are
+/// added or removed.
+type Stamp = Option<(SystemTime, u64)>;
+
+/// Polls the files and directories behind resources and reports the URIs of
+/// those that changed since the previous poll.
+#[derive(Default)]
+pub struct ResourceWatcher {
+    watched: Mutex<HashMap<String, (PathBuf, Stamp)>>,
+}
+
+impl ResourceWatcher {
+    pub fn watch(&self, uri: impl Into<String>, path: PathBuf) {
+        let stamp = stamp(&path);
+        self.watched
+            .lock()
+            .unwrap()
+            .insert(uri.into(), (path, stamp));
+    }
+
+    /// URIs whose path was modified, created or removed since the last poll.
+    pub fn poll(&self) -> Vec<String> {
+        let mut watched = self.watched.lock().unwrap();
+        let mut changed = Vec::new();
+        for (uri, (path, last)) in watched.iter_mut() {
+            let current = stamp(path);
+            if current != *last {
+                *last = current;
+                changed.push(uri.clone());
+            }
+        }
+        changed
+    }
+}
+
+fn stamp(path: &Path) -> Stamp {
+    let metadata = std::fs::metadata(path).ok()?;
+    Some((metadata.modified().ok()?, metadata.len()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reports_changed_files_once() {
+        let dir = std::env::temp_dir().join(format!("resource-watcher-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let watched = dir.join("watched.txt");
+        let other = dir.join("other.txt");
+        std::fs::write(&watched, "a").unwrap();
+        std::fs::write(&other, "a").unwrap();
+
+        let watcher = ResourceWatcher::default();
+        watcher.watch("file:///watched.txt", watched.clone());
+        watcher.watch("file:///other.txt", other);
+        assert!(watcher.poll().is_empty());
+
+        std::fs::write(&watched, "changed").unwrap();
+        assert_eq!(watcher.poll(), vec!["file:///watched.txt".to_string()]);
+        assert!(watcher.poll().is_empty());
+
+        std::fs::remove_file(&watched).unwrap();
+        assert_eq!(watcher.poll(), vec!["file:///watched.txt".to_string()]);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,252 @@
+//! Example: MCP Server Protected by an External Identity Provider
+//!
+//! This example demonstrates how to protect an MCP server with JWT access
+//! tokens issued by an external IdP such as Keycloak or Auth0:
+//! - `JwtTokenVerifier` checks signatures with keys from the IdP's JWKS URI
+//! - `BearerAuthLayer` rejects requests without a valid token with 401
+//! - Protected resource metadata (RFC 9728) points clients at the IdP
+//!
+//! Configuration (environment variables):
+//! - `MCP_JWT_ISSUER` - expected `iss` claim (default: http://localhost:8180/realms/mcp)
+//! - `MCP_JWT_AUDIENCE` - expected `aud` claim (default: mcp-server)
+//! - `MCP_JWKS_URI` - JWKS URI (default: Keycloak's certs endpoint for the issuer)
+//!
+//! Run with: cargo run -p mcp-oauth-protected-server
+//!
+//! Test with curl:
+//! ```bash
+//! # Without a token: 401 with a WWW-Authenticate challenge
+//! curl -i -X POST http://localhost:8080/mcp \
+//!      -H "Content-Type: application/json" \
+//!      -d '{"jsonrpc":"2.0","id":1,"method":"tools/list","params":{}}'
+//!
+//! # With an access token from the IdP
+//! curl -X POST http://localhost:8080/mcp \
+//!      -H "Content-Type: application/json" \
+//!      -H "Authorization: Bearer $TOKEN" \
+//!      -d '{"jsonrpc":"2.0","id":2,"method":"tools/call","params":{"name":"whoami","arguments":{}}}'
+//! ```
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use axum::Router;
+use axum::routing::get;
+use mcp_core::auth::OAuthProtectedResourceMetadata;
+use mcp_core::protocol::RequestContext;
+use mcp_core::types::{
+    BaseMetadata, CallToolResult, ContentBlock, Icons, Implementation, TextContent, Tool,
+};
+use mcp_server::auth::middleware::{BearerAuthLayer, BearerAuthOptions};
+use mcp_server::auth::{CacheConfig, CachingTokenVerifier, JwtTokenVerifier, OAuthTokenVerifier};
+use mcp_server::{
+    AxumHandlerConfig, AxumHandlerState, McpServer, ServerError, ServerOptions, SessionMode, create_router,
+};
+use serde_json::{Value, json};
+
+const RESOURCE_URL: &str = "http://localhost:8080";
+
+#[tokio::main]
+async fn main() {
+    if let Err(e) = run().await {
+        eprintln!("Server error: {}", e);
+        std::process::exit(1);
+    }
+}
+
+async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    let issuer = std::env::var("MCP_JWT_ISSUER").unwrap_or_else(|_| "http://localhost:8180/realms/mcp".to_string());
+    let audience = std::env::var("MCP_JWT_AUDIENCE").unwrap_or_else(|_| "mcp-server".to_string());
+    let jwks_uri = std::env::var("MCP_JWKS_URI")
+        .unwrap_or_else(|_| format!("{}/protocol/openid-connect/certs", issuer));
+
+    // Cache verified tokens so the signature is not rechecked on every request
+    let verifier = JwtTokenVerifier::new(&issuer, audience, &jwks_uri).with_leeway(Duration::from_secs(30));
+    let verifier = Arc::new(CachingTokenVerifier::new(verifier, CacheConfig::default()));
+
+    let app = app(verifier, &issuer)?;
+
+    let addr = "0.0.0.0:8080";
+    let listener = tokio::net::TcpListener::bind(addr).await?;
+
+    println!("MCP OAuth-protected server listening on http://{}", addr);
+    println!();
+    println!("Issuer:   {}", issuer);
+    println!("JWKS URI: {}", jwks_uri);
+    println!();
+    println!("Endpoints:");
+    println!("  POST http://{}/mcp - JSON-RPC, requires a bearer token", addr);
+    println!("  GET  http://{}/.well-known/oauth-protected-resource - Resource metadata", addr);
+
+    axum::serve(listener, app).await?;
+
+    Ok(())
+}
+
+/// MCP routes behind bearer authentication, plus the public resource metadata
+fn app<V: OAuthTokenVerifier + 'static>(verifier: Arc<V>, issuer: &str) -> Result<Router, Box<dyn std::error::Error>> {
+    let server_info = Implementation {
+        base: BaseMetadata {
+            name: "mcp-oauth-protected-server-example".to_string(),
+            title: Some("MCP OAuth-Protected Server Example".to_string()),
+        },
+        icons: Icons::default(),
+        version: "0.1.0".to_string(),
+        website_url: None,
+        description: Some("Example MCP server accepting JWT access tokens".to_string()),
+    };
+
+    let mut mcp_server = McpServer::new(server_info, ServerOptions::default());
+    register_tools(&mut mcp_server)?;
+
+    let config = AxumHandlerConfig {
+        session_mode: SessionMode::Stateless,
+        ..Default::default()
+    };
+    let state = Arc::new(AxumHandlerState::new(Arc::new(mcp_server), config));
+
+    let metadata_url = format!("{}/.well-known/oauth-protected-resource", RESOURCE_URL);
+    let options = BearerAuthOptions::new().with_resource_metadata_url(metadata_url);
+
+    let metadata = OAuthProtectedResourceMetadata {
+        resource: RESOURCE_URL.to_string(),
+        authorization_servers: Some(vec![issuer.to_string()]),
+        jwks_uri: None,
+        scopes_supported: None,
+        bearer_methods_supported: Some(vec!["header".to_string()]),
+        resource_name: Some("MCP OAuth-Protected Server Example".to_string()),
+        resource_documentation: None,
+    };
+
+    Ok(create_router(state)
+        .layer(BearerAuthLayer::with_options(verifier, options))
+        .route(
+            "/.well-known/oauth-protected-resource",
+            get(move || async move { axum::Json(metadata) }),
+        ))
+}
+
+fn register_tools(server: &mut McpServer) -> Result<(), Box<dyn std::error::Error>> {
+    // Report who the verified token belongs to
+    server.register_tool(
+        Tool {
+            base: BaseMetadata {
+                name: "whoami".to_string(),
+                title: Some("Who Am I".to_string()),
+            },
+            icons: Icons::default(),
+            description: Some("Returns the subject and scopes of the access token".to_string()),
+            input_schema: json!({ "type": "object", "properties": {} }),
+            output_schema: None,
+            annotations: None,
+            execution: None,
+            meta: None,
+        },
+        |_args: Option<Value>, ctx: RequestContext| async move {
+            // The bearer layer guarantees a verified token
+            let info = ctx
+                .auth_info
+                .ok_or_else(|| ServerError::Handler("missing auth info".to_string()))?;
+            let subject = info
+                .extra
+                .as_ref()
+                .and_then(|claims| claims.get("sub"))
+                .and_then(Value::as_str)
+                .unwrap_or("unknown")
+                .to_string();
+            let text = format!("subject: {}, scopes: {}", subject, info.scopes.join(" "));
+            Ok(CallToolResult {
+                content: vec![ContentBlock::Text(TextContent::new(text))],
+                structured_content: None,
+                is_error: None,
+                meta: None,
+            })
+        },
+    )?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    use async_trait::async_trait;
+    use axum::body::Body;
+    use axum::http::{Request, StatusCode, header};
+    use jsonwebtoken::{Algorithm, EncodingKey, Header};
+    use mcp_server::auth::{JwksFetcher, OAuthProviderError};
+    use tower::util::ServiceExt;
+
+    const ISSUER: &str = "https://idp.example.com";
+    const TEST_KEY: &str = include_str!("../fixtures/test_key.pem");
+    const TEST_MODULUS: &str = "rBrWUYSCSVXsNiF1-eGlFCeyPEiuOhcwP6QgtbwqS2K7AnKZKV9NQnwyQD-LrjMMepxAKyJiYeUlGKkOAcO2HzV0Iyc0IpzdNGHjLr_Oe43Ps1c9ZBcCFRs5ZpDVoK8Rc0NH11LWM91P6YJic-BbJiPfya9m-QK001LqX80Q27X6ANUO-MX5mqNNkPdo4gsBIwwnFujES-CtxAllvknu6sLWhix4Nz7QB2wMF_n3BX0sDJ5Xg-T03Sk-BT62ylbSmbs4ndW1huSwIIMoDgmTvACdExECQd-_N-BV_pq3wb4ctiyZOyhJ4r04zRdq8hkMoxlxyuYkSUITo3Ke0t2uKQ";
+
+    /// Serves the test key pair's public half
+    struct TestJwks;
+
+    #[async_trait]
+    impl JwksFetcher for TestJwks {
+        async fn fetch(&self, _jwks_uri: &str) -> Result<Value, OAuthProviderError> {
+            Ok(json!({
+                "keys": [{ "kty": "RSA", "kid": "test", "alg": "RS256", "n": TEST_MODULUS, "e": "AQAB" }]
+            }))
+        }
+    }
+
+    fn test_app() -> Router {
+        let verifier = JwtTokenVerifier::new(ISSUER, "mcp-server", "https://idp.example.com/jwks")
+            .with_fetcher(Arc::new(TestJwks));
+        app(Arc::new(verifier), ISSUER).unwrap()
+    }
+
+    fn mint() -> String {
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs();
+        let claims = json!({
+            "iss": ISSUER, "aud": "mcp-server", "sub": "alice",
+            "scope": "mcp:tools", "iat": now, "exp": now + 300
+        });
+        let mut header = Header::new(Algorithm::RS256);
+        header.kid = Some("test".to_string());
+        jsonwebtoken::encode(&header, &claims, &EncodingKey::from_rsa_pem(TEST_KEY.as_bytes()).unwrap()).unwrap()
+    }
+
+    async fn call_whoami(token: &str) -> axum::response::Response {
+        let request = Request::post("/mcp")
+            .header(header::CONTENT_TYPE, "application/json")
+            .header(header::ACCEPT, "application/json, text/event-stream")
+            .header(header::AUTHORIZATION, format!("Bearer {token}"))
+            .body(Body::from(
+                json!({
+                    "jsonrpc": "2.0", "id": 1, "method": "tools/call",
+                    "params": { "name": "whoami", "arguments": {} }
+                })
+                .to_string(),
+            ))
+            .unwrap();
+        test_app().oneshot(request).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn minted_token_is_accepted() {
+        let response = call_whoami(&mint()).await;
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body: Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body["result"]["content"][0]["text"], "subject: alice, scopes: mcp:tools");
+    }
+
+    #[tokio::test]
+    async fn tampered_signature_is_unauthorized() {
+        let token = mint();
+        let (signed, signature) = token.rsplit_once('.').unwrap();
+        let flipped = if signature.starts_with('A') { "B" } else { "A" };
+        let tampered = format!("{}.{}{}", signed, flipped, &signature[1..]);
+
+        let response = call_whoami(&tampered).await;
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        assert!(response.headers().contains_key(header::WWW_AUTHENTICATE));
+    }
+}
@@ -34,11 +34,12 @@ use std::time::Duration;
 
 use mcp_core::protocol::RequestContext;
 use mcp_core::types::{
-    BaseMetadata, ContentBlock, GetPromptResult, Icons, Implementation, Prompt, PromptArgument,
-    PromptMessage, Role, ServerCapabilities, TextContent,
+    BaseMetadata, ContentBlock, GetPromptResult, Icons, Prompt, PromptArgument, PromptMessage,
+    Role, TextContent,
 };
 use mcp_server::{
-    AxumHandlerConfig, AxumHandlerState, McpServer, ServerError, ServerOptions, create_router,
+    AxumHandlerConfig, AxumHandlerState, CorsPolicy, McpServer, McpServerBuilder, ServerError,
+    create_router,
 };
 
 #[tokio::main]
@@ -50,36 +51,8 @@ async fn main() {
 }
 
 async fn run() -> Result<(), Box<dyn std::error::Error>> {
-    // Create server info
-    let server_info = Implementation {
-        base: BaseMetadata {
-            name: "mcp-prompts-server".to_string(),
-            title: Some("MCP Prompts Server Example".to_string()),
-        },
-        icons: Icons::default(),
-        version: "0.1.0".to_string(),
-        website_url: None,
-        description: Some("Example MCP server demonstrating prompts functionality".to_string()),
-    };
-
-    // Configure server capabilities with prompts support
-    let mut server_options = ServerOptions::default();
-    server_options.capabilities = Some(ServerCapabilities {
-        prompts: Some(mcp_core::types::PromptCapabilities {
-            list_changed: Some(true),
-        }),
-        ..Default::default()
-    });
-    server_options.instructions =
-        Some("This server provides reusable prompt templates for LLM interactions.".to_string());
-
-    // Create MCP server
-    let mut mcp_server = McpServer::new(server_info, server_options);
-
-    // Register example prompts
-    register_prompts(&mut mcp_server)?;
-
-    let mcp_server = Arc::new(mcp_server);
+    // Capabilities are inferred from the registered prompts
+    let mcp_server = Arc::new(build_server()?);
 
     // Configure HTTP handler
     let config = AxumHandlerConfig {
@@ -87,7 +60,7 @@ async fn run() -> Result<(), Box<dyn std::error::Error>> {
         endpoint_path: "/mcp".to_string(),
         keep_alive_interval: Duration::from_secs(30),
         broadcast_capacity: 100,
-        enable_cors: true,
+        cors: CorsPolicy::permissive(),
         ..Default::default()
     };
 
@@ -125,271 +98,325 @@ async fn run() -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
-fn register_prompts(server: &mut McpServer) -> Result<(), Box<dyn std::error::Error>> {
-    // 1. Code Review Prompt
-    server.register_prompt(
-        Prompt {
-            base: BaseMetadata {
-                name: "code_review".to_string(),
-                title: Some("Code Review".to_string()),
-            },
-            icons: Icons::default(),
-            description: Some(
-                "Review code for best practices, bugs, and improvements".to_string(),
-            ),
-            arguments: Some(vec![
-                PromptArgument {
-                    name: "language".to_string(),
-                    description: Some("Programming language of the code".to_string()),
-                    required: Some(true),
-                },
-                PromptArgument {
-                    name: "code".to_string(),
-                    description: Some("The code to review".to_string()),
-                    required: Some(true),
-                },
-                PromptArgument {
-                    name: "focus".to_string(),
-                    description: Some(
-                        "Optional focus area (security, performance, readability)".to_string(),
-                    ),
-                    required: Some(false),
-                },
-            ]),
-            meta: None,
-        },
-        |args: Option<HashMap<String, String>>, _ctx: RequestContext| async move {
-            let args = args.unwrap_or_default();
-            let language = args.get("language").map(|s| s.as_str()).unwrap_or("unknown");
-            let code = args
-                .get("code")
-                .map(|s| s.as_str())
-                .unwrap_or("(no code provided)");
-            let focus = args.get("focus").map(|s| s.as_str());
-
-            let focus_instruction = match focus {
-                Some("security") => "Focus especially on security vulnerabilities.",
-                Some("performance") => "Focus especially on performance optimizations.",
-                Some("readability") => "Focus especially on code readability and clarity.",
-                _ => "Review all aspects including correctness, style, and best practices.",
-            };
-
-            let system_message = format!(
-                "You are an expert {} code reviewer. {}",
-                language, focus_instruction
-            );
-
-            let user_message = format!(
-                "Please review the following {} code:\n\n```{}\n{}\n```",
-                language, language, code
-            );
+fn build_server() -> Result<McpServer, ServerError> {
+    let builder = McpServer::builder("mcp-prompts-server", "0.1.0")
+        .title("MCP Prompts Server Example")
+        .description("Example MCP server demonstrating prompts functionality")
+        .instructions("This server provides reusable prompt templates for LLM interactions.");
+    with_prompts(builder).build()
+}
 
-            Ok::<_, ServerError>(GetPromptResult {
-                description: Some(format!("Code review for {} code", language)),
-                messages: vec![
-                    PromptMessage {
-                        role: Role::Assistant,
-                        content: ContentBlock::Text(TextContent::new(system_message)),
+fn with_prompts(builder: McpServerBuilder) -> McpServerBuilder {
+    builder
+        // 1. Code Review Prompt
+        .prompt(
+            Prompt {
+                base: BaseMetadata {
+                    name: "code_review".to_string(),
+                    title: Some("Code Review".to_string()),
+                },
+                icons: Icons::default(),
+                description: Some(
+                    "Review code for best practices, bugs, and improvements".to_string(),
+                ),
+                arguments: Some(vec![
+                    PromptArgument {
+                        name: "language".to_string(),
+                        description: Some("Programming language of the code".to_string()),
+                        required: Some(true),
                     },
-                    PromptMessage {
-                        role: Role::User,
-                        content: ContentBlock::Text(TextContent::new(user_message)),
+                    PromptArgument {
+                        name: "code".to_string(),
+                        description: Some("The code to review".to_string()),
+                        required: Some(true),
                     },
-                ],
+                    PromptArgument {
+                        name: "focus".to_string(),
+                        description: Some(
+                            "Optional focus area (security, performance, readability)".to_string(),
+                        ),
+                        required: Some(false),
+                    },
+                ]),
                 meta: None,
-            })
-        },
-    )?;
-
-    // 2. Explain Concept Prompt
-    server.register_prompt(
-        Prompt {
-            base: BaseMetadata {
-                name: "explain_concept".to_string(),
-                title: Some("Explain Concept".to_string()),
             },
-            icons: Icons::default(),
-            description: Some("Explain a technical concept at a specified level".to_string()),
-            arguments: Some(vec![
-                PromptArgument {
-                    name: "concept".to_string(),
-                    description: Some("The concept to explain".to_string()),
-                    required: Some(true),
-                },
-                PromptArgument {
-                    name: "level".to_string(),
-                    description: Some(
-                        "Explanation level: beginner, intermediate, or expert".to_string(),
-                    ),
-                    required: Some(false),
+            |args: Option<HashMap<String, String>>, _ctx: RequestContext| async move {
+                let args = args.unwrap_or_default();
+                let language = args
+                    .get("language")
+                    .map(|s| s.as_str())
+                    .unwrap_or("unknown");
+                let code = args
+                    .get("code")
+                    .map(|s| s.as_str())
+                    .unwrap_or("(no code provided)");
+                let focus = args.get("focus").map(|s| s.as_str());
+
+                let focus_instruction = match focus {
+                    Some("security") => "Focus especially on security vulnerabilities.",
+                    Some("performance") => "Focus especially on performance optimizations.",
+                    Some("readability") => "Focus especially on code readability and clarity.",
+                    _ => "Review all aspects including correctness, style, and best practices.",
+                };
+
+                let system_message = format!(
+                    "You are an expert {} code reviewer. {}",
+                    language, focus_instruction
+                );
+
+                let user_message = format!(
+                    "Please review the following {} code:\n\n```{}\n{}\n```",
+                    language, language, code
+                );
+
+                Ok::<_, ServerError>(GetPromptResult {
+                    description: Some(format!("Code review for {} code", language)),
+                    messages: vec![
+                        PromptMessage {
+                            role: Role::Assistant,
+                            content: ContentBlock::Text(TextContent::new(system_message)),
+                        },
+                        PromptMessage {
+                            role: Role::User,
+                            content: ContentBlock::Text(TextContent::new(user_message)),
+                        },
+                    ],
+                    meta: None,
+                })
+            },
+        )
+        // 2. Explain Concept Prompt
+        .prompt(
+            Prompt {
+                base: BaseMetadata {
+                    name: "explain_concept".to_string(),
+                    title: Some("Explain Concept".to_string()),
                 },
-            ]),
-            meta: None,
-        },
-        |args: Option<HashMap<String, String>>, _ctx: RequestContext| async move {
-            let args = args.unwrap_or_default();
-            let concept = args
-                .get("concept")
-                .map(|s| s.as_str())
-                .unwrap_or("(no concept provided)");
-            let level = args
-                .get("level")
-                .map(|s| s.as_str())
-                .unwrap_or("intermediate");
-
-            let level_instruction = match level {
-                "beginner" => "Explain in simple terms, avoiding jargon. Use analogies.",
-                "expert" => "Provide a detailed technical explanation with advanced concepts.",
-                _ => "Provide a balanced explanation suitable for someone with basic knowledge.",
-            };
-
-            let message = format!(
-                "Please explain the concept of \"{}\".\n\n{}",
-                concept, level_instruction
-            );
-
-            Ok::<_, ServerError>(GetPromptResult {
-                description: Some(format!("Explanation of {} at {} level", concept, level)),
-                messages: vec![PromptMessage {
-                    role: Role::User,
-                    content: ContentBlock::Text(TextContent::new(message)),
-                }],
+                icons: Icons::default(),
+                description: Some("Explain a technical concept at a specified level".to_string()),
+                arguments: Some(vec![
+                    PromptArgument {
+                        name: "concept".to_string(),
+                        description: Some("The concept to explain".to_string()),
+                        required: Some(true),
+                    },
+                    PromptArgument {
+                        name: "level".to_string(),
+                        description: Some(
+                            "Explanation level: beginner, intermediate, or expert".to_string(),
+                        ),
+                        required: Some(false),
+                    },
+                ]),
                 meta: None,
-            })
-        },
-    )?;
-
-    // 3. Translation Prompt
-    server.register_prompt(
-        Prompt {
-            base: BaseMetadata {
-                name: "translate".to_string(),
-                title: Some("Translate Text".to_string()),
             },
-            icons: Icons::default(),
-            description: Some("Translate text between languages".to_string()),
-            arguments: Some(vec![
-                PromptArgument {
-                    name: "text".to_string(),
-                    description: Some("The text to translate".to_string()),
-                    required: Some(true),
-                },
-                PromptArgument {
-                    name: "source_language".to_string(),
-                    description: Some("Source language (or 'auto' for detection)".to_string()),
-                    required: Some(false),
-                },
-                PromptArgument {
-                    name: "target_language".to_string(),
-                    description: Some("Target language".to_string()),
-                    required: Some(true),
+            |args: Option<HashMap<String, String>>, _ctx: RequestContext| async move {
+                let args = args.unwrap_or_default();
+                let concept = args
+                    .get("concept")
+                    .map(|s| s.as_str())
+                    .unwrap_or("(no concept provided)");
+                let level = args
+                    .get("level")
+                    .map(|s| s.as_str())
+                    .unwrap_or("intermediate");
+
+                let level_instruction = match level {
+                    "beginner" => "Explain in simple terms, avoiding jargon. Use analogies.",
+                    "expert" => "Provide a detailed technical explanation with advanced concepts.",
+                    _ => {
+                        "Provide a balanced explanation suitable for someone with basic knowledge."
+                    }
+                };
+
+                let message = format!(
+                    "Please explain the concept of \"{}\".\n\n{}",
+                    concept, level_instruction
+                );
+
+                Ok::<_, ServerError>(GetPromptResult {
+                    description: Some(format!("Explanation of {} at {} level", concept, level)),
+                    messages: vec![PromptMessage {
+                        role: Role::User,
+                        content: ContentBlock::Text(TextContent::new(message)),
+                    }],
+                    meta: None,
+                })
+            },
+        )
+        // 3. Translation Prompt
+        .prompt(
+            Prompt {
+                base: BaseMetadata {
+                    name: "translate".to_string(),
+                    title: Some("Translate Text".to_string()),
                 },
-            ]),
-            meta: None,
-        },
-        |args: Option<HashMap<String, String>>, _ctx: RequestContext| async move {
-            let args = args.unwrap_or_default();
-            let text = args
-                .get("text")
-                .map(|s| s.as_str())
-                .unwrap_or("(no text provided)");
-            let source = args
-                .get("source_language")
-                .map(|s| s.as_str())
-                .unwrap_or("auto");
-            let target = args
-                .get("target_language")
-                .map(|s| s.as_str())
-                .unwrap_or("English");
-
-            let message = if source == "auto" {
-                format!(
-                    "Please translate the following text to {}:\n\n{}",
-                    target, text
-                )
-            } else {
-                format!(
-                    "Please translate the following text from {} to {}:\n\n{}",
-                    source, target, text
-                )
-            };
-
-            Ok::<_, ServerError>(GetPromptResult {
-                description: Some(format!("Translation to {}", target)),
-                messages: vec![PromptMessage {
-                    role: Role::User,
-                    content: ContentBlock::Text(TextContent::new(message)),
-                }],
+                icons: Icons::default(),
+                description: Some("Translate text between languages".to_string()),
+                arguments: Some(vec![
+                    PromptArgument {
+                        name: "text".to_string(),
+                        description: Some("The text to translate".to_string()),
+                        required: Some(true),
+                    },
+                    PromptArgument {
+                        name: "source_language".to_string(),
+                        description: Some("Source language (or 'auto' for detection)".to_string()),
+                        required: Some(false),
+                    },
+                    PromptArgument {
+                        name: "target_language".to_string(),
+                        description: Some("Target language".to_string()),
+                        required: Some(true),
+                    },
+                ]),
                 meta: None,
-            })
-        },
-    )?;
-
-    // 4. Summarize Prompt
-    server.register_prompt(
-        Prompt {
-            base: BaseMetadata {
-                name: "summarize".to_string(),
-                title: Some("Summarize Text".to_string()),
             },
-            icons: Icons::default(),
-            description: Some("Summarize text content".to_string()),
-            arguments: Some(vec![
-                PromptArgument {
-                    name: "text".to_string(),
-                    description: Some("The text to summarize".to_string()),
-                    required: Some(true),
-                },
-                PromptArgument {
-                    name: "style".to_string(),
-                    description: Some(
-                        "Summary style: brief, detailed, or bullet_points".to_string(),
-                    ),
-                    required: Some(false),
-                },
-                PromptArgument {
-                    name: "max_length".to_string(),
-                    description: Some("Maximum length in words (optional)".to_string()),
-                    required: Some(false),
+            |args: Option<HashMap<String, String>>, _ctx: RequestContext| async move {
+                let args = args.unwrap_or_default();
+                let text = args
+                    .get("text")
+                    .map(|s| s.as_str())
+                    .unwrap_or("(no text provided)");
+                let source = args
+                    .get("source_language")
+                    .map(|s| s.as_str())
+                    .unwrap_or("auto");
+                let target = args
+                    .get("target_language")
+                    .map(|s| s.as_str())
+                    .unwrap_or("English");
+
+                let message = if source == "auto" {
+                    format!(
+                        "Please translate the following text to {}:\n\n{}",
+                        target, text
+                    )
+                } else {
+                    format!(
+                        "Please translate the following text from {} to {}:\n\n{}",
+                        source, target, text
+                    )
+                };
+
+                Ok::<_, ServerError>(GetPromptResult {
+                    description: Some(format!("Translation to {}", target)),
+                    messages: vec![PromptMessage {
+                        role: Role::User,
+                        content: ContentBlock::Text(TextContent::new(message)),
+                    }],
+                    meta: None,
+                })
+            },
+        )
+        // 4. Summarize Prompt
+        .prompt(
+            Prompt {
+                base: BaseMetadata {
+                    name: "summarize".to_string(),
+                    title: Some("Summarize Text".to_string()),
                 },
-            ]),
-            meta: None,
-        },
-        |args: Option<HashMap<String, String>>, _ctx: RequestContext| async move {
-            let args = args.unwrap_or_default();
-            let text = args
-                .get("text")
-                .map(|s| s.as_str())
-                .unwrap_or("(no text provided)");
-            let style = args.get("style").map(|s| s.as_str()).unwrap_or("brief");
-            let max_length = args.get("max_length").map(|s| s.as_str());
-
-            let style_instruction = match style {
-                "detailed" => "Provide a comprehensive summary covering all main points.",
-                "bullet_points" => "Summarize using bullet points for key takeaways.",
-                _ => "Provide a concise summary of the main idea.",
-            };
-
-            let length_instruction = match max_length {
-                Some(len) if !len.is_empty() => format!(" Keep the summary under {} words.", len),
-                _ => String::new(),
-            };
-
-            let message = format!(
-                "Please summarize the following text.\n\n{}{}\n\nText:\n{}",
-                style_instruction, length_instruction, text
-            );
-
-            Ok::<_, ServerError>(GetPromptResult {
-                description: Some(format!("{} summary", style)),
-                messages: vec![PromptMessage {
-                    role: Role::User,
-                    content: ContentBlock::Text(TextContent::new(message)),
-                }],
+                icons: Icons::default(),
+                description: Some("Summarize text content".to_string()),
+                arguments: Some(vec![
+                    PromptArgument {
+                        name: "text".to_string(),
+                        description: Some("The text to summarize".to_string()),
+                        required: Some(true),
+                    },
+                    PromptArgument {
+                        name: "style".to_string(),
+                        description: Some(
+                            "Summary style: brief, detailed, or bullet_points".to_string(),
+                        ),
+                        required: Some(false),
+                    },
+                    PromptArgument {
+                        name: "max_length".to_string(),
+                        description: Some("Maximum length in words (optional)".to_string()),
+                        required: Some(false),
+                    },
+                ]),
                 meta: None,
-            })
-        },
-    )?;
+            },
+            |args: Option<HashMap<String, String>>, _ctx: RequestContext| async move {
+                let args = args.unwrap_or_default();
+                let text = args
+                    .get("text")
+                    .map(|s| s.as_str())
+                    .unwrap_or("(no text provided)");
+                let style = args.get("style").map(|s| s.as_str()).unwrap_or("brief");
+                let max_length = args.get("max_length").map(|s| s.as_str());
+
+                let style_instruction = match style {
+                    "detailed" => "Provide a comprehensive summary covering all main points.",
+                    "bullet_points" => "Summarize using bullet points for key takeaways.",
+                    _ => "Provide a concise summary of the main idea.",
+                };
+
+                let length_instruction = match max_length {
+                    Some(len) if !len.is_empty() => {
+                        format!(" Keep the summary under {} words.", len)
+                    }
+                    _ => String::new(),
+                };
+
+                let message = format!(
+                    "Please summarize the following text.\n\n{}{}\n\nText:\n{}",
+                    style_instruction, length_instruction, text
+                );
+
+                Ok::<_, ServerError>(GetPromptResult {
+                    description: Some(format!("{} summary", style)),
+                    messages: vec![PromptMessage {
+                        role: Role::User,
+                        content: ContentBlock::Text(TextContent::new(message)),
+                    }],
+                    meta: None,
+                })
+            },
+        )
+}
 
-    Ok(())
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mcp_core::types::{PromptCapabilities, ServerCapabilities};
+    use mcp_server::ServerOptions;
+    use serde_json::json;
+
+    #[test]
+    fn builder_advertises_the_hand_written_capabilities() {
+        let built = build_server().unwrap().server().get_capabilities();
+
+        // What the example configured before it used the builder
+        let options = ServerOptions {
+            capabilities: Some(ServerCapabilities {
+                prompts: Some(PromptCapabilities {
+                    list_changed: Some(true),
+                }),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let hand_written = McpServer::new(
+            mcp_core::types::Implementation {
+                base: BaseMetadata {
+                    name: "mcp-prompts-server".to_string(),
+                    title: None,
+                },
+                icons: Icons::default(),
+                version: "0.1.0".to_string(),
+                website_url: None,
+                description: None,
+            },
+            options,
+        );
+
+        assert_eq!(built, hand_written.server().get_capabilities());
+        assert_eq!(
+            serde_json::to_value(&built).unwrap(),
+            json!({ "prompts": { "listChanged": true } })
+        );
+    }
 }
@@ -27,7 +27,7 @@ use mcp_core::types::{
     TextContent, Tool,
 };
 use mcp_server::{
-    AxumHandlerConfig, AxumHandlerState, LegacySseConfig, LegacySseState, McpServer, ServerError,
+    AxumHandlerConfig, AxumHandlerState, CorsPolicy, LegacySseConfig, LegacySseState, McpServer, ServerError,
     ServerOptions, create_legacy_sse_router, create_router,
 };
 use serde_json::json;
@@ -78,7 +78,7 @@ async fn run() -> Result<(), Box<dyn std::error::Error>> {
     // Create modern Streamable HTTP router
     let streamable_config = AxumHandlerConfig {
         endpoint_path: "/mcp".to_string(),
-        enable_cors: true,
+        cors: CorsPolicy::permissive(),
         ..Default::default()
     };
     let streamable_state = Arc::new(AxumHandlerState::new(Arc::clone(&mcp_server), streamable_config));
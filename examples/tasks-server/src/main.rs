@@ -49,16 +49,20 @@
 use std::sync::Arc;
 use std::time::Duration;
 
-use mcp_core::protocol::{ProtocolOptions, RequestContext};
+use mcp_core::protocol::{CancellationToken, ProtocolOptions, RequestContext};
 use mcp_core::types::{
     BaseMetadata, CallToolResult, ContentBlock, Icons, Implementation, ServerCapabilities,
     TextContent, Tool,
 };
 use mcp_server::{
-    AxumHandlerConfig, AxumHandlerState, InMemoryTaskStore, McpServer, ServerError, ServerOptions,
-    create_router,
+    AxumHandlerConfig, AxumHandlerState, CorsPolicy, InMemoryTaskStore, McpServer, ServerError, ServerOptions,
+    TypedTool, create_router_with_shutdown, tokio_task_spawner,
 };
-use serde_json::json;
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+
+/// How long in-flight requests get to finish once a shutdown signal arrives.
+const DRAIN_TIMEOUT: Duration = Duration::from_secs(10);
 
 #[tokio::main]
 async fn main() {
@@ -97,8 +101,11 @@ async fn run() -> Result<(), Box<dyn std::error::Error>> {
         ),
         protocol_options: Some(ProtocolOptions {
             task_store: Some(task_store),
+            // Run tasks in the background so tasks/cancel can abort them.
+            task_spawner: Some(tokio_task_spawner()),
             ..Default::default()
         }),
+        ..Default::default()
     };
 
     // Create MCP server
@@ -115,13 +122,24 @@ async fn run() -> Result<(), Box<dyn std::error::Error>> {
         endpoint_path: "/mcp".to_string(),
         keep_alive_interval: Duration::from_secs(30),
         broadcast_capacity: 100,
-        enable_cors: true,
+        cors: CorsPolicy::permissive(),
         ..Default::default()
     };
 
     // Create handler state and router
-    let state = Arc::new(AxumHandlerState::new(mcp_server, config));
-    let app = create_router(state);
+    let state = Arc::new(AxumHandlerState::new(Arc::clone(&mcp_server), config));
+    let shutdown = CancellationToken::default();
+    let app = create_router_with_shutdown(state, shutdown.clone());
+
+    // On Ctrl-C or SIGTERM, let in-flight requests finish before the open
+    // streams are ended
+    let signal = shutdown.clone();
+    tokio::spawn(async move {
+        shutdown_signal().await;
+        println!("Draining in-flight requests...");
+        mcp_server.drain(DRAIN_TIMEOUT).await;
+        shutdown.cancel();
+    });
 
     // Start server
     let addr = "0.0.0.0:8080";
@@ -146,11 +164,37 @@ async fn run() -> Result<(), Box<dyn std::error::Error>> {
     println!("  - tasks/cancel: Cancel a running task");
     println!();
 
-    axum::serve(listener, app).await?;
+    axum::serve(listener, app)
+        .with_graceful_shutdown(async move { signal.cancelled().await })
+        .await?;
 
     Ok(())
 }
 
+/// Resolve on Ctrl-C, or on SIGTERM where available.
+async fn shutdown_signal() {
+    let ctrl_c = async {
+        tokio::signal::ctrl_c().await.ok();
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        match tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate()) {
+            Ok(mut sigterm) => {
+                sigterm.recv().await;
+            }
+            Err(_) => std::future::pending::<()>().await,
+        }
+    };
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => {},
+        _ = terminate => {},
+    }
+}
+
 fn register_tools(server: &mut McpServer) -> Result<(), Box<dyn std::error::Error>> {
     // 1. Slow Operation Tool - simulates a long-running task
     server.register_tool(
@@ -273,81 +317,8 @@ fn register_tools(server: &mut McpServer) -> Result<(), Box<dyn std::error::Erro
         },
     )?;
 
-    // 3. Process Data Tool - simulates batch processing
-    server.register_tool(
-        Tool {
-            base: BaseMetadata {
-                name: "process_data".to_string(),
-                title: Some("Process Data".to_string()),
-            },
-            icons: Icons::default(),
-            description: Some("Simulates processing a batch of data items.".to_string()),
-            input_schema: json!({
-                "type": "object",
-                "properties": {
-                    "items": {
-                        "type": "array",
-                        "items": { "type": "string" },
-                        "description": "Data items to process"
-                    },
-                    "delay_ms": {
-                        "type": "integer",
-                        "description": "Delay between items in milliseconds",
-                        "default": 500
-                    }
-                },
-                "required": ["items"]
-            }),
-            output_schema: None,
-            annotations: None,
-            execution: None,
-            meta: None,
-        },
-        |params: Option<serde_json::Value>, _context: RequestContext| {
-            Box::pin(async move {
-                let items: Vec<String> = params
-                    .as_ref()
-                    .and_then(|p| p.get("items"))
-                    .and_then(|v| v.as_array())
-                    .map(|arr| {
-                        arr.iter()
-                            .filter_map(|v| v.as_str().map(String::from))
-                            .collect()
-                    })
-                    .unwrap_or_default();
-
-                let delay_ms = params
-                    .as_ref()
-                    .and_then(|p| p.get("delay_ms"))
-                    .and_then(|v| v.as_u64())
-                    .unwrap_or(500);
-
-                let mut results = Vec::new();
-                for (i, item) in items.iter().enumerate() {
-                    // Simulate processing
-                    tokio::time::sleep(Duration::from_millis(delay_ms)).await;
-                    results.push(json!({
-                        "index": i,
-                        "item": item,
-                        "processed": format!("Processed: {}", item.to_uppercase())
-                    }));
-                }
-
-                Ok::<_, ServerError>(CallToolResult {
-                    content: vec![ContentBlock::Text(TextContent::new(format!(
-                        "Processed {} items successfully",
-                        results.len()
-                    )))],
-                    structured_content: Some(json!({
-                        "total_items": items.len(),
-                        "results": results
-                    })),
-                    is_error: None,
-                    meta: None,
-                })
-            })
-        },
-    )?;
+    // 3. Process Data Tool - simulates batch processing, statically typed
+    server.register_tool_type::<ProcessData>()?;
 
     // 4. Quick Echo Tool - for comparison with sync execution
     server.register_tool(
@@ -397,6 +368,89 @@ fn register_tools(server: &mut McpServer) -> Result<(), Box<dyn std::error::Erro
     Ok(())
 }
 
+/// Simulates processing a batch of data items.
+struct ProcessData;
+
+#[derive(Deserialize)]
+struct ProcessDataArgs {
+    items: Vec<String>,
+    #[serde(default = "default_delay_ms")]
+    delay_ms: u64,
+}
+
+fn default_delay_ms() -> u64 {
+    500
+}
+
+#[derive(Serialize)]
+struct ProcessDataOutput {
+    total_items: usize,
+    results: Vec<ProcessedItem>,
+}
+
+#[derive(Serialize)]
+struct ProcessedItem {
+    index: usize,
+    item: String,
+    processed: String,
+}
+
+impl TypedTool for ProcessData {
+    type Args = ProcessDataArgs;
+    type Output = ProcessDataOutput;
+
+    fn name() -> &'static str {
+        "process_data"
+    }
+
+    fn title() -> Option<&'static str> {
+        Some("Process Data")
+    }
+
+    fn description() -> &'static str {
+        "Simulates processing a batch of data items."
+    }
+
+    fn input_schema() -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "items": {
+                    "type": "array",
+                    "items": { "type": "string" },
+                    "description": "Data items to process"
+                },
+                "delay_ms": {
+                    "type": "integer",
+                    "description": "Delay between items in milliseconds",
+                    "default": 500
+                }
+            },
+            "required": ["items"]
+        })
+    }
+
+    async fn call(
+        args: ProcessDataArgs,
+        _ctx: RequestContext,
+    ) -> Result<ProcessDataOutput, ServerError> {
+        let mut results = Vec::new();
+        for (index, item) in args.items.into_iter().enumerate() {
+            // Simulate processing
+            tokio::time::sleep(Duration::from_millis(args.delay_ms)).await;
+            results.push(ProcessedItem {
+                index,
+                processed: format!("Processed: {}", item.to_uppercase()),
+                item,
+            });
+        }
+        Ok(ProcessDataOutput {
+            total_items: results.len(),
+            results,
+        })
+    }
+}
+
 /// Compute Fibonacci number (intentionally using recursive method for demo)
 fn fibonacci(n: u32) -> u64 {
     match n {
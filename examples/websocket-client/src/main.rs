@@ -61,8 +61,8 @@ async fn run() -> Result<(), Box<dyn std::error::Error>> {
         eprintln!("[Error] {:?}", err);
     });
 
-    transport.on_close(|| {
-        println!("[Connection closed]");
+    transport.on_close_with_reason(|reason| {
+        println!("[Connection closed: {}]", reason);
     });
 
     // Start the transport (establishes WebSocket connection)
@@ -209,6 +209,7 @@ async fn run() -> Result<(), Box<dyn std::error::Error>> {
     println!();
     println!("Closing WebSocket connection...");
     transport.close().await?;
+    println!("Transport stats: {}", transport.stats());
     println!("Done!");
 
     Ok(())
@@ -28,7 +28,7 @@ use mcp_core::types::{
     TextContent, Tool,
 };
 use mcp_server::{
-    McpServer, ServerError, ServerOptions, WebSocketConfig, WebSocketState,
+    CorsPolicy, McpServer, ServerError, ServerOptions, WebSocketConfig, WebSocketState,
     create_websocket_router,
 };
 use serde_json::json;
@@ -76,8 +76,9 @@ async fn run() -> Result<(), Box<dyn std::error::Error>> {
     // Configure WebSocket handler
     let config = WebSocketConfig {
         endpoint_path: "/ws".to_string(),
-        enable_cors: true,
+        cors: CorsPolicy::permissive(),
         channel_buffer_size: 100,
+        ..Default::default()
     };
 
     // Create handler state and router
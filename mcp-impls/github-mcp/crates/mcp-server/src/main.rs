@@ -2,13 +2,10 @@ mod client;
 mod config;
 mod tools;
 
-use std::io::{BufRead, BufReader, Write};
 use std::sync::Arc;
 
-use futures::executor::block_on;
-use mcp_core::stdio::{serialize_message, JsonRpcMessage, ReadBuffer};
 use mcp_core::types::*;
-use mcp_server::{McpServer, ServerOptions};
+use mcp_server::{stdio, McpServer, ServerOptions};
 
 use client::GithubState;
 use tools::register_all_tools;
@@ -82,36 +79,9 @@ fn run() -> Result<(), Box<dyn std::error::Error>> {
     register_all_tools(&mut server, state)?;
 
     // 启动 stdio 通信循环
-    let stdin = std::io::stdin();
-    let mut stdout = std::io::stdout();
-    let mut reader = BufReader::new(stdin.lock());
-    let mut buffer = String::new();
-    let mut read_buffer = ReadBuffer::default();
-
-    loop {
-        buffer.clear();
-        let bytes_read = reader.read_line(&mut buffer)?;
-        if bytes_read == 0 {
-            break;
-        }
-
-        read_buffer.append(buffer.as_bytes());
-        while let Ok(Some(message)) = read_buffer.read_message() {
-            match message {
-                JsonRpcMessage::Request(request) => {
-                    let response = block_on(server.server().handle_request(request, None))?;
-                    let response_msg = JsonRpcMessage::Result(response);
-                    let serialized = serialize_message(&response_msg)?;
-                    stdout.write_all(serialized.as_bytes())?;
-                    stdout.flush()?;
-                }
-                JsonRpcMessage::Notification(notification) => {
-                    block_on(server.server().handle_notification(notification, None))?;
-                }
-                JsonRpcMessage::Result(_) => {}
-            }
-        }
-    }
+    let server = Arc::new(server);
+    stdio::run(server.clone())?;
 
+    eprintln!("{}", server.summary_report());
     Ok(())
 }
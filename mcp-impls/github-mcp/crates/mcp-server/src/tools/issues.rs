@@ -175,7 +175,10 @@ pub fn register_tools(
             "required": ["owner", "repo", "title"]
         }),
         output_schema: None,
-        annotations: None,
+        annotations: Some(ToolAnnotations {
+            idempotent_hint: Some(false),
+            ..Default::default()
+        }),
         execution: None,
         meta: None,
     };
@@ -386,7 +389,10 @@ pub fn register_tools(
             "required": ["owner", "repo", "issue_number", "body"]
         }),
         output_schema: None,
-        annotations: None,
+        annotations: Some(ToolAnnotations {
+            idempotent_hint: Some(false),
+            ..Default::default()
+        }),
         execution: None,
         meta: None,
     };
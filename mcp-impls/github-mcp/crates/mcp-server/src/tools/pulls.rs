@@ -178,7 +178,10 @@ pub fn register_tools(
             "required": ["owner", "repo", "title", "head", "base"]
         }),
         output_schema: None,
-        annotations: None,
+        annotations: Some(ToolAnnotations {
+            idempotent_hint: Some(false),
+            ..Default::default()
+        }),
         execution: None,
         meta: None,
     };
@@ -446,7 +449,10 @@ pub fn register_tools(
             "required": ["owner", "repo", "pull_number", "body"]
         }),
         output_schema: None,
-        annotations: None,
+        annotations: Some(ToolAnnotations {
+            idempotent_hint: Some(false),
+            ..Default::default()
+        }),
         execution: None,
         meta: None,
     };
@@ -1,7 +1,8 @@
-use governor::{clock::DefaultClock, state::InMemoryState, Quota, RateLimiter};
-use reqwest::Client as HttpClient;
-use std::num::NonZeroU32;
+use reqwest::{Client as HttpClient, StatusCode};
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::time::Duration;
+use tokio::sync::Mutex;
+use tokio::time::{Instant, Interval, MissedTickBehavior};
 use tracing::{debug, warn};
 
 use crate::error::{Result, ScraperError};
@@ -9,18 +10,21 @@ use crate::error::{Result, ScraperError};
 /// HTTP client for scraping GitLab documentation
 pub struct DocScraperClient {
     http_client: HttpClient,
-    rate_limiter: RateLimiter<InMemoryState, DefaultClock>,
+    /// Time between request starts; `None` disables rate limiting
+    request_period: Option<Duration>,
+    /// Created on first use, since an interval needs a running runtime
+    rate_limiter: Mutex<Option<Interval>>,
     base_url: String,
-    max_retries: u32,
+    max_retries: u8,
+    retry_delay: Duration,
+    concurrency: usize,
+    retried: AtomicUsize,
+    rate_limited_pauses: AtomicUsize,
 }
 
 impl DocScraperClient {
     /// Create a new documentation scraper client
     pub fn new() -> Result<Self> {
-        // Rate limit: 1 request per second, burst of 5
-        let quota = Quota::per_second(NonZeroU32::new(1).unwrap());
-        let rate_limiter = RateLimiter::direct(quota);
-
         let http_client = HttpClient::builder()
             .timeout(Duration::from_secs(30))
             .build()
@@ -28,9 +32,15 @@ impl DocScraperClient {
 
         Ok(Self {
             http_client,
-            rate_limiter,
+            // Rate limit: 1 request per second
+            request_period: Some(Duration::from_secs(1)),
+            rate_limiter: Mutex::new(None),
             base_url: "https://docs.gitlab.com".to_string(),
             max_retries: 3,
+            retry_delay: Duration::from_millis(500),
+            concurrency: 1,
+            retried: AtomicUsize::new(0),
+            rate_limited_pauses: AtomicUsize::new(0),
         })
     }
 
@@ -41,25 +51,48 @@ impl DocScraperClient {
     }
 
     /// Set max retries
-    pub fn with_max_retries(mut self, max_retries: u32) -> Self {
+    pub fn with_max_retries(mut self, max_retries: u8) -> Self {
         self.max_retries = max_retries;
         self
     }
 
+    /// Set the delay before the first retry; it doubles on each retry, up to 10 seconds
+    pub fn with_retry_delay(mut self, delay: Duration) -> Self {
+        self.retry_delay = delay;
+        self
+    }
+
+    /// Limit requests to `requests_per_second`, shared by all concurrent fetches.
+    /// Zero, negative or non-finite values disable rate limiting.
+    pub fn with_requests_per_second(mut self, requests_per_second: f64) -> Self {
+        self.request_period = (requests_per_second.is_finite() && requests_per_second > 0.0)
+            .then(|| Duration::from_secs_f64(1.0 / requests_per_second));
+        self.rate_limiter = Mutex::new(None);
+        self
+    }
+
+    /// Set how many pages may be scraped at the same time (at least 1)
+    pub fn with_concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency.max(1);
+        self
+    }
+
     /// Fetch a page with retry logic
     pub async fn fetch_page(&self, path: &str) -> Result<String> {
         let url = format!("{}/{}", self.base_url.trim_end_matches('/'), path.trim_start_matches('/'));
-        self.fetch_with_retry(&url, self.max_retries).await
+        self.fetch_page_with_retry(&url, self.max_retries).await
     }
 
-    /// Fetch with exponential backoff retry
-    async fn fetch_with_retry(&self, url: &str, max_retries: u32) -> Result<String> {
-        let mut delay = Duration::from_millis(500);
-        let mut last_error = None;
+    /// Fetch `url`, retrying with exponential backoff when the server answers
+    /// 429 or 503 or the request fails in transit.
+    ///
+    /// Other HTTP errors, such as 404, are returned immediately.
+    pub async fn fetch_page_with_retry(&self, url: &str, max_retries: u8) -> Result<String> {
+        let mut delay = self.retry_delay;
+        let mut attempt = 0;
 
-        for attempt in 0..=max_retries {
-            // Respect rate limit
-            self.rate_limiter.until_ready().await;
+        loop {
+            self.wait_for_rate_limit().await;
 
             debug!("Fetching {} (attempt {}/{})", url, attempt + 1, max_retries + 1);
 
@@ -70,27 +103,40 @@ impl DocScraperClient {
                     }
                     return Ok(content);
                 }
+                Err(e) if !e.is_retryable() => return Err(e),
+                Err(e) if attempt >= max_retries => {
+                    warn!("Giving up on {} after {} attempts: {}", url, attempt + 1, e);
+                    return Err(ScraperError::max_retries_exceeded(url));
+                }
                 Err(e) => {
-                    last_error = Some(e.clone());
-
-                    // Don't retry on 404
-                    if matches!(e, ScraperError::HttpError(ref req_err) if req_err.status().map_or(false, |s| s.as_u16() == 404)) {
-                        return Err(e);
-                    }
-
-                    // Don't retry on last attempt
-                    if attempt >= max_retries {
-                        break;
-                    }
-
                     warn!("Request failed: {}, retrying in {:?}...", e, delay);
+                    self.retried.fetch_add(1, Ordering::Relaxed);
                     tokio::time::sleep(delay).await;
                     delay = std::cmp::min(delay * 2, Duration::from_secs(10));
+                    attempt += 1;
                 }
             }
         }
+    }
+
+    /// Wait for the next request slot
+    async fn wait_for_rate_limit(&self) {
+        let Some(period) = self.request_period else {
+            return;
+        };
 
-        Err(last_error.unwrap_or_else(|| ScraperError::max_retries_exceeded(url)))
+        let mut rate_limiter = self.rate_limiter.lock().await;
+        let interval = rate_limiter.get_or_insert_with(|| {
+            let mut interval = tokio::time::interval(period);
+            interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+            interval
+        });
+
+        // A slot scheduled after we started waiting means the limiter held us back
+        let started = Instant::now();
+        if interval.tick().await > started {
+            self.rate_limited_pauses.fetch_add(1, Ordering::Relaxed);
+        }
     }
 
     /// Single fetch attempt
@@ -107,10 +153,12 @@ impl DocScraperClient {
         if status.is_success() {
             let text = response.text().await?;
             Ok(text)
-        } else if status.as_u16() == 404 {
+        } else if status == StatusCode::NOT_FOUND {
             Err(ScraperError::network(format!("Not found: {}", url)))
-        } else if status.as_u16() == 429 {
+        } else if status == StatusCode::TOO_MANY_REQUESTS {
             Err(ScraperError::RateLimitExceeded)
+        } else if status == StatusCode::SERVICE_UNAVAILABLE {
+            Err(ScraperError::ServiceUnavailable)
         } else {
             let text = response.text().await.unwrap_or_else(|_| "Unknown error".to_string());
             Err(ScraperError::network(format!("HTTP {}: {}", status.as_u16(), text)))
@@ -121,6 +169,21 @@ impl DocScraperClient {
     pub fn base_url(&self) -> &str {
         &self.base_url
     }
+
+    /// Number of pages that may be scraped at the same time
+    pub fn concurrency(&self) -> usize {
+        self.concurrency
+    }
+
+    /// Retries performed so far
+    pub fn retried(&self) -> usize {
+        self.retried.load(Ordering::Relaxed)
+    }
+
+    /// Times a request had to wait for the rate limiter so far
+    pub fn rate_limited_pauses(&self) -> usize {
+        self.rate_limited_pauses.load(Ordering::Relaxed)
+    }
 }
 
 impl Default for DocScraperClient {
@@ -132,6 +195,38 @@ impl Default for DocScraperClient {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    /// Serve one connection per status in `statuses`, in order, and return the base URL
+    async fn serve(statuses: Vec<u16>) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            for status in statuses {
+                let (mut socket, _) = listener.accept().await.unwrap();
+                let mut buf = [0u8; 4096];
+                let _ = socket.read(&mut buf).await;
+                let body = if status == 200 { "<h1>Issues</h1>" } else { "" };
+                let response = format!(
+                    "HTTP/1.1 {} Status\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{}",
+                    status,
+                    body.len(),
+                    body
+                );
+                let _ = socket.write_all(response.as_bytes()).await;
+            }
+        });
+        format!("http://{}", addr)
+    }
+
+    fn test_client(base_url: String) -> DocScraperClient {
+        DocScraperClient::new()
+            .unwrap()
+            .with_base_url(base_url)
+            .with_retry_delay(Duration::from_millis(1))
+            .with_requests_per_second(0.0)
+    }
 
     #[test]
     fn test_client_creation() {
@@ -139,5 +234,51 @@ mod tests {
         assert!(client.is_ok());
         let client = client.unwrap();
         assert_eq!(client.base_url(), "https://docs.gitlab.com");
+        assert_eq!(client.concurrency(), 1);
+        assert_eq!(client.with_concurrency(0).concurrency(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_retries_on_429_and_503() {
+        let client = test_client(serve(vec![429, 503, 200]).await);
+        let url = format!("{}/ee/api/issues.html", client.base_url());
+
+        let page = client.fetch_page_with_retry(&url, 3).await.unwrap();
+
+        assert_eq!(page, "<h1>Issues</h1>");
+        assert_eq!(client.retried(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_gives_up_after_max_retries() {
+        let client = test_client(serve(vec![503, 503]).await);
+        let url = format!("{}/ee/api/issues.html", client.base_url());
+
+        let err = client.fetch_page_with_retry(&url, 1).await.unwrap_err();
+
+        assert!(matches!(err, ScraperError::MaxRetriesExceeded(_)));
+        assert_eq!(client.retried(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_does_not_retry_not_found() {
+        let client = test_client(serve(vec![404]).await);
+        let url = format!("{}/ee/api/missing.html", client.base_url());
+
+        assert!(client.fetch_page_with_retry(&url, 3).await.is_err());
+        assert_eq!(client.retried(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_rate_limit_pauses_between_requests() {
+        let client = DocScraperClient::new().unwrap().with_requests_per_second(20.0);
+
+        let start = Instant::now();
+        for _ in 0..3 {
+            client.wait_for_rate_limit().await;
+        }
+
+        assert!(start.elapsed() >= Duration::from_millis(90));
+        assert_eq!(client.rate_limited_pauses(), 2);
     }
 }
@@ -0,0 +1,116 @@
+/// Where an endpoint attribute is sent
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParameterLocation {
+    /// Part of the URL path, e.g. `{id}` in `/projects/{id}`
+    Path,
+    /// Query string parameter
+    Query,
+    /// Property of the JSON request body
+    Body,
+}
+
+impl ParameterLocation {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Path => "path",
+            Self::Query => "query",
+            Self::Body => "body",
+        }
+    }
+}
+
+/// An attribute from the table documenting an endpoint
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ApiParameter {
+    pub name: String,
+    pub location: ParameterLocation,
+    /// Type as written in the docs, e.g. `integer or string`
+    pub type_name: String,
+    pub required: bool,
+    pub description: String,
+}
+
+/// A REST endpoint documented on a page
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ApiEndpoint {
+    /// Upper-case HTTP method
+    pub method: String,
+    /// Path in OpenAPI form, e.g. `/projects/{id}/issues`
+    pub path: String,
+    /// Heading of the section documenting the endpoint
+    pub summary: String,
+    /// Path and query parameters
+    pub parameters: Vec<ApiParameter>,
+    /// Body properties, for `POST`, `PUT` and `PATCH` endpoints
+    pub request_body: Vec<ApiParameter>,
+}
+
+impl ApiEndpoint {
+    /// Parse an endpoint line such as `GET /projects/:id/issues`.
+    ///
+    /// `:name` segments become `{name}` and any query string is dropped.
+    pub fn parse_line(line: &str, summary: impl Into<String>) -> Option<Self> {
+        let (method, path) = line.trim().split_once(char::is_whitespace)?;
+        if !matches!(method, "GET" | "POST" | "PUT" | "PATCH" | "DELETE" | "HEAD") {
+            return None;
+        }
+        let path = path.trim();
+        let path = path.split_once('?').map_or(path, |(path, _)| path);
+        if !path.starts_with('/') || path.contains(char::is_whitespace) {
+            return None;
+        }
+
+        let path = path
+            .split('/')
+            .map(|segment| match segment.strip_prefix(':') {
+                Some(name) => format!("{{{}}}", name),
+                None => segment.to_string(),
+            })
+            .collect::<Vec<_>>()
+            .join("/");
+
+        Some(Self {
+            method: method.to_string(),
+            path,
+            summary: summary.into(),
+            parameters: Vec::new(),
+            request_body: Vec::new(),
+        })
+    }
+
+    /// Whether attributes not in the path are sent in the request body
+    pub fn has_request_body(&self) -> bool {
+        matches!(self.method.as_str(), "POST" | "PUT" | "PATCH")
+    }
+
+    /// Names of the `{name}` segments in the path
+    pub fn path_parameter_names(&self) -> impl Iterator<Item = &str> {
+        self.path
+            .split('/')
+            .filter_map(|segment| segment.strip_prefix('{')?.strip_suffix('}'))
+    }
+
+    /// Add a documented attribute, placing it in the path, query or body
+    pub fn add_attribute(&mut self, name: String, type_name: String, required: bool, description: String) {
+        let location = if self.path_parameter_names().any(|segment| segment == name) {
+            ParameterLocation::Path
+        } else if self.has_request_body() {
+            ParameterLocation::Body
+        } else {
+            ParameterLocation::Query
+        };
+
+        let parameter = ApiParameter {
+            name,
+            location,
+            type_name,
+            // Path parameters are always required
+            required: required || location == ParameterLocation::Path,
+            description,
+        };
+        match location {
+            ParameterLocation::Body => self.request_body.push(parameter),
+            _ => self.parameters.push(parameter),
+        }
+    }
+}
@@ -18,6 +18,9 @@ pub enum ScraperError {
     #[error("Rate limit exceeded")]
     RateLimitExceeded,
 
+    #[error("Service unavailable")]
+    ServiceUnavailable,
+
     #[error("Max retries exceeded for {0}")]
     MaxRetriesExceeded(String),
 
@@ -44,6 +47,16 @@ impl ScraperError {
     pub fn network(msg: impl Into<String>) -> Self {
         Self::Network(msg.into())
     }
+
+    /// Whether the request may succeed if sent again: 429, 503, or a
+    /// connection failure or timeout.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            Self::RateLimitExceeded | Self::ServiceUnavailable => true,
+            Self::HttpError(err) => err.is_connect() || err.is_timeout(),
+            _ => false,
+        }
+    }
 }
 
 pub type Result<T> = std::result::Result<T, ScraperError>;
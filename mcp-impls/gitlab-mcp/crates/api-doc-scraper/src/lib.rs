@@ -1,9 +1,13 @@
 pub mod client;
+pub mod endpoint;
 pub mod error;
+pub mod openapi;
 pub mod parser;
 pub mod resources;
 
 pub use client::DocScraperClient;
+pub use endpoint::{ApiEndpoint, ApiParameter, ParameterLocation};
 pub use error::{Result, ScraperError};
-pub use parser::HtmlParser;
+pub use openapi::to_openapi_yaml;
+pub use parser::{HtmlParser, ParsedPage, TableFormat};
 pub use resources::{ApiResource, ResourceCategory, get_all_resources, get_resources_by_category};
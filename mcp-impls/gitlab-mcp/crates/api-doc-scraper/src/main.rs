@@ -1,8 +1,9 @@
 use std::path::PathBuf;
 use std::time::Instant;
 
-use api_doc_scraper::{DocScraperClient, HtmlParser, ResourceCategory, get_all_resources, get_resources_by_category};
+use api_doc_scraper::{DocScraperClient, HtmlParser, ParsedPage, ResourceCategory, TableFormat, get_all_resources, get_resources_by_category, to_openapi_yaml};
 use clap::Parser;
+use futures::stream::{FuturesUnordered, StreamExt};
 use indicatif::{HumanDuration, ProgressBar, ProgressStyle};
 use tracing::{error, info, warn};
 use tracing_subscriber::EnvFilter;
@@ -28,6 +29,22 @@ struct Cli {
     #[arg(long)]
     category: Option<String>,
 
+    /// Number of resources to scrape at the same time
+    #[arg(long, default_value_t = 1, value_parser = clap::value_parser!(u16).range(1..))]
+    concurrency: u16,
+
+    /// Maximum requests per second across all concurrent scrapes (0 disables the limit)
+    #[arg(long, default_value_t = 1.0)]
+    requests_per_second: f64,
+
+    /// Also write the scraped endpoints as an OpenAPI 3.1 YAML document to this path
+    #[arg(long, value_name = "PATH")]
+    openapi: Option<PathBuf>,
+
+    /// Render tables as JSON arrays of row objects instead of Markdown tables
+    #[arg(long)]
+    json_tables: bool,
+
     /// Dry run - show what would be scraped without actually scraping
     #[arg(long)]
     dry_run: bool,
@@ -43,6 +60,8 @@ struct ScrapeSummary {
     successful: usize,
     failed: usize,
     skipped: usize,
+    retried: usize,
+    rate_limited_pauses: usize,
     duration: std::time::Duration,
     failed_resources: Vec<(String, String)>,
 }
@@ -97,7 +116,9 @@ async fn main() -> anyhow::Result<()> {
     create_output_dirs(&cli.output_dir, &resources)?;
 
     // Initialize scraper
-    let client = DocScraperClient::new()?;
+    let client = DocScraperClient::new()?
+        .with_concurrency(cli.concurrency as usize)
+        .with_requests_per_second(cli.requests_per_second);
 
     // Setup progress bar
     let pb = ProgressBar::new(resources.len() as u64);
@@ -114,17 +135,39 @@ async fn main() -> anyhow::Result<()> {
         successful: 0,
         failed: 0,
         skipped: 0,
+        retried: 0,
+        rate_limited_pauses: 0,
         duration: std::time::Duration::ZERO,
         failed_resources: Vec::new(),
     };
 
-    // Scrape each resource
-    for resource in &resources {
-        pb.set_message(format!("Scraping {}...", resource.name));
+    // Parsed pages, kept for the OpenAPI document
+    let mut pages: Vec<ParsedPage> = Vec::new();
+
+    // Scrape resources, keeping at most `concurrency` in flight
+    let table_format = if cli.json_tables { TableFormat::Json } else { TableFormat::Markdown };
+    let mut pending = resources.iter();
+    let mut in_flight = FuturesUnordered::new();
+    loop {
+        while in_flight.len() < client.concurrency() {
+            let Some(resource) = pending.next() else {
+                break;
+            };
+            let (client, output_dir) = (&client, &cli.output_dir);
+            in_flight.push(async move {
+                (resource, scrape_resource(client, output_dir, resource, table_format).await)
+            });
+        }
 
-        match scrape_resource(&client, &cli.output_dir, resource).await {
-            Ok(_) => {
+        let Some((resource, result)) = in_flight.next().await else {
+            break;
+        };
+        match result {
+            Ok(page) => {
                 summary.successful += 1;
+                if cli.openapi.is_some() {
+                    pages.push(page);
+                }
             }
             Err(e) => {
                 summary.failed += 1;
@@ -133,9 +176,12 @@ async fn main() -> anyhow::Result<()> {
             }
         }
 
+        pb.set_message(format!("Scraped {}", resource.name));
         pb.inc(1);
     }
 
+    summary.retried = client.retried();
+    summary.rate_limited_pauses = client.rate_limited_pauses();
     summary.duration = start.elapsed();
     pb.finish_with_message(format!("Completed in {}", HumanDuration(summary.duration)));
 
@@ -145,6 +191,11 @@ async fn main() -> anyhow::Result<()> {
     // Generate index
     generate_index(&cli.output_dir, &resources)?;
 
+    // Generate OpenAPI document
+    if let Some(openapi_path) = &cli.openapi {
+        write_openapi(openapi_path, pages)?;
+    }
+
     Ok(())
 }
 
@@ -198,25 +249,26 @@ async fn scrape_resource(
     client: &DocScraperClient,
     output_dir: &PathBuf,
     resource: &api_doc_scraper::ApiResource,
-) -> anyhow::Result<()> {
+    table_format: TableFormat,
+) -> anyhow::Result<ParsedPage> {
     // Fetch the page
     let html = client.fetch_page(&resource.url()).await?;
 
     // Parse and convert to markdown
-    let (title, markdown) = HtmlParser::process_page(&html)?;
+    let page = HtmlParser::process_page_with_tables(&html, table_format)?;
 
     // Write to file
     let output_path = output_dir.join(&resource.output_path);
     std::fs::create_dir_all(output_path.parent().unwrap())?;
 
     let content = format!("# {}\n\n> Source: [GitLab Documentation](https://docs.gitlab.com/{}.html)\n\n{}\n",
-        title, resource.url(), markdown);
+        page.title, resource.url(), page.markdown);
 
     std::fs::write(&output_path, content)?;
 
     tracing::debug!("Wrote {}", output_path.display());
 
-    Ok(())
+    Ok(page)
 }
 
 fn print_summary(summary: &ScrapeSummary) {
@@ -226,6 +278,8 @@ fn print_summary(summary: &ScrapeSummary) {
     println!("Total resources:  {}", summary.total);
     println!("Successful:      {}", summary.successful);
     println!("Failed:          {}", summary.failed);
+    println!("Retried:         {}", summary.retried);
+    println!("Rate-limited:    {} pauses", summary.rate_limited_pauses);
     println!("Duration:        {}", HumanDuration(summary.duration));
 
     if !summary.failed_resources.is_empty() {
@@ -237,6 +291,21 @@ fn print_summary(summary: &ScrapeSummary) {
     println!("{}", "=".repeat(60));
 }
 
+fn write_openapi(path: &PathBuf, mut pages: Vec<ParsedPage>) -> anyhow::Result<()> {
+    // Pages finish in arbitrary order; sort so the output is stable
+    pages.sort_by(|a, b| a.title.cmp(&b.title));
+    let endpoints: usize = pages.iter().map(|page| page.endpoints.len()).sum();
+
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(path, to_openapi_yaml("GitLab REST API", &pages))?;
+
+    info!("Wrote {} endpoints to {}", endpoints, path.display());
+
+    Ok(())
+}
+
 fn generate_index(
     output_dir: &PathBuf,
     resources: &[api_doc_scraper::ApiResource],
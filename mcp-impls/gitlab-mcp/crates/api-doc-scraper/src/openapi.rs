@@ -0,0 +1,236 @@
+//! OpenAPI 3.1 generation from scraped endpoints.
+//!
+//! The document is written as YAML directly: every free-text scalar is a
+//! JSON string literal, which YAML accepts as a double-quoted scalar.
+
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+use crate::endpoint::{ApiEndpoint, ApiParameter, ParameterLocation};
+use crate::parser::ParsedPage;
+
+/// OpenAPI version of generated documents
+pub const OPENAPI_VERSION: &str = "3.1.0";
+
+/// Documented paths are relative to this server URL
+const SERVER_URL: &str = "https://gitlab.com/api/v4";
+
+/// Render the endpoints of `pages` as an OpenAPI 3.1 YAML document.
+///
+/// Operations are tagged with their page title. Paths are sorted; when
+/// several pages document the same method and path, the first one wins.
+pub fn to_openapi_yaml(title: &str, pages: &[ParsedPage]) -> String {
+    let mut paths: BTreeMap<&str, Vec<(&str, &ApiEndpoint)>> = BTreeMap::new();
+    for page in pages {
+        for endpoint in &page.endpoints {
+            let operations = paths.entry(endpoint.path.as_str()).or_default();
+            if !operations.iter().any(|(_, op)| op.method == endpoint.method) {
+                operations.push((page.title.as_str(), endpoint));
+            }
+        }
+    }
+
+    let mut out = String::new();
+    let _ = writeln!(out, "openapi: {}", OPENAPI_VERSION);
+    let _ = writeln!(out, "info:");
+    let _ = writeln!(out, "  title: {}", quote(title));
+    let _ = writeln!(out, "  version: \"v4\"");
+    let _ = writeln!(out, "servers:");
+    let _ = writeln!(out, "  - url: {}", quote(SERVER_URL));
+    if paths.is_empty() {
+        let _ = writeln!(out, "paths: {{}}");
+        return out;
+    }
+    let _ = writeln!(out, "paths:");
+    for (path, operations) in paths {
+        let _ = writeln!(out, "  {}:", quote(path));
+        for (tag, endpoint) in operations {
+            write_operation(&mut out, tag, endpoint);
+        }
+    }
+    out
+}
+
+fn write_operation(out: &mut String, tag: &str, endpoint: &ApiEndpoint) {
+    let _ = writeln!(out, "    {}:", endpoint.method.to_lowercase());
+    if !endpoint.summary.is_empty() {
+        let _ = writeln!(out, "      summary: {}", quote(&endpoint.summary));
+    }
+    let _ = writeln!(out, "      tags:");
+    let _ = writeln!(out, "        - {}", quote(tag));
+
+    // Every path template segment must be declared, documented or not
+    let undocumented = endpoint
+        .path_parameter_names()
+        .filter(|name| !endpoint.parameters.iter().any(|p| p.name == *name));
+    let parameters: Vec<ApiParameter> = endpoint
+        .parameters
+        .iter()
+        .cloned()
+        .chain(undocumented.map(|name| ApiParameter {
+            name: name.to_string(),
+            location: ParameterLocation::Path,
+            type_name: "string".to_string(),
+            required: true,
+            description: String::new(),
+        }))
+        .collect();
+
+    if !parameters.is_empty() {
+        let _ = writeln!(out, "      parameters:");
+        for parameter in &parameters {
+            let _ = writeln!(out, "        - name: {}", quote(&parameter.name));
+            let _ = writeln!(out, "          in: {}", parameter.location.as_str());
+            if parameter.required {
+                let _ = writeln!(out, "          required: true");
+            }
+            if !parameter.description.is_empty() {
+                let _ = writeln!(out, "          description: {}", quote(&parameter.description));
+            }
+            let _ = writeln!(out, "          schema:");
+            write_schema(out, 12, &parameter.type_name);
+        }
+    }
+
+    if !endpoint.request_body.is_empty() {
+        let _ = writeln!(out, "      requestBody:");
+        let _ = writeln!(out, "        content:");
+        let _ = writeln!(out, "          application/json:");
+        let _ = writeln!(out, "            schema:");
+        let _ = writeln!(out, "              type: object");
+        let required: Vec<&ApiParameter> = endpoint.request_body.iter().filter(|p| p.required).collect();
+        if !required.is_empty() {
+            let _ = writeln!(out, "              required:");
+            for property in required {
+                let _ = writeln!(out, "                - {}", quote(&property.name));
+            }
+        }
+        let _ = writeln!(out, "              properties:");
+        for property in &endpoint.request_body {
+            let _ = writeln!(out, "                {}:", quote(&property.name));
+            write_schema(out, 18, &property.type_name);
+            if !property.description.is_empty() {
+                let _ = writeln!(out, "                  description: {}", quote(&property.description));
+            }
+        }
+    }
+}
+
+/// Write the JSON Schema for a GitLab docs type name at `indent` spaces
+fn write_schema(out: &mut String, indent: usize, type_name: &str) {
+    let pad = " ".repeat(indent);
+    let type_name = type_name.trim().to_lowercase();
+    let scalar = |name: &str| match name {
+        "integer" => "integer",
+        "float" | "number" => "number",
+        "boolean" => "boolean",
+        "hash" | "object" | "json" => "object",
+        _ => "string",
+    };
+
+    if type_name.starts_with("array") || type_name.ends_with("array") {
+        let items = if type_name.contains("integer") { "integer" } else { "string" };
+        let _ = writeln!(out, "{}type: array", pad);
+        let _ = writeln!(out, "{}items:", pad);
+        let _ = writeln!(out, "{}  type: {}", pad, items);
+        return;
+    }
+
+    let _ = writeln!(out, "{}type: {}", pad, scalar(&type_name));
+    let format = match type_name.as_str() {
+        "datetime" => Some("date-time"),
+        "date" => Some("date"),
+        _ => None,
+    };
+    if let Some(format) = format {
+        let _ = writeln!(out, "{}format: {}", pad, format);
+    }
+}
+
+/// A YAML double-quoted scalar
+fn quote(value: &str) -> String {
+    serde_json::to_string(value).expect("strings always serialize")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::HtmlParser;
+
+    const ISSUES_HTML: &str = r#"<html><body><main>
+<h1>Issues API</h1>
+<h2>List project issues</h2>
+<pre><code>GET /projects/:id/issues</code></pre>
+<table>
+  <thead><tr><th>Attribute</th><th>Type</th><th>Required</th><th>Description</th></tr></thead>
+  <tbody>
+    <tr><td><code>id</code></td><td>integer or string</td><td>Yes</td><td>The ID of the project.</td></tr>
+    <tr><td><code>labels</code></td><td>string</td><td>No</td><td>Comma-separated list of "label" names.</td></tr>
+  </tbody>
+</table>
+<h2>New issue</h2>
+<pre><code>POST /projects/:id/issues</code></pre>
+<table>
+  <thead><tr><th>Attribute</th><th>Type</th><th>Required</th><th>Description</th></tr></thead>
+  <tbody>
+    <tr><td><code>id</code></td><td>integer or string</td><td>Yes</td><td>The ID of the project.</td></tr>
+    <tr><td><code>title</code></td><td>string</td><td>Yes</td><td>The title of an issue.</td></tr>
+    <tr><td><code>assignee_ids</code></td><td>integer array</td><td>No</td><td>Users to assign.</td></tr>
+    <tr><td><code>due_date</code></td><td>date</td><td>No</td><td>Due date.</td></tr>
+  </tbody>
+</table>
+</main></body></html>"#;
+
+    fn issues_page() -> ParsedPage {
+        HtmlParser::process_page(ISSUES_HTML).unwrap()
+    }
+
+    #[test]
+    fn test_generates_openapi_document() {
+        let yaml = to_openapi_yaml("GitLab REST API", &[issues_page()]);
+
+        assert!(yaml.starts_with("openapi: 3.1.0\n"));
+        assert!(yaml.contains("  \"/projects/{id}/issues\":\n    get:\n      summary: \"List project issues\"\n"));
+        assert!(yaml.contains("        - \"Issues API\"\n"));
+        assert!(yaml.contains("        - name: \"labels\"\n          in: query\n"));
+        assert!(yaml.contains("description: \"Comma-separated list of \\\"label\\\" names.\""));
+        assert!(yaml.contains("    post:\n"));
+        assert!(yaml.contains("              required:\n                - \"title\"\n              properties:\n"));
+        assert!(yaml.contains(
+            "                \"assignee_ids\":\n                  type: array\n                  items:\n                    type: integer\n"
+        ));
+        assert!(yaml.contains("                  type: string\n                  format: date\n"));
+    }
+
+    #[test]
+    fn test_declares_undocumented_path_parameters() {
+        let page = ParsedPage {
+            title: "Notes API".to_string(),
+            markdown: String::new(),
+            tables: Vec::new(),
+            endpoints: vec![ApiEndpoint::parse_line("DELETE /projects/:id/notes/:note_id", "Delete a note").unwrap()],
+        };
+
+        let yaml = to_openapi_yaml("GitLab REST API", &[page]);
+
+        assert!(yaml.contains("        - name: \"id\"\n          in: path\n          required: true\n"));
+        assert!(yaml.contains("        - name: \"note_id\"\n          in: path\n          required: true\n"));
+    }
+
+    #[test]
+    fn test_duplicate_operations_keep_first_page() {
+        let mut other = issues_page();
+        other.title = "Other".to_string();
+
+        let yaml = to_openapi_yaml("GitLab REST API", &[issues_page(), other]);
+
+        assert_eq!(yaml.matches("    get:\n").count(), 1);
+        assert!(!yaml.contains("\"Other\""));
+    }
+
+    #[test]
+    fn test_empty_document() {
+        let yaml = to_openapi_yaml("GitLab REST API", &[]);
+        assert!(yaml.ends_with("paths: {}\n"));
+    }
+}
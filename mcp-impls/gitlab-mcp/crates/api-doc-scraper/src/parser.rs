@@ -1,8 +1,32 @@
-use scraper::{Html, Selector};
+use std::collections::HashMap;
+
+use scraper::{ElementRef, Html, Selector};
 use tracing::debug;
 
+use crate::endpoint::ApiEndpoint;
 use crate::error::{Result, ScraperError};
 
+/// A processed documentation page
+#[derive(Debug, Clone)]
+pub struct ParsedPage {
+    pub title: String,
+    pub markdown: String,
+    /// Endpoints documented on the page, in page order
+    pub endpoints: Vec<ApiEndpoint>,
+    /// Tables on the page, each a list of rows keyed by column header
+    pub tables: Vec<Vec<HashMap<String, String>>>,
+}
+
+/// How tables are rendered in the page Markdown
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TableFormat {
+    /// Aligned Markdown table
+    #[default]
+    Markdown,
+    /// Fenced `json` block holding an array of row objects keyed by column header
+    Json,
+}
+
 /// HTML parser for extracting documentation content
 pub struct HtmlParser;
 
@@ -60,8 +84,228 @@ impl HtmlParser {
         None
     }
 
-    /// Clean and convert HTML to final markdown
-    pub fn process_page(html: &str) -> Result<(String, String)> {
+    /// Extract the endpoints documented in a GitLab API page.
+    ///
+    /// Endpoints are the `METHOD /path` lines of `<pre>` blocks; each takes
+    /// its summary from the preceding `h2`/`h3`. The first attribute table
+    /// after them (one with `Attribute` and `Required` columns) documents
+    /// their parameters. Response attribute tables have no `Required`
+    /// column and are skipped.
+    pub fn extract_endpoints(html: &str) -> Vec<ApiEndpoint> {
+        let document = Html::parse_document(html);
+        let selector = Selector::parse("h2, h3, pre, table").unwrap();
+
+        let mut endpoints = Vec::new();
+        let mut summary = String::new();
+        // Endpoints still waiting for their attribute table
+        let mut pending = 0;
+
+        for element in document.select(&selector) {
+            match element.value().name() {
+                "h2" | "h3" => {
+                    summary = element_text(element);
+                    pending = 0;
+                }
+                "pre" => {
+                    let text = element.text().collect::<String>();
+                    for line in text.lines() {
+                        if let Some(endpoint) = ApiEndpoint::parse_line(line, summary.clone()) {
+                            endpoints.push(endpoint);
+                            pending += 1;
+                        }
+                    }
+                }
+                "table" if pending > 0 => {
+                    if let Some(attributes) = Self::parse_attribute_table(element) {
+                        let start = endpoints.len() - pending;
+                        for endpoint in &mut endpoints[start..] {
+                            for (name, type_name, required, description) in &attributes {
+                                endpoint.add_attribute(name.clone(), type_name.clone(), *required, description.clone());
+                            }
+                        }
+                        pending = 0;
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        endpoints
+    }
+
+    /// Rows of an attribute table as `(name, type, required, description)`,
+    /// or `None` if the table lacks `Attribute` and `Required` columns
+    fn parse_attribute_table(table: ElementRef<'_>) -> Option<Vec<(String, String, bool, String)>> {
+        let header_selector = Selector::parse("th").unwrap();
+        let row_selector = Selector::parse("tbody tr").unwrap();
+        let cell_selector = Selector::parse("td").unwrap();
+
+        let headers: Vec<String> = table
+            .select(&header_selector)
+            .map(|th| element_text(th).to_lowercase())
+            .collect();
+        let column = |names: &[&str]| headers.iter().position(|h| names.contains(&h.as_str()));
+        let name_col = column(&["attribute", "parameter"])?;
+        let required_col = column(&["required"])?;
+        let type_col = column(&["type"]);
+        let description_col = column(&["description"]);
+
+        let rows = table
+            .select(&row_selector)
+            .filter_map(|row| {
+                let cells: Vec<String> = row.select(&cell_selector).map(element_text).collect();
+                let name = cells.get(name_col)?.trim_matches('`').to_string();
+                if name.is_empty() {
+                    return None;
+                }
+                let cell = |col: Option<usize>| col.and_then(|c| cells.get(c)).cloned().unwrap_or_default();
+                let required = cells
+                    .get(required_col)
+                    .is_some_and(|value| value.to_lowercase().starts_with("yes"));
+                Some((name, cell(type_col), required, cell(description_col)))
+            })
+            .collect();
+
+        Some(rows)
+    }
+
+    /// Extract every table as a list of rows keyed by column header.
+    ///
+    /// Headers come from the `th` cells, or the first row when there are
+    /// none. Missing cells map to empty strings.
+    pub fn extract_tables(html: &str) -> Vec<Vec<HashMap<String, String>>> {
+        let document = Html::parse_document(html);
+        let table_selector = Selector::parse("table").unwrap();
+
+        document
+            .select(&table_selector)
+            .map(|table| {
+                let (headers, rows) = Self::parse_table(table);
+                rows.into_iter()
+                    .map(|row| headers.iter().cloned().zip(row).collect())
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Header texts and row cells of a table; every row has one cell per header
+    fn parse_table(table: ElementRef<'_>) -> (Vec<String>, Vec<Vec<String>>) {
+        let row_selector = Selector::parse("tr").unwrap();
+        let header_selector = Selector::parse("th").unwrap();
+        let cell_selector = Selector::parse("td").unwrap();
+
+        let mut rows: Vec<Vec<String>> = Vec::new();
+        let mut headers: Vec<String> = Vec::new();
+        for row in table.select(&row_selector) {
+            let header_cells: Vec<String> = row.select(&header_selector).map(element_text).collect();
+            if headers.is_empty() && !header_cells.is_empty() {
+                headers = header_cells;
+                continue;
+            }
+            let cells: Vec<String> = row.select(&cell_selector).map(element_text).collect();
+            if !cells.is_empty() {
+                rows.push(cells);
+            }
+        }
+        if headers.is_empty() && !rows.is_empty() {
+            headers = rows.remove(0);
+        }
+
+        for row in &mut rows {
+            row.resize(headers.len(), String::new());
+        }
+        (headers, rows)
+    }
+
+    /// Render a table as Markdown with padded columns
+    fn table_to_markdown(headers: &[String], rows: &[Vec<String>]) -> String {
+        let escape = |cell: &str| cell.replace('|', "\\|");
+        let headers: Vec<String> = headers.iter().map(|h| escape(h)).collect();
+        let rows: Vec<Vec<String>> = rows.iter().map(|row| row.iter().map(|c| escape(c)).collect()).collect();
+
+        let widths: Vec<usize> = (0..headers.len())
+            .map(|col| {
+                rows.iter()
+                    .map(|row| row[col].chars().count())
+                    .chain([headers[col].chars().count(), 3])
+                    .max()
+                    .unwrap_or(3)
+            })
+            .collect();
+        let line = |cells: &[String]| {
+            let cells: Vec<String> = cells
+                .iter()
+                .zip(&widths)
+                .map(|(cell, width)| format!("{}{}", cell, " ".repeat(width - cell.chars().count())))
+                .collect();
+            format!("| {} |", cells.join(" | "))
+        };
+
+        let mut lines = vec![line(&headers)];
+        lines.push(line(&widths.iter().map(|width| "-".repeat(*width)).collect::<Vec<_>>()));
+        lines.extend(rows.iter().map(|row| line(row)));
+        lines.join("\n")
+    }
+
+    /// Render a table as a fenced `json` block of row objects
+    fn table_to_json(headers: &[String], rows: &[Vec<String>]) -> String {
+        let rows: Vec<serde_json::Value> = rows
+            .iter()
+            .map(|row| {
+                let object = headers
+                    .iter()
+                    .zip(row)
+                    .map(|(header, cell)| (header.clone(), serde_json::Value::String(cell.clone())))
+                    .collect();
+                serde_json::Value::Object(object)
+            })
+            .collect();
+        let json = serde_json::to_string_pretty(&rows).unwrap_or_default();
+        format!("```json\n{}\n```", json)
+    }
+
+    /// Convert HTML to Markdown, rendering tables in `format`.
+    ///
+    /// Each table is swapped for a placeholder before conversion and the
+    /// rendered table put back afterwards, since the generic converter
+    /// flattens tables.
+    pub fn html_to_markdown_with_tables(html: &str, format: TableFormat) -> Result<String> {
+        let document = Html::parse_document(html);
+        let table_selector = Selector::parse("table").unwrap();
+
+        let mut html = html.to_string();
+        let mut rendered = Vec::new();
+        for table in document.select(&table_selector) {
+            let table_html = table.html();
+            if !html.contains(&table_html) {
+                continue;
+            }
+            let (headers, rows) = Self::parse_table(table);
+            if headers.is_empty() {
+                continue;
+            }
+            let placeholder = format!("TABLEPLACEHOLDER{}END", rendered.len());
+            html = html.replacen(&table_html, &format!("<p>{}</p>", placeholder), 1);
+            rendered.push(match format {
+                TableFormat::Markdown => Self::table_to_markdown(&headers, &rows),
+                TableFormat::Json => Self::table_to_json(&headers, &rows),
+            });
+        }
+
+        let mut markdown = Self::html_to_markdown(&html)?;
+        for (index, table) in rendered.iter().enumerate() {
+            markdown = markdown.replacen(&format!("TABLEPLACEHOLDER{}END", index), table, 1);
+        }
+        Ok(markdown)
+    }
+
+    /// Clean and convert HTML to final markdown, with Markdown tables
+    pub fn process_page(html: &str) -> Result<ParsedPage> {
+        Self::process_page_with_tables(html, TableFormat::Markdown)
+    }
+
+    /// Clean and convert HTML to final markdown, rendering tables in `table_format`
+    pub fn process_page_with_tables(html: &str, table_format: TableFormat) -> Result<ParsedPage> {
         debug!("Processing HTML page");
 
         // Extract title
@@ -71,12 +315,23 @@ impl HtmlParser {
         let content_html = Self::extract_main_content(html)?;
 
         // Convert to markdown
-        let markdown = Self::html_to_markdown(&content_html)?;
+        let markdown = Self::html_to_markdown_with_tables(&content_html, table_format)?;
 
         // Clean up markdown
         let markdown = Self::clean_markdown(&markdown);
 
-        Ok((title, markdown))
+        // Extract endpoints
+        let endpoints = Self::extract_endpoints(&content_html);
+
+        // Extract tables
+        let tables = Self::extract_tables(&content_html);
+
+        Ok(ParsedPage {
+            title,
+            markdown,
+            endpoints,
+            tables,
+        })
     }
 
     /// Clean up markdown content
@@ -107,9 +362,15 @@ impl HtmlParser {
     }
 }
 
+/// Text of an element with whitespace collapsed
+fn element_text(element: ElementRef<'_>) -> String {
+    element.text().collect::<String>().split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::endpoint::ParameterLocation;
 
     #[test]
     fn test_extract_title_from_html() {
@@ -131,4 +392,123 @@ mod tests {
         let cleaned = HtmlParser::clean_markdown(markdown);
         assert_eq!(cleaned, "Hello\n\nWorld");
     }
+
+    const TABLE_HTML: &str = r#"<main>
+<p>Supported attributes:</p>
+<table>
+  <thead><tr><th>Attribute</th><th>Type</th><th>Description</th></tr></thead>
+  <tbody>
+    <tr><td><code>id</code></td><td>integer or string</td><td>The ID of the project.</td></tr>
+    <tr><td><code>state</code></td><td>string</td><td>Either <code>opened</code> | <code>closed</code>.</td></tr>
+  </tbody>
+</table>
+</main>"#;
+
+    #[test]
+    fn test_extract_tables() {
+        let tables = HtmlParser::extract_tables(TABLE_HTML);
+
+        assert_eq!(tables.len(), 1);
+        assert_eq!(tables[0].len(), 2);
+        assert_eq!(tables[0][0]["Attribute"], "id");
+        assert_eq!(tables[0][0]["Type"], "integer or string");
+        assert_eq!(tables[0][1]["Description"], "Either opened | closed.");
+    }
+
+    #[test]
+    fn test_extract_tables_without_header_cells() {
+        let html = "<table><tr><td>Name</td><td>Value</td></tr><tr><td>a</td></tr></table>";
+
+        let tables = HtmlParser::extract_tables(html);
+
+        assert_eq!(tables[0].len(), 1);
+        assert_eq!(tables[0][0]["Name"], "a");
+        assert_eq!(tables[0][0]["Value"], "");
+    }
+
+    #[test]
+    fn test_tables_rendered_as_markdown() {
+        let page = HtmlParser::process_page(TABLE_HTML).unwrap();
+
+        let expected = "\
+| Attribute | Type              | Description              |
+| --------- | ----------------- | ------------------------ |
+| id        | integer or string | The ID of the project.   |
+| state     | string            | Either opened \\| closed. |";
+        assert!(page.markdown.contains(expected), "{}", page.markdown);
+        assert!(page.markdown.starts_with("Supported attributes:"));
+        assert_eq!(page.tables.len(), 1);
+    }
+
+    #[test]
+    fn test_tables_rendered_as_json() {
+        let page = HtmlParser::process_page_with_tables(TABLE_HTML, TableFormat::Json).unwrap();
+
+        let start = page.markdown.find("```json\n").unwrap() + "```json\n".len();
+        let end = page.markdown[start..].find("\n```").unwrap() + start;
+        let rows: serde_json::Value = serde_json::from_str(&page.markdown[start..end]).unwrap();
+        assert_eq!(rows[1]["Attribute"], "state");
+        assert_eq!(rows[1]["Type"], "string");
+    }
+
+    #[test]
+    fn test_extract_endpoints() {
+        let html = r#"<main>
+<h2>Get a single issue</h2>
+<pre><code>GET /projects/:id/issues/:issue_iid?with_labels_details=true</code></pre>
+<table>
+  <thead><tr><th>Attribute</th><th>Type</th><th>Required</th><th>Description</th></tr></thead>
+  <tbody>
+    <tr><td><code>id</code></td><td>integer or string</td><td>Yes</td><td>Project ID.</td></tr>
+    <tr><td><code>with_labels_details</code></td><td>boolean</td><td>No</td><td>Include label details.</td></tr>
+  </tbody>
+</table>
+<pre><code>curl --header "PRIVATE-TOKEN: token" "https://gitlab.example.com/api/v4/projects/4/issues/41"</code></pre>
+<h2>Delete an issue</h2>
+<pre><code>DELETE /projects/:id/issues/:issue_iid</code></pre>
+<table>
+  <thead><tr><th>Attribute</th><th>Type</th><th>Description</th></tr></thead>
+  <tbody><tr><td><code>state</code></td><td>string</td><td>Response field.</td></tr></tbody>
+</table>
+</main>"#;
+
+        let endpoints = HtmlParser::extract_endpoints(html);
+
+        assert_eq!(endpoints.len(), 2);
+        let get = &endpoints[0];
+        assert_eq!(get.method, "GET");
+        assert_eq!(get.path, "/projects/{id}/issues/{issue_iid}");
+        assert_eq!(get.summary, "Get a single issue");
+        assert_eq!(get.parameters.len(), 2);
+        assert_eq!(get.parameters[0].location, ParameterLocation::Path);
+        assert_eq!(get.parameters[1].location, ParameterLocation::Query);
+        assert!(!get.parameters[1].required);
+        assert!(get.request_body.is_empty());
+
+        // The response table has no Required column
+        let delete = &endpoints[1];
+        assert_eq!(delete.summary, "Delete an issue");
+        assert!(delete.parameters.is_empty());
+    }
+
+    #[test]
+    fn test_body_attributes_for_post() {
+        let html = r#"<h2>New issue</h2>
+<pre><code>POST /projects/:id/issues</code></pre>
+<table>
+  <thead><tr><th>Attribute</th><th>Type</th><th>Required</th><th>Description</th></tr></thead>
+  <tbody>
+    <tr><td><code>id</code></td><td>integer</td><td>Yes</td><td>Project ID.</td></tr>
+    <tr><td><code>title</code></td><td>string</td><td>Yes</td><td>Issue title.</td></tr>
+  </tbody>
+</table>"#;
+
+        let endpoints = HtmlParser::extract_endpoints(html);
+
+        assert_eq!(endpoints[0].parameters.len(), 1);
+        assert_eq!(endpoints[0].request_body.len(), 1);
+        assert_eq!(endpoints[0].request_body[0].name, "title");
+        assert_eq!(endpoints[0].request_body[0].location, ParameterLocation::Body);
+        assert!(endpoints[0].request_body[0].required);
+    }
 }
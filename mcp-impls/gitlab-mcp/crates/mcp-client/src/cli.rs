@@ -1,5 +1,7 @@
 use clap::{Parser, Subcommand};
 
+use crate::output::OutputMode;
+
 #[derive(Parser)]
 #[command(name = "gitlab-mcp")]
 #[command(about = "GitLab MCP Client - CLI tool for GitLab operations", long_about = None)]
@@ -13,9 +15,9 @@ pub struct Cli {
     #[arg(long)]
     pub token: Option<String>,
 
-    /// Output format (table, json, plain)
-    #[arg(long, default_value = "table")]
-    pub output: String,
+    /// Output format
+    #[arg(long, value_enum, default_value_t = OutputMode::Table)]
+    pub output: OutputMode,
 
     /// Disable colors
     #[arg(long, action = clap::ArgAction::SetFalse)]
@@ -86,6 +88,9 @@ pub enum Commands {
     /// User operations
     #[command(subcommand)]
     User(UserCommands),
+
+    /// Interactive session reusing one MCP server connection
+    Repl,
 }
 
 /// Project commands
@@ -149,7 +154,8 @@ pub enum IssueCommands {
         /// Issue description
         #[arg(long)]
         description: Option<String>,
-        /// Issue labels (comma-separated)
+        /// Issue labels as comma-separated label names, not label IDs
+        /// (missing labels are created by GitLab)
         #[arg(long)]
         labels: Option<String>,
         /// Assignee username(s)
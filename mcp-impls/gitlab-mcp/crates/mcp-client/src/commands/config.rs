@@ -1,6 +1,44 @@
 use crate::{cli::ConfigCommands, config::ClientConfig, output::OutputFormatter, Result};
 use crate::mcp_transport::McpServerClient;
 use gitlab_mcp_server::Config as ServerConfig;
+use serde::Serialize;
+
+/// Field order of the `config show` table
+const SHOW_FIELDS: &[&str] = &[
+    "gitlab_url",
+    "gitlab_token",
+    "output_format",
+    "color",
+    "server_config_file",
+    "server_gitlab_url",
+    "server_gitlab_token",
+    "server_log_level",
+    "mcp_server_command",
+    "mcp_server_args",
+];
+
+/// Client and server configuration shown by `config show`
+#[derive(Serialize)]
+struct ConfigView {
+    gitlab_url: String,
+    gitlab_token: String,
+    output_format: String,
+    color: bool,
+    server_config_file: Option<String>,
+    server_gitlab_url: Option<String>,
+    server_gitlab_token: Option<String>,
+    server_log_level: Option<String>,
+    mcp_server_command: String,
+    mcp_server_args: Option<String>,
+}
+
+/// Config file locations shown by `config path`
+#[derive(Serialize)]
+struct ConfigPaths {
+    client_config: String,
+    server_config: String,
+    server_config_exists: bool,
+}
 
 pub async fn execute_config(
     cmd: ConfigCommands,
@@ -12,35 +50,26 @@ pub async fn execute_config(
 
     match cmd {
         ConfigCommands::Show => {
-            println!("\nGitLab MCP Configuration:");
-            println!("========================");
-            println!("GitLab URL: {}", config.gitlab_url);
-            println!("Token: {}***", if config.gitlab_token.len() > 8 { &config.gitlab_token[..8] } else { &config.gitlab_token });
-            println!("Output Format: {}", config.output_format);
-            println!("Colors: {}", config.color);
-
-            // Also show server config if exists
-            if let Ok(path) = ServerConfig::config_file() {
-                println!("\nServer Config File: {}", path.display());
-                if path.exists() {
-                    if let Ok(server_config) = ServerConfig::from_file(path) {
-                        println!("Server GitLab URL: {}", server_config.gitlab_url);
-                        println!("Server Token: {}***", if server_config.gitlab_token.len() > 8 {
-                            &server_config.gitlab_token[..8]
-                        } else {
-                            &server_config.gitlab_token
-                        });
-                        println!("Server Log Level: {}", server_config.log_level);
-                    }
-                }
-            }
+            let server_config_file = ServerConfig::config_file().ok();
+            let server_config = server_config_file
+                .as_ref()
+                .filter(|path| path.exists())
+                .and_then(|path| ServerConfig::from_file(path.clone()).ok());
 
-            // Show MCP server configuration
-            println!("\nMCP Server:");
-            println!("-----------");
-            println!("Command: {}", std::env::var("GITLAB_MCP_SERVER").unwrap_or_else(|_| "gitlab-mcp-server".to_string()));
-            println!("Args: {}", std::env::var("GITLAB_MCP_SERVER_ARGS").unwrap_or_else(|_| "(none)".to_string()));
-            println!();
+            let view = ConfigView {
+                gitlab_url: config.gitlab_url.clone(),
+                gitlab_token: mask_token(&config.gitlab_token),
+                output_format: config.output_format.clone(),
+                color: config.color,
+                server_config_file: server_config_file.map(|path| path.display().to_string()),
+                server_gitlab_url: server_config.as_ref().map(|c| c.gitlab_url.clone()),
+                server_gitlab_token: server_config.as_ref().map(|c| mask_token(&c.gitlab_token)),
+                server_log_level: server_config.as_ref().map(|c| c.log_level.clone()),
+                mcp_server_command: std::env::var("GITLAB_MCP_SERVER")
+                    .unwrap_or_else(|_| "gitlab-mcp-server".to_string()),
+                mcp_server_args: std::env::var("GITLAB_MCP_SERVER_ARGS").ok(),
+            };
+            formatter.print_record(&view, SHOW_FIELDS)?;
         }
 
         ConfigCommands::SetUrl { url } => {
@@ -86,23 +115,25 @@ pub async fn execute_config(
         }
 
         ConfigCommands::Path => {
-            println!("\nConfig file locations:");
-            println!("=====================");
-            println!("Client config: {}", ClientConfig::config_file()?.display());
-            println!("Server config: {}", ServerConfig::config_file()?.display());
-            println!();
+            let server_config = ServerConfig::config_file()?;
+            let paths = ConfigPaths {
+                client_config: ClientConfig::config_file()?.display().to_string(),
+                server_config_exists: server_config.exists(),
+                server_config: server_config.display().to_string(),
+            };
+            formatter.print_record(&paths, &["client_config", "server_config", "server_config_exists"])?;
 
-            // Show server config status
-            if let Ok(path) = ServerConfig::config_file() {
-                if path.exists() {
-                    println!("Server config exists and will be used by the MCP server.");
-                } else {
-                    println!("Server config does not exist yet.");
-                    println!("Run: gitlab-mcp config set-token <your-token> to create it.");
-                }
+            if formatter.is_table() && !paths.server_config_exists {
+                formatter.info("Run: gitlab-mcp config set-token <your-token> to create the server config.");
             }
         }
     }
 
     Ok((mcp_client, ()))
 }
+
+/// Show only the first 8 characters of a token
+fn mask_token(token: &str) -> String {
+    let visible: String = token.chars().take(8).collect();
+    format!("{}***", visible)
+}
@@ -1,5 +1,6 @@
 pub mod project;
 pub mod config;
+pub mod repl;
 
 use crate::{Cli, OutputFormatter, Result, Commands};
 use crate::mcp_transport::McpServerClient;
@@ -7,6 +8,7 @@ use clap::Parser;
 
 pub use project::*;
 pub use config::*;
+pub use repl::*;
 
 /// Execute a command
 pub async fn execute() -> Result<()> {
@@ -43,6 +45,11 @@ pub async fn execute() -> Result<()> {
             let (client, _) = execute_project(cmd, mcp_client, formatter).await?;
             client
         }
+        Commands::Repl => {
+            let (client, _) =
+                execute_repl(mcp_client, &server_command, &server_args, cli.output, cli.color).await?;
+            client
+        }
         _ => {
             mcp_client.close()?;
             formatter.error("Command not implemented yet");
@@ -50,6 +57,10 @@ pub async fn execute() -> Result<()> {
         }
     };
 
+    if cli.verbose {
+        eprintln!("MCP transport stats: {}", mcp_client.stats());
+    }
+
     // Close the MCP connection
     mcp_client.close()?;
 
@@ -0,0 +1,230 @@
+use std::path::PathBuf;
+
+use clap::{CommandFactory, Parser};
+use rustyline::completion::{Completer, Pair};
+use rustyline::error::ReadlineError;
+use rustyline::highlight::Highlighter;
+use rustyline::hint::Hinter;
+use rustyline::history::FileHistory;
+use rustyline::validate::Validator;
+use rustyline::{Context, Editor, Helper};
+
+use crate::mcp_transport::McpServerClient;
+use crate::output::{OutputFormatter, OutputMode};
+use crate::{Commands, Result};
+
+use super::{execute_config, execute_project};
+
+/// Words that end the session
+const QUIT_COMMANDS: &[&str] = &["quit", "exit"];
+
+/// One line typed at the REPL prompt
+#[derive(Parser)]
+#[command(
+    name = "gitlab-mcp",
+    no_binary_name = true,
+    override_usage = "<COMMAND> [ARGS]...",
+    after_help = "Type `quit` or press Ctrl-D to leave the REPL."
+)]
+struct ReplLine {
+    #[command(subcommand)]
+    command: Commands,
+}
+
+/// Run an interactive session on one MCP server connection.
+///
+/// Each line is parsed like the command line, without the global flags;
+/// `output` and `color` apply to every command. A command that fails
+/// consumes the connection, so the server is restarted before the next
+/// prompt.
+pub async fn execute_repl(
+    mut mcp_client: McpServerClient,
+    server_command: &str,
+    server_args: &[String],
+    output: OutputMode,
+    color: bool,
+) -> Result<(McpServerClient, ())> {
+    let mut editor: Editor<ReplHelper, FileHistory> = Editor::new()?;
+    editor.set_helper(Some(ReplHelper::new()));
+
+    let history = history_file();
+    if let Some(path) = &history {
+        // A missing history file is expected on first use
+        let _ = editor.load_history(path);
+    }
+
+    loop {
+        let line = match editor.readline("> ") {
+            Ok(line) => line,
+            Err(ReadlineError::Interrupted) => continue,
+            Err(ReadlineError::Eof) => break,
+            Err(e) => return Err(e.into()),
+        };
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        editor.add_history_entry(line)?;
+        if QUIT_COMMANDS.contains(&line) {
+            break;
+        }
+
+        let formatter = OutputFormatter::new(&output, color);
+        let Some(words) = shlex::split(line) else {
+            formatter.error("Unbalanced quotes");
+            continue;
+        };
+        let command = match ReplLine::try_parse_from(words) {
+            Ok(parsed) => parsed.command,
+            Err(e) => {
+                // Also prints `help` and `--help` output
+                let _ = e.print();
+                continue;
+            }
+        };
+
+        let result = match command {
+            Commands::Config(cmd) => execute_config(cmd, mcp_client, formatter).await,
+            Commands::Project(cmd) => execute_project(cmd, mcp_client, formatter).await,
+            Commands::Repl => {
+                formatter.error("Already in the REPL");
+                continue;
+            }
+            _ => {
+                formatter.error("Command not implemented yet");
+                continue;
+            }
+        };
+
+        mcp_client = match result {
+            Ok((client, _)) => client,
+            Err(e) => {
+                OutputFormatter::new(&output, color).error(&e.to_string());
+                McpServerClient::start(server_command, server_args)?
+            }
+        };
+    }
+
+    if let Some(path) = &history {
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        editor.save_history(path)?;
+    }
+
+    Ok((mcp_client, ()))
+}
+
+/// `~/.mcp/history`
+fn history_file() -> Option<PathBuf> {
+    dirs::home_dir().map(|home| home.join(".mcp").join("history"))
+}
+
+/// Completes subcommand names at the prompt
+struct ReplHelper {
+    /// Top-level commands with their subcommands
+    commands: Vec<(String, Vec<String>)>,
+}
+
+impl ReplHelper {
+    fn new() -> Self {
+        let mut commands: Vec<(String, Vec<String>)> = ReplLine::command()
+            .get_subcommands()
+            .map(|command| {
+                let subcommands = command.get_subcommands().map(|sub| sub.get_name().to_string()).collect();
+                (command.get_name().to_string(), subcommands)
+            })
+            .collect();
+        commands.push(("help".to_string(), Vec::new()));
+        commands.extend(QUIT_COMMANDS.iter().map(|quit| (quit.to_string(), Vec::new())));
+        Self { commands }
+    }
+
+    /// Start of the word being completed and the candidate names
+    fn candidates(&self, line: &str) -> (usize, Vec<String>) {
+        let start = line.rfind(char::is_whitespace).map_or(0, |i| i + 1);
+        let prefix = &line[start..];
+        let previous: Vec<&str> = line[..start].split_whitespace().collect();
+
+        let names: Vec<&String> = match previous.as_slice() {
+            [] => self.commands.iter().map(|(name, _)| name).collect(),
+            // `help` takes a command name
+            ["help"] => self.commands.iter().map(|(name, _)| name).filter(|name| *name != "help").collect(),
+            [command] => self
+                .commands
+                .iter()
+                .find(|(name, _)| name == command)
+                .map(|(_, subcommands)| subcommands.iter().collect())
+                .unwrap_or_default(),
+            _ => Vec::new(),
+        };
+
+        let matches = names
+            .into_iter()
+            .filter(|name| name.starts_with(prefix))
+            .cloned()
+            .collect();
+        (start, matches)
+    }
+}
+
+impl Completer for ReplHelper {
+    type Candidate = Pair;
+
+    fn complete(&self, line: &str, pos: usize, _ctx: &Context<'_>) -> rustyline::Result<(usize, Vec<Pair>)> {
+        let (start, names) = self.candidates(&line[..pos]);
+        let pairs = names
+            .into_iter()
+            .map(|name| Pair {
+                display: name.clone(),
+                replacement: format!("{} ", name),
+            })
+            .collect();
+        Ok((start, pairs))
+    }
+}
+
+impl Hinter for ReplHelper {
+    type Hint = String;
+}
+
+impl Highlighter for ReplHelper {}
+
+impl Validator for ReplHelper {}
+
+impl Helper for ReplHelper {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_completes_command_names() {
+        let helper = ReplHelper::new();
+
+        let (start, names) = helper.candidates("pro");
+        assert_eq!(start, 0);
+        assert_eq!(names, ["project"]);
+
+        let (start, names) = helper.candidates("project ");
+        assert_eq!(start, 8);
+        assert!(names.contains(&"list".to_string()) && names.contains(&"get".to_string()));
+
+        let (_, names) = helper.candidates("config set-");
+        assert_eq!(names, ["set-url", "set-token", "set-log-level"]);
+
+        let (_, names) = helper.candidates("project list --");
+        assert!(names.is_empty());
+    }
+
+    #[test]
+    fn test_parses_lines_like_the_command_line() {
+        let words = shlex::split("project list --search 'my group'").unwrap();
+        let line = ReplLine::try_parse_from(words).unwrap();
+
+        assert!(matches!(
+            line.command,
+            Commands::Project(crate::cli::ProjectCommands::List { search: Some(ref s), .. }) if s == "my group"
+        ));
+    }
+}
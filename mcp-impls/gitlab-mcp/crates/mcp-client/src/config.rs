@@ -8,7 +8,7 @@ pub struct ClientConfig {
     pub gitlab_url: String,
     /// GitLab personal access token
     pub gitlab_token: String,
-    /// Output format: table, json, plain
+    /// Output format: table, json, yaml
     pub output_format: String,
     /// Enable colors
     pub color: bool,
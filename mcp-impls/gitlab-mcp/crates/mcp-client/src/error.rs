@@ -0,0 +1,22 @@
+//! Errors of the connection to gitlab-mcp-server
+
+use thiserror::Error;
+
+/// Errors raised by [`McpServerClient`](crate::mcp_transport::McpServerClient)
+#[derive(Debug, Error)]
+pub enum ClientError {
+    /// The server process exited or its pipes broke
+    #[error("Connection to MCP server lost: {0}")]
+    ConnectionLost(String),
+
+    /// The connection kept failing after every restart the policy allows
+    #[error("{method} failed after {attempts} reconnect attempt(s): {reason}")]
+    ReconnectExhausted {
+        /// JSON-RPC method of the request that could not be completed
+        method: String,
+        /// Number of times the server was restarted
+        attempts: u32,
+        /// Why the last attempt failed
+        reason: String,
+    },
+}
@@ -5,11 +5,14 @@
 pub mod cli;
 pub mod config;
 pub mod commands;
+pub mod error;
 pub mod output;
 pub mod mcp_transport;
 
 // Re-export commonly used types
 pub use cli::{Cli, Commands, ConfigCommands};
 pub use config::ClientConfig;
-pub use output::OutputFormatter;
+pub use error::ClientError;
+pub use mcp_transport::RestartPolicy;
+pub use output::{OutputFormatter, OutputMode};
 pub use anyhow::Result;
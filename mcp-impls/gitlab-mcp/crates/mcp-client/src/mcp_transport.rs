@@ -4,22 +4,112 @@ use std::collections::HashMap;
 use std::sync::mpsc::{self, RecvTimeoutError};
 use std::time::Duration;
 
-use mcp_client::stdio::{JsonRpcMessage, StdioClientTransport, StdioServerParameters, StdioStream};
+use mcp_client::stdio::{
+    JsonRpcMessage, StdioClientTransport, StdioClientTransportError, StdioServerParameters, StdioStream,
+};
+use mcp_core::stdio::CloseReason;
+use mcp_core::TransportStats;
 use mcp_core::{NotificationMessage, RequestMessage, ResultMessage};
 use serde_json::{json, Value};
 
+use crate::error::ClientError;
 use crate::Result;
 
-/// MCP client that communicates with gitlab-mcp-server via stdio
+/// How `McpServerClient` recovers when the server process dies
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RestartPolicy {
+    /// Restarts attempted for one request before giving up
+    pub max_retries: u32,
+    /// Delay before the first restart; it doubles on every further one
+    pub backoff: Duration,
+}
+
+impl Default for RestartPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            backoff: Duration::from_millis(200),
+        }
+    }
+}
+
+impl RestartPolicy {
+    /// Restart at most `max_retries` times per request
+    pub fn new(max_retries: u32) -> Self {
+        Self {
+            max_retries,
+            ..Default::default()
+        }
+    }
+
+    /// Never restart the server
+    pub fn never() -> Self {
+        Self::new(0)
+    }
+
+    /// Set the delay before the first restart
+    pub fn with_backoff(mut self, backoff: Duration) -> Self {
+        self.backoff = backoff;
+        self
+    }
+
+    /// Delay before restart number `attempt`, counting from 1
+    fn delay(&self, attempt: u32) -> Duration {
+        self.backoff.saturating_mul(2u32.saturating_pow(attempt.saturating_sub(1)))
+    }
+}
+
+/// What the transport reports to the client
+enum TransportEvent {
+    Message(JsonRpcMessage),
+    Closed(CloseReason),
+}
+
+/// MCP client that communicates with gitlab-mcp-server via stdio.
+///
+/// When the server process exits or its pipes break during a request, the
+/// server is restarted, the `initialize` handshake is replayed and the
+/// request is sent again, as allowed by the [`RestartPolicy`]. Replayed
+/// tool calls may run twice on the server if it crashed after acting.
 pub struct McpServerClient {
     transport: StdioClientTransport,
-    receiver: mpsc::Receiver<JsonRpcMessage>,
+    receiver: mpsc::Receiver<TransportEvent>,
+    server_command: String,
+    server_args: Vec<String>,
+    restart_policy: RestartPolicy,
 }
 
 impl McpServerClient {
     /// Start the MCP server and create a new client connection
     pub fn start(server_command: &str, server_args: &[String]) -> Result<Self> {
-        let (message_tx, message_rx) = mpsc::channel();
+        let (transport, receiver) = Self::spawn(server_command, server_args)?;
+
+        let mut client = Self {
+            transport,
+            receiver,
+            server_command: server_command.to_string(),
+            server_args: server_args.to_vec(),
+            restart_policy: RestartPolicy::default(),
+        };
+
+        // Initialize the MCP session
+        client.initialize()?;
+
+        Ok(client)
+    }
+
+    /// Set how the server is restarted after a crash
+    pub fn with_restart_policy(mut self, policy: RestartPolicy) -> Self {
+        self.restart_policy = policy;
+        self
+    }
+
+    /// Start the server process
+    fn spawn(
+        server_command: &str,
+        server_args: &[String],
+    ) -> Result<(StdioClientTransport, mpsc::Receiver<TransportEvent>)> {
+        let (event_tx, event_rx) = mpsc::channel();
 
         // Collect environment variables to pass to the server
         let mut server_env = HashMap::new();
@@ -43,8 +133,12 @@ impl McpServerClient {
 
         let mut transport = StdioClientTransport::new(params);
 
+        let message_tx = event_tx.clone();
         transport.on_message(move |message| {
-            let _ = message_tx.send(message);
+            let _ = message_tx.send(TransportEvent::Message(message));
+        });
+        transport.on_close_with_reason(move |reason| {
+            let _ = event_tx.send(TransportEvent::Closed(reason));
         });
 
         transport.on_error(|error| eprintln!("MCP transport error: {error}"));
@@ -52,15 +146,16 @@ impl McpServerClient {
         // Start the server process
         transport.start()?;
 
-        let mut client = Self {
-            transport,
-            receiver: message_rx,
-        };
-
-        // Initialize the MCP session
-        client.initialize()?;
+        Ok((transport, event_rx))
+    }
 
-        Ok(client)
+    /// Replace a dead server process with a new, initialized one
+    fn reconnect(&mut self) -> Result<()> {
+        let _ = self.transport.close();
+        let (transport, receiver) = Self::spawn(&self.server_command, &self.server_args)?;
+        self.transport = transport;
+        self.receiver = receiver;
+        self.initialize()
     }
 
     /// Initialize the MCP session
@@ -99,9 +194,7 @@ impl McpServerClient {
 
     /// List available tools from the server
     pub fn list_tools(&mut self) -> Result<Vec<Tool>> {
-        self.send_request("client-tools-list", "tools/list", json!({}))?;
-
-        match self.wait_for_result("client-tools-list", Duration::from_secs(5))? {
+        match self.request("client-tools-list", "tools/list", json!({}), Duration::from_secs(5))? {
             Some(result) => {
                 if let Some(error) = result.error {
                     return Err(anyhow::anyhow!("List tools failed: {}", error.message));
@@ -123,13 +216,9 @@ impl McpServerClient {
     pub fn call_tool(&mut self, name: &str, arguments: Value) -> Result<ToolResponse> {
         let request_id = format!("call-tool-{}", name);
 
-        self.send_request(
-            &request_id,
-            "tools/call",
-            json!({ "name": name, "arguments": arguments }),
-        )?;
+        let params = json!({ "name": name, "arguments": arguments });
 
-        match self.wait_for_result(&request_id, Duration::from_secs(30))? {
+        match self.request(&request_id, "tools/call", params, Duration::from_secs(30))? {
             Some(result) => {
                 if let Some(error) = result.error {
                     return Err(anyhow::anyhow!("Tool call failed: {}", error.message));
@@ -162,7 +251,7 @@ impl McpServerClient {
             args["page"] = json!(page);
         }
 
-        let response = self.call_tool("list_projects", args)?;
+        let response = self.call_tool("gitlab_list_projects", args)?;
 
         // Check if the response is an error
         if let Some(is_error) = response.result.get("isError").and_then(|v| v.as_bool()) {
@@ -193,7 +282,7 @@ impl McpServerClient {
     /// Get a project by ID
     pub fn get_project(&mut self, project_id: &str) -> Result<Project> {
         let args = json!({ "project_id": project_id });
-        let response = self.call_tool("get_project", args)?;
+        let response = self.call_tool("gitlab_get_project", args)?;
 
         if let Some(content) = response.result.get("content").and_then(|c| c.as_array()) {
             if let Some(text) = content.first().and_then(|c| c.get("text").and_then(|t| t.as_str())) {
@@ -209,7 +298,7 @@ impl McpServerClient {
     /// List issues for a project
     pub fn list_issues(&mut self, project_id: &str) -> Result<Vec<Issue>> {
         let args = json!({ "project_id": project_id });
-        let response = self.call_tool("list_issues", args)?;
+        let response = self.call_tool("gitlab_list_issues", args)?;
 
         if let Some(content) = response.result.get("content").and_then(|c| c.as_array()) {
             if let Some(text) = content.first().and_then(|c| c.get("text").and_then(|t| t.as_str())) {
@@ -225,7 +314,7 @@ impl McpServerClient {
     /// Get an issue by IID
     pub fn get_issue(&mut self, project_id: &str, issue_iid: u64) -> Result<Issue> {
         let args = json!({ "project_id": project_id, "issue_iid": issue_iid });
-        let response = self.call_tool("get_issue", args)?;
+        let response = self.call_tool("gitlab_get_issue", args)?;
 
         if let Some(content) = response.result.get("content").and_then(|c| c.as_array()) {
             if let Some(text) = content.first().and_then(|c| c.get("text").and_then(|t| t.as_str())) {
@@ -241,7 +330,7 @@ impl McpServerClient {
     /// List merge requests for a project
     pub fn list_merge_requests(&mut self, project_id: &str) -> Result<Vec<MergeRequest>> {
         let args = json!({ "project_id": project_id });
-        let response = self.call_tool("list_merge_requests", args)?;
+        let response = self.call_tool("gitlab_list_merge_requests", args)?;
 
         if let Some(content) = response.result.get("content").and_then(|c| c.as_array()) {
             if let Some(text) = content.first().and_then(|c| c.get("text").and_then(|t| t.as_str())) {
@@ -257,7 +346,7 @@ impl McpServerClient {
     /// Get a merge request by IID
     pub fn get_merge_request(&mut self, project_id: &str, mr_iid: u64) -> Result<MergeRequest> {
         let args = json!({ "project_id": project_id, "mr_iid": mr_iid });
-        let response = self.call_tool("get_merge_request", args)?;
+        let response = self.call_tool("gitlab_get_merge_request", args)?;
 
         if let Some(content) = response.result.get("content").and_then(|c| c.as_array()) {
             if let Some(text) = content.first().and_then(|c| c.get("text").and_then(|t| t.as_str())) {
@@ -270,6 +359,11 @@ impl McpServerClient {
         Err(anyhow::anyhow!("No merge request data in response"))
     }
 
+    /// Messages and bytes exchanged with the server so far
+    pub fn stats(&self) -> TransportStats {
+        self.transport.stats()
+    }
+
     /// Close the connection
     pub fn close(mut self) -> Result<()> {
         self.transport.close()?;
@@ -278,6 +372,47 @@ impl McpServerClient {
 
     // Helper methods
 
+    /// Send a request and wait for its result, restarting the server and
+    /// replaying the request if the connection is lost
+    fn request(
+        &mut self,
+        request_id: &str,
+        method: &str,
+        params: Value,
+        timeout: Duration,
+    ) -> Result<Option<ResultMessage>> {
+        let mut attempts = 0;
+        loop {
+            let outcome = self
+                .send_request(request_id, method, params.clone())
+                .and_then(|()| self.wait_for_result(request_id, timeout));
+
+            let error = match outcome {
+                Ok(result) => return Ok(result),
+                Err(e) if is_connection_lost(&e) => e,
+                Err(e) => return Err(e),
+            };
+            if attempts >= self.restart_policy.max_retries {
+                if attempts == 0 {
+                    return Err(error);
+                }
+                return Err(ClientError::ReconnectExhausted {
+                    method: method.to_string(),
+                    attempts,
+                    reason: error.to_string(),
+                }
+                .into());
+            }
+
+            attempts += 1;
+            std::thread::sleep(self.restart_policy.delay(attempts));
+            tracing::warn!("MCP server connection lost ({}), restarting (attempt {})", error, attempts);
+            if let Err(e) = self.reconnect() {
+                tracing::warn!("Failed to restart MCP server: {}", e);
+            }
+        }
+    }
+
     fn send_request(
         &mut self,
         request_id: &str,
@@ -287,7 +422,12 @@ impl McpServerClient {
         let request = RequestMessage::new(request_id, method, params);
         self.transport
             .send(&JsonRpcMessage::Request(request))
-            .map_err(|e| anyhow::anyhow!("Failed to send request: {}", e))
+            .map_err(|e| match e {
+                StdioClientTransportError::Io(_) | StdioClientTransportError::NotConnected => {
+                    ClientError::ConnectionLost(format!("failed to send request: {}", e)).into()
+                }
+                e => anyhow::anyhow!("Failed to send request: {}", e),
+            })
     }
 
     fn wait_for_result(
@@ -303,18 +443,24 @@ impl McpServerClient {
                 .unwrap_or_else(|| Duration::from_secs(0));
 
             match self.receiver.recv_timeout(remaining.min(Duration::from_secs(1))) {
-                Ok(JsonRpcMessage::Result(message)) if message_id_matches(&message.id, request_id) => {
+                Ok(TransportEvent::Message(JsonRpcMessage::Result(message)))
+                    if message_id_matches(&message.id, request_id) =>
+                {
                     return Ok(Some(message));
                 }
-                Ok(JsonRpcMessage::Request(request)) if request.method == "roots/list" => {
+                Ok(TransportEvent::Message(JsonRpcMessage::Request(request))) if request.method == "roots/list" => {
                     // Respond with empty roots for now
                     let result = ResultMessage::success(request.id.clone(), json!({ "roots": [] }));
                     let _ = self.transport.send(&JsonRpcMessage::Result(result));
                 }
-                Ok(JsonRpcMessage::Notification(_)) => {}
-                Ok(_) => {}
+                Ok(TransportEvent::Message(_)) => {}
+                Ok(TransportEvent::Closed(reason)) => {
+                    return Err(ClientError::ConnectionLost(format!("server closed the connection ({})", reason)).into());
+                }
                 Err(RecvTimeoutError::Timeout) => continue,
-                Err(RecvTimeoutError::Disconnected) => break,
+                Err(RecvTimeoutError::Disconnected) => {
+                    return Err(ClientError::ConnectionLost("transport stopped".to_string()).into());
+                }
             }
         }
 
@@ -322,6 +468,10 @@ impl McpServerClient {
     }
 }
 
+fn is_connection_lost(error: &anyhow::Error) -> bool {
+    matches!(error.downcast_ref::<ClientError>(), Some(ClientError::ConnectionLost(_)))
+}
+
 fn message_id_matches(message_id: &mcp_core::MessageId, expected: &str) -> bool {
     message_id.as_str() == Some(expected)
 }
@@ -341,7 +491,7 @@ pub struct Tool {
 }
 
 /// GitLab Project
-#[derive(Debug, Clone, serde::Deserialize)]
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize)]
 pub struct Project {
     pub id: u64,
     pub iid: Option<u64>,
@@ -405,3 +555,78 @@ pub struct User {
     pub name: String,
     pub web_url: Option<String>,
 }
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    /// A minimal MCP server. On `tools/call` it closes its stdin and exits,
+    /// as if it crashed mid-request, unless the marker file `$1` exists;
+    /// the first crash creates the marker when `$2` is `once`.
+    const FAKE_SERVER: &str = r#"
+while IFS= read -r line; do
+  case "$line" in
+    *'"method":"initialize"'*)
+      printf '%s\n' '{"jsonrpc":"2.0","id":"client-initialize","result":{"protocolVersion":"2025-11-25","capabilities":{},"serverInfo":{"name":"fake","version":"0"}}}' ;;
+    *'"method":"tools/call"'*)
+      if [ ! -e "$1" ]; then
+        [ "$2" = once ] && : > "$1"
+        exec 0<&-
+        exit 1
+      fi
+      printf '%s\n' '{"jsonrpc":"2.0","id":"call-tool-echo","result":{"content":[{"type":"text","text":"ok"}]}}' ;;
+  esac
+done
+"#;
+
+    fn fake_server(name: &str, mode: &str) -> (McpServerClient, std::path::PathBuf) {
+        let marker = std::env::temp_dir().join(format!("gitlab-mcp-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_file(&marker);
+        let args = vec![
+            "-c".to_string(),
+            FAKE_SERVER.to_string(),
+            "fake-server".to_string(),
+            marker.display().to_string(),
+            mode.to_string(),
+        ];
+        let client = McpServerClient::start("sh", &args)
+            .unwrap()
+            .with_restart_policy(RestartPolicy::new(2).with_backoff(Duration::from_millis(10)));
+        (client, marker)
+    }
+
+    #[test]
+    fn test_replays_request_after_crash() {
+        let (mut client, marker) = fake_server("replay", "once");
+
+        let response = client.call_tool("echo", json!({})).unwrap();
+        assert_eq!(response.result["content"][0]["text"], "ok");
+        // The first server crashed before answering
+        assert!(marker.exists());
+
+        let _ = std::fs::remove_file(&marker);
+        client.close().unwrap();
+    }
+
+    #[test]
+    fn test_reconnect_exhausted() {
+        let (mut client, _) = fake_server("exhausted", "always");
+
+        let error = client.call_tool("echo", json!({})).unwrap_err();
+        match error.downcast_ref::<ClientError>() {
+            Some(ClientError::ReconnectExhausted { method, attempts, .. }) => {
+                assert_eq!(method, "tools/call");
+                assert_eq!(*attempts, 2);
+            }
+            other => panic!("unexpected error: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_restart_backoff_doubles() {
+        let policy = RestartPolicy::new(3).with_backoff(Duration::from_millis(100));
+        assert_eq!(policy.delay(1), Duration::from_millis(100));
+        assert_eq!(policy.delay(3), Duration::from_millis(400));
+        assert_eq!(RestartPolicy::never().max_retries, 0);
+    }
+}
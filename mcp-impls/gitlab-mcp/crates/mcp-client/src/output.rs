@@ -1,24 +1,37 @@
+use clap::ValueEnum;
 use console::{style, Color};
+use serde::Serialize;
 use serde_json::Value;
+use tabled::builder::Builder;
+use tabled::settings::Style;
 use tabled::Tabled;
 
+/// Output mode selected with `--output`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum OutputMode {
+    /// Pretty-printed JSON
+    Json,
+    /// YAML
+    Yaml,
+    /// Aligned columns, headed by JSON field names
+    #[default]
+    Table,
+}
+
 /// Output formatter
 pub struct OutputFormatter {
-    format: String,
+    mode: OutputMode,
     color: bool,
 }
 
 impl OutputFormatter {
-    pub fn new(format: &str, color: bool) -> Self {
-        Self {
-            format: format.to_string(),
-            color,
-        }
+    pub fn new(mode: &OutputMode, color: bool) -> Self {
+        Self { mode: *mode, color }
     }
 
     /// Print output
     pub fn print(&self, output: String) {
-        if self.format == "json" {
+        if self.mode == OutputMode::Json {
             // Try to parse as JSON for pretty printing
             if let Ok(value) = serde_json::from_str::<Value>(&output) {
                 println!("{}", serde_json::to_string_pretty(&value).unwrap_or(output));
@@ -30,6 +43,66 @@ impl OutputFormatter {
         }
     }
 
+    /// Print a list of records.
+    ///
+    /// In table mode `columns` selects and orders the fields shown, one row
+    /// per item; an empty slice shows every field. JSON and YAML always
+    /// contain every field.
+    pub fn print_list<T: Serialize>(&self, items: &[T], columns: &[&str]) -> anyhow::Result<()> {
+        println!("{}", self.render_list(items, columns)?);
+        Ok(())
+    }
+
+    /// Print a single record.
+    ///
+    /// In table mode the record is shown as one `field | value` row per
+    /// field, ordered by `fields` (an empty slice shows every field).
+    pub fn print_record<T: Serialize>(&self, record: &T, fields: &[&str]) -> anyhow::Result<()> {
+        println!("{}", self.render_record(record, fields)?);
+        Ok(())
+    }
+
+    /// Render a list of records in the current mode
+    pub fn render_list<T: Serialize>(&self, items: &[T], columns: &[&str]) -> anyhow::Result<String> {
+        let value = serde_json::to_value(items)?;
+        if self.mode != OutputMode::Table {
+            return self.render_value(&value);
+        }
+
+        let rows: Vec<&Value> = value.as_array().map(|items| items.iter().collect()).unwrap_or_default();
+        let headers = select_fields(rows.iter().copied(), columns);
+        let mut builder = Builder::default();
+        builder.set_header(headers.iter().cloned());
+        for row in rows {
+            builder.push_record(headers.iter().map(|field| cell(row.get(field))));
+        }
+        Ok(builder.build().with(Style::psql()).to_string())
+    }
+
+    /// Render a single record in the current mode
+    pub fn render_record<T: Serialize>(&self, record: &T, fields: &[&str]) -> anyhow::Result<String> {
+        let value = serde_json::to_value(record)?;
+        if self.mode != OutputMode::Table {
+            return self.render_value(&value);
+        }
+
+        let mut builder = Builder::default();
+        builder.set_header(["field", "value"]);
+        for field in select_fields(std::iter::once(&value), fields) {
+            let text = cell(value.get(&field));
+            builder.push_record([field, text]);
+        }
+        Ok(builder.build().with(Style::psql()).to_string())
+    }
+
+    fn render_value(&self, value: &Value) -> anyhow::Result<String> {
+        Ok(match self.mode {
+            OutputMode::Json => serde_json::to_string_pretty(value)?,
+            OutputMode::Yaml => serde_yaml::to_string(value)?.trim_end().to_string(),
+            OutputMode::Table => unreachable!("tables are rendered by the caller"),
+        })
+    }
+
     /// Print success message
     pub fn success(&self, message: &str) {
         if self.color {
@@ -59,12 +132,43 @@ impl OutputFormatter {
 
     /// Check if output format is table
     pub fn is_table(&self) -> bool {
-        self.format == "table"
+        self.mode == OutputMode::Table
     }
 
-    /// Get the output format
-    pub fn format(&self) -> &str {
-        &self.format
+    /// Get the output mode
+    pub fn mode(&self) -> OutputMode {
+        self.mode
+    }
+}
+
+/// `columns` if given, otherwise every field of the objects in order of
+/// first appearance
+fn select_fields<'a>(objects: impl Iterator<Item = &'a Value>, columns: &[&str]) -> Vec<String> {
+    if !columns.is_empty() {
+        return columns.iter().map(|column| column.to_string()).collect();
+    }
+
+    let mut fields: Vec<String> = Vec::new();
+    for key in objects.filter_map(Value::as_object).flat_map(|object| object.keys()) {
+        if !fields.contains(key) {
+            fields.push(key.clone());
+        }
+    }
+    fields
+}
+
+/// Text of a table cell: strings unquoted, string lists comma-separated,
+/// missing and null values empty
+fn cell(value: Option<&Value>) -> String {
+    match value {
+        None | Some(Value::Null) => String::new(),
+        Some(Value::String(text)) => text.clone(),
+        Some(Value::Array(items)) if items.iter().all(Value::is_string) => items
+            .iter()
+            .filter_map(Value::as_str)
+            .collect::<Vec<_>>()
+            .join(", "),
+        Some(other) => other.to_string(),
     }
 }
 
@@ -72,3 +176,51 @@ impl OutputFormatter {
 pub trait TableRow: Tabled {
     fn headers() -> Vec<&'static str>;
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn projects() -> Vec<Value> {
+        vec![
+            json!({ "id": 1, "name": "alpha", "topics": ["rust", "mcp"], "description": null }),
+            json!({ "id": 22, "name": "beta", "topics": [], "description": "Second" }),
+        ]
+    }
+
+    #[test]
+    fn test_table_uses_field_names_as_headers() {
+        let formatter = OutputFormatter::new(&OutputMode::Table, false);
+        let table = formatter.render_list(&projects(), &["id", "name", "topics"]).unwrap();
+        let lines: Vec<&str> = table.lines().collect();
+
+        assert_eq!(lines[0].split('|').map(str::trim).collect::<Vec<_>>(), ["id", "name", "topics"]);
+        assert!(lines[2].contains("alpha") && lines[2].contains("rust, mcp"));
+        // Columns are aligned
+        assert_eq!(lines[2].find('|'), lines[3].find('|'));
+    }
+
+    #[test]
+    fn test_table_record_lists_fields() {
+        let formatter = OutputFormatter::new(&OutputMode::Table, false);
+        let table = formatter.render_record(&projects()[1], &[]).unwrap();
+
+        assert!(table.lines().next().unwrap().contains("field"));
+        assert!(table.contains("description") && table.contains("Second"));
+    }
+
+    #[test]
+    fn test_json_and_yaml_keep_every_field() {
+        let json = OutputFormatter::new(&OutputMode::Json, false)
+            .render_list(&projects(), &["id"])
+            .unwrap();
+        assert_eq!(serde_json::from_str::<Value>(&json).unwrap(), json!(projects()));
+
+        let yaml = OutputFormatter::new(&OutputMode::Yaml, false)
+            .render_record(&projects()[0], &["id"])
+            .unwrap();
+        assert!(yaml.contains("name: alpha"));
+        assert!(yaml.contains("- rust\n"));
+    }
+}
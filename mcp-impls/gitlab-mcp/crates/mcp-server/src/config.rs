@@ -1,15 +1,53 @@
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
+use std::time::Duration;
+
+use crate::gitlab::{parse_base_url, PoolOptions, TlsOptions};
+
+/// Defaults of the connection pool settings
+const DEFAULT_POOL_MAX_IDLE_PER_HOST: usize = 8;
+const DEFAULT_POOL_IDLE_TIMEOUT_SECS: u64 = 90;
+const DEFAULT_TCP_KEEPALIVE_SECS: u64 = 60;
 
 /// GitLab MCP server configuration
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Config {
-    /// GitLab instance URL
+    /// GitLab instance URL, e.g. `https://gitlab.example.com` for a self-managed instance
     pub gitlab_url: String,
     /// GitLab personal access token
     pub gitlab_token: String,
     /// Log level
     pub log_level: String,
+    /// Simulate write tools instead of calling the GitLab API
+    #[serde(default)]
+    pub dry_run: bool,
+    /// PEM bundle of CA certificates to trust in addition to the system roots
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ca_cert_path: Option<PathBuf>,
+    /// Skip TLS certificate verification (development only)
+    #[serde(default)]
+    pub insecure_skip_tls_verify: bool,
+    /// Idle keep-alive connections kept per host
+    #[serde(default = "default_pool_max_idle_per_host")]
+    pub pool_max_idle_per_host: usize,
+    /// Seconds an idle connection is kept open; 0 keeps it until the server closes it
+    #[serde(default = "default_pool_idle_timeout")]
+    pub pool_idle_timeout: u64,
+    /// Seconds between TCP keepalive probes; 0 disables them
+    #[serde(default = "default_tcp_keepalive")]
+    pub tcp_keepalive: u64,
+}
+
+fn default_pool_max_idle_per_host() -> usize {
+    DEFAULT_POOL_MAX_IDLE_PER_HOST
+}
+
+fn default_pool_idle_timeout() -> u64 {
+    DEFAULT_POOL_IDLE_TIMEOUT_SECS
+}
+
+fn default_tcp_keepalive() -> u64 {
+    DEFAULT_TCP_KEEPALIVE_SECS
 }
 
 impl Default for Config {
@@ -18,6 +56,12 @@ impl Default for Config {
             gitlab_url: "https://gitlab.com".to_string(),
             gitlab_token: String::new(),
             log_level: "info".to_string(),
+            dry_run: false,
+            ca_cert_path: None,
+            insecure_skip_tls_verify: false,
+            pool_max_idle_per_host: DEFAULT_POOL_MAX_IDLE_PER_HOST,
+            pool_idle_timeout: DEFAULT_POOL_IDLE_TIMEOUT_SECS,
+            tcp_keepalive: DEFAULT_TCP_KEEPALIVE_SECS,
         }
     }
 }
@@ -64,6 +108,30 @@ impl Config {
             config.log_level = level;
         }
 
+        if let Ok(dry_run) = std::env::var("GITLAB_MCP_DRY_RUN") {
+            config.dry_run = parse_flag(&dry_run);
+        }
+
+        if let Ok(path) = std::env::var("GITLAB_CA_CERT") {
+            config.ca_cert_path = (!path.is_empty()).then(|| PathBuf::from(path));
+        }
+
+        if let Ok(insecure) = std::env::var("GITLAB_INSECURE_SKIP_TLS_VERIFY") {
+            config.insecure_skip_tls_verify = parse_flag(&insecure);
+        }
+
+        if let Some(max_idle) = env_number("GITLAB_POOL_MAX_IDLE_PER_HOST") {
+            config.pool_max_idle_per_host = max_idle as usize;
+        }
+
+        if let Some(timeout) = env_number("GITLAB_POOL_IDLE_TIMEOUT") {
+            config.pool_idle_timeout = timeout;
+        }
+
+        if let Some(keepalive) = env_number("GITLAB_TCP_KEEPALIVE") {
+            config.tcp_keepalive = keepalive;
+        }
+
         config
     }
 
@@ -73,14 +141,36 @@ impl Config {
             return Err("GITLAB_TOKEN is required. Set it via environment variable or config file.".to_string());
         }
 
-        // Validate URL format
-        if let Err(e) = url::Url::parse(&self.gitlab_url) {
+        // Validate URL format; bare hostnames are rejected
+        if let Err(e) = parse_base_url(&self.gitlab_url) {
             return Err(format!("Invalid GITLAB_URL: {}", e));
         }
 
+        if let Some(path) = self.ca_cert_path.as_ref().filter(|path| !path.is_file()) {
+            return Err(format!("GITLAB_CA_CERT does not point to a file: {}", path.display()));
+        }
+
         Ok(())
     }
 
+    /// TLS settings for the GitLab HTTP client
+    pub fn tls_options(&self) -> TlsOptions {
+        TlsOptions {
+            ca_cert_path: self.ca_cert_path.clone(),
+            insecure_skip_verify: self.insecure_skip_tls_verify,
+        }
+    }
+
+    /// Connection pool settings for the GitLab HTTP client
+    pub fn pool_options(&self) -> PoolOptions {
+        let seconds = |secs: u64| (secs > 0).then(|| Duration::from_secs(secs));
+        PoolOptions {
+            max_idle_per_host: self.pool_max_idle_per_host,
+            idle_timeout: seconds(self.pool_idle_timeout),
+            tcp_keepalive: seconds(self.tcp_keepalive),
+        }
+    }
+
     /// Save configuration to file
     pub fn save(&self) -> Result<(), anyhow::Error> {
         let config_dir = Self::config_dir()?;
@@ -124,6 +214,19 @@ impl Config {
     }
 }
 
+/// Read a non-negative integer environment variable, ignoring invalid values
+fn env_number(name: &str) -> Option<u64> {
+    std::env::var(name).ok()?.trim().parse().ok()
+}
+
+/// Interpret an environment flag such as `1`, `true`, `yes` or `on`
+fn parse_flag(value: &str) -> bool {
+    matches!(
+        value.trim().to_ascii_lowercase().as_str(),
+        "1" | "true" | "yes" | "on"
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -133,6 +236,7 @@ mod tests {
         let config = Config::default();
         assert_eq!(config.gitlab_url, "https://gitlab.com");
         assert_eq!(config.log_level, "info");
+        assert!(!config.dry_run);
     }
 
     #[test]
@@ -156,12 +260,41 @@ mod tests {
         assert!(config.validate().is_err());
     }
 
+    #[test]
+    fn test_config_validate_bare_hostname() {
+        let mut config = Config {
+            gitlab_token: "test_token".to_string(),
+            gitlab_url: "gitlab.example.com:8443".to_string(),
+            ..Default::default()
+        };
+        assert!(config.validate().is_err());
+
+        config.gitlab_url = "https://gitlab.example.com:8443".to_string();
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_config_validate_missing_ca_cert() {
+        let config = Config {
+            gitlab_token: "test_token".to_string(),
+            ca_cert_path: Some(PathBuf::from("/nonexistent/ca.pem")),
+            ..Default::default()
+        };
+        assert!(config.validate().unwrap_err().contains("GITLAB_CA_CERT"));
+    }
+
     #[test]
     fn test_config_serialize() {
         let config = Config {
             gitlab_url: "https://gitlab.example.com".to_string(),
             gitlab_token: "glpat_123456".to_string(),
             log_level: "debug".to_string(),
+            dry_run: false,
+            ca_cert_path: None,
+            insecure_skip_tls_verify: false,
+            pool_max_idle_per_host: 4,
+            pool_idle_timeout: 30,
+            tcp_keepalive: 0,
         };
 
         let toml_str = toml::to_string(&config).unwrap();
@@ -169,4 +302,46 @@ mod tests {
         assert!(toml_str.contains("gitlab_token"));
         assert!(toml_str.contains("log_level"));
     }
+
+    #[test]
+    fn test_config_without_dry_run_deserializes() {
+        let config: Config = toml::from_str(
+            "gitlab_url = \"https://gitlab.com\"\ngitlab_token = \"t\"\nlog_level = \"info\"\n",
+        )
+        .unwrap();
+        assert!(!config.dry_run);
+        assert_eq!(config.pool_max_idle_per_host, DEFAULT_POOL_MAX_IDLE_PER_HOST);
+    }
+
+    #[test]
+    fn test_pool_options() {
+        let defaults = Config::default().pool_options();
+        assert_eq!(defaults, PoolOptions::default());
+        assert!(defaults.tcp_keepalive.is_some());
+
+        let config = Config {
+            pool_max_idle_per_host: 2,
+            pool_idle_timeout: 0,
+            tcp_keepalive: 15,
+            ..Config::default()
+        };
+        assert_eq!(
+            config.pool_options(),
+            PoolOptions {
+                max_idle_per_host: 2,
+                idle_timeout: None,
+                tcp_keepalive: Some(Duration::from_secs(15)),
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_flag() {
+        for value in ["1", "true", "TRUE", " yes ", "on"] {
+            assert!(parse_flag(value), "{value}");
+        }
+        for value in ["", "0", "false", "off", "nope"] {
+            assert!(!parse_flag(value), "{value}");
+        }
+    }
 }
@@ -1,3 +1,7 @@
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use serde_json::Value;
 use thiserror::Error;
 
 /// GitLab MCP Server errors
@@ -12,18 +16,44 @@ pub enum GitLabError {
     #[error("Authentication failed: {0}")]
     AuthError(String),
 
+    /// HTTP 401: the token is missing, invalid or expired
+    #[error("Unauthorized: {0} (check that the GitLab token is valid and not expired)")]
+    Unauthorized(String),
+
+    /// HTTP 403: the token lacks permission for the action
+    #[error("Forbidden: {0} (the GitLab token lacks permission for this action)")]
+    Forbidden(String),
+
     #[error("Not found: {0}")]
     NotFound(String),
 
+    /// HTTP 409: the request conflicts with existing state
+    #[error("Conflict: {0}")]
+    Conflict(String),
+
+    /// HTTP 422, or 400 with field-keyed errors: GitLab rejected the input.
+    /// `fields` maps attribute names to their messages.
+    #[error("Validation failed: {}", describe_validation(message, fields))]
+    Validation {
+        message: String,
+        fields: BTreeMap<String, Vec<String>>,
+    },
+
     #[error("Invalid parameter: {0}")]
     InvalidParameter(String),
 
     #[error("Rate limit exceeded")]
     RateLimitExceeded,
 
+    #[error("Rate limited by GitLab, retry after {}s", retry_after.as_secs_f64())]
+    RateLimited { retry_after: Duration },
+
     #[error("Network error: {0}")]
     Network(String),
 
+    #[error("GitLab GraphQL error: {0}")]
+    GraphQl(String),
+
     #[error("Serialization error: {0}")]
     Serialization(#[from] serde_json::Error),
 
@@ -42,6 +72,32 @@ impl GitLabError {
         }
     }
 
+    /// Map an unsuccessful GitLab response to a semantic variant.
+    ///
+    /// GitLab bodies carry `{"message": ...}` or `{"error": ...}`, where the
+    /// message is a string, a list of strings or an object of per-field
+    /// messages. Other statuses become [`GitLabError::ApiResponse`].
+    pub fn from_response(status: u16, body: &str) -> Self {
+        let (message, fields) = parse_error_body(body);
+        let message_or = |default: &str| message.clone().unwrap_or_else(|| default.to_string());
+
+        match status {
+            401 => Self::Unauthorized(message_or("Invalid or expired token")),
+            403 => Self::Forbidden(message_or("Insufficient permissions")),
+            404 => Self::NotFound(message_or("Resource not found")),
+            409 => Self::Conflict(message.unwrap_or_else(|| describe_fields(&fields))),
+            422 => Self::Validation {
+                message: message.unwrap_or_default(),
+                fields,
+            },
+            400 if !fields.is_empty() => Self::Validation {
+                message: message.unwrap_or_default(),
+                fields,
+            },
+            _ => Self::api_response(status, message.unwrap_or_else(|| body.to_string())),
+        }
+    }
+
     pub fn not_found(resource: impl Into<String>) -> Self {
         Self::NotFound(resource.into())
     }
@@ -60,3 +116,103 @@ impl GitLabError {
 }
 
 pub type Result<T> = std::result::Result<T, GitLabError>;
+
+/// Split a GitLab error body into its message and per-field messages
+fn parse_error_body(body: &str) -> (Option<String>, BTreeMap<String, Vec<String>>) {
+    let mut fields = BTreeMap::new();
+    let Ok(value) = serde_json::from_str::<Value>(body) else {
+        let body = body.trim();
+        return ((!body.is_empty()).then(|| body.to_string()), fields);
+    };
+
+    let message = match value.get("message").or_else(|| value.get("error")) {
+        Some(Value::String(message)) => Some(message.clone()),
+        Some(Value::Array(messages)) => Some(join_messages(messages)),
+        Some(Value::Object(per_field)) => {
+            for (field, messages) in per_field {
+                let messages = match messages {
+                    Value::Array(messages) => messages.iter().map(message_text).collect(),
+                    other => vec![message_text(other)],
+                };
+                fields.insert(field.clone(), messages);
+            }
+            None
+        }
+        _ => None,
+    };
+    (message, fields)
+}
+
+fn join_messages(messages: &[Value]) -> String {
+    messages.iter().map(message_text).collect::<Vec<_>>().join("; ")
+}
+
+fn message_text(value: &Value) -> String {
+    match value {
+        Value::String(text) => text.clone(),
+        other => other.to_string(),
+    }
+}
+
+/// `title can't be blank; name has already been taken`
+fn describe_fields(fields: &BTreeMap<String, Vec<String>>) -> String {
+    fields
+        .iter()
+        .flat_map(|(field, messages)| messages.iter().map(move |message| format!("{} {}", field, message)))
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+fn describe_validation(message: &str, fields: &BTreeMap<String, Vec<String>>) -> String {
+    match (message.is_empty(), fields.is_empty()) {
+        (_, true) => message.to_string(),
+        (true, false) => describe_fields(fields),
+        (false, false) => format!("{}: {}", message, describe_fields(fields)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validation_preserves_field_errors() {
+        let body = r#"{"message":{"name":["has already been taken","is too long"],"path":["can't be blank"]}}"#;
+
+        let error = GitLabError::from_response(422, body);
+        let GitLabError::Validation { ref message, ref fields } = error else {
+            panic!("expected Validation, got {:?}", error);
+        };
+        assert!(message.is_empty());
+        assert_eq!(fields["name"], ["has already been taken", "is too long"]);
+        assert_eq!(fields["path"], ["can't be blank"]);
+        assert_eq!(
+            error.to_string(),
+            "Validation failed: name has already been taken; name is too long; path can't be blank"
+        );
+    }
+
+    #[test]
+    fn test_maps_statuses_to_variants() {
+        let error = GitLabError::from_response(401, r#"{"message":"401 Unauthorized"}"#);
+        assert!(matches!(error, GitLabError::Unauthorized(ref m) if m == "401 Unauthorized"));
+
+        let error = GitLabError::from_response(403, r#"{"message":"403 Forbidden"}"#);
+        assert!(matches!(error, GitLabError::Forbidden(_)));
+
+        let error = GitLabError::from_response(404, r#"{"message":"404 Project Not Found"}"#);
+        assert!(matches!(error, GitLabError::NotFound(ref m) if m == "404 Project Not Found"));
+
+        let error = GitLabError::from_response(409, r#"{"message":["Branch already exists"]}"#);
+        assert!(matches!(error, GitLabError::Conflict(ref m) if m == "Branch already exists"));
+
+        let error = GitLabError::from_response(400, r#"{"error":"title is missing"}"#);
+        assert!(matches!(error, GitLabError::ApiResponse { status: 400, ref message } if message == "title is missing"));
+
+        let error = GitLabError::from_response(400, r#"{"message":{"title":["can't be blank"]}}"#);
+        assert!(matches!(error, GitLabError::Validation { ref fields, .. } if fields.contains_key("title")));
+
+        let error = GitLabError::from_response(500, "<html>oops</html>");
+        assert!(matches!(error, GitLabError::ApiResponse { status: 500, ref message } if message == "<html>oops</html>"));
+    }
+}
@@ -1,22 +1,131 @@
-use reqwest::{header, Client as HttpClient};
+use futures::stream::{self, Stream, TryStreamExt};
+use reqwest::{header, Client as HttpClient, RequestBuilder, Response, StatusCode};
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex, Once};
 use std::time::Duration;
+use mcp_core::schema::{CustomKeywordValidator, SchemaValidator, ValidationError};
 use url::Url;
 
+use crate::config::Config;
 use crate::error::{GitLabError, Result};
 
+/// Default number of retries after HTTP 429 before giving up
+pub const DEFAULT_MAX_RETRIES: u32 = 3;
+
+/// First backoff delay when a 429 response carries no `Retry-After`
+const DEFAULT_RETRY_BACKOFF: Duration = Duration::from_secs(1);
+
+/// Longest `Retry-After` worth waiting for inside a single tool call
+const MAX_RETRY_DELAY: Duration = Duration::from_secs(60);
+
+/// TLS settings for self-managed instances
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TlsOptions {
+    /// PEM bundle of extra CA certificates to trust, e.g. a private CA
+    pub ca_cert_path: Option<PathBuf>,
+    /// Accept any server certificate. Only for development.
+    pub insecure_skip_verify: bool,
+}
+
+/// Connection pool settings of the underlying HTTP client
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PoolOptions {
+    /// Idle keep-alive connections kept per host
+    pub max_idle_per_host: usize,
+    /// How long an idle connection is kept; `None` keeps it until the server closes it
+    pub idle_timeout: Option<Duration>,
+    /// TCP keepalive probe interval; `None` disables keepalive probes
+    pub tcp_keepalive: Option<Duration>,
+}
+
+impl Default for PoolOptions {
+    fn default() -> Self {
+        Self {
+            max_idle_per_host: 8,
+            idle_timeout: Some(Duration::from_secs(90)),
+            tcp_keepalive: Some(Duration::from_secs(60)),
+        }
+    }
+}
+
+/// Client shared by tool handlers, with the config it was built from
+static SHARED_CLIENT: Mutex<Option<(Config, Arc<GitLabClient>)>> = Mutex::new(None);
+
+/// Parse a GitLab base URL, which must be absolute `http` or `https`.
+///
+/// Bare hostnames are rejected: `gitlab.example.com` has no scheme and
+/// `gitlab.example.com:8443` would parse with `gitlab.example.com` as its
+/// scheme.
+pub fn parse_base_url(base_url: &str) -> Result<Url> {
+    let url = Url::parse(base_url)
+        .map_err(|e| GitLabError::invalid_parameter(format!("Invalid GitLab URL `{}`: {}", base_url, e)))?;
+    if !matches!(url.scheme(), "http" | "https") || !url.has_host() {
+        return Err(GitLabError::invalid_parameter(format!(
+            "Invalid GitLab URL `{}`: expected an http:// or https:// URL such as https://gitlab.example.com",
+            base_url
+        )));
+    }
+    Ok(url)
+}
+
 /// GitLab API client
 pub struct GitLabClient {
     http_client: HttpClient,
     base_url: Url,
     token: String,
+    max_retries: u32,
+    retry_backoff: Duration,
+    dry_run: bool,
 }
 
 impl GitLabClient {
     /// Create a new GitLab client
     pub fn new(base_url: impl AsRef<str>, token: impl AsRef<str>) -> Result<Self> {
-        let base_url = Url::parse(base_url.as_ref())
-            .map_err(|e| GitLabError::invalid_parameter(format!("Invalid GitLab URL: {}", e)))?;
+        Self::with_tls(base_url, token, &TlsOptions::default())
+    }
+
+    /// Create a client for the instance, TLS, pool and dry-run settings of `config`
+    pub fn from_config(config: &Config) -> Result<Self> {
+        let client = Self::with_http_options(
+            &config.gitlab_url,
+            &config.gitlab_token,
+            &config.tls_options(),
+            &config.pool_options(),
+        )?;
+        Ok(client.with_dry_run(config.dry_run))
+    }
+
+    /// Client for `config` shared across calls, so tool handlers reuse its
+    /// pooled connections instead of opening new ones.
+    ///
+    /// The client is rebuilt when the configuration changes, e.g. after a
+    /// new token is saved.
+    pub fn shared(config: &Config) -> Result<Arc<Self>> {
+        let mut shared = SHARED_CLIENT.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some((_, client)) = shared.as_ref().filter(|(cached, _)| cached == config) {
+            return Ok(Arc::clone(client));
+        }
+        let client = Arc::new(Self::from_config(config)?);
+        *shared = Some((config.clone(), Arc::clone(&client)));
+        Ok(client)
+    }
+
+    /// Create a new GitLab client with custom TLS settings
+    pub fn with_tls(base_url: impl AsRef<str>, token: impl AsRef<str>, tls: &TlsOptions) -> Result<Self> {
+        Self::with_http_options(base_url, token, tls, &PoolOptions::default())
+    }
+
+    /// Create a new GitLab client with custom TLS and connection pool settings
+    pub fn with_http_options(
+        base_url: impl AsRef<str>,
+        token: impl AsRef<str>,
+        tls: &TlsOptions,
+        pool: &PoolOptions,
+    ) -> Result<Self> {
+        let base_url = parse_base_url(base_url.as_ref())?;
 
         let token = token.as_ref().to_string();
 
@@ -24,8 +133,41 @@ impl GitLabClient {
             return Err(GitLabError::auth_error("GITLAB_TOKEN is required"));
         }
 
-        let http_client = HttpClient::builder()
+        let mut builder = HttpClient::builder()
             .timeout(Duration::from_secs(30))
+            .pool_max_idle_per_host(pool.max_idle_per_host)
+            .pool_idle_timeout(pool.idle_timeout)
+            .tcp_keepalive(pool.tcp_keepalive);
+        if let Some(path) = &tls.ca_cert_path {
+            let pem = std::fs::read(path).map_err(|e| {
+                GitLabError::invalid_parameter(format!("Cannot read CA bundle {}: {}", path.display(), e))
+            })?;
+            let certificates = reqwest::Certificate::from_pem_bundle(&pem).map_err(|e| {
+                GitLabError::invalid_parameter(format!("Invalid CA bundle {}: {}", path.display(), e))
+            })?;
+            if certificates.is_empty() {
+                return Err(GitLabError::invalid_parameter(format!(
+                    "CA bundle {} contains no certificates",
+                    path.display()
+                )));
+            }
+            for certificate in certificates {
+                builder = builder.add_root_certificate(certificate);
+            }
+        }
+        if tls.insecure_skip_verify {
+            static WARN_INSECURE: Once = Once::new();
+            WARN_INSECURE.call_once(|| {
+                tracing::warn!(
+                    "TLS certificate verification is DISABLED for {} (insecure_skip_tls_verify); \
+                     never use this outside development",
+                    base_url
+                );
+            });
+            builder = builder.danger_accept_invalid_certs(true);
+        }
+
+        let http_client = builder
             .build()
             .map_err(|e| GitLabError::network(format!("Failed to create HTTP client: {}", e)))?;
 
@@ -33,9 +175,37 @@ impl GitLabClient {
             http_client,
             base_url,
             token,
+            max_retries: DEFAULT_MAX_RETRIES,
+            retry_backoff: DEFAULT_RETRY_BACKOFF,
+            dry_run: false,
         })
     }
 
+    /// Retry rate-limited (HTTP 429) requests at most `max_retries` times
+    pub fn with_max_retries(mut self, max_retries: u32) -> Self {
+        self.max_retries = max_retries;
+        self
+    }
+
+    /// Set the first backoff delay used when GitLab sends no `Retry-After`;
+    /// it doubles on every further retry
+    pub fn with_retry_backoff(mut self, backoff: Duration) -> Self {
+        self.retry_backoff = backoff;
+        self
+    }
+
+    /// Mark the client as dry-run; write tools then describe their request
+    /// instead of sending it
+    pub fn with_dry_run(mut self, dry_run: bool) -> Self {
+        self.dry_run = dry_run;
+        self
+    }
+
+    /// Whether write tools should be simulated
+    pub fn is_dry_run(&self) -> bool {
+        self.dry_run
+    }
+
     /// Create client from environment variables
     pub fn from_env() -> Result<Self> {
         let base_url = std::env::var("GITLAB_URL").unwrap_or_else(|_| "https://gitlab.com".to_string());
@@ -53,13 +223,12 @@ impl GitLabClient {
     /// Make a GET request to the GitLab API
     pub async fn get<T: for<'de> Deserialize<'de>>(&self, path: &str) -> Result<T> {
         let url = self.api_url(path);
-        let response = self
+        let request = self
             .http_client
             .get(url)
             .header(header::AUTHORIZATION, format!("Bearer {}", self.token))
-            .header(header::USER_AGENT, "gitlab-mcp-server/0.1.0")
-            .send()
-            .await?;
+            .header(header::USER_AGENT, "gitlab-mcp-server/0.1.0");
+        let response = self.send(request).await?;
 
         self.handle_response(response).await
     }
@@ -77,17 +246,71 @@ impl GitLabClient {
                 query_pairs.append_pair(key, value);
             }
         }
-        let response = self
+        let request = self
             .http_client
             .get(url)
             .header(header::AUTHORIZATION, format!("Bearer {}", self.token))
-            .header(header::USER_AGENT, "gitlab-mcp-server/0.1.0")
-            .send()
-            .await?;
+            .header(header::USER_AGENT, "gitlab-mcp-server/0.1.0");
+        let response = self.send(request).await?;
 
         self.handle_response(response).await
     }
 
+    /// Iterate every item of a paginated list endpoint.
+    ///
+    /// Pages of `per_page` items (at most 100) are requested lazily while
+    /// the stream is polled, following GitLab's `X-Next-Page` header until it
+    /// comes back empty. `query` must not contain `page` or `per_page`.
+    pub fn paginate<'a, T: DeserializeOwned + 'a>(
+        &'a self,
+        path: &str,
+        query: &[(String, String)],
+        per_page: u8,
+    ) -> impl Stream<Item = Result<T>> + 'a {
+        let path = path.to_string();
+        let mut query = query.to_vec();
+        query.push(("per_page".to_string(), per_page.clamp(1, 100).to_string()));
+
+        stream::try_unfold(Some(1u64), move |page| {
+            let path = path.clone();
+            let mut query = query.clone();
+            async move {
+                let Some(page) = page else {
+                    return Result::Ok(None);
+                };
+                query.push(("page".to_string(), page.to_string()));
+                let (items, next_page) = self.get_page::<T>(&path, &query).await?;
+                Ok(Some((stream::iter(items.into_iter().map(Ok)), next_page)))
+            }
+        })
+        .try_flatten()
+    }
+
+    /// Fetch one page of a list endpoint along with the `X-Next-Page` number
+    async fn get_page<T: DeserializeOwned>(
+        &self,
+        path: &str,
+        query: &[(String, String)],
+    ) -> Result<(Vec<T>, Option<u64>)> {
+        let mut url = self.api_url(path);
+        url.query_pairs_mut().extend_pairs(query);
+        let request = self
+            .http_client
+            .get(url)
+            .header(header::AUTHORIZATION, format!("Bearer {}", self.token))
+            .header(header::USER_AGENT, "gitlab-mcp-server/0.1.0");
+        let response = self.send(request).await?;
+
+        // The header is present but empty on the last page
+        let next_page = response
+            .headers()
+            .get("x-next-page")
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.trim().parse().ok());
+        let items = self.handle_response(response).await?;
+        Ok((items, next_page))
+    }
+
     /// Make a POST request to the GitLab API
     pub async fn post<T: for<'de> Deserialize<'de>, B: Serialize>(
         &self,
@@ -95,15 +318,14 @@ impl GitLabClient {
         body: &B,
     ) -> Result<T> {
         let url = self.api_url(path);
-        let response = self
+        let request = self
             .http_client
             .post(url)
             .header(header::AUTHORIZATION, format!("Bearer {}", self.token))
             .header(header::USER_AGENT, "gitlab-mcp-server/0.1.0")
             .header(header::CONTENT_TYPE, "application/json")
-            .json(body)
-            .send()
-            .await?;
+            .json(body);
+        let response = self.send(request).await?;
 
         self.handle_response(response).await
     }
@@ -115,15 +337,14 @@ impl GitLabClient {
         body: &B,
     ) -> Result<T> {
         let url = self.api_url(path);
-        let response = self
+        let request = self
             .http_client
             .put(url)
             .header(header::AUTHORIZATION, format!("Bearer {}", self.token))
             .header(header::USER_AGENT, "gitlab-mcp-server/0.1.0")
             .header(header::CONTENT_TYPE, "application/json")
-            .json(body)
-            .send()
-            .await?;
+            .json(body);
+        let response = self.send(request).await?;
 
         self.handle_response(response).await
     }
@@ -131,27 +352,90 @@ impl GitLabClient {
     /// Make a DELETE request to the GitLab API
     pub async fn delete(&self, path: &str) -> Result<()> {
         let url = self.api_url(path);
-        let response = self
+        let request = self
+            .http_client
+            .delete(url)
+            .header(header::AUTHORIZATION, format!("Bearer {}", self.token))
+            .header(header::USER_AGENT, "gitlab-mcp-server/0.1.0");
+        let response = self.send(request).await?;
+
+        // DELETE endpoints usually answer 204 No Content, so there is no body to parse
+        if response.status().is_success() {
+            return Ok(());
+        }
+        self.handle_response(response).await
+    }
+
+    /// Make a DELETE request with query parameters to the GitLab API
+    pub async fn delete_with_query(&self, path: &str, query: &[(String, String)]) -> Result<()> {
+        let mut url = self.api_url(path);
+        {
+            let mut query_pairs = url.query_pairs_mut();
+            for (key, value) in query {
+                query_pairs.append_pair(key, value);
+            }
+        }
+        let request = self
             .http_client
             .delete(url)
             .header(header::AUTHORIZATION, format!("Bearer {}", self.token))
+            .header(header::USER_AGENT, "gitlab-mcp-server/0.1.0");
+        let response = self.send(request).await?;
+
+        if response.status().is_success() {
+            return Ok(());
+        }
+        self.handle_response(response).await
+    }
+
+    /// Run a GraphQL query against `/api/graphql` and return the raw response
+    /// body, including any `errors` GitLab reports next to `data`
+    pub async fn graphql(&self, query: &str, variables: Value) -> Result<Value> {
+        let request = self
+            .http_client
+            .post(self.graphql_url())
+            .header(header::AUTHORIZATION, format!("Bearer {}", self.token))
             .header(header::USER_AGENT, "gitlab-mcp-server/0.1.0")
-            .send()
-            .await?;
+            .header(header::CONTENT_TYPE, "application/json")
+            .json(&json!({ "query": query, "variables": variables }));
+        let response = self.send(request).await?;
 
         self.handle_response(response).await
     }
 
+    /// Run a GraphQL query and deserialize its `data`.
+    ///
+    /// GraphQL reports failures in an `errors` array with HTTP 200; their
+    /// messages are returned as [`GitLabError::GraphQl`].
+    pub async fn graphql_typed<R: DeserializeOwned>(&self, query: &str, variables: Value) -> Result<R> {
+        let mut body = self.graphql(query, variables).await?;
+
+        let messages: Vec<&str> = body
+            .get("errors")
+            .and_then(|errors| errors.as_array())
+            .into_iter()
+            .flatten()
+            .map(|error| error.get("message").and_then(|m| m.as_str()).unwrap_or("unknown error"))
+            .collect();
+        if !messages.is_empty() {
+            return Err(GitLabError::GraphQl(messages.join("; ")));
+        }
+
+        match body.get_mut("data").map(Value::take) {
+            Some(data) if !data.is_null() => Ok(serde_json::from_value(data)?),
+            _ => Err(GitLabError::GraphQl("response has no data".to_string())),
+        }
+    }
+
     /// Make a GET request and return raw bytes
     pub async fn get_bytes(&self, path: &str) -> Result<Vec<u8>> {
         let url = self.api_url(path);
-        let response = self
+        let request = self
             .http_client
             .get(url)
             .header(header::AUTHORIZATION, format!("Bearer {}", self.token))
-            .header(header::USER_AGENT, "gitlab-mcp-server/0.1.0")
-            .send()
-            .await?;
+            .header(header::USER_AGENT, "gitlab-mcp-server/0.1.0");
+        let response = self.send(request).await?;
 
         let status = response.status();
 
@@ -161,13 +445,39 @@ impl GitLabClient {
                 .await
                 .map_err(|e| GitLabError::ApiError(e))?;
             Ok(bytes.to_vec())
-        } else if status.as_u16() == 401 {
-            Err(GitLabError::auth_error("Invalid or expired token"))
-        } else if status.as_u16() == 404 {
-            Err(GitLabError::not_found("Resource not found"))
         } else {
-            let text = response.text().await.unwrap_or_else(|_| "Unknown error".to_string());
-            Err(GitLabError::api_response(status.as_u16(), text))
+            let text = response.text().await.unwrap_or_default();
+            Err(GitLabError::from_response(status.as_u16(), &text))
+        }
+    }
+
+    /// Send a request, waiting and retrying while GitLab answers 429.
+    ///
+    /// The `Retry-After` header (in seconds) is honoured; without it the
+    /// delay backs off exponentially from `retry_backoff`. Once retries are
+    /// exhausted, or GitLab asks for a longer wait than [`MAX_RETRY_DELAY`],
+    /// the request fails with [`GitLabError::RateLimited`].
+    async fn send(&self, mut request: RequestBuilder) -> Result<Response> {
+        let mut attempt = 0;
+        loop {
+            // Streaming bodies cannot be replayed, so their 429s are final
+            let retry = request.try_clone();
+            let response = request.send().await?;
+            if response.status() != StatusCode::TOO_MANY_REQUESTS {
+                return Ok(response);
+            }
+
+            let retry_after = retry_after(&response)
+                .unwrap_or_else(|| self.retry_backoff.saturating_mul(2u32.saturating_pow(attempt)));
+            match retry {
+                Some(retry) if attempt < self.max_retries && retry_after <= MAX_RETRY_DELAY => {
+                    tracing::warn!("GitLab rate limit hit, retrying in {:?}", retry_after);
+                    tokio::time::sleep(retry_after).await;
+                    request = retry;
+                    attempt += 1;
+                }
+                _ => return Err(GitLabError::RateLimited { retry_after }),
+            }
         }
     }
 
@@ -183,18 +493,26 @@ impl GitLabClient {
                 .json()
                 .await
                 .map_err(|e| GitLabError::ApiError(e))
-        } else if status.as_u16() == 401 {
-            Err(GitLabError::auth_error("Invalid or expired token"))
-        } else if status.as_u16() == 404 {
-            Err(GitLabError::not_found("Resource not found"))
-        } else if status.as_u16() == 429 {
-            Err(GitLabError::RateLimitExceeded)
+        } else if status == StatusCode::TOO_MANY_REQUESTS {
+            Err(GitLabError::RateLimited {
+                retry_after: retry_after(&response).unwrap_or(self.retry_backoff),
+            })
         } else {
-            let text = response.text().await.unwrap_or_else(|_| "Unknown error".to_string());
-            Err(GitLabError::api_response(status.as_u16(), text))
+            let text = response.text().await.unwrap_or_default();
+            Err(GitLabError::from_response(status.as_u16(), &text))
         }
     }
 
+    /// Build the GraphQL endpoint URL
+    fn graphql_url(&self) -> Url {
+        let mut url = self.base_url.clone();
+        url.path_segments_mut()
+            .expect("Invalid base URL")
+            .pop_if_empty()
+            .extend(["api", "graphql"]);
+        url
+    }
+
     /// Build full API URL
     fn api_url(&self, path: &str) -> Url {
         let mut url = self.base_url.clone();
@@ -215,11 +533,384 @@ impl GitLabClient {
 
         url
     }
+
+    /// Register the `x-gitlab-project-id` schema keyword, so malformed
+    /// project ids are rejected at the tool input boundary instead of
+    /// reaching the API as 404s.
+    pub fn register_schema_keywords(validator: &mut impl SchemaValidator) {
+        validator.register_keyword(PROJECT_ID_KEYWORD, Box::new(ProjectIdKeyword));
+    }
+}
+
+/// Schema keyword marking a string as a GitLab project id
+pub const PROJECT_ID_KEYWORD: &str = "x-gitlab-project-id";
+
+/// Accepts a numeric project id or a namespaced path such as
+/// `group/project`, either plain or URL-encoded (`group%2Fproject`)
+struct ProjectIdKeyword;
+
+impl CustomKeywordValidator for ProjectIdKeyword {
+    fn validate(&self, value: &Value, schema_value: &Value) -> std::result::Result<(), ValidationError> {
+        if schema_value != &Value::Bool(true) {
+            return Ok(());
+        }
+        match value.as_str() {
+            Some(id) if is_project_id(id) => Ok(()),
+            _ => Err(ValidationError::Failed(vec![format!(
+                "{} is not a numeric project id or a namespaced path such as group/project",
+                value
+            )])),
+        }
+    }
+}
+
+fn is_project_id(id: &str) -> bool {
+    if !id.is_empty() && id.bytes().all(|b| b.is_ascii_digit()) {
+        return true;
+    }
+    let path = id.replace("%2F", "/").replace("%2f", "/");
+    let segments: Vec<&str> = path.split('/').collect();
+    segments.len() >= 2
+        && segments.iter().all(|segment| {
+            !segment.is_empty()
+                && segment
+                    .chars()
+                    .all(|c| c.is_alphanumeric() || matches!(c, '-' | '_' | '.'))
+        })
+}
+
+/// Delay requested by a `Retry-After` header given in seconds
+fn retry_after(response: &Response) -> Option<Duration> {
+    response
+        .headers()
+        .get(header::RETRY_AFTER)?
+        .to_str()
+        .ok()?
+        .trim()
+        .parse()
+        .ok()
+        .map(Duration::from_secs)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    /// Serve `responses` in order, one request per connection, counting requests
+    async fn mock_server(responses: Vec<&'static str>) -> (String, Arc<AtomicUsize>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let requests = Arc::new(AtomicUsize::new(0));
+        let counter = requests.clone();
+        tokio::spawn(async move {
+            for (index, response) in responses.iter().cycle().enumerate() {
+                let (mut socket, _) = listener.accept().await.unwrap();
+                let mut request = Vec::new();
+                let mut buf = [0u8; 1024];
+                while !request.windows(4).any(|w| w == b"\r\n\r\n") {
+                    let n = socket.read(&mut buf).await.unwrap();
+                    if n == 0 {
+                        break;
+                    }
+                    request.extend_from_slice(&buf[..n]);
+                }
+                counter.store(index + 1, Ordering::SeqCst);
+                socket.write_all(response.as_bytes()).await.unwrap();
+                socket.shutdown().await.ok();
+            }
+        });
+        (url, requests)
+    }
+
+    /// Answer every request with `OK_USER_KEEP_ALIVE` over keep-alive
+    /// connections, counting accepted connections
+    async fn keep_alive_server() -> (String, Arc<AtomicUsize>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let connections = Arc::new(AtomicUsize::new(0));
+        let counter = connections.clone();
+        tokio::spawn(async move {
+            loop {
+                let (mut socket, _) = listener.accept().await.unwrap();
+                counter.fetch_add(1, Ordering::SeqCst);
+                tokio::spawn(async move {
+                    let mut pending = Vec::new();
+                    let mut buf = [0u8; 1024];
+                    loop {
+                        while let Some(end) = pending.windows(4).position(|w| w == b"\r\n\r\n") {
+                            pending.drain(..end + 4);
+                            if socket.write_all(OK_USER_KEEP_ALIVE.as_bytes()).await.is_err() {
+                                return;
+                            }
+                        }
+                        match socket.read(&mut buf).await {
+                            Ok(0) | Err(_) => return,
+                            Ok(n) => pending.extend_from_slice(&buf[..n]),
+                        }
+                    }
+                });
+            }
+        });
+        (url, connections)
+    }
+
+    const OK_USER_KEEP_ALIVE: &str =
+        "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: 20\r\n\r\n{\"username\":\"alice\"}";
+
+    /// Make `calls` sequential requests, returning the number of connections
+    /// the server accepted
+    async fn run_calls(pool: &PoolOptions, calls: usize) -> usize {
+        let (url, connections) = keep_alive_server().await;
+        let client = GitLabClient::with_http_options(&url, "test_token", &TlsOptions::default(), pool).unwrap();
+        for _ in 0..calls {
+            let user: User = client.get("user").await.unwrap();
+            assert_eq!(user.username, "alice");
+        }
+        connections.load(Ordering::SeqCst)
+    }
+
+    #[tokio::test]
+    async fn test_pooled_connections_are_reused() {
+        let calls = 50;
+        let pooled = run_calls(&PoolOptions::default(), calls).await;
+        let no_pool = PoolOptions {
+            max_idle_per_host: 0,
+            ..PoolOptions::default()
+        };
+        let unpooled = run_calls(&no_pool, calls).await;
+
+        assert_eq!(pooled, 1);
+        assert_eq!(unpooled, calls);
+    }
+
+    #[test]
+    fn test_shared_client_is_reused_until_config_changes() {
+        let mut config = Config {
+            gitlab_token: "test_token".to_string(),
+            ..Config::default()
+        };
+        let first = GitLabClient::shared(&config).unwrap();
+        assert!(Arc::ptr_eq(&first, &GitLabClient::shared(&config).unwrap()));
+
+        config.gitlab_token = "other_token".to_string();
+        assert!(!Arc::ptr_eq(&first, &GitLabClient::shared(&config).unwrap()));
+    }
+
+    const RATE_LIMITED: &str =
+        "HTTP/1.1 429 Too Many Requests\r\nRetry-After: 0\r\nContent-Length: 0\r\nConnection: close\r\n\r\n";
+    const RATE_LIMITED_NO_HEADER: &str =
+        "HTTP/1.1 429 Too Many Requests\r\nContent-Length: 0\r\nConnection: close\r\n\r\n";
+    const OK_USER: &str = "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: 20\r\nConnection: close\r\n\r\n{\"username\":\"alice\"}";
+
+    #[derive(Deserialize)]
+    struct User {
+        username: String,
+    }
+
+    #[tokio::test]
+    async fn test_retries_rate_limited_requests() {
+        let (url, requests) = mock_server(vec![RATE_LIMITED, RATE_LIMITED_NO_HEADER, OK_USER]).await;
+        let client = GitLabClient::new(&url, "test_token")
+            .unwrap()
+            .with_retry_backoff(Duration::from_millis(10));
+
+        let user: User = client.get("user").await.unwrap();
+        assert_eq!(user.username, "alice");
+        assert_eq!(requests.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_rate_limited_after_max_retries() {
+        let (url, requests) = mock_server(vec![RATE_LIMITED]).await;
+        let client = GitLabClient::new(&url, "test_token").unwrap().with_max_retries(1);
+
+        let err = client.get::<User>("user").await.err().unwrap();
+        assert!(matches!(err, GitLabError::RateLimited { retry_after } if retry_after == Duration::ZERO));
+        assert_eq!(requests.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_exponential_backoff_without_retry_after() {
+        let (url, _) = mock_server(vec![RATE_LIMITED_NO_HEADER]).await;
+        let client = GitLabClient::new(&url, "test_token")
+            .unwrap()
+            .with_max_retries(2)
+            .with_retry_backoff(Duration::from_millis(5));
+
+        let err = client.get::<User>("user").await.err().unwrap();
+        // Waited 5ms and 10ms; the third 429 would have waited 20ms
+        assert!(matches!(err, GitLabError::RateLimited { retry_after } if retry_after == Duration::from_millis(20)));
+    }
+
+    /// 200 response carrying a JSON body
+    fn json_response(body: &str) -> &'static str {
+        Box::leak(
+            format!(
+                "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                body.len(),
+                body
+            )
+            .into_boxed_str(),
+        )
+    }
+
+    /// 200 response for one page of a list, with the given `X-Next-Page`
+    fn page_response(body: &str, next_page: &str) -> &'static str {
+        Box::leak(
+            format!(
+                "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nX-Next-Page: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                next_page,
+                body.len(),
+                body
+            )
+            .into_boxed_str(),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_paginate_follows_next_page() {
+        let (url, requests) = mock_server(vec![
+            page_response(r#"[{"username":"alice"},{"username":"bob"}]"#, "2"),
+            page_response(r#"[{"username":"carol"}]"#, ""),
+        ])
+        .await;
+        let client = GitLabClient::new(&url, "test_token").unwrap();
+
+        let users: Vec<User> = client.paginate("users", &[], 2).try_collect().await.unwrap();
+        let names: Vec<&str> = users.iter().map(|u| u.username.as_str()).collect();
+        assert_eq!(names, ["alice", "bob", "carol"]);
+        assert_eq!(requests.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_paginate_stops_at_limit() {
+        use futures::StreamExt;
+
+        let (url, requests) = mock_server(vec![page_response(
+            r#"[{"username":"alice"},{"username":"bob"}]"#,
+            "2",
+        )])
+        .await;
+        let client = GitLabClient::new(&url, "test_token").unwrap();
+
+        // Every page claims a next page; only the pages needed are fetched
+        let users: Vec<User> = client.paginate("users", &[], 2).take(3).try_collect().await.unwrap();
+        assert_eq!(users.len(), 3);
+        assert_eq!(requests.load(Ordering::SeqCst), 2);
+    }
+
+    #[derive(Deserialize)]
+    struct CurrentUser {
+        #[serde(rename = "currentUser")]
+        current_user: User,
+    }
+
+    #[tokio::test]
+    async fn test_graphql_typed_extracts_data() {
+        let (url, _) = mock_server(vec![json_response(r#"{"data":{"currentUser":{"username":"alice"}}}"#)]).await;
+        let client = GitLabClient::new(&url, "test_token").unwrap();
+
+        let data: CurrentUser = client
+            .graphql_typed("query { currentUser { username } }", json!({}))
+            .await
+            .unwrap();
+        assert_eq!(data.current_user.username, "alice");
+    }
+
+    #[tokio::test]
+    async fn test_graphql_typed_maps_errors() {
+        let body = r#"{"data":null,"errors":[{"message":"Field 'nope' doesn't exist"},{"message":"second"}]}"#;
+        let (url, _) = mock_server(vec![json_response(body)]).await;
+        let client = GitLabClient::new(&url, "test_token").unwrap();
+
+        // The raw response keeps the errors
+        let raw = client.graphql("query { nope }", json!({})).await.unwrap();
+        assert_eq!(raw["errors"].as_array().unwrap().len(), 2);
+
+        let err = client.graphql_typed::<CurrentUser>("query { nope }", json!({})).await.err().unwrap();
+        assert!(matches!(err, GitLabError::GraphQl(ref m) if m == "Field 'nope' doesn't exist; second"));
+    }
+
+    #[tokio::test]
+    async fn test_unprocessable_entity_maps_to_validation() {
+        let body = r#"{"message":{"title":["can't be blank"]}}"#;
+        let response = Box::leak(
+            format!(
+                "HTTP/1.1 422 Unprocessable Entity\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                body.len(),
+                body
+            )
+            .into_boxed_str(),
+        );
+        let (url, _) = mock_server(vec![response]).await;
+        let client = GitLabClient::new(&url, "test_token").unwrap();
+
+        let err = client.post::<User, _>("projects/1/issues", &json!({})).await.err().unwrap();
+        assert!(matches!(err, GitLabError::Validation { ref fields, .. } if fields["title"] == ["can't be blank"]));
+    }
+
+    /// Self-signed certificate for `localhost`
+    const TEST_CA: &str = "-----BEGIN CERTIFICATE-----
+MIIBfTCCASOgAwIBAgIUBnnrDgMlHlPOZJWc8i/8278RYOcwCgYIKoZIzj0EAwIw
+FDESMBAGA1UEAwwJbG9jYWxob3N0MB4XDTI2MTAxNjEzMDcwNVoXDTM2MTAxMzEz
+MDcwNVowFDESMBAGA1UEAwwJbG9jYWxob3N0MFkwEwYHKoZIzj0CAQYIKoZIzj0D
+AQcDQgAE8qXlCcvfE1YUuLPAnoYZmJaCVltmfl/LVKBe0aJOECpzGcy8YgZ09/Iw
+LrrvjlEn0UZla7qZ8poIdKB+L0SuGKNTMFEwHQYDVR0OBBYEFNp5blpM73C5dsvi
+iIP5IPKp/nmcMB8GA1UdIwQYMBaAFNp5blpM73C5dsviiIP5IPKp/nmcMA8GA1Ud
+EwEB/wQFMAMBAf8wCgYIKoZIzj0EAwIDSAAwRQIgBW1DmOG7sJQmBJVTrPp0bwMG
+cVOFcKCKbjLdtq+KCHACIQD+PB1bSuwrhIpPiY5JWBjI8flJ7Db0CmGUzG5zefA9
+vQ==
+-----END CERTIFICATE-----
+";
+
+    #[test]
+    fn test_rejects_bare_hostnames() {
+        for url in ["gitlab.example.com", "gitlab.example.com:8443", "ftp://gitlab.example.com", "https://"] {
+            assert!(parse_base_url(url).is_err(), "{url}");
+        }
+        assert!(parse_base_url("https://gitlab.example.com/gitlab").is_ok());
+        assert!(parse_base_url("http://10.0.0.5:8080").is_ok());
+    }
+
+    #[test]
+    fn test_ca_bundle_is_loaded() {
+        let path = std::env::temp_dir().join(format!("gitlab-mcp-ca-{}.pem", std::process::id()));
+        std::fs::write(&path, TEST_CA).unwrap();
+        let tls = TlsOptions {
+            ca_cert_path: Some(path.clone()),
+            insecure_skip_verify: false,
+        };
+        let result = GitLabClient::with_tls("https://gitlab.example.com", "test_token", &tls);
+
+        std::fs::write(&path, "not a certificate").unwrap();
+        let invalid = GitLabClient::with_tls("https://gitlab.example.com", "test_token", &tls);
+        let _ = std::fs::remove_file(&path);
+
+        assert!(result.is_ok());
+        assert!(matches!(invalid, Err(GitLabError::InvalidParameter(_))));
+    }
+
+    #[test]
+    fn test_missing_ca_bundle_is_rejected() {
+        let tls = TlsOptions {
+            ca_cert_path: Some(PathBuf::from("/nonexistent/ca.pem")),
+            insecure_skip_verify: false,
+        };
+        let err = GitLabClient::with_tls("https://gitlab.example.com", "test_token", &tls).err().unwrap();
+        assert!(err.to_string().contains("/nonexistent/ca.pem"));
+    }
+
+    #[test]
+    fn test_build_graphql_url() {
+        let client = GitLabClient::new("https://gitlab.example.com/gitlab/", "test_token").unwrap();
+        assert_eq!(client.graphql_url().as_str(), "https://gitlab.example.com/gitlab/api/graphql");
+        let client = GitLabClient::new("https://gitlab.com", "test_token").unwrap();
+        assert_eq!(client.graphql_url().as_str(), "https://gitlab.com/api/graphql");
+    }
 
     #[test]
     fn test_build_api_url() {
@@ -234,4 +925,30 @@ mod tests {
         let url = client.api_url("/projects/123");
         assert_eq!(url.as_str(), "https://gitlab.com/api/v4/projects/123");
     }
+
+    #[test]
+    fn test_project_id_keyword() {
+        let mut validator = mcp_core::schema::JsonSchemaValidator::default();
+        GitLabClient::register_schema_keywords(&mut validator);
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "project_id": { "type": "string", "x-gitlab-project-id": true }
+            }
+        });
+
+        for id in ["42", "group/project", "group%2Fsub%2Fmy.project", "my-group/my_project"] {
+            assert!(
+                validator.validate_value(&schema, &json!({ "project_id": id })).is_ok(),
+                "{}",
+                id
+            );
+        }
+        for id in ["", "-1", "project", "group//project", "group/", "12 ", "group/pro ject"] {
+            let err = validator
+                .validate_value(&schema, &json!({ "project_id": id }))
+                .unwrap_err();
+            assert!(err.to_string().contains("x-gitlab-project-id"), "{}: {}", id, err);
+        }
+    }
 }
@@ -6,7 +6,10 @@ pub mod config;
 pub mod error;
 pub mod gitlab;
 pub mod logging;
+pub mod redaction;
+pub mod resources;
 pub mod server;
+pub mod summary;
 pub mod tools;
 
 pub use config::Config;
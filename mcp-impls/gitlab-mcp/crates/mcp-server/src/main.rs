@@ -1,7 +1,9 @@
-use std::io::{self, BufRead, BufReader, Write};
-use gitlab_mcp_server::{GitLabMcpServer, logging};
-use mcp_core::stdio::{JsonRpcMessage, serialize_message};
-use mcp_core::types::{Implementation, BaseMetadata, Icons, ServerCapabilities};
+use std::sync::{Arc, Mutex};
+use gitlab_mcp_server::{Config, GitLabClient, GitLabMcpServer, logging, redaction, resources};
+use gitlab_mcp_server::summary::{self, TransportMode};
+use mcp_core::schema::JsonSchemaValidator;
+use mcp_core::types::{RequestMessage, ServerCapabilities};
+use mcp_server::{StdioOptions, stdio};
 
 fn main() -> anyhow::Result<()> {
     // Create Tokio runtime for async operations
@@ -15,17 +17,8 @@ fn main() -> anyhow::Result<()> {
 
     tracing::info!("GitLab MCP Server starting (version {})", env!("CARGO_PKG_VERSION"));
 
-    // Create server info
-    let server_info = Implementation {
-        base: BaseMetadata {
-            name: "gitlab-mcp-server".to_string(),
-            title: Some("GitLab MCP Server".to_string()),
-        },
-        icons: Icons::default(),
-        version: env!("CARGO_PKG_VERSION").to_string(),
-        website_url: Some("https://gitlab.com".to_string()),
-        description: Some("MCP server for GitLab operations".to_string()),
-    };
+    // Create server info; the description is regenerated once tools are registered
+    let server_info = GitLabMcpServer::server_info("MCP server for GitLab operations".to_string());
 
     // Create server options
     let mut server_options = mcp_server::ServerOptions::default();
@@ -35,10 +28,13 @@ fn main() -> anyhow::Result<()> {
         }),
         ..Default::default()
     });
-    server_options.instructions = Some(
-        "GitLab MCP server providing tools for managing GitLab projects, issues, merge requests, pipelines, and more.".to_string()
-    );
-
+    // Mask tokens that show up in file contents, logs or descriptions
+    server_options.result_transform =
+        Some(redaction::token_redaction(Config::from_env().gitlab_token));
+    // Reject malformed project ids before they reach the GitLab API
+    let mut tool_input_validator = JsonSchemaValidator::default();
+    GitLabClient::register_schema_keywords(&mut tool_input_validator);
+    server_options.tool_input_validator = Some(std::sync::Arc::new(tool_input_validator));
     // Create MCP server
     let mut server = mcp_server::McpServer::new(server_info, server_options);
 
@@ -52,60 +48,54 @@ fn main() -> anyhow::Result<()> {
         }
     }
 
-    // Stdio loop
-    let stdin = io::stdin();
-    let mut stdout = io::stdout();
-    let mut reader = BufReader::new(stdin.lock());
-    let mut buffer = String::new();
-    let mut read_buffer = mcp_core::stdio::ReadBuffer::default();
+    // Register resource templates
+    if let Err(e) = resources::register_resource_templates(&mut server) {
+        tracing::error!("Failed to register resource templates: {}", e);
+    }
 
-    loop {
-        buffer.clear();
-        let bytes_read = reader.read_line(&mut buffer)?;
-        if bytes_read == 0 {
-            break;
-        }
+    // Describe the actual configuration and self-check results to hosts
+    let described_config = Config::from_env();
+    let server_summary = rt.block_on(GitLabMcpServer::refresh_server_info(
+        &server,
+        &described_config,
+        TransportMode::Stdio,
+    ));
+    tracing::info!("{}", summary::describe(&server_summary));
 
-        read_buffer.append(buffer.as_bytes());
-        while let Ok(Some(message)) = read_buffer.read_message() {
-            match message {
-                JsonRpcMessage::Request(request) => {
-                    match rt.block_on(server.server().handle_request(request, None)) {
-                        Ok(response) => {
-                            let response_msg = JsonRpcMessage::Result(response);
-                            match serialize_message(&response_msg) {
-                                Ok(serialized) => {
-                                    if let Err(e) = stdout.write_all(serialized.as_bytes()) {
-                                        eprintln!("[gitlab-mcp-server] Error writing response: {}", e);
-                                        break;
-                                    }
-                                    if let Err(e) = stdout.flush() {
-                                        eprintln!("[gitlab-mcp-server] Error flushing: {}", e);
-                                        break;
-                                    }
-                                }
-                                Err(e) => {
-                                    tracing::error!("Error serializing response: {}", e);
-                                }
-                            }
-                        }
-                        Err(e) => {
-                            tracing::error!("Error handling request: {}", e);
-                        }
-                    }
+    // Tools re-read the config on every call; keep initialize in step with it
+    let server = Arc::new(server);
+    let refresh_server = Arc::clone(&server);
+    let described_config = Arc::new(Mutex::new(described_config));
+    let options = StdioOptions {
+        before_request: Some(Arc::new(move |request: &RequestMessage| {
+            let server = Arc::clone(&refresh_server);
+            let described_config = Arc::clone(&described_config);
+            let is_initialize = request.method == "initialize";
+            Box::pin(async move {
+                if !is_initialize {
+                    return;
                 }
-                JsonRpcMessage::Notification(notification) => {
-                    if let Err(e) = rt.block_on(server.server().handle_notification(notification, None)) {
-                        tracing::error!("Error handling notification: {}", e);
-                    }
+                let config = Config::from_env();
+                if config == *described_config.lock().unwrap() {
+                    return;
                 }
-                JsonRpcMessage::Result(result) => {
-                    tracing::debug!("Received result: {:?}", result);
-                }
-            }
-        }
+                GitLabMcpServer::refresh_server_info(&server, &config, TransportMode::Stdio).await;
+                *described_config.lock().unwrap() = config;
+            })
+        })),
+    };
+
+    let stdin = tokio::io::BufReader::new(tokio::io::stdin());
+    if let Err(e) = rt.block_on(stdio::run_async_with(
+        Arc::clone(&server),
+        options,
+        stdin,
+        tokio::io::stdout(),
+    )) {
+        tracing::error!("Stdio transport error: {}", e);
     }
 
+    eprintln!("{}", server.summary_report());
     tracing::info!("Server shutdown");
     Ok(())
 }
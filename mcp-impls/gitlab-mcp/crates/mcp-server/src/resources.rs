@@ -0,0 +1,121 @@
+//! Resource templates
+//!
+//! Hosts can attach GitLab data to a conversation as resources, addressed
+//! with `gitlab://` URIs.
+
+use std::collections::HashMap;
+
+use mcp_core::protocol::RequestContext;
+use mcp_core::types::{
+    BaseMetadata, Icons, ReadResourceResult, ResourceContents, ResourceContentsBase,
+    ResourceTemplate, TextResourceContents,
+};
+use mcp_server::{McpServer, ServerError};
+use serde_json::Value;
+
+use crate::config::Config;
+use crate::gitlab::GitLabClient;
+
+/// Open issues of a project, by numeric ID or URL-encoded path
+pub const PROJECT_ISSUES_TEMPLATE: &str = "gitlab://project/{id}/issues";
+
+/// Register the resource templates with the MCP server
+pub fn register_resource_templates(server: &mut McpServer) -> Result<(), ServerError> {
+    let project_issues = ResourceTemplate {
+        base: BaseMetadata {
+            name: "project_issues".to_string(),
+            title: Some("Project Issues".to_string()),
+        },
+        icons: Icons::default(),
+        uri_template: PROJECT_ISSUES_TEMPLATE.to_string(),
+        description: Some(
+            "Open issues of a project; `id` is the numeric project ID or its URL-encoded path (group%2Fproject)"
+                .to_string(),
+        ),
+        mime_type: Some("application/json".to_string()),
+        annotations: None,
+        meta: None,
+    };
+
+    server.register_resource_template(
+        project_issues,
+        |uri: String, variables: HashMap<String, String>, _context: RequestContext| async move {
+            let project_id = variables
+                .get("id")
+                .ok_or_else(|| ServerError::Handler("id is required".to_string()))?;
+            let client = GitLabClient::shared(&Config::from_env())
+                .map_err(|e| ServerError::Handler(format!("Failed to create client: {}", e)))?;
+
+            let query = vec![
+                ("state".to_string(), "opened".to_string()),
+                ("per_page".to_string(), "100".to_string()),
+            ];
+            let issues: Value = client
+                .get_with_query(&project_issues_path(project_id), &query)
+                .await
+                .map_err(|e| ServerError::Handler(e.to_string()))?;
+
+            json_contents(uri, &issues)
+        },
+    )
+}
+
+/// API path listing a project's issues; `project_id` is already decoded
+fn project_issues_path(project_id: &str) -> String {
+    format!("projects/{}/issues", urlencoding::encode(project_id))
+}
+
+fn json_contents(uri: String, value: &Value) -> Result<ReadResourceResult, ServerError> {
+    Ok(ReadResourceResult {
+        contents: vec![ResourceContents::Text(TextResourceContents {
+            base: ResourceContentsBase {
+                uri,
+                mime_type: Some("application/json".to_string()),
+                meta: None,
+            },
+            text: serde_json::to_string_pretty(value)?,
+        })],
+        meta: None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::server::GitLabMcpServer;
+    use mcp_core::types::RequestMessage;
+    use mcp_core::uri_template::UriTemplate;
+    use mcp_server::ServerOptions;
+    use serde_json::json;
+
+    #[test]
+    fn test_project_paths_are_reencoded() {
+        let template = UriTemplate::parse(PROJECT_ISSUES_TEMPLATE).unwrap();
+        let variables = template
+            .match_uri("gitlab://project/group%2Fapp/issues")
+            .unwrap();
+        assert_eq!(
+            project_issues_path(&variables["id"]),
+            "projects/group%2Fapp/issues"
+        );
+
+        let variables = template.match_uri("gitlab://project/42/issues").unwrap();
+        assert_eq!(project_issues_path(&variables["id"]), "projects/42/issues");
+    }
+
+    #[test]
+    fn test_templates_are_listed() {
+        let mut server = McpServer::new(
+            GitLabMcpServer::server_info("test".to_string()),
+            ServerOptions::default(),
+        );
+        register_resource_templates(&mut server).unwrap();
+
+        let request = RequestMessage::new("1", "resources/templates/list", json!({}));
+        let response =
+            futures::executor::block_on(server.server().handle_request(request, None)).unwrap();
+        let templates = &response.result.unwrap()["resourceTemplates"];
+        assert_eq!(templates[0]["uriTemplate"], PROJECT_ISSUES_TEMPLATE);
+        assert_eq!(templates[0]["name"], "project_issues");
+    }
+}
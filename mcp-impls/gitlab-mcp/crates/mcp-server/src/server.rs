@@ -1,13 +1,19 @@
-use mcp_server::{McpServer, ServerError};
+use mcp_server::{BoxedToolHandler, McpServer, ServerError};
 use mcp_core::{
     types::{
-        BaseMetadata, Icons, Tool, CallToolResult, ContentBlock, TextContent,
+        BaseMetadata, Icons, Implementation, Tool, ToolAnnotations, CallToolResult, ContentBlock,
+        TextContent,
     },
     protocol::RequestContext,
 };
 use crate::gitlab::GitLabClient;
 use crate::config::Config;
+use crate::summary::{self, ServerSummary, TransportMode};
+use crate::tools::{
+    boards, dry_run_result, fetch_list, labels, milestones, registry, vulnerabilities, with_pagination,
+};
 use serde_json::json;
+use std::sync::Arc;
 
 /// GitLab MCP server
 pub struct GitLabMcpServer {
@@ -20,13 +26,18 @@ impl GitLabMcpServer {
         config.validate()
             .map_err(|e| format!("Invalid config: {}", e))?;
 
-        let _client = GitLabClient::new(&config.gitlab_url, &config.gitlab_token)?;
+        let _client = GitLabClient::from_config(&config)?;
 
         Ok(Self { _client })
     }
 
     /// Register tools with the MCP server
+    ///
+    /// Tools are registered as one group named `gitlab_<tool>`, so they do
+    /// not collide with tools from other servers.
     pub fn register_tools(server: &mut McpServer) -> Result<(), ServerError> {
+        let mut tools: Vec<(Tool, BoxedToolHandler)> = Vec::new();
+
         // === Configuration Tools ===
 
         // Register config_status tool
@@ -47,9 +58,9 @@ impl GitLabMcpServer {
             meta: None,
         };
 
-        server.register_tool(
+        tools.push((
             config_status_tool,
-            |_arguments: Option<serde_json::Value>, _context: RequestContext| {
+            Box::new(|_arguments: Option<serde_json::Value>, _context: RequestContext| {
                 Box::pin(async move {
                     let config = Config::from_env();
                     let config_file = Config::config_file();
@@ -92,6 +103,19 @@ impl GitLabMcpServer {
                     // Log level
                     status.push(format!("**Log Level:** {}", config.log_level));
 
+                    // Dry-run mode
+                    if config.dry_run {
+                        status.push("**Dry Run:** Enabled (write tools are simulated)".to_string());
+                    }
+
+                    // TLS
+                    if let Some(path) = &config.ca_cert_path {
+                        status.push(format!("**CA Bundle:** {}", path.display()));
+                    }
+                    if config.insecure_skip_tls_verify {
+                        status.push("**TLS Verification:** DISABLED (development only)".to_string());
+                    }
+
                     // Validation
                     match config.validate() {
                         Ok(_) => status.push("\n**Configuration:** Valid".to_string()),
@@ -112,8 +136,8 @@ impl GitLabMcpServer {
                         ..Default::default()
                     })
                 })
-            },
-        )?;
+            }),
+        ));
 
         // Register get_config_info tool
         let get_config_info_tool = Tool {
@@ -133,9 +157,9 @@ impl GitLabMcpServer {
             meta: None,
         };
 
-        server.register_tool(
+        tools.push((
             get_config_info_tool,
-            |_arguments: Option<serde_json::Value>, _context: RequestContext| {
+            Box::new(|_arguments: Option<serde_json::Value>, _context: RequestContext| {
                 Box::pin(async move {
                     let info = vec![
                         "## GitLab MCP Configuration\n".to_string(),
@@ -151,6 +175,9 @@ impl GitLabMcpServer {
                         "```bash".to_string(),
                         "export GITLAB_URL=\"https://gitlab.com\"".to_string(),
                         "export GITLAB_TOKEN=\"glpat-xxxxxxxxxxxxxx\"".to_string(),
+                        "export GITLAB_MCP_DRY_RUN=true  # Simulate write tools".to_string(),
+                        "export GITLAB_CA_CERT=/etc/ssl/gitlab-ca.pem  # Trust a private CA".to_string(),
+                        "export GITLAB_INSECURE_SKIP_TLS_VERIFY=true  # Development only".to_string(),
                         "```".to_string(),
                         "".to_string(),
                         "**Via Config File:**".to_string(),
@@ -160,12 +187,24 @@ impl GitLabMcpServer {
                         "gitlab_url = \"https://gitlab.com\"".to_string(),
                         "gitlab_token = \"glpat-xxxxxxxxxxxxxx\"".to_string(),
                         "log_level = \"info\"".to_string(),
+                        "dry_run = false".to_string(),
+                        "ca_cert_path = \"/etc/ssl/gitlab-ca.pem\"".to_string(),
+                        "insecure_skip_tls_verify = false".to_string(),
+                        "pool_max_idle_per_host = 8".to_string(),
+                        "pool_idle_timeout = 90".to_string(),
+                        "tcp_keepalive = 60".to_string(),
                         "```".to_string(),
                         "".to_string(),
                         "### Configuration Options\n".to_string(),
                         "- **gitlab_url**: GitLab instance URL (default: https://gitlab.com)".to_string(),
                         "- **gitlab_token**: Personal Access Token for authentication".to_string(),
                         "- **log_level**: Logging level (trace, debug, info, warn, error)".to_string(),
+                        "- **dry_run**: Validate write tools and describe the request instead of calling the API (default: false)".to_string(),
+                        "- **ca_cert_path**: PEM bundle of CA certificates to trust, for self-managed instances with a private CA".to_string(),
+                        "- **insecure_skip_tls_verify**: Skip TLS certificate verification; development only (default: false)".to_string(),
+                        "- **pool_max_idle_per_host**: Idle keep-alive connections kept per host (default: 8)".to_string(),
+                        "- **pool_idle_timeout**: Seconds an idle connection is kept, 0 for no limit (default: 90)".to_string(),
+                        "- **tcp_keepalive**: Seconds between TCP keepalive probes, 0 to disable (default: 60)".to_string(),
                         "".to_string(),
                         "### Priority Order".to_string(),
                         "1. Environment variables (highest priority)".to_string(),
@@ -178,8 +217,8 @@ impl GitLabMcpServer {
                         ..Default::default()
                     })
                 })
-            },
-        )?;
+            }),
+        ));
 
         // Register set_config tool
         let set_config_tool = Tool {
@@ -208,9 +247,9 @@ impl GitLabMcpServer {
             meta: None,
         };
 
-        server.register_tool(
+        tools.push((
             set_config_tool,
-            |arguments: Option<serde_json::Value>, _context: RequestContext| {
+            Box::new(|arguments: Option<serde_json::Value>, _context: RequestContext| {
                 Box::pin(async move {
                     let args = arguments.and_then(|a| a.as_object().cloned()).unwrap_or_default();
 
@@ -278,8 +317,8 @@ impl GitLabMcpServer {
                         ..Default::default()
                     })
                 })
-            },
-        )?;
+            }),
+        ));
 
         // === Project Tools ===
 
@@ -296,6 +335,7 @@ impl GitLabMcpServer {
                 "properties": {
                     "project_id": {
                         "type": "string",
+                        "x-gitlab-project-id": true,
                         "description": "Project ID or URL-encoded path"
                     }
                 },
@@ -307,9 +347,9 @@ impl GitLabMcpServer {
             meta: None,
         };
 
-        server.register_tool(
+        tools.push((
             get_project_tool,
-            |arguments: Option<serde_json::Value>, _context: RequestContext| {
+            Box::new(|arguments: Option<serde_json::Value>, _context: RequestContext| {
                 Box::pin(async move {
                     let args = arguments.as_ref().and_then(|a| a.as_object());
                     let project_id = args
@@ -318,7 +358,7 @@ impl GitLabMcpServer {
                         .ok_or_else(|| ServerError::Handler("project_id is required".to_string()))?;
 
                     let config = Config::from_env();
-                    let client = GitLabClient::new(&config.gitlab_url, &config.gitlab_token)
+                    let client = GitLabClient::shared(&config)
                         .map_err(|e| ServerError::Handler(format!("Failed to create client: {}", e)))?;
 
                     let path = format!("projects/{}", urlencoding::encode(project_id));
@@ -368,8 +408,8 @@ impl GitLabMcpServer {
                         }
                     }
                 })
-            },
-        )?;
+            }),
+        ));
 
         // Register list_projects tool
         let list_projects_tool = Tool {
@@ -379,7 +419,7 @@ impl GitLabMcpServer {
             },
             icons: Icons::default(),
             description: Some("List projects accessible by the current user".to_string()),
-            input_schema: json!({
+            input_schema: with_pagination(json!({
                 "type": "object",
                 "properties": {
                     "search": {
@@ -403,16 +443,16 @@ impl GitLabMcpServer {
                         "description": "Limit by projects that the current user is a member of"
                     }
                 }
-            }),
+            })),
             output_schema: None,
             annotations: None,
             execution: None,
             meta: None,
         };
 
-        server.register_tool(
+        tools.push((
             list_projects_tool,
-            |arguments: Option<serde_json::Value>, _context: RequestContext| {
+            Box::new(|arguments: Option<serde_json::Value>, _context: RequestContext| {
                 Box::pin(async move {
                     let args = arguments.and_then(|a| a.as_object().cloned()).unwrap_or_default();
 
@@ -422,17 +462,13 @@ impl GitLabMcpServer {
                     let membership = args.get("membership").and_then(|v| v.as_bool()).unwrap_or(true);
 
                     let config = Config::from_env();
-                    let client = GitLabClient::new(&config.gitlab_url, &config.gitlab_token)
+                    let client = GitLabClient::shared(&config)
                         .map_err(|e| ServerError::Handler(format!("Failed to create client: {}", e)))?;
 
                     tracing::info!("Listing projects: per_page={}, page={}, membership={}", per_page, page, membership);
 
                     // Build query parameters using get_with_query
-                    let mut query = vec![
-                        ("per_page".to_string(), per_page.to_string()),
-                        ("page".to_string(), page.to_string()),
-                        ("membership".to_string(), membership.to_string()),
-                    ];
+                    let mut query = vec![("membership".to_string(), membership.to_string())];
                     if let Some(s) = search {
                         query.push(("search".to_string(), s.to_string()));
                         query.push(("order_by".to_string(), "last_activity_at".to_string()));
@@ -457,7 +493,7 @@ impl GitLabMcpServer {
                         default_branch: Option<String>,
                     }
 
-                    match client.get_with_query::<Vec<Project>>("projects", &query).await {
+                    match fetch_list::<Project>(&client, "projects", query, &args).await {
                         Ok(projects) => {
                             tracing::info!("Successfully retrieved {} projects", projects.len());
                             let json = serde_json::to_string_pretty(&projects)
@@ -477,8 +513,8 @@ impl GitLabMcpServer {
                         }
                     }
                 })
-            },
-        )?;
+            }),
+        ));
 
         // Register create_project tool
         let create_project_tool = Tool {
@@ -524,14 +560,17 @@ impl GitLabMcpServer {
                 "required": ["name"]
             }),
             output_schema: None,
-            annotations: None,
+            annotations: Some(ToolAnnotations {
+                idempotent_hint: Some(false),
+                ..Default::default()
+            }),
             execution: None,
             meta: None,
         };
 
-        server.register_tool(
+        tools.push((
             create_project_tool,
-            |arguments: Option<serde_json::Value>, _context: RequestContext| {
+            Box::new(|arguments: Option<serde_json::Value>, _context: RequestContext| {
                 Box::pin(async move {
                     tracing::info!("create_project tool called");
 
@@ -570,9 +609,7 @@ impl GitLabMcpServer {
                         }
                     }
 
-                    let config = Config::from_env();
-                    let client = GitLabClient::new(&config.gitlab_url, &config.gitlab_token)
-                        .map_err(|e| ServerError::Handler(format!("Failed to create client: {}", e)))?;
+                    let client = client_from_env()?;
 
                     #[derive(serde::Serialize)]
                     struct CreateProjectRequest {
@@ -601,6 +638,11 @@ impl GitLabMcpServer {
                         default_branch,
                     };
 
+                    if client.is_dry_run() {
+                        tracing::info!("Dry run: not creating project {}", name);
+                        return Ok(dry_run_result("POST", "projects", serde_json::to_value(&request).ok()));
+                    }
+
                     #[derive(serde::Deserialize)]
                     struct GitLabProject {
                         id: u64,
@@ -652,8 +694,8 @@ impl GitLabMcpServer {
                         }
                     }
                 })
-            },
-        )?;
+            }),
+        ));
 
         // === Issue Tools ===
 
@@ -665,11 +707,12 @@ impl GitLabMcpServer {
             },
             icons: Icons::default(),
             description: Some("List issues for a project".to_string()),
-            input_schema: json!({
+            input_schema: with_pagination(json!({
                 "type": "object",
                 "properties": {
                     "project_id": {
                         "type": "string",
+                        "x-gitlab-project-id": true,
                         "description": "Project ID or URL-encoded path"
                     },
                     "state": {
@@ -679,7 +722,7 @@ impl GitLabMcpServer {
                     },
                     "labels": {
                         "type": "string",
-                        "description": "Comma-separated list of label names"
+                        "description": "Comma-separated list of label names (not label IDs); use list_labels to look names up"
                     },
                     "per_page": {
                         "type": "integer",
@@ -691,16 +734,16 @@ impl GitLabMcpServer {
                     }
                 },
                 "required": ["project_id"]
-            }),
+            })),
             output_schema: None,
             annotations: None,
             execution: None,
             meta: None,
         };
 
-        server.register_tool(
+        tools.push((
             list_issues_tool,
-            |arguments: Option<serde_json::Value>, _context: RequestContext| {
+            Box::new(|arguments: Option<serde_json::Value>, _context: RequestContext| {
                 Box::pin(async move {
                     let args = arguments.as_ref().and_then(|a| a.as_object());
                     let project_id = args
@@ -710,17 +753,16 @@ impl GitLabMcpServer {
 
                     let state = args.and_then(|a| a.get("state")).and_then(|v| v.as_str()).unwrap_or("opened");
                     let labels = args.and_then(|a| a.get("labels")).and_then(|v| v.as_str());
-                    let per_page = args.and_then(|a| a.get("per_page")).and_then(|v| v.as_u64()).unwrap_or(20);
-                    let page = args.and_then(|a| a.get("page")).and_then(|v| v.as_u64()).unwrap_or(1);
 
                     let config = Config::from_env();
-                    let client = GitLabClient::new(&config.gitlab_url, &config.gitlab_token)
+                    let client = GitLabClient::shared(&config)
                         .map_err(|e| ServerError::Handler(format!("Failed to create client: {}", e)))?;
 
                     let encoded_project = urlencoding::encode(project_id);
-                    let mut path = format!("projects/{}/issues?per_page={}&page={}&state={}", encoded_project, per_page, page, state);
+                    let path = format!("projects/{}/issues", encoded_project);
+                    let mut query = vec![("state".to_string(), state.to_string())];
                     if let Some(l) = labels {
-                        path.push_str(&format!("&labels={}", urlencoding::encode(l)));
+                        query.push(("labels".to_string(), l.to_string()));
                     }
 
                     #[derive(serde::Deserialize)]
@@ -737,7 +779,7 @@ impl GitLabMcpServer {
                         labels: Vec<String>,
                     }
 
-                    match client.get::<Vec<Issue>>(&path).await {
+                    match fetch_list::<Issue>(&client, &path, query, &args.cloned().unwrap_or_default()).await {
                         Ok(issues) => {
                             let mut output = vec![];
                             output.push(format!("## Issues ({} found)\n", issues.len()));
@@ -782,8 +824,8 @@ impl GitLabMcpServer {
                         }
                     }
                 })
-            },
-        )?;
+            }),
+        ));
 
         // Register get_issue tool
         let get_issue_tool = Tool {
@@ -798,6 +840,7 @@ impl GitLabMcpServer {
                 "properties": {
                     "project_id": {
                         "type": "string",
+                        "x-gitlab-project-id": true,
                         "description": "Project ID or URL-encoded path"
                     },
                     "issue_iid": {
@@ -813,9 +856,9 @@ impl GitLabMcpServer {
             meta: None,
         };
 
-        server.register_tool(
+        tools.push((
             get_issue_tool,
-            |arguments: Option<serde_json::Value>, _context: RequestContext| {
+            Box::new(|arguments: Option<serde_json::Value>, _context: RequestContext| {
                 Box::pin(async move {
                     let args = arguments.as_ref().and_then(|a| a.as_object());
                     let project_id = args
@@ -828,7 +871,7 @@ impl GitLabMcpServer {
                         .ok_or_else(|| ServerError::Handler("issue_iid is required".to_string()))?;
 
                     let config = Config::from_env();
-                    let client = GitLabClient::new(&config.gitlab_url, &config.gitlab_token)
+                    let client = GitLabClient::shared(&config)
                         .map_err(|e| ServerError::Handler(format!("Failed to create client: {}", e)))?;
 
                     let encoded_project = urlencoding::encode(project_id);
@@ -899,30 +942,81 @@ impl GitLabMcpServer {
                         }
                     }
                 })
+            }),
+        ));
+
+        // === Label Tools ===
+        // Register get_issue_statistics tool
+        let issue_statistics_tool = Tool {
+            base: BaseMetadata {
+                name: "get_issue_statistics".to_string(),
+                title: Some("Get Issue Statistics".to_string()),
             },
-        )?;
+            icons: Icons::default(),
+            description: Some("Get issue counts (total, opened, closed) for a project, returned as structured content".to_string()),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "project_id": {
+                        "type": "string",
+                        "x-gitlab-project-id": true,
+                        "description": "Project ID or URL-encoded path"
+                    },
+                    "labels": {
+                        "type": "string",
+                        "description": "Comma-separated list of label names to filter by"
+                    },
+                    "milestone": {
+                        "type": "string",
+                        "description": "Milestone title"
+                    },
+                    "assignee_username": {
+                        "type": "string",
+                        "description": "Assignee username"
+                    },
+                    "author_username": {
+                        "type": "string",
+                        "description": "Author username"
+                    },
+                    "search": {
+                        "type": "string",
+                        "description": "Search issues by title and description"
+                    }
+                },
+                "required": ["project_id"]
+            }),
+            output_schema: None,
+            annotations: None,
+            execution: None,
+            meta: None,
+        };
 
-        // === Merge Request Tools ===
+        tools.push((
+            issue_statistics_tool,
+            Box::new(|arguments: Option<serde_json::Value>, _context: RequestContext| {
+                Box::pin(async move { labels::get_issue_statistics(client_from_env()?, arguments).await })
+            }),
+        ));
 
-        // Register list_merge_requests tool
-        let list_mrs_tool = Tool {
+        // Register list_labels tool
+        let list_labels_tool = Tool {
             base: BaseMetadata {
-                name: "list_merge_requests".to_string(),
-                title: Some("List Merge Requests".to_string()),
+                name: "list_labels".to_string(),
+                title: Some("List Labels".to_string()),
             },
             icons: Icons::default(),
-            description: Some("List merge requests for a project".to_string()),
-            input_schema: json!({
+            description: Some("List labels of a project with issue and merge request counts".to_string()),
+            input_schema: with_pagination(json!({
                 "type": "object",
                 "properties": {
                     "project_id": {
                         "type": "string",
+                        "x-gitlab-project-id": true,
                         "description": "Project ID or URL-encoded path"
                     },
-                    "state": {
+                    "search": {
                         "type": "string",
-                        "description": "MR state (opened, closed, merged, all)",
-                        "enum": ["opened", "closed", "merged", "all"]
+                        "description": "Filter labels by keyword"
                     },
                     "per_page": {
                         "type": "integer",
@@ -934,106 +1028,210 @@ impl GitLabMcpServer {
                     }
                 },
                 "required": ["project_id"]
-            }),
+            })),
             output_schema: None,
             annotations: None,
             execution: None,
             meta: None,
         };
 
-        server.register_tool(
-            list_mrs_tool,
-            |arguments: Option<serde_json::Value>, _context: RequestContext| {
-                Box::pin(async move {
-                    let args = arguments.as_ref().and_then(|a| a.as_object());
-                    let project_id = args
-                        .and_then(|a| a.get("project_id"))
-                        .and_then(|v| v.as_str())
-                        .ok_or_else(|| ServerError::Handler("project_id is required".to_string()))?;
-
-                    let state = args.and_then(|a| a.get("state")).and_then(|v| v.as_str()).unwrap_or("opened");
-                    let per_page = args.and_then(|a| a.get("per_page")).and_then(|v| v.as_u64()).unwrap_or(20);
-                    let page = args.and_then(|a| a.get("page")).and_then(|v| v.as_u64()).unwrap_or(1);
+        tools.push((
+            list_labels_tool,
+            Box::new(|arguments: Option<serde_json::Value>, _context: RequestContext| {
+                Box::pin(async move { labels::list_labels(client_from_env()?, arguments).await })
+            }),
+        ));
 
-                    let config = Config::from_env();
-                    let client = GitLabClient::new(&config.gitlab_url, &config.gitlab_token)
-                        .map_err(|e| ServerError::Handler(format!("Failed to create client: {}", e)))?;
+        // Register create_label tool
+        let create_label_tool = Tool {
+            base: BaseMetadata {
+                name: "create_label".to_string(),
+                title: Some("Create Label".to_string()),
+            },
+            icons: Icons::default(),
+            description: Some("Create a new project label".to_string()),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "project_id": {
+                        "type": "string",
+                        "x-gitlab-project-id": true,
+                        "description": "Project ID or URL-encoded path"
+                    },
+                    "name": {
+                        "type": "string",
+                        "description": "Label name"
+                    },
+                    "color": {
+                        "type": "string",
+                        "description": "Label color as hex code, e.g. #FF0000",
+                        "pattern": "^#([0-9A-Fa-f]{3}|[0-9A-Fa-f]{6})$"
+                    },
+                    "description": {
+                        "type": "string",
+                        "description": "Label description"
+                    }
+                },
+                "required": ["project_id", "name", "color"]
+            }),
+            output_schema: None,
+            annotations: Some(ToolAnnotations {
+                idempotent_hint: Some(false),
+                ..Default::default()
+            }),
+            execution: None,
+            meta: None,
+        };
 
-                    let encoded_project = urlencoding::encode(project_id);
-                    let path = format!("projects/{}/merge_requests?per_page={}&page={}&state={}", encoded_project, per_page, page, state);
+        tools.push((
+            create_label_tool,
+            Box::new(|arguments: Option<serde_json::Value>, _context: RequestContext| {
+                Box::pin(async move { labels::create_label(client_from_env()?, arguments).await })
+            }),
+        ));
 
-                    #[derive(serde::Deserialize)]
-                    struct MergeRequest {
-                        iid: u64,
-                        title: String,
-                        state: String,
-                        web_url: String,
-                        created_at: String,
-                        #[serde(rename = "updated_at")]
-                        _updated_at: String,
-                        author: serde_json::Value,
-                        source_branch: String,
-                        target_branch: String,
-                        merge_status: Option<String>,
+        // Register update_label tool
+        let update_label_tool = Tool {
+            base: BaseMetadata {
+                name: "update_label".to_string(),
+                title: Some("Update Label".to_string()),
+            },
+            icons: Icons::default(),
+            description: Some("Update the name, color or description of a project label".to_string()),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "project_id": {
+                        "type": "string",
+                        "x-gitlab-project-id": true,
+                        "description": "Project ID or URL-encoded path"
+                    },
+                    "label_id": {
+                        "type": ["integer", "string"],
+                        "description": "Label ID or label name"
+                    },
+                    "new_name": {
+                        "type": "string",
+                        "description": "New label name"
+                    },
+                    "color": {
+                        "type": "string",
+                        "description": "New label color as hex code, e.g. #FF0000"
+                    },
+                    "description": {
+                        "type": "string",
+                        "description": "New label description"
                     }
+                },
+                "required": ["project_id", "label_id"]
+            }),
+            output_schema: None,
+            annotations: None,
+            execution: None,
+            meta: None,
+        };
 
-                    match client.get::<Vec<MergeRequest>>(&path).await {
-                        Ok(mrs) => {
-                            let mut output = vec![];
-                            output.push(format!("## Merge Requests ({} found)\n", mrs.len()));
+        tools.push((
+            update_label_tool,
+            Box::new(|arguments: Option<serde_json::Value>, _context: RequestContext| {
+                Box::pin(async move { labels::update_label(client_from_env()?, arguments).await })
+            }),
+        ));
 
-                            for mr in mrs {
-                                let author = mr.author.get("name").and_then(|v| v.as_str()).unwrap_or("Unknown");
-                                let status = mr.merge_status.as_deref().unwrap_or("unknown");
+        // Register delete_label tool
+        let delete_label_tool = Tool {
+            base: BaseMetadata {
+                name: "delete_label".to_string(),
+                title: Some("Delete Label".to_string()),
+            },
+            icons: Icons::default(),
+            description: Some("Delete a project label".to_string()),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "project_id": {
+                        "type": "string",
+                        "x-gitlab-project-id": true,
+                        "description": "Project ID or URL-encoded path"
+                    },
+                    "label_id": {
+                        "type": ["integer", "string"],
+                        "description": "Label ID or label name"
+                    }
+                },
+                "required": ["project_id", "label_id"]
+            }),
+            output_schema: None,
+            annotations: None,
+            execution: None,
+            meta: None,
+        };
 
-                                output.push(format!("### !{} - {}", mr.iid, mr.title));
-                                output.push(format!("**State:** {}", mr.state));
-                                output.push(format!("**Author:** {}", author));
-                                output.push(format!("**Branch:** {} → {}", mr.source_branch, mr.target_branch));
-                                output.push(format!("**Merge Status:** {}", status));
-                                output.push(format!("**Created:** {}", mr.created_at));
-                                output.push(format!("**URL:** {}", mr.web_url));
-                                output.push(String::new());
-                            }
+        tools.push((
+            delete_label_tool,
+            Box::new(|arguments: Option<serde_json::Value>, _context: RequestContext| {
+                Box::pin(async move { labels::delete_label(client_from_env()?, arguments).await })
+            }),
+        ));
 
-                            Ok(CallToolResult {
-                                content: vec![ContentBlock::Text(TextContent::new(output.join("\n")))],
-                                ..Default::default()
-                            })
-                        }
-                        Err(e) => {
-                            Ok(CallToolResult {
-                                content: vec![ContentBlock::Text(TextContent::new(format!("Error listing MRs: {}", e)))],
-                                is_error: Some(true),
-                                ..Default::default()
-                            })
-                        }
-                    }
-                })
+        // Register subscribe_to_label tool
+        let subscribe_label_tool = Tool {
+            base: BaseMetadata {
+                name: "subscribe_to_label".to_string(),
+                title: Some("Subscribe to Label".to_string()),
             },
-        )?;
+            icons: Icons::default(),
+            description: Some("Subscribe the current user to notifications for a label".to_string()),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "project_id": {
+                        "type": "string",
+                        "x-gitlab-project-id": true,
+                        "description": "Project ID or URL-encoded path"
+                    },
+                    "label_id": {
+                        "type": ["integer", "string"],
+                        "description": "Label ID or label name"
+                    }
+                },
+                "required": ["project_id", "label_id"]
+            }),
+            output_schema: None,
+            annotations: None,
+            execution: None,
+            meta: None,
+        };
 
-        // Register get_merge_request tool
-        let get_mr_tool = Tool {
+        tools.push((
+            subscribe_label_tool,
+            Box::new(|arguments: Option<serde_json::Value>, _context: RequestContext| {
+                Box::pin(async move { labels::subscribe_to_label(client_from_env()?, arguments).await })
+            }),
+        ));
+
+        // Register unsubscribe_from_label tool
+        let unsubscribe_label_tool = Tool {
             base: BaseMetadata {
-                name: "get_merge_request".to_string(),
-                title: Some("Get Merge Request Details".to_string()),
+                name: "unsubscribe_from_label".to_string(),
+                title: Some("Unsubscribe from Label".to_string()),
             },
             icons: Icons::default(),
-            description: Some("Get detailed information about a merge request".to_string()),
+            description: Some("Unsubscribe the current user from notifications for a label".to_string()),
             input_schema: json!({
                 "type": "object",
                 "properties": {
                     "project_id": {
                         "type": "string",
+                        "x-gitlab-project-id": true,
                         "description": "Project ID or URL-encoded path"
                     },
-                    "mr_iid": {
-                        "type": "integer",
-                        "description": "Merge Request IID"
+                    "label_id": {
+                        "type": ["integer", "string"],
+                        "description": "Label ID or label name"
                     }
                 },
-                "required": ["project_id", "mr_iid"]
+                "required": ["project_id", "label_id"]
             }),
             output_schema: None,
             annotations: None,
@@ -1041,37 +1239,614 @@ impl GitLabMcpServer {
             meta: None,
         };
 
-        server.register_tool(
-            get_mr_tool,
-            |arguments: Option<serde_json::Value>, _context: RequestContext| {
-                Box::pin(async move {
-                    let args = arguments.as_ref().and_then(|a| a.as_object());
-                    let project_id = args
-                        .and_then(|a| a.get("project_id"))
-                        .and_then(|v| v.as_str())
-                        .ok_or_else(|| ServerError::Handler("project_id is required".to_string()))?;
-                    let mr_iid = args
-                        .and_then(|a| a.get("mr_iid"))
-                        .and_then(|v| v.as_u64())
-                        .ok_or_else(|| ServerError::Handler("mr_iid is required".to_string()))?;
-
-                    let config = Config::from_env();
-                    let client = GitLabClient::new(&config.gitlab_url, &config.gitlab_token)
-                        .map_err(|e| ServerError::Handler(format!("Failed to create client: {}", e)))?;
+        tools.push((
+            unsubscribe_label_tool,
+            Box::new(|arguments: Option<serde_json::Value>, _context: RequestContext| {
+                Box::pin(async move { labels::unsubscribe_from_label(client_from_env()?, arguments).await })
+            }),
+        ));
 
-                    let encoded_project = urlencoding::encode(project_id);
-                    let path = format!("projects/{}/merge_requests/{}", encoded_project, mr_iid);
+        // === Milestone Tools ===
 
-                    #[derive(serde::Deserialize)]
-                    struct MergeRequest {
-                        iid: u64,
-                        title: String,
-                        description: Option<String>,
-                        state: String,
-                        web_url: String,
-                        created_at: String,
-                        #[serde(rename = "updated_at")]
-                        _updated_at: String,
+        // Register list_milestones tool
+        let list_milestones_tool = Tool {
+            base: BaseMetadata {
+                name: "list_milestones".to_string(),
+                title: Some("List Milestones".to_string()),
+            },
+            icons: Icons::default(),
+            description: Some("List milestones of a project".to_string()),
+            input_schema: with_pagination(json!({
+                "type": "object",
+                "properties": {
+                    "project_id": {
+                        "type": "string",
+                        "x-gitlab-project-id": true,
+                        "description": "Project ID or URL-encoded path"
+                    },
+                    "state": {
+                        "type": "string",
+                        "description": "Milestone state (active, closed)",
+                        "enum": ["active", "closed"]
+                    },
+                    "search": {
+                        "type": "string",
+                        "description": "Search milestones by title"
+                    },
+                    "per_page": {
+                        "type": "integer",
+                        "description": "Number per page (default: 20)"
+                    },
+                    "page": {
+                        "type": "integer",
+                        "description": "Page number (default: 1)"
+                    }
+                },
+                "required": ["project_id"]
+            })),
+            output_schema: None,
+            annotations: None,
+            execution: None,
+            meta: None,
+        };
+
+        tools.push((
+            list_milestones_tool,
+            Box::new(|arguments: Option<serde_json::Value>, _context: RequestContext| {
+                Box::pin(async move { milestones::list_milestones(client_from_env()?, arguments).await })
+            }),
+        ));
+
+        // Register get_milestone tool
+        let get_milestone_tool = Tool {
+            base: BaseMetadata {
+                name: "get_milestone".to_string(),
+                title: Some("Get Milestone".to_string()),
+            },
+            icons: Icons::default(),
+            description: Some("Get details of a project milestone".to_string()),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "project_id": {
+                        "type": "string",
+                        "x-gitlab-project-id": true,
+                        "description": "Project ID or URL-encoded path"
+                    },
+                    "milestone_id": {
+                        "type": "integer",
+                        "description": "Milestone ID (not IID)"
+                    }
+                },
+                "required": ["project_id", "milestone_id"]
+            }),
+            output_schema: None,
+            annotations: None,
+            execution: None,
+            meta: None,
+        };
+
+        tools.push((
+            get_milestone_tool,
+            Box::new(|arguments: Option<serde_json::Value>, _context: RequestContext| {
+                Box::pin(async move { milestones::get_milestone(client_from_env()?, arguments).await })
+            }),
+        ));
+
+        // Register create_milestone tool
+        let create_milestone_tool = Tool {
+            base: BaseMetadata {
+                name: "create_milestone".to_string(),
+                title: Some("Create Milestone".to_string()),
+            },
+            icons: Icons::default(),
+            description: Some("Create a new project milestone".to_string()),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "project_id": {
+                        "type": "string",
+                        "x-gitlab-project-id": true,
+                        "description": "Project ID or URL-encoded path"
+                    },
+                    "title": {
+                        "type": "string",
+                        "description": "Milestone title"
+                    },
+                    "description": {
+                        "type": "string",
+                        "description": "Milestone description"
+                    },
+                    "start_date": {
+                        "type": "string",
+                        "description": "Start date (ISO-8601, e.g. 2026-01-31)"
+                    },
+                    "due_date": {
+                        "type": "string",
+                        "description": "Due date (ISO-8601, e.g. 2026-03-31)"
+                    }
+                },
+                "required": ["project_id", "title"]
+            }),
+            output_schema: None,
+            annotations: Some(ToolAnnotations {
+                idempotent_hint: Some(false),
+                ..Default::default()
+            }),
+            execution: None,
+            meta: None,
+        };
+
+        tools.push((
+            create_milestone_tool,
+            Box::new(|arguments: Option<serde_json::Value>, _context: RequestContext| {
+                Box::pin(async move { milestones::create_milestone(client_from_env()?, arguments).await })
+            }),
+        ));
+
+        // Register update_milestone tool
+        let update_milestone_tool = Tool {
+            base: BaseMetadata {
+                name: "update_milestone".to_string(),
+                title: Some("Update Milestone".to_string()),
+            },
+            icons: Icons::default(),
+            description: Some("Update the title, description, dates or state of a project milestone".to_string()),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "project_id": {
+                        "type": "string",
+                        "x-gitlab-project-id": true,
+                        "description": "Project ID or URL-encoded path"
+                    },
+                    "milestone_id": {
+                        "type": "integer",
+                        "description": "Milestone ID (not IID)"
+                    },
+                    "title": {
+                        "type": "string",
+                        "description": "New milestone title"
+                    },
+                    "description": {
+                        "type": "string",
+                        "description": "New milestone description"
+                    },
+                    "start_date": {
+                        "type": "string",
+                        "description": "Start date (ISO-8601, e.g. 2026-01-31)"
+                    },
+                    "due_date": {
+                        "type": "string",
+                        "description": "Due date (ISO-8601, e.g. 2026-03-31)"
+                    },
+                    "state_event": {
+                        "type": "string",
+                        "description": "State transition (close, activate)",
+                        "enum": ["close", "activate"]
+                    }
+                },
+                "required": ["project_id", "milestone_id"]
+            }),
+            output_schema: None,
+            annotations: None,
+            execution: None,
+            meta: None,
+        };
+
+        tools.push((
+            update_milestone_tool,
+            Box::new(|arguments: Option<serde_json::Value>, _context: RequestContext| {
+                Box::pin(async move { milestones::update_milestone(client_from_env()?, arguments).await })
+            }),
+        ));
+
+        // Register close_milestone tool
+        let close_milestone_tool = Tool {
+            base: BaseMetadata {
+                name: "close_milestone".to_string(),
+                title: Some("Close Milestone".to_string()),
+            },
+            icons: Icons::default(),
+            description: Some("Close a project milestone".to_string()),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "project_id": {
+                        "type": "string",
+                        "x-gitlab-project-id": true,
+                        "description": "Project ID or URL-encoded path"
+                    },
+                    "milestone_id": {
+                        "type": "integer",
+                        "description": "Milestone ID (not IID)"
+                    }
+                },
+                "required": ["project_id", "milestone_id"]
+            }),
+            output_schema: None,
+            annotations: None,
+            execution: None,
+            meta: None,
+        };
+
+        tools.push((
+            close_milestone_tool,
+            Box::new(|arguments: Option<serde_json::Value>, _context: RequestContext| {
+                Box::pin(async move { milestones::close_milestone(client_from_env()?, arguments).await })
+            }),
+        ));
+
+        // Register delete_milestone tool
+        let delete_milestone_tool = Tool {
+            base: BaseMetadata {
+                name: "delete_milestone".to_string(),
+                title: Some("Delete Milestone".to_string()),
+            },
+            icons: Icons::default(),
+            description: Some("Delete a project milestone".to_string()),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "project_id": {
+                        "type": "string",
+                        "x-gitlab-project-id": true,
+                        "description": "Project ID or URL-encoded path"
+                    },
+                    "milestone_id": {
+                        "type": "integer",
+                        "description": "Milestone ID (not IID)"
+                    }
+                },
+                "required": ["project_id", "milestone_id"]
+            }),
+            output_schema: None,
+            annotations: None,
+            execution: None,
+            meta: None,
+        };
+
+        tools.push((
+            delete_milestone_tool,
+            Box::new(|arguments: Option<serde_json::Value>, _context: RequestContext| {
+                Box::pin(async move { milestones::delete_milestone(client_from_env()?, arguments).await })
+            }),
+        ));
+
+        // Register list_milestone_issues tool
+        let list_milestone_issues_tool = Tool {
+            base: BaseMetadata {
+                name: "list_milestone_issues".to_string(),
+                title: Some("List Milestone Issues".to_string()),
+            },
+            icons: Icons::default(),
+            description: Some("List issues assigned to a milestone with their IID, title and assignee".to_string()),
+            input_schema: with_pagination(json!({
+                "type": "object",
+                "properties": {
+                    "project_id": {
+                        "type": "string",
+                        "x-gitlab-project-id": true,
+                        "description": "Project ID or URL-encoded path"
+                    },
+                    "milestone_id": {
+                        "type": "integer",
+                        "description": "Milestone ID (not IID)"
+                    },
+                    "state": {
+                        "type": "string",
+                        "description": "Issue state (opened, closed, all)",
+                        "enum": ["opened", "closed", "all"]
+                    },
+                    "per_page": {
+                        "type": "integer",
+                        "description": "Number per page (default: 20)"
+                    },
+                    "page": {
+                        "type": "integer",
+                        "description": "Page number (default: 1)"
+                    }
+                },
+                "required": ["project_id", "milestone_id"]
+            })),
+            output_schema: None,
+            annotations: None,
+            execution: None,
+            meta: None,
+        };
+
+        tools.push((
+            list_milestone_issues_tool,
+            Box::new(|arguments: Option<serde_json::Value>, _context: RequestContext| {
+                Box::pin(async move { milestones::list_milestone_issues(client_from_env()?, arguments).await })
+            }),
+        ));
+
+        // === Issue Board Tools ===
+
+        // Register list_boards tool
+        let list_boards_tool = Tool {
+            base: BaseMetadata {
+                name: "list_boards".to_string(),
+                title: Some("List Issue Boards".to_string()),
+            },
+            icons: Icons::default(),
+            description: Some("List issue boards of a project with the label, milestone or assignee behind each list".to_string()),
+            input_schema: with_pagination(json!({
+                "type": "object",
+                "properties": {
+                    "project_id": {
+                        "type": "string",
+                        "x-gitlab-project-id": true,
+                        "description": "Project ID or URL-encoded path"
+                    }
+                },
+                "required": ["project_id"]
+            })),
+            output_schema: None,
+            annotations: None,
+            execution: None,
+            meta: None,
+        };
+
+        tools.push((
+            list_boards_tool,
+            Box::new(|arguments: Option<serde_json::Value>, _context: RequestContext| {
+                Box::pin(async move { boards::list_boards(client_from_env()?, arguments).await })
+            }),
+        ));
+
+        // Register get_board tool
+        let get_board_tool = Tool {
+            base: BaseMetadata {
+                name: "get_board".to_string(),
+                title: Some("Get Issue Board".to_string()),
+            },
+            icons: Icons::default(),
+            description: Some("Get an issue board and the definitions of its lists".to_string()),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "project_id": {
+                        "type": "string",
+                        "x-gitlab-project-id": true,
+                        "description": "Project ID or URL-encoded path"
+                    },
+                    "board_id": {
+                        "type": "integer",
+                        "description": "Issue board ID"
+                    }
+                },
+                "required": ["project_id", "board_id"]
+            }),
+            output_schema: None,
+            annotations: None,
+            execution: None,
+            meta: None,
+        };
+
+        tools.push((
+            get_board_tool,
+            Box::new(|arguments: Option<serde_json::Value>, _context: RequestContext| {
+                Box::pin(async move { boards::get_board(client_from_env()?, arguments).await })
+            }),
+        ));
+
+        // Register move_issue_to_list tool
+        let move_issue_to_list_tool = Tool {
+            base: BaseMetadata {
+                name: "move_issue_to_list".to_string(),
+                title: Some("Move Issue To Board List".to_string()),
+            },
+            icons: Icons::default(),
+            description: Some("Move an issue to another list of a label-based issue board by swapping the list labels, keeping unrelated labels".to_string()),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "project_id": {
+                        "type": "string",
+                        "x-gitlab-project-id": true,
+                        "description": "Project ID or URL-encoded path"
+                    },
+                    "board_id": {
+                        "type": "integer",
+                        "description": "Issue board ID"
+                    },
+                    "list_id": {
+                        "type": "integer",
+                        "description": "ID of the destination board list"
+                    },
+                    "issue_iid": {
+                        "type": "integer",
+                        "description": "Issue IID"
+                    }
+                },
+                "required": ["project_id", "board_id", "list_id", "issue_iid"]
+            }),
+            output_schema: None,
+            annotations: None,
+            execution: None,
+            meta: None,
+        };
+
+        tools.push((
+            move_issue_to_list_tool,
+            Box::new(|arguments: Option<serde_json::Value>, _context: RequestContext| {
+                Box::pin(async move { boards::move_issue_to_list(client_from_env()?, arguments).await })
+            }),
+        ));
+
+        // === Merge Request Tools ===
+
+        // Register list_merge_requests tool
+        let list_mrs_tool = Tool {
+            base: BaseMetadata {
+                name: "list_merge_requests".to_string(),
+                title: Some("List Merge Requests".to_string()),
+            },
+            icons: Icons::default(),
+            description: Some("List merge requests for a project".to_string()),
+            input_schema: with_pagination(json!({
+                "type": "object",
+                "properties": {
+                    "project_id": {
+                        "type": "string",
+                        "x-gitlab-project-id": true,
+                        "description": "Project ID or URL-encoded path"
+                    },
+                    "state": {
+                        "type": "string",
+                        "description": "MR state (opened, closed, merged, all)",
+                        "enum": ["opened", "closed", "merged", "all"]
+                    },
+                    "per_page": {
+                        "type": "integer",
+                        "description": "Number per page (default: 20)"
+                    },
+                    "page": {
+                        "type": "integer",
+                        "description": "Page number"
+                    }
+                },
+                "required": ["project_id"]
+            })),
+            output_schema: None,
+            annotations: None,
+            execution: None,
+            meta: None,
+        };
+
+        tools.push((
+            list_mrs_tool,
+            Box::new(|arguments: Option<serde_json::Value>, _context: RequestContext| {
+                Box::pin(async move {
+                    let args = arguments.as_ref().and_then(|a| a.as_object());
+                    let project_id = args
+                        .and_then(|a| a.get("project_id"))
+                        .and_then(|v| v.as_str())
+                        .ok_or_else(|| ServerError::Handler("project_id is required".to_string()))?;
+
+                    let state = args.and_then(|a| a.get("state")).and_then(|v| v.as_str()).unwrap_or("opened");
+
+                    let config = Config::from_env();
+                    let client = GitLabClient::shared(&config)
+                        .map_err(|e| ServerError::Handler(format!("Failed to create client: {}", e)))?;
+
+                    let encoded_project = urlencoding::encode(project_id);
+                    let path = format!("projects/{}/merge_requests", encoded_project);
+                    let query = vec![("state".to_string(), state.to_string())];
+
+                    #[derive(serde::Deserialize)]
+                    struct MergeRequest {
+                        iid: u64,
+                        title: String,
+                        state: String,
+                        web_url: String,
+                        created_at: String,
+                        #[serde(rename = "updated_at")]
+                        _updated_at: String,
+                        author: serde_json::Value,
+                        source_branch: String,
+                        target_branch: String,
+                        merge_status: Option<String>,
+                    }
+
+                    match fetch_list::<MergeRequest>(&client, &path, query, &args.cloned().unwrap_or_default()).await {
+                        Ok(mrs) => {
+                            let mut output = vec![];
+                            output.push(format!("## Merge Requests ({} found)\n", mrs.len()));
+
+                            for mr in mrs {
+                                let author = mr.author.get("name").and_then(|v| v.as_str()).unwrap_or("Unknown");
+                                let status = mr.merge_status.as_deref().unwrap_or("unknown");
+
+                                output.push(format!("### !{} - {}", mr.iid, mr.title));
+                                output.push(format!("**State:** {}", mr.state));
+                                output.push(format!("**Author:** {}", author));
+                                output.push(format!("**Branch:** {} → {}", mr.source_branch, mr.target_branch));
+                                output.push(format!("**Merge Status:** {}", status));
+                                output.push(format!("**Created:** {}", mr.created_at));
+                                output.push(format!("**URL:** {}", mr.web_url));
+                                output.push(String::new());
+                            }
+
+                            Ok(CallToolResult {
+                                content: vec![ContentBlock::Text(TextContent::new(output.join("\n")))],
+                                ..Default::default()
+                            })
+                        }
+                        Err(e) => {
+                            Ok(CallToolResult {
+                                content: vec![ContentBlock::Text(TextContent::new(format!("Error listing MRs: {}", e)))],
+                                is_error: Some(true),
+                                ..Default::default()
+                            })
+                        }
+                    }
+                })
+            }),
+        ));
+
+        // Register get_merge_request tool
+        let get_mr_tool = Tool {
+            base: BaseMetadata {
+                name: "get_merge_request".to_string(),
+                title: Some("Get Merge Request Details".to_string()),
+            },
+            icons: Icons::default(),
+            description: Some("Get detailed information about a merge request".to_string()),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "project_id": {
+                        "type": "string",
+                        "x-gitlab-project-id": true,
+                        "description": "Project ID or URL-encoded path"
+                    },
+                    "mr_iid": {
+                        "type": "integer",
+                        "description": "Merge Request IID"
+                    }
+                },
+                "required": ["project_id", "mr_iid"]
+            }),
+            output_schema: None,
+            annotations: None,
+            execution: None,
+            meta: None,
+        };
+
+        tools.push((
+            get_mr_tool,
+            Box::new(|arguments: Option<serde_json::Value>, _context: RequestContext| {
+                Box::pin(async move {
+                    let args = arguments.as_ref().and_then(|a| a.as_object());
+                    let project_id = args
+                        .and_then(|a| a.get("project_id"))
+                        .and_then(|v| v.as_str())
+                        .ok_or_else(|| ServerError::Handler("project_id is required".to_string()))?;
+                    let mr_iid = args
+                        .and_then(|a| a.get("mr_iid"))
+                        .and_then(|v| v.as_u64())
+                        .ok_or_else(|| ServerError::Handler("mr_iid is required".to_string()))?;
+
+                    let config = Config::from_env();
+                    let client = GitLabClient::shared(&config)
+                        .map_err(|e| ServerError::Handler(format!("Failed to create client: {}", e)))?;
+
+                    let encoded_project = urlencoding::encode(project_id);
+                    let path = format!("projects/{}/merge_requests/{}", encoded_project, mr_iid);
+
+                    #[derive(serde::Deserialize)]
+                    struct MergeRequest {
+                        iid: u64,
+                        title: String,
+                        description: Option<String>,
+                        state: String,
+                        web_url: String,
+                        created_at: String,
+                        #[serde(rename = "updated_at")]
+                        _updated_at: String,
                         author: serde_json::Value,
                         assignees: Vec<serde_json::Value>,
                         reviewers: Vec<serde_json::Value>,
@@ -1144,8 +1919,8 @@ impl GitLabMcpServer {
                         }
                     }
                 })
-            },
-        )?;
+            }),
+        ));
 
         // === Branch Tools ===
 
@@ -1157,11 +1932,12 @@ impl GitLabMcpServer {
             },
             icons: Icons::default(),
             description: Some("List branches in a project".to_string()),
-            input_schema: json!({
+            input_schema: with_pagination(json!({
                 "type": "object",
                 "properties": {
                     "project_id": {
                         "type": "string",
+                        "x-gitlab-project-id": true,
                         "description": "Project ID or URL-encoded path"
                     },
                     "search": {
@@ -1170,16 +1946,16 @@ impl GitLabMcpServer {
                     }
                 },
                 "required": ["project_id"]
-            }),
+            })),
             output_schema: None,
             annotations: None,
             execution: None,
             meta: None,
         };
 
-        server.register_tool(
+        tools.push((
             list_branches_tool,
-            |arguments: Option<serde_json::Value>, _context: RequestContext| {
+            Box::new(|arguments: Option<serde_json::Value>, _context: RequestContext| {
                 Box::pin(async move {
                     let args = arguments.as_ref().and_then(|a| a.as_object());
                     let project_id = args
@@ -1190,13 +1966,14 @@ impl GitLabMcpServer {
                     let search = args.and_then(|a| a.get("search")).and_then(|v| v.as_str());
 
                     let config = Config::from_env();
-                    let client = GitLabClient::new(&config.gitlab_url, &config.gitlab_token)
+                    let client = GitLabClient::shared(&config)
                         .map_err(|e| ServerError::Handler(format!("Failed to create client: {}", e)))?;
 
                     let encoded_project = urlencoding::encode(project_id);
-                    let mut path = format!("projects/{}/repository/branches", encoded_project);
+                    let path = format!("projects/{}/repository/branches", encoded_project);
+                    let mut query = Vec::new();
                     if let Some(s) = search {
-                        path.push_str(&format!("?search={}", urlencoding::encode(s)));
+                        query.push(("search".to_string(), s.to_string()));
                     }
 
                     #[derive(serde::Deserialize)]
@@ -1208,7 +1985,7 @@ impl GitLabMcpServer {
                         web_url: String,
                     }
 
-                    match client.get::<Vec<Branch>>(&path).await {
+                    match fetch_list::<Branch>(&client, &path, query, &args.cloned().unwrap_or_default()).await {
                         Ok(branches) => {
                             let mut output = vec![];
                             output.push(format!("## Branches ({} found)\n", branches.len()));
@@ -1240,8 +2017,8 @@ impl GitLabMcpServer {
                         }
                     }
                 })
-            },
-        )?;
+            }),
+        ));
 
         // === Commit Tools ===
 
@@ -1253,11 +2030,12 @@ impl GitLabMcpServer {
             },
             icons: Icons::default(),
             description: Some("List commits in a project".to_string()),
-            input_schema: json!({
+            input_schema: with_pagination(json!({
                 "type": "object",
                 "properties": {
                     "project_id": {
                         "type": "string",
+                        "x-gitlab-project-id": true,
                         "description": "Project ID or URL-encoded path"
                     },
                     "ref_name": {
@@ -1270,16 +2048,16 @@ impl GitLabMcpServer {
                     }
                 },
                 "required": ["project_id"]
-            }),
+            })),
             output_schema: None,
             annotations: None,
             execution: None,
             meta: None,
         };
 
-        server.register_tool(
+        tools.push((
             list_commits_tool,
-            |arguments: Option<serde_json::Value>, _context: RequestContext| {
+            Box::new(|arguments: Option<serde_json::Value>, _context: RequestContext| {
                 Box::pin(async move {
                     let args = arguments.as_ref().and_then(|a| a.as_object());
                     let project_id = args
@@ -1288,16 +2066,16 @@ impl GitLabMcpServer {
                         .ok_or_else(|| ServerError::Handler("project_id is required".to_string()))?;
 
                     let ref_name = args.and_then(|a| a.get("ref_name")).and_then(|v| v.as_str());
-                    let per_page = args.and_then(|a| a.get("per_page")).and_then(|v| v.as_u64()).unwrap_or(20);
 
                     let config = Config::from_env();
-                    let client = GitLabClient::new(&config.gitlab_url, &config.gitlab_token)
+                    let client = GitLabClient::shared(&config)
                         .map_err(|e| ServerError::Handler(format!("Failed to create client: {}", e)))?;
 
                     let encoded_project = urlencoding::encode(project_id);
-                    let mut path = format!("projects/{}/repository/commits?per_page={}", encoded_project, per_page);
+                    let path = format!("projects/{}/repository/commits", encoded_project);
+                    let mut query = Vec::new();
                     if let Some(r) = ref_name {
-                        path.push_str(&format!("&ref_name={}", urlencoding::encode(r)));
+                        query.push(("ref_name".to_string(), r.to_string()));
                     }
 
                     #[derive(serde::Deserialize)]
@@ -1312,7 +2090,7 @@ impl GitLabMcpServer {
                         web_url: String,
                     }
 
-                    match client.get::<Vec<Commit>>(&path).await {
+                    match fetch_list::<Commit>(&client, &path, query, &args.cloned().unwrap_or_default()).await {
                         Ok(commits) => {
                             let mut output = vec![];
                             output.push(format!("## Commits ({} found)\n", commits.len()));
@@ -1340,8 +2118,8 @@ impl GitLabMcpServer {
                         }
                     }
                 })
-            },
-        )?;
+            }),
+        ));
 
         // === Pipeline Tools ===
 
@@ -1353,11 +2131,12 @@ impl GitLabMcpServer {
             },
             icons: Icons::default(),
             description: Some("List CI/CD pipelines for a project".to_string()),
-            input_schema: json!({
+            input_schema: with_pagination(json!({
                 "type": "object",
                 "properties": {
                     "project_id": {
                         "type": "string",
+                        "x-gitlab-project-id": true,
                         "description": "Project ID or URL-encoded path"
                     },
                     "status": {
@@ -1374,16 +2153,16 @@ impl GitLabMcpServer {
                     }
                 },
                 "required": ["project_id"]
-            }),
+            })),
             output_schema: None,
             annotations: None,
             execution: None,
             meta: None,
         };
 
-        server.register_tool(
+        tools.push((
             list_pipelines_tool,
-            |arguments: Option<serde_json::Value>, _context: RequestContext| {
+            Box::new(|arguments: Option<serde_json::Value>, _context: RequestContext| {
                 Box::pin(async move {
                     let args = arguments.as_ref().and_then(|a| a.as_object());
                     let project_id = args
@@ -1393,19 +2172,19 @@ impl GitLabMcpServer {
 
                     let status = args.and_then(|a| a.get("status")).and_then(|v| v.as_str());
                     let ref_name = args.and_then(|a| a.get("ref")).and_then(|v| v.as_str());
-                    let per_page = args.and_then(|a| a.get("per_page")).and_then(|v| v.as_u64()).unwrap_or(20);
 
                     let config = Config::from_env();
-                    let client = GitLabClient::new(&config.gitlab_url, &config.gitlab_token)
+                    let client = GitLabClient::shared(&config)
                         .map_err(|e| ServerError::Handler(format!("Failed to create client: {}", e)))?;
 
                     let encoded_project = urlencoding::encode(project_id);
-                    let mut path = format!("projects/{}/pipelines?per_page={}", encoded_project, per_page);
+                    let path = format!("projects/{}/pipelines", encoded_project);
+                    let mut query = Vec::new();
                     if let Some(s) = status {
-                        path.push_str(&format!("&status={}", s));
+                        query.push(("status".to_string(), s.to_string()));
                     }
                     if let Some(r) = ref_name {
-                        path.push_str(&format!("&ref={}", urlencoding::encode(r)));
+                        query.push(("ref".to_string(), r.to_string()));
                     }
 
                     #[derive(serde::Deserialize)]
@@ -1424,7 +2203,7 @@ impl GitLabMcpServer {
                         user: serde_json::Value,
                     }
 
-                    match client.get::<Vec<Pipeline>>(&path).await {
+                    match fetch_list::<Pipeline>(&client, &path, query, &args.cloned().unwrap_or_default()).await {
                         Ok(pipelines) => {
                             let mut output = vec![];
                             output.push(format!("## Pipelines ({} found)\n", pipelines.len()));
@@ -1457,8 +2236,360 @@ impl GitLabMcpServer {
                         }
                     }
                 })
+            }),
+        ));
+
+        // === Container Registry Tools ===
+
+        // Register list_registry_repositories tool
+        let list_registry_repositories_tool = Tool {
+            base: BaseMetadata {
+                name: "list_registry_repositories".to_string(),
+                title: Some("List Registry Repositories".to_string()),
+            },
+            icons: Icons::default(),
+            description: Some("List the container registry repositories of a project with their tag counts".to_string()),
+            input_schema: with_pagination(json!({
+                "type": "object",
+                "properties": {
+                    "project_id": {
+                        "type": "string",
+                        "x-gitlab-project-id": true,
+                        "description": "Project ID or URL-encoded path"
+                    },
+                    "page": {
+                        "type": "integer",
+                        "description": "Page number (default: 1)"
+                    },
+                    "per_page": {
+                        "type": "integer",
+                        "description": "Number of items per page (default: 20, max: 100)"
+                    }
+                },
+                "required": ["project_id"]
+            })),
+            output_schema: None,
+            annotations: None,
+            execution: None,
+            meta: None,
+        };
+
+        tools.push((
+            list_registry_repositories_tool,
+            Box::new(|arguments: Option<serde_json::Value>, _context: RequestContext| {
+                Box::pin(async move { registry::list_registry_repositories(client_from_env()?, arguments).await })
+            }),
+        ));
+
+        // Register get_registry_repository tool
+        let get_registry_repository_tool = Tool {
+            base: BaseMetadata {
+                name: "get_registry_repository".to_string(),
+                title: Some("Get Registry Repository".to_string()),
+            },
+            icons: Icons::default(),
+            description: Some("Get a container registry repository of a project".to_string()),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "project_id": {
+                        "type": "string",
+                        "x-gitlab-project-id": true,
+                        "description": "Project ID or URL-encoded path"
+                    },
+                    "repository_id": {
+                        "type": "integer",
+                        "description": "Registry repository ID"
+                    }
+                },
+                "required": ["project_id", "repository_id"]
+            }),
+            output_schema: None,
+            annotations: None,
+            execution: None,
+            meta: None,
+        };
+
+        tools.push((
+            get_registry_repository_tool,
+            Box::new(|arguments: Option<serde_json::Value>, _context: RequestContext| {
+                Box::pin(async move { registry::get_registry_repository(client_from_env()?, arguments).await })
+            }),
+        ));
+
+        // Register delete_registry_repository tool
+        let delete_registry_repository_tool = Tool {
+            base: BaseMetadata {
+                name: "delete_registry_repository".to_string(),
+                title: Some("Delete Registry Repository".to_string()),
+            },
+            icons: Icons::default(),
+            description: Some("Delete a container registry repository and all of its tags".to_string()),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "project_id": {
+                        "type": "string",
+                        "x-gitlab-project-id": true,
+                        "description": "Project ID or URL-encoded path"
+                    },
+                    "repository_id": {
+                        "type": "integer",
+                        "description": "Registry repository ID"
+                    }
+                },
+                "required": ["project_id", "repository_id"]
+            }),
+            output_schema: None,
+            annotations: None,
+            execution: None,
+            meta: None,
+        };
+
+        tools.push((
+            delete_registry_repository_tool,
+            Box::new(|arguments: Option<serde_json::Value>, _context: RequestContext| {
+                Box::pin(async move { registry::delete_registry_repository(client_from_env()?, arguments).await })
+            }),
+        ));
+
+        // Register list_registry_tags tool
+        let list_registry_tags_tool = Tool {
+            base: BaseMetadata {
+                name: "list_registry_tags".to_string(),
+                title: Some("List Registry Tags".to_string()),
+            },
+            icons: Icons::default(),
+            description: Some("List the tags of a container registry repository, optionally only those matching name_regex_delete".to_string()),
+            input_schema: with_pagination(json!({
+                "type": "object",
+                "properties": {
+                    "project_id": {
+                        "type": "string",
+                        "x-gitlab-project-id": true,
+                        "description": "Project ID or URL-encoded path"
+                    },
+                    "repository_id": {
+                        "type": "integer",
+                        "description": "Registry repository ID"
+                    },
+                    "name_regex_delete": {
+                        "type": "string",
+                        "description": "Only list tags whose whole name matches this regex (preview of a cleanup)"
+                    },
+                    "page": {
+                        "type": "integer",
+                        "description": "Page number (default: 1)"
+                    },
+                    "per_page": {
+                        "type": "integer",
+                        "description": "Number of items per page (default: 20, max: 100)"
+                    }
+                },
+                "required": ["project_id", "repository_id"]
+            })),
+            output_schema: None,
+            annotations: None,
+            execution: None,
+            meta: None,
+        };
+
+        tools.push((
+            list_registry_tags_tool,
+            Box::new(|arguments: Option<serde_json::Value>, _context: RequestContext| {
+                Box::pin(async move { registry::list_registry_tags(client_from_env()?, arguments).await })
+            }),
+        ));
+
+        // Register get_registry_tag tool
+        let get_registry_tag_tool = Tool {
+            base: BaseMetadata {
+                name: "get_registry_tag".to_string(),
+                title: Some("Get Registry Tag".to_string()),
+            },
+            icons: Icons::default(),
+            description: Some("Get a registry tag with its digest, creation time and total size".to_string()),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "project_id": {
+                        "type": "string",
+                        "x-gitlab-project-id": true,
+                        "description": "Project ID or URL-encoded path"
+                    },
+                    "repository_id": {
+                        "type": "integer",
+                        "description": "Registry repository ID"
+                    },
+                    "tag_name": {
+                        "type": "string",
+                        "description": "Tag name"
+                    }
+                },
+                "required": ["project_id", "repository_id", "tag_name"]
+            }),
+            output_schema: None,
+            annotations: None,
+            execution: None,
+            meta: None,
+        };
+
+        tools.push((
+            get_registry_tag_tool,
+            Box::new(|arguments: Option<serde_json::Value>, _context: RequestContext| {
+                Box::pin(async move { registry::get_registry_tag(client_from_env()?, arguments).await })
+            }),
+        ));
+
+        // Register delete_registry_tag tool
+        let delete_registry_tag_tool = Tool {
+            base: BaseMetadata {
+                name: "delete_registry_tag".to_string(),
+                title: Some("Delete Registry Tag".to_string()),
+            },
+            icons: Icons::default(),
+            description: Some("Delete a single container registry tag".to_string()),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "project_id": {
+                        "type": "string",
+                        "x-gitlab-project-id": true,
+                        "description": "Project ID or URL-encoded path"
+                    },
+                    "repository_id": {
+                        "type": "integer",
+                        "description": "Registry repository ID"
+                    },
+                    "tag_name": {
+                        "type": "string",
+                        "description": "Tag name"
+                    }
+                },
+                "required": ["project_id", "repository_id", "tag_name"]
+            }),
+            output_schema: None,
+            annotations: None,
+            execution: None,
+            meta: None,
+        };
+
+        tools.push((
+            delete_registry_tag_tool,
+            Box::new(|arguments: Option<serde_json::Value>, _context: RequestContext| {
+                Box::pin(async move { registry::delete_registry_tag(client_from_env()?, arguments).await })
+            }),
+        ));
+
+        // Register bulk_delete_registry_tags tool
+        let bulk_delete_registry_tags_tool = Tool {
+            base: BaseMetadata {
+                name: "bulk_delete_registry_tags".to_string(),
+                title: Some("Bulk Delete Registry Tags".to_string()),
+            },
+            icons: Icons::default(),
+            description: Some("Delete registry tags in bulk following a cleanup policy (name_regex_delete, keep_n, name_regex_keep, older_than)".to_string()),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "project_id": {
+                        "type": "string",
+                        "x-gitlab-project-id": true,
+                        "description": "Project ID or URL-encoded path"
+                    },
+                    "repository_id": {
+                        "type": "integer",
+                        "description": "Registry repository ID"
+                    },
+                    "name_regex_delete": {
+                        "type": "string",
+                        "description": "Regex of tag names to delete, e.g. '.*' for all tags"
+                    },
+                    "keep_n": {
+                        "type": "integer",
+                        "description": "Number of most recent matching tags to keep"
+                    },
+                    "name_regex_keep": {
+                        "type": "string",
+                        "description": "Regex of tag names to always keep"
+                    },
+                    "older_than": {
+                        "type": "string",
+                        "description": "Only delete tags older than this, e.g. '1h', '7d', '1month'"
+                    }
+                },
+                "required": ["project_id", "repository_id", "name_regex_delete"]
+            }),
+            output_schema: None,
+            annotations: None,
+            execution: None,
+            meta: None,
+        };
+
+        tools.push((
+            bulk_delete_registry_tags_tool,
+            Box::new(|arguments: Option<serde_json::Value>, _context: RequestContext| {
+                Box::pin(async move { registry::bulk_delete_registry_tags(client_from_env()?, arguments).await })
+            }),
+        ));
+
+        // === Security Tools ===
+
+        // Register get_project_vulnerabilities tool
+        let get_project_vulnerabilities_tool = Tool {
+            base: BaseMetadata {
+                name: "get_project_vulnerabilities".to_string(),
+                title: Some("Get Project Vulnerabilities".to_string()),
             },
-        )?;
+            icons: Icons::default(),
+            description: Some("List vulnerability findings of a project with severity, state, scanner, identifiers and location (via GraphQL)".to_string()),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "project_id": {
+                        "type": "string",
+                        "x-gitlab-project-id": true,
+                        "description": "Project ID or full path (e.g. 'group/project')"
+                    },
+                    "severity": {
+                        "type": "array",
+                        "items": {
+                            "type": "string",
+                            "enum": ["CRITICAL", "HIGH", "MEDIUM", "LOW", "INFO", "UNKNOWN"]
+                        },
+                        "description": "Only return findings with these severities"
+                    },
+                    "state": {
+                        "type": "array",
+                        "items": {
+                            "type": "string",
+                            "enum": ["DETECTED", "CONFIRMED", "RESOLVED", "DISMISSED"]
+                        },
+                        "description": "Only return findings in these states"
+                    },
+                    "first": {
+                        "type": "integer",
+                        "description": "Number of findings to return (default: 20, max: 100)"
+                    },
+                    "after": {
+                        "type": "string",
+                        "description": "Cursor from pageInfo.endCursor of the previous page"
+                    }
+                },
+                "required": ["project_id"]
+            }),
+            output_schema: None,
+            annotations: None,
+            execution: None,
+            meta: None,
+        };
+
+        tools.push((
+            get_project_vulnerabilities_tool,
+            Box::new(|arguments: Option<serde_json::Value>, _context: RequestContext| {
+                Box::pin(async move { vulnerabilities::get_project_vulnerabilities(client_from_env()?, arguments).await })
+            }),
+        ));
 
         // === Repository/File Tools ===
 
@@ -1470,11 +2601,12 @@ impl GitLabMcpServer {
             },
             icons: Icons::default(),
             description: Some("List files in a project repository".to_string()),
-            input_schema: json!({
+            input_schema: with_pagination(json!({
                 "type": "object",
                 "properties": {
                     "project_id": {
                         "type": "string",
+                        "x-gitlab-project-id": true,
                         "description": "Project ID or URL-encoded path"
                     },
                     "path": {
@@ -1487,16 +2619,16 @@ impl GitLabMcpServer {
                     }
                 },
                 "required": ["project_id"]
-            }),
+            })),
             output_schema: None,
             annotations: None,
             execution: None,
             meta: None,
         };
 
-        server.register_tool(
+        tools.push((
             list_files_tool,
-            |arguments: Option<serde_json::Value>, _context: RequestContext| {
+            Box::new(|arguments: Option<serde_json::Value>, _context: RequestContext| {
                 Box::pin(async move {
                     let args = arguments.as_ref().and_then(|a| a.as_object());
                     let project_id = args
@@ -1508,14 +2640,14 @@ impl GitLabMcpServer {
                     let ref_name = args.and_then(|a| a.get("ref")).and_then(|v| v.as_str());
 
                     let config = Config::from_env();
-                    let client = GitLabClient::new(&config.gitlab_url, &config.gitlab_token)
+                    let client = GitLabClient::shared(&config)
                         .map_err(|e| ServerError::Handler(format!("Failed to create client: {}", e)))?;
 
                     let encoded_project = urlencoding::encode(project_id);
-                    let encoded_path = urlencoding::encode(path);
-                    let mut url = format!("projects/{}/repository/tree/{}?path={}", encoded_project, encoded_path, encoded_path);
+                    let url = format!("projects/{}/repository/tree", encoded_project);
+                    let mut query = vec![("path".to_string(), path.to_string())];
                     if let Some(r) = ref_name {
-                        url.push_str(&format!("&ref={}", urlencoding::encode(r)));
+                        query.push(("ref".to_string(), r.to_string()));
                     }
 
                     #[derive(serde::Deserialize)]
@@ -1529,7 +2661,7 @@ impl GitLabMcpServer {
                         _mode: String,
                     }
 
-                    match client.get::<Vec<FileInfo>>(&url).await {
+                    match fetch_list::<FileInfo>(&client, &url, query, &args.cloned().unwrap_or_default()).await {
                         Ok(files) => {
                             let mut output = vec![];
                             output.push(format!("## Files in `{}`\n", if path.is_empty() { "/" } else { path }));
@@ -1554,8 +2686,8 @@ impl GitLabMcpServer {
                         }
                     }
                 })
-            },
-        )?;
+            }),
+        ));
 
         // Register get_file tool
         let get_file_tool = Tool {
@@ -1570,6 +2702,7 @@ impl GitLabMcpServer {
                 "properties": {
                     "project_id": {
                         "type": "string",
+                        "x-gitlab-project-id": true,
                         "description": "Project ID or URL-encoded path"
                     },
                     "file_path": {
@@ -1589,9 +2722,9 @@ impl GitLabMcpServer {
             meta: None,
         };
 
-        server.register_tool(
+        tools.push((
             get_file_tool,
-            |arguments: Option<serde_json::Value>, _context: RequestContext| {
+            Box::new(|arguments: Option<serde_json::Value>, _context: RequestContext| {
                 Box::pin(async move {
                     let args = arguments.as_ref().and_then(|a| a.as_object());
                     let project_id = args
@@ -1606,7 +2739,7 @@ impl GitLabMcpServer {
                     let ref_name = args.and_then(|a| a.get("ref")).and_then(|v| v.as_str());
 
                     let config = Config::from_env();
-                    let client = GitLabClient::new(&config.gitlab_url, &config.gitlab_token)
+                    let client = GitLabClient::shared(&config)
                         .map_err(|e| ServerError::Handler(format!("Failed to create client: {}", e)))?;
 
                     let encoded_project = urlencoding::encode(project_id);
@@ -1634,15 +2767,53 @@ impl GitLabMcpServer {
                         ..Default::default()
                     })
                 })
-            },
-        )?;
+            }),
+        ));
 
+        server.register_tool_group(Some("gitlab"), tools)?;
         Ok(())
     }
 
     /// Run the server (stdio transport)
+    /// Implementation info advertised in `initialize`
+    pub fn server_info(description: String) -> Implementation {
+        Implementation {
+            base: BaseMetadata {
+                name: "gitlab-mcp-server".to_string(),
+                title: Some("GitLab MCP Server".to_string()),
+            },
+            icons: Icons::default(),
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            website_url: Some("https://gitlab.com".to_string()),
+            description: Some(description),
+        }
+    }
+
+    /// Recompute the `initialize` description and instructions from `config`
+    /// and a fresh self-check, so they follow configuration changes
+    pub async fn refresh_server_info(
+        server: &McpServer,
+        config: &Config,
+        transport: TransportMode,
+    ) -> ServerSummary {
+        let user = summary::authenticated_user(config).await;
+        let tool_names: Vec<String> = server.list_tools().into_iter().map(|tool| tool.base.name).collect();
+        let summary = ServerSummary::collect(config, user, &tool_names, transport);
+
+        server.server().set_server_info(Self::server_info(summary::describe(&summary)));
+        server.server().set_instructions(Some(summary::instructions(&summary)));
+        summary
+    }
+
     pub async fn run(&mut self) -> Result<(), Box<dyn std::error::Error>> {
         // This will be implemented with the stdio loop
         Ok(())
     }
 }
+
+/// Shared GitLab client for the current configuration
+fn client_from_env() -> Result<Arc<GitLabClient>, ServerError> {
+    let config = Config::from_env();
+    GitLabClient::shared(&config)
+        .map_err(|e| ServerError::Handler(format!("Failed to create client: {}", e)))
+}
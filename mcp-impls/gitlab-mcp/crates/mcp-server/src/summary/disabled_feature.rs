@@ -0,0 +1,15 @@
+/// A feature that is unavailable, with a one-line reason
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DisabledFeature {
+    pub name: String,
+    pub reason: String,
+}
+
+impl DisabledFeature {
+    pub fn new(name: impl Into<String>, reason: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            reason: reason.into(),
+        }
+    }
+}
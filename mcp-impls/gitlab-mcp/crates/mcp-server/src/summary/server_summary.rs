@@ -0,0 +1,77 @@
+use crate::config::Config;
+
+use super::{categorize_tools, DisabledFeature, ToolCategory, TransportMode};
+
+/// Secret-free snapshot of the server's configuration and self-check results
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ServerSummary {
+    /// Hostname of the GitLab instance, without scheme, credentials or path
+    pub instance_host: Option<String>,
+    /// Username the token authenticates as, when the self-check succeeded
+    pub user: Option<String>,
+    pub tool_categories: Vec<ToolCategory>,
+    pub disabled_features: Vec<DisabledFeature>,
+    pub transport: TransportMode,
+}
+
+impl ServerSummary {
+    /// Build a summary from the configuration and the result of looking up the
+    /// authenticated user.
+    ///
+    /// Only the hostname of the instance URL is kept, and the token and any
+    /// URL credentials are redacted from self-check failure reasons.
+    pub fn collect(
+        config: &Config,
+        user: Result<String, String>,
+        tool_names: &[String],
+        transport: TransportMode,
+    ) -> Self {
+        let url = url::Url::parse(&config.gitlab_url).ok();
+        let instance_host = url.as_ref().and_then(|u| u.host_str()).map(str::to_string);
+
+        let mut secrets = vec![config.gitlab_token.as_str()];
+        if let Some(password) = url.as_ref().and_then(|u| u.password()) {
+            secrets.push(password);
+        }
+
+        let mut disabled_features = Vec::new();
+        let user = match config.validate() {
+            Err(reason) => {
+                disabled_features.push(DisabledFeature::new("GitLab API tools", redact(&reason, &secrets)));
+                None
+            }
+            Ok(()) => match user {
+                Ok(username) => Some(username),
+                Err(reason) => {
+                    disabled_features.push(DisabledFeature::new(
+                        "GitLab API tools",
+                        format!("self-check failed: {}", redact(&reason, &secrets)),
+                    ));
+                    None
+                }
+            },
+        };
+
+        Self {
+            instance_host,
+            user,
+            tool_categories: categorize_tools(tool_names),
+            disabled_features,
+            transport,
+        }
+    }
+
+    /// Total number of registered tools
+    pub fn tool_count(&self) -> usize {
+        self.tool_categories.iter().map(|c| c.tool_count).sum()
+    }
+}
+
+/// Replace every occurrence of a secret, and keep the reason on one line
+fn redact(text: &str, secrets: &[&str]) -> String {
+    let mut text = text.replace(['\r', '\n'], " ");
+    for secret in secrets.iter().filter(|s| !s.is_empty()) {
+        text = text.replace(secret, "[redacted]");
+    }
+    text
+}
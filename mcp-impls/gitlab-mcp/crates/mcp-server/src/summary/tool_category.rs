@@ -0,0 +1,15 @@
+/// A group of registered tools, e.g. "Issues" with 3 tools
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ToolCategory {
+    pub name: String,
+    pub tool_count: usize,
+}
+
+impl ToolCategory {
+    pub fn new(name: impl Into<String>, tool_count: usize) -> Self {
+        Self {
+            name: name.into(),
+            tool_count,
+        }
+    }
+}
@@ -0,0 +1,17 @@
+use std::fmt;
+
+/// Transport the server is reachable over
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransportMode {
+    Stdio,
+    Http,
+}
+
+impl fmt::Display for TransportMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TransportMode::Stdio => write!(f, "stdio"),
+            TransportMode::Http => write!(f, "HTTP"),
+        }
+    }
+}
@@ -0,0 +1,388 @@
+use mcp_core::types::CallToolResult;
+use crate::gitlab::GitLabClient;
+use super::{dry_run_result, fetch_list, object_arguments, required_str, to_tool_error, to_tool_result};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+use std::sync::Arc;
+use mcp_server::ServerError;
+
+/// Issue board response from GitLab API
+#[derive(Deserialize)]
+struct GitLabBoard {
+    id: u64,
+    name: String,
+    #[serde(default)]
+    lists: Vec<GitLabBoardList>,
+}
+
+/// A list (column) on an issue board
+#[derive(Deserialize)]
+struct GitLabBoardList {
+    id: u64,
+    #[serde(default)]
+    position: Option<i64>,
+    #[serde(default)]
+    label: Option<NamedRef>,
+    #[serde(default)]
+    milestone: Option<TitledRef>,
+    #[serde(default)]
+    assignee: Option<UserRef>,
+    #[serde(default)]
+    iteration: Option<TitledRef>,
+}
+
+#[derive(Deserialize)]
+struct NamedRef {
+    name: String,
+}
+
+#[derive(Deserialize)]
+struct TitledRef {
+    title: String,
+}
+
+#[derive(Deserialize)]
+struct UserRef {
+    username: String,
+}
+
+/// Issue fields needed to move it between lists
+#[derive(Deserialize)]
+struct GitLabIssueLabels {
+    iid: u64,
+    #[serde(default)]
+    labels: Vec<String>,
+}
+
+/// Issue update request that edits labels without touching unrelated ones
+#[derive(Serialize)]
+struct UpdateIssueLabelsRequest {
+    #[serde(skip_serializing_if = "String::is_empty")]
+    add_labels: String,
+    #[serde(skip_serializing_if = "String::is_empty")]
+    remove_labels: String,
+}
+
+/// What a board list is scoped by
+#[derive(Serialize, Debug, PartialEq)]
+#[serde(tag = "kind", content = "value", rename_all = "lowercase")]
+enum ListScope {
+    Label(String),
+    Milestone(String),
+    Assignee(String),
+    Iteration(String),
+    Unknown,
+}
+
+/// Label changes required to move an issue onto a label list
+#[derive(Serialize, Debug, PartialEq)]
+struct LabelMove {
+    /// Board list labels the issue currently carries
+    from_lists: Vec<String>,
+    add: Vec<String>,
+    remove: Vec<String>,
+    labels_after: Vec<String>,
+}
+
+/// List issue boards of a project
+pub async fn list_boards(
+    client: Arc<GitLabClient>,
+    arguments: Option<Value>,
+) -> Result<CallToolResult, ServerError> {
+    let args = object_arguments(arguments)?;
+    let project_id = required_str(&args, "project_id")?;
+    let path = format!("projects/{}/boards", urlencoding::encode(project_id));
+
+    match fetch_list::<GitLabBoard>(&client, &path, Vec::new(), &args).await {
+        Ok(boards) if boards.is_empty() => {
+            Ok(to_tool_result("No issue boards found for this project".to_string()))
+        }
+        Ok(boards) => {
+            let boards: Vec<Value> = boards.iter().map(board_summary).collect();
+            let json = serde_json::to_string_pretty(&boards).unwrap_or_else(|_| "[]".to_string());
+            Ok(to_tool_result(json))
+        }
+        Err(e) => Ok(to_tool_error(format!("Failed to list boards: {}", e))),
+    }
+}
+
+/// Get an issue board with its list definitions
+pub async fn get_board(
+    client: Arc<GitLabClient>,
+    arguments: Option<Value>,
+) -> Result<CallToolResult, ServerError> {
+    let args = object_arguments(arguments)?;
+    let project_id = required_str(&args, "project_id")?;
+    let board_id = required_u64(&args, "board_id")?;
+
+    match client.get::<GitLabBoard>(&board_path(project_id, board_id)).await {
+        Ok(board) => {
+            let summary = board_summary(&board);
+            let json = serde_json::to_string_pretty(&summary).unwrap_or_else(|_| "{}".to_string());
+            let mut result = to_tool_result(json);
+            result.structured_content = Some(summary);
+            Ok(result)
+        }
+        Err(e) => Ok(to_tool_error(format!("Failed to get board: {}", e))),
+    }
+}
+
+/// Move an issue to another list of a label-based board
+pub async fn move_issue_to_list(
+    client: Arc<GitLabClient>,
+    arguments: Option<Value>,
+) -> Result<CallToolResult, ServerError> {
+    let args = object_arguments(arguments)?;
+    let project_id = required_str(&args, "project_id")?;
+    let board_id = required_u64(&args, "board_id")?;
+    let list_id = required_u64(&args, "list_id")?;
+    let issue_iid = required_u64(&args, "issue_iid")?;
+
+    let board = match client.get::<GitLabBoard>(&board_path(project_id, board_id)).await {
+        Ok(board) => board,
+        Err(e) => return Ok(to_tool_error(format!("Failed to get board: {}", e))),
+    };
+
+    let Some(target) = board.lists.iter().find(|list| list.id == list_id) else {
+        return Ok(to_tool_error(format!(
+            "List {} does not belong to board `{}`",
+            list_id, board.name
+        )));
+    };
+    let target_label = match list_scope(target) {
+        ListScope::Label(name) => name,
+        scope => {
+            return Ok(to_tool_error(format!(
+                "List {} of board `{}` is scoped by {}; only label-based lists are supported",
+                list_id,
+                board.name,
+                scope_kind(&scope)
+            )))
+        }
+    };
+    let board_labels: Vec<String> = board
+        .lists
+        .iter()
+        .filter_map(|list| list.label.as_ref().map(|label| label.name.clone()))
+        .collect();
+
+    let issue_path = format!(
+        "projects/{}/issues/{}",
+        urlencoding::encode(project_id),
+        issue_iid
+    );
+    let issue = match client.get::<GitLabIssueLabels>(&issue_path).await {
+        Ok(issue) => issue,
+        Err(e) => return Ok(to_tool_error(format!("Failed to get issue: {}", e))),
+    };
+
+    let change = compute_label_move(&issue.labels, &board_labels, &target_label);
+    let labels_after = if change.add.is_empty() && change.remove.is_empty() {
+        issue.labels.clone()
+    } else {
+        let request = UpdateIssueLabelsRequest {
+            add_labels: change.add.join(","),
+            remove_labels: change.remove.join(","),
+        };
+        if client.is_dry_run() {
+            return Ok(dry_run_result("PUT", &issue_path, serde_json::to_value(&request).ok()));
+        }
+        match client.put::<GitLabIssueLabels, _>(&issue_path, &request).await {
+            Ok(updated) => updated.labels,
+            Err(e) => return Ok(to_tool_error(format!("Failed to update issue labels: {}", e))),
+        }
+    };
+
+    let structured = json!({
+        "issue_iid": issue.iid,
+        "board": board.name,
+        "before": {
+            "lists": change.from_lists,
+            "labels": issue.labels,
+        },
+        "after": {
+            "list": target_label,
+            "labels": labels_after,
+        },
+        "added": change.add,
+        "removed": change.remove,
+    });
+
+    let from = if change.from_lists.is_empty() {
+        "no list".to_string()
+    } else {
+        change.from_lists.join(", ")
+    };
+    let mut result = to_tool_result(format!(
+        "Moved issue #{} from {} to `{}`",
+        issue.iid, from, target_label
+    ));
+    result.structured_content = Some(structured);
+    Ok(result)
+}
+
+/// Compute the labels to add and remove so an issue lands on `target`.
+///
+/// Labels of other lists on the board are removed, the target label is added
+/// and all labels unrelated to the board are kept.
+fn compute_label_move(current: &[String], board_labels: &[String], target: &str) -> LabelMove {
+    let from_lists: Vec<String> = current
+        .iter()
+        .filter(|label| board_labels.contains(label))
+        .cloned()
+        .collect();
+    let remove: Vec<String> = from_lists
+        .iter()
+        .filter(|label| label.as_str() != target)
+        .cloned()
+        .collect();
+    let add: Vec<String> = if current.iter().any(|label| label == target) {
+        Vec::new()
+    } else {
+        vec![target.to_string()]
+    };
+    let mut labels_after: Vec<String> = current
+        .iter()
+        .filter(|label| !remove.contains(label))
+        .cloned()
+        .collect();
+    labels_after.extend(add.iter().cloned());
+
+    LabelMove {
+        from_lists,
+        add,
+        remove,
+        labels_after,
+    }
+}
+
+fn list_scope(list: &GitLabBoardList) -> ListScope {
+    if let Some(label) = &list.label {
+        ListScope::Label(label.name.clone())
+    } else if let Some(milestone) = &list.milestone {
+        ListScope::Milestone(milestone.title.clone())
+    } else if let Some(assignee) = &list.assignee {
+        ListScope::Assignee(assignee.username.clone())
+    } else if let Some(iteration) = &list.iteration {
+        ListScope::Iteration(iteration.title.clone())
+    } else {
+        ListScope::Unknown
+    }
+}
+
+fn scope_kind(scope: &ListScope) -> &'static str {
+    match scope {
+        ListScope::Label(_) => "label",
+        ListScope::Milestone(_) => "milestone",
+        ListScope::Assignee(_) => "assignee",
+        ListScope::Iteration(_) => "iteration",
+        ListScope::Unknown => "an unknown scope",
+    }
+}
+
+fn board_summary(board: &GitLabBoard) -> Value {
+    let lists: Vec<Value> = board
+        .lists
+        .iter()
+        .map(|list| {
+            json!({
+                "id": list.id,
+                "position": list.position,
+                "scope": list_scope(list),
+            })
+        })
+        .collect();
+    json!({
+        "id": board.id,
+        "name": board.name,
+        "lists": lists,
+    })
+}
+
+fn board_path(project_id: &str, board_id: u64) -> String {
+    format!("projects/{}/boards/{}", urlencoding::encode(project_id), board_id)
+}
+
+fn required_u64(args: &Map<String, Value>, key: &str) -> Result<u64, ServerError> {
+    args.get(key)
+        .and_then(|v| v.as_u64())
+        .ok_or_else(|| ServerError::Handler(format!("{} is required", key)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn labels(names: &[&str]) -> Vec<String> {
+        names.iter().map(|name| name.to_string()).collect()
+    }
+
+    #[test]
+    fn test_move_between_lists() {
+        let board = labels(&["To Do", "Doing", "Review"]);
+        let change = compute_label_move(&labels(&["bug", "To Do"]), &board, "Doing");
+        assert_eq!(change.from_lists, labels(&["To Do"]));
+        assert_eq!(change.remove, labels(&["To Do"]));
+        assert_eq!(change.add, labels(&["Doing"]));
+        assert_eq!(change.labels_after, labels(&["bug", "Doing"]));
+    }
+
+    #[test]
+    fn test_move_issue_on_no_list() {
+        let board = labels(&["To Do", "Doing"]);
+        let change = compute_label_move(&labels(&["bug"]), &board, "To Do");
+        assert!(change.from_lists.is_empty());
+        assert!(change.remove.is_empty());
+        assert_eq!(change.add, labels(&["To Do"]));
+        assert_eq!(change.labels_after, labels(&["bug", "To Do"]));
+    }
+
+    #[test]
+    fn test_move_keeps_unrelated_scoped_labels() {
+        let board = labels(&["workflow::todo", "workflow::doing", "workflow::done"]);
+        let current = labels(&["priority::high", "workflow::todo", "team::backend"]);
+        let change = compute_label_move(&current, &board, "workflow::done");
+        assert_eq!(change.remove, labels(&["workflow::todo"]));
+        assert_eq!(change.add, labels(&["workflow::done"]));
+        assert_eq!(
+            change.labels_after,
+            labels(&["priority::high", "team::backend", "workflow::done"])
+        );
+    }
+
+    #[test]
+    fn test_move_to_current_list_is_noop() {
+        let board = labels(&["To Do", "Doing"]);
+        let change = compute_label_move(&labels(&["Doing"]), &board, "Doing");
+        assert!(change.add.is_empty());
+        assert!(change.remove.is_empty());
+        assert_eq!(change.labels_after, labels(&["Doing"]));
+    }
+
+    #[test]
+    fn test_non_label_lists_are_detected() {
+        let board: GitLabBoard = serde_json::from_value(json!({
+            "id": 1,
+            "name": "Sprint",
+            "lists": [
+                { "id": 10, "position": 0, "label": { "name": "To Do" } },
+                { "id": 11, "position": 1, "label": null, "milestone": { "title": "v1.0" } },
+                { "id": 12, "position": 2, "label": null, "assignee": { "username": "alice" } }
+            ]
+        }))
+        .unwrap();
+        let scopes: Vec<ListScope> = board.lists.iter().map(list_scope).collect();
+        assert_eq!(
+            scopes,
+            vec![
+                ListScope::Label("To Do".to_string()),
+                ListScope::Milestone("v1.0".to_string()),
+                ListScope::Assignee("alice".to_string()),
+            ]
+        );
+        assert_eq!(
+            board_summary(&board)["lists"][1]["scope"],
+            json!({ "kind": "milestone", "value": "v1.0" })
+        );
+    }
+}
@@ -0,0 +1,379 @@
+use mcp_core::types::CallToolResult;
+use crate::gitlab::GitLabClient;
+use super::{dry_run_result, fetch_list, object_arguments, required_str, to_tool_error, to_tool_result};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+use std::sync::Arc;
+use mcp_server::ServerError;
+
+/// Label creation request
+#[derive(Serialize)]
+struct CreateLabelRequest {
+    name: String,
+    color: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    description: Option<String>,
+}
+
+/// Label update request
+#[derive(Serialize)]
+struct UpdateLabelRequest {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    new_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    color: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    description: Option<String>,
+}
+
+/// Label response from GitLab API
+#[derive(Deserialize, Serialize)]
+struct GitLabLabel {
+    id: u64,
+    name: String,
+    color: String,
+    #[serde(default)]
+    description: Option<String>,
+    #[serde(default)]
+    open_issues_count: Option<u64>,
+    #[serde(default)]
+    closed_issues_count: Option<u64>,
+    #[serde(default)]
+    open_merge_requests_count: Option<u64>,
+    #[serde(default)]
+    subscribed: Option<bool>,
+    #[serde(default)]
+    priority: Option<u64>,
+    #[serde(default)]
+    is_project_label: Option<bool>,
+}
+
+/// Response of `/projects/:id/issues_statistics`
+#[derive(Deserialize)]
+struct IssuesStatistics {
+    statistics: IssuesStatisticsBody,
+}
+
+#[derive(Deserialize)]
+struct IssuesStatisticsBody {
+    counts: IssueCounts,
+}
+
+/// Issue counts by state
+#[derive(Deserialize, Serialize, Debug, PartialEq)]
+struct IssueCounts {
+    #[serde(rename(deserialize = "all"))]
+    total: u64,
+    opened: u64,
+    closed: u64,
+}
+
+/// Get aggregate issue counts for a project
+pub async fn get_issue_statistics(
+    client: Arc<GitLabClient>,
+    arguments: Option<Value>,
+) -> Result<CallToolResult, ServerError> {
+    let args = object_arguments(arguments)?;
+    let project_id = required_str(&args, "project_id")?;
+
+    let mut query = Vec::new();
+    for key in ["labels", "milestone", "assignee_username", "author_username", "search"] {
+        if let Some(value) = args.get(key).and_then(|v| v.as_str()) {
+            query.push((key.to_string(), value.to_string()));
+        }
+    }
+
+    let path = format!("projects/{}/issues_statistics", urlencoding::encode(project_id));
+
+    match client.get_with_query::<IssuesStatistics>(&path, &query).await {
+        Ok(stats) => {
+            let counts = stats.statistics.counts;
+            let output = [
+                "## Issue Statistics\n".to_string(),
+                format!("**Total:** {}", counts.total),
+                format!("**Opened:** {}", counts.opened),
+                format!("**Closed:** {}", counts.closed),
+            ];
+            let mut result = to_tool_result(output.join("\n"));
+            result.structured_content = Some(json!(counts));
+            Ok(result)
+        }
+        Err(e) => Ok(to_tool_error(format!("Failed to get issue statistics: {}", e))),
+    }
+}
+
+/// List labels of a project
+pub async fn list_labels(
+    client: Arc<GitLabClient>,
+    arguments: Option<Value>,
+) -> Result<CallToolResult, ServerError> {
+    let args = object_arguments(arguments)?;
+    let project_id = required_str(&args, "project_id")?;
+
+    let mut query = vec![("with_counts".to_string(), "true".to_string())];
+    if let Some(search) = args.get("search").and_then(|v| v.as_str()) {
+        query.push(("search".to_string(), search.to_string()));
+    }
+
+    let path = format!("projects/{}/labels", urlencoding::encode(project_id));
+
+    match fetch_list::<GitLabLabel>(&client, &path, query, &args).await {
+        Ok(labels) => {
+            let json = serde_json::to_string_pretty(&labels).unwrap_or_else(|_| "[]".to_string());
+            Ok(to_tool_result(json))
+        }
+        Err(e) => Ok(to_tool_error(format!("Failed to list labels: {}", e))),
+    }
+}
+
+/// Create a project label
+pub async fn create_label(
+    client: Arc<GitLabClient>,
+    arguments: Option<Value>,
+) -> Result<CallToolResult, ServerError> {
+    let args = object_arguments(arguments)?;
+    let project_id = required_str(&args, "project_id")?;
+    let name = required_str(&args, "name")?.to_string();
+    let color = required_str(&args, "color")?.to_string();
+    let description = args.get("description").and_then(|v| v.as_str()).map(|s| s.to_string());
+
+    if !is_hex_color(&color) {
+        return Ok(to_tool_error("color must be a hex color such as #FF0000"));
+    }
+
+    let request = CreateLabelRequest {
+        name,
+        color,
+        description,
+    };
+    let path = format!("projects/{}/labels", urlencoding::encode(project_id));
+
+    if client.is_dry_run() {
+        return Ok(dry_run_result("POST", &path, serde_json::to_value(&request).ok()));
+    }
+
+    match client.post::<GitLabLabel, _>(&path, &request).await {
+        Ok(label) => Ok(to_tool_result(format_label("Label Created Successfully", &label))),
+        Err(e) => Ok(to_tool_error(format!("Failed to create label: {}", e))),
+    }
+}
+
+/// Update a project label
+pub async fn update_label(
+    client: Arc<GitLabClient>,
+    arguments: Option<Value>,
+) -> Result<CallToolResult, ServerError> {
+    let args = object_arguments(arguments)?;
+    let project_id = required_str(&args, "project_id")?;
+    let label_id = label_id(&args)?;
+
+    let request = UpdateLabelRequest {
+        new_name: args.get("new_name").and_then(|v| v.as_str()).map(|s| s.to_string()),
+        color: args.get("color").and_then(|v| v.as_str()).map(|s| s.to_string()),
+        description: args.get("description").and_then(|v| v.as_str()).map(|s| s.to_string()),
+    };
+
+    if request.new_name.is_none() && request.color.is_none() && request.description.is_none() {
+        return Ok(to_tool_error("one of new_name, color or description is required"));
+    }
+    if request.color.as_deref().is_some_and(|color| !is_hex_color(color)) {
+        return Ok(to_tool_error("color must be a hex color such as #FF0000"));
+    }
+
+    let path = label_path(project_id, &label_id);
+
+    if client.is_dry_run() {
+        return Ok(dry_run_result("PUT", &path, serde_json::to_value(&request).ok()));
+    }
+
+    match client.put::<GitLabLabel, _>(&path, &request).await {
+        Ok(label) => Ok(to_tool_result(format_label("Label Updated Successfully", &label))),
+        Err(e) => Ok(to_tool_error(format!("Failed to update label: {}", e))),
+    }
+}
+
+/// Delete a project label
+pub async fn delete_label(
+    client: Arc<GitLabClient>,
+    arguments: Option<Value>,
+) -> Result<CallToolResult, ServerError> {
+    let args = object_arguments(arguments)?;
+    let project_id = required_str(&args, "project_id")?;
+    let label_id = label_id(&args)?;
+    let path = label_path(project_id, &label_id);
+
+    if client.is_dry_run() {
+        return Ok(dry_run_result("DELETE", &path, None));
+    }
+
+    match client.delete(&path).await {
+        Ok(()) => Ok(to_tool_result(format!("Label `{}` deleted", label_id))),
+        Err(e) => Ok(to_tool_error(format!("Failed to delete label: {}", e))),
+    }
+}
+
+/// Subscribe the current user to a label
+pub async fn subscribe_to_label(
+    client: Arc<GitLabClient>,
+    arguments: Option<Value>,
+) -> Result<CallToolResult, ServerError> {
+    set_subscription(client, arguments, "subscribe").await
+}
+
+/// Unsubscribe the current user from a label
+pub async fn unsubscribe_from_label(
+    client: Arc<GitLabClient>,
+    arguments: Option<Value>,
+) -> Result<CallToolResult, ServerError> {
+    set_subscription(client, arguments, "unsubscribe").await
+}
+
+async fn set_subscription(
+    client: Arc<GitLabClient>,
+    arguments: Option<Value>,
+    action: &str,
+) -> Result<CallToolResult, ServerError> {
+    let args = object_arguments(arguments)?;
+    let project_id = required_str(&args, "project_id")?;
+    let label_id = label_id(&args)?;
+
+    let path = format!("{}/{}", label_path(project_id, &label_id), action);
+
+    if client.is_dry_run() {
+        return Ok(dry_run_result("POST", &path, None));
+    }
+
+    match client.post::<GitLabLabel, _>(&path, &json!({})).await {
+        Ok(label) => {
+            let state = if label.subscribed.unwrap_or(action == "subscribe") {
+                "Subscribed to"
+            } else {
+                "Unsubscribed from"
+            };
+            Ok(to_tool_result(format!("{} label `{}`", state, label.name)))
+        }
+        Err(e) => Ok(to_tool_error(format!("Failed to {} label: {}", action, e))),
+    }
+}
+
+/// Render a label as markdown
+fn format_label(heading: &str, label: &GitLabLabel) -> String {
+    let mut output = vec![
+        format!("## {}\n", heading),
+        format!("**Name:** {}", label.name),
+        format!("**ID:** {}", label.id),
+        format!("**Color:** {}", label.color),
+    ];
+    if let Some(desc) = &label.description {
+        output.push(format!("**Description:** {}", desc));
+    }
+    output.join("\n")
+}
+
+/// Path of a single label; GitLab accepts either the label ID or its name
+fn label_path(project_id: &str, label_id: &str) -> String {
+    format!(
+        "projects/{}/labels/{}",
+        urlencoding::encode(project_id),
+        urlencoding::encode(label_id)
+    )
+}
+
+/// Read `label_id` as either an integer ID or a label name
+fn label_id(args: &Map<String, Value>) -> Result<String, ServerError> {
+    match args.get("label_id") {
+        Some(Value::String(s)) => Ok(s.clone()),
+        Some(Value::Number(n)) => Ok(n.to_string()),
+        _ => Err(ServerError::Handler("label_id is required".to_string())),
+    }
+}
+
+/// Check for `#RGB` or `#RRGGBB` hex colors
+fn is_hex_color(color: &str) -> bool {
+    color
+        .strip_prefix('#')
+        .map(|hex| matches!(hex.len(), 3 | 6) && hex.chars().all(|c| c.is_ascii_hexdigit()))
+        .unwrap_or(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_issue_counts_from_statistics() {
+        let body = json!({
+            "statistics": { "counts": { "all": 12, "closed": 4, "opened": 8 } }
+        });
+        let stats: IssuesStatistics = serde_json::from_value(body).unwrap();
+        assert_eq!(
+            json!(stats.statistics.counts),
+            json!({ "total": 12, "opened": 8, "closed": 4 })
+        );
+    }
+
+    #[test]
+    fn test_hex_color() {
+        assert!(is_hex_color("#FF0000"));
+        assert!(is_hex_color("#f00"));
+        assert!(!is_hex_color("FF0000"));
+        assert!(!is_hex_color("#GG0000"));
+        assert!(!is_hex_color("red"));
+    }
+
+    /// A dry-run client whose API is unreachable, so any request fails
+    fn dry_run_client() -> Arc<GitLabClient> {
+        Arc::new(
+            GitLabClient::new("http://127.0.0.1:1", "token")
+                .unwrap()
+                .with_max_retries(0)
+                .with_dry_run(true),
+        )
+    }
+
+    fn is_dry_run(result: &CallToolResult) -> bool {
+        result
+            .meta
+            .as_ref()
+            .and_then(|meta| meta.extra.get("dry_run"))
+            == Some(&json!(true))
+    }
+
+    #[tokio::test]
+    async fn test_dry_run_describes_write() {
+        let args = json!({ "project_id": "group/app", "name": "bug", "color": "#FF0000" });
+        let result = create_label(dry_run_client(), Some(args)).await.unwrap();
+
+        assert!(is_dry_run(&result));
+        assert_eq!(result.is_error, None);
+        assert_eq!(
+            result.structured_content,
+            Some(json!({
+                "method": "POST",
+                "path": "projects/group%2Fapp/labels",
+                "params": { "name": "bug", "color": "#FF0000" }
+            }))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_dry_run_still_validates_arguments() {
+        let args = json!({ "project_id": "group/app", "name": "bug", "color": "red" });
+        let result = create_label(dry_run_client(), Some(args)).await.unwrap();
+        assert_eq!(result.is_error, Some(true));
+        assert!(!is_dry_run(&result));
+
+        let missing = json!({ "project_id": "group/app" });
+        assert!(delete_label(dry_run_client(), Some(missing)).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_dry_run_leaves_read_tools_alone() {
+        let args = json!({ "project_id": "group/app" });
+        let result = list_labels(dry_run_client(), Some(args)).await.unwrap();
+
+        // The read went to the (unreachable) API instead of being simulated
+        assert_eq!(result.is_error, Some(true));
+        assert!(!is_dry_run(&result));
+    }
+}
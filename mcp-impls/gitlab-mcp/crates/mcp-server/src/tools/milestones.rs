@@ -0,0 +1,405 @@
+use mcp_core::types::CallToolResult;
+use crate::error::GitLabError;
+use crate::gitlab::GitLabClient;
+use super::{dry_run_result, fetch_list, object_arguments, required_str, to_tool_error, to_tool_result};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::sync::Arc;
+use mcp_server::ServerError;
+
+/// Milestone creation request
+#[derive(Serialize)]
+struct CreateMilestoneRequest {
+    title: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    start_date: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    due_date: Option<String>,
+}
+
+/// Milestone update request
+#[derive(Serialize, Default)]
+struct UpdateMilestoneRequest {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    title: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    start_date: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    due_date: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    state_event: Option<String>,
+}
+
+/// Milestone response from GitLab API
+#[derive(Deserialize, Serialize)]
+struct GitLabMilestone {
+    id: u64,
+    iid: u64,
+    title: String,
+    #[serde(default)]
+    description: Option<String>,
+    state: String,
+    #[serde(default)]
+    start_date: Option<String>,
+    #[serde(default)]
+    due_date: Option<String>,
+    #[serde(default)]
+    expired: Option<bool>,
+    #[serde(default)]
+    web_url: Option<String>,
+}
+
+/// Issue as returned by `/projects/:id/milestones/:milestone_id/issues`
+#[derive(Deserialize)]
+struct MilestoneIssue {
+    iid: u64,
+    title: String,
+    state: String,
+    #[serde(default)]
+    assignees: Vec<Assignee>,
+}
+
+#[derive(Deserialize)]
+struct Assignee {
+    username: String,
+}
+
+/// Compact issue summary returned by `list_milestone_issues`
+#[derive(Serialize, Debug, PartialEq)]
+struct MilestoneIssueSummary {
+    iid: u64,
+    title: String,
+    assignee: Option<String>,
+}
+
+/// List milestones of a project
+pub async fn list_milestones(
+    client: Arc<GitLabClient>,
+    arguments: Option<Value>,
+) -> Result<CallToolResult, ServerError> {
+    let args = object_arguments(arguments)?;
+    let project_id = required_str(&args, "project_id")?;
+
+    let mut query = Vec::new();
+    for key in ["state", "search"] {
+        if let Some(value) = args.get(key).and_then(|v| v.as_str()) {
+            query.push((key.to_string(), value.to_string()));
+        }
+    }
+
+    let path = format!("projects/{}/milestones", urlencoding::encode(project_id));
+
+    match fetch_list::<GitLabMilestone>(&client, &path, query, &args).await {
+        Ok(milestones) if milestones.is_empty() => {
+            Ok(to_tool_result("No milestones found for this project".to_string()))
+        }
+        Ok(milestones) => {
+            let json = serde_json::to_string_pretty(&milestones).unwrap_or_else(|_| "[]".to_string());
+            Ok(to_tool_result(json))
+        }
+        Err(e) => Ok(to_tool_error(format!("Failed to list milestones: {}", e))),
+    }
+}
+
+/// Get a single milestone
+pub async fn get_milestone(
+    client: Arc<GitLabClient>,
+    arguments: Option<Value>,
+) -> Result<CallToolResult, ServerError> {
+    let args = object_arguments(arguments)?;
+    let project_id = required_str(&args, "project_id")?;
+    let milestone_id = milestone_id(&args)?;
+
+    match client.get::<GitLabMilestone>(&milestone_path(project_id, milestone_id)).await {
+        Ok(milestone) => Ok(to_tool_result(format_milestone("Milestone", &milestone))),
+        Err(e) => Ok(milestone_error("get", milestone_id, e)),
+    }
+}
+
+/// Create a project milestone
+pub async fn create_milestone(
+    client: Arc<GitLabClient>,
+    arguments: Option<Value>,
+) -> Result<CallToolResult, ServerError> {
+    let args = object_arguments(arguments)?;
+    let project_id = required_str(&args, "project_id")?;
+
+    let request = CreateMilestoneRequest {
+        title: required_str(&args, "title")?.to_string(),
+        description: args.get("description").and_then(|v| v.as_str()).map(|s| s.to_string()),
+        start_date: match date_argument(&args, "start_date") {
+            Ok(date) => date,
+            Err(message) => return Ok(to_tool_error(message)),
+        },
+        due_date: match date_argument(&args, "due_date") {
+            Ok(date) => date,
+            Err(message) => return Ok(to_tool_error(message)),
+        },
+    };
+    let path = format!("projects/{}/milestones", urlencoding::encode(project_id));
+
+    if client.is_dry_run() {
+        return Ok(dry_run_result("POST", &path, serde_json::to_value(&request).ok()));
+    }
+
+    match client.post::<GitLabMilestone, _>(&path, &request).await {
+        Ok(milestone) => Ok(to_tool_result(format_milestone("Milestone Created Successfully", &milestone))),
+        Err(e) => Ok(to_tool_error(format!("Failed to create milestone: {}", e))),
+    }
+}
+
+/// Update a project milestone
+pub async fn update_milestone(
+    client: Arc<GitLabClient>,
+    arguments: Option<Value>,
+) -> Result<CallToolResult, ServerError> {
+    let args = object_arguments(arguments)?;
+    let project_id = required_str(&args, "project_id")?;
+    let milestone_id = milestone_id(&args)?;
+
+    let request = UpdateMilestoneRequest {
+        title: args.get("title").and_then(|v| v.as_str()).map(|s| s.to_string()),
+        description: args.get("description").and_then(|v| v.as_str()).map(|s| s.to_string()),
+        start_date: match date_argument(&args, "start_date") {
+            Ok(date) => date,
+            Err(message) => return Ok(to_tool_error(message)),
+        },
+        due_date: match date_argument(&args, "due_date") {
+            Ok(date) => date,
+            Err(message) => return Ok(to_tool_error(message)),
+        },
+        state_event: args.get("state_event").and_then(|v| v.as_str()).map(|s| s.to_string()),
+    };
+
+    if request.title.is_none()
+        && request.description.is_none()
+        && request.start_date.is_none()
+        && request.due_date.is_none()
+        && request.state_event.is_none()
+    {
+        return Ok(to_tool_error(
+            "one of title, description, start_date, due_date or state_event is required",
+        ));
+    }
+
+    put_milestone(client, project_id, milestone_id, &request, "Milestone Updated Successfully").await
+}
+
+/// Close a project milestone
+pub async fn close_milestone(
+    client: Arc<GitLabClient>,
+    arguments: Option<Value>,
+) -> Result<CallToolResult, ServerError> {
+    let args = object_arguments(arguments)?;
+    let project_id = required_str(&args, "project_id")?;
+    let milestone_id = milestone_id(&args)?;
+
+    let request = UpdateMilestoneRequest {
+        state_event: Some("close".to_string()),
+        ..Default::default()
+    };
+    put_milestone(client, project_id, milestone_id, &request, "Milestone Closed").await
+}
+
+/// Delete a project milestone
+pub async fn delete_milestone(
+    client: Arc<GitLabClient>,
+    arguments: Option<Value>,
+) -> Result<CallToolResult, ServerError> {
+    let args = object_arguments(arguments)?;
+    let project_id = required_str(&args, "project_id")?;
+    let milestone_id = milestone_id(&args)?;
+    let path = milestone_path(project_id, milestone_id);
+
+    if client.is_dry_run() {
+        return Ok(dry_run_result("DELETE", &path, None));
+    }
+
+    match client.delete(&path).await {
+        Ok(()) => Ok(to_tool_result(format!("Milestone {} deleted", milestone_id))),
+        Err(e) => Ok(milestone_error("delete", milestone_id, e)),
+    }
+}
+
+/// List the issues assigned to a milestone
+pub async fn list_milestone_issues(
+    client: Arc<GitLabClient>,
+    arguments: Option<Value>,
+) -> Result<CallToolResult, ServerError> {
+    let args = object_arguments(arguments)?;
+    let project_id = required_str(&args, "project_id")?;
+    let milestone_id = milestone_id(&args)?;
+    let state = args.get("state").and_then(|v| v.as_str()).filter(|state| *state != "all");
+
+    let path = format!("{}/issues", milestone_path(project_id, milestone_id));
+
+    match fetch_list::<MilestoneIssue>(&client, &path, Vec::new(), &args).await {
+        Ok(issues) => {
+            let summaries = summarize_issues(issues, state);
+            if summaries.is_empty() {
+                return Ok(to_tool_result("No issues found for this milestone".to_string()));
+            }
+            let json = serde_json::to_string_pretty(&summaries).unwrap_or_else(|_| "[]".to_string());
+            Ok(to_tool_result(json))
+        }
+        Err(e) => Ok(milestone_error("list issues of", milestone_id, e)),
+    }
+}
+
+async fn put_milestone(
+    client: Arc<GitLabClient>,
+    project_id: &str,
+    milestone_id: u64,
+    request: &UpdateMilestoneRequest,
+    heading: &str,
+) -> Result<CallToolResult, ServerError> {
+    let path = milestone_path(project_id, milestone_id);
+
+    if client.is_dry_run() {
+        return Ok(dry_run_result("PUT", &path, serde_json::to_value(request).ok()));
+    }
+
+    match client.put::<GitLabMilestone, _>(&path, request).await {
+        Ok(milestone) => Ok(to_tool_result(format_milestone(heading, &milestone))),
+        Err(e) => Ok(milestone_error("update", milestone_id, e)),
+    }
+}
+
+/// The milestone endpoint answers 404 when the project has no such milestone
+fn milestone_error(action: &str, milestone_id: u64, error: GitLabError) -> CallToolResult {
+    match error {
+        GitLabError::NotFound(_) => {
+            to_tool_error(format!("Milestone {} not found in this project", milestone_id))
+        }
+        e => to_tool_error(format!("Failed to {} milestone: {}", action, e)),
+    }
+}
+
+/// Keep only issues in `state` and reduce them to IID, title and first assignee
+fn summarize_issues(issues: Vec<MilestoneIssue>, state: Option<&str>) -> Vec<MilestoneIssueSummary> {
+    issues
+        .into_iter()
+        .filter(|issue| state.is_none_or(|state| issue.state == state))
+        .map(|issue| MilestoneIssueSummary {
+            iid: issue.iid,
+            title: issue.title,
+            assignee: issue.assignees.into_iter().next().map(|a| a.username),
+        })
+        .collect()
+}
+
+/// Render a milestone as markdown
+fn format_milestone(heading: &str, milestone: &GitLabMilestone) -> String {
+    let mut output = vec![
+        format!("## {}\n", heading),
+        format!("**Title:** {}", milestone.title),
+        format!("**ID:** {} (IID: {})", milestone.id, milestone.iid),
+        format!("**State:** {}", milestone.state),
+    ];
+    if let Some(start) = &milestone.start_date {
+        output.push(format!("**Start Date:** {}", start));
+    }
+    if let Some(due) = &milestone.due_date {
+        output.push(format!("**Due Date:** {}", due));
+    }
+    if milestone.expired == Some(true) {
+        output.push("**Expired:** yes".to_string());
+    }
+    if let Some(desc) = &milestone.description {
+        output.push(format!("**Description:** {}", desc));
+    }
+    if let Some(url) = &milestone.web_url {
+        output.push(format!("**URL:** {}", url));
+    }
+    output.join("\n")
+}
+
+fn milestone_path(project_id: &str, milestone_id: u64) -> String {
+    format!("projects/{}/milestones/{}", urlencoding::encode(project_id), milestone_id)
+}
+
+fn milestone_id(args: &Map<String, Value>) -> Result<u64, ServerError> {
+    args.get("milestone_id")
+        .and_then(|v| v.as_u64())
+        .ok_or_else(|| ServerError::Handler("milestone_id is required".to_string()))
+}
+
+/// Read an optional ISO-8601 date argument, keeping only the `YYYY-MM-DD` part
+fn date_argument(args: &Map<String, Value>, key: &str) -> Result<Option<String>, String> {
+    match args.get(key).and_then(|v| v.as_str()) {
+        None => Ok(None),
+        Some(value) => normalize_date(value)
+            .map(Some)
+            .ok_or_else(|| format!("{} must be an ISO-8601 date such as 2026-01-31", key)),
+    }
+}
+
+/// Accept `YYYY-MM-DD` or a full ISO-8601 timestamp and return the date part
+fn normalize_date(value: &str) -> Option<String> {
+    let date = value.get(..10)?;
+    let rest = &value[10..];
+    if !rest.is_empty() && !rest.starts_with('T') {
+        return None;
+    }
+    let bytes = date.as_bytes();
+    let digits_ok = bytes
+        .iter()
+        .enumerate()
+        .all(|(i, b)| if i == 4 || i == 7 { *b == b'-' } else { b.is_ascii_digit() });
+    if !digits_ok {
+        return None;
+    }
+    let month: u32 = date[5..7].parse().ok()?;
+    let day: u32 = date[8..10].parse().ok()?;
+    if !(1..=12).contains(&month) || !(1..=31).contains(&day) {
+        return None;
+    }
+    Some(date.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_normalize_date() {
+        assert_eq!(normalize_date("2026-01-31"), Some("2026-01-31".to_string()));
+        assert_eq!(normalize_date("2026-01-31T12:00:00Z"), Some("2026-01-31".to_string()));
+        assert_eq!(normalize_date("2026-13-01"), None);
+        assert_eq!(normalize_date("31/01/2026"), None);
+        assert_eq!(normalize_date("2026-01-31 12:00"), None);
+    }
+
+    #[test]
+    fn test_summarize_issues_filters_by_state() {
+        let issues: Vec<MilestoneIssue> = serde_json::from_value(json!([
+            { "iid": 1, "title": "Open one", "state": "opened", "assignees": [{ "username": "alice" }] },
+            { "iid": 2, "title": "Done", "state": "closed", "assignees": [] }
+        ]))
+        .unwrap();
+
+        let opened = summarize_issues(issues, Some("opened"));
+        assert_eq!(
+            opened,
+            vec![MilestoneIssueSummary {
+                iid: 1,
+                title: "Open one".to_string(),
+                assignee: Some("alice".to_string()),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_close_request_uses_state_event() {
+        let request = UpdateMilestoneRequest {
+            state_event: Some("close".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(serde_json::to_value(&request).unwrap(), json!({ "state_event": "close" }));
+    }
+}
@@ -1,8 +1,24 @@
 //! Tool implementations
 
-use mcp_core::types::{CallToolResult, ContentBlock, TextContent};
+use futures::{StreamExt, TryStreamExt};
+use mcp_core::types::{CallToolResult, ContentBlock, RequestMeta, TextContent};
+use mcp_server::ServerError;
+use serde::de::DeserializeOwned;
+use serde_json::{json, Map, Value};
+use std::collections::HashMap;
 
+use crate::error::GitLabError;
+use crate::gitlab::GitLabClient;
+
+pub mod boards;
+pub mod labels;
+pub mod milestones;
 pub mod project;
+pub mod registry;
+pub mod vulnerabilities;
+
+/// Page size used while following `X-Next-Page`
+const PAGINATE_PAGE_SIZE: u64 = 100;
 
 /// Convert a result to MCP tool result
 pub fn to_tool_result(content: String) -> CallToolResult {
@@ -20,3 +36,102 @@ pub fn to_tool_error(error: impl std::fmt::Display) -> CallToolResult {
         ..Default::default()
     }
 }
+
+/// Describe the write request a tool would have sent in dry-run mode.
+///
+/// `params` is the request body, or the query for `DELETE` endpoints that
+/// take one. The result is flagged with `dry_run: true` in its `_meta`.
+pub(crate) fn dry_run_result(method: &str, path: &str, params: Option<Value>) -> CallToolResult {
+    let mut output = format!(
+        "## Dry Run\n\nWould send `{} {}`; no changes were made.",
+        method, path
+    );
+    if let Some(params) = &params {
+        let json = serde_json::to_string_pretty(params).unwrap_or_else(|_| "{}".to_string());
+        output.push_str(&format!("\n\n```json\n{}\n```", json));
+    }
+
+    let mut result = to_tool_result(output);
+    result.structured_content = Some(json!({
+        "method": method,
+        "path": path,
+        "params": params,
+    }));
+    result.meta = Some(RequestMeta {
+        extra: HashMap::from([("dry_run".to_string(), Value::Bool(true))]),
+        ..Default::default()
+    });
+    result
+}
+
+/// Extract tool arguments as a JSON object
+pub(crate) fn object_arguments(arguments: Option<Value>) -> Result<Map<String, Value>, ServerError> {
+    arguments
+        .and_then(|v| v.as_object().cloned())
+        .ok_or_else(|| ServerError::Handler("Expected object arguments".to_string()))
+}
+
+/// Read a required string argument
+pub(crate) fn required_str<'a>(args: &'a Map<String, Value>, key: &str) -> Result<&'a str, ServerError> {
+    args.get(key)
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| ServerError::Handler(format!("{} is required", key)))
+}
+
+/// Add the `paginate` and `max_results` arguments to a list tool's input schema
+pub(crate) fn with_pagination(mut schema: Value) -> Value {
+    if let Some(properties) = schema.get_mut("properties").and_then(|p| p.as_object_mut()) {
+        properties.insert(
+            "paginate".to_string(),
+            json!({
+                "type": "boolean",
+                "description": "Follow X-Next-Page and return every page instead of a single one (default: false)"
+            }),
+        );
+        properties.insert(
+            "max_results".to_string(),
+            json!({
+                "type": "integer",
+                "description": "Stop after this many results"
+            }),
+        );
+    }
+    schema
+}
+
+/// Fetch the items of a list endpoint for a list tool.
+///
+/// Without `paginate` a single page is fetched, chosen by the `per_page`
+/// and `page` arguments. With `paginate: true` pages are followed until
+/// `max_results` items are collected or the last page is reached.
+pub(crate) async fn fetch_list<T: DeserializeOwned>(
+    client: &GitLabClient,
+    path: &str,
+    mut query: Vec<(String, String)>,
+    args: &Map<String, Value>,
+) -> Result<Vec<T>, GitLabError> {
+    let max_results = args
+        .get("max_results")
+        .and_then(|v| v.as_u64())
+        .map(|max| usize::try_from(max).unwrap_or(usize::MAX));
+
+    if args.get("paginate").and_then(|v| v.as_bool()).unwrap_or(false) {
+        // Small limits need no more than one page of that size
+        let per_page = max_results.map_or(PAGINATE_PAGE_SIZE, |max| (max as u64).clamp(1, PAGINATE_PAGE_SIZE));
+        return client
+            .paginate::<T>(path, &query, per_page as u8)
+            .take(max_results.unwrap_or(usize::MAX))
+            .try_collect()
+            .await;
+    }
+
+    let per_page = args.get("per_page").and_then(|v| v.as_u64()).unwrap_or(20);
+    let page = args.get("page").and_then(|v| v.as_u64()).unwrap_or(1);
+    query.push(("per_page".to_string(), per_page.to_string()));
+    query.push(("page".to_string(), page.to_string()));
+    let mut items: Vec<T> = client.get_with_query(path, &query).await?;
+    if let Some(max) = max_results {
+        items.truncate(max);
+    }
+    Ok(items)
+}
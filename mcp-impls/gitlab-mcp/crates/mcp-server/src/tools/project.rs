@@ -1,5 +1,6 @@
 use mcp_core::types::{CallToolResult, ContentBlock, TextContent};
 use crate::gitlab::GitLabClient;
+use super::dry_run_result;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::sync::Arc;
@@ -93,6 +94,10 @@ pub async fn create_project(
         default_branch,
     };
 
+    if client.is_dry_run() {
+        return Ok(dry_run_result("POST", "projects", serde_json::to_value(&request).ok()));
+    }
+
     match client.post::<GitLabProject, _>("projects", &request).await {
         Ok(project) => {
             let mut output = vec![
@@ -0,0 +1,422 @@
+use mcp_core::types::CallToolResult;
+use crate::error::GitLabError;
+use crate::gitlab::GitLabClient;
+use super::{dry_run_result, fetch_list, object_arguments, required_str, to_tool_error, to_tool_result};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+use std::sync::Arc;
+use mcp_server::ServerError;
+
+/// Page size used when scanning all repositories of a project
+const SCAN_PAGE_SIZE: u64 = 100;
+
+/// Container registry repository response from GitLab API
+#[derive(Deserialize, Serialize)]
+struct RegistryRepository {
+    id: u64,
+    name: String,
+    path: String,
+    #[serde(default)]
+    project_id: Option<u64>,
+    #[serde(default)]
+    location: Option<String>,
+    #[serde(default)]
+    created_at: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    cleanup_policy_started_at: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    tags_count: Option<u64>,
+}
+
+/// Tag as returned by the tag list endpoint
+#[derive(Deserialize, Serialize)]
+struct RegistryTag {
+    name: String,
+    path: String,
+    location: String,
+}
+
+/// Tag details as returned by the single tag endpoint
+#[derive(Deserialize)]
+struct RegistryTagDetails {
+    name: String,
+    path: String,
+    location: String,
+    #[serde(default)]
+    revision: Option<String>,
+    #[serde(default)]
+    digest: Option<String>,
+    #[serde(default)]
+    created_at: Option<String>,
+    #[serde(default)]
+    total_size: Option<u64>,
+}
+
+/// List container registry repositories of a project
+pub async fn list_registry_repositories(
+    client: Arc<GitLabClient>,
+    arguments: Option<Value>,
+) -> Result<CallToolResult, ServerError> {
+    let args = object_arguments(arguments)?;
+    let project_id = required_str(&args, "project_id")?;
+
+    let query = vec![("tags_count".to_string(), "true".to_string())];
+
+    match fetch_list::<RegistryRepository>(&client, &repositories_path(project_id), query, &args).await {
+        Ok(repositories) if repositories.is_empty() => Ok(to_tool_result(
+            "No container registry repositories found for this project".to_string(),
+        )),
+        Ok(repositories) => {
+            let json = serde_json::to_string_pretty(&repositories).unwrap_or_else(|_| "[]".to_string());
+            Ok(to_tool_result(json))
+        }
+        Err(e) => Ok(to_tool_error(format!("Failed to list registry repositories: {}", e))),
+    }
+}
+
+/// Get a container registry repository of a project
+///
+/// GitLab only offers a project-scoped list endpoint, so the repository is
+/// looked up page by page.
+pub async fn get_registry_repository(
+    client: Arc<GitLabClient>,
+    arguments: Option<Value>,
+) -> Result<CallToolResult, ServerError> {
+    let args = object_arguments(arguments)?;
+    let project_id = required_str(&args, "project_id")?;
+    let repository_id = repository_id(&args)?;
+
+    let path = repositories_path(project_id);
+    let mut page = 1;
+    loop {
+        let query = vec![
+            ("per_page".to_string(), SCAN_PAGE_SIZE.to_string()),
+            ("page".to_string(), page.to_string()),
+            ("tags_count".to_string(), "true".to_string()),
+        ];
+        let repositories = match client.get_with_query::<Vec<RegistryRepository>>(&path, &query).await {
+            Ok(repositories) => repositories,
+            Err(e) => return Ok(to_tool_error(format!("Failed to get registry repository: {}", e))),
+        };
+        let last_page = (repositories.len() as u64) < SCAN_PAGE_SIZE;
+        if let Some(repository) = repositories.into_iter().find(|r| r.id == repository_id) {
+            let json = serde_json::to_string_pretty(&repository).unwrap_or_else(|_| "{}".to_string());
+            return Ok(to_tool_result(json));
+        }
+        if last_page {
+            return Ok(to_tool_error(format!(
+                "Registry repository {} not found in this project",
+                repository_id
+            )));
+        }
+        page += 1;
+    }
+}
+
+/// Delete a container registry repository and all of its tags
+pub async fn delete_registry_repository(
+    client: Arc<GitLabClient>,
+    arguments: Option<Value>,
+) -> Result<CallToolResult, ServerError> {
+    let args = object_arguments(arguments)?;
+    let project_id = required_str(&args, "project_id")?;
+    let repository_id = repository_id(&args)?;
+    let path = repository_path(project_id, repository_id);
+
+    if client.is_dry_run() {
+        return Ok(dry_run_result("DELETE", &path, None));
+    }
+
+    match client.delete(&path).await {
+        Ok(()) => Ok(to_tool_result(format!(
+            "Registry repository {} scheduled for deletion",
+            repository_id
+        ))),
+        Err(e) => Ok(registry_error("delete", &format!("Registry repository {}", repository_id), e)),
+    }
+}
+
+/// List the tags of a container registry repository
+pub async fn list_registry_tags(
+    client: Arc<GitLabClient>,
+    arguments: Option<Value>,
+) -> Result<CallToolResult, ServerError> {
+    let args = object_arguments(arguments)?;
+    let project_id = required_str(&args, "project_id")?;
+    let repository_id = repository_id(&args)?;
+    let name_regex = match optional_regex(&args, "name_regex_delete") {
+        Ok(regex) => regex,
+        Err(message) => return Ok(to_tool_error(message)),
+    };
+
+    let path = format!("{}/tags", repository_path(project_id, repository_id));
+
+    match fetch_list::<RegistryTag>(&client, &path, Vec::new(), &args).await {
+        Ok(tags) => {
+            let tags = filter_tags(tags, name_regex.as_ref());
+            if tags.is_empty() {
+                return Ok(to_tool_result("No tags found for this repository".to_string()));
+            }
+            let json = serde_json::to_string_pretty(&tags).unwrap_or_else(|_| "[]".to_string());
+            Ok(to_tool_result(json))
+        }
+        Err(e) => Ok(registry_error("list tags of", &format!("Registry repository {}", repository_id), e)),
+    }
+}
+
+/// Get the details of a single registry tag
+pub async fn get_registry_tag(
+    client: Arc<GitLabClient>,
+    arguments: Option<Value>,
+) -> Result<CallToolResult, ServerError> {
+    let args = object_arguments(arguments)?;
+    let project_id = required_str(&args, "project_id")?;
+    let repository_id = repository_id(&args)?;
+    let tag_name = required_str(&args, "tag_name")?;
+
+    match client.get::<RegistryTagDetails>(&tag_path(project_id, repository_id, tag_name)).await {
+        Ok(tag) => {
+            let mut result = to_tool_result(format_tag(&tag));
+            result.structured_content = Some(json!({
+                "name": tag.name,
+                "path": tag.path,
+                "location": tag.location,
+                "digest": tag.digest,
+                "created_at": tag.created_at,
+                "total_size": tag.total_size,
+            }));
+            Ok(result)
+        }
+        Err(e) => Ok(registry_error("get", &format!("Tag `{}`", tag_name), e)),
+    }
+}
+
+/// Delete a single registry tag
+pub async fn delete_registry_tag(
+    client: Arc<GitLabClient>,
+    arguments: Option<Value>,
+) -> Result<CallToolResult, ServerError> {
+    let args = object_arguments(arguments)?;
+    let project_id = required_str(&args, "project_id")?;
+    let repository_id = repository_id(&args)?;
+    let tag_name = required_str(&args, "tag_name")?;
+    let path = tag_path(project_id, repository_id, tag_name);
+
+    if client.is_dry_run() {
+        return Ok(dry_run_result("DELETE", &path, None));
+    }
+
+    match client.delete(&path).await {
+        Ok(()) => Ok(to_tool_result(format!("Tag `{}` deleted", tag_name))),
+        Err(e) => Ok(registry_error("delete", &format!("Tag `{}`", tag_name), e)),
+    }
+}
+
+/// Delete registry tags in bulk according to a cleanup policy
+pub async fn bulk_delete_registry_tags(
+    client: Arc<GitLabClient>,
+    arguments: Option<Value>,
+) -> Result<CallToolResult, ServerError> {
+    let args = object_arguments(arguments)?;
+    let project_id = required_str(&args, "project_id")?;
+    let repository_id = repository_id(&args)?;
+    let name_regex_delete = required_str(&args, "name_regex_delete")?;
+
+    // Reject bad patterns before GitLab queues a cleanup job that would fail
+    for key in ["name_regex_delete", "name_regex_keep"] {
+        if let Err(message) = optional_regex(&args, key) {
+            return Ok(to_tool_error(message));
+        }
+    }
+
+    let mut query = vec![("name_regex_delete".to_string(), name_regex_delete.to_string())];
+    if let Some(keep_n) = args.get("keep_n").and_then(|v| v.as_u64()) {
+        query.push(("keep_n".to_string(), keep_n.to_string()));
+    }
+    for key in ["name_regex_keep", "older_than"] {
+        if let Some(value) = args.get(key).and_then(|v| v.as_str()) {
+            query.push((key.to_string(), value.to_string()));
+        }
+    }
+
+    let path = format!("{}/tags", repository_path(project_id, repository_id));
+    if client.is_dry_run() {
+        let params: Map<String, Value> = query
+            .iter()
+            .map(|(key, value)| (key.clone(), Value::String(value.clone())))
+            .collect();
+        return Ok(dry_run_result("DELETE", &path, Some(Value::Object(params))));
+    }
+    match client.delete_with_query(&path, &query).await {
+        Ok(()) => {
+            let policy: Vec<String> = query
+                .iter()
+                .map(|(key, value)| format!("- {}: `{}`", key, value))
+                .collect();
+            Ok(to_tool_result(format!(
+                "Bulk tag deletion scheduled for registry repository {}\n{}",
+                repository_id,
+                policy.join("\n")
+            )))
+        }
+        Err(e) => Ok(registry_error(
+            "bulk delete tags of",
+            &format!("Registry repository {}", repository_id),
+            e,
+        )),
+    }
+}
+
+/// Compile a tag name pattern with GitLab semantics: the whole name must match
+fn compile_tag_regex(pattern: &str) -> Result<Regex, regex::Error> {
+    Regex::new(&format!("^(?:{})$", pattern))
+}
+
+fn optional_regex(args: &Map<String, Value>, key: &str) -> Result<Option<Regex>, String> {
+    match args.get(key).and_then(|v| v.as_str()) {
+        Some(pattern) => compile_tag_regex(pattern)
+            .map(Some)
+            .map_err(|e| format!("Invalid {}: {}", key, e)),
+        None => Ok(None),
+    }
+}
+
+/// Keep only tags whose name fully matches `name_regex`
+fn filter_tags(tags: Vec<RegistryTag>, name_regex: Option<&Regex>) -> Vec<RegistryTag> {
+    tags.into_iter()
+        .filter(|tag| name_regex.is_none_or(|regex| regex.is_match(&tag.name)))
+        .collect()
+}
+
+/// Registry endpoints answer 404 for unknown repositories and tags
+fn registry_error(action: &str, subject: &str, error: GitLabError) -> CallToolResult {
+    match error {
+        GitLabError::NotFound(_) => to_tool_error(format!("{} not found in this project", subject)),
+        e => to_tool_error(format!("Failed to {} {}: {}", action, subject.to_lowercase(), e)),
+    }
+}
+
+/// Render tag details as markdown
+fn format_tag(tag: &RegistryTagDetails) -> String {
+    let mut output = vec![
+        "## Registry Tag\n".to_string(),
+        format!("**Name:** {}", tag.name),
+        format!("**Path:** {}", tag.path),
+        format!("**Location:** {}", tag.location),
+    ];
+    if let Some(digest) = &tag.digest {
+        output.push(format!("**Digest:** {}", digest));
+    }
+    if let Some(revision) = &tag.revision {
+        output.push(format!("**Revision:** {}", revision));
+    }
+    if let Some(created_at) = &tag.created_at {
+        output.push(format!("**Created:** {}", created_at));
+    }
+    if let Some(total_size) = tag.total_size {
+        output.push(format!("**Total Size:** {}", format_size(total_size)));
+    }
+    output.join("\n")
+}
+
+fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+    if bytes < 1024 {
+        return format!("{} B", bytes);
+    }
+    let mut size = bytes as f64;
+    let mut unit = "B";
+    for next in UNITS {
+        if size < 1024.0 {
+            break;
+        }
+        size /= 1024.0;
+        unit = next;
+    }
+    format!("{:.1} {} ({} bytes)", size, unit, bytes)
+}
+
+fn repositories_path(project_id: &str) -> String {
+    format!("projects/{}/registry/repositories", urlencoding::encode(project_id))
+}
+
+fn repository_path(project_id: &str, repository_id: u64) -> String {
+    format!("{}/{}", repositories_path(project_id), repository_id)
+}
+
+fn tag_path(project_id: &str, repository_id: u64, tag_name: &str) -> String {
+    format!(
+        "{}/tags/{}",
+        repository_path(project_id, repository_id),
+        urlencoding::encode(tag_name)
+    )
+}
+
+fn repository_id(args: &Map<String, Value>) -> Result<u64, ServerError> {
+    args.get("repository_id")
+        .and_then(|v| v.as_u64())
+        .ok_or_else(|| ServerError::Handler("repository_id is required".to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tags(names: &[&str]) -> Vec<RegistryTag> {
+        names
+            .iter()
+            .map(|name| RegistryTag {
+                name: name.to_string(),
+                path: format!("group/app:{}", name),
+                location: format!("registry.example.com/group/app:{}", name),
+            })
+            .collect()
+    }
+
+    fn names(tags: &[RegistryTag]) -> Vec<&str> {
+        tags.iter().map(|tag| tag.name.as_str()).collect()
+    }
+
+    #[test]
+    fn test_tag_regex_matches_whole_name() {
+        let regex = compile_tag_regex("v1\\..*").unwrap();
+        let filtered = filter_tags(tags(&["v1.0", "v1.2.3", "v10", "prefix-v1.0", "latest"]), Some(&regex));
+        assert_eq!(names(&filtered), vec!["v1.0", "v1.2.3"]);
+    }
+
+    #[test]
+    fn test_tag_regex_alternation_is_anchored() {
+        let regex = compile_tag_regex("dev|feature-.*").unwrap();
+        let filtered = filter_tags(tags(&["dev", "devel", "feature-x", "main"]), Some(&regex));
+        assert_eq!(names(&filtered), vec!["dev", "feature-x"]);
+    }
+
+    #[test]
+    fn test_no_regex_keeps_all_tags() {
+        let filtered = filter_tags(tags(&["a", "b"]), None);
+        assert_eq!(names(&filtered), vec!["a", "b"]);
+    }
+
+    #[test]
+    fn test_invalid_regex_is_reported() {
+        let args = json!({ "name_regex_delete": "v1(" });
+        let error = optional_regex(args.as_object().unwrap(), "name_regex_delete").unwrap_err();
+        assert!(error.starts_with("Invalid name_regex_delete"));
+    }
+
+    #[test]
+    fn test_tag_path_encodes_project_and_tag() {
+        assert_eq!(
+            tag_path("group/app", 7, "v1.0"),
+            "projects/group%2Fapp/registry/repositories/7/tags/v1.0"
+        );
+    }
+
+    #[test]
+    fn test_format_size() {
+        assert_eq!(format_size(512), "512 B");
+        assert_eq!(format_size(1536), "1.5 KiB (1536 bytes)");
+        assert_eq!(format_size(3 * 1024 * 1024), "3.0 MiB (3145728 bytes)");
+    }
+}
@@ -0,0 +1,226 @@
+use mcp_core::types::CallToolResult;
+use crate::gitlab::GitLabClient;
+use super::{object_arguments, required_str, to_tool_error, to_tool_result};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+use std::sync::Arc;
+use mcp_server::ServerError;
+
+/// Largest page GitLab's GraphQL API returns
+const MAX_PAGE_SIZE: u64 = 100;
+
+const SEVERITIES: &[&str] = &["CRITICAL", "HIGH", "MEDIUM", "LOW", "INFO", "UNKNOWN"];
+const STATES: &[&str] = &["DETECTED", "CONFIRMED", "RESOLVED", "DISMISSED"];
+
+/// Vulnerability findings of a project.
+///
+/// Locations, identifiers and scanners are only exposed in full through
+/// GraphQL; the REST vulnerability endpoints return a reduced view.
+const VULNERABILITIES_QUERY: &str = r#"
+query ($fullPath: ID!, $first: Int!, $after: String, $severity: [VulnerabilitySeverity!], $state: [VulnerabilityState!]) {
+  project(fullPath: $fullPath) {
+    vulnerabilities(first: $first, after: $after, severity: $severity, state: $state) {
+      pageInfo { hasNextPage endCursor }
+      nodes {
+        id
+        title
+        description
+        severity
+        state
+        reportType
+        detectedAt
+        webUrl
+        scanner { name vendor }
+        identifiers { externalType externalId name url }
+        location {
+          ... on VulnerabilityLocationSast { file startLine endLine }
+          ... on VulnerabilityLocationSecretDetection { file startLine endLine }
+          ... on VulnerabilityLocationDependencyScanning { file dependency { package { name } version } }
+          ... on VulnerabilityLocationContainerScanning { image operatingSystem dependency { package { name } version } }
+          ... on VulnerabilityLocationDast { hostname path requestMethod param }
+        }
+      }
+    }
+  }
+}
+"#;
+
+#[derive(Deserialize)]
+struct VulnerabilitiesData {
+    project: Option<ProjectVulnerabilities>,
+}
+
+#[derive(Deserialize)]
+struct ProjectVulnerabilities {
+    vulnerabilities: VulnerabilityConnection,
+}
+
+#[derive(Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct VulnerabilityConnection {
+    page_info: PageInfo,
+    nodes: Vec<Vulnerability>,
+}
+
+#[derive(Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct PageInfo {
+    has_next_page: bool,
+    end_cursor: Option<String>,
+}
+
+/// Vulnerability finding as returned by GraphQL
+#[derive(Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Vulnerability {
+    id: String,
+    title: Option<String>,
+    #[serde(default)]
+    description: Option<String>,
+    severity: Option<String>,
+    state: Option<String>,
+    #[serde(default)]
+    report_type: Option<String>,
+    #[serde(default)]
+    detected_at: Option<String>,
+    #[serde(default)]
+    web_url: Option<String>,
+    #[serde(default)]
+    scanner: Option<Value>,
+    #[serde(default)]
+    identifiers: Vec<Value>,
+    #[serde(default)]
+    location: Option<Value>,
+}
+
+/// Project lookup used to turn a numeric ID into a full path
+#[derive(Deserialize)]
+struct ProjectPath {
+    path_with_namespace: String,
+}
+
+/// List the vulnerability findings of a project through GraphQL
+pub async fn get_project_vulnerabilities(
+    client: Arc<GitLabClient>,
+    arguments: Option<Value>,
+) -> Result<CallToolResult, ServerError> {
+    let args = object_arguments(arguments)?;
+    let project_id = required_str(&args, "project_id")?;
+    let severity = match enum_list(&args, "severity", SEVERITIES) {
+        Ok(values) => values,
+        Err(message) => return Ok(to_tool_error(message)),
+    };
+    let state = match enum_list(&args, "state", STATES) {
+        Ok(values) => values,
+        Err(message) => return Ok(to_tool_error(message)),
+    };
+    let first = args.get("first").and_then(|v| v.as_u64()).unwrap_or(20).clamp(1, MAX_PAGE_SIZE);
+    let after = args.get("after").and_then(|v| v.as_str());
+
+    // GraphQL addresses projects by full path only
+    let full_path = if project_id.chars().all(|c| c.is_ascii_digit()) {
+        match client.get::<ProjectPath>(&format!("projects/{}", project_id)).await {
+            Ok(project) => project.path_with_namespace,
+            Err(e) => return Ok(to_tool_error(format!("Failed to resolve project {}: {}", project_id, e))),
+        }
+    } else {
+        project_id.to_string()
+    };
+
+    let variables = json!({
+        "fullPath": full_path,
+        "first": first,
+        "after": after,
+        "severity": severity,
+        "state": state,
+    });
+    let data = match client.graphql_typed::<VulnerabilitiesData>(VULNERABILITIES_QUERY, variables).await {
+        Ok(data) => data,
+        Err(e) => return Ok(to_tool_error(format!("Failed to get vulnerabilities: {}", e))),
+    };
+    let Some(project) = data.project else {
+        return Ok(to_tool_error(format!("Project {} not found or not accessible", full_path)));
+    };
+
+    let connection = project.vulnerabilities;
+    if connection.nodes.is_empty() {
+        return Ok(to_tool_result("No vulnerabilities found for this project".to_string()));
+    }
+    let json = serde_json::to_string_pretty(&connection).unwrap_or_else(|_| "{}".to_string());
+    Ok(to_tool_result(json))
+}
+
+/// Read an optional list of GraphQL enum values, normalized to upper case
+fn enum_list(
+    args: &Map<String, Value>,
+    key: &str,
+    allowed: &[&str],
+) -> Result<Option<Vec<String>>, String> {
+    let Some(value) = args.get(key) else {
+        return Ok(None);
+    };
+    let items: Vec<&Value> = match value {
+        Value::Array(items) => items.iter().collect(),
+        other => vec![other],
+    };
+    items
+        .into_iter()
+        .map(|item| {
+            let value = item.as_str().map(|s| s.to_ascii_uppercase()).unwrap_or_default();
+            if allowed.contains(&value.as_str()) {
+                Ok(value)
+            } else {
+                Err(format!("{} must be one of: {}", key, allowed.join(", ")))
+            }
+        })
+        .collect::<Result<Vec<_>, _>>()
+        .map(Some)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_enum_list() {
+        let args = json!({ "severity": ["critical", "HIGH"], "state": "detected", "bad": ["urgent"] });
+        let args = args.as_object().unwrap();
+
+        assert_eq!(
+            enum_list(args, "severity", SEVERITIES).unwrap(),
+            Some(vec!["CRITICAL".to_string(), "HIGH".to_string()])
+        );
+        assert_eq!(enum_list(args, "state", STATES).unwrap(), Some(vec!["DETECTED".to_string()]));
+        assert_eq!(enum_list(args, "missing", STATES).unwrap(), None);
+        assert!(enum_list(args, "bad", SEVERITIES).unwrap_err().starts_with("bad must be one of"));
+    }
+
+    #[test]
+    fn test_vulnerabilities_response() {
+        let data = json!({
+            "project": {
+                "vulnerabilities": {
+                    "pageInfo": { "hasNextPage": true, "endCursor": "abc" },
+                    "nodes": [{
+                        "id": "gid://gitlab/Vulnerability/1",
+                        "title": "SQL injection",
+                        "severity": "HIGH",
+                        "state": "DETECTED",
+                        "reportType": "SAST",
+                        "scanner": { "name": "semgrep", "vendor": "GitLab" },
+                        "identifiers": [{ "externalType": "cwe", "externalId": "89", "name": "CWE-89", "url": null }],
+                        "location": { "file": "app/db.rb", "startLine": 12, "endLine": 12 }
+                    }]
+                }
+            }
+        });
+        let data: VulnerabilitiesData = serde_json::from_value(data).unwrap();
+        let connection = data.project.unwrap().vulnerabilities;
+        assert_eq!(connection.page_info.end_cursor.as_deref(), Some("abc"));
+
+        let output = serde_json::to_value(&connection).unwrap();
+        assert_eq!(output["nodes"][0]["location"]["file"], "app/db.rb");
+        assert_eq!(output["nodes"][0]["reportType"], "SAST");
+        assert_eq!(output["pageInfo"]["hasNextPage"], true);
+    }
+}